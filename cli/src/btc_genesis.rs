@@ -0,0 +1,190 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::{fs, io::Write, path::PathBuf};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use chainx_runtime::BtcNetwork;
+use xpallet_gateway_bitcoin::is_valid_proof_of_work;
+
+use crate::genesis::bitcoin::{btc_genesis_params, BtcGenesisParams};
+
+/// The maximum bits of the Bitcoin mainnet and testnet.
+pub(crate) const MAINNET_MAX_BITS: u32 = 486604799;
+/// The maximum bits of signet and regtest, same as the one in the testnet chain specs.
+const SIGNET_MAX_BITS: u32 = 545259519;
+
+/// The `btc-genesis-params` command used to generate the Bitcoin genesis params of a chain spec.
+///
+/// The header at `--height` is fetched from a bitcoind node, its hash and proof of work are
+/// verified before the params are written in the format of `res/btc_genesis_params_*.json`.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct BtcGenesisParamsCmd {
+    /// The height of the Bitcoin block used as the genesis of the relay.
+    #[clap(long, value_name = "HEIGHT")]
+    pub height: u32,
+
+    /// The JSON-RPC endpoint of the bitcoind node.
+    #[clap(long, value_name = "URL", default_value = "http://127.0.0.1:8332")]
+    pub rpc: String,
+
+    /// The username of the bitcoind JSON-RPC.
+    #[clap(long, value_name = "USER")]
+    pub rpc_user: Option<String>,
+
+    /// The password of the bitcoind JSON-RPC.
+    #[clap(long, value_name = "PASSWORD")]
+    pub rpc_password: Option<String>,
+
+    /// The confirmation number of the relay, 6 for mainnet and 4 for the others if unspecified.
+    ///
+    /// The genesis block must have been confirmed by at least this many blocks.
+    #[clap(long, value_name = "COUNT")]
+    pub confirmation_number: Option<u32>,
+
+    /// Output file name or stdout if unspecified.
+    #[clap(short, long, parse(from_os_str))]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockchainInfo {
+    chain: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockHeader {
+    hash: String,
+    confirmations: i64,
+    height: u32,
+    version: i32,
+    merkleroot: String,
+    time: u32,
+    nonce: u32,
+    bits: String,
+    previousblockhash: Option<String>,
+}
+
+impl BtcGenesisParamsCmd {
+    /// Run the btc-genesis-params command.
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let info: BlockchainInfo = self.request("getblockchaininfo", json!([]))?;
+        let (network, max_bits) = match info.chain.as_str() {
+            "main" => (BtcNetwork::Mainnet, MAINNET_MAX_BITS),
+            "test" => (BtcNetwork::Testnet, MAINNET_MAX_BITS),
+            "signet" | "regtest" => (BtcNetwork::Testnet, SIGNET_MAX_BITS),
+            chain => return Err(format!("Unknown bitcoin chain `{}`", chain).into()),
+        };
+        let confirmation_number = self.confirmation_number.unwrap_or(match network {
+            BtcNetwork::Mainnet => 6,
+            _ => 4,
+        });
+
+        let hash: String = self.request("getblockhash", json!([self.height]))?;
+        let header: BlockHeader = self.request("getblockheader", json!([hash, true]))?;
+
+        if header.hash != hash || header.height != self.height {
+            return Err(format!(
+                "The node returned the header {}#{} instead of {}#{}",
+                header.hash, header.height, hash, self.height
+            )
+            .into());
+        }
+        if header.confirmations < i64::from(confirmation_number) {
+            return Err(format!(
+                "The block #{} has only {} confirmations, at least {} are required",
+                self.height, header.confirmations, confirmation_number
+            )
+            .into());
+        }
+
+        let params = BtcGenesisParams {
+            network,
+            confirmation_number,
+            height: header.height,
+            hash: header.hash,
+            version: header.version as u32,
+            previous_header_hash: header
+                .previousblockhash
+                .ok_or("The genesis block of bitcoin can not be used")?,
+            merkle_root_hash: header.merkleroot,
+            time: header.time,
+            bits: u32::from_str_radix(&header.bits, 16)
+                .map_err(|e| format!("Invalid bits `{}`: {}", header.bits, e))?,
+            nonce: header.nonce,
+        };
+
+        let block_header = params.header();
+        if block_header.hash() != params.hash() {
+            return Err(format!(
+                "The hash of the header does not match {}, the node returned malformed fields",
+                params.hash
+            )
+            .into());
+        }
+        if !is_valid_proof_of_work(max_bits.into(), block_header.bits, block_header.hash()) {
+            return Err(format!(
+                "The header {} does not meet the proof of work of bits {:#x}",
+                params.hash, params.bits
+            )
+            .into());
+        }
+
+        let mut output = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+        params
+            .serialize(&mut serializer)
+            .map_err(|e| format!("Failed to serialize the params: {}", e))?;
+        output.push(b'\n');
+
+        // Make sure the output can be loaded by the chain specs.
+        btc_genesis_params(std::str::from_utf8(&output).expect("JSON is valid utf8; qed"));
+
+        if let Some(path) = &self.output {
+            fs::write(path, output)?;
+        } else {
+            std::io::stdout().write_all(&output)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends a JSON-RPC request to the bitcoind node and returns the result.
+    fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> sc_cli::Result<T> {
+        let mut request = ureq::post(&self.rpc);
+        if let Some(user) = &self.rpc_user {
+            let password = self.rpc_password.as_deref().unwrap_or_default();
+            let credentials = base64::encode(format!("{}:{}", user, password));
+            request = request.set("Authorization", &format!("Basic {}", credentials));
+        }
+
+        let body = json!({
+            "jsonrpc": "1.0",
+            "id": "chainx",
+            "method": method,
+            "params": params,
+        });
+        // bitcoind responds to a failed call with an HTTP error status and a JSON body.
+        let response = match request.send_json(body) {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(format!("Failed to request `{}`: {}", method, e).into()),
+        };
+        let response: RpcResponse<T> = response
+            .into_json()
+            .map_err(|e| format!("Invalid response of `{}`: {}", method, e))?;
+
+        match (response.result, response.error) {
+            (Some(result), None) => Ok(result),
+            (_, Some(error)) => Err(format!("`{}` failed: {}", method, error).into()),
+            (None, None) => Err(format!("`{}` returned no result", method).into()),
+        }
+    }
+}
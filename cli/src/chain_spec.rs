@@ -3,6 +3,7 @@
 #![allow(unused)]
 use std::collections::BTreeMap;
 use std::convert::TryInto;
+use std::path::Path;
 
 use hex_literal::hex;
 use serde::{Deserialize, Serialize};
@@ -30,6 +31,9 @@ use xpallet_gateway_common::types::TrusteeInfoConfig;
 
 use crate::genesis::assets::{genesis_assets, init_assets, pcx, AssetParams};
 use crate::genesis::bitcoin::{btc_genesis_params, BtcGenesisParams, BtcTrusteeParams};
+use crate::genesis::endowment::{Endowed, EndowmentBuilder};
+use crate::genesis::evm::{evm_genesis_accounts, EvmGenesisAccounts};
+use crate::genesis::GenesisBuilderParams;
 
 use chainx_runtime as chainx;
 use dev_runtime as dev;
@@ -102,42 +106,10 @@ pub fn authority_keys_from_seed(seed: &str) -> AuthorityKeysTuple {
     )
 }
 
-#[inline]
-fn balance(input: Balance, decimals: u8) -> Balance {
-    input * 10_u128.pow(decimals as u32)
-}
-
-/// A small macro for generating the info of PCX endowed accounts.
-macro_rules! endowed_gen {
-    ( $( ($seed:expr, $value:expr), )+ ) => {
-        {
-            let mut endowed = BTreeMap::new();
-            let pcx_id = pcx().0;
-            let endowed_info = vec![
-                $((get_account_id_from_seed::<sr25519::Public>($seed), balance($value, PCX_DECIMALS)),)+
-            ];
-            endowed.insert(pcx_id, endowed_info);
-            endowed
-        }
-    }
-}
-
-macro_rules! endowed {
-    ( $( ($pubkey:expr, $value:expr), )+ ) => {
-        {
-            let mut endowed = BTreeMap::new();
-            let pcx_id = pcx().0;
-            let endowed_info = vec![
-                $((($pubkey).into(), balance($value, PCX_DECIMALS)),)+
-            ];
-            endowed.insert(pcx_id, endowed_info);
-            endowed
-        }
-    }
-}
-
 const ENDOWMENT: Balance = 10_000_000 * DOLLARS;
 const STASH: Balance = 100 * DOLLARS;
+/// X-BTC endowed to the dev accounts at genesis, in BTC.
+const XBTC_ENDOWMENT: Balance = 100;
 
 /// Helper function to generate the network properties.
 fn as_properties(network: NetworkType) -> Properties {
@@ -152,23 +124,33 @@ fn as_properties(network: NetworkType) -> Properties {
     .to_owned()
 }
 
-pub fn development_config() -> Result<DevChainSpec, String> {
+pub fn development_config(genesis_state: Option<&Path>) -> Result<DevChainSpec, String> {
     let wasm_binary =
         dev::WASM_BINARY.ok_or_else(|| "Development wasm binary not available".to_string())?;
 
-    let endowed_balance = 50 * DOLLARS;
+    let endowed_balance = 50;
+    let endowed = EndowmentBuilder::new()
+        .pcx("Alice", endowed_balance)
+        .pcx("Bob", endowed_balance)
+        .pcx("Alice//stash", endowed_balance)
+        .pcx("Bob//stash", endowed_balance)
+        .asset(X_BTC, "Alice", XBTC_ENDOWMENT)
+        .asset(X_BTC, "Bob", XBTC_ENDOWMENT)
+        .build(&genesis_assets())?;
+    let params = crate::genesis::genesis_builder_params(genesis_state)?;
+    let evm_accounts = evm_genesis_accounts(
+        include_str!("res/evm_genesis_contracts.json"),
+        &dev::ChainXPrecompiles::<dev::Runtime>::used_addresses(),
+    )?;
     let constructor = move || {
         build_dev_genesis(
             wasm_binary,
             vec![authority_keys_from_seed("Alice")],
             get_account_id_from_seed::<sr25519::Public>("Alice"),
             genesis_assets(),
-            endowed_gen![
-                ("Alice", endowed_balance),
-                ("Bob", endowed_balance),
-                ("Alice//stash", endowed_balance),
-                ("Bob//stash", endowed_balance),
-            ],
+            params.clone(),
+            endowed.clone(),
+            evm_accounts.clone(),
             btc_genesis_params(include_str!("res/btc_genesis_params_testnet.json")),
             crate::genesis::bitcoin::local_testnet_trustees(),
         )
@@ -188,23 +170,31 @@ pub fn development_config() -> Result<DevChainSpec, String> {
 }
 
 #[cfg(feature = "runtime-benchmarks")]
-pub fn benchmarks_config() -> Result<DevChainSpec, String> {
+pub fn benchmarks_config(genesis_state: Option<&Path>) -> Result<DevChainSpec, String> {
     let wasm_binary =
         dev::WASM_BINARY.ok_or_else(|| "Development wasm binary not available".to_string())?;
 
-    let endowed_balance = 50 * DOLLARS;
+    let endowed_balance = 50;
+    let endowed = EndowmentBuilder::new()
+        .pcx("Alice", endowed_balance)
+        .pcx("Bob", endowed_balance)
+        .pcx("Alice//stash", endowed_balance)
+        .pcx("Bob//stash", endowed_balance)
+        .build(&genesis_assets())?;
+    let params = crate::genesis::genesis_builder_params(genesis_state)?;
+    let evm_accounts = evm_genesis_accounts(
+        include_str!("res/evm_genesis_contracts.json"),
+        &dev::ChainXPrecompiles::<dev::Runtime>::used_addresses(),
+    )?;
     let constructor = move || {
         build_dev_genesis(
             wasm_binary,
             vec![authority_keys_from_seed("Alice")],
             get_account_id_from_seed::<sr25519::Public>("Alice"),
             genesis_assets(),
-            endowed_gen![
-                ("Alice", endowed_balance),
-                ("Bob", endowed_balance),
-                ("Alice//stash", endowed_balance),
-                ("Bob//stash", endowed_balance),
-            ],
+            params.clone(),
+            endowed.clone(),
+            evm_accounts.clone(),
             btc_genesis_params(include_str!("res/btc_genesis_params_benchmarks.json")),
             crate::genesis::bitcoin::benchmarks_trustees(),
         )
@@ -223,34 +213,56 @@ pub fn benchmarks_config() -> Result<DevChainSpec, String> {
     ))
 }
 
-pub fn local_testnet_config() -> Result<DevChainSpec, String> {
+pub fn local_testnet_config(genesis_state: Option<&Path>) -> Result<DevChainSpec, String> {
+    local_testnet_config_with_validators(&["Alice", "Bob"], genesis_state)
+}
+
+/// The seeds of the well-known dev accounts endowed in the local testnet.
+pub const LOCAL_TESTNET_SEEDS: [&str; 6] = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+/// Local testnet config with the given dev accounts as the genesis validators.
+pub fn local_testnet_config_with_validators(
+    validators: &[&str],
+    genesis_state: Option<&Path>,
+) -> Result<DevChainSpec, String> {
     let wasm_binary =
         dev::WASM_BINARY.ok_or_else(|| "Development wasm binary not available".to_string())?;
 
-    let endowed_balance = 50 * DOLLARS;
+    let endowed_balance = 50;
+    let endowed = EndowmentBuilder::new()
+        .pcx("Alice", endowed_balance)
+        .pcx("Bob", endowed_balance)
+        .pcx("Charlie", endowed_balance)
+        .pcx("Dave", endowed_balance)
+        .pcx("Eve", endowed_balance)
+        .pcx("Ferdie", endowed_balance)
+        .pcx("Alice//stash", endowed_balance)
+        .pcx("Bob//stash", endowed_balance)
+        .pcx("Charlie//stash", endowed_balance)
+        .pcx("Dave//stash", endowed_balance)
+        .pcx("Eve//stash", endowed_balance)
+        .pcx("Ferdie//stash", endowed_balance)
+        .asset(X_BTC, "Alice", XBTC_ENDOWMENT)
+        .asset(X_BTC, "Bob", XBTC_ENDOWMENT)
+        .build(&genesis_assets())?;
+    let params = crate::genesis::genesis_builder_params(genesis_state)?;
+    let evm_accounts = evm_genesis_accounts(
+        include_str!("res/evm_genesis_contracts.json"),
+        &dev::ChainXPrecompiles::<dev::Runtime>::used_addresses(),
+    )?;
+    let initial_authorities = validators
+        .iter()
+        .map(|seed| authority_keys_from_seed(seed))
+        .collect::<Vec<_>>();
     let constructor = move || {
         build_dev_genesis(
             wasm_binary,
-            vec![
-                authority_keys_from_seed("Alice"),
-                authority_keys_from_seed("Bob"),
-            ],
+            initial_authorities.clone(),
             get_account_id_from_seed::<sr25519::Public>("Alice"),
             genesis_assets(),
-            endowed_gen![
-                ("Alice", endowed_balance),
-                ("Bob", endowed_balance),
-                ("Charlie", endowed_balance),
-                ("Dave", endowed_balance),
-                ("Eve", endowed_balance),
-                ("Ferdie", endowed_balance),
-                ("Alice//stash", endowed_balance),
-                ("Bob//stash", endowed_balance),
-                ("Charlie//stash", endowed_balance),
-                ("Dave//stash", endowed_balance),
-                ("Eve//stash", endowed_balance),
-                ("Ferdie//stash", endowed_balance),
-            ],
+            params.clone(),
+            endowed.clone(),
+            evm_accounts.clone(),
             btc_genesis_params(include_str!("res/btc_genesis_params_testnet.json")),
             crate::genesis::bitcoin::local_testnet_trustees(),
         )
@@ -273,7 +285,7 @@ pub fn mainnet_config() -> Result<ChainXChainSpec, String> {
     ChainXChainSpec::from_json_bytes(&include_bytes!("./res/chainx_regenesis.json")[..])
 }
 
-pub fn new_mainnet_config() -> Result<ChainXChainSpec, String> {
+pub fn new_mainnet_config(genesis_state: Option<&Path>) -> Result<ChainXChainSpec, String> {
     let wasm_binary =
         chainx::WASM_BINARY.ok_or_else(|| "ChainX wasm binary not available".to_string())?;
 
@@ -374,11 +386,18 @@ pub fn new_mainnet_config() -> Result<ChainXChainSpec, String> {
                 .unchecked_into(),
         ),
     ];
+    let params = crate::genesis::genesis_builder_params(genesis_state)?;
+    let evm_accounts = evm_genesis_accounts(
+        include_str!("res/evm_genesis_contracts.json"),
+        &chainx::ChainXPrecompiles::<chainx::Runtime>::used_addresses(),
+    )?;
     let constructor = move || {
         mainnet_genesis(
             wasm_binary,
             initial_authorities.clone(),
             genesis_assets(),
+            params.clone(),
+            evm_accounts.clone(),
             btc_genesis_params(include_str!("res/btc_genesis_params_mainnet.json")),
             crate::genesis::bitcoin::mainnet_trustees(),
         )
@@ -424,6 +443,8 @@ fn mainnet_genesis(
     wasm_binary: &[u8],
     initial_authorities: Vec<AuthorityKeysTuple>,
     assets: Vec<AssetParams>,
+    params: GenesisBuilderParams,
+    evm_accounts: EvmGenesisAccounts,
     bitcoin: BtcGenesisParams,
     trustees: Vec<(Chain, TrusteeInfoConfig, Vec<BtcTrusteeParams>)>,
 ) -> chainx::GenesisConfig {
@@ -455,6 +476,9 @@ fn mainnet_genesis(
         })
         .expect("bitcoin trustees generation can not fail; qed");
 
+    // The technical committee members provide the identity judgements at first.
+    let registrars = tech_comm_members.clone();
+
     chainx::GenesisConfig {
         system: chainx::SystemConfig {
             code: wasm_binary.to_vec(),
@@ -498,6 +522,7 @@ fn mainnet_genesis(
         indices: chainx::IndicesConfig { indices: vec![] },
         x_system: chainx::XSystemConfig {
             network_props: NetworkType::Mainnet,
+            enactment_delay: 2 * DAYS,
         },
         x_assets_registrar: chainx::XAssetsRegistrarConfig { assets },
         x_assets: chainx::XAssetsConfig {
@@ -528,7 +553,7 @@ fn mainnet_genesis(
             sessions_per_era: 1,
             glob_dist_ratio: (12, 88), // (Treasury, X-type Asset and Staking) = (12, 88)
             mining_ratio: (10, 90),    // (Asset Mining, Staking) = (10, 90)
-            minimum_penalty: 100 * DOLLARS,
+            minimum_penalty: 100 * DOLLARS, // Minimum slash of the offenders, the equivocations are slashed after `SlashDeferDuration` sessions unless canceled by the council
             candidate_requirement: (100 * DOLLARS, 1_000 * DOLLARS), // Minimum value (self_bonded, total_bonded) to be a validator candidate
             ..Default::default()
         },
@@ -540,14 +565,17 @@ fn mainnet_genesis(
             trading_pairs: vec![(PCX, X_BTC, 9, 2, 100000, true)],
         },
         x_genesis_builder: chainx::XGenesisBuilderConfig {
-            params: crate::genesis::genesis_builder_params(),
+            params,
             initial_authorities: initial_authorities
                 .iter()
                 .map(|i| (i.0).1.clone())
                 .collect(),
+            registrars,
         },
         ethereum_chain_id: chainx::EthereumChainIdConfig { chain_id: 1501u64 },
-        evm: Default::default(),
+        evm: chainx::EvmConfig {
+            accounts: evm_accounts,
+        },
         ethereum: Default::default(),
         base_fee: chainx::BaseFeeConfig::new(
             chainx::DefaultBaseFeePerGas::get(),
@@ -556,6 +584,7 @@ fn mainnet_genesis(
         ),
         x_assets_bridge: chainx::XAssetsBridgeConfig { admin_key: None },
         x_btc_ledger: Default::default(),
+        vesting: Default::default(),
     }
 }
 
@@ -563,7 +592,7 @@ pub fn malan_config() -> Result<MalanChainSpec, String> {
     MalanChainSpec::from_json_bytes(&include_bytes!("./res/malan.json")[..])
 }
 
-pub fn new_malan_config() -> Result<MalanChainSpec, String> {
+pub fn new_malan_config(genesis_state: Option<&Path>) -> Result<MalanChainSpec, String> {
     let wasm_binary =
         malan::WASM_BINARY.ok_or_else(|| "ChainX wasm binary not available".to_string())?;
 
@@ -626,11 +655,18 @@ pub fn new_malan_config() -> Result<MalanChainSpec, String> {
                 .unchecked_into(),
         ),
     ];
+    let params = crate::genesis::genesis_builder_params(genesis_state)?;
+    let evm_accounts = evm_genesis_accounts(
+        include_str!("res/evm_genesis_contracts.json"),
+        &malan::ChainXPrecompiles::<malan::Runtime>::used_addresses(),
+    )?;
     let constructor = move || {
         malan_genesis(
             wasm_binary,
             initial_authorities.clone(),
             genesis_assets(),
+            params.clone(),
+            evm_accounts.clone(),
             btc_genesis_params(include_str!("res/btc_genesis_params_testnet.json")),
             crate::genesis::bitcoin::mainnet_trustees(),
         )
@@ -673,6 +709,8 @@ fn malan_genesis(
     wasm_binary: &[u8],
     initial_authorities: Vec<AuthorityKeysTuple>,
     assets: Vec<AssetParams>,
+    params: GenesisBuilderParams,
+    evm_accounts: EvmGenesisAccounts,
     bitcoin: BtcGenesisParams,
     trustees: Vec<(Chain, TrusteeInfoConfig, Vec<BtcTrusteeParams>)>,
 ) -> malan::GenesisConfig {
@@ -704,6 +742,9 @@ fn malan_genesis(
         })
         .expect("bitcoin trustees generation can not fail; qed");
 
+    // The technical committee members provide the identity judgements at first.
+    let registrars = tech_comm_members.clone();
+
     malan::GenesisConfig {
         sudo: malan::SudoConfig {
             key: Some(
@@ -752,6 +793,7 @@ fn malan_genesis(
         indices: malan::IndicesConfig { indices: vec![] },
         x_system: malan::XSystemConfig {
             network_props: NetworkType::Testnet,
+            enactment_delay: DAYS,
         },
         x_assets_registrar: malan::XAssetsRegistrarConfig { assets },
         x_assets: malan::XAssetsConfig {
@@ -782,7 +824,7 @@ fn malan_genesis(
             sessions_per_era: 12,
             glob_dist_ratio: (12, 88), // (Treasury, X-type Asset and Staking) = (12, 88)
             mining_ratio: (10, 90),    // (Asset Mining, Staking) = (10, 90)
-            minimum_penalty: 100 * DOLLARS,
+            minimum_penalty: 100 * DOLLARS, // Minimum slash of the offenders, the equivocations are slashed after `SlashDeferDuration` sessions unless canceled by the council
             candidate_requirement: (100 * DOLLARS, 1_000 * DOLLARS), // Minimum value (self_bonded, total_bonded) to be a validator candidate
             minimum_validator_count: 2,
             ..Default::default()
@@ -795,14 +837,17 @@ fn malan_genesis(
             trading_pairs: vec![(PCX, X_BTC, 9, 2, 100000, true)],
         },
         x_genesis_builder: malan::XGenesisBuilderConfig {
-            params: crate::genesis::genesis_builder_params(),
+            params,
             initial_authorities: initial_authorities
                 .iter()
                 .map(|i| (i.0).1.clone())
                 .collect(),
+            registrars,
         },
         ethereum_chain_id: malan::EthereumChainIdConfig { chain_id: 1502u64 },
-        evm: Default::default(),
+        evm: malan::EvmConfig {
+            accounts: evm_accounts,
+        },
         ethereum: Default::default(),
         base_fee: malan::BaseFeeConfig::new(
             malan::DefaultBaseFeePerGas::get(),
@@ -811,6 +856,7 @@ fn malan_genesis(
         ),
         x_assets_bridge: malan::XAssetsBridgeConfig { admin_key: None },
         x_btc_ledger: Default::default(),
+        vesting: Default::default(),
     }
 }
 
@@ -819,7 +865,9 @@ fn build_dev_genesis(
     initial_authorities: Vec<AuthorityKeysTuple>,
     root_key: AccountId,
     assets: Vec<AssetParams>,
-    endowed: BTreeMap<AssetId, Vec<(AccountId, Balance)>>,
+    params: GenesisBuilderParams,
+    endowed: Endowed,
+    evm_accounts: EvmGenesisAccounts,
     bitcoin: BtcGenesisParams,
     trustees: Vec<(Chain, TrusteeInfoConfig, Vec<BtcTrusteeParams>)>,
 ) -> dev::GenesisConfig {
@@ -883,6 +931,9 @@ fn build_dev_genesis(
             }
         })
         .expect("bitcoin trustees generation can not fail; qed");
+    // The technical committee members provide the identity judgements at first.
+    let registrars = tech_comm_members.clone();
+
     dev::GenesisConfig {
         sudo: dev::SudoConfig {
             key: Some(root_key),
@@ -931,6 +982,8 @@ fn build_dev_genesis(
         indices: dev::IndicesConfig { indices: vec![] },
         x_system: dev::XSystemConfig {
             network_props: NetworkType::Testnet,
+            // A short delay to try out the scheduled enactments.
+            enactment_delay: 10,
         },
         x_assets_registrar: dev::XAssetsRegistrarConfig { assets },
         x_assets: dev::XAssetsConfig {
@@ -961,7 +1014,7 @@ fn build_dev_genesis(
             sessions_per_era: 12,
             glob_dist_ratio: (12, 88), // (Treasury, X-type Asset and Staking) = (12, 88)
             mining_ratio: (10, 90),    // (Asset Mining, Staking) = (10, 90)
-            minimum_penalty: 100 * DOLLARS,
+            minimum_penalty: 100 * DOLLARS, // Minimum slash of the offenders, the equivocations are slashed after `SlashDeferDuration` sessions unless canceled by the council
             candidate_requirement: (100 * DOLLARS, 1_000 * DOLLARS), // Minimum value (self_bonded, total_bonded) to be a validator candidate
             ..Default::default()
         },
@@ -973,14 +1026,17 @@ fn build_dev_genesis(
             trading_pairs: vec![(PCX, X_BTC, 9, 2, 100000, true)],
         },
         x_genesis_builder: dev::XGenesisBuilderConfig {
-            params: crate::genesis::genesis_builder_params(),
+            params,
             initial_authorities: initial_authorities
                 .iter()
                 .map(|i| (i.0).1.clone())
                 .collect(),
+            registrars,
         },
         ethereum_chain_id: dev::EthereumChainIdConfig { chain_id: 1503u64 },
-        evm: Default::default(),
+        evm: dev::EvmConfig {
+            accounts: evm_accounts,
+        },
         ethereum: Default::default(),
         base_fee: dev::BaseFeeConfig::new(
             dev::DefaultBaseFeePerGas::get(),
@@ -989,5 +1045,6 @@ fn build_dev_genesis(
         ),
         x_assets_bridge: dev::XAssetsBridgeConfig { admin_key: None },
         x_btc_ledger: Default::default(),
+        vesting: Default::default(),
     }
 }
@@ -0,0 +1,440 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::{fs, path::PathBuf};
+
+use codec::Encode;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use chainx_primitives::{AccountId, AssetId, Balance};
+use chainx_runtime::{
+    trustees::bitcoin::BtcTrusteeType, BtcHeader, BtcNetwork, BtcParams, Chain, TrusteeInfoConfig,
+    H256,
+};
+use xp_protocol::NetworkType;
+use xpallet_gateway_bitcoin::is_valid_proof_of_work;
+
+use crate::btc_genesis::MAINNET_MAX_BITS;
+
+/// The `check-spec` command used to validate a chain spec before launching a network.
+///
+/// Only the plain chain specs can be checked, i.e., the ones exported by `build-spec` without
+/// `--raw`, since the genesis of a raw chain spec is no more than the storage.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct CheckSpecCmd {
+    /// The chain spec file to check.
+    #[clap(value_name = "FILE", parse(from_os_str))]
+    pub spec: PathBuf,
+
+    /// Print the result in JSON, e.g. for the CI pipelines.
+    #[clap(long)]
+    pub json: bool,
+}
+
+/// An inconsistency found in the chain spec.
+#[derive(Debug, Serialize)]
+pub struct SpecError {
+    /// The name of the failed check.
+    pub check: &'static str,
+    /// The details of the failure.
+    pub message: String,
+}
+
+impl SpecError {
+    fn new(check: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            message: message.into(),
+        }
+    }
+}
+
+/// The part of the genesis config shared by the runtimes which is checked.
+struct GenesisSummary {
+    network: NetworkType,
+    authorities: Vec<(AccountId, Vec<Vec<u8>>)>,
+    balances: Vec<(AccountId, Balance)>,
+    assets_endowed: BTreeMap<AssetId, Vec<(AccountId, Balance)>>,
+    trustees: Vec<(Chain, TrusteeInfoConfig, Vec<(AccountId, Vec<u8>, Vec<u8>)>)>,
+    btc_genesis_trustees: Vec<AccountId>,
+    btc_network: BtcNetwork,
+    btc_genesis_hash: H256,
+    btc_genesis_info: (BtcHeader, u32),
+    btc_params: BtcParams,
+    btc_confirmation_number: u32,
+}
+
+macro_rules! genesis_summary {
+    ($runtime:ident, $genesis:expr) => {{
+        let config: $runtime::GenesisConfig = serde_json::from_value($genesis)
+            .map_err(|e| SpecError::new("format", format!("Invalid genesis config: {}", e)))?;
+        GenesisSummary {
+            network: config.x_system.network_props,
+            authorities: config
+                .session
+                .keys
+                .into_iter()
+                .map(|(validator, _, keys)| {
+                    let keys = vec![
+                        keys.babe.encode(),
+                        keys.grandpa.encode(),
+                        keys.im_online.encode(),
+                        keys.authority_discovery.encode(),
+                    ];
+                    (validator, keys)
+                })
+                .collect(),
+            balances: config.balances.balances,
+            assets_endowed: config.x_assets.endowed,
+            trustees: config
+                .x_gateway_common
+                .trustees
+                .into_iter()
+                .map(|(chain, info, trustees)| {
+                    let trustees = trustees
+                        .into_iter()
+                        .map(|(who, _, hot, cold)| (who, hot, cold))
+                        .collect();
+                    (chain, info, trustees)
+                })
+                .collect(),
+            btc_genesis_trustees: config.x_gateway_bitcoin.genesis_trustees,
+            btc_network: config.x_gateway_bitcoin.network_id,
+            btc_genesis_hash: config.x_gateway_bitcoin.genesis_hash,
+            btc_genesis_info: config.x_gateway_bitcoin.genesis_info,
+            btc_params: config.x_gateway_bitcoin.params_info,
+            btc_confirmation_number: config.x_gateway_bitcoin.confirmation_number,
+        }
+    }};
+}
+
+impl CheckSpecCmd {
+    /// Run the check-spec command.
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let errors = match fs::read(&self.spec) {
+            Ok(content) => match serde_json::from_slice(&content) {
+                Ok(spec) => check_spec(spec),
+                Err(e) => vec![SpecError::new("format", format!("Invalid JSON: {}", e))],
+            },
+            Err(e) => vec![SpecError::new("format", format!("Failed to read: {}", e))],
+        };
+
+        if self.json {
+            let result = json!({
+                "spec": self.spec,
+                "valid": errors.is_empty(),
+                "errors": errors,
+            });
+            println!("{}", result);
+        } else if errors.is_empty() {
+            println!("{}: OK", self.spec.display());
+        } else {
+            for error in &errors {
+                eprintln!(
+                    "{}: [{}] {}",
+                    self.spec.display(),
+                    error.check,
+                    error.message
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("{} check(s) of the chain spec failed", errors.len()).into())
+        }
+    }
+}
+
+/// Returns all the inconsistencies found in the given plain chain spec.
+pub fn check_spec(mut spec: Value) -> Vec<SpecError> {
+    let summary = match genesis_of(&mut spec) {
+        Ok(summary) => summary,
+        Err(error) => return vec![error],
+    };
+
+    let mut errors = Vec::new();
+    check_properties(&spec, &summary, &mut errors);
+    check_authorities(&summary, &mut errors);
+    check_endowments(&summary, &mut errors);
+    check_trustees(&summary, &mut errors);
+    check_btc_genesis(&summary, &mut errors);
+    errors
+}
+
+fn genesis_of(spec: &mut Value) -> Result<GenesisSummary, SpecError> {
+    if spec.pointer("/genesis/raw").is_some() {
+        return Err(SpecError::new(
+            "format",
+            "The raw chain spec can not be checked, export the plain one by `build-spec`",
+        ));
+    }
+    let id = spec["id"]
+        .as_str()
+        .ok_or_else(|| SpecError::new("format", "Missing the chain id"))?
+        .to_string();
+    let genesis = spec
+        .pointer_mut("/genesis/runtime")
+        .map(Value::take)
+        .ok_or_else(|| SpecError::new("format", "Missing the runtime genesis config"))?;
+
+    // Same as the `IdentifyVariant` of the service.
+    Ok(if id == "chainx" {
+        genesis_summary!(chainx_runtime, genesis)
+    } else if id.contains("malan") {
+        genesis_summary!(malan_runtime, genesis)
+    } else if id == "dev" {
+        genesis_summary!(dev_runtime, genesis)
+    } else {
+        return Err(SpecError::new(
+            "format",
+            format!("Unknown chain id `{}`", id),
+        ));
+    })
+}
+
+fn check_properties(spec: &Value, summary: &GenesisSummary, errors: &mut Vec<SpecError>) {
+    let expected = summary.network.ss58_addr_format_id();
+    match spec
+        .pointer("/properties/ss58Format")
+        .and_then(Value::as_u64)
+    {
+        Some(ss58) if ss58 == u64::from(expected) => {}
+        Some(ss58) => errors.push(SpecError::new(
+            "properties",
+            format!(
+                "ss58Format {} does not match the {:?} network, expected {}",
+                ss58, summary.network, expected
+            ),
+        )),
+        None => errors.push(SpecError::new("properties", "Missing ss58Format")),
+    }
+}
+
+fn check_authorities(summary: &GenesisSummary, errors: &mut Vec<SpecError>) {
+    if summary.authorities.is_empty() {
+        errors.push(SpecError::new("authorities", "No genesis authority"));
+    }
+
+    let mut validators = BTreeSet::new();
+    let mut keys = BTreeSet::new();
+    for (validator, session_keys) in &summary.authorities {
+        if !validators.insert(validator) {
+            errors.push(SpecError::new(
+                "authorities",
+                format!("Duplicate authority {}", validator),
+            ));
+        }
+        for key in session_keys {
+            if !keys.insert(key) {
+                errors.push(SpecError::new(
+                    "authorities",
+                    format!(
+                        "Duplicate session key 0x{} of authority {}",
+                        hex::encode(key),
+                        validator
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn check_endowments(summary: &GenesisSummary, errors: &mut Vec<SpecError>) {
+    let mut check = |name: String, endowed: &[(AccountId, Balance)]| {
+        let mut accounts = BTreeSet::new();
+        let mut total: Balance = 0;
+        for (who, value) in endowed {
+            if !accounts.insert(who) {
+                errors.push(SpecError::new(
+                    "endowments",
+                    format!("{} is endowed more than once with {}", who, name),
+                ));
+            }
+            total = match total.checked_add(*value) {
+                Some(total) => total,
+                None => {
+                    errors.push(SpecError::new(
+                        "endowments",
+                        format!("The total endowment of {} overflows", name),
+                    ));
+                    return;
+                }
+            };
+        }
+    };
+
+    check("PCX".into(), &summary.balances);
+    for (asset_id, endowed) in &summary.assets_endowed {
+        check(format!("asset #{}", asset_id), endowed);
+    }
+}
+
+fn check_trustees(summary: &GenesisSummary, errors: &mut Vec<SpecError>) {
+    let mut chains = BTreeSet::new();
+    for (chain, info, trustees) in &summary.trustees {
+        if !chains.insert(chain) {
+            errors.push(SpecError::new(
+                "trustees",
+                format!("Duplicate trustees of {:?}", chain),
+            ));
+        }
+        let count = trustees.len() as u32;
+        if count < info.min_trustee_count || count > info.max_trustee_count {
+            errors.push(SpecError::new(
+                "trustees",
+                format!(
+                    "{} trustees of {:?} not in [{}, {}]",
+                    count, chain, info.min_trustee_count, info.max_trustee_count
+                ),
+            ));
+        }
+
+        let mut accounts = BTreeSet::new();
+        let mut keys = BTreeSet::new();
+        for (who, hot, cold) in trustees {
+            if !accounts.insert(who) {
+                errors.push(SpecError::new(
+                    "trustees",
+                    format!("Duplicate trustee {} of {:?}", who, chain),
+                ));
+            }
+            for (kind, key) in [("hot", hot), ("cold", cold)] {
+                if !keys.insert(key) {
+                    errors.push(SpecError::new(
+                        "trustees",
+                        format!(
+                            "Duplicate {} key 0x{} of trustee {}",
+                            kind,
+                            hex::encode(key),
+                            who
+                        ),
+                    ));
+                }
+                if *chain == Chain::Bitcoin && BtcTrusteeType::try_from(key.clone()).is_err() {
+                    errors.push(SpecError::new(
+                        "trustees",
+                        format!(
+                            "Invalid bitcoin {} key 0x{} of trustee {}",
+                            kind,
+                            hex::encode(key),
+                            who
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    let btc_trustees = summary
+        .trustees
+        .iter()
+        .filter(|(chain, _, _)| *chain == Chain::Bitcoin)
+        .flat_map(|(_, _, trustees)| trustees.iter().map(|(who, _, _)| who))
+        .collect::<BTreeSet<_>>();
+    for who in &summary.btc_genesis_trustees {
+        if !btc_trustees.contains(who) {
+            errors.push(SpecError::new(
+                "trustees",
+                format!(
+                    "The bitcoin genesis trustee {} is not a bitcoin trustee",
+                    who
+                ),
+            ));
+        }
+    }
+}
+
+fn check_btc_genesis(summary: &GenesisSummary, errors: &mut Vec<SpecError>) {
+    let (header, height) = &summary.btc_genesis_info;
+    let hash = header.hash();
+    if hash != summary.btc_genesis_hash {
+        errors.push(SpecError::new(
+            "btc-genesis",
+            format!(
+                "The genesis hash {:?} does not match the hash {:?} of the genesis header",
+                summary.btc_genesis_hash, hash
+            ),
+        ));
+    }
+    if *height == 0 {
+        errors.push(SpecError::new(
+            "btc-genesis",
+            "The genesis block of bitcoin can not be used",
+        ));
+    }
+    if summary.btc_confirmation_number == 0 {
+        errors.push(SpecError::new(
+            "btc-genesis",
+            "The confirmation number must not be zero",
+        ));
+    }
+
+    let max_bits = summary.btc_params.max_bits();
+    if matches!(summary.btc_network, BtcNetwork::Mainnet) && u32::from(max_bits) != MAINNET_MAX_BITS
+    {
+        errors.push(SpecError::new(
+            "btc-genesis",
+            format!(
+                "The max bits {:#x} are not the ones of the bitcoin mainnet",
+                u32::from(max_bits)
+            ),
+        ));
+    }
+    if !is_valid_proof_of_work(max_bits, header.bits, hash) {
+        errors.push(SpecError::new(
+            "btc-genesis",
+            format!(
+                "The genesis header #{} does not meet the proof of work of the {:?} network",
+                height, summary.btc_network
+            ),
+        ));
+    }
+}
+
+#[test]
+fn test_check_spec() {
+    use sc_service::ChainSpec;
+
+    let plain_spec = || {
+        let spec = crate::chain_spec::local_testnet_config(None).unwrap();
+        serde_json::from_str::<Value>(&spec.as_json(false).unwrap()).unwrap()
+    };
+    let checks = |spec: Value| {
+        check_spec(spec)
+            .into_iter()
+            .map(|e| e.check)
+            .collect::<Vec<_>>()
+    };
+
+    assert!(checks(plain_spec()).is_empty());
+
+    let mut spec = plain_spec();
+    spec["properties"]["ss58Format"] = json!(44);
+    assert_eq!(checks(spec), vec!["properties"]);
+
+    let mut spec = plain_spec();
+    let keys = spec.pointer_mut("/genesis/runtime/session/keys").unwrap();
+    let first = keys[0].clone();
+    keys.as_array_mut().unwrap().push(first);
+    assert!(checks(spec).contains(&"authorities"));
+
+    let mut spec = plain_spec();
+    let balances = spec
+        .pointer_mut("/genesis/runtime/balances/balances")
+        .unwrap();
+    let first = balances[0].clone();
+    balances.as_array_mut().unwrap().push(first);
+    assert_eq!(checks(spec), vec!["endowments"]);
+
+    let mut spec = plain_spec();
+    spec["genesis"]["runtime"]["xGatewayBitcoin"]["genesisInfo"][1] = json!(0);
+    assert_eq!(checks(spec), vec!["btc-genesis"]);
+
+    let spec = crate::chain_spec::mainnet_config().unwrap();
+    let spec = serde_json::from_str(&spec.as_json(true).unwrap()).unwrap();
+    assert_eq!(checks(spec), vec!["format"]);
+}
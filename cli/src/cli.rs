@@ -50,6 +50,37 @@ pub enum Subcommand {
     /// Export the state of a given block into a chain spec.
     ExportState(sc_cli::ExportStateCmd),
 
+    /// Report the storage size of each pallet at a given block.
+    StorageReport(crate::storage_report::StorageReportCmd),
+
+    /// Export a chain spec embedding the light sync state of the latest finalized block.
+    ExportSyncSpec(crate::sync_spec::ExportSyncSpecCmd),
+
+    /// Export the genesis head of the chain spec, e.g. for a parachain registration.
+    ExportGenesisState(crate::export_genesis::ExportGenesisStateCmd),
+
+    /// Export the genesis runtime wasm of the chain spec, e.g. for a parachain registration.
+    ExportGenesisWasm(crate::export_genesis::ExportGenesisWasmCmd),
+
+    /// Export the state of a live chain in the format of `--genesis-state` for a re-genesis.
+    ExportGenesisBuilderState(crate::genesis_builder_state::ExportGenesisBuilderStateCmd),
+
+    /// Compute the genesis state root and block hash of the chain spec without starting a node.
+    GenesisHash(crate::export_genesis::GenesisHashCmd),
+
+    /// Check the consistency of a plain chain spec, e.g. before launching a network.
+    CheckSpec(crate::check_spec::CheckSpecCmd),
+
+    /// Decode the genesis of the chain specs, e.g. `--spec a.json --spec b.json --diff` to
+    /// audit a re-genesis.
+    InspectGenesis(crate::inspect_genesis::InspectGenesisCmd),
+
+    /// Generate the bitcoin genesis params of a chain spec from a bitcoind node.
+    BtcGenesisParams(crate::btc_genesis::BtcGenesisParamsCmd),
+
+    /// Launch a local network of validator nodes, e.g. `dev-network --validators 4`.
+    DevNetwork(crate::dev_network::DevNetworkCmd),
+
     /// Import blocks.
     ImportBlocks(sc_cli::ImportBlocksCmd),
 
@@ -58,6 +89,10 @@ pub enum Subcommand {
 
     /// Revert the chain to a previous state.
     Revert(sc_cli::RevertCmd),
+
+    /// Revert all the unfinalized blocks, keeping the GRANDPA voter state, e.g. to recover
+    /// from a finality stall.
+    RevertFinalitySafe(crate::revert_finality::RevertFinalitySafeCmd),
 }
 
 #[allow(missing_docs)]
@@ -76,11 +111,230 @@ pub struct RunCmd {
     #[clap(long = "config", value_name = "PATH", parse(from_os_str))]
     pub config_file: Option<std::path::PathBuf>,
 
+    /// Apply the preset options for the role of this node.
+    ///
+    /// The preset covers the state pruning, offchain workers, RPC exposure, transaction pool
+    /// limits and telemetry. Any of these options passed from the command line or the config
+    /// file overrides the one of the preset.
+    #[clap(long = "profile", arg_enum, value_name = "PROFILE")]
+    pub profile: Option<NodeProfile>,
+
+    /// Specify the number of finalized blocks to keep in the database.
+    ///
+    /// Same as `--keep-blocks`, takes precedence over it when both are given.
+    #[clap(long = "blocks-pruning", value_name = "COUNT")]
+    pub blocks_pruning: Option<u32>,
+
+    /// Keep a copy of the relayed bitcoin headers in the aux database.
+    ///
+    /// This allows a node running with a pruned state (e.g. `--pruning 256`) to keep
+    /// serving `xgatewaybitcoin_getBtcBlockHeader` for the headers relayed in pruned blocks.
+    #[clap(long = "btc-header-archive")]
+    pub btc_header_archive: bool,
+
+    /// Select the network tuning profile.
+    ///
+    /// `high-latency` enlarges the yamux window of each substream and downloads blocks from
+    /// more peers in parallel, for the validators whose peers are across continents.
+    #[clap(
+        long = "network-profile",
+        arg_enum,
+        value_name = "PROFILE",
+        default_value = "default"
+    )]
+    pub network_profile: NetworkProfile,
+
+    /// Specify the yamux window size of each substream in bytes, overrides the one of
+    /// `--network-profile`.
+    #[clap(long = "yamux-window-size", value_name = "BYTES")]
+    pub yamux_window_size: Option<u32>,
+
+    /// Specify the number of 64KiB heap pages allocated by the wasm executor, overrides the
+    /// `:heappages` of the runtime state.
+    #[clap(long = "executor-heap-pages", value_name = "COUNT")]
+    pub executor_heap_pages: Option<u64>,
+
+    /// Specify the number of the compiled wasm runtimes kept in the cache of the executor.
+    ///
+    /// A larger cache avoids compiling the runtime again when the blocks of several runtime
+    /// versions are imported or queried, e.g. around a runtime upgrade.
+    #[clap(long = "executor-cache-size", value_name = "COUNT")]
+    pub executor_cache_size: Option<u8>,
+
+    /// Execute each imported best block once more to report the time of `on_initialize`,
+    /// the extrinsics and `on_finalize` via Prometheus.
+    ///
+    /// This doubles the execution cost of importing a block. The execution strategy of the
+    /// block import (`--execution-import-block`) is used, e.g. `wasm` to measure the wasm
+    /// executor only.
+    #[clap(long = "execution-metrics")]
+    pub execution_metrics: bool,
+
+    /// Specify the number of the latest blocks kept in the fee history cache.
+    ///
+    /// `eth_feeHistory` only serves the blocks within the cache, `eth_maxPriorityFeePerGas`
+    /// is derived from the rewards of the cached blocks as well.
+    #[clap(
+        long = "fee-history-limit",
+        value_name = "COUNT",
+        default_value = "2048"
+    )]
+    pub fee_history_limit: u64,
+
+    /// Enable the EVM tracing RPC, i.e. `debug_traceTransaction` and `trace_filter`.
+    ///
+    /// The runtime deployed on chain doesn't trace, the tracing runtime of the same spec
+    /// version, built by `cargo build -p <runtime> --features evm-tracing`, must be supplied
+    /// via `--wasm-runtime-overrides`.
+    #[clap(long = "evm-tracing")]
+    pub evm_tracing: bool,
+
+    /// Start the node even though it predates the runtime upgrade enacted on chain.
+    ///
+    /// The node refuses to start once the upgrade announced via
+    /// `XSystem::announce_runtime_upgrade` is enacted, if its version or native runtime is
+    /// older than the announced one.
+    #[clap(long = "allow-outdated-client")]
+    pub allow_outdated_client: bool,
+
+    /// Broadcast the fully signed bitcoin withdrawal txs to the given Esplora API.
+    ///
+    /// The signed tx at each finalized block is pushed via `POST <URL>/tx`, e.g.
+    /// `https://blockstream.info/api`, and reported back on chain via an unsigned extrinsic
+    /// once it's accepted, so that the trustees don't have to broadcast it manually.
+    #[clap(long = "btc-broadcast-url", value_name = "URL")]
+    pub btc_broadcast_url: Option<String>,
+
+    /// Specify the number of the blocks the finalized block may lag behind the best block
+    /// before the finality is considered stalled.
+    ///
+    /// A stall sets the `finality_stalled` Prometheus gauge to 1 and is logged as an error,
+    /// the node can then be recovered by `revert-finality-safe`.
+    #[clap(
+        long = "finality-lag-threshold",
+        value_name = "COUNT",
+        default_value = "100"
+    )]
+    pub finality_lag_threshold: u32,
+
+    /// Apply the RPC methods ACL of the given JSON file.
+    ///
+    /// The ACL classifies the methods as `safe`, `unsafe` or `gateway-internal`, and sets the
+    /// classes served and the per-method rate limits of each interface, i.e. `http` and `ws`,
+    /// see `rpc/src/access_control.rs` for the format.
+    #[clap(long = "rpc-methods-acl", value_name = "PATH", parse(from_os_str))]
+    pub rpc_methods_acl: Option<std::path::PathBuf>,
+
+    /// Specify the directory of the frontier database, which indexes the ethereum blocks,
+    /// transactions and logs for `eth_getLogs` and the other EVM RPC.
+    ///
+    /// Defaults to `<base-path>/chains/<chain>/frontier/db`. The database of an existing node
+    /// is moved over from the default directory on start, e.g. to put it on a faster disk.
+    #[clap(
+        long = "frontier-backend-path",
+        value_name = "PATH",
+        parse(from_os_str),
+        global = true
+    )]
+    pub frontier_backend_path: Option<std::path::PathBuf>,
+
+    /// Read the balances and staking snapshot of the re-genesis from a JSON file instead of
+    /// the compiled-in one.
+    ///
+    /// The snapshot has the same format as `res/3696796_regenesis_params.json`, e.g. the one
+    /// exported by `export-genesis-builder-state`, it only takes effect when a chain spec is
+    /// built from scratch, e.g. `--chain=new-mainnet`.
+    #[clap(
+        long = "genesis-state",
+        value_name = "PATH",
+        parse(from_os_str),
+        global = true
+    )]
+    pub genesis_state: Option<std::path::PathBuf>,
+
     #[clap(flatten)]
     pub logger: crate::logger::LoggerParams,
 }
 
+/// The network tuning profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ArgEnum)]
+pub enum NetworkProfile {
+    /// The Substrate defaults.
+    Default,
+    /// Tuned for the peers with a high latency.
+    HighLatency,
+}
+
+/// The preset options for the common roles of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ArgEnum)]
+pub enum NodeProfile {
+    /// Archive state for the deposit lookups, safe local RPC, larger pool, no telemetry.
+    Exchange,
+    /// Authoring with offchain workers, pruned state, local RPC only.
+    Validator,
+    /// Public safe RPC with CORS open, pruned state, larger pool, no telemetry.
+    Rpc,
+    /// Archive state, safe local RPC, no offchain workers.
+    Archive,
+}
+
+impl NodeProfile {
+    /// Returns the options applied by this profile.
+    pub fn preset_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::Exchange => &[
+                "--pruning=archive",
+                "--rpc-methods=safe",
+                "--offchain-worker=never",
+                "--pool-limit=16384",
+                "--pool-kbytes=40960",
+                "--no-telemetry",
+            ],
+            Self::Validator => &[
+                "--validator",
+                "--pruning=256",
+                "--rpc-methods=auto",
+                "--offchain-worker=when-validating",
+                "--pool-limit=8192",
+            ],
+            Self::Rpc => &[
+                "--pruning=10000",
+                "--rpc-external",
+                "--ws-external",
+                "--rpc-methods=safe",
+                "--rpc-cors=all",
+                "--ws-max-connections=1000",
+                "--offchain-worker=never",
+                "--pool-limit=16384",
+                "--pool-kbytes=40960",
+                "--no-telemetry",
+            ],
+            Self::Archive => &[
+                "--pruning=archive",
+                "--rpc-methods=safe",
+                "--offchain-worker=never",
+            ],
+        }
+    }
+}
+
 impl Cli {
+    pub fn node_options(&self) -> chainx_service::NodeOptions {
+        chainx_service::NodeOptions {
+            btc_header_archive: self.run.btc_header_archive,
+            high_latency_network: self.run.network_profile == NetworkProfile::HighLatency,
+            yamux_window_size: self.run.yamux_window_size,
+            execution_metrics: self.run.execution_metrics,
+            fee_history_limit: self.run.fee_history_limit,
+            evm_tracing: self.run.evm_tracing,
+            frontier_backend_path: self.run.frontier_backend_path.clone(),
+            allow_outdated_client: self.run.allow_outdated_client,
+            btc_broadcast_url: self.run.btc_broadcast_url.clone(),
+            finality_lag_threshold: self.run.finality_lag_threshold,
+            rpc_methods_acl: self.run.rpc_methods_acl.clone(),
+        }
+    }
+
     pub fn try_init_logger(&self) -> sc_cli::Result<()> {
         crate::logger::init(&self.run.base.log_filters()?, &self.run.logger)?;
 
@@ -8,7 +8,7 @@ use sc_cli::{
 };
 use sc_service::{
     config::{PrometheusConfig, TelemetryEndpoints},
-    BasePath, TransactionPoolOptions,
+    BasePath, KeepBlocks, TransactionPoolOptions,
 };
 use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat};
 
@@ -64,6 +64,13 @@ impl CliConfiguration<Self> for Cli {
         self.run.base.role(is_dev)
     }
 
+    fn keep_blocks(&self) -> sc_cli::Result<KeepBlocks> {
+        match self.run.blocks_pruning {
+            Some(n) => Ok(KeepBlocks::Some(n)),
+            None => self.run.base.keep_blocks(),
+        }
+    }
+
     fn transaction_pool(&self) -> sc_cli::Result<TransactionPoolOptions> {
         self.run.base.transaction_pool()
     }
@@ -128,6 +135,21 @@ impl CliConfiguration<Self> for Cli {
     fn max_runtime_instances(&self) -> sc_cli::Result<Option<usize>> {
         self.run.base.max_runtime_instances()
     }
+
+    fn default_heap_pages(&self) -> sc_cli::Result<Option<u64>> {
+        match self.run.executor_heap_pages {
+            Some(pages) => Ok(Some(pages)),
+            None => self.run.base.default_heap_pages(),
+        }
+    }
+
+    fn runtime_cache_size(&self) -> sc_cli::Result<u8> {
+        match self.run.executor_cache_size {
+            Some(0) => Err("`--executor-cache-size` must be at least 1".into()),
+            Some(size) => Ok(size),
+            None => self.run.base.runtime_cache_size(),
+        }
+    }
 }
 
 impl SubstrateCli for Cli {
@@ -161,7 +183,7 @@ impl SubstrateCli for Cli {
 
     fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
         // this id is from `--chain=<id>`
-        load_spec(id)
+        load_spec(id, self.run.genesis_state.as_deref())
     }
 
     /// Create a runner for the command provided in argument. This will create a Configuration and
@@ -209,18 +231,21 @@ impl SubstrateCli for Cli {
     }
 }
 
-fn load_spec(id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
+fn load_spec(
+    id: &str,
+    genesis_state: Option<&std::path::Path>,
+) -> Result<Box<dyn sc_service::ChainSpec>, String> {
     Ok(match id {
         "" | "mainnet" => Box::new(chain_spec::mainnet_config()?),
-        "new-mainnet" => Box::new(chain_spec::new_mainnet_config()?),
-        "dev" => Box::new(chain_spec::development_config()?),
+        "new-mainnet" => Box::new(chain_spec::new_mainnet_config(genesis_state)?),
+        "dev" => Box::new(chain_spec::development_config(genesis_state)?),
         "malan" | "testnet" => Box::new(chain_spec::malan_config()?),
-        "new-malan" => Box::new(chain_spec::new_malan_config()?),
-        "local" => Box::new(chain_spec::local_testnet_config()?),
+        "new-malan" => Box::new(chain_spec::new_malan_config(genesis_state)?),
+        "local" => Box::new(chain_spec::local_testnet_config(genesis_state)?),
         "benchmarks" => {
             #[cfg(feature = "runtime-benchmarks")]
             {
-                Box::new(chain_spec::benchmarks_config()?)
+                Box::new(chain_spec::benchmarks_config(genesis_state)?)
             }
             #[cfg(not(feature = "runtime-benchmarks"))]
             {
@@ -257,6 +282,7 @@ macro_rules! construct_async_run {
                     chainx_executor::MalanExecutor
                 >(
                     &mut $config,
+                    &$cli.node_options(),
                 )?;
                 let task_manager = $components.task_manager;
                 { $( $code )* }.map(|v| (v, task_manager))
@@ -268,6 +294,7 @@ macro_rules! construct_async_run {
                     chainx_executor::DevExecutor
                 >(
                     &mut $config,
+                    &$cli.node_options(),
                 )?;
                 let task_manager = $components.task_manager;
                 { $( $code )* }.map(|v| (v, task_manager))
@@ -279,6 +306,7 @@ macro_rules! construct_async_run {
                     chainx_executor::ChainXExecutor,
                 >(
                     &mut $config,
+                    &$cli.node_options(),
                 )?;
                 let task_manager = $components.task_manager;
                 { $( $code )* }.map(|v| (v, task_manager))
@@ -300,9 +328,10 @@ pub fn run() -> sc_cli::Result<()> {
     match &cli.subcommand {
         None => {
             let runner = cli.create_runner(&cli.run.base)?;
+            let options = cli.node_options();
 
             runner.run_node_until_exit(|config| async move {
-                service::build_full(config).map_err(sc_cli::Error::Service)
+                service::build_full(config, options).map_err(sc_cli::Error::Service)
             })
         }
         Some(Subcommand::Benchmark(cmd)) => {
@@ -344,6 +373,47 @@ pub fn run() -> sc_cli::Result<()> {
                 Ok(cmd.run(components.client, config.chain_spec))
             })
         }
+        Some(Subcommand::StorageReport(cmd)) => {
+            construct_async_run!(|components, cli, cmd, config| { Ok(cmd.run(components.client)) })
+        }
+        Some(Subcommand::ExportSyncSpec(cmd)) => {
+            construct_async_run!(|components, cli, cmd, config| {
+                let (_, grandpa_link, babe_link) = components.other.0;
+                Ok(cmd.run(
+                    config.chain_spec,
+                    components.client,
+                    grandpa_link.shared_authority_set().clone(),
+                    babe_link.epoch_changes().clone(),
+                ))
+            })
+        }
+        Some(Subcommand::ExportGenesisState(cmd)) => {
+            let chain_spec = cli.load_spec(&cmd.shared_params.chain.clone().unwrap_or_default())?;
+            let state_version = Cli::native_runtime_version(&chain_spec).state_version();
+
+            cmd.run::<chainx_primitives::Block>(&*chain_spec, state_version)
+        }
+        Some(Subcommand::ExportGenesisWasm(cmd)) => {
+            let chain_spec = cli.load_spec(&cmd.shared_params.chain.clone().unwrap_or_default())?;
+
+            cmd.run(&*chain_spec)
+        }
+        Some(Subcommand::GenesisHash(cmd)) => {
+            let chain_spec = cli.load_spec(&cmd.shared_params.chain.clone().unwrap_or_default())?;
+            let state_version = Cli::native_runtime_version(&chain_spec).state_version();
+
+            cmd.run::<chainx_primitives::Block>(&*chain_spec, state_version)
+        }
+        Some(Subcommand::ExportGenesisBuilderState(cmd)) => match &cmd.url {
+            Some(url) => cmd.run_remote(url),
+            None => construct_async_run!(|components, cli, cmd, config| {
+                Ok(cmd.run(components.client))
+            }),
+        },
+        Some(Subcommand::CheckSpec(cmd)) => cmd.run(),
+        Some(Subcommand::InspectGenesis(cmd)) => cmd.run(),
+        Some(Subcommand::BtcGenesisParams(cmd)) => cmd.run(),
+        Some(Subcommand::DevNetwork(cmd)) => cmd.run(cli.run.genesis_state.as_deref()),
         Some(Subcommand::ImportBlocks(cmd)) => {
             construct_async_run!(|components, cli, cmd, config| {
                 Ok(cmd.run(components.client, components.import_queue))
@@ -355,7 +425,10 @@ pub fn run() -> sc_cli::Result<()> {
             runner.sync_run(|config| {
                 // Remove Frontier offchain db
                 let frontier_database_config = sc_service::DatabaseSource::RocksDb {
-                    path: service::frontier_database_dir(&config),
+                    path: service::frontier_database_dir(
+                        &config,
+                        cli.run.frontier_backend_path.as_deref(),
+                    ),
                     cache_size: 0,
                 };
                 cmd.run(frontier_database_config)?;
@@ -367,6 +440,11 @@ pub fn run() -> sc_cli::Result<()> {
                 Ok(cmd.run(components.client, components.backend))
             })
         }
+        Some(Subcommand::RevertFinalitySafe(cmd)) => {
+            construct_async_run!(|components, cli, cmd, config| {
+                Ok(cmd.run(components.client, components.backend))
+            })
+        }
         #[cfg(feature = "try-runtime")]
         Some(Subcommand::TryRuntime(cmd)) => {
             let runner = cli.create_runner(cmd)?;
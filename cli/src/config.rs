@@ -6,6 +6,10 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+use clap::ArgEnum;
+
+use crate::cli::NodeProfile;
+
 fn read_config_file(path: &Path) -> Result<HashMap<String, Value>, Box<dyn std::error::Error>> {
     let mut bytes = Vec::new();
     File::open(path)?.read_to_end(&mut bytes)?;
@@ -75,27 +79,85 @@ fn extend_cli_args(
     Ok(args)
 }
 
-/// Try to inject the options from the config file.
-pub fn preprocess_cli_args(cli_args: Vec<String>) -> Vec<String> {
-    let mut config_path: Option<String> = None;
+/// Returns the value of the last `opt` option in the arg list.
+fn find_last_option(cli_args: &[String], opt: &str) -> Option<String> {
+    let prefix = format!("{}=", opt);
+    let mut value = None;
 
-    // Find the last --config option.
     let mut cli_args_iter = cli_args.iter();
     while let Some(arg) = cli_args_iter.next() {
-        if arg == "--config" {
-            let path = cli_args_iter
-                .next()
-                .expect("The argument '--config <PATH>' requires a value but none was supplied");
-            config_path = Some(path.to_string());
-        } else if arg.starts_with("--config=") {
-            config_path = arg.split('=').nth(1).map(|s| s.to_string());
-            assert!(config_path.is_some(), "missing PATH in --config=<PATH>");
+        if arg == opt {
+            let v = cli_args_iter.next().unwrap_or_else(|| {
+                panic!(
+                    "The argument '{} <VALUE>' requires a value but none was supplied",
+                    opt
+                )
+            });
+            value = Some(v.to_string());
+        } else if let Some(v) = arg.strip_prefix(&prefix) {
+            assert!(!v.is_empty(), "missing VALUE in {}=<VALUE>", opt);
+            value = Some(v.to_string());
         }
     }
 
+    value
+}
+
+/// Returns the options which take precedence over the preset option `opt`.
+fn overridden_by(opt: &str) -> &'static [&'static str] {
+    match opt {
+        "--no-telemetry" => &["--telemetry-url"],
+        "--rpc-external" => &["--unsafe-rpc-external"],
+        "--ws-external" => &["--unsafe-ws-external"],
+        _ => &[],
+    }
+}
+
+/// Extends the arg list with the preset options of `--profile`.
+///
+/// Only the options that do not appear in the arg list will be appended.
+fn extend_profile_args(args: Vec<String>) -> Vec<String> {
+    let profile = match find_last_option(&args, "--profile") {
+        Some(profile) => NodeProfile::from_str(&profile, true)
+            .unwrap_or_else(|e| panic!("Invalid value for '--profile <PROFILE>': {}", e)),
+        None => return args,
+    };
+
+    let present_opts = args
+        .iter()
+        .filter(|i| i.starts_with("--"))
+        .filter_map(|i| i.split('=').next())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    let preset_opts = profile
+        .preset_args()
+        .iter()
+        .filter(|preset| {
+            let opt = preset.split('=').next().unwrap_or(preset);
+            !present_opts
+                .iter()
+                .any(|present| present == opt || overridden_by(opt).contains(&present.as_str()))
+        })
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    let mut args = args;
+    args.extend(preset_opts);
+    args
+}
+
+/// Try to inject the options from the config file and the node profile.
+///
+/// The options passed from the command line take precedence over the ones in the config file,
+/// which in turn take precedence over the preset options of `--profile`.
+pub fn preprocess_cli_args(cli_args: Vec<String>) -> Vec<String> {
+    // Find the last --config option.
+    let config_path = find_last_option(&cli_args, "--config");
+
     let path: Option<&Path> = config_path.as_ref().map(Path::new);
     match extend_cli_args(cli_args, path) {
-        Ok(args) => args,
+        Ok(args) => extend_profile_args(args),
         Err(e) => panic!("{}", e.to_string()),
     }
 }
@@ -0,0 +1,250 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use codec::Encode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use sc_service::ChainSpec;
+
+use crate::chain_spec::{
+    authority_keys_from_seed, local_testnet_config_with_validators, LOCAL_TESTNET_SEEDS,
+};
+
+/// How long to wait for the RPC server of a spawned node.
+const RPC_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The `dev-network` command used to launch a local network of validator nodes.
+///
+/// The genesis validators are the well-known dev accounts, i.e., Alice, Bob, Charlie, etc.
+/// Each node runs in its own base path and ports, the nodes are connected to the first one
+/// and the session keys of the validators are injected via the `author_insertKey` RPC.
+#[derive(Debug, clap::Parser)]
+pub struct DevNetworkCmd {
+    /// Number of the validator nodes, at most 6.
+    #[clap(long, value_name = "COUNT", default_value = "4")]
+    pub validators: usize,
+
+    /// Directory holding the generated chain spec and the base paths of the nodes.
+    ///
+    /// Defaults to `chainx-dev-network` in the temporary directory.
+    #[clap(long, value_name = "PATH", parse(from_os_str))]
+    pub base_path: Option<PathBuf>,
+
+    /// Remove the existing chain data of the nodes before launching them.
+    #[clap(long)]
+    pub purge: bool,
+
+    /// The p2p port of the first node, the next nodes use the following ports.
+    #[clap(long, value_name = "PORT", default_value = "30333")]
+    pub port: u16,
+
+    /// The RPC port of the first node, the next nodes use the following ports.
+    #[clap(long, value_name = "PORT", default_value = "9933")]
+    pub rpc_port: u16,
+
+    /// The WebSocket port of the first node, the next nodes use the following ports.
+    #[clap(long, value_name = "PORT", default_value = "9944")]
+    pub ws_port: u16,
+
+    /// Extra arguments passed to every node, e.g. `-- -lruntime=debug`.
+    #[clap(last = true)]
+    pub node_args: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    result: Value,
+    error: Option<Value>,
+}
+
+/// A spawned validator node.
+struct Node {
+    name: &'static str,
+    port: u16,
+    rpc_port: u16,
+    ws_port: u16,
+    child: Child,
+}
+
+/// The spawned nodes, killed once the command exits.
+#[derive(Default)]
+struct Network(Vec<Node>);
+
+impl Drop for Network {
+    fn drop(&mut self) {
+        for node in &mut self.0 {
+            let _ = node.child.kill();
+            let _ = node.child.wait();
+        }
+    }
+}
+
+impl DevNetworkCmd {
+    /// Run the dev-network command.
+    pub fn run(&self, genesis_state: Option<&Path>) -> sc_cli::Result<()> {
+        if self.validators == 0 || self.validators > LOCAL_TESTNET_SEEDS.len() {
+            return Err(format!(
+                "The number of validators must be in [1, {}]",
+                LOCAL_TESTNET_SEEDS.len()
+            )
+            .into());
+        }
+        let seeds = &LOCAL_TESTNET_SEEDS[..self.validators];
+
+        let base_path = self
+            .base_path
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("chainx-dev-network"));
+        if self.purge && base_path.exists() {
+            fs::remove_dir_all(&base_path)?;
+        }
+        fs::create_dir_all(&base_path)?;
+
+        let chain_spec = local_testnet_config_with_validators(seeds, genesis_state)?;
+        let spec_path = base_path.join("chain-spec.json");
+        fs::write(&spec_path, chain_spec.as_json(true)?)?;
+        println!("Chain spec: {}", spec_path.display());
+
+        let exe = std::env::current_exe()?;
+        let mut network = Network::default();
+        let mut bootnode = None;
+        for (i, &seed) in seeds.iter().enumerate() {
+            let offset = i as u16;
+            let mut node = Node {
+                name: seed,
+                port: self.port + offset,
+                rpc_port: self.rpc_port + offset,
+                ws_port: self.ws_port + offset,
+                child: Command::new(&exe)
+                    .arg("--chain")
+                    .arg(&spec_path)
+                    .arg("--base-path")
+                    .arg(base_path.join(seed.to_lowercase()))
+                    .args(["--name", seed, "--validator", "--rpc-methods", "unsafe"])
+                    // The deterministic node keys keep the peer ids stable across the restarts.
+                    .arg("--node-key")
+                    .arg(hex::encode(sp_core::blake2_256(seed.as_bytes())))
+                    .arg("--port")
+                    .arg((self.port + offset).to_string())
+                    .arg("--rpc-port")
+                    .arg((self.rpc_port + offset).to_string())
+                    .arg("--ws-port")
+                    .arg((self.ws_port + offset).to_string())
+                    .args(bootnode.iter().flat_map(|addr| ["--bootnodes", addr.as_str()]))
+                    .args(&self.node_args)
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()?,
+            };
+            forward_logs(seed, node.child.stdout.take());
+            forward_logs(seed, node.child.stderr.take());
+            network.0.push(node);
+            let node = network.0.last_mut().expect("the node was just pushed; qed");
+
+            let peer_id: String = wait_for_rpc(node, "system_localPeerId", json!([]))?;
+            if bootnode.is_none() {
+                bootnode = Some(format!("/ip4/127.0.0.1/tcp/{}/p2p/{}", node.port, peer_id));
+            }
+            insert_session_keys(node)?;
+        }
+
+        println!("The dev network of {} validators is running:", seeds.len());
+        for node in &network.0 {
+            println!(
+                "  {:<8} p2p: {}, rpc: http://127.0.0.1:{}, ws: ws://127.0.0.1:{}",
+                node.name, node.port, node.rpc_port, node.ws_port
+            );
+        }
+
+        loop {
+            for node in &mut network.0 {
+                if let Some(status) = node.child.try_wait()? {
+                    return Err(format!("The node {} exited with {}", node.name, status).into());
+                }
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+    }
+}
+
+/// Prints the output of the node line by line, prefixed by the name of the node.
+fn forward_logs(name: &'static str, output: Option<impl Read + Send + 'static>) {
+    if let Some(output) = output {
+        thread::spawn(move || {
+            for line in BufReader::new(output).lines() {
+                match line {
+                    Ok(line) => {
+                        let _ = writeln!(std::io::stdout().lock(), "{:>8} | {}", name, line);
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+    }
+}
+
+/// Inserts the babe, grandpa, im-online and authority-discovery keys of the node.
+fn insert_session_keys(node: &Node) -> sc_cli::Result<()> {
+    let suri = format!("//{}", node.name);
+    let (_, babe, grandpa, im_online, authority_discovery) = authority_keys_from_seed(node.name);
+    let keys = [
+        ("babe", babe.encode()),
+        ("gran", grandpa.encode()),
+        ("imon", im_online.encode()),
+        ("audi", authority_discovery.encode()),
+    ];
+    for (key_type, public) in keys {
+        let params = json!([key_type, suri, format!("0x{}", hex::encode(public))]);
+        let _: () = request(node.rpc_port, "author_insertKey", params)?;
+    }
+    Ok(())
+}
+
+/// Polls the RPC of the node until it responds, fails if the node exits in the meantime.
+fn wait_for_rpc<T: DeserializeOwned>(
+    node: &mut Node,
+    method: &str,
+    params: Value,
+) -> sc_cli::Result<T> {
+    let started = Instant::now();
+    loop {
+        if let Some(status) = node.child.try_wait()? {
+            return Err(format!("The node {} exited with {}", node.name, status).into());
+        }
+        match request(node.rpc_port, method, params.clone()) {
+            Ok(result) => return Ok(result),
+            Err(e) if started.elapsed() > RPC_TIMEOUT => return Err(e),
+            Err(_) => thread::sleep(Duration::from_millis(500)),
+        }
+    }
+}
+
+/// Sends a JSON-RPC request to the node listening on the given port.
+fn request<T: DeserializeOwned>(port: u16, method: &str, params: Value) -> sc_cli::Result<T> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+    let response: RpcResponse = ureq::post(&format!("http://127.0.0.1:{}", port))
+        .send_json(body)
+        .map_err(|e| format!("Failed to request `{}`: {}", method, e))?
+        .into_json()
+        .map_err(|e| format!("Invalid response of `{}`: {}", method, e))?;
+
+    if let Some(error) = response.error {
+        return Err(format!("`{}` failed: {}", method, error).into());
+    }
+    serde_json::from_value(response.result)
+        .map_err(|e| format!("Invalid result of `{}`: {}", method, e).into())
+}
@@ -0,0 +1,191 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::{fs, io::Write, path::PathBuf};
+
+use codec::Encode;
+use sc_cli::{ChainSpec, CliConfiguration, SharedParams};
+use sp_core::storage::{well_known_keys, StateVersion};
+use sp_runtime::{
+    traits::{Block as BlockT, Hash as HashT, Header as HeaderT, Zero},
+    BuildStorage,
+};
+
+/// The `export-genesis-state` command used to export the genesis head of a chain spec.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportGenesisStateCmd {
+    /// Output file name or stdout if unspecified.
+    #[clap(parse(from_os_str))]
+    pub output: Option<PathBuf>,
+
+    /// Write the output in binary. Default is to write in hex.
+    #[clap(short, long)]
+    pub raw: bool,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+}
+
+impl ExportGenesisStateCmd {
+    /// Run the export-genesis-state command.
+    pub fn run<Block: BlockT>(
+        &self,
+        chain_spec: &dyn ChainSpec,
+        state_version: StateVersion,
+    ) -> sc_cli::Result<()> {
+        let block = generate_genesis_block::<Block>(chain_spec, state_version)?;
+        write_output(self.output.as_ref(), &block.header().encode(), self.raw)
+    }
+}
+
+impl CliConfiguration for ExportGenesisStateCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+}
+
+/// The `export-genesis-wasm` command used to export the genesis runtime wasm of a chain spec.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportGenesisWasmCmd {
+    /// Output file name or stdout if unspecified.
+    #[clap(parse(from_os_str))]
+    pub output: Option<PathBuf>,
+
+    /// Write the output in binary. Default is to write in hex.
+    #[clap(short, long)]
+    pub raw: bool,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+}
+
+impl ExportGenesisWasmCmd {
+    /// Run the export-genesis-wasm command.
+    pub fn run(&self, chain_spec: &dyn ChainSpec) -> sc_cli::Result<()> {
+        let storage = chain_spec.build_storage()?;
+        let code = storage
+            .top
+            .get(well_known_keys::CODE)
+            .ok_or("Could not find the runtime wasm in the genesis storage")?;
+        write_output(self.output.as_ref(), code, self.raw)
+    }
+}
+
+impl CliConfiguration for ExportGenesisWasmCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+}
+
+/// The `genesis-hash` command used to compute the genesis state root and block hash of a
+/// chain spec, e.g. to pin them in the deployment configs.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct GenesisHashCmd {
+    /// Fail unless the genesis block hash equals the given one.
+    #[clap(long, value_name = "HASH")]
+    pub expected: Option<String>,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+}
+
+impl GenesisHashCmd {
+    /// Run the genesis-hash command.
+    pub fn run<Block: BlockT>(
+        &self,
+        chain_spec: &dyn ChainSpec,
+        state_version: StateVersion,
+    ) -> sc_cli::Result<()> {
+        let block = generate_genesis_block::<Block>(chain_spec, state_version)?;
+        let genesis_hash = format!("0x{}", hex::encode(block.header().hash()));
+
+        println!("Chain: {}", chain_spec.id());
+        println!("State root: 0x{}", hex::encode(block.header().state_root()));
+        println!("Genesis hash: {}", genesis_hash);
+
+        match &self.expected {
+            Some(expected) if !expected.eq_ignore_ascii_case(&genesis_hash) => Err(format!(
+                "The genesis hash {} doesn't match the expected {}",
+                genesis_hash, expected
+            )
+            .into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl CliConfiguration for GenesisHashCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+}
+
+/// Builds the genesis block of the chain spec without opening a database.
+fn generate_genesis_block<Block: BlockT>(
+    chain_spec: &dyn ChainSpec,
+    state_version: StateVersion,
+) -> sc_cli::Result<Block> {
+    let storage = chain_spec.build_storage()?;
+
+    let child_roots = storage.children_default.iter().map(|(key, child)| {
+        let root = <<Block::Header as HeaderT>::Hashing as HashT>::trie_root(
+            child.data.clone().into_iter().collect(),
+            state_version,
+        );
+        (key.clone(), root.encode())
+    });
+    let state_root = <<Block::Header as HeaderT>::Hashing as HashT>::trie_root(
+        storage.top.clone().into_iter().chain(child_roots).collect(),
+        state_version,
+    );
+    let extrinsics_root =
+        <<Block::Header as HeaderT>::Hashing as HashT>::trie_root(Vec::new(), state_version);
+
+    Ok(Block::new(
+        <Block::Header as HeaderT>::new(
+            Zero::zero(),
+            extrinsics_root,
+            state_root,
+            Default::default(),
+            Default::default(),
+        ),
+        Default::default(),
+    ))
+}
+
+fn write_output(output: Option<&PathBuf>, data: &[u8], raw: bool) -> sc_cli::Result<()> {
+    let data = if raw {
+        data.to_vec()
+    } else {
+        format!("0x{}", hex::encode(data)).into_bytes()
+    };
+
+    if let Some(path) = output {
+        fs::write(path, data)?;
+    } else {
+        std::io::stdout().write_all(&data)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_genesis_hash_never_drifts() {
+    let genesis_hash = |chain_spec: &dyn ChainSpec| {
+        let block =
+            generate_genesis_block::<chainx_primitives::Block>(chain_spec, StateVersion::V0)
+                .unwrap();
+        format!("0x{}", hex::encode(block.header().hash()))
+    };
+
+    assert_eq!(
+        genesis_hash(&crate::chain_spec::mainnet_config().unwrap()),
+        "0x6ac13efb5b368b97b4934cef6edfdd99c2af51ba5109bfb8dacc116f9c584c10"
+    );
+    assert_eq!(
+        genesis_hash(&crate::chain_spec::malan_config().unwrap()),
+        "0x728b9eb51b1ed85f613291acf65ba33635e2555485feb19fdb66d44eeb9b26ed"
+    );
+}
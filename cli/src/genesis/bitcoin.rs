@@ -3,31 +3,30 @@
 use std::convert::TryFrom;
 
 use hex_literal::hex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use sp_core::sr25519;
 
 use chainx_primitives::AccountId;
 
 use chainx_runtime::{
-    h256_rev, trustees, BtcHeader, BtcNetwork, Chain, Compact as BtcCompact, TrusteeInfoConfig,
-    H256 as BtcHash,
+    h256_rev, trustees, BtcHeader, BtcNetwork, Chain, TrusteeInfoConfig, H256 as BtcHash,
 };
 
 use crate::chain_spec::get_account_id_from_seed;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BtcGenesisParams {
     pub network: BtcNetwork,
     pub confirmation_number: u32,
     pub height: u32,
-    hash: String,
-    version: u32,
-    previous_header_hash: String,
-    merkle_root_hash: String,
-    time: u32,
-    bits: BtcCompact,
-    nonce: u32,
+    pub(crate) hash: String,
+    pub(crate) version: u32,
+    pub(crate) previous_header_hash: String,
+    pub(crate) merkle_root_hash: String,
+    pub(crate) time: u32,
+    pub(crate) bits: u32,
+    pub(crate) nonce: u32,
 }
 
 impl BtcGenesisParams {
@@ -48,7 +47,7 @@ impl BtcGenesisParams {
             previous_header_hash: h256_rev(&self.previous_header_hash),
             merkle_root_hash: h256_rev(&self.merkle_root_hash),
             time: self.time,
-            bits: self.bits,
+            bits: self.bits.into(),
             nonce: self.nonce,
         }
     }
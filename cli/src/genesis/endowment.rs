@@ -0,0 +1,81 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use sp_core::sr25519;
+
+use chainx_primitives::{AccountId, AssetId, Balance};
+use xp_protocol::PCX;
+
+use crate::chain_spec::get_account_id_from_seed;
+use crate::genesis::assets::AssetParams;
+
+/// The endowed accounts of each asset, keyed by the asset id.
+pub type Endowed = BTreeMap<AssetId, Vec<(AccountId, Balance)>>;
+
+/// Builder of the genesis endowment of the dev accounts.
+///
+/// The endowed values are in the units of the asset, e.g., `asset(X_BTC, "Bob", 1)`
+/// endows 1 BTC to Bob, they are scaled by the decimals of the asset in [`Self::build`].
+#[derive(Debug, Clone, Default)]
+pub struct EndowmentBuilder {
+    endowed: BTreeMap<AssetId, Vec<(String, Balance)>>,
+}
+
+impl EndowmentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Endows `value` PCX to the account derived from `seed`.
+    pub fn pcx(self, seed: &str, value: Balance) -> Self {
+        self.asset(PCX, seed, value)
+    }
+
+    /// Endows `value` of the asset `asset_id` to the account derived from `seed`.
+    pub fn asset(mut self, asset_id: AssetId, seed: &str, value: Balance) -> Self {
+        self.endowed
+            .entry(asset_id)
+            .or_default()
+            .push((seed.to_string(), value));
+        self
+    }
+
+    /// Builds the endowment of the genesis assets `assets`.
+    ///
+    /// Fails if an asset is not one of `assets`, an account is endowed twice with the same
+    /// asset or the total endowment of an asset overflows.
+    pub fn build(self, assets: &[AssetParams]) -> Result<Endowed, String> {
+        if !self.endowed.contains_key(&PCX) {
+            return Err("No PCX endowed account".into());
+        }
+
+        let mut endowed = Endowed::new();
+        for (asset_id, accounts) in self.endowed {
+            let decimals = assets
+                .iter()
+                .find(|(id, ..)| *id == asset_id)
+                .map(|(_, info, ..)| info.decimals())
+                .ok_or_else(|| format!("Asset {} is not a genesis asset", asset_id))?;
+            let unit = 10_u128.pow(decimals as u32);
+
+            let mut seeds = BTreeSet::new();
+            let mut total: Balance = 0;
+            let mut endowed_info = Vec::with_capacity(accounts.len());
+            for (seed, value) in accounts {
+                if !seeds.insert(seed.clone()) {
+                    return Err(format!("{} is endowed twice with asset {}", seed, asset_id));
+                }
+                let value = value
+                    .checked_mul(unit)
+                    .ok_or_else(|| format!("Endowment of {} overflows", seed))?;
+                total = total
+                    .checked_add(value)
+                    .ok_or_else(|| format!("Total endowment of asset {} overflows", asset_id))?;
+                endowed_info.push((get_account_id_from_seed::<sr25519::Public>(&seed), value));
+            }
+            endowed.insert(asset_id, endowed_info);
+        }
+        Ok(endowed)
+    }
+}
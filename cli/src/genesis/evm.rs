@@ -0,0 +1,79 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use sp_core::{hashing::keccak_256, Bytes, H160, H256, U256};
+
+use chainx_runtime::GenesisAccount;
+
+/// The EVM accounts deployed at genesis by their address.
+pub type EvmGenesisAccounts = BTreeMap<H160, GenesisAccount>;
+
+/// A system contract pre-deployed at genesis, e.g. the bridge helper, the WPCX wrapped native
+/// token or the multicall.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmGenesisContract {
+    pub name: String,
+    pub address: H160,
+    /// The deployed (runtime) bytecode, not the creation bytecode.
+    pub code: Bytes,
+    /// `keccak256(code)`, the `extcodehash` of the contract.
+    pub code_hash: H256,
+    /// The initial storage, e.g. the slots written by the constructor.
+    #[serde(default)]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvmGenesisManifest {
+    pub contracts: Vec<EvmGenesisContract>,
+}
+
+/// Returns the EVM accounts of the system contracts declared in the manifest `res`.
+///
+/// The code of each contract must match the declared hash, and the contract must not be
+/// placed at a precompile address or at the address of another contract.
+pub fn evm_genesis_accounts(res: &str, precompiles: &[H160]) -> Result<EvmGenesisAccounts, String> {
+    let manifest: EvmGenesisManifest = serde_json::from_str(res)
+        .map_err(|e| format!("Failed to parse the EVM genesis contracts: {}", e))?;
+
+    let mut accounts = EvmGenesisAccounts::new();
+    for contract in manifest.contracts {
+        if contract.code.is_empty() {
+            return Err(format!(
+                "EVM genesis contract {} has no code",
+                contract.name
+            ));
+        }
+        let code_hash = H256(keccak_256(&contract.code));
+        if code_hash != contract.code_hash {
+            return Err(format!(
+                "EVM genesis contract {} has the code hash {:?}, expected {:?}",
+                contract.name, code_hash, contract.code_hash
+            ));
+        }
+        if precompiles.contains(&contract.address) {
+            return Err(format!(
+                "EVM genesis contract {} is at the precompile address {:?}",
+                contract.name, contract.address
+            ));
+        }
+        let account = GenesisAccount {
+            // The contracts created by `CREATE` start with the nonce 1 (EIP-161).
+            nonce: U256::one(),
+            balance: U256::zero(),
+            storage: contract.storage,
+            code: contract.code.0,
+        };
+        if accounts.insert(contract.address, account).is_some() {
+            return Err(format!(
+                "EVM genesis contract {} is at the duplicated address {:?}",
+                contract.name, contract.address
+            ));
+        }
+    }
+    Ok(accounts)
+}
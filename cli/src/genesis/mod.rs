@@ -2,13 +2,83 @@
 
 pub mod assets;
 pub mod bitcoin;
+pub mod endowment;
+pub mod evm;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
 
 use xp_genesis_builder::AllParams;
 
 use chainx_primitives::{AccountId, Balance};
 
-pub fn genesis_builder_params() -> AllParams<AccountId, Balance, Balance, Balance> {
-    serde_json::from_str(include_str!("../res/3696796_regenesis_params.json"))
-        .map_err(|e| log::error!("{:?}", e))
-        .expect("JSON was not well-formatted")
+pub type GenesisBuilderParams = AllParams<AccountId, Balance, Balance, Balance>;
+
+/// Returns the re-genesis params read from the snapshot file `genesis_state`, or the
+/// compiled-in ones if not specified.
+pub fn genesis_builder_params(
+    genesis_state: Option<&Path>,
+) -> Result<GenesisBuilderParams, String> {
+    let params: GenesisBuilderParams = match genesis_state {
+        Some(path) => {
+            let file = File::open(path).map_err(|e| {
+                format!("Failed to open the genesis state {}: {}", path.display(), e)
+            })?;
+            serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+                format!(
+                    "Failed to parse the genesis state {}: {}",
+                    path.display(),
+                    e
+                )
+            })?
+        }
+        None => serde_json::from_str(include_str!("../res/3696796_regenesis_params.json"))
+            .map_err(|e| log::error!("{:?}", e))
+            .expect("JSON was not well-formatted"),
+    };
+    validate_genesis_builder_params(&params)?;
+    Ok(params)
+}
+
+/// Ensures the nominees of the snapshot are all validators, each validator is unique and the
+/// vesting schedules lock no more than the PCX balances.
+fn validate_genesis_builder_params(params: &GenesisBuilderParams) -> Result<(), String> {
+    let mut validators = BTreeSet::new();
+    for validator in &params.xstaking.validators {
+        if !validators.insert(&validator.who) {
+            return Err(format!("Validator {} is duplicated", validator.who));
+        }
+    }
+    for nominator in &params.xstaking.nominators {
+        for nomination in &nominator.nominations {
+            if !validators.contains(&nomination.nominee) {
+                return Err(format!(
+                    "Nominee {} of {} is not a validator",
+                    nomination.nominee, nominator.nominator
+                ));
+            }
+        }
+    }
+
+    let mut locked = BTreeMap::<&AccountId, Balance>::new();
+    for schedule in &params.vesting.schedules {
+        let total = locked.entry(&schedule.who).or_default();
+        *total = total.saturating_add(schedule.locked);
+    }
+    for (who, locked) in locked {
+        let free = params
+            .balances
+            .iter()
+            .filter(|info| &info.who == who)
+            .fold(0, |acc: Balance, info| acc.saturating_add(info.free));
+        if locked > free {
+            return Err(format!(
+                "Vesting of {} locks {}, more than its balance {}",
+                who, locked, free
+            ));
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,372 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::Arc};
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::{StorageData, StorageKey};
+use sp_runtime::generic::BlockId;
+
+use chainx_primitives::{AccountId, AssetId, Balance, Block, BlockNumber};
+use chainx_runtime::{AssetType, Chain, VoteWeight, X_BTC};
+use xp_genesis_builder::{
+    AssetRestriction, FreeBalanceInfo, Nomination, NominatorInfo, ReferralBind, ValidatorInfo,
+    VestingInfo, VestingParams, XStakingParams,
+};
+use xpallet_mining_staking::{NominatorLedger, ValidatorLedger, ValidatorProfile};
+
+use crate::genesis::GenesisBuilderParams;
+use crate::inspect_genesis::{
+    decode_key, decode_value, storage_prefix, AccountInfo, BLAKE2_128_LEN, STORAGE_PREFIX_LEN,
+    TWOX_64_LEN,
+};
+
+/// The number of the storage keys fetched by each RPC request.
+const RPC_PAGE_SIZE: u32 = 1000;
+
+/// The `export-genesis-builder-state` command used to take the snapshot of a re-genesis.
+///
+/// The PCX balances, vesting schedules, X-BTC balances, staking ledgers, referral bindings
+/// and asset restrictions are read from the state of the local database, or of a live node given by
+/// `--url`, and written in the format of `--genesis-state`, i.e. the params of the
+/// `XGenesisBuilder` genesis config.
+///
+/// The reserved balances are merged into the free ones and the pending withdrawals of X-BTC
+/// into the usable ones, since the re-genesis only sets the free balances. The vote weights
+/// are not exported, the re-genesis resets them. The vesting schedules are rebased on the
+/// exported block, as the re-genesis starts from block 0.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportGenesisBuilderStateCmd {
+    /// Output file name or stdout if unspecified.
+    #[clap(parse(from_os_str))]
+    pub output: Option<PathBuf>,
+
+    /// Read the state from the node at this HTTP RPC endpoint instead of the local database.
+    #[clap(long, value_name = "URL")]
+    pub url: Option<String>,
+
+    /// Block hash or number, the latest finalized block if unspecified.
+    #[clap(long, value_name = "HASH or NUMBER")]
+    pub at: Option<BlockNumberOrHash>,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+type StoragePairs = Vec<(Vec<u8>, Vec<u8>)>;
+
+impl ExportGenesisBuilderStateCmd {
+    /// Run the export-genesis-builder-state command against the local database.
+    pub async fn run<BA, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+    where
+        BA: Backend<Block>,
+        C: HeaderBackend<Block> + StorageProvider<Block, BA>,
+    {
+        let at = match &self.at {
+            Some(at) => at.parse::<Block>()?,
+            None => BlockId::hash(client.info().finalized_hash),
+        };
+        let params = snapshot(|prefix| {
+            let pairs = client
+                .storage_pairs(&at, &StorageKey(prefix))
+                .map_err(|e| format!("Failed to read the storage at {}: {}", at, e))?;
+            Ok(pairs
+                .into_iter()
+                .map(|(key, value)| (key.0, value.0))
+                .collect())
+        })?;
+        self.write_output(&params)
+    }
+
+    /// Run the export-genesis-builder-state command against the node at `url`.
+    pub fn run_remote(&self, url: &str) -> sc_cli::Result<()> {
+        let at: String = match self.at.as_ref().map(|at| at.parse::<Block>()).transpose()? {
+            Some(BlockId::Hash(hash)) => format!("{:?}", hash),
+            Some(BlockId::Number(number)) => {
+                rpc_request(url, "chain_getBlockHash", json!([number]))?
+            }
+            None => rpc_request(url, "chain_getFinalizedHead", json!([]))?,
+        };
+        let params = snapshot(|prefix| remote_storage_pairs(url, prefix, &at))?;
+        self.write_output(&params)
+    }
+
+    fn write_output(&self, params: &GenesisBuilderParams) -> sc_cli::Result<()> {
+        let json = serde_json::to_string_pretty(params)
+            .map_err(|e| format!("Failed to serialize the genesis state: {}", e))?;
+        match &self.output {
+            Some(path) => fs::write(path, json)?,
+            None => println!("{}", json),
+        }
+        Ok(())
+    }
+}
+
+impl CliConfiguration for ExportGenesisBuilderStateCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
+
+/// Returns the re-genesis params from the storage entries returned by `pairs`, which are
+/// the entries under the given prefix with the prefix included in the key.
+fn snapshot(
+    pairs: impl Fn(Vec<u8>) -> sc_cli::Result<StoragePairs>,
+) -> sc_cli::Result<GenesisBuilderParams> {
+    let map = |pallet: &str, item: &str| -> sc_cli::Result<StoragePairs> {
+        Ok(pairs(storage_prefix(pallet, item))?
+            .into_iter()
+            .map(|(key, value)| (key[STORAGE_PREFIX_LEN..].to_vec(), value))
+            .collect())
+    };
+
+    let mut balances = Vec::new();
+    for (key, value) in map("System", "Account")? {
+        let (who, _) = decode_key::<AccountId>(&key, BLAKE2_128_LEN)?;
+        let info = decode_value::<AccountInfo>(&value)?;
+        let free = info.free.saturating_add(info.reserved);
+        if free > 0 {
+            balances.push(FreeBalanceInfo { who, free });
+        }
+    }
+
+    let now = match map("System", "Number")?.first() {
+        Some((_, value)) => decode_value::<BlockNumber>(value)?,
+        None => 0,
+    };
+    let mut schedules = Vec::new();
+    for (key, value) in map("Vesting", "Vesting")? {
+        let (who, _) = decode_key::<AccountId>(&key, BLAKE2_128_LEN)?;
+        // The same encoding as `pallet_vesting::VestingInfo`.
+        let infos = decode_value::<Vec<(Balance, Balance, BlockNumber)>>(&value)?;
+        for (locked, per_block, starting_block) in infos {
+            let vested = per_block.saturating_mul(now.saturating_sub(starting_block).into());
+            let locked = locked.saturating_sub(vested);
+            if locked > 0 {
+                schedules.push(VestingInfo {
+                    who: who.clone(),
+                    locked,
+                    per_block,
+                    starting_block: starting_block.saturating_sub(now),
+                });
+            }
+        }
+    }
+
+    let mut xassets = Vec::new();
+    for (key, value) in map("XAssets", "AssetBalance")? {
+        let (who, rest) = decode_key::<AccountId>(&key, BLAKE2_128_LEN)?;
+        let (asset_id, _) = decode_key::<AssetId>(rest, TWOX_64_LEN)?;
+        if asset_id != X_BTC {
+            continue;
+        }
+        let free = decode_value::<BTreeMap<AssetType, Balance>>(&value)?
+            .values()
+            .fold(0, |acc: Balance, x| acc.saturating_add(*x));
+        if free > 0 {
+            xassets.push(FreeBalanceInfo { who, free });
+        }
+    }
+
+    let mut total_nominations = BTreeMap::new();
+    for (key, value) in map("XStaking", "ValidatorLedgers")? {
+        let (who, _) = decode_key::<AccountId>(&key, TWOX_64_LEN)?;
+        let ledger = decode_value::<ValidatorLedger<Balance, VoteWeight, BlockNumber>>(&value)?;
+        total_nominations.insert(who, ledger.total_nomination);
+    }
+    let mut validators = Vec::new();
+    for (key, value) in map("XStaking", "Validators")? {
+        let (who, _) = decode_key::<AccountId>(&key, TWOX_64_LEN)?;
+        let profile = decode_value::<ValidatorProfile<BlockNumber>>(&value)?;
+        let total_nomination = total_nominations.get(&who).copied().unwrap_or_default();
+        validators.push(ValidatorInfo {
+            who,
+            referral_id: profile.referral_id,
+            total_nomination,
+        });
+    }
+
+    let mut nominations = BTreeMap::<AccountId, Vec<Nomination<AccountId, Balance>>>::new();
+    for (key, value) in map("XStaking", "Nominations")? {
+        let (nominator, rest) = decode_key::<AccountId>(&key, TWOX_64_LEN)?;
+        let (nominee, _) = decode_key::<AccountId>(rest, TWOX_64_LEN)?;
+        let ledger = decode_value::<NominatorLedger<Balance, VoteWeight, BlockNumber>>(&value)?;
+        if ledger.nomination > 0 {
+            nominations.entry(nominator).or_default().push(Nomination {
+                nominee,
+                nomination: ledger.nomination,
+            });
+        }
+    }
+    let nominators = nominations
+        .into_iter()
+        .map(|(nominator, nominations)| NominatorInfo {
+            nominator,
+            nominations,
+        })
+        .collect();
+
+    let mut referral_binds = Vec::new();
+    for (key, value) in map("XGatewayCommon", "ReferralBindingOf")? {
+        let (who, rest) = decode_key::<AccountId>(&key, BLAKE2_128_LEN)?;
+        let (chain, _) = decode_key::<Chain>(rest, TWOX_64_LEN)?;
+        let referral = decode_value::<AccountId>(&value)?;
+        referral_binds.push(ReferralBind {
+            who,
+            chain,
+            referral,
+        });
+    }
+
+    let mut asset_restrictions = Vec::new();
+    for (key, value) in map("XAssets", "AssetRestrictionsOf")? {
+        let (asset_id, _) = decode_key::<AssetId>(&key, TWOX_64_LEN)?;
+        let restrictions = decode_value::<u32>(&value)?;
+        if restrictions != 0 {
+            asset_restrictions.push(AssetRestriction {
+                asset_id,
+                restrictions,
+            });
+        }
+    }
+
+    Ok(GenesisBuilderParams {
+        balances,
+        xassets,
+        xstaking: XStakingParams {
+            validators,
+            nominators,
+        },
+        referral_binds,
+        asset_restrictions,
+        vesting: VestingParams { schedules },
+    })
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct StorageChangeSet {
+    changes: Vec<(StorageKey, Option<StorageData>)>,
+}
+
+fn rpc_request<T: DeserializeOwned>(url: &str, method: &str, params: Value) -> sc_cli::Result<T> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+    let response: RpcResponse<T> = ureq::post(url)
+        .send_json(body)
+        .map_err(|e| format!("Failed to request `{}`: {}", method, e))?
+        .into_json()
+        .map_err(|e| format!("Invalid response of `{}`: {}", method, e))?;
+    match (response.result, response.error) {
+        (Some(result), None) => Ok(result),
+        (_, Some(error)) => Err(format!("`{}` failed: {}", method, error).into()),
+        (None, None) => Err(format!("`{}` returned nothing", method).into()),
+    }
+}
+
+/// Reads the storage entries under `prefix` page by page, since `state_getPairs` is unsafe
+/// and denied by the public nodes.
+fn remote_storage_pairs(url: &str, prefix: Vec<u8>, at: &str) -> sc_cli::Result<StoragePairs> {
+    let prefix = StorageKey(prefix);
+    let mut pairs = Vec::new();
+    let mut start_key: Option<StorageKey> = None;
+    loop {
+        let keys: Vec<StorageKey> = rpc_request(
+            url,
+            "state_getKeysPaged",
+            json!([prefix, RPC_PAGE_SIZE, start_key, at]),
+        )?;
+        let last = match keys.last() {
+            Some(last) => last.clone(),
+            None => break,
+        };
+        let change_sets: Vec<StorageChangeSet> =
+            rpc_request(url, "state_queryStorageAt", json!([keys, at]))?;
+        pairs.extend(
+            change_sets
+                .into_iter()
+                .flat_map(|set| set.changes)
+                .filter_map(|(key, value)| Some((key.0, value?.0))),
+        );
+        if keys.len() < RPC_PAGE_SIZE as usize {
+            break;
+        }
+        start_key = Some(last);
+    }
+    Ok(pairs)
+}
+
+#[test]
+fn test_snapshot() {
+    use sp_runtime::BuildStorage;
+
+    let spec = crate::chain_spec::local_testnet_config(None).unwrap();
+    let storage = spec.build_storage().unwrap().top;
+    let params = snapshot(|prefix| {
+        Ok(storage
+            .range(prefix.clone()..)
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    })
+    .unwrap();
+
+    // The state restored from the compiled-in snapshot is exported as is.
+    let genesis_state = crate::genesis::genesis_builder_params(None).unwrap();
+    let validators = params
+        .xstaking
+        .validators
+        .iter()
+        .map(|v| (&v.who, &v.referral_id))
+        .collect::<BTreeMap<_, _>>();
+    assert!(genesis_state
+        .xstaking
+        .validators
+        .iter()
+        .all(|v| validators.get(&v.who) == Some(&&v.referral_id)));
+    let xbtc = params
+        .xassets
+        .iter()
+        .map(|x| (&x.who, x.free))
+        .collect::<BTreeMap<_, _>>();
+    assert!(genesis_state
+        .xassets
+        .iter()
+        .filter(|x| x.free > 0)
+        .all(|x| xbtc.get(&x.who) == Some(&x.free)));
+
+    // The snapshot is read back as the genesis state.
+    let json = serde_json::to_string(&params).unwrap();
+    let decoded: GenesisBuilderParams = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.balances.len(), params.balances.len());
+}
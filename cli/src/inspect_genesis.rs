@@ -0,0 +1,455 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::collections::BTreeMap;
+use std::{fs, path::PathBuf};
+
+use codec::Decode;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sp_core::hashing::twox_128;
+
+use chainx_primitives::{AccountId, AssetId, Balance, Index};
+use chainx_runtime::{
+    AssetInfo, AssetType, Chain, GenericTrusteeIntentionProps, TrusteeInfoConfig,
+};
+
+/// The length of `twox_128(pallet) ++ twox_128(item)` of a storage key.
+pub(crate) const STORAGE_PREFIX_LEN: usize = 32;
+/// The length of the hash of `Twox64Concat`.
+pub(crate) const TWOX_64_LEN: usize = 8;
+/// The length of the hash of `Blake2_128Concat`.
+pub(crate) const BLAKE2_128_LEN: usize = 16;
+
+/// The staking params inspected, named after the storage items of `XStaking`.
+const STAKING_PARAMS: [&str; 6] = [
+    "ValidatorCount",
+    "MinimumValidatorCount",
+    "MaximumValidatorCount",
+    "SessionsPerEra",
+    "BondingDuration",
+    "ValidatorBondingDuration",
+];
+
+/// The `inspect-genesis` command used to audit the genesis of the chain specs.
+///
+/// The balances, assets, trustees and staking params are decoded from the genesis config of
+/// a plain chain spec, or from the genesis storage of a raw one, e.g. `chainx_regenesis.json`,
+/// so that a plain chain spec can be compared to the raw one built from it as well.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct InspectGenesisCmd {
+    /// The chain spec file to inspect, given twice along with `--diff`.
+    #[clap(
+        long = "spec",
+        value_name = "FILE",
+        parse(from_os_str),
+        required = true
+    )]
+    pub specs: Vec<PathBuf>,
+
+    /// Print the difference between the genesis of the two chain specs.
+    #[clap(long)]
+    pub diff: bool,
+
+    /// Print the result in JSON.
+    #[clap(long)]
+    pub json: bool,
+}
+
+/// The decoded entries of each part of the genesis, by the display of the entry key.
+type Sections = BTreeMap<&'static str, BTreeMap<String, String>>;
+
+/// The difference of a part of the genesis, `changed` maps to `[before, after]`.
+#[derive(Debug, Default, Serialize)]
+struct SectionDiff {
+    added: BTreeMap<String, String>,
+    removed: BTreeMap<String, String>,
+    changed: BTreeMap<String, (String, String)>,
+}
+
+impl SectionDiff {
+    fn new(before: &BTreeMap<String, String>, after: &BTreeMap<String, String>) -> Self {
+        let mut diff = Self::default();
+        for (key, value) in before {
+            match after.get(key) {
+                None => {
+                    diff.removed.insert(key.clone(), value.clone());
+                }
+                Some(new) if new != value => {
+                    diff.changed
+                        .insert(key.clone(), (value.clone(), new.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, value) in after {
+            if !before.contains_key(key) {
+                diff.added.insert(key.clone(), value.clone());
+            }
+        }
+        diff
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+macro_rules! plain_sections {
+    ($runtime:ident, $genesis:expr) => {{
+        let config: $runtime::GenesisConfig = serde_json::from_value($genesis)
+            .map_err(|e| format!("Invalid genesis config: {}", e))?;
+
+        let balances = config
+            .balances
+            .balances
+            .into_iter()
+            .map(|(who, value)| (who.to_string(), value.to_string()))
+            .collect();
+
+        let mut assets = BTreeMap::new();
+        for (asset_id, info, _, _) in config.x_assets_registrar.assets {
+            assets.insert(asset_key(asset_id), asset_info(&info));
+        }
+        for (asset_id, endowed) in config.x_assets.endowed {
+            for (who, value) in endowed {
+                assets.insert(asset_balance_key(asset_id, &who), value.to_string());
+            }
+        }
+
+        let mut trustees = BTreeMap::new();
+        for (chain, info, intentions) in config.x_gateway_common.trustees {
+            trustees.insert(format!("{:?}", chain), trustee_info(&info));
+            for (who, _, hot, cold) in intentions {
+                trustees.insert(trustee_key(chain, &who), trustee_keys(&hot, &cold));
+            }
+        }
+
+        let staking = config.x_staking;
+        let staking = STAKING_PARAMS
+            .iter()
+            .zip([
+                staking.validator_count,
+                staking.minimum_validator_count,
+                staking.maximum_validator_count,
+                staking.sessions_per_era,
+                staking.bonding_duration,
+                staking.validator_bonding_duration,
+            ])
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        Sections::from([
+            ("balances", balances),
+            ("assets", assets),
+            ("trustees", trustees),
+            ("staking", staking),
+        ])
+    }};
+}
+
+impl InspectGenesisCmd {
+    /// Run the inspect-genesis command.
+    pub fn run(&self) -> sc_cli::Result<()> {
+        if self.diff && self.specs.len() != 2 {
+            return Err("`--diff` requires exactly two `--spec`".into());
+        }
+        let sections = self
+            .specs
+            .iter()
+            .map(|path| {
+                let content = fs::read(path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                let spec = serde_json::from_slice(&content)
+                    .map_err(|e| format!("Invalid JSON of {}: {}", path.display(), e))?;
+                inspect_genesis(spec).map_err(|e| format!("{}: {}", path.display(), e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        if self.diff {
+            let diffs = diff_genesis(&sections[0], &sections[1]);
+            if self.json {
+                println!("{}", json!(diffs));
+            } else if diffs.values().all(SectionDiff::is_empty) {
+                println!("No difference in the balances, assets, trustees and staking params");
+            } else {
+                print_diffs(&diffs);
+            }
+        } else if self.json {
+            let result = self
+                .specs
+                .iter()
+                .zip(&sections)
+                .map(|(path, sections)| (path.display().to_string(), json!(sections)))
+                .collect::<serde_json::Map<_, _>>();
+            println!("{}", Value::Object(result));
+        } else {
+            for (path, sections) in self.specs.iter().zip(&sections) {
+                for (name, entries) in sections {
+                    println!("== {} {}: {} entries", path.display(), name, entries.len());
+                    for (key, value) in entries {
+                        println!("  {}: {}", key, value);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn print_diffs(diffs: &BTreeMap<&'static str, SectionDiff>) {
+    for (name, diff) in diffs.iter().filter(|(_, diff)| !diff.is_empty()) {
+        println!(
+            "== {}: {} added, {} removed, {} changed",
+            name,
+            diff.added.len(),
+            diff.removed.len(),
+            diff.changed.len()
+        );
+        for (key, value) in &diff.added {
+            println!("+ {}: {}", key, value);
+        }
+        for (key, value) in &diff.removed {
+            println!("- {}: {}", key, value);
+        }
+        for (key, (before, after)) in &diff.changed {
+            println!("~ {}: {} -> {}", key, before, after);
+        }
+    }
+}
+
+fn diff_genesis(before: &Sections, after: &Sections) -> BTreeMap<&'static str, SectionDiff> {
+    let empty = BTreeMap::new();
+    before
+        .keys()
+        .chain(after.keys())
+        .map(|name| {
+            let diff = SectionDiff::new(
+                before.get(name).unwrap_or(&empty),
+                after.get(name).unwrap_or(&empty),
+            );
+            (*name, diff)
+        })
+        .collect()
+}
+
+/// Returns the decoded genesis of the given plain or raw chain spec.
+fn inspect_genesis(mut spec: Value) -> Result<Sections, String> {
+    if let Some(top) = spec.pointer("/genesis/raw/top") {
+        return raw_sections(top);
+    }
+
+    let id = spec["id"]
+        .as_str()
+        .ok_or("Missing the chain id")?
+        .to_string();
+    let genesis = spec
+        .pointer_mut("/genesis/runtime")
+        .map(Value::take)
+        .ok_or("Missing the runtime genesis config")?;
+
+    // Same as the `IdentifyVariant` of the service.
+    Ok(if id == "chainx" {
+        plain_sections!(chainx_runtime, genesis)
+    } else if id.contains("malan") {
+        plain_sections!(malan_runtime, genesis)
+    } else if id == "dev" {
+        plain_sections!(dev_runtime, genesis)
+    } else {
+        return Err(format!("Unknown chain id `{}`", id));
+    })
+}
+
+/// The layout of `frame_system::AccountInfo<Index, pallet_balances::AccountData<Balance>>`.
+#[derive(Decode)]
+pub(crate) struct AccountInfo {
+    _nonce: Index,
+    _consumers: u32,
+    _providers: u32,
+    _sufficients: u32,
+    pub free: Balance,
+    pub reserved: Balance,
+    _misc_frozen: Balance,
+    _fee_frozen: Balance,
+}
+
+fn raw_sections(top: &Value) -> Result<Sections, String> {
+    let top = top
+        .as_object()
+        .ok_or("Invalid raw genesis storage")?
+        .iter()
+        .map(|(key, value)| {
+            let decode = |s: &str| hex::decode(s.trim_start_matches("0x"));
+            match (decode(key), value.as_str().map(decode)) {
+                (Ok(key), Some(Ok(value))) => Ok((key, value)),
+                _ => Err(format!("Invalid raw genesis storage entry {}", key)),
+            }
+        })
+        .collect::<Result<BTreeMap<_, _>, String>>()?;
+    let storage = RawStorage(top);
+
+    let mut balances = BTreeMap::new();
+    for (key, value) in storage.iter("System", "Account") {
+        let (who, _) = decode_key::<AccountId>(key, BLAKE2_128_LEN)?;
+        let info = decode_value::<AccountInfo>(value)?;
+        if info.free > 0 {
+            balances.insert(who.to_string(), info.free.to_string());
+        }
+    }
+
+    let mut assets = BTreeMap::new();
+    for (key, value) in storage.iter("XAssetsRegistrar", "AssetInfoOf") {
+        let (asset_id, _) = decode_key::<AssetId>(key, TWOX_64_LEN)?;
+        assets.insert(asset_key(asset_id), asset_info(&decode_value(value)?));
+    }
+    for (key, value) in storage.iter("XAssets", "AssetBalance") {
+        let (who, rest) = decode_key::<AccountId>(key, BLAKE2_128_LEN)?;
+        let (asset_id, _) = decode_key::<AssetId>(rest, TWOX_64_LEN)?;
+        let balances = decode_value::<BTreeMap<AssetType, Balance>>(value)?;
+        if let Some(value) = balances.get(&AssetType::Usable) {
+            assets.insert(asset_balance_key(asset_id, &who), value.to_string());
+        }
+    }
+
+    let mut trustees = BTreeMap::new();
+    for (key, value) in storage.iter("XGatewayCommon", "TrusteeInfoConfigOf") {
+        let (chain, _) = decode_key::<Chain>(key, TWOX_64_LEN)?;
+        trustees.insert(format!("{:?}", chain), trustee_info(&decode_value(value)?));
+    }
+    for (key, value) in storage.iter("XGatewayCommon", "TrusteeIntentionPropertiesOf") {
+        let (who, rest) = decode_key::<AccountId>(key, BLAKE2_128_LEN)?;
+        let (chain, _) = decode_key::<Chain>(rest, TWOX_64_LEN)?;
+        let props = decode_value::<GenericTrusteeIntentionProps<AccountId>>(value)?.0;
+        trustees.insert(
+            trustee_key(chain, &who),
+            trustee_keys(&props.hot_entity, &props.cold_entity),
+        );
+    }
+
+    let mut staking = BTreeMap::new();
+    for name in STAKING_PARAMS {
+        if let Some(value) = storage.get("XStaking", name) {
+            staking.insert(name.to_string(), decode_value::<u32>(value)?.to_string());
+        }
+    }
+
+    Ok(Sections::from([
+        ("balances", balances),
+        ("assets", assets),
+        ("trustees", trustees),
+        ("staking", staking),
+    ]))
+}
+
+/// Returns `twox_128(pallet) ++ twox_128(item)`.
+pub(crate) fn storage_prefix(pallet: &str, item: &str) -> Vec<u8> {
+    let mut prefix = twox_128(pallet.as_bytes()).to_vec();
+    prefix.extend_from_slice(&twox_128(item.as_bytes()));
+    prefix
+}
+
+struct RawStorage(BTreeMap<Vec<u8>, Vec<u8>>);
+
+impl RawStorage {
+    fn get(&self, pallet: &str, item: &str) -> Option<&[u8]> {
+        self.0.get(&storage_prefix(pallet, item)).map(Vec::as_slice)
+    }
+
+    /// Returns the entries of the storage map along with the key suffix after the prefix.
+    fn iter<'a>(&'a self, pallet: &str, item: &str) -> impl Iterator<Item = (&'a [u8], &'a [u8])> {
+        let prefix = storage_prefix(pallet, item);
+        self.0
+            .range(prefix.clone()..)
+            .take_while(move |(key, _)| key.starts_with(&prefix))
+            .map(|(key, value)| (&key[STORAGE_PREFIX_LEN..], value.as_slice()))
+    }
+}
+
+/// Decodes the key hashed by a `*Concat` hasher of `hash_len`, returns the rest of the key.
+pub(crate) fn decode_key<K: Decode>(key: &[u8], hash_len: usize) -> Result<(K, &[u8]), String> {
+    let mut input = key.get(hash_len..).unwrap_or_default();
+    let decoded = K::decode(&mut input).map_err(|e| {
+        format!(
+            "Failed to decode the storage key 0x{}: {}",
+            hex::encode(key),
+            e
+        )
+    })?;
+    Ok((decoded, input))
+}
+
+pub(crate) fn decode_value<V: Decode>(value: &[u8]) -> Result<V, String> {
+    V::decode(&mut &value[..]).map_err(|e| {
+        format!(
+            "Failed to decode the storage value 0x{}: {}",
+            hex::encode(value),
+            e
+        )
+    })
+}
+
+fn asset_key(asset_id: AssetId) -> String {
+    format!("#{}", asset_id)
+}
+
+fn asset_balance_key(asset_id: AssetId, who: &AccountId) -> String {
+    format!("#{}/{}", asset_id, who)
+}
+
+fn asset_info(info: &AssetInfo) -> String {
+    format!("{:?}", info)
+}
+
+fn trustee_key(chain: Chain, who: &AccountId) -> String {
+    format!("{:?}/{}", chain, who)
+}
+
+fn trustee_info(info: &TrusteeInfoConfig) -> String {
+    format!(
+        "trustee count in [{}, {}]",
+        info.min_trustee_count, info.max_trustee_count
+    )
+}
+
+fn trustee_keys(hot: &[u8], cold: &[u8]) -> String {
+    format!("hot 0x{}, cold 0x{}", hex::encode(hot), hex::encode(cold))
+}
+
+#[test]
+fn test_inspect_genesis() {
+    use sc_service::ChainSpec;
+
+    let spec = crate::chain_spec::local_testnet_config(None).unwrap();
+    let plain = serde_json::from_str::<Value>(&spec.as_json(false).unwrap()).unwrap();
+    let raw = serde_json::from_str::<Value>(&spec.as_json(true).unwrap()).unwrap();
+
+    let before = inspect_genesis(plain.clone()).unwrap();
+    assert!(!before["balances"].is_empty());
+    assert!(!before["trustees"].is_empty());
+
+    // The raw genesis is decoded the same as the plain one.
+    let decoded = inspect_genesis(raw).unwrap();
+    assert_eq!(decoded["staking"], before["staking"]);
+    assert!(before["balances"]
+        .iter()
+        .all(|(who, value)| decoded["balances"].get(who) == Some(value)));
+
+    let mut spec = plain;
+    spec["genesis"]["runtime"]["xStaking"]["validatorCount"] = json!(1000);
+    let balances = spec
+        .pointer_mut("/genesis/runtime/balances/balances")
+        .unwrap()
+        .as_array_mut()
+        .unwrap();
+    balances.remove(0);
+    let after = inspect_genesis(spec).unwrap();
+
+    let diffs = diff_genesis(&before, &after);
+    assert_eq!(
+        diffs["staking"].changed["ValidatorCount"].1,
+        "1000".to_string()
+    );
+    assert_eq!(diffs["balances"].removed.len(), 1);
+    assert!(diffs["assets"].is_empty());
+    assert!(diffs["trustees"].is_empty());
+}
@@ -5,12 +5,22 @@
 #![warn(missing_docs)]
 #![warn(unused_extern_crates)]
 
-mod chain_spec;
+mod btc_genesis;
+#[allow(missing_docs)]
+pub mod chain_spec;
+mod check_spec;
 mod cli;
 mod command;
 mod config;
+mod dev_network;
+mod export_genesis;
 mod genesis;
+mod genesis_builder_state;
+mod inspect_genesis;
 mod logger;
+mod revert_finality;
+mod storage_report;
+mod sync_spec;
 
 pub use sc_cli::Result;
 
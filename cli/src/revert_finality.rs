@@ -0,0 +1,218 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::sync::Arc;
+
+use sc_cli::{CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, UsageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_consensus_babe::{digests::ConsensusLog as BabeConsensusLog, BABE_ENGINE_ID};
+use sp_runtime::{
+    generic::{BlockId, OpaqueDigestItemId},
+    traits::{Block as BlockT, Header as HeaderT, NumberFor, Zero},
+};
+
+/// The `revert-finality-safe` command used to recover a node whose finality is stalled.
+///
+/// All the blocks after the latest finalized one are reverted, the finalized blocks are never
+/// touched. The GRANDPA voter set state is kept as is: it records the votes this node has cast,
+/// resetting it would let the voter vote again in a round it has voted in, i.e. equivocate.
+/// The voter resumes from its last round on restart and catches up with its peers from there.
+///
+/// Neither the GRANDPA authority set changes nor the BABE epoch changes are tracked by the
+/// block revert, they live in the consensus aux data. The revert is therefore refused if one of
+/// the reverted blocks signals such a change, use `chainx revert` on a stopped non-authority
+/// node and resync in that case.
+#[derive(Debug, Clone, clap::Parser)]
+#[clap(name = "revert-finality-safe")]
+pub struct RevertFinalitySafeCmd {
+    /// Only report the blocks to be reverted, nothing is changed.
+    #[clap(long)]
+    pub dry_run: bool,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+impl RevertFinalitySafeCmd {
+    /// Run the revert-finality-safe command.
+    pub async fn run<B, BA, C>(&self, client: Arc<C>, backend: Arc<BA>) -> sc_cli::Result<()>
+    where
+        B: BlockT,
+        BA: Backend<B>,
+        C: HeaderBackend<B> + UsageProvider<B>,
+    {
+        let info = client.info();
+        let unfinalized = info.best_number - info.finalized_number;
+        log::info!(
+            "Best block: #{} ({}), finalized block: #{} ({}), {} unfinalized block(s)",
+            info.best_number,
+            info.best_hash,
+            info.finalized_number,
+            info.finalized_hash,
+            unfinalized
+        );
+
+        ensure_revertable(&*client, info.best_hash, info.finalized_number)?;
+
+        if self.dry_run || unfinalized.is_zero() {
+            return Ok(());
+        }
+
+        sc_service::chain_ops::revert_chain(client, backend, unfinalized)?;
+        log::info!(
+            "Reverted {} block(s), restart the node to resume",
+            unfinalized
+        );
+
+        Ok(())
+    }
+}
+
+/// Ensures none of the blocks from `best` back to `finalized` (exclusive) signals a consensus
+/// change that the block revert leaves behind.
+fn ensure_revertable<B, C>(client: &C, best: B::Hash, finalized: NumberFor<B>) -> sc_cli::Result<()>
+where
+    B: BlockT,
+    C: HeaderBackend<B>,
+{
+    let mut hash = best;
+    loop {
+        let header = client
+            .header(BlockId::hash(hash))?
+            .ok_or_else(|| format!("Header of {} not found", hash))?;
+        if *header.number() <= finalized {
+            return Ok(());
+        }
+        if let Some(change) = unrevertable_change::<B>(&header) {
+            return Err(format!(
+                "Block #{} ({}) signals a {}, which can't be reverted",
+                header.number(),
+                hash,
+                change
+            )
+            .into());
+        }
+        hash = *header.parent_hash();
+    }
+}
+
+/// Returns the consensus change signaled by `header` that the block revert leaves behind.
+fn unrevertable_change<B: BlockT>(header: &B::Header) -> Option<&'static str> {
+    if sc_finality_grandpa::find_scheduled_change::<B>(header).is_some()
+        || sc_finality_grandpa::find_forced_change::<B>(header).is_some()
+    {
+        return Some("GRANDPA authority set change");
+    }
+    let babe_change = header.digest().logs().iter().any(|log| {
+        matches!(
+            log.try_to::<BabeConsensusLog>(OpaqueDigestItemId::Consensus(&BABE_ENGINE_ID)),
+            Some(BabeConsensusLog::NextEpochData(_) | BabeConsensusLog::NextConfigData(_))
+        )
+    });
+    if babe_change {
+        return Some("BABE epoch change");
+    }
+    None
+}
+
+impl CliConfiguration for RevertFinalitySafeCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use codec::Encode;
+    use sp_consensus_babe::digests::NextEpochDescriptor;
+    use sp_core::H256;
+    use sp_finality_grandpa::{
+        ConsensusLog as GrandpaConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID,
+    };
+    use sp_runtime::{
+        testing::{Block, ExtrinsicWrapper, Header},
+        Digest, DigestItem,
+    };
+
+    type TestBlock = Block<ExtrinsicWrapper<u64>>;
+
+    fn header_with(logs: Vec<DigestItem>) -> Header {
+        Header::new(
+            1,
+            H256::default(),
+            H256::default(),
+            H256::default(),
+            Digest { logs },
+        )
+    }
+
+    #[test]
+    fn plain_block_should_be_revertable() {
+        assert_eq!(unrevertable_change::<TestBlock>(&header_with(vec![])), None);
+        // The seals and the other engines are not consensus changes.
+        let logs = vec![
+            DigestItem::Seal(BABE_ENGINE_ID, vec![1]),
+            DigestItem::Consensus(*b"test", vec![1]),
+        ];
+        assert_eq!(unrevertable_change::<TestBlock>(&header_with(logs)), None);
+    }
+
+    #[test]
+    fn grandpa_authority_set_change_should_not_be_revertable() {
+        let change = ScheduledChange {
+            next_authorities: vec![],
+            delay: 0u64,
+        };
+        for log in [
+            GrandpaConsensusLog::ScheduledChange(change.clone()),
+            GrandpaConsensusLog::ForcedChange(0, change),
+        ] {
+            let header = header_with(vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, log.encode())]);
+            assert_eq!(
+                unrevertable_change::<TestBlock>(&header),
+                Some("GRANDPA authority set change")
+            );
+        }
+
+        // The pause and the resume don't change the authority set.
+        let log = GrandpaConsensusLog::<u64>::Pause(0);
+        let header = header_with(vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, log.encode())]);
+        assert_eq!(unrevertable_change::<TestBlock>(&header), None);
+    }
+
+    #[test]
+    fn babe_epoch_change_should_not_be_revertable() {
+        let log = BabeConsensusLog::NextEpochData(NextEpochDescriptor {
+            authorities: vec![],
+            randomness: [0; 32],
+        });
+        let header = header_with(vec![DigestItem::Consensus(BABE_ENGINE_ID, log.encode())]);
+        assert_eq!(
+            unrevertable_change::<TestBlock>(&header),
+            Some("BABE epoch change")
+        );
+
+        let log = BabeConsensusLog::OnDisabled(0);
+        let header = header_with(vec![DigestItem::Consensus(BABE_ENGINE_ID, log.encode())]);
+        assert_eq!(unrevertable_change::<TestBlock>(&header), None);
+    }
+}
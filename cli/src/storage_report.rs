@@ -0,0 +1,193 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+    fmt::Debug,
+    str::FromStr,
+    sync::Arc,
+};
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, NumberFor},
+};
+
+/// The length of the storage prefix of a pallet, i.e. `twox_128(pallet_prefix)`.
+const PALLET_PREFIX_LEN: usize = 16;
+
+/// The `storage-report` command used to report the storage size of each pallet.
+///
+/// The top trie of the state at the given block is walked key by key and grouped by the
+/// pallet prefix, the pallet names are resolved from the runtime metadata of that block.
+/// The keys without a known pallet prefix (e.g. `:code`) are reported by their raw prefix.
+/// The child tries are not walked.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct StorageReportCmd {
+    /// Block hash or number, the best block if unspecified.
+    #[clap(long, value_name = "HASH or NUMBER")]
+    pub at: Option<BlockNumberOrHash>,
+
+    /// The number of the largest keys reported for each pallet.
+    #[clap(long, value_name = "COUNT", default_value = "10")]
+    pub top: usize,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+/// The storage accounting of a pallet.
+#[derive(Default)]
+struct PalletStorage {
+    keys: u64,
+    key_bytes: u64,
+    value_bytes: u64,
+    /// The largest keys along with the size of key and value, the smallest on the top.
+    largest: BinaryHeap<Reverse<(usize, Vec<u8>)>>,
+}
+
+impl PalletStorage {
+    fn total_bytes(&self) -> u64 {
+        self.key_bytes + self.value_bytes
+    }
+
+    fn add(&mut self, key: Vec<u8>, value_len: usize, top: usize) {
+        let size = key.len() + value_len;
+        self.keys += 1;
+        self.key_bytes += key.len() as u64;
+        self.value_bytes += value_len as u64;
+        if top == 0 {
+            return;
+        }
+        if self.largest.len() < top {
+            self.largest.push(Reverse((size, key)));
+        } else if matches!(self.largest.peek(), Some(Reverse((min, _))) if *min < size) {
+            self.largest.pop();
+            self.largest.push(Reverse((size, key)));
+        }
+    }
+}
+
+impl StorageReportCmd {
+    /// Run the storage-report command.
+    pub async fn run<B, BA, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+    where
+        B: BlockT,
+        <B::Hash as FromStr>::Err: Debug,
+        <NumberFor<B> as FromStr>::Err: Debug,
+        BA: Backend<B>,
+        C: ProvideRuntimeApi<B> + HeaderBackend<B> + StorageProvider<B, BA> + Send + Sync,
+        C::Api: Metadata<B>,
+    {
+        let at = match &self.at {
+            Some(at) => at.parse::<B>()?,
+            None => BlockId::hash(client.info().best_hash),
+        };
+        let names = pallet_names(&*client, &at)?;
+
+        let mut pallets = BTreeMap::<Vec<u8>, PalletStorage>::new();
+        let keys = client
+            .storage_keys_iter(&at, None, None)
+            .map_err(|e| format!("Failed to iterate the storage at {}: {}", at, e))?;
+        for StorageKey(key) in keys {
+            let value_len = client
+                .storage(&at, &StorageKey(key.clone()))
+                .map_err(|e| format!("Failed to read the storage at {}: {}", at, e))?
+                .map_or(0, |value| value.0.len());
+            let prefix = key[..key.len().min(PALLET_PREFIX_LEN)].to_vec();
+            pallets
+                .entry(prefix)
+                .or_default()
+                .add(key, value_len, self.top);
+        }
+
+        let mut pallets = pallets.into_iter().collect::<Vec<_>>();
+        pallets.sort_by_key(|(_, storage)| Reverse(storage.total_bytes()));
+
+        let (total_keys, total_bytes) = pallets.iter().fold((0, 0), |(keys, bytes), (_, s)| {
+            (keys + s.keys, bytes + s.total_bytes())
+        });
+        println!(
+            "Storage at {}: {} keys, {} bytes",
+            at, total_keys, total_bytes
+        );
+
+        for (prefix, storage) in pallets {
+            let name = names
+                .get(&prefix)
+                .cloned()
+                .unwrap_or_else(|| format!("0x{}", hex::encode(&prefix)));
+            println!(
+                "\n{}: {} keys, {} bytes (keys {} bytes, values {} bytes)",
+                name,
+                storage.keys,
+                storage.total_bytes(),
+                storage.key_bytes,
+                storage.value_bytes
+            );
+            for Reverse((size, key)) in storage.largest.into_sorted_vec() {
+                println!("  {:>10} 0x{}", size, hex::encode(key));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the pallet names of the runtime at `at` by their storage prefix.
+fn pallet_names<B, C>(client: &C, at: &BlockId<B>) -> sc_cli::Result<BTreeMap<Vec<u8>, String>>
+where
+    B: BlockT,
+    C: ProvideRuntimeApi<B>,
+    C::Api: Metadata<B>,
+{
+    let metadata = client
+        .runtime_api()
+        .metadata(at)
+        .map_err(|e| format!("Failed to get the runtime metadata at {}: {}", at, e))?;
+    let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+        .map_err(|e| format!("Failed to decode the runtime metadata: {}", e))?;
+    let metadata = match metadata.1 {
+        RuntimeMetadata::V14(metadata) => metadata,
+        _ => return Err("Unsupported metadata version".into()),
+    };
+
+    Ok(metadata
+        .pallets
+        .into_iter()
+        .filter_map(|pallet| {
+            let storage = pallet.storage?;
+            Some((twox_128(storage.prefix.as_bytes()).to_vec(), pallet.name))
+        })
+        .collect())
+}
+
+impl CliConfiguration for StorageReportCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::{fs, io::Write, path::PathBuf, sync::Arc};
+
+use sc_cli::{CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::AuxStore;
+use sc_sync_state_rpc::{SyncStateRpcApi, SyncStateRpcHandler};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+
+/// The `export-sync-spec` command used to export a chain spec embedding the light sync state.
+///
+/// The light sync state (the latest finalized header, the BABE epoch changes and the GRANDPA
+/// authority set) is stored in the `light_sync_state` extension of the exported spec, from
+/// which a light client can start syncing instead of from the genesis.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportSyncSpecCmd {
+    /// Output file name or stdout if unspecified.
+    #[clap(short, long, parse(from_os_str))]
+    pub output: Option<PathBuf>,
+
+    /// Force raw genesis storage output.
+    #[clap(long)]
+    pub raw: bool,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+impl ExportSyncSpecCmd {
+    /// Run the export-sync-spec command.
+    pub async fn run<B, C>(
+        &self,
+        chain_spec: Box<dyn sc_cli::ChainSpec>,
+        client: Arc<C>,
+        shared_authority_set: sc_finality_grandpa::SharedAuthoritySet<B::Hash, NumberFor<B>>,
+        shared_epoch_changes: sc_consensus_epochs::SharedEpochChanges<B, sc_consensus_babe::Epoch>,
+    ) -> sc_cli::Result<()>
+    where
+        B: BlockT,
+        C: HeaderBackend<B> + AuxStore + Send + Sync + 'static,
+    {
+        let handler = SyncStateRpcHandler::new(
+            chain_spec,
+            client,
+            shared_authority_set,
+            shared_epoch_changes,
+        )
+        .map_err(|e| format!("Failed to build the light sync state: {}", e))?;
+        let spec = handler
+            .system_gen_sync_spec(self.raw)
+            .map_err(|e| format!("Failed to generate the sync spec: {}", e.message))?;
+        let json = serde_json::to_string_pretty(&spec)
+            .map_err(|e| format!("Failed to serialize the sync spec: {}", e))?;
+
+        if let Some(path) = &self.output {
+            fs::write(path, json)?;
+        } else {
+            std::io::stdout().write_all(json.as_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CliConfiguration for ExportSyncSpecCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity ^0.8.0;
+
+// The PCX are staked by the substrate account derived from the calling contract,
+// so the functions are internal and call the precompile from the contract itself.
+library SystemStaking {
+    address constant private precompile = address(0x404);
+
+    function bond(bytes32 validator, uint256 value) internal returns (bool) {
+        return _call(abi.encodePacked(uint8(0), validator, value));
+    }
+
+    function unbond(bytes32 validator, uint256 value) internal returns (bool) {
+        return _call(abi.encodePacked(uint8(1), validator, value));
+    }
+
+    function rebond(bytes32 from, bytes32 to, uint256 value) internal returns (bool) {
+        return _call(abi.encodePacked(uint8(2), from, to, value));
+    }
+
+    function unlockUnbondedWithdrawal(bytes32 validator, uint256 unbondedIndex) internal returns (bool) {
+        return _call(abi.encodePacked(uint8(3), validator, unbondedIndex));
+    }
+
+    function claim(bytes32 validator) internal returns (bool) {
+        return _call(abi.encodePacked(uint8(4), validator));
+    }
+
+    function nomination(
+        address nominator,
+        bytes32 validator
+    ) internal view returns (uint256 nominated, uint256 unbonded, uint256 unbondedChunks) {
+        (bool success, bytes memory returnData) = precompile.staticcall(abi.encodePacked(uint8(5), nominator, validator));
+
+        require(success, string(returnData));
+
+        return abi.decode(returnData, (uint256, uint256, uint256));
+    }
+
+    function _call(bytes memory input) private returns (bool) {
+        (bool success, bytes memory returnData) = precompile.call(input);
+
+        require(success, string(returnData));
+
+        return success;
+    }
+}
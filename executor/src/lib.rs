@@ -1,5 +1,15 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
+//! Native executors of the ChainX runtimes.
+//!
+//! The sr25519 and ed25519 signatures of the extrinsics are verified in batch during the block
+//! import: `Executive::execute_block` starts the signature batching of the runtime and the
+//! `verify` host functions of `sp_io` defer the checks to the background tasks of the
+//! `TaskExecutorExt`, which the state machine registers for every runtime call. The ecdsa
+//! signatures of the extrinsics and the ecdsa public key recovery used by the gateways (e.g.
+//! `claim_account` of the assets bridge) can not be batched, they are recovered one by one.
+//! Both paths are covered by the `signature_batching` tests of `chainx-runtime-upgrade-tests`.
+
 pub use sc_executor::NativeElseWasmExecutor;
 
 pub struct ChainXExecutor;
@@ -0,0 +1,175 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Validation of the addresses of the chains supported by ChainX, i.e., the SS58 addresses
+//! of ChainX and the base58 and bech32/bech32m addresses of Bitcoin.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
+
+pub use xp_assets_registrar::Chain;
+
+/// The prefix of the SS58 checksum preimage.
+const SS58_PREFIX: &[u8] = b"SS58PRE";
+/// The length of the SS58 checksum.
+const SS58_CHECKSUM_LEN: usize = 2;
+/// The length of the account id encoded in a SS58 address.
+const SS58_ACCOUNT_LEN: usize = 32;
+
+/// The encoding of a valid address.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum AddressFormat {
+    /// SS58 address with the network prefix.
+    Ss58(u16),
+    /// Base58 Bitcoin address, i.e., P2PKH or P2SH.
+    Base58,
+    /// Bech32 Bitcoin address of the witness version 0, i.e., P2WPKH or P2WSH.
+    Bech32,
+    /// Bech32m Bitcoin address of the witness version 1, i.e., P2TR.
+    Bech32m,
+}
+
+/// The reason why an address is invalid.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum AddressError {
+    /// The addresses of the chain can not be verified.
+    UnsupportedChain,
+    /// The address can not be decoded.
+    InvalidEncoding,
+    /// The checksum of the address mismatches.
+    InvalidChecksum,
+    /// The address belongs to another network, e.g., a testnet address on the mainnet.
+    NetworkMismatch,
+    /// The address is a trustee address, which can not receive the withdrawals.
+    TrusteeAddress,
+}
+
+/// The verification result of an address.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AddressVerification {
+    pub valid: bool,
+    /// The encoding of the address if it's valid.
+    pub format: Option<AddressFormat>,
+    /// The reason if the address is invalid.
+    pub error: Option<AddressError>,
+}
+
+impl From<Result<AddressFormat, AddressError>> for AddressVerification {
+    fn from(result: Result<AddressFormat, AddressError>) -> Self {
+        match result {
+            Ok(format) => Self {
+                valid: true,
+                format: Some(format),
+                error: None,
+            },
+            Err(error) => Self {
+                valid: false,
+                format: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Decodes the SS58 `address`, returns the network prefix and the account id.
+pub fn decode_ss58(address: &[u8]) -> Result<(u16, [u8; 32]), AddressError> {
+    let data = bs58::decode(address)
+        .into_vec()
+        .map_err(|_| AddressError::InvalidEncoding)?;
+    let (prefix_len, prefix) = match data.first() {
+        Some(&first) if first < 64 => (1, u16::from(first)),
+        Some(&first) if first < 128 => {
+            let second = *data.get(1).ok_or(AddressError::InvalidEncoding)?;
+            // The 14 bits of the prefix are split across the two bytes.
+            let lower = (first << 2) | (second >> 6);
+            let upper = second & 0b0011_1111;
+            (2, u16::from(lower) | (u16::from(upper) << 8))
+        }
+        _ => return Err(AddressError::InvalidEncoding),
+    };
+    if data.len() != prefix_len + SS58_ACCOUNT_LEN + SS58_CHECKSUM_LEN {
+        return Err(AddressError::InvalidEncoding);
+    }
+
+    let (body, checksum) = data.split_at(prefix_len + SS58_ACCOUNT_LEN);
+    let hash = sp_io::hashing::blake2_512(&[SS58_PREFIX, body].concat());
+    if checksum != &hash[..SS58_CHECKSUM_LEN] {
+        return Err(AddressError::InvalidChecksum);
+    }
+
+    let mut account = [0u8; SS58_ACCOUNT_LEN];
+    account.copy_from_slice(&body[prefix_len..]);
+    Ok((prefix, account))
+}
+
+/// Verifies the SS58 `address` belongs to the network of `expected_prefix`.
+pub fn verify_ss58_address(
+    address: &[u8],
+    expected_prefix: u16,
+) -> Result<AddressFormat, AddressError> {
+    let (prefix, _) = decode_ss58(address)?;
+    if prefix != expected_prefix {
+        return Err(AddressError::NetworkMismatch);
+    }
+    Ok(AddressFormat::Ss58(prefix))
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait AddressApi {
+        /// Verifies `address` of `chain` according to the network of ChainX, the Bitcoin
+        /// addresses are verified the same as the withdrawal addresses.
+        fn verify_address(chain: Chain, address: Vec<u8>) -> Result<AddressFormat, AddressError>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_ss58_address() {
+        // Alice of the generic substrate format and the ChainX format.
+        let alice_42 = b"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let alice_44 = b"5USGSZK3raH3LD4uxvNTa23HN5VULnYrkXonRktyizTJUYg9";
+
+        assert_eq!(
+            verify_ss58_address(alice_42, 42),
+            Ok(AddressFormat::Ss58(42))
+        );
+        assert_eq!(
+            verify_ss58_address(alice_44, 44),
+            Ok(AddressFormat::Ss58(44))
+        );
+        assert_eq!(
+            decode_ss58(alice_42).unwrap().1,
+            decode_ss58(alice_44).unwrap().1
+        );
+        assert_eq!(
+            verify_ss58_address(alice_42, 44),
+            Err(AddressError::NetworkMismatch)
+        );
+
+        let mut corrupted = alice_42.to_vec();
+        corrupted[10] = b'b';
+        assert_eq!(
+            verify_ss58_address(&corrupted, 42),
+            Err(AddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            verify_ss58_address(b"0x1234", 42),
+            Err(AddressError::InvalidEncoding)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Call traces of the EVM transactions.
+//!
+//! A block is traced by executing its extrinsics again on top of the parent state with the
+//! [`CallTracer`] listening to the events of the EVM. The events are only emitted by the
+//! runtime built with the `evm-tracing` feature, which is not the one deployed on chain: the
+//! node executes the tracing runtime of the same spec version via `--wasm-runtime-overrides`,
+//! the runtime without the feature returns an error instead.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+#[cfg(feature = "evm-tracing")]
+mod tracer;
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_std::prelude::Vec;
+
+#[cfg(feature = "evm-tracing")]
+pub use self::tracer::CallTracer;
+
+/// The kind of a call frame.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CallType {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+    Create,
+    Create2,
+    SelfDestruct,
+}
+
+/// A call frame of an EVM transaction along with the frames it entered.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CallTrace {
+    pub call_type: CallType,
+    /// The caller, or the contract executing the code of `to` for `DelegateCall` and
+    /// `CallCode`.
+    pub from: H160,
+    /// The callee, the created contract or the beneficiary of `SelfDestruct`.
+    pub to: H160,
+    pub value: U256,
+    pub gas: u64,
+    /// The gas used by this frame and the frames it entered, including the intrinsic gas of
+    /// the transaction for the top frame.
+    pub gas_used: u64,
+    /// The call data, or the init code for `Create` and `Create2`.
+    pub input: Vec<u8>,
+    /// The return data, or the deployed code for `Create` and `Create2`.
+    pub output: Vec<u8>,
+    /// The reason of the failure in the wording of geth, e.g. `execution reverted`.
+    pub error: Option<Vec<u8>>,
+    pub calls: Vec<CallTrace>,
+}
+
+/// The call trace of an Ethereum transaction of a block.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TransactionTrace {
+    pub transaction_hash: H256,
+    /// The index of the transaction among the Ethereum transactions of the block.
+    pub transaction_index: u32,
+    pub trace: CallTrace,
+}
+
+sp_api::decl_runtime_apis! {
+    /// The API to trace the EVM transactions, only implemented by the tracing runtime.
+    ///
+    /// The block must be initialized by `Core_initialize_block` before tracing its extrinsics.
+    pub trait EvmTracingApi {
+        /// Applies `extrinsics` until the Ethereum transaction `transaction_hash` and returns
+        /// its call trace, `None` if the transaction is not in `extrinsics`.
+        fn trace_transaction(
+            extrinsics: Vec<Block::Extrinsic>,
+            transaction_hash: H256,
+        ) -> Result<Option<CallTrace>, DispatchError>;
+
+        /// Applies all `extrinsics` and returns the call traces of the Ethereum transactions.
+        fn trace_block(
+            extrinsics: Vec<Block::Extrinsic>,
+        ) -> Result<Vec<TransactionTrace>, DispatchError>;
+
+        /// Executes the EVM call on top of the state the API is called at without applying it
+        /// and returns its call trace, the block is not initialized.
+        fn trace_call(
+            from: H160,
+            to: H160,
+            data: Vec<u8>,
+            value: U256,
+            gas_limit: U256,
+            max_fee_per_gas: Option<U256>,
+            max_priority_fee_per_gas: Option<U256>,
+            nonce: Option<U256>,
+            access_list: Vec<(H160, Vec<H256>)>,
+        ) -> Result<CallTrace, DispatchError>;
+    }
+}
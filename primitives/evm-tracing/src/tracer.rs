@@ -0,0 +1,200 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_std::{cell::RefCell, prelude::*, rc::Rc};
+
+use evm::{
+    tracing::{Event, EventListener},
+    CreateScheme, ExitError, ExitReason,
+};
+use evm_gasometer::tracing::{Event as GasometerEvent, EventListener as GasometerListener};
+
+use crate::{CallTrace, CallType};
+
+/// Builds the call tree of an EVM transaction from the events of the EVM.
+#[derive(Default)]
+pub struct CallTracer {
+    /// The frames entered but not exited yet, the innermost on the top.
+    stack: Vec<CallTrace>,
+    /// The intrinsic gas of the transaction, charged before the top frame is entered.
+    transaction_cost: u64,
+    /// The top frame once exited.
+    trace: Option<CallTrace>,
+}
+
+impl CallTracer {
+    /// Runs `f` with the tracer listening and returns the call trace of the EVM transaction
+    /// executed by `f`, if any.
+    pub fn trace<R, F: FnOnce() -> R>(f: F) -> (R, Option<CallTrace>) {
+        let tracer = Rc::new(RefCell::new(Self::default()));
+        let mut evm_listener = ListenerProxy(Rc::clone(&tracer));
+        let mut gasometer_listener = ListenerProxy(Rc::clone(&tracer));
+
+        let result = evm::tracing::using(&mut evm_listener, || {
+            evm_gasometer::tracing::using(&mut gasometer_listener, f)
+        });
+
+        let trace = tracer.borrow_mut().trace.take();
+        (result, trace)
+    }
+
+    fn enter(&mut self, frame: CallTrace) {
+        self.stack.push(frame);
+    }
+
+    fn exit(&mut self, reason: &ExitReason, return_value: &[u8]) {
+        let mut frame = match self.stack.pop() {
+            Some(frame) => frame,
+            None => return,
+        };
+        frame.output = return_value.to_vec();
+        frame.error = exit_error(reason).map(|error| error.to_vec());
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => {
+                frame.gas_used = frame.gas_used.saturating_add(self.transaction_cost);
+                self.trace = Some(frame);
+            }
+        }
+    }
+}
+
+/// Returns the reason of the failure in the wording of geth.
+fn exit_error(reason: &ExitReason) -> Option<&'static [u8]> {
+    match reason {
+        ExitReason::Succeed(_) => None,
+        ExitReason::Revert(_) => Some(b"execution reverted"),
+        ExitReason::Error(error) => Some(match error {
+            ExitError::OutOfGas => b"out of gas",
+            ExitError::OutOfFund => b"insufficient balance for transfer",
+            ExitError::CallTooDeep => b"max call depth exceeded",
+            ExitError::StackUnderflow => b"stack underflow",
+            ExitError::StackOverflow => b"stack limit reached",
+            ExitError::InvalidJump => b"invalid jump destination",
+            ExitError::CreateCollision => b"contract address collision",
+            ExitError::CreateContractLimit => b"max code size exceeded",
+            _ => b"execution error",
+        }),
+        ExitReason::Fatal(_) => Some(b"fatal error"),
+    }
+}
+
+/// Forwards the events of the different EVM components to the shared tracer.
+struct ListenerProxy(Rc<RefCell<CallTracer>>);
+
+impl EventListener for ListenerProxy {
+    fn event(&mut self, event: Event) {
+        let mut tracer = self.0.borrow_mut();
+        match event {
+            Event::Call {
+                code_address,
+                transfer,
+                input,
+                target_gas,
+                is_static,
+                context,
+            } => {
+                let call_type = if is_static {
+                    CallType::StaticCall
+                } else if context.address != code_address {
+                    if transfer.is_some() {
+                        CallType::CallCode
+                    } else {
+                        CallType::DelegateCall
+                    }
+                } else {
+                    CallType::Call
+                };
+                let from = match call_type {
+                    CallType::CallCode | CallType::DelegateCall => context.address,
+                    _ => context.caller,
+                };
+                tracer.enter(CallTrace {
+                    call_type,
+                    from,
+                    to: code_address,
+                    value: transfer
+                        .as_ref()
+                        .map(|transfer| transfer.value)
+                        .unwrap_or_default(),
+                    gas: target_gas.unwrap_or_default(),
+                    gas_used: 0,
+                    input: input.to_vec(),
+                    output: Vec::new(),
+                    error: None,
+                    calls: Vec::new(),
+                });
+            }
+            Event::Create {
+                caller,
+                address,
+                scheme,
+                value,
+                init_code,
+                target_gas,
+            } => {
+                let call_type = match scheme {
+                    CreateScheme::Create2 { .. } => CallType::Create2,
+                    _ => CallType::Create,
+                };
+                tracer.enter(CallTrace {
+                    call_type,
+                    from: caller,
+                    to: address,
+                    value,
+                    gas: target_gas.unwrap_or_default(),
+                    gas_used: 0,
+                    input: init_code.to_vec(),
+                    output: Vec::new(),
+                    error: None,
+                    calls: Vec::new(),
+                });
+            }
+            Event::Suicide {
+                address,
+                target,
+                balance,
+            } => {
+                if let Some(frame) = tracer.stack.last_mut() {
+                    frame.calls.push(CallTrace {
+                        call_type: CallType::SelfDestruct,
+                        from: address,
+                        to: target,
+                        value: balance,
+                        gas: 0,
+                        gas_used: 0,
+                        input: Vec::new(),
+                        output: Vec::new(),
+                        error: None,
+                        calls: Vec::new(),
+                    });
+                }
+            }
+            Event::Exit {
+                reason,
+                return_value,
+            } => tracer.exit(reason, return_value),
+            // The top frame is entered by `Call` or `Create` as well.
+            _ => {}
+        }
+    }
+}
+
+impl GasometerListener for ListenerProxy {
+    fn event(&mut self, event: GasometerEvent) {
+        let mut tracer = self.0.borrow_mut();
+        let snapshot = match event {
+            GasometerEvent::RecordTransaction { cost, .. } => {
+                tracer.transaction_cost = cost;
+                return;
+            }
+            GasometerEvent::RecordCost { snapshot, .. }
+            | GasometerEvent::RecordRefund { snapshot, .. }
+            | GasometerEvent::RecordStipend { snapshot, .. }
+            | GasometerEvent::RecordDynamicCost { snapshot, .. } => snapshot,
+        };
+        // Each frame has its own gasometer, the events are emitted by the innermost one.
+        if let (Some(frame), Some(snapshot)) = (tracer.stack.last_mut(), snapshot) {
+            frame.gas_used = snapshot.used_gas;
+        }
+    }
+}
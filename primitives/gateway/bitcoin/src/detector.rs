@@ -218,7 +218,7 @@ impl BtcTxTypeDetector {
             if let Some(dest_addr) = extract_output_addr(output, self.network) {
                 // check if the script address of the output is the hot trustee address
                 if dest_addr.hash == hot_addr.hash && output.value > 0 {
-                    deposit_value += output.value;
+                    deposit_value = deposit_value.saturating_add(output.value);
                 }
             }
         }
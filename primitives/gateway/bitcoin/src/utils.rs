@@ -6,6 +6,8 @@ use sp_std::{cmp::Ordering, prelude::Vec};
 use light_bitcoin::{
     chain::{Transaction, TransactionOutput},
     keys::{Address, Network},
+    merkle::PartialMerkleTree,
+    primitives::H256,
     script::{Opcode, Script, ScriptType},
 };
 
@@ -67,6 +69,85 @@ pub fn is_trustee_addr(addr: Address, trustee_pair: (Address, Address)) -> bool
     addr.hash == hot_addr.hash || addr.hash == cold_addr.hash
 }
 
+/// Check if the transaction `tx_hash` is proved by the partial merkle tree `proof` to be
+/// included in the block whose merkle root is `merkle_root`.
+pub fn verify_merkle_proof(proof: &PartialMerkleTree, merkle_root: H256, tx_hash: H256) -> bool {
+    let mut matches = Vec::new();
+    let mut _indexes = Vec::new();
+    let hash = match proof.extract_matches(&mut matches, &mut _indexes) {
+        Ok(hash) => hash,
+        Err(err) => {
+            error!(
+                "[verify_merkle_proof] Invalid merkle tree proof, err:{:?}",
+                err
+            );
+            return false;
+        }
+    };
+    if merkle_root != hash {
+        error!(
+            "[verify_merkle_proof] Check merkle tree proof error, merkle_root:{:?}, hash:{:?}",
+            merkle_root, hash
+        );
+        return false;
+    }
+    if !matches.iter().any(|h| *h == tx_hash) {
+        error!("[verify_merkle_proof] Tx hash should in matches of partial merkle tree");
+        return false;
+    }
+    true
+}
+
+/// Check if the outputs of the withdrawal transaction pay exactly the withdrawal `applications`.
+///
+/// The change to the `hot_addr` is skipped, and every other output is expected to be the
+/// applied balance minus the `withdrawal_fee`. Returns an error if the address of any output
+/// can't be extracted.
+pub fn match_withdrawal_outputs(
+    tx: &Transaction,
+    mut applications: Vec<(Address, u64)>,
+    hot_addr: &Address,
+    network: Network,
+    withdrawal_fee: u64,
+) -> Result<bool, &'static str> {
+    let mut outputs = Vec::new();
+    for output in &tx.outputs {
+        let addr = extract_output_addr(output, network).ok_or("not found addr in this out")?;
+        if addr.hash != hot_addr.hash {
+            // expect change to trustee_addr output
+            outputs.push((addr, output.value.saturating_add(withdrawal_fee)));
+        }
+    }
+
+    outputs.sort();
+    applications.sort();
+
+    if applications.len() != outputs.len() {
+        error!(
+            "[match_withdrawal_outputs] Withdrawal tx's outputs (len:{}) != withdrawal application list (len:{}), \
+            withdrawal tx's outputs:{:?}, withdrawal application list:{:?}",
+            outputs.len(),
+            applications.len(),
+            outputs,
+            applications
+        );
+        return Ok(false);
+    }
+
+    Ok(applications.iter().zip(outputs).all(|(a, b)| {
+        if a.0.hash == b.0.hash && a.1 == b.1 {
+            true
+        } else {
+            error!(
+                "[match_withdrawal_outputs] Withdrawal tx's output not match to withdrawal application. \
+                withdrawal application:{:?}, tx withdrawal output:{:?}",
+                a, b
+            );
+            false
+        }
+    }))
+}
+
 /// Extract the opreturn data from btc null data script.
 /// OP_RETURN format:
 /// - op_return + op_push(<0x4c) + data (op_push == data.len())
@@ -156,3 +237,34 @@ fn test_extract_opreturn_data() {
         b"5QZYGVVUPsp7cbqGUcHsRJUZrnmTuEyh6SLH6jdpfsFxgpRK@Laocius".to_vec()
     );
 }
+
+#[test]
+fn test_match_withdrawal_outputs() {
+    use core::str::FromStr;
+
+    // tx: 0200000001776ae4d3... (the second output is an opreturn, drop it)
+    let mut tx = Transaction::from_str("0200000001776ae4d3fbebbd8568c610b265f54a1a8e1f03f2a16cac99ca9490e32583313b000000006a473044022074edd3b4f333ba3b0edb685922420bf904d417cd24584dbe76ad2e9b9c54e37602202a4027f77b7a4f6aaa7a8e7423e0b4740531e7a97527d51f341f75a950480b7f012102ebaf854b6220e3d44a32373aabbe1b6e4c3f824a7855aeac65b6854cd84d6f87ffffffff02a0bb0d00000000001976a9146ffd34b262b5099b80f8e84fe7e5dccaa79e2e7a88ac0000000000000000536a4c50999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999900000000").unwrap();
+    assert_eq!(
+        match_withdrawal_outputs(&tx, vec![], &Address::default(), Network::Mainnet, 0),
+        Err("not found addr in this out")
+    );
+    tx.outputs.truncate(1);
+
+    let addr = extract_output_addr(&tx.outputs[0], Network::Mainnet).unwrap();
+    let hot_addr = Address::default();
+    let fee = 1_000;
+    let check = |tx: &Transaction, applications| {
+        match_withdrawal_outputs(tx, applications, &hot_addr, Network::Mainnet, fee)
+    };
+    assert_eq!(check(&tx, vec![(addr, 900_000 + fee)]), Ok(true));
+    assert_eq!(check(&tx, vec![(addr, 900_000)]), Ok(false));
+    assert_eq!(check(&tx, vec![]), Ok(false));
+    assert_eq!(
+        match_withdrawal_outputs(&tx, vec![], &addr, Network::Mainnet, fee),
+        Ok(true)
+    );
+
+    // regression of fuzzing: the output value plus the withdrawal fee overflowed.
+    tx.outputs[0].value = u64::MAX;
+    assert_eq!(check(&tx, vec![(addr, u64::MAX)]), Ok(true));
+}
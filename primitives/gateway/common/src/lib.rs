@@ -184,3 +184,22 @@ pub fn from_ss58_check(raw_account: &[u8]) -> Option<AccountId32> {
         Some(res.into())
     }
 }
+
+/// Encode the raw account as a SS58Check address with the given address format, e.g. the
+/// OP_RETURN data of a bitcoin deposit, same as `sp_core::crypto::Ss58Codec::to_ss58check`.
+pub fn to_ss58_check(raw_account: &[u8], format: u16) -> Vec<u8> {
+    // https://github.com/paritytech/substrate/blob/polkadot-v0.9.18/primitives/core/src/crypto.rs#L320
+    let ident = format & 0b0011_1111_1111_1111;
+    let mut data = Vec::with_capacity(raw_account.len() + 4);
+    if ident < 64 {
+        data.push(ident as u8);
+    } else {
+        let first = ((ident & 0b0000_0000_1111_1100) as u8) >> 2;
+        let second = ((ident >> 8) as u8) | ((ident & 0b0000_0000_0000_0011) as u8) << 6;
+        data.extend_from_slice(&[first | 0b0100_0000, second]);
+    }
+    data.extend_from_slice(raw_account);
+    let hash = sp_core::hashing::blake2_512(&[&b"SS58PRE"[..], &data].concat());
+    data.extend_from_slice(&hash[0..2]);
+    bs58::encode(data).into_vec()
+}
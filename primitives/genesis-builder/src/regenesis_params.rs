@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use chainx_primitives::{AssetId, BlockNumber};
+use xp_assets_registrar::Chain;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FreeBalanceInfo<AccountId, Balance> {
     pub free: Balance,
@@ -32,11 +35,53 @@ pub struct XStakingParams<AccountId, Balance> {
     pub nominators: Vec<NominatorInfo<AccountId, Balance>>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferralBind<AccountId> {
+    pub who: AccountId,
+    pub chain: Chain,
+    pub referral: AccountId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetRestriction {
+    pub asset_id: AssetId,
+    /// The bits of `xpallet_assets::AssetRestrictions`.
+    pub restrictions: u32,
+}
+
+/// A vesting schedule locking the PCX balance of `who`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VestingInfo<AccountId, Balance> {
+    pub who: AccountId,
+    /// The amount locked at `starting_block`.
+    pub locked: Balance,
+    /// The amount unlocked every block since `starting_block`.
+    pub per_block: Balance,
+    pub starting_block: BlockNumber,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VestingParams<AccountId, Balance> {
+    pub schedules: Vec<VestingInfo<AccountId, Balance>>,
+}
+
+impl<AccountId, Balance> Default for VestingParams<AccountId, Balance> {
+    fn default() -> Self {
+        VestingParams { schedules: vec![] }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AllParams<AccountId, Balance, AssetBalanceOf, StakingBalanceOf> {
     pub balances: Vec<FreeBalanceInfo<AccountId, Balance>>,
     pub xassets: Vec<FreeBalanceInfo<AccountId, AssetBalanceOf>>,
     pub xstaking: XStakingParams<AccountId, StakingBalanceOf>,
+    #[serde(default)]
+    pub referral_binds: Vec<ReferralBind<AccountId>>,
+    #[serde(default)]
+    pub asset_restrictions: Vec<AssetRestriction>,
+    #[serde(default)]
+    pub vesting: VestingParams<AccountId, Balance>,
 }
 
 impl<AccountId, Balance, AssetBalanceOf, StakingBalanceOf> Default
@@ -50,6 +95,9 @@ impl<AccountId, Balance, AssetBalanceOf, StakingBalanceOf> Default
                 validators: vec![],
                 nominators: vec![],
             },
+            referral_binds: vec![],
+            asset_restrictions: vec![],
+            vesting: Default::default(),
         }
     }
 }
@@ -0,0 +1,95 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Overview of the on-chain governance, i.e., the referenda of the democracy, the motions
+//! of the council and the technical committee and the treasury proposals.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
+
+/// Approval threshold of a referendum.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ReferendumThreshold {
+    /// A supermajority of approvals is needed to pass.
+    SuperMajorityApprove,
+    /// A supermajority of rejects is needed to fail.
+    SuperMajorityAgainst,
+    /// A simple majority of approvals is needed to pass.
+    SimpleMajority,
+}
+
+/// An ongoing referendum with its tally.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReferendumOverview<BlockNumber, Hash, Balance> {
+    pub index: u32,
+    /// When voting on this referendum will end.
+    pub end: BlockNumber,
+    pub proposal_hash: Hash,
+    pub threshold: ReferendumThreshold,
+    /// The delay (in blocks) to wait after a successful referendum before deploying.
+    pub delay: BlockNumber,
+    pub ayes: Balance,
+    pub nays: Balance,
+    pub turnout: Balance,
+}
+
+/// An open motion of a collective with its votes.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MotionOverview<AccountId, BlockNumber, Hash> {
+    pub hash: Hash,
+    pub index: u32,
+    /// The number of approval votes that are needed to pass the motion.
+    pub threshold: u32,
+    pub ayes: Vec<AccountId>,
+    pub nays: Vec<AccountId>,
+    /// The hard end time of this vote.
+    pub end: BlockNumber,
+}
+
+/// A pending treasury proposal.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TreasuryProposalOverview<AccountId, Balance> {
+    pub index: u32,
+    pub proposer: AccountId,
+    pub value: Balance,
+    pub beneficiary: AccountId,
+    pub bond: Balance,
+    /// Whether the proposal has been approved and is waiting for the next spend period.
+    pub approved: bool,
+}
+
+/// Governance state of the chain at some block.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct GovernanceOverview<AccountId, Balance, BlockNumber, Hash> {
+    pub referenda: Vec<ReferendumOverview<BlockNumber, Hash, Balance>>,
+    pub council_motions: Vec<MotionOverview<AccountId, BlockNumber, Hash>>,
+    pub technical_motions: Vec<MotionOverview<AccountId, BlockNumber, Hash>>,
+    pub treasury_proposals: Vec<TreasuryProposalOverview<AccountId, Balance>>,
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait GovernanceApi<AccountId, Balance, BlockNumber, Hash>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+    {
+        fn governance_overview() -> GovernanceOverview<AccountId, Balance, BlockNumber, Hash>;
+    }
+}
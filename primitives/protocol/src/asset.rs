@@ -31,6 +31,7 @@ use chainx_primitives::{AssetId, Decimals};
 //      thus we agree on the prefix:
 //      L_: use 0x90000000
 //      S_: use 0xa0000000
+//      LP_: use 0xb0000000, the LP tokens of the pools in xpallet_dex_swap
 
 /// Native asset of ChainX.
 pub const PCX: AssetId = 0;
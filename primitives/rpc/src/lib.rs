@@ -167,6 +167,32 @@ pub mod serde_text {
     }
 }
 
+/// Text serialization/deserialization of the optional bytes, `None` is `null`
+pub mod serde_option_text {
+    use super::*;
+
+    /// A serializer that encodes the bytes as a string
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+        T: AsRef<[u8]>,
+    {
+        match value {
+            Some(value) => serde_text::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// A deserializer that decodes the string to the bytes (Vec<u8>)
+    pub fn deserialize<'de, D>(deserializer: D) -> StdResult<Option<Vec<u8>>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let data = Option::<String>::deserialize(deserializer)?;
+        Ok(data.map(String::into_bytes))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
@@ -208,4 +234,22 @@ mod tests {
         let de = serde_json::from_str::<TextTest>(&ser).unwrap();
         assert_eq!(de, test);
     }
+
+    #[test]
+    fn test_serde_option_text_attr() {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct TextTest(#[serde(with = "super::serde_option_text")] Option<Vec<u8>>);
+
+        let test = TextTest(Some(b"0123456789".to_vec()));
+        let ser = serde_json::to_string(&test).unwrap();
+        assert_eq!(ser, "\"0123456789\"");
+        let de = serde_json::from_str::<TextTest>(&ser).unwrap();
+        assert_eq!(de, test);
+
+        let test = TextTest(None);
+        let ser = serde_json::to_string(&test).unwrap();
+        assert_eq!(ser, "null");
+        let de = serde_json::from_str::<TextTest>(&ser).unwrap();
+        assert_eq!(de, test);
+    }
 }
@@ -0,0 +1,407 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Access control and rate limits of the RPC methods per listening interface.
+//!
+//! `--rpc-methods` only tells the safe methods from the unsafe ones for all the interfaces at
+//! once, the public RPC nodes have no way to throttle the expensive methods, e.g. the scans of
+//! `state_getStorage`. The ACL file given by `--rpc-methods-acl` classifies the methods and
+//! configures each interface, i.e. `http` and `ws`, separately:
+//!
+//! ```json
+//! {
+//!   "unsafe": ["debug_*", "trace_*"],
+//!   "gatewayInternal": ["xgatewaybitcoin_broadcast*"],
+//!   "interfaces": {
+//!     "http": {
+//!       "allow": ["safe"],
+//!       "rateLimits": { "state_getStorage": 50, "state_*": 200 }
+//!     },
+//!     "ws": { "allow": ["safe", "unsafe", "gateway-internal"] }
+//!   }
+//! }
+//! ```
+//!
+//! A method is `gateway-internal` or `unsafe` if it matches one of the patterns of the class,
+//! a pattern ending with `*` matches all the methods with the prefix, the unsafe methods of
+//! Substrate and ChainX are always included. The other methods are `safe`. An interface only
+//! serves the classes in `allow`, `["safe", "unsafe"]` by default, so the gateway-internal
+//! methods have to be allowed explicitly. `rateLimits` caps the calls per second of the
+//! methods matching each pattern on the interface, the most specific pattern applies.
+//!
+//! The `author_*`, `chain_*` and `rpc_*` methods, and the `system_*` methods about the node,
+//! e.g. `system_peers`, are served by the handler of the service and bypass the ACL, they are
+//! still subject to `--rpc-methods`. Only `system_accountNextIndex` and `system_dryRun*` of
+//! `system_*` are served by ChainX.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::FutureExt;
+use jsonrpc_core::{Error, ErrorCode, RemoteProcedure, Result};
+use jsonrpc_pubsub::PubSubMetadata;
+use serde::Deserialize;
+
+use crate::RpcExtension;
+
+const LOG_TARGET: &str = "rpc-acl";
+
+/// The method is not allowed on the interface by the ACL.
+const METHOD_NOT_ALLOWED_ERROR: i64 = 21000;
+/// The calls of the method exceed the rate limit of the interface.
+const RATE_LIMITED_ERROR: i64 = METHOD_NOT_ALLOWED_ERROR + 1;
+
+/// The unsafe methods served by ChainX, i.e. the ones checking `DenyUnsafe`.
+const UNSAFE_METHODS: &[&str] = &[
+    "babe_epochAuthorship",
+    "state_getPairs",
+    "state_traceBlock",
+    "childstate_getPairs",
+    "system_dryRun*",
+    "sync_state_genSyncSpec",
+    "chainx_peerReputations",
+    "chainx_bannedPeers",
+    "chainx_banPeer",
+    "chainx_unbanPeer",
+];
+
+/// The listening interface of the RPC server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RpcInterface {
+    /// The HTTP server.
+    Http,
+    /// The WebSocket server.
+    Ws,
+}
+
+impl RpcInterface {
+    /// Returns the interface of a call, only the calls over a WebSocket carry a session.
+    fn of(meta: &sc_rpc::Metadata) -> Self {
+        if meta.session().is_some() {
+            Self::Ws
+        } else {
+            Self::Http
+        }
+    }
+}
+
+/// The class of an RPC method in the ACL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MethodClass {
+    /// Available to anyone.
+    Safe,
+    /// Controls or exposes the node, also subject to `--rpc-methods`.
+    Unsafe,
+    /// Controls the gateway services of the node, e.g. the bitcoin broadcaster.
+    GatewayInternal,
+}
+
+fn default_allowed() -> Vec<MethodClass> {
+    vec![MethodClass::Safe, MethodClass::Unsafe]
+}
+
+/// The ACL of an interface.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct InterfaceAcl {
+    /// The classes of the methods served on the interface.
+    #[serde(default = "default_allowed")]
+    pub allow: Vec<MethodClass>,
+    /// The maximum calls per second of the methods matching each pattern.
+    #[serde(default)]
+    pub rate_limits: BTreeMap<String, u32>,
+}
+
+/// The ACL file given by `--rpc-methods-acl`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct AclFile {
+    /// The patterns of the unsafe methods in addition to the built-in ones.
+    #[serde(default, rename = "unsafe")]
+    unsafe_methods: Vec<String>,
+    /// The patterns of the gateway-internal methods.
+    #[serde(default)]
+    gateway_internal: Vec<String>,
+    /// The ACL of each interface, an interface not listed serves all the methods.
+    #[serde(default)]
+    interfaces: BTreeMap<RpcInterface, InterfaceAcl>,
+}
+
+/// Returns true if `method` matches `pattern`, which matches the prefix if it ends with `*`.
+fn matches(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
+fn method_not_allowed_err(method: &str) -> Error {
+    Error {
+        code: ErrorCode::ServerError(METHOD_NOT_ALLOWED_ERROR),
+        message: "Method not allowed on this interface".into(),
+        data: Some(method.into()),
+    }
+}
+
+fn rate_limited_err(method: &str) -> Error {
+    Error {
+        code: ErrorCode::ServerError(RATE_LIMITED_ERROR),
+        message: "Too many requests, try again later".into(),
+        data: Some(method.into()),
+    }
+}
+
+/// The access control of the RPC methods loaded from the ACL file.
+pub struct RpcAccessControl {
+    unsafe_methods: Vec<String>,
+    gateway_internal: Vec<String>,
+    interfaces: BTreeMap<RpcInterface, InterfaceAcl>,
+    /// The start and the number of calls of the current one second window of each rate limit.
+    windows: Mutex<HashMap<(RpcInterface, String), (Instant, u32)>>,
+}
+
+impl RpcAccessControl {
+    /// Loads the ACL from the JSON file at `path`.
+    pub fn load(path: &Path) -> std::result::Result<Self, String> {
+        let file = std::fs::read(path).map_err(|e| {
+            format!(
+                "Failed to read the RPC methods ACL {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        let acl: AclFile = serde_json::from_slice(&file).map_err(|e| {
+            format!(
+                "Failed to parse the RPC methods ACL {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        Ok(Self::new(acl))
+    }
+
+    fn new(acl: AclFile) -> Self {
+        let mut unsafe_methods: Vec<String> =
+            UNSAFE_METHODS.iter().map(|m| m.to_string()).collect();
+        unsafe_methods.extend(acl.unsafe_methods);
+
+        Self {
+            unsafe_methods,
+            gateway_internal: acl.gateway_internal,
+            interfaces: acl.interfaces,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the class of `method`.
+    pub fn class_of(&self, method: &str) -> MethodClass {
+        let any_matches = |patterns: &[String]| patterns.iter().any(|p| matches(p, method));
+        if any_matches(&self.gateway_internal) {
+            MethodClass::GatewayInternal
+        } else if any_matches(&self.unsafe_methods) {
+            MethodClass::Unsafe
+        } else {
+            MethodClass::Safe
+        }
+    }
+
+    /// Ensures `method` is allowed on `interface` and counts the call against the rate limit.
+    pub fn check(&self, interface: RpcInterface, method: &str) -> Result<()> {
+        self.check_at(interface, method, Instant::now())
+    }
+
+    fn check_at(&self, interface: RpcInterface, method: &str, now: Instant) -> Result<()> {
+        let acl = match self.interfaces.get(&interface) {
+            Some(acl) => acl,
+            None => return Ok(()),
+        };
+
+        if !acl.allow.contains(&self.class_of(method)) {
+            log::debug!(
+                target: LOG_TARGET,
+                "Denied {} on the {:?} interface",
+                method,
+                interface
+            );
+            return Err(method_not_allowed_err(method));
+        }
+
+        let limit = acl
+            .rate_limits
+            .iter()
+            .filter(|(pattern, _)| matches(pattern, method))
+            .max_by_key(|(pattern, _)| pattern.len());
+        if let Some((pattern, &limit)) = limit {
+            let mut windows = self.windows.lock().expect("Lock poisoned; qed");
+            let (started, calls) = windows
+                .entry((interface, pattern.clone()))
+                .or_insert((now, 0));
+            if now.duration_since(*started) >= Duration::from_secs(1) {
+                *started = now;
+                *calls = 0;
+            }
+            if *calls >= limit {
+                log::debug!(
+                    target: LOG_TARGET,
+                    "Rate limited {} on the {:?} interface, {} calls/s at most",
+                    method,
+                    interface,
+                    limit
+                );
+                return Err(rate_limited_err(method));
+            }
+            *calls += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// Wraps all the methods of `io` so that each call is checked against `access_control` first.
+pub fn guard_methods(io: RpcExtension, access_control: Arc<RpcAccessControl>) -> RpcExtension {
+    let mut guarded = RpcExtension::default();
+    for (name, procedure) in io.iter() {
+        let method = match procedure {
+            RemoteProcedure::Method(method) => method.clone(),
+            // The aliases resolve to the guarded methods.
+            procedure => {
+                guarded.extend_with(std::iter::once((name.clone(), procedure.clone())));
+                continue;
+            }
+        };
+
+        let access_control = access_control.clone();
+        let method_name = name.clone();
+        guarded.add_method_with_meta(name, move |params, meta: sc_rpc::Metadata| {
+            match access_control.check(RpcInterface::of(&meta), &method_name) {
+                Ok(()) => method.call(params, meta),
+                Err(err) => futures::future::err(err).boxed(),
+            }
+        });
+    }
+    guarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access_control(json: &str) -> RpcAccessControl {
+        RpcAccessControl::new(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn pattern_should_match_exact_method_or_prefix() {
+        assert!(matches("state_getStorage", "state_getStorage"));
+        assert!(!matches("state_getStorage", "state_getStorageAt"));
+        assert!(!matches("state_getStorage", "state_getStorag"));
+        assert!(matches("state_*", "state_getStorage"));
+        assert!(matches("state_*", "state_"));
+        assert!(!matches("state_*", "childstate_getStorage"));
+        assert!(matches("*", "eth_call"));
+    }
+
+    #[test]
+    fn methods_should_be_classified() {
+        let acl = access_control(
+            r#"{
+                "unsafe": ["debug_*"],
+                "gatewayInternal": ["xgatewaybitcoin_broadcast*", "debug_traceCall"]
+            }"#,
+        );
+        assert_eq!(acl.class_of("eth_call"), MethodClass::Safe);
+        // The built-in unsafe methods are always included.
+        assert_eq!(acl.class_of("state_getPairs"), MethodClass::Unsafe);
+        assert_eq!(acl.class_of("system_dryRunAt"), MethodClass::Unsafe);
+        assert_eq!(acl.class_of("system_accountNextIndex"), MethodClass::Safe);
+        assert_eq!(acl.class_of("debug_traceTransaction"), MethodClass::Unsafe);
+        assert_eq!(
+            acl.class_of("xgatewaybitcoin_broadcastTx"),
+            MethodClass::GatewayInternal
+        );
+        // The gateway-internal class takes precedence over the unsafe one.
+        assert_eq!(
+            acl.class_of("debug_traceCall"),
+            MethodClass::GatewayInternal
+        );
+    }
+
+    #[test]
+    fn methods_should_be_allowed_per_interface() {
+        let acl = access_control(
+            r#"{
+                "gatewayInternal": ["xgatewaybitcoin_broadcast*"],
+                "interfaces": {
+                    "http": { "allow": ["safe"] },
+                    "ws": {}
+                }
+            }"#,
+        );
+        let now = Instant::now();
+        assert!(acl.check_at(RpcInterface::Http, "eth_call", now).is_ok());
+        let err = acl
+            .check_at(RpcInterface::Http, "state_getPairs", now)
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::ServerError(METHOD_NOT_ALLOWED_ERROR));
+
+        // The safe and the unsafe methods are allowed by default.
+        assert!(acl
+            .check_at(RpcInterface::Ws, "state_getPairs", now)
+            .is_ok());
+        assert!(acl
+            .check_at(RpcInterface::Ws, "xgatewaybitcoin_broadcastTx", now)
+            .is_err());
+
+        // All the methods are served on the interface not listed.
+        let acl = access_control(r#"{ "interfaces": { "ws": { "allow": [] } } }"#);
+        assert!(acl
+            .check_at(RpcInterface::Http, "state_getPairs", now)
+            .is_ok());
+        assert!(acl.check_at(RpcInterface::Ws, "eth_call", now).is_err());
+    }
+
+    #[test]
+    fn calls_should_be_rate_limited_per_window() {
+        let acl = access_control(
+            r#"{
+                "interfaces": {
+                    "http": { "rateLimits": { "state_getStorage": 1, "state_*": 2 } }
+                }
+            }"#,
+        );
+        let start = Instant::now();
+        let check = |method, elapsed| {
+            acl.check_at(
+                RpcInterface::Http,
+                method,
+                start + Duration::from_millis(elapsed),
+            )
+        };
+
+        // The most specific pattern applies.
+        assert!(check("state_getStorage", 0).is_ok());
+        let err = check("state_getStorage", 10).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ServerError(RATE_LIMITED_ERROR));
+        assert!(check("state_getMetadata", 20).is_ok());
+        assert!(check("state_getKeys", 30).is_ok());
+        assert!(check("state_getMetadata", 40).is_err());
+        // Not limited on the other interface or by other patterns.
+        assert!(check("chain_getBlock", 50).is_ok());
+        assert!(acl
+            .check_at(RpcInterface::Ws, "state_getStorage", start)
+            .is_ok());
+
+        // The calls are counted again once the window of one second has passed.
+        assert!(check("state_getStorage", 999).is_err());
+        assert!(check("state_getStorage", 1000).is_ok());
+        assert!(check("state_getStorage", 1500).is_err());
+        // The window of each pattern starts with its first call.
+        assert!(check("state_getMetadata", 1019).is_err());
+        assert!(check("state_getMetadata", 1020).is_ok());
+        assert!(check("state_getMetadata", 1021).is_ok());
+        assert!(check("state_getMetadata", 1022).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! RPC interface of the address validation, shared by the front-ends and the on-chain
+//! withdrawal checks.
+
+use std::sync::Arc;
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xp_address::{AddressApi as AddressRuntimeApi, AddressVerification, Chain};
+use xp_rpc::runtime_error_into_rpc_err;
+
+#[rpc]
+pub trait ChainXAddressApi<BlockHash> {
+    /// Verifies `address` of `chain` according to the network of ChainX at block `at`, i.e.,
+    /// the SS58 addresses of ChainX and the base58 and bech32/bech32m addresses of Bitcoin.
+    #[rpc(name = "chainx_verifyAddress")]
+    fn verify_address(
+        &self,
+        chain: Chain,
+        address: String,
+        at: Option<BlockHash>,
+    ) -> Result<AddressVerification>;
+}
+
+/// A struct that implements the [`ChainXAddressApi`].
+pub struct ChainXAddress<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> ChainXAddress<C, B> {
+    /// Create new `ChainXAddress` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block> ChainXAddressApi<<Block as BlockT>::Hash> for ChainXAddress<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AddressRuntimeApi<Block>,
+{
+    fn verify_address(
+        &self,
+        chain: Chain,
+        address: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<AddressVerification> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.verify_address(&at, chain, address.into_bytes())
+            .map(Into::into)
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
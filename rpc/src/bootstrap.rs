@@ -0,0 +1,162 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! RPC interface serving everything a wallet needs to initialize in one call.
+//!
+//! The response aggregates the existing RPC methods (`xassets_getAssets`,
+//! `xassets_assetMetadata`, `xspot_getTradingPairs`, `xgatewaycommon_withdrawalLimit`, ...)
+//! read at the same block, so the pieces are consistent with each other.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use sc_chain_spec::Properties;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+
+use chainx_primitives::{AccountId, Balance, Block, BlockNumber, Hash};
+use fp_rpc::EthereumRuntimeRPCApi;
+use xp_rpc::{runtime_error_into_rpc_err, RpcBalance, RpcPrice};
+
+use xpallet_assets_rpc::{Assets, XAssetsApi};
+use xpallet_assets_rpc_runtime_api::{AssetId, AssetMetadata, TotalAssetInfo};
+use xpallet_dex_spot_rpc::{XSpot, XSpotApi};
+use xpallet_dex_spot_rpc_runtime_api::FullPairInfo;
+use xpallet_gateway_bitcoin_rpc_runtime_api::{
+    BtcGatewayParams, XGatewayBitcoinApi as XGatewayBitcoinRuntimeApi,
+};
+use xpallet_gateway_common_rpc::{XGatewayCommon, XGatewayCommonApi};
+use xpallet_gateway_common_rpc_runtime_api::{
+    Chain, WithdrawalLimit, XGatewayCommonApi as XGatewayCommonRuntimeApi,
+};
+
+/// A registered asset along with its metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapAsset {
+    pub id: AssetId,
+    #[serde(flatten)]
+    pub info: TotalAssetInfo<RpcBalance<Balance>>,
+    pub metadata: Option<AssetMetadata>,
+    /// The minimal withdrawal and the fee, `None` for the assets not withdrawable via a gateway.
+    pub withdrawal_limit: Option<WithdrawalLimit<RpcBalance<Balance>>>,
+}
+
+/// The gateway parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapGateway {
+    pub bitcoin: BtcGatewayParams,
+    /// The multisig account of the trustees of each chain.
+    pub trustee_multisigs: BTreeMap<Chain, AccountId>,
+}
+
+/// Everything a wallet needs to initialize.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bootstrap {
+    /// The block the response is read at.
+    pub block_hash: Hash,
+    pub block_number: BlockNumber,
+    /// The chain properties of the chain spec, i.e. `ss58Format`, `tokenDecimals` and
+    /// `tokenSymbol`.
+    pub properties: Properties,
+    pub assets: Vec<BootstrapAsset>,
+    pub trading_pairs: Vec<FullPairInfo<RpcPrice<Balance>, BlockNumber>>,
+    pub gateway: BootstrapGateway,
+    /// The chain id of the EVM, used to sign the Ethereum transactions.
+    pub evm_chain_id: u64,
+}
+
+#[rpc]
+pub trait ChainXBootstrapApi {
+    /// Returns the chain properties, the registered assets, the trading pairs, the gateway
+    /// parameters and the EVM chain id at block `at`, the best block by default.
+    #[rpc(name = "chainx_getBootstrap")]
+    fn bootstrap(&self, at: Option<Hash>) -> Result<Bootstrap>;
+}
+
+/// A struct that implements the [`ChainXBootstrapApi`].
+pub struct ChainXBootstrap<C> {
+    client: Arc<C>,
+    properties: Properties,
+}
+
+impl<C> ChainXBootstrap<C> {
+    /// Create new `ChainXBootstrap` with the client and the properties of the chain spec.
+    pub fn new(client: Arc<C>, properties: Properties) -> Self {
+        Self { client, properties }
+    }
+}
+
+impl<C> ChainXBootstrapApi for ChainXBootstrap<C>
+where
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance>,
+    C::Api:
+        xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance>,
+    C::Api: XGatewayBitcoinRuntimeApi<Block, AccountId>,
+    C::Api: XGatewayCommonRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: EthereumRuntimeRPCApi<Block>,
+{
+    fn bootstrap(&self, at: Option<Hash>) -> Result<Bootstrap> {
+        let block_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let block_number = self
+            .client
+            .number(block_hash)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("Unknown block"))?;
+        let at = Some(block_hash);
+
+        let assets_api = Assets::<C, Block>::new(self.client.clone());
+        let gateway_api =
+            XGatewayCommon::<C, Block, AccountId, Balance, BlockNumber>::new(self.client.clone());
+        let assets = XAssetsApi::<Hash, AccountId, Balance>::assets(&assets_api, at)?
+            .into_iter()
+            .map(|(id, info)| {
+                Ok(BootstrapAsset {
+                    id,
+                    info,
+                    metadata: XAssetsApi::<Hash, AccountId, Balance>::asset_metadata(
+                        &assets_api,
+                        id,
+                        at,
+                    )?,
+                    withdrawal_limit: gateway_api.withdrawal_limit(id, at).ok(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let trading_pairs =
+            XSpotApi::<Hash, AccountId, Balance, BlockNumber, Balance>::trading_pairs(
+                &XSpot::<C, Block>::new(self.client.clone()),
+                at,
+            )?;
+
+        let api = self.client.runtime_api();
+        let id = BlockId::hash(block_hash);
+        let gateway = BootstrapGateway {
+            bitcoin: api
+                .get_gateway_params(&id)
+                .map_err(runtime_error_into_rpc_err)?,
+            trustee_multisigs: api
+                .trustee_multisigs(&id)
+                .map_err(runtime_error_into_rpc_err)?,
+        };
+        let evm_chain_id = api.chain_id(&id).map_err(runtime_error_into_rpc_err)?;
+
+        Ok(Bootstrap {
+            block_hash,
+            block_number,
+            properties: self.properties.clone(),
+            assets,
+            trading_pairs,
+            gateway,
+            evm_chain_id,
+        })
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Compatibility layer for the renamed RPC methods and storage items.
+//!
+//! Downstream integrations keep working for at least one release cycle after a rename:
+//! - the pre-rename RPC method names are served as aliases of the new methods;
+//! - the storage under a pre-rename key can be read via `xcompat_getStorage`.
+//!
+//! The responses served through the aliases carry a deprecation warning, an entry is expected
+//! to be removed in the release given by `removed_in`.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use jsonrpc_core::{futures::FutureExt, Error, RemoteProcedure, Result, Value};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use sc_client_api::{backend::Backend, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+    hashing::twox_128,
+    storage::{StorageData, StorageKey},
+};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xp_rpc::runtime_error_into_rpc_err;
+
+use crate::RpcExtension;
+
+/// A renamed RPC method.
+pub struct DeprecatedMethod {
+    /// The method name before the rename.
+    pub name: &'static str,
+    /// The method serving the requests now.
+    pub replacement: &'static str,
+    /// The release in which the alias is removed.
+    pub removed_in: &'static str,
+}
+
+impl DeprecatedMethod {
+    fn warning(&self) -> String {
+        format!(
+            "`{}` is deprecated and will be removed in {}, use `{}` instead",
+            self.name, self.removed_in, self.replacement
+        )
+    }
+}
+
+/// A renamed storage prefix, `item` is `None` if the whole pallet prefix was renamed.
+pub struct StorageAlias {
+    /// The pallet prefix and the storage item before the rename.
+    pub old: (&'static str, Option<&'static str>),
+    /// The pallet prefix and the storage item after the rename.
+    pub new: (&'static str, Option<&'static str>),
+    /// The release in which the alias is removed.
+    pub removed_in: &'static str,
+}
+
+impl StorageAlias {
+    fn prefix((pallet, item): (&'static str, Option<&'static str>)) -> Vec<u8> {
+        let mut prefix = twox_128(pallet.as_bytes()).to_vec();
+        if let Some(item) = item {
+            prefix.extend_from_slice(&twox_128(item.as_bytes()));
+        }
+        prefix
+    }
+
+    /// Returns the key after the rename if `key` is under the old prefix.
+    fn resolve(&self, key: &[u8]) -> Option<StorageKey> {
+        let old_prefix = Self::prefix(self.old);
+        key.strip_prefix(old_prefix.as_slice()).map(|suffix| {
+            let mut new_key = Self::prefix(self.new);
+            new_key.extend_from_slice(suffix);
+            StorageKey(new_key)
+        })
+    }
+
+    fn warning(&self) -> String {
+        let name = |(pallet, item): (&str, Option<&str>)| match item {
+            Some(item) => format!("{}::{}", pallet, item),
+            None => pallet.to_string(),
+        };
+        format!(
+            "the storage prefix `{}` is deprecated and will be removed in {}, use `{}` instead",
+            name(self.old),
+            self.removed_in,
+            name(self.new)
+        )
+    }
+}
+
+/// The RPC methods renamed in the recent releases.
+pub const DEPRECATED_METHODS: &[DeprecatedMethod] = &[DeprecatedMethod {
+    name: "btcledger_getTotal",
+    replacement: "btcledger_getTotalInComing",
+    removed_in: "v5.3.0",
+}];
+
+/// The storage prefixes renamed in the recent releases.
+pub const STORAGE_ALIASES: &[StorageAlias] = &[
+    StorageAlias {
+        old: ("Instance1Collective", None),
+        new: ("Council", None),
+        removed_in: "v5.3.0",
+    },
+    StorageAlias {
+        old: ("Instance2Collective", None),
+        new: ("TechnicalCommittee", None),
+        removed_in: "v5.3.0",
+    },
+    StorageAlias {
+        old: ("Instance1Membership", None),
+        new: ("TechnicalMembership", None),
+        removed_in: "v5.3.0",
+    },
+];
+
+/// Registers the aliases of the deprecated methods in `io`, the replacements must have been
+/// registered already.
+pub fn add_deprecated_methods(io: &mut RpcExtension) {
+    for method in DEPRECATED_METHODS {
+        let replacement = io
+            .iter()
+            .find(|(name, _)| name.as_str() == method.replacement)
+            .map(|(_, procedure)| procedure.clone());
+        let replacement = match replacement {
+            Some(RemoteProcedure::Method(replacement)) => replacement,
+            _ => {
+                log::warn!(
+                    target: "rpc",
+                    "Skip the deprecated method `{}`, `{}` is not available",
+                    method.name,
+                    method.replacement
+                );
+                continue;
+            }
+        };
+
+        let warning = method.warning();
+        io.add_method_with_meta(method.name, move |params, meta| {
+            log::warn!(target: "rpc", "{}", warning);
+            let warning = warning.clone();
+            replacement.call(params, meta).map(move |result| {
+                result
+                    .map(|value| with_deprecation(value, &warning))
+                    .map_err(|err| with_deprecation_err(err, &warning))
+            })
+        });
+    }
+}
+
+/// Attaches the deprecation warning to an object result, the other results are left intact
+/// so that the existing decoders keep working.
+fn with_deprecation(value: Value, warning: &str) -> Value {
+    match value {
+        Value::Object(mut map) => {
+            map.insert("deprecation".into(), warning.into());
+            Value::Object(map)
+        }
+        value => value,
+    }
+}
+
+fn with_deprecation_err(mut err: Error, warning: &str) -> Error {
+    if err.data.is_none() {
+        err.data = Some(warning.into());
+    }
+    err
+}
+
+/// The storage read via a possibly renamed key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatStorage {
+    /// The key the storage is read from
+    pub key: StorageKey,
+    pub value: Option<StorageData>,
+    /// Present if the requested key is under a renamed prefix
+    pub deprecation: Option<String>,
+}
+
+#[rpc]
+pub trait ChainXCompatApi<BlockHash> {
+    /// Returns the storage of `key` at block `at`, the key under a renamed storage prefix is
+    /// redirected to the new prefix.
+    #[rpc(name = "xcompat_getStorage")]
+    fn storage(&self, key: StorageKey, at: Option<BlockHash>) -> Result<CompatStorage>;
+}
+
+/// A struct that implements the [`ChainXCompatApi`].
+pub struct ChainXCompat<C, B, BE> {
+    client: Arc<C>,
+    _marker: PhantomData<(B, BE)>,
+}
+
+impl<C, B, BE> ChainXCompat<C, B, BE> {
+    /// Create new `ChainXCompat` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, BE> ChainXCompatApi<<Block as BlockT>::Hash> for ChainXCompat<C, Block, BE>
+where
+    Block: BlockT,
+    BE: Backend<Block> + Send + Sync + 'static,
+    C: Send + Sync + 'static + HeaderBackend<Block> + StorageProvider<Block, BE>,
+{
+    fn storage(
+        &self,
+        key: StorageKey,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<CompatStorage> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let (key, deprecation) = STORAGE_ALIASES
+            .iter()
+            .find_map(|alias| {
+                alias
+                    .resolve(&key.0)
+                    .map(|new| (new, Some(alias.warning())))
+            })
+            .unwrap_or((key, None));
+        let value = self
+            .client
+            .storage(&at, &key)
+            .map_err(runtime_error_into_rpc_err)?;
+
+        Ok(CompatStorage {
+            key,
+            value,
+            deprecation,
+        })
+    }
+}
@@ -0,0 +1,418 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Registry of the stable error codes of the xpallet dispatch errors.
+//!
+//! The index of a dispatch error is only the position of the variant in the `Error` enum
+//! of the pallet, which is free to change between runtime upgrades. The registry assigns
+//! each xpallet error a stable numeric code keyed by the pallet and error names, the code
+//! of a new error must never be reused once released.
+//!
+//! The code is `pallet_index * 1000 + n`, where `n` is the position of the error when
+//! it was registered.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use scale_info::TypeDef;
+use serde::{Deserialize, Serialize};
+
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xp_rpc::{runtime_error_into_rpc_err, RUNTIME_ERROR};
+
+/// The runtime metadata can not be decoded.
+const METADATA_DECODE_ERROR: i64 = RUNTIME_ERROR + 200;
+
+/// A registered xpallet dispatch error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorCodeEntry {
+    /// Stable numeric code of the error.
+    pub code: u32,
+    /// Name of the pallet in `construct_runtime`.
+    pub pallet: &'static str,
+    /// Name of the error variant.
+    pub error: &'static str,
+    /// Common remediation of the error.
+    pub remediation: &'static str,
+}
+
+const fn entry(
+    code: u32,
+    pallet: &'static str,
+    error: &'static str,
+    remediation: &'static str,
+) -> ErrorCodeEntry {
+    ErrorCodeEntry {
+        code,
+        pallet,
+        error,
+        remediation,
+    }
+}
+
+/// All the registered xpallet errors.
+#[rustfmt::skip]
+pub const ERROR_CODES: &[ErrorCodeEntry] = &[
+    // XAssetsRegistrar
+    entry(25000, "XAssetsRegistrar", "InvalidAssetTokenSymbolLength", "Use a token symbol of 1 to 24 characters."),
+    entry(25001, "XAssetsRegistrar", "InvalidAssetTokenSymbolChar", "Use only ASCII alphanumeric characters or '-', '.', '|', '~' in the token symbol."),
+    entry(25002, "XAssetsRegistrar", "InvalidAssetTokenNameLength", "Use a token name of 1 to 48 characters."),
+    entry(25003, "XAssetsRegistrar", "InvalidAssetDescLength", "Shorten the asset description to at most 128 characters."),
+    entry(25004, "XAssetsRegistrar", "InvalidAscii", "Only use visible ASCII characters (0x20 to 0x7E) in the asset info."),
+    entry(25005, "XAssetsRegistrar", "AssetAlreadyExists", "Choose another asset id, query `XAssetsRegistrar.AssetInfoOf` for the registered ones."),
+    entry(25006, "XAssetsRegistrar", "AssetDoesNotExist", "Check the asset id against `XAssetsRegistrar.AssetInfoOf`."),
+    entry(25007, "XAssetsRegistrar", "AssetAlreadyValid", "The asset is online already, no recovery is needed."),
+    entry(25008, "XAssetsRegistrar", "AssetIsInvalid", "The asset is offline, wait for it to be recovered by root."),
+    entry(25009, "XAssetsRegistrar", "InvalidCoingeckoId", "Use a CoinGecko id of at most 64 lowercase ASCII alphanumeric characters or '-'."),
+    entry(25010, "XAssetsRegistrar", "DenyNativeAsset", "The native asset PCX uses the existential deposit of the `Balances` pallet instead."),
+    // XAssets
+    entry(26000, "XAssets", "InvalidAsset", "Check the asset id is registered and online via `xassets_getAssets`."),
+    entry(26001, "XAssets", "Overflow", "Reduce the amount, the resulting balance would overflow."),
+    entry(26002, "XAssets", "InsufficientBalance", "Reduce the amount or top up the usable balance, query it via `xassets_getAssetsByAccount`."),
+    entry(26003, "XAssets", "LiquidityRestrictions", "Part of the balance is locked, retry with an amount within the usable balance."),
+    entry(26004, "XAssets", "AmountIntoBalanceFailed", "Use an amount within the balance range of the asset."),
+    entry(26005, "XAssets", "TotalAssetOverflow", "Reduce the amount, the total issuance of the asset would overflow."),
+    entry(26006, "XAssets", "TotalAssetInsufficientBalance", "Reduce the amount, it exceeds the total issuance of the asset."),
+    entry(26007, "XAssets", "DenyNativeAsset", "Use the `Balances` pallet for the native asset PCX."),
+    entry(26008, "XAssets", "ActionNotAllowed", "The asset restrictions forbid this action, check `XAssets.AssetRestrictionsOf`."),
+    entry(26009, "XAssets", "StillHasActiveReserved", "Release the reserved balances (e.g. cancel orders or withdrawals) before retrying."),
+    entry(26010, "XAssets", "NoProvider", "Keep some native balance on the account so that it can hold the asset."),
+    entry(26011, "XAssets", "DuplicateBurnProof", "The proof-of-burn is recorded already, query `XAssets.BurnProofOf` and use the hash of another burn transaction."),
+    entry(26012, "XAssets", "AccountAssetFrozen", "The asset of the account is frozen by the technical committee, check `XAssets.FrozenAccountAssets` for the reason."),
+    entry(26013, "XAssets", "AccountAssetNotFrozen", "Check `XAssets.FrozenAccountAssets`, only the frozen asset can be thawed."),
+    entry(26014, "XAssets", "NotDust", "Only a usable balance below `XAssetsRegistrar.AssetMinBalanceOf` without any reserved balance can be reaped."),
+    entry(26015, "XAssets", "ActiveAccount", "Only the dust of the accounts that never sent a transaction can be reaped, the others collect their dust by their own transfers."),
+    entry(26016, "XAssets", "ReapExemptAccount", "The pallet, reward pot, trustee and EVM-bound accounts hold the assets for others, their dust can't be reaped."),
+    // XStaking
+    entry(27000, "XStaking", "ZeroBalance", "Use a non-zero amount."),
+    entry(27001, "XStaking", "ZeroVoteWeight", "There is no reward to claim until the vote weight accumulates."),
+    entry(27002, "XStaking", "NotValidator", "Check the target via `xstaking_getValidators`."),
+    entry(27003, "XStaking", "AlreadyValidator", "The account is registered as a validator already."),
+    entry(27004, "XStaking", "TooManyValidators", "Wait for a validator slot, the count is capped by `MaximumValidatorCount`."),
+    entry(27005, "XStaking", "NoMoreAcceptableVotes", "Vote for another validator, the upper bound depends on the validator self-bonded amount."),
+    entry(27006, "XStaking", "TooFewActiveValidators", "Wait until more validators are active before chilling."),
+    entry(27007, "XStaking", "InsufficientBalance", "Reduce the bond amount to the free balance."),
+    entry(27008, "XStaking", "NoMoreRebond", "Wait for the rebond frequency limit to expire."),
+    entry(27009, "XStaking", "InvalidRebondBalance", "Rebond no more than the amount bonded to the source validator."),
+    entry(27010, "XStaking", "RebondSelfBondedNotAllowed", "Unbond the self-bonded votes instead of rebonding them."),
+    entry(27011, "XStaking", "InvalidUnbondBalance", "Unbond no more than the amount bonded to the validator."),
+    entry(27012, "XStaking", "NoMoreUnbondChunks", "Withdraw the due unbonded chunks via `unlock_unbonded_withdrawal` first."),
+    entry(27013, "XStaking", "EmptyUnbondedChunks", "There is nothing to withdraw, unbond first."),
+    entry(27014, "XStaking", "InvalidUnbondedIndex", "Check the index via `xstaking_getNominationByAccount`."),
+    entry(27015, "XStaking", "UnbondedWithdrawalNotYetDue", "Wait until the locked until block of the unbonded chunk."),
+    entry(27016, "XStaking", "InvalidReferralIdentityLength", "Use a referral identity of 2 to 12 bytes."),
+    entry(27017, "XStaking", "OccupiedReferralIdentity", "Choose another referral identity."),
+    entry(27018, "XStaking", "XssCheckFailed", "Remove the script like characters from the input."),
+    entry(27019, "XStaking", "AllocateDividendFailed", "Report it to the ChainX team, the reward pot could not pay the dividend."),
+    entry(27020, "XStaking", "DeferredSlashNotFound", "Check the pending slashes via the `DeferredSlashes` storage, the slash may have been applied."),
+    entry(27021, "XStaking", "InvalidSessionKeys", "Pass the SCALE encoded session keys returned by `author_rotateKeys`."),
+    entry(27022, "XStaking", "InvalidBackup", "Use an account other than the validator itself and the other validators as the backup."),
+    entry(27023, "XStaking", "AlreadyBackup", "The account stands by for another validator, check `XStaking.PrimaryOf`."),
+    entry(27024, "XStaking", "BackupInService", "Call `resume_primary` and wait for the backup to leave the validator set."),
+    entry(27025, "XStaking", "NotFailedOver", "The validator is in service itself, check `XStaking.FailedOverAt`."),
+    entry(27026, "XStaking", "ParameterOutOfBounds", "Keep the validator count within the minimum and maximum validator count, the sessions per era within 1..=288, and the treasury or asset mining shares no more than half."),
+    // XMiningAsset
+    entry(28000, "XMiningAsset", "NotPrevilegedAsset", "Check the mining assets via `xminingasset_getMiningAssets`."),
+    entry(28001, "XMiningAsset", "InsufficientStaking", "Bond more PCX, claiming requires a staking amount proportional to the dividend."),
+    entry(28002, "XMiningAsset", "UnexpiredFrequencyLimit", "Wait for the claim frequency limit of the asset to expire."),
+    entry(28003, "XMiningAsset", "ZeroMiningWeight", "There is no dividend to claim until the mining weight accumulates."),
+    entry(28004, "XMiningAsset", "DispatchError", "Check the balances of the reward pot and the claimer."),
+    entry(28005, "XMiningAsset", "NothingToClaim", "Wait for the rewards to accrue and the claim restrictions to expire, see `XMiningAsset.ClaimRestrictionOf`."),
+    entry(28006, "XMiningAsset", "InvalidAsset", "Only the valid assets other than PCX can be given the mining power, check the asset via `xassets_getAssets`."),
+    // XGatewayRecords
+    entry(29000, "XGatewayRecords", "NotExisted", "Check the withdrawal id via `xgatewayrecords_withdrawalList`."),
+    entry(29001, "XGatewayRecords", "NotApplyingState", "Only withdrawals in `Applying` state can be processed or cancelled."),
+    entry(29002, "XGatewayRecords", "NotProcessingState", "Only withdrawals in `Processing` state can be recovered or finished."),
+    entry(29003, "XGatewayRecords", "InvalidAccount", "Only the applicant can cancel the withdrawal."),
+    entry(29004, "XGatewayRecords", "InvalidState", "Use `RootFinish` or `RootCancel`."),
+    entry(29005, "XGatewayRecords", "UnexpectedChain", "The withdrawal belongs to another chain, check the asset chain."),
+    entry(29006, "XGatewayRecords", "NotBroadcastableStatus", "Only the processing or broadcasted withdrawals can be reported, check `xgatewayrecords_withdrawalStatus`."),
+    entry(29007, "XGatewayRecords", "InsufficientWithdrawalBalance", "The held back part of a withdrawal can not exceed the withdrawal balance."),
+    // XGatewayCommon
+    entry(30000, "XGatewayCommon", "InvalidWithdrawal", "Increase the amount above the minimal withdrawal, query it via `xgatewaycommon_withdrawalLimit`."),
+    entry(30001, "XGatewayCommon", "InvalidGenericData", "Check the trustee info encoding, the hot and cold entities must match the chain."),
+    entry(30002, "XGatewayCommon", "InvalidTrusteeSession", "Check the session number via `xgatewaycommon_bitcoinTrusteeSessionInfo`."),
+    entry(30003, "XGatewayCommon", "InvalidAboutLen", "Shorten the about field to at most 128 bytes."),
+    entry(30004, "XGatewayCommon", "InvalidMultisig", "Use the trustee multisig account of the current session."),
+    entry(30005, "XGatewayCommon", "NotSupportedChain", "Only the chains supported by the gateway (Bitcoin) can be used."),
+    entry(30006, "XGatewayCommon", "DuplicatedAccountId", "Remove the duplicated trustee accounts."),
+    entry(30007, "XGatewayCommon", "NotRegistered", "Set up the trustee info via `setup_trustee` first."),
+    entry(30008, "XGatewayCommon", "NotTrusteePreselectedMember", "Only validators or preselected trustee candidates can set trustee info."),
+    entry(30009, "XGatewayCommon", "InvalidSessionNum", "Check the session number against `XGatewayCommon.TrusteeSessionInfoLen`."),
+    entry(30010, "XGatewayCommon", "InvalidTrusteeHisMember", "Only members of that trustee session are accepted."),
+    entry(30011, "XGatewayCommon", "InvalidMultiAccount", "Use the trustee multisig account of the current session."),
+    entry(30012, "XGatewayCommon", "InvalidTrusteeWeight", "Check the trustee reward weights of the session."),
+    entry(30013, "XGatewayCommon", "LastTransitionNotCompleted", "Wait for the running trustee transition to complete."),
+    entry(30014, "XGatewayCommon", "WithdrawalProposalExist", "Wait for the pending withdrawal proposal to finish before the transition."),
+    entry(30015, "XGatewayCommon", "TrusteeMembersNotEnough", "Wait for more trustee candidates to register."),
+    entry(30016, "XGatewayCommon", "ExistCurrentTrustee", "The account is in the current trustee session already."),
+    entry(30017, "XGatewayCommon", "InvalidMaintenanceWindow", "Schedule a window starting no earlier than the current block with a non-zero duration and some operations."),
+    entry(30018, "XGatewayCommon", "NoMaintenanceWindow", "There is no maintenance to cancel, check `xgatewaycommon_maintenanceWindow`."),
+    entry(30019, "XGatewayCommon", "InMaintenance", "Retry after the maintenance ends, query the countdown via `xgatewaycommon_maintenanceWindow`."),
+    entry(30020, "XGatewayCommon", "AlreadyStandbyTrustee", "The account is in the standby trustee pool already."),
+    entry(30021, "XGatewayCommon", "NotStandbyTrustee", "The account is not in the standby trustee pool, it may have been promoted."),
+    entry(30022, "XGatewayCommon", "TooManyStandbyTrustees", "The standby trustee pool is full, remove a member first."),
+    entry(30023, "XGatewayCommon", "InvalidWithdrawalBatchPolicy", "Use a non-zero window and a count within the max withdrawal count."),
+    entry(30024, "XGatewayCommon", "NoWithdrawalBatchPolicy", "Set a withdrawal batch policy for the chain first."),
+    entry(30025, "XGatewayCommon", "EmptyWithdrawalBatch", "Wait for withdrawals to be applied before flushing."),
+    entry(30026, "XGatewayCommon", "WithdrawalBatchAlreadySealed", "Propose the sealed batch before flushing again."),
+    entry(30027, "XGatewayCommon", "WithdrawalBatchMismatch", "Propose exactly the sealed batch, see xgatewaycommon_withdrawalBatch."),
+    entry(30028, "XGatewayCommon", "OperationIsPaused", "The operation is paused in an emergency, wait for the technical committee to resume it."),
+    entry(30029, "XGatewayCommon", "OperationAlreadyPaused", "The operation is paused already."),
+    entry(30030, "XGatewayCommon", "OperationNotPaused", "Only a paused operation can be resumed."),
+    entry(30031, "XGatewayCommon", "TooManyWithdrawalOutputs", "Split the outputs, a withdrawal can have at most `XGatewayBitcoin.MaxWithdrawalCount` outputs."),
+    entry(30032, "XGatewayCommon", "SignatureShareTooLong", "The partial signature can have at most `XGatewayCommon.MaxSignatureShareLen` bytes."),
+    entry(30033, "XGatewayCommon", "WithdrawalProposalMismatch", "Sign the current withdrawal proposal, see `XGatewayBitcoin.WithdrawalProposal`."),
+    entry(30034, "XGatewayCommon", "DuplicateSignatureShare", "The trustee published the partial signature of the proposal already."),
+    entry(30035, "XGatewayCommon", "SignatureRoundAlreadyFinalized", "Enough partial signatures were collected, see `XGatewayCommon.SignatureRoundOf`."),
+    entry(30036, "XGatewayCommon", "TrusteeSessionNotFinished", "The fee reward of a trustee session can be claimed once the session is over."),
+    entry(30037, "XGatewayCommon", "NoTrusteeFeeReward", "The account signed no withdrawal in the session or claimed the reward already."),
+    // XGatewayBitcoin
+    entry(31000, "XGatewayBitcoin", "InvalidBase58", "Check the bitcoin address is valid base58."),
+    entry(31001, "XGatewayBitcoin", "InvalidAddr", "Check the bitcoin address matches the network of the chain."),
+    entry(31002, "XGatewayBitcoin", "InvalidPoW", "The header does not satisfy its nBits, relay a valid header."),
+    entry(31003, "XGatewayBitcoin", "AncientFork", "The fork is deeper than the max reorg depth or below the confirmed header, relay the main chain instead."),
+    entry(31004, "XGatewayBitcoin", "InvalidPrevTx", "Provide the transaction spent by the first input."),
+    entry(31005, "XGatewayBitcoin", "HeaderFuturisticTimestamp", "Relay the header once its timestamp is not ahead of the chain time."),
+    entry(31006, "XGatewayBitcoin", "HeaderNBitsNotMatch", "The header difficulty is wrong, relay a valid header."),
+    entry(31007, "XGatewayBitcoin", "ExistingHeader", "The header is relayed already, skip it."),
+    entry(31008, "XGatewayBitcoin", "PrevHeaderNotExisted", "Relay the parent headers first, check the best via `xgatewaybitcoin_getBtcBlockHeader`."),
+    entry(31009, "XGatewayBitcoin", "DeserializeErr", "Check the header or transaction serialization."),
+    entry(31010, "XGatewayBitcoin", "BadMerkleProof", "Rebuild the merkle proof from the block including the transaction."),
+    entry(31011, "XGatewayBitcoin", "UnconfirmedTx", "Wait for the block of the transaction to be confirmed."),
+    entry(31012, "XGatewayBitcoin", "ReplayedTx", "The transaction is processed already, skip it."),
+    entry(31013, "XGatewayBitcoin", "ProcessTxFailed", "Check the transaction type (deposit or withdrawal) and its outputs."),
+    entry(31014, "XGatewayBitcoin", "InvalidAddress", "Check the bitcoin address matches the network of the chain."),
+    entry(31015, "XGatewayBitcoin", "InvalidPublicKey", "Use a compressed secp256k1 public key."),
+    entry(31016, "XGatewayBitcoin", "NotTrustee", "Only the trustees of the current session can do this."),
+    entry(31017, "XGatewayBitcoin", "DuplicatedKeys", "Remove the duplicated trustee public keys."),
+    entry(31018, "XGatewayBitcoin", "GenerateMultisigFailed", "Check the trustee public keys and the threshold."),
+    entry(31019, "XGatewayBitcoin", "InvalidTrusteeCount", "Check the trustee count against `XGatewayCommon.TrusteeInfoConfigOf`."),
+    entry(31020, "XGatewayBitcoin", "WrongWithdrawalCount", "Check the withdrawal id list against the pending withdrawals."),
+    entry(31021, "XGatewayBitcoin", "NoProposal", "Create a withdrawal proposal first."),
+    entry(31022, "XGatewayBitcoin", "TxOutputsNotMatch", "Build the transaction outputs from the withdrawal id list."),
+    entry(31023, "XGatewayBitcoin", "NotFinishProposal", "Wait for the current withdrawal proposal to finish."),
+    entry(31024, "XGatewayBitcoin", "NoWithdrawalRecord", "Check the withdrawal id via `xgatewayrecords_withdrawalList`."),
+    entry(31025, "XGatewayBitcoin", "DuplicateVote", "The trustee has voted for this proposal already."),
+    entry(31026, "XGatewayBitcoin", "TrusteeTransitionPeriod", "Wait for the trustee transition to finish."),
+    entry(31027, "XGatewayBitcoin", "TxOutputNotColdAddr", "Send the transition outputs to the cold address of the new trustees."),
+    entry(31028, "XGatewayBitcoin", "TxNotFullAmount", "Transfer the full trustee balance during the transition."),
+    entry(31029, "XGatewayBitcoin", "NoPendingDeposit", "Check the address has pending deposits via `XGatewayBitcoin::PendingDeposits` and claim with a non-zero limit."),
+    entry(31030, "XGatewayBitcoin", "InvalidAttestationConfig", "Set a non-zero attestation expiry."),
+    entry(31031, "XGatewayBitcoin", "NoDeferredDeposit", "The deposit is not deferred, it may have been attested or expired already."),
+    entry(31032, "XGatewayBitcoin", "InvalidWithdrawalFeePolicy", "Use a non-zero vsize and a min fee not above the max fee."),
+    entry(31033, "XGatewayBitcoin", "InvalidAttestationHeight", "Attest a height above `XGatewayBitcoin::LastAttestedBtcHeight` and not above the best height."),
+    entry(31034, "XGatewayBitcoin", "InvalidProposalExpiry", "Set a non-zero withdrawal proposal expiry, or `None` to never expire."),
+    entry(31035, "XGatewayBitcoin", "InvalidCheckpoint", "Reset the relay at a checkpoint above `XGatewayBitcoin::BestIndex`."),
+    entry(31036, "XGatewayBitcoin", "UnsettledDeferredDeposits", "Attest or wait for the expiry of `XGatewayBitcoin::DeferredDeposits` before resetting the relay."),
+    entry(31037, "XGatewayBitcoin", "WithdrawalTxMismatch", "Report the txid of the transaction in `XGatewayBitcoin::WithdrawalProposal`."),
+    entry(31038, "XGatewayBitcoin", "ReorgDepthTooLarge", "Set a max reorg depth below `XGatewayBitcoin::ConfirmationNumber`."),
+    entry(31039, "XGatewayBitcoin", "UnsettledPendingDeposits", "Claim `XGatewayBitcoin::PendingDeposits` by binding the deposit addresses before resetting the relay."),
+    entry(31040, "XGatewayBitcoin", "UnsettledWithdrawals", "Finish or cancel the bitcoin withdrawals in `XGatewayRecords::PendingWithdrawals` before resetting the relay."),
+    entry(31041, "XGatewayBitcoin", "UnattestedWallet", "Have the trustees attest the wallet balance at `XGatewayBitcoin::BestIndex`, the attested balance must not exceed the backed supply, otherwise relay the missing deposits first."),
+    entry(31042, "XGatewayBitcoin", "ConfirmedIndexRollback", "Keep relaying the main chain, a side branch must not roll back `XGatewayBitcoin::ConfirmedIndex`."),
+    // XSpot
+    entry(32000, "XSpot", "InvalidPrice", "Use a non-zero price which is a multiple of the tick of the pair."),
+    entry(32001, "XSpot", "TooHighBidPrice", "Lower the bid price within the price volatility of the lowest ask."),
+    entry(32002, "XSpot", "TooLowAskPrice", "Raise the ask price within the price volatility of the highest bid."),
+    entry(32003, "XSpot", "VolumeTooSmall", "Increase the amount, its quote volume rounds to zero."),
+    entry(32004, "XSpot", "ZeroAmount", "Use a non-zero amount."),
+    entry(32005, "XSpot", "InsufficientBalance", "Reduce the amount or top up the usable balance of the asset."),
+    entry(32006, "XSpot", "InvalidOrderType", "Only limit orders are supported."),
+    entry(32007, "XSpot", "InvalidTradingPair", "Check the pair via `xspot_getTradingPairs`."),
+    entry(32008, "XSpot", "TradingPairUntradable", "Wait for the pair to become tradable."),
+    entry(32009, "XSpot", "NonexistentTradingPair", "Check the pair via `xspot_getTradingPairs`."),
+    entry(32010, "XSpot", "InvalidTickdecimals", "Use tick decimals no less than the ones of the pair."),
+    entry(32011, "XSpot", "InvalidPriceVolatility", "Use a price volatility less than 100."),
+    entry(32012, "XSpot", "TradingPairAlreadyExists", "The pair exists already, update it instead."),
+    entry(32013, "XSpot", "TooManyBacklogOrders", "Use another price, the backlog at this price is full."),
+    entry(32014, "XSpot", "InvalidTradingPairAsset", "Check the assets of the pair are registered."),
+    entry(32015, "XSpot", "CancelOrderNotAllowed", "Only orders with `ZeroFill` or `PartialFill` status can be canceled."),
+    entry(32016, "XSpot", "InvalidOrderId", "Check the order index via `xspot_getOrdersByAccount`."),
+    entry(32017, "XSpot", "AssetError", "Check the asset balances of the account."),
+    entry(32018, "XSpot", "PostOnlyWouldMatch", "Use a price that does not cross the handicap or another order flavor."),
+    entry(32019, "XSpot", "FillOrKillUnfilled", "Reduce the amount or use `ImmediateOrCancel` to accept a partial fill."),
+    entry(32020, "XSpot", "InvalidCurrencyPair", "Use different assets as the base and quote of the pair."),
+    entry(32021, "XSpot", "InvalidPrecision", "Use tick decimals no more than the pip decimals."),
+    entry(32022, "XSpot", "TooManyOrdersToMigrate", "Cancel some resting orders of the pair before changing its precision."),
+    entry(32023, "XSpot", "InvalidTradingFee", "Use maker and taker fees of at most 1000 basis points, i.e., 10%."),
+    // XTransactionFee
+    entry(35000, "XTransactionFee", "InvalidFeeMultiplierParams", "Use a positive target fullness, adjustment variable and minimum multiplier."),
+    entry(35001, "XTransactionFee", "TooManySponsoredCalls", "Sponsor all calls or fewer calls, see the constant `MaxSponsoredCalls`."),
+    entry(35002, "XTransactionFee", "NotSponsor", "Register as a sponsor via `set_sponsor` first."),
+    // XAssetsBridge
+    entry(45000, "XAssetsBridge", "AccountIdHasMapped", "The account is bound to an eth address already."),
+    entry(45001, "XAssetsBridge", "EthAddressHasMapped", "The eth address is bound to an account already."),
+    entry(45002, "XAssetsBridge", "BadSignature", "Sign the claim message with the private key of the eth address."),
+    entry(45003, "XAssetsBridge", "InvalidSignature", "The signature does not match the eth address, sign again."),
+    entry(45004, "XAssetsBridge", "AccountIdHasNotMapped", "Bind an eth address via `claim_account` first."),
+    entry(45005, "XAssetsBridge", "EthAddressHasNotMapped", "Bind the eth address via `claim_account` first."),
+    entry(45006, "XAssetsBridge", "AssetIdHasMapped", "The asset is bound to an erc20 contract already."),
+    entry(45007, "XAssetsBridge", "AssetIdHasNotMapped", "Use an asset bound to an erc20 contract."),
+    entry(45008, "XAssetsBridge", "ContractAddressHasMapped", "The erc20 contract is bound to an asset already."),
+    entry(45009, "XAssetsBridge", "ContractAddressHasNotMapped", "Use an erc20 contract bound to an asset."),
+    entry(45010, "XAssetsBridge", "ExecutedFailed", "Check the erc20 contract balance and allowance, then retry."),
+    entry(45011, "XAssetsBridge", "RequireAdmin", "Only the bridge admin can do this."),
+    entry(45012, "XAssetsBridge", "InEmergency", "Wait for the bridge to leave the emergency state."),
+    entry(45013, "XAssetsBridge", "BanBackForeign", "Transferring this asset back is disabled."),
+    entry(45014, "XAssetsBridge", "ZeroBalance", "Use a non-zero amount."),
+    entry(45015, "XAssetsBridge", "Deprecated", "The call is deprecated, use its replacement."),
+    // XBtcLedger
+    entry(46000, "XBtcLedger", "InsufficientBalance", "Reduce the amount or top up the BTC balance."),
+    entry(46001, "XBtcLedger", "DeadAccount", "Use a beneficiary account which exists."),
+    entry(46002, "XBtcLedger", "ZeroAmount", "Lock or unlock a non-zero amount."),
+    entry(46003, "XBtcLedger", "InsufficientLocked", "Check the locked balance via `XBtcLedger.InterestLedgers`."),
+    entry(46004, "XBtcLedger", "NoInterest", "Wait for the locked balance to accrue some interest."),
+    entry(46005, "XBtcLedger", "InsufficientInterestFund", "The treasury can not afford the interest for now, try again after it is funded."),
+    // XGatewayEthereum
+    entry(47000, "XGatewayEthereum", "InvalidCommitteeCount", "Keep the committee size within `XGatewayCommon.TrusteeInfoConfigOf` of Ethereum."),
+    entry(47001, "XGatewayEthereum", "DuplicateCommitteeMember", "Remove the duplicated account or Ethereum address from the committee."),
+    entry(47002, "XGatewayEthereum", "NotCommitteeMember", "Only the committee members can do this, see `XGatewayEthereum.Committee`."),
+    entry(47003, "XGatewayEthereum", "NotEthereumAsset", "Register an asset whose chain is Ethereum."),
+    entry(47004, "XGatewayEthereum", "TokenAlreadyRegistered", "The token or the asset is registered already, see `XGatewayEthereum.AssetOfToken`."),
+    entry(47005, "XGatewayEthereum", "UnregisteredToken", "Register the token via `register_token` first."),
+    entry(47006, "XGatewayEthereum", "ZeroAmount", "Attest a non-zero deposit."),
+    entry(47007, "XGatewayEthereum", "DepositAlreadyProcessed", "The deposit was minted already, see `XGatewayEthereum.ProcessedDeposits`."),
+    entry(47008, "XGatewayEthereum", "DuplicateAttestation", "The member has attested it already, wait for the other members."),
+    entry(47009, "XGatewayEthereum", "NotApplyingWithdrawal", "Only the applying withdrawal can be signed, check `XGatewayRecords.WithdrawalStateOf`."),
+    entry(47010, "XGatewayEthereum", "NotProcessingWithdrawal", "Only the processing withdrawal can be confirmed, check `XGatewayRecords.WithdrawalStateOf`."),
+    entry(47011, "XGatewayEthereum", "MultipleWithdrawalOutputs", "Sign the withdrawals one by one."),
+    entry(47012, "XGatewayEthereum", "InvalidAddress", "Use a hex encoded Ethereum address."),
+    entry(47013, "XGatewayEthereum", "InvalidSignature", "Sign the withdrawal digest by the Ethereum address registered in the committee."),
+    // XSwap
+    entry(49000, "XSwap", "IdenticalAssets", "Use two different assets."),
+    entry(49001, "XSwap", "InvalidAsset", "Use an online asset, check `XAssetsRegistrar.AssetOnline`."),
+    entry(49002, "XSwap", "PoolAlreadyExists", "The pool of the asset pair exists already, add liquidity to it instead."),
+    entry(49003, "XSwap", "PoolNotFound", "Ask the council to create the pool via `create_pool` first."),
+    entry(49004, "XSwap", "ZeroAmount", "Use a positive amount."),
+    entry(49005, "XSwap", "InsufficientLiquidity", "The pool has no liquidity yet, add liquidity first."),
+    entry(49006, "XSwap", "InsufficientLiquidityMinted", "Add more liquidity, the first liquidity must exceed the constant `MinimumLiquidity`."),
+    entry(49007, "XSwap", "InsufficientLiquidityBurned", "Remove more liquidity."),
+    entry(49008, "XSwap", "SlippageExceeded", "Quote the pool again and lower the min amounts."),
+    entry(49009, "XSwap", "Overflow", "Use a smaller amount."),
+];
+
+/// Returns the registered entry of the error `error` of pallet `pallet`.
+pub fn lookup(pallet: &str, error: &str) -> Option<&'static ErrorCodeEntry> {
+    ERROR_CODES
+        .iter()
+        .find(|entry| entry.pallet == pallet && entry.error == error)
+}
+
+/// Returns the registered entry of the stable error code `code`.
+pub fn lookup_code(code: u32) -> Option<&'static ErrorCodeEntry> {
+    ERROR_CODES.iter().find(|entry| entry.code == code)
+}
+
+/// Explanation of a dispatch error.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorExplanation {
+    pub module_index: u8,
+    pub error_index: u8,
+    /// Stable error code, `None` if the error is not a registered xpallet error.
+    pub code: Option<u32>,
+    pub pallet: String,
+    pub name: String,
+    pub docs: Vec<String>,
+    pub remediation: Option<String>,
+}
+
+#[rpc]
+pub trait ChainXErrorApi<BlockHash> {
+    /// Explains the dispatch error `DispatchError::Module { index, error }` at block `at`.
+    #[rpc(name = "chainx_explainError")]
+    fn explain_error(
+        &self,
+        module_index: u8,
+        error_index: u8,
+        at: Option<BlockHash>,
+    ) -> Result<Option<ErrorExplanation>>;
+}
+
+/// A struct that implements the [`ChainXErrorApi`].
+pub struct ChainXError<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> ChainXError<C, B> {
+    /// Create new `ChainXError` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block> ChainXErrorApi<<Block as BlockT>::Hash> for ChainXError<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: Metadata<Block>,
+{
+    fn explain_error(
+        &self,
+        module_index: u8,
+        error_index: u8,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<ErrorExplanation>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let metadata = api.metadata(&at).map_err(runtime_error_into_rpc_err)?;
+        let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+            .map_err(metadata_decode_error_into_rpc_err)?;
+        let metadata = match metadata.1 {
+            RuntimeMetadata::V14(metadata) => metadata,
+            _ => {
+                return Err(metadata_decode_error_into_rpc_err(
+                    "Unsupported metadata version",
+                ))
+            }
+        };
+
+        let pallet = match metadata.pallets.iter().find(|p| p.index == module_index) {
+            Some(pallet) => pallet,
+            None => return Ok(None),
+        };
+        let variants = match pallet
+            .error
+            .as_ref()
+            .and_then(|error| metadata.types.resolve(error.ty.id()))
+            .map(|ty| ty.type_def())
+        {
+            Some(TypeDef::Variant(def)) => def.variants(),
+            _ => return Ok(None),
+        };
+        let variant = match variants.iter().find(|v| v.index() == error_index) {
+            Some(variant) => variant,
+            None => return Ok(None),
+        };
+
+        let registered = lookup(&pallet.name, variant.name());
+        Ok(Some(ErrorExplanation {
+            module_index,
+            error_index,
+            code: registered.map(|entry| entry.code),
+            pallet: pallet.name.clone(),
+            name: variant.name().clone(),
+            docs: variant.docs().to_vec(),
+            remediation: registered.map(|entry| entry.remediation.into()),
+        }))
+    }
+}
+
+fn metadata_decode_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(METADATA_DECODE_ERROR),
+        message: "Can not decode runtime metadata".into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Node side pre-validation of the EVM transactions submitted via `eth_sendRawTransaction`.
+//!
+//! During the EVM bursts, the transactions which can never be valid, i.e. the malformed ones,
+//! the ones with an invalid signature and the ones with a gas limit below the intrinsic gas, are
+//! rejected before they reach the pool, so neither the pool nor the proposer spend a runtime
+//! call on them. The recovered signers are cached by the transaction hash, which covers the
+//! signature, so the resubmissions of the bots and the wallets are not recovered again.
+//!
+//! The runtime is not involved, it still recovers the signers on its own when validating and
+//! applying the transactions, so the block execution doesn't depend on the cache of the node.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use ethereum::{
+    EIP1559TransactionMessage, EIP2930TransactionMessage, EnvelopedDecodable,
+    LegacyTransactionMessage, TransactionAction, TransactionV2,
+};
+use jsonrpc_core::{futures::FutureExt, Error, Params, RemoteProcedure, Result};
+use sp_core::{hashing::keccak_256, Bytes, H160, H256, U256};
+
+use crate::RpcExtension;
+
+const SEND_RAW_TRANSACTION: &str = "eth_sendRawTransaction";
+
+/// Maximum number of the recovered signers kept by the node.
+const SIGNER_CACHE_CAPACITY: usize = 8192;
+
+/// The intrinsic gas of the EVM transactions, as of London.
+const TX_GAS: u64 = 21_000;
+const TX_CREATE_GAS: u64 = 53_000;
+const TX_DATA_ZERO_GAS: u64 = 4;
+const TX_DATA_NON_ZERO_GAS: u64 = 16;
+const TX_ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+const TX_ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+
+/// Bounded FIFO cache of the recovered signers.
+#[derive(Default)]
+struct SignerCache {
+    signers: HashMap<H256, H160>,
+    order: VecDeque<H256>,
+}
+
+impl SignerCache {
+    fn get(&self, tx_hash: &H256) -> Option<H160> {
+        self.signers.get(tx_hash).copied()
+    }
+
+    fn insert(&mut self, tx_hash: H256, signer: H160) {
+        if self.signers.insert(tx_hash, signer).is_some() {
+            return;
+        }
+        self.order.push_back(tx_hash);
+        while self.order.len() > SIGNER_CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.signers.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Pre-validates the raw transactions before `eth_sendRawTransaction` submits them to the
+/// pool, `eth_sendRawTransaction` must have been registered already.
+pub fn prevalidate_raw_transactions(io: &mut RpcExtension) {
+    let send_raw_transaction = io
+        .iter()
+        .find(|(name, _)| name.as_str() == SEND_RAW_TRANSACTION)
+        .map(|(_, procedure)| procedure.clone());
+    let send_raw_transaction = match send_raw_transaction {
+        Some(RemoteProcedure::Method(method)) => method,
+        _ => {
+            log::warn!(
+                target: "rpc",
+                "Skip the pre-validation of the EVM transactions, `{}` is not available",
+                SEND_RAW_TRANSACTION
+            );
+            return;
+        }
+    };
+
+    let signers = Arc::new(Mutex::new(SignerCache::default()));
+    io.add_method_with_meta(
+        SEND_RAW_TRANSACTION,
+        move |params: Params, meta: sc_rpc::Metadata| {
+            // The malformed params are reported by `eth_sendRawTransaction` itself.
+            if let Ok((raw,)) = params.clone().parse::<(Bytes,)>() {
+                if let Err(err) = prevalidate(&raw, &signers) {
+                    return jsonrpc_core::futures::future::err(err).boxed();
+                }
+            }
+            send_raw_transaction.call(params, meta)
+        },
+    );
+}
+
+/// Returns the signer of the raw transaction `raw` if it could be valid.
+fn prevalidate(raw: &[u8], signers: &Mutex<SignerCache>) -> Result<H160> {
+    let transaction: TransactionV2 = EnvelopedDecodable::decode(raw)
+        .map_err(|_| Error::invalid_params("Invalid transaction encoding"))?;
+    let tx_hash = transaction.hash();
+    if let Some(signer) = signers.lock().expect("Lock poisoned; qed").get(&tx_hash) {
+        return Ok(signer);
+    }
+
+    let (gas_limit, intrinsic_gas) = gas_limit_and_intrinsic_gas(&transaction);
+    if gas_limit < U256::from(intrinsic_gas) {
+        return Err(Error::invalid_params(format!(
+            "Gas limit {} is below the intrinsic gas {}",
+            gas_limit, intrinsic_gas
+        )));
+    }
+    let signer = recover_signer(&transaction)
+        .ok_or_else(|| Error::invalid_params("Invalid transaction signature"))?;
+
+    signers
+        .lock()
+        .expect("Lock poisoned; qed")
+        .insert(tx_hash, signer);
+    Ok(signer)
+}
+
+fn gas_limit_and_intrinsic_gas(transaction: &TransactionV2) -> (U256, u64) {
+    let (gas_limit, action, input, access_list) = match transaction {
+        TransactionV2::Legacy(t) => (t.gas_limit, &t.action, &t.input, &[][..]),
+        TransactionV2::EIP2930(t) => (t.gas_limit, &t.action, &t.input, &t.access_list[..]),
+        TransactionV2::EIP1559(t) => (t.gas_limit, &t.action, &t.input, &t.access_list[..]),
+    };
+
+    let base = match action {
+        TransactionAction::Call(_) => TX_GAS,
+        TransactionAction::Create => TX_CREATE_GAS,
+    };
+    let data = input
+        .iter()
+        .map(|byte| match byte {
+            0 => TX_DATA_ZERO_GAS,
+            _ => TX_DATA_NON_ZERO_GAS,
+        })
+        .sum::<u64>();
+    let access_list = access_list
+        .iter()
+        .map(|item| {
+            TX_ACCESS_LIST_ADDRESS_GAS + TX_ACCESS_LIST_STORAGE_KEY_GAS * item.slots.len() as u64
+        })
+        .sum::<u64>();
+    (gas_limit, base + data + access_list)
+}
+
+/// Recovers the signer the same way as `pallet_ethereum`.
+fn recover_signer(transaction: &TransactionV2) -> Option<H160> {
+    let mut sig = [0u8; 65];
+    let mut msg = [0u8; 32];
+    match transaction {
+        TransactionV2::Legacy(t) => {
+            sig[0..32].copy_from_slice(&t.signature.r()[..]);
+            sig[32..64].copy_from_slice(&t.signature.s()[..]);
+            sig[64] = t.signature.standard_v();
+            msg.copy_from_slice(&LegacyTransactionMessage::from(t.clone()).hash()[..]);
+        }
+        TransactionV2::EIP2930(t) => {
+            sig[0..32].copy_from_slice(&t.r[..]);
+            sig[32..64].copy_from_slice(&t.s[..]);
+            sig[64] = t.odd_y_parity as u8;
+            msg.copy_from_slice(&EIP2930TransactionMessage::from(t.clone()).hash()[..]);
+        }
+        TransactionV2::EIP1559(t) => {
+            sig[0..32].copy_from_slice(&t.r[..]);
+            sig[32..64].copy_from_slice(&t.s[..]);
+            sig[64] = t.odd_y_parity as u8;
+            msg.copy_from_slice(&EIP1559TransactionMessage::from(t.clone()).hash()[..]);
+        }
+    }
+
+    let signature = libsecp256k1::Signature::parse_standard_slice(&sig[..64]).ok()?;
+    let recovery_id = libsecp256k1::RecoveryId::parse(sig[64]).ok()?;
+    let public = libsecp256k1::recover(
+        &libsecp256k1::Message::parse(&msg),
+        &signature,
+        &recovery_id,
+    )
+    .ok()?;
+    Some(H160::from(H256::from(keccak_256(&public.serialize()[1..]))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ethereum::{EIP1559Transaction, EnvelopedEncodable};
+
+    const SECRET: [u8; 32] = [1; 32];
+
+    fn signer() -> H160 {
+        let secret = libsecp256k1::SecretKey::parse(&SECRET).unwrap();
+        let public = libsecp256k1::PublicKey::from_secret_key(&secret);
+        H160::from(H256::from(keccak_256(&public.serialize()[1..])))
+    }
+
+    fn signed_transaction(gas_limit: u64, input: Vec<u8>) -> TransactionV2 {
+        let message = EIP1559TransactionMessage {
+            chain_id: 1501,
+            nonce: U256::zero(),
+            max_priority_fee_per_gas: U256::zero(),
+            max_fee_per_gas: U256::one(),
+            gas_limit: gas_limit.into(),
+            action: TransactionAction::Call(H160::repeat_byte(2)),
+            value: U256::zero(),
+            input,
+            access_list: vec![],
+        };
+        let secret = libsecp256k1::SecretKey::parse(&SECRET).unwrap();
+        let (signature, recovery_id) = libsecp256k1::sign(
+            &libsecp256k1::Message::parse(message.hash().as_fixed_bytes()),
+            &secret,
+        );
+        let signature = signature.serialize();
+        TransactionV2::EIP1559(EIP1559Transaction {
+            chain_id: message.chain_id,
+            nonce: message.nonce,
+            max_priority_fee_per_gas: message.max_priority_fee_per_gas,
+            max_fee_per_gas: message.max_fee_per_gas,
+            gas_limit: message.gas_limit,
+            action: message.action,
+            value: message.value,
+            input: message.input,
+            access_list: message.access_list,
+            odd_y_parity: recovery_id.serialize() != 0,
+            r: H256::from_slice(&signature[..32]),
+            s: H256::from_slice(&signature[32..]),
+        })
+    }
+
+    #[test]
+    fn valid_transaction_signer_should_be_recovered_and_cached() {
+        let signers = Mutex::new(SignerCache::default());
+        let transaction = signed_transaction(TX_GAS + 2 * TX_DATA_NON_ZERO_GAS, vec![1, 2]);
+        let raw = EnvelopedEncodable::encode(&transaction);
+
+        assert_eq!(prevalidate(&raw, &signers).unwrap(), signer());
+        assert_eq!(
+            signers.lock().unwrap().get(&transaction.hash()),
+            Some(signer())
+        );
+        assert_eq!(prevalidate(&raw, &signers).unwrap(), signer());
+    }
+
+    #[test]
+    fn invalid_transaction_should_be_rejected() {
+        let signers = Mutex::new(SignerCache::default());
+        assert!(prevalidate(&[2, 0xc0], &signers).is_err());
+
+        // Below the intrinsic gas of the input.
+        let transaction = signed_transaction(TX_GAS + TX_DATA_NON_ZERO_GAS, vec![1, 0, 2]);
+        assert_eq!(
+            gas_limit_and_intrinsic_gas(&transaction).1,
+            TX_GAS + TX_DATA_ZERO_GAS + 2 * TX_DATA_NON_ZERO_GAS
+        );
+        let raw = EnvelopedEncodable::encode(&transaction);
+        assert!(prevalidate(&raw, &signers).is_err());
+
+        let mut transaction = match signed_transaction(TX_GAS, vec![]) {
+            TransactionV2::EIP1559(t) => t,
+            _ => unreachable!(),
+        };
+        transaction.s = H256::zero();
+        let raw = EnvelopedEncodable::encode(&TransactionV2::EIP1559(transaction));
+        assert!(prevalidate(&raw, &signers).is_err());
+        assert!(signers.lock().unwrap().signers.is_empty());
+    }
+
+    #[test]
+    fn signer_cache_should_evict_the_oldest() {
+        let signer = H160::repeat_byte(1);
+        let mut cache = SignerCache::default();
+        for i in 0..=SIGNER_CACHE_CAPACITY as u64 {
+            cache.insert(H256::from_low_u64_be(i), signer);
+        }
+        assert_eq!(cache.get(&H256::from_low_u64_be(0)), None);
+        assert_eq!(cache.get(&H256::from_low_u64_be(1)), Some(signer));
+        assert_eq!(cache.signers.len(), SIGNER_CACHE_CAPACITY);
+    }
+}
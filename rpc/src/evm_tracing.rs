@@ -0,0 +1,633 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! RPC interface of the EVM call traces.
+//!
+//! The block of a transaction is executed again on top of its parent state through
+//! [`EvmTracingRuntimeApi`], which is only implemented by the runtime built with the
+//! `evm-tracing` feature. The node must load the tracing runtime of each traced spec version
+//! via `--wasm-runtime-overrides`, the on-chain runtime returns an error instead.
+//!
+//! `debug_traceTransaction` and `debug_traceCall` only support the `callTracer` of geth, the
+//! struct logs of each opcode are not recorded. `debug_traceCall` executes the call on top of
+//! the state of a block without applying it, e.g. for the ERC-4337 bundlers to validate the
+//! user operations. `trace_filter` returns the flat traces in the format of OpenEthereum.
+
+use std::sync::Arc;
+
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use fc_rpc_core::types::BlockNumber;
+use fp_rpc::EthereumRuntimeRPCApi;
+use sp_api::{ApiRef, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256, U256};
+use sp_runtime::{
+    generic::{BlockId, Digest},
+    traits::{Block as BlockT, Header as HeaderT, NumberFor, SaturatedConversion},
+};
+
+use xp_evm_tracing::{CallTrace, CallType, EvmTracingApi as EvmTracingRuntimeApi};
+use xp_rpc::runtime_error_into_rpc_err;
+
+/// The block or the transaction can not be traced.
+const EVM_TRACING_ERROR: i64 = 21000;
+
+/// The maximum number of blocks traced by a `trace_filter` call.
+const MAX_TRACE_FILTER_BLOCKS: u64 = 100;
+
+/// The options of `debug_traceTransaction`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceOptions {
+    /// Only `callTracer` is supported.
+    pub tracer: Option<String>,
+}
+
+/// An entry of the access list of [`TraceCallRequest`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListEntry {
+    pub address: H160,
+    pub storage_keys: Vec<H256>,
+}
+
+/// The call of `debug_traceCall`, in the format of `eth_call`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallRequest {
+    /// The zero address if not specified.
+    pub from: Option<H160>,
+    pub to: H160,
+    /// The gas limit of the block if not specified.
+    pub gas: Option<U256>,
+    /// Used as `maxFeePerGas` if the latter is not specified.
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub value: Option<U256>,
+    pub data: Option<Bytes>,
+    pub nonce: Option<U256>,
+    pub access_list: Option<Vec<AccessListEntry>>,
+}
+
+/// A call frame in the format of the `callTracer` of geth.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcCallTrace {
+    #[serde(rename = "type")]
+    pub call_type: String,
+    pub from: H160,
+    pub to: H160,
+    pub value: U256,
+    pub gas: U256,
+    pub gas_used: U256,
+    pub input: Bytes,
+    pub output: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<RpcCallTrace>,
+}
+
+impl From<CallTrace> for RpcCallTrace {
+    fn from(trace: CallTrace) -> Self {
+        let call_type = match trace.call_type {
+            CallType::Call => "CALL",
+            CallType::CallCode => "CALLCODE",
+            CallType::DelegateCall => "DELEGATECALL",
+            CallType::StaticCall => "STATICCALL",
+            CallType::Create => "CREATE",
+            CallType::Create2 => "CREATE2",
+            CallType::SelfDestruct => "SELFDESTRUCT",
+        };
+        Self {
+            call_type: call_type.into(),
+            from: trace.from,
+            to: trace.to,
+            value: trace.value,
+            gas: trace.gas.into(),
+            gas_used: trace.gas_used.into(),
+            input: trace.input.into(),
+            output: trace.output.into(),
+            error: trace
+                .error
+                .map(|error| String::from_utf8_lossy(&error).into_owned()),
+            calls: trace.calls.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The filter of `trace_filter`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    /// The first block to trace, `latest` if not specified.
+    pub from_block: Option<BlockNumber>,
+    /// The last block to trace, `latest` if not specified.
+    pub to_block: Option<BlockNumber>,
+    /// Only the traces from one of these addresses, all if not specified.
+    pub from_address: Option<Vec<H160>>,
+    /// Only the traces to one of these addresses, all if not specified.
+    pub to_address: Option<Vec<H160>>,
+    /// The number of the matched traces to skip.
+    pub after: Option<u32>,
+    /// The maximum number of the traces to return.
+    pub count: Option<u32>,
+}
+
+/// The action of a flat trace.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RpcTraceAction {
+    #[serde(rename_all = "camelCase")]
+    Call {
+        call_type: String,
+        from: H160,
+        to: H160,
+        value: U256,
+        gas: U256,
+        input: Bytes,
+    },
+    #[serde(rename_all = "camelCase")]
+    Create {
+        from: H160,
+        value: U256,
+        gas: U256,
+        init: Bytes,
+    },
+    #[serde(rename_all = "camelCase")]
+    Suicide {
+        address: H160,
+        refund_address: H160,
+        balance: U256,
+    },
+}
+
+/// The result of a successful flat trace.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RpcTraceResult {
+    #[serde(rename_all = "camelCase")]
+    Call { gas_used: U256, output: Bytes },
+    #[serde(rename_all = "camelCase")]
+    Create {
+        gas_used: U256,
+        code: Bytes,
+        address: H160,
+    },
+}
+
+/// A call frame in the flat format of OpenEthereum.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTrace {
+    pub action: RpcTraceAction,
+    pub block_hash: H256,
+    pub block_number: u64,
+    pub result: Option<RpcTraceResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub subtraces: u32,
+    pub trace_address: Vec<u32>,
+    pub transaction_hash: H256,
+    pub transaction_position: u32,
+    #[serde(rename = "type")]
+    pub trace_type: String,
+}
+
+/// The location of a flattened call frame.
+struct TraceLocation {
+    block_hash: H256,
+    block_number: u64,
+    transaction_hash: H256,
+    transaction_position: u32,
+}
+
+/// Appends `trace` and the frames it entered to `traces` in the depth-first order.
+fn flatten_trace(
+    trace: &CallTrace,
+    trace_address: Vec<u32>,
+    location: &TraceLocation,
+    traces: &mut Vec<RpcTrace>,
+) {
+    let (action, trace_type) = match trace.call_type {
+        CallType::Create | CallType::Create2 => (
+            RpcTraceAction::Create {
+                from: trace.from,
+                value: trace.value,
+                gas: trace.gas.into(),
+                init: trace.input.clone().into(),
+            },
+            "create",
+        ),
+        CallType::SelfDestruct => (
+            RpcTraceAction::Suicide {
+                address: trace.from,
+                refund_address: trace.to,
+                balance: trace.value,
+            },
+            "suicide",
+        ),
+        call_type => (
+            RpcTraceAction::Call {
+                call_type: match call_type {
+                    CallType::CallCode => "callcode",
+                    CallType::DelegateCall => "delegatecall",
+                    CallType::StaticCall => "staticcall",
+                    _ => "call",
+                }
+                .into(),
+                from: trace.from,
+                to: trace.to,
+                value: trace.value,
+                gas: trace.gas.into(),
+                input: trace.input.clone().into(),
+            },
+            "call",
+        ),
+    };
+    let result = match (trace.call_type, &trace.error) {
+        (_, Some(_)) | (CallType::SelfDestruct, _) => None,
+        (CallType::Create | CallType::Create2, None) => Some(RpcTraceResult::Create {
+            gas_used: trace.gas_used.into(),
+            code: trace.output.clone().into(),
+            address: trace.to,
+        }),
+        (_, None) => Some(RpcTraceResult::Call {
+            gas_used: trace.gas_used.into(),
+            output: trace.output.clone().into(),
+        }),
+    };
+    traces.push(RpcTrace {
+        action,
+        block_hash: location.block_hash,
+        block_number: location.block_number,
+        result,
+        error: trace
+            .error
+            .as_ref()
+            .map(|error| String::from_utf8_lossy(error).into_owned()),
+        subtraces: trace.calls.len() as u32,
+        trace_address: trace_address.clone(),
+        transaction_hash: location.transaction_hash,
+        transaction_position: location.transaction_position,
+        trace_type: trace_type.into(),
+    });
+    for (index, call) in trace.calls.iter().enumerate() {
+        let mut call_address = trace_address.clone();
+        call_address.push(index as u32);
+        flatten_trace(call, call_address, location, traces);
+    }
+}
+
+fn matches_addresses(addresses: &Option<Vec<H160>>, address: H160) -> bool {
+    addresses
+        .as_ref()
+        .map_or(true, |addresses| addresses.contains(&address))
+}
+
+fn ensure_call_tracer(options: Option<TraceOptions>) -> Result<()> {
+    let tracer = options.and_then(|options| options.tracer);
+    if tracer.as_deref() != Some("callTracer") {
+        return Err(Error::invalid_params(
+            "Only the `callTracer` is supported, e.g. `{\"tracer\": \"callTracer\"}`",
+        ));
+    }
+    Ok(())
+}
+
+fn tracing_err(err: impl std::fmt::Debug) -> Error {
+    Error {
+        code: ErrorCode::ServerError(EVM_TRACING_ERROR),
+        message: "EVM tracing error".into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
+
+#[rpc]
+pub trait EvmTracingApi {
+    /// Returns the call trace of the Ethereum transaction `transaction_hash` in the format of
+    /// the `callTracer` of geth.
+    #[rpc(name = "debug_traceTransaction")]
+    fn trace_transaction(
+        &self,
+        transaction_hash: H256,
+        options: Option<TraceOptions>,
+    ) -> Result<RpcCallTrace>;
+
+    /// Returns the call trace of `request` executed on top of the state of the block `number`,
+    /// `latest` if not specified, in the format of the `callTracer` of geth. The call is not
+    /// applied.
+    #[rpc(name = "debug_traceCall")]
+    fn trace_call(
+        &self,
+        request: TraceCallRequest,
+        number: Option<BlockNumber>,
+        options: Option<TraceOptions>,
+    ) -> Result<RpcCallTrace>;
+
+    /// Returns the flat call traces of the Ethereum transactions within the block range of
+    /// `filter`, at most 100 blocks are traced by a call.
+    #[rpc(name = "trace_filter")]
+    fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<RpcTrace>>;
+}
+
+/// A struct that implements the [`EvmTracingApi`].
+pub struct EvmTracing<C, B: BlockT> {
+    client: Arc<C>,
+    backend: Arc<fc_db::Backend<B>>,
+}
+
+impl<C, B: BlockT> EvmTracing<C, B> {
+    /// Create new `EvmTracing` with the given reference to the client and the frontier backend.
+    pub fn new(client: Arc<C>, backend: Arc<fc_db::Backend<B>>) -> Self {
+        Self { client, backend }
+    }
+}
+
+impl<C, B> EvmTracing<C, B>
+where
+    B: BlockT<Hash = H256>,
+    C: ProvideRuntimeApi<B> + HeaderBackend<B> + sc_client_api::BlockBackend<B>,
+    C::Api: EvmTracingRuntimeApi<B>,
+{
+    fn is_canon(&self, hash: B::Hash) -> bool {
+        match self.client.header(BlockId::hash(hash)) {
+            Ok(Some(header)) => self.client.hash(*header.number()).ok().flatten() == Some(hash),
+            _ => false,
+        }
+    }
+
+    /// Returns the canonical block including the Ethereum transaction `transaction_hash`.
+    fn transaction_block(&self, transaction_hash: H256) -> Result<B::Hash> {
+        self.backend
+            .mapping()
+            .transaction_metadata(&transaction_hash)
+            .map_err(tracing_err)?
+            .into_iter()
+            .map(|metadata| metadata.block_hash)
+            .find(|hash| self.is_canon(*hash))
+            .ok_or_else(|| tracing_err(format!("Transaction {:?} not found", transaction_hash)))
+    }
+
+    /// Initializes the block `hash` on top of its parent state, returns the runtime api to
+    /// apply the extrinsics of the block with.
+    fn initialize_block(
+        &self,
+        hash: B::Hash,
+    ) -> Result<(ApiRef<'_, C::Api>, BlockId<B>, Vec<B::Extrinsic>)> {
+        let id = BlockId::hash(hash);
+        let header = self
+            .client
+            .header(id)
+            .map_err(tracing_err)?
+            .ok_or_else(|| tracing_err(format!("Header of {:?} not found", hash)))?;
+        let extrinsics = self
+            .client
+            .block_body(&id)
+            .map_err(tracing_err)?
+            .ok_or_else(|| tracing_err(format!("Body of {:?} not found", hash)))?;
+
+        // The seal is added after the execution when a block is authored.
+        let digest = Digest {
+            logs: header
+                .digest()
+                .logs()
+                .iter()
+                .filter(|item| item.as_seal().is_none())
+                .cloned()
+                .collect(),
+        };
+        let header = <B::Header as HeaderT>::new(
+            *header.number(),
+            Default::default(),
+            Default::default(),
+            *header.parent_hash(),
+            digest,
+        );
+        let parent = BlockId::hash(*header.parent_hash());
+
+        let api = self.client.runtime_api();
+        api.initialize_block(&parent, &header)
+            .map_err(runtime_error_into_rpc_err)?;
+        Ok((api, parent, extrinsics))
+    }
+
+    fn block_number(&self, number: Option<BlockNumber>) -> Result<u64> {
+        let best = self.client.info().best_number.saturated_into::<u64>();
+        match number {
+            None | Some(BlockNumber::Latest) | Some(BlockNumber::Pending) => Ok(best),
+            Some(BlockNumber::Earliest) => Ok(0),
+            Some(BlockNumber::Num(number)) if number <= best => Ok(number),
+            Some(BlockNumber::Num(number)) => Err(Error::invalid_params(format!(
+                "Block #{} is beyond the best block #{}",
+                number, best
+            ))),
+            Some(BlockNumber::Hash { .. }) => Err(Error::invalid_params(
+                "Only the block numbers are supported",
+            )),
+        }
+    }
+}
+
+impl<C, B> EvmTracingApi for EvmTracing<C, B>
+where
+    B: BlockT<Hash = H256>,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<B> + HeaderBackend<B> + sc_client_api::BlockBackend<B>,
+    C::Api: EvmTracingRuntimeApi<B> + EthereumRuntimeRPCApi<B>,
+{
+    fn trace_transaction(
+        &self,
+        transaction_hash: H256,
+        options: Option<TraceOptions>,
+    ) -> Result<RpcCallTrace> {
+        ensure_call_tracer(options)?;
+
+        let block_hash = self.transaction_block(transaction_hash)?;
+        let (api, parent, extrinsics) = self.initialize_block(block_hash)?;
+        api.trace_transaction(&parent, extrinsics, transaction_hash)
+            .map_err(runtime_error_into_rpc_err)?
+            .map_err(tracing_err)?
+            .map(Into::into)
+            .ok_or_else(|| tracing_err(format!("Transaction {:?} not traced", transaction_hash)))
+    }
+
+    fn trace_call(
+        &self,
+        request: TraceCallRequest,
+        number: Option<BlockNumber>,
+        options: Option<TraceOptions>,
+    ) -> Result<RpcCallTrace> {
+        ensure_call_tracer(options)?;
+
+        let number = self.block_number(number)?;
+        let hash = self
+            .client
+            .hash(number.saturated_into::<NumberFor<B>>())
+            .map_err(tracing_err)?
+            .ok_or_else(|| tracing_err(format!("Block #{} not found", number)))?;
+        let id = BlockId::hash(hash);
+
+        let api = self.client.runtime_api();
+        let gas_limit = match request.gas {
+            Some(gas) => gas,
+            None => {
+                api.current_block(&id)
+                    .map_err(runtime_error_into_rpc_err)?
+                    .ok_or_else(|| tracing_err(format!("Block #{} has no EVM", number)))?
+                    .header
+                    .gas_limit
+            }
+        };
+        let access_list = request
+            .access_list
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| (entry.address, entry.storage_keys))
+            .collect();
+
+        api.trace_call(
+            &id,
+            request.from.unwrap_or_default(),
+            request.to,
+            request.data.map(|data| data.0).unwrap_or_default(),
+            request.value.unwrap_or_default(),
+            gas_limit,
+            request.max_fee_per_gas.or(request.gas_price),
+            request.max_priority_fee_per_gas,
+            request.nonce,
+            access_list,
+        )
+        .map_err(runtime_error_into_rpc_err)?
+        .map_err(tracing_err)
+        .map(Into::into)
+    }
+
+    fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<RpcTrace>> {
+        let from = self.block_number(filter.from_block.clone())?;
+        let to = self.block_number(filter.to_block.clone())?;
+        if from > to {
+            return Err(Error::invalid_params("`fromBlock` is after `toBlock`"));
+        }
+        if to - from >= MAX_TRACE_FILTER_BLOCKS {
+            return Err(Error::invalid_params(format!(
+                "At most {} blocks can be traced at once",
+                MAX_TRACE_FILTER_BLOCKS
+            )));
+        }
+
+        let mut traces = Vec::new();
+        for number in from..=to {
+            let hash = self
+                .client
+                .hash(number.saturated_into::<NumberFor<B>>())
+                .map_err(tracing_err)?
+                .ok_or_else(|| tracing_err(format!("Block #{} not found", number)))?;
+            let ethereum_block = self
+                .client
+                .runtime_api()
+                .current_block(&BlockId::hash(hash))
+                .map_err(runtime_error_into_rpc_err)?;
+            let block_hash = match ethereum_block {
+                Some(block) => block.header.hash(),
+                // No Ethereum transaction before the EVM was enabled.
+                None => continue,
+            };
+
+            let (api, parent, extrinsics) = self.initialize_block(hash)?;
+            let transaction_traces = api
+                .trace_block(&parent, extrinsics)
+                .map_err(runtime_error_into_rpc_err)?
+                .map_err(tracing_err)?;
+            for transaction_trace in transaction_traces {
+                let location = TraceLocation {
+                    block_hash,
+                    block_number: number,
+                    transaction_hash: transaction_trace.transaction_hash,
+                    transaction_position: transaction_trace.transaction_index,
+                };
+                flatten_trace(&transaction_trace.trace, vec![], &location, &mut traces);
+            }
+        }
+
+        Ok(traces
+            .into_iter()
+            .filter(|trace| {
+                let (from, to) = match trace.action {
+                    RpcTraceAction::Call { from, to, .. } => (from, to),
+                    RpcTraceAction::Create { from, .. } => (from, H160::zero()),
+                    RpcTraceAction::Suicide {
+                        address,
+                        refund_address,
+                        ..
+                    } => (address, refund_address),
+                };
+                let to = match &trace.result {
+                    Some(RpcTraceResult::Create { address, .. }) => *address,
+                    _ => to,
+                };
+                matches_addresses(&filter.from_address, from)
+                    && matches_addresses(&filter.to_address, to)
+            })
+            .skip(filter.after.unwrap_or_default() as usize)
+            .take(filter.count.map_or(usize::MAX, |count| count as usize))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_call_request_should_be_deserialized() {
+        let request: TraceCallRequest = serde_json::from_str(
+            r#"{
+                "from": "0x0000000000000000000000000000000000000001",
+                "to": "0x0000000000000000000000000000000000000002",
+                "gas": "0x5208",
+                "maxFeePerGas": "0x10",
+                "data": "0x1234",
+                "accessList": [{
+                    "address": "0x0000000000000000000000000000000000000003",
+                    "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000004"]
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            TraceCallRequest {
+                from: Some(H160::from_low_u64_be(1)),
+                to: H160::from_low_u64_be(2),
+                gas: Some(21000.into()),
+                max_fee_per_gas: Some(16.into()),
+                data: Some(vec![0x12, 0x34].into()),
+                access_list: Some(vec![AccessListEntry {
+                    address: H160::from_low_u64_be(3),
+                    storage_keys: vec![H256::from_low_u64_be(4)],
+                }]),
+                ..Default::default()
+            }
+        );
+
+        // The callee is required.
+        assert!(serde_json::from_str::<TraceCallRequest>("{}").is_err());
+    }
+
+    #[test]
+    fn only_call_tracer_should_be_supported() {
+        assert!(ensure_call_tracer(Some(TraceOptions {
+            tracer: Some("callTracer".into())
+        }))
+        .is_ok());
+        assert!(ensure_call_tracer(None).is_err());
+        assert!(ensure_call_tracer(Some(TraceOptions {
+            tracer: Some("prestateTracer".into())
+        }))
+        .is_err());
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! RPC interface of the governance overview.
+
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xp_governance::{
+    GovernanceApi as GovernanceRuntimeApi, GovernanceOverview, ReferendumOverview,
+    TreasuryProposalOverview,
+};
+use xp_rpc::{runtime_error_into_rpc_err, RpcBalance};
+
+#[rpc]
+pub trait ChainXGovernanceApi<BlockHash, AccountId, Balance, BlockNumber, Hash>
+where
+    Balance: Display + FromStr,
+{
+    /// Returns the ongoing referenda, the open council and technical committee motions and
+    /// the pending treasury proposals at block `at`.
+    #[rpc(name = "chainx_governanceOverview")]
+    fn governance_overview(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<GovernanceOverview<AccountId, RpcBalance<Balance>, BlockNumber, Hash>>;
+}
+
+/// A struct that implements the [`ChainXGovernanceApi`].
+pub struct ChainXGovernance<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> ChainXGovernance<C, B> {
+    /// Create new `ChainXGovernance` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber, Hash>
+    ChainXGovernanceApi<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber, Hash>
+    for ChainXGovernance<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: GovernanceRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+    AccountId: Codec,
+    Balance: Codec + Display + FromStr,
+    BlockNumber: Codec,
+    Hash: Codec,
+{
+    fn governance_overview(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<GovernanceOverview<AccountId, RpcBalance<Balance>, BlockNumber, Hash>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.governance_overview(&at)
+            .map(|overview| GovernanceOverview {
+                referenda: overview
+                    .referenda
+                    .into_iter()
+                    .map(|referendum| ReferendumOverview {
+                        index: referendum.index,
+                        end: referendum.end,
+                        proposal_hash: referendum.proposal_hash,
+                        threshold: referendum.threshold,
+                        delay: referendum.delay,
+                        ayes: referendum.ayes.into(),
+                        nays: referendum.nays.into(),
+                        turnout: referendum.turnout.into(),
+                    })
+                    .collect(),
+                council_motions: overview.council_motions,
+                technical_motions: overview.technical_motions,
+                treasury_proposals: overview
+                    .treasury_proposals
+                    .into_iter()
+                    .map(|proposal| TreasuryProposalOverview {
+                        index: proposal.index,
+                        proposer: proposal.proposer,
+                        value: proposal.value.into(),
+                        beneficiary: proposal.beneficiary,
+                        bond: proposal.bond.into(),
+                        approved: proposal.approved,
+                    })
+                    .collect(),
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
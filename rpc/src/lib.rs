@@ -1,5 +1,15 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
+pub mod access_control;
+pub mod address;
+pub mod bootstrap;
+pub mod compat;
+pub mod error_registry;
+pub mod evm_prevalidation;
+pub mod evm_tracing;
+pub mod governance;
+pub mod network;
+
 use std::sync::Arc;
 
 use sc_client_api::AuxStore;
@@ -87,6 +97,8 @@ pub struct FrontierDeps<A: sc_transaction_pool::ChainApi> {
     pub overrides: Arc<OverrideHandle<Block>>,
     /// Cache for Ethereum block data.
     pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+    /// Whether to enable the EVM tracing RPC, which requires the tracing runtime.
+    pub enable_evm_tracing: bool,
 }
 
 /// Full client dependencies.
@@ -107,6 +119,14 @@ pub struct FullDeps<C, P, SC, B, A: sc_transaction_pool::ChainApi> {
     pub grandpa: GrandpaDeps<B>,
     /// Frontier specific dependencies.
     pub frontier: FrontierDeps<A>,
+    /// The network service of the node.
+    pub network: Arc<NetworkService<Block, Hash>>,
+    /// The peer bans enforced on the network.
+    pub peer_bans: Arc<network::PeerBans<C>>,
+    /// The access control of the methods, `None` if `--rpc-methods-acl` is not given.
+    pub access_control: Option<Arc<access_control::RpcAccessControl>>,
+    /// The maximum payload of the RPC responses in MiB.
+    pub rpc_max_payload: Option<usize>,
 }
 
 pub fn overrides_handle<C, B>(client: Arc<C>) -> Arc<OverrideHandle<Block>>
@@ -159,13 +179,19 @@ where
         + HeaderMetadata<Block, Error = BlockChainError>
         + StorageProvider<Block, B>
         + BlockchainEvents<Block>
+        + sc_client_api::BlockBackend<Block>
+        + sc_client_api::ExecutorProvider<Block>
+        + sc_client_api::ProofProvider<Block>
+        + sp_api::CallApiAt<Block>
         + Send
         + Sync
         + 'static,
     C::Api: BlockBuilder<Block>,
+    C::Api: sp_api::Metadata<Block>,
     C::Api: BabeApi<Block>,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
     C::Api: xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance>,
     C::Api:
         xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance>,
@@ -198,14 +224,24 @@ where
     >,
     C::Api: xpallet_btc_ledger_runtime_api::BtcLedgerApi<Block, AccountId, Balance>,
     C::Api: xpallet_transaction_fee_rpc_runtime_api::XTransactionFeeApi<Block, Balance>,
+    C::Api: xp_governance::GovernanceApi<Block, AccountId, Balance, BlockNumber, Hash>,
+    C::Api: xp_address::AddressApi<Block>,
     C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
     C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
+    C::Api: xp_evm_tracing::EvmTracingApi<Block>,
     P: TransactionPool<Block = Block> + Sync + Send + 'static,
     SC: SelectChain<Block> + 'static,
     B: sc_client_api::Backend<Block> + Send + Sync + 'static,
     B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
     A: ChainApi<Block = Block> + 'static,
 {
+    use address::{ChainXAddress, ChainXAddressApi};
+    use bootstrap::{ChainXBootstrap, ChainXBootstrapApi};
+    use compat::{ChainXCompat, ChainXCompatApi};
+    use error_registry::{ChainXError, ChainXErrorApi};
+    use governance::{ChainXGovernance, ChainXGovernanceApi};
+    use network::{ChainXNetwork, ChainXNetworkApi};
+    use pallet_contracts_rpc::{Contracts, ContractsApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
     use xpallet_assets_rpc::{Assets, XAssetsApi};
@@ -228,6 +264,10 @@ where
         grandpa,
         babe,
         frontier,
+        network,
+        peer_bans,
+        access_control,
+        rpc_max_payload,
     } = deps;
 
     let BabeDeps {
@@ -251,6 +291,7 @@ where
     io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
         client.clone(),
     )));
+    io.extend_with(ContractsApi::to_delegate(Contracts::new(client.clone())));
     io.extend_with(sc_consensus_babe_rpc::BabeApi::to_delegate(
         BabeRpcHandler::new(
             client.clone(),
@@ -270,6 +311,8 @@ where
             finality_provider,
         ),
     ));
+    // The chain spec is moved into the sync state RPC.
+    let properties = chain_spec.properties();
     io.extend_with(sc_sync_state_rpc::SyncStateRpcApi::to_delegate(
         sc_sync_state_rpc::SyncStateRpcHandler::new(
             chain_spec,
@@ -298,8 +341,48 @@ where
         client.clone(),
     )));
     io.extend_with(BtcLedgerApi::to_delegate(BtcLedger::new(client.clone())));
+    io.extend_with(ChainXErrorApi::to_delegate(ChainXError::new(
+        client.clone(),
+    )));
+    io.extend_with(ChainXGovernanceApi::to_delegate(ChainXGovernance::new(
+        client.clone(),
+    )));
+    io.extend_with(ChainXAddressApi::to_delegate(ChainXAddress::new(
+        client.clone(),
+    )));
+    io.extend_with(ChainXCompatApi::to_delegate(ChainXCompat::<_, _, B>::new(
+        client.clone(),
+    )));
+    io.extend_with(ChainXBootstrapApi::to_delegate(ChainXBootstrap::new(
+        client.clone(),
+        properties,
+    )));
+    io.extend_with(ChainXNetworkApi::to_delegate(ChainXNetwork::new(
+        network,
+        peer_bans,
+        deny_unsafe,
+    )));
+
+    if access_control.is_some() {
+        // The state methods are served here as well so that the ACL applies to them, they
+        // override the ones of the service.
+        let (state, child_state) = sc_rpc::state::new_full(
+            client.clone(),
+            subscription_task_executor.clone(),
+            deny_unsafe,
+            rpc_max_payload,
+        );
+        io.extend_with(sc_rpc_api::state::StateApi::to_delegate(state));
+        io.extend_with(sc_rpc_api::child_state::ChildStateApi::to_delegate(
+            child_state,
+        ));
+    }
 
     // EVM
+    //
+    // ERC-4337 user operations are not handled by the node: a bundler submits them to the
+    // EntryPoint contract as normal transactions through `eth_sendRawTransaction`, and
+    // validates them with `debug_traceCall` of the EVM tracing RPC.
     {
         use fc_rpc::{
             EthApi, EthApiServer, EthFilterApi, EthFilterApiServer, EthPubSubApi,
@@ -317,6 +400,7 @@ where
             fee_history_cache,
             overrides,
             block_data_cache,
+            enable_evm_tracing,
         } = frontier;
 
         let convert_transaction: Option<Never> = None;
@@ -338,6 +422,15 @@ where
             fee_history_cache,
         )));
 
+        // Must be applied after `eth_sendRawTransaction` is registered.
+        evm_prevalidation::prevalidate_raw_transactions(&mut io);
+
+        if enable_evm_tracing {
+            io.extend_with(evm_tracing::EvmTracingApi::to_delegate(
+                evm_tracing::EvmTracing::new(client.clone(), backend.clone()),
+            ));
+        }
+
         if let Some(filter_pool) = filter_pool {
             io.extend_with(EthFilterApiServer::to_delegate(EthFilterApi::new(
                 client.clone(),
@@ -370,5 +463,13 @@ where
         )));
     }
 
+    // Must be the last one so that the replacements of the deprecated methods are available.
+    compat::add_deprecated_methods(&mut io);
+
+    // Must be applied after all the methods are registered.
+    if let Some(access_control) = access_control {
+        io = access_control::guard_methods(io, access_control);
+    }
+
     Ok(io)
 }
@@ -0,0 +1,373 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! RPC interface of the peer reputations and the peer bans of the node.
+//!
+//! The reputations of the peers are kept by the peerset of the network, a misbehaving peer is
+//! disconnected once its reputation drops below the ban threshold, but the reputation recovers
+//! over time and the operators have no way to intervene.
+//!
+//! A peer banned via `chainx_banPeer` is reported with a fatal reputation change, which also
+//! disconnects it. The bans are persisted in the aux storage of the client and enforced by
+//! [`run_peer_bans_worker`]: a banned peer reconnecting after its reputation has recovered is
+//! reported again until the ban expires or `chainx_unbanPeer` is called.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use codec::{Decode, Encode};
+use futures::{FutureExt, StreamExt};
+use jsonrpc_core::{BoxFuture, Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use sc_client_api::AuxStore;
+use sc_network::{Event, NetworkService, PeerId, ReputationChange};
+use sc_rpc_api::DenyUnsafe;
+use sp_blockchain::Result as ClientResult;
+
+use chainx_primitives::{Block, Hash};
+
+const LOG_TARGET: &str = "peer-bans";
+
+/// Aux storage key of the peer bans.
+const PEER_BANS_KEY: &[u8] = b"chainx:network:peer_bans";
+
+/// The peer id is not a valid base58 encoded libp2p peer id.
+const INVALID_PEER_ID_ERROR: i64 = 20000;
+/// Failed to query or persist the state of the network.
+const NETWORK_ERROR: i64 = INVALID_PEER_ID_ERROR + 1;
+
+/// A ban of a peer.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerBan {
+    /// Base58 encoded peer id.
+    pub peer_id: String,
+    /// Unix timestamp in milliseconds at which the ban expires, `None` for a permanent ban.
+    pub banned_until: Option<u64>,
+    /// Why the peer was banned.
+    pub reason: String,
+}
+
+impl PeerBan {
+    fn is_expired(&self, now: u64) -> bool {
+        self.banned_until.map_or(false, |until| until <= now)
+    }
+}
+
+/// A connected peer with its reputation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerReputation {
+    /// Base58 encoded peer id.
+    pub peer_id: String,
+    /// The version string reported by the peer.
+    pub version: Option<String>,
+    /// The reputation of the peer in the peerset, `None` if the peerset does not track it.
+    pub reputation: Option<i64>,
+    /// The ban of the peer, if any.
+    pub ban: Option<PeerBan>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn invalid_peer_id_err(peer_id: &str) -> Error {
+    Error {
+        code: ErrorCode::ServerError(INVALID_PEER_ID_ERROR),
+        message: "Invalid peer id".into(),
+        data: Some(peer_id.into()),
+    }
+}
+
+fn network_err(err: impl std::fmt::Debug) -> Error {
+    Error {
+        code: ErrorCode::ServerError(NETWORK_ERROR),
+        message: "Network error".into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
+
+/// The peer bans persisted in the aux storage of the client.
+pub struct PeerBans<C> {
+    client: Arc<C>,
+    bans: Mutex<BTreeMap<String, PeerBan>>,
+}
+
+impl<C: AuxStore> PeerBans<C> {
+    /// Loads the peer bans from the aux storage of `client`.
+    pub fn load(client: Arc<C>) -> ClientResult<Self> {
+        let bans = match client.get_aux(PEER_BANS_KEY)? {
+            Some(raw) => Vec::<PeerBan>::decode(&mut &raw[..]).map_err(|e| {
+                sp_blockchain::Error::Backend(format!("Peer bans are corrupted: {}", e))
+            })?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            client,
+            bans: Mutex::new(
+                bans.into_iter()
+                    .map(|ban| (ban.peer_id.clone(), ban))
+                    .collect(),
+            ),
+        })
+    }
+
+    fn persist(&self, bans: &BTreeMap<String, PeerBan>) -> ClientResult<()> {
+        let encoded = bans.values().cloned().collect::<Vec<_>>().encode();
+        self.client
+            .insert_aux(&[(PEER_BANS_KEY, &encoded[..])], &[])
+    }
+
+    /// Bans `peer_id` for `duration`, or permanently if `duration` is `None`.
+    pub fn ban(
+        &self,
+        peer_id: String,
+        duration: Option<Duration>,
+        reason: String,
+    ) -> ClientResult<PeerBan> {
+        let ban = PeerBan {
+            peer_id: peer_id.clone(),
+            banned_until: duration.map(|d| now().saturating_add(d.as_millis() as u64)),
+            reason,
+        };
+        let mut bans = self.bans.lock().expect("Peer bans lock is poisoned; qed");
+        bans.insert(peer_id, ban.clone());
+        self.persist(&bans)?;
+        Ok(ban)
+    }
+
+    /// Lifts the ban of `peer_id`, returns false if it was not banned.
+    pub fn unban(&self, peer_id: &str) -> ClientResult<bool> {
+        let mut bans = self.bans.lock().expect("Peer bans lock is poisoned; qed");
+        if bans.remove(peer_id).is_none() {
+            return Ok(false);
+        }
+        self.persist(&bans)?;
+        Ok(true)
+    }
+
+    /// Returns the ban of `peer_id` if it's still active.
+    pub fn ban_of(&self, peer_id: &str) -> Option<PeerBan> {
+        let bans = self.bans.lock().expect("Peer bans lock is poisoned; qed");
+        bans.get(peer_id)
+            .filter(|ban| !ban.is_expired(now()))
+            .cloned()
+    }
+
+    /// Returns all the active bans.
+    pub fn active_bans(&self) -> Vec<PeerBan> {
+        let now = now();
+        let bans = self.bans.lock().expect("Peer bans lock is poisoned; qed");
+        bans.values()
+            .filter(|ban| !ban.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Removes the expired bans, returns the peer ids of them.
+    pub fn prune_expired(&self) -> ClientResult<Vec<String>> {
+        let now = now();
+        let mut bans = self.bans.lock().expect("Peer bans lock is poisoned; qed");
+        let expired = bans
+            .values()
+            .filter(|ban| ban.is_expired(now))
+            .map(|ban| ban.peer_id.clone())
+            .collect::<Vec<_>>();
+        if !expired.is_empty() {
+            for peer_id in &expired {
+                bans.remove(peer_id);
+            }
+            self.persist(&bans)?;
+        }
+        Ok(expired)
+    }
+}
+
+fn ban_peer(network: &NetworkService<Block, Hash>, peer_id: PeerId) {
+    network.report_peer(
+        peer_id,
+        ReputationChange::new_fatal("Banned by the operator"),
+    );
+}
+
+fn unban_peer(network: &NetworkService<Block, Hash>, peer_id: PeerId) {
+    network.report_peer(
+        peer_id,
+        ReputationChange::new(i32::MAX, "Unbanned by the operator"),
+    );
+}
+
+/// Returns the reputations of the peers tracked by the peerset, given its debug info.
+fn peerset_reputations(peerset: &serde_json::Value) -> BTreeMap<String, i64> {
+    let mut reputations = BTreeMap::new();
+    let sets = peerset["sets"].as_array().cloned().unwrap_or_default();
+    for set in sets.iter().chain(std::iter::once(peerset)) {
+        if let Some(nodes) = set["nodes"].as_object() {
+            for (peer_id, node) in nodes {
+                if let Some(reputation) = node["reputation"].as_i64() {
+                    reputations.entry(peer_id.clone()).or_insert(reputation);
+                }
+            }
+        }
+    }
+    reputations
+}
+
+/// Enforces the peer bans, the banned peers are reported again once they reconnect and the
+/// expired bans are lifted.
+pub async fn run_peer_bans_worker<C: AuxStore>(
+    network: Arc<NetworkService<Block, Hash>>,
+    bans: Arc<PeerBans<C>>,
+) {
+    for ban in bans.active_bans() {
+        if let Ok(peer_id) = PeerId::from_str(&ban.peer_id) {
+            ban_peer(&network, peer_id);
+        }
+    }
+
+    let mut events = network.event_stream(LOG_TARGET);
+    while let Some(event) = events.next().await {
+        match bans.prune_expired() {
+            Ok(expired) => {
+                for peer_id in expired {
+                    log::info!(target: LOG_TARGET, "The ban of {} has expired", peer_id);
+                    if let Ok(peer_id) = PeerId::from_str(&peer_id) {
+                        unban_peer(&network, peer_id);
+                    }
+                }
+            }
+            Err(e) => log::warn!(target: LOG_TARGET, "Failed to prune the peer bans: {:?}", e),
+        }
+
+        let remote = match event {
+            Event::SyncConnected { remote } | Event::NotificationStreamOpened { remote, .. } => {
+                remote
+            }
+            _ => continue,
+        };
+        if bans.ban_of(&remote.to_base58()).is_some() {
+            log::debug!(target: LOG_TARGET, "Banned peer {} reconnected", remote);
+            ban_peer(&network, remote);
+        }
+    }
+}
+
+/// ChainX network RPC methods.
+#[rpc]
+pub trait ChainXNetworkApi {
+    /// Returns the connected peers with their reputations and bans.
+    #[rpc(name = "chainx_peerReputations")]
+    fn peer_reputations(&self) -> BoxFuture<Result<Vec<PeerReputation>>>;
+
+    /// Returns the active peer bans, including the ones of the disconnected peers.
+    #[rpc(name = "chainx_bannedPeers")]
+    fn banned_peers(&self) -> Result<Vec<PeerBan>>;
+
+    /// Bans the peer for `duration` seconds, or permanently if `duration` is not given.
+    ///
+    /// The ban survives the restarts of the node.
+    #[rpc(name = "chainx_banPeer")]
+    fn ban_peer(
+        &self,
+        peer_id: String,
+        duration: Option<u64>,
+        reason: Option<String>,
+    ) -> Result<PeerBan>;
+
+    /// Lifts the ban of the peer, returns false if the peer was not banned.
+    #[rpc(name = "chainx_unbanPeer")]
+    fn unban_peer(&self, peer_id: String) -> Result<bool>;
+}
+
+/// A struct that implements the [`ChainXNetworkApi`].
+pub struct ChainXNetwork<C> {
+    network: Arc<NetworkService<Block, Hash>>,
+    bans: Arc<PeerBans<C>>,
+    deny_unsafe: DenyUnsafe,
+}
+
+impl<C> ChainXNetwork<C> {
+    /// Create new `ChainXNetwork` with the given network service and peer bans.
+    pub fn new(
+        network: Arc<NetworkService<Block, Hash>>,
+        bans: Arc<PeerBans<C>>,
+        deny_unsafe: DenyUnsafe,
+    ) -> Self {
+        Self {
+            network,
+            bans,
+            deny_unsafe,
+        }
+    }
+}
+
+impl<C> ChainXNetworkApi for ChainXNetwork<C>
+where
+    C: AuxStore + Send + Sync + 'static,
+{
+    fn peer_reputations(&self) -> BoxFuture<Result<Vec<PeerReputation>>> {
+        if let Err(e) = self.deny_unsafe.check_if_safe() {
+            return futures::future::err(e.into()).boxed();
+        }
+
+        let network = self.network.clone();
+        let bans = self.bans.clone();
+        async move {
+            let state = network.network_state().await.map_err(network_err)?;
+            let reputations = peerset_reputations(&state.peerset);
+            Ok(state
+                .connected_peers
+                .into_iter()
+                .map(|(peer_id, peer)| PeerReputation {
+                    reputation: reputations.get(&peer_id).copied(),
+                    ban: bans.ban_of(&peer_id),
+                    version: peer.version_string,
+                    peer_id,
+                })
+                .collect())
+        }
+        .boxed()
+    }
+
+    fn banned_peers(&self) -> Result<Vec<PeerBan>> {
+        self.deny_unsafe.check_if_safe()?;
+        Ok(self.bans.active_bans())
+    }
+
+    fn ban_peer(
+        &self,
+        peer_id: String,
+        duration: Option<u64>,
+        reason: Option<String>,
+    ) -> Result<PeerBan> {
+        self.deny_unsafe.check_if_safe()?;
+        let peer = PeerId::from_str(&peer_id).map_err(|_| invalid_peer_id_err(&peer_id))?;
+        let ban = self
+            .bans
+            .ban(
+                peer.to_base58(),
+                duration.map(Duration::from_secs),
+                reason.unwrap_or_default(),
+            )
+            .map_err(network_err)?;
+        ban_peer(&self.network, peer);
+        Ok(ban)
+    }
+
+    fn unban_peer(&self, peer_id: String) -> Result<bool> {
+        self.deny_unsafe.check_if_safe()?;
+        let peer = PeerId::from_str(&peer_id).map_err(|_| invalid_peer_id_err(&peer_id))?;
+        let unbanned = self.bans.unban(&peer.to_base58()).map_err(network_err)?;
+        if unbanned {
+            unban_peer(&self.network, peer);
+        }
+        Ok(unbanned)
+    }
+}
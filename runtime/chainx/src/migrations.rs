@@ -8,6 +8,16 @@ impl frame_support::traits::OnRuntimeUpgrade for RemoveCollectiveFlip {
         migration::remove_storage_prefix(b"RandomnessCollectiveFlip", b"RandomMaterial", b"");
         <Runtime as frame_system::Config>::DbWeight::get().writes(1)
     }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        use frame_support::storage::migration;
+        frame_support::ensure!(
+            !migration::have_storage_value(b"RandomnessCollectiveFlip", b"RandomMaterial", b""),
+            "RandomMaterial still exists after migration"
+        );
+        Ok(())
+    }
 }
 
 /// Migrate from `PalletVersion` to the new `StorageVersion`
@@ -225,6 +235,21 @@ impl frame_support::traits::OnRuntimeUpgrade for ElectionsPrefixMigration {
             .expect("Elections is part of runtime, so it has a name; qed");
         pallet_elections_phragmen::migrations::v4::migrate::<Runtime, _>(name)
     }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<(), &'static str> {
+        use frame_support::traits::PalletInfo;
+        let name = <Runtime as frame_system::Config>::PalletInfo::name::<Elections>()
+            .expect("Elections is part of runtime, so it has a name; qed");
+        pallet_elections_phragmen::migrations::v4::pre_migration::<Elections, _>(name);
+        Ok(())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        pallet_elections_phragmen::migrations::v4::post_migration::<Elections>();
+        Ok(())
+    }
 }
 
 pub struct XGatewayCommonStorageMigration;
@@ -232,6 +257,42 @@ impl frame_support::traits::OnRuntimeUpgrade for XGatewayCommonStorageMigration
     fn on_runtime_upgrade() -> frame_support::weights::Weight {
         xpallet_gateway_common::migrations::taproot::apply::<Runtime>()
     }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<(), &'static str> {
+        xpallet_gateway_common::migrations::taproot::pre_migrate::<Runtime>()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        xpallet_gateway_common::migrations::taproot::post_migrate::<Runtime>()
+    }
+}
+
+/// Record the members of the existing trustee sessions.
+pub struct XGatewayCommonSessionMembersMigration;
+impl frame_support::traits::OnRuntimeUpgrade for XGatewayCommonSessionMembersMigration {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        xpallet_gateway_common::migrations::session_members::apply::<Runtime>()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        xpallet_gateway_common::migrations::session_members::post_migrate::<Runtime>()
+    }
+}
+
+/// Compute the totals of the applying withdrawals.
+pub struct XGatewayRecordsApplyingWithdrawalsMigration;
+impl frame_support::traits::OnRuntimeUpgrade for XGatewayRecordsApplyingWithdrawalsMigration {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        xpallet_gateway_records::migrations::applying_withdrawals::apply::<Runtime>()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        xpallet_gateway_records::migrations::applying_withdrawals::post_migrate::<Runtime>()
+    }
 }
 
 use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
@@ -320,6 +381,22 @@ impl OnRuntimeUpgrade for CustomOnRuntimeUpgrades {
         weight += <XGatewayCommonStorageMigration as OnRuntimeUpgrade>::on_runtime_upgrade();
         frame_support::log::info!("🚀 XGatewayCommonStorageMigration end");
 
+        // 15. XGatewayCommonSessionMembersMigration
+        frame_support::log::info!("🔍️ XGatewayCommonSessionMembersMigration start");
+        weight += <XGatewayCommonSessionMembersMigration as OnRuntimeUpgrade>::on_runtime_upgrade();
+        frame_support::log::info!("🚀 XGatewayCommonSessionMembersMigration end");
+
         weight
     }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<(), &'static str> {
+        <XGatewayCommonStorageMigration as OnRuntimeUpgrade>::pre_upgrade()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        <XGatewayCommonStorageMigration as OnRuntimeUpgrade>::post_upgrade()?;
+        <XGatewayCommonSessionMembersMigration as OnRuntimeUpgrade>::post_upgrade()
+    }
 }
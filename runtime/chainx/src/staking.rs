@@ -0,0 +1,242 @@
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use fp_evm::{
+    Context, ExitRevert, ExitSucceed, PrecompileFailure, PrecompileOutput, PrecompileResult,
+};
+use frame_support::{log, weights::Weight};
+use frame_system::RawOrigin;
+use pallet_evm::{AddressMapping, GasWeightMapping, Precompile};
+use sp_core::{H160, U256};
+use sp_runtime::{
+    traits::{SaturatedConversion, StaticLookup, UniqueSaturatedInto, Zero},
+    AccountId32, DispatchResult,
+};
+use sp_std::vec::Vec;
+
+use xpallet_mining_staking::WeightInfo;
+
+const BASE_GAS_COST: u64 = 100_000;
+
+/// The staking precompile, through which the EVM contracts, e.g. the liquid staking protocols,
+/// nominate the validators with the PCX of the account derived from the contract address.
+///
+/// input = (flag, 1 byte) + args, the validators are passed as the substrate pubkeys and the
+/// values as the 32 bytes big endian PCX balances (decimals=8):
+/// - 0 bond: validator(32 bytes) + value(32 bytes)
+/// - 1 unbond: validator(32 bytes) + value(32 bytes)
+/// - 2 rebond: from(32 bytes) + to(32 bytes) + value(32 bytes)
+/// - 3 unlock unbonded withdrawal: validator(32 bytes) + unbonded index(32 bytes)
+/// - 4 claim: validator(32 bytes)
+/// - 5 nomination ledger: nominator(evm address, 20 bytes) + validator(32 bytes)
+///
+/// The nomination ledger is returned as nomination(32 bytes) + unbonded(32 bytes) +
+/// unbonded chunks(32 bytes), the others return true. Only the nomination ledger can be
+/// queried by a static call, the others are only allowed for the contracts in the allowlist
+/// of `xpallet_mining_staking` once it's enabled by the council.
+pub struct Staking<T: xpallet_mining_staking::Config + pallet_evm::Config> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: xpallet_mining_staking::Config + pallet_evm::Config> Staking<T> {
+    fn process(
+        caller: &H160,
+        input: &[u8],
+        is_static: bool,
+    ) -> Result<(Vec<u8>, u64), PrecompileFailure> {
+        let flag = input.first().copied();
+        if flag != Some(5) {
+            frame_support::ensure!(!is_static, Self::revert("can not stake in a static call"));
+            frame_support::ensure!(
+                xpallet_mining_staking::Pallet::<T>::is_evm_staker_allowed(caller),
+                Self::revert("caller is not allowed to stake")
+            );
+        }
+
+        let who = T::AddressMapping::into_account_id(*caller);
+        let origin = || RawOrigin::Signed(who.clone()).into();
+
+        match flag {
+            // Bond
+            Some(0) if input.len() == 65 => {
+                let target = Self::account_from_pubkey(&input[1..33])?;
+                let value = Self::balance(&input[33..65])?;
+                log::debug!(target: "evm-staking", "bond: {:?}, {:?}", target, value);
+
+                Self::dispatch(
+                    xpallet_mining_staking::Pallet::<T>::bond(
+                        origin(),
+                        T::Lookup::unlookup(target),
+                        value,
+                    ),
+                    <T as xpallet_mining_staking::Config>::WeightInfo::bond(),
+                )
+            }
+            // Unbond
+            Some(1) if input.len() == 65 => {
+                let target = Self::account_from_pubkey(&input[1..33])?;
+                let value = Self::balance(&input[33..65])?;
+                log::debug!(target: "evm-staking", "unbond: {:?}, {:?}", target, value);
+
+                Self::dispatch(
+                    xpallet_mining_staking::Pallet::<T>::unbond(
+                        origin(),
+                        T::Lookup::unlookup(target),
+                        value,
+                    ),
+                    <T as xpallet_mining_staking::Config>::WeightInfo::unbond(),
+                )
+            }
+            // Rebond
+            Some(2) if input.len() == 97 => {
+                let from = Self::account_from_pubkey(&input[1..33])?;
+                let to = Self::account_from_pubkey(&input[33..65])?;
+                let value = Self::balance(&input[65..97])?;
+                log::debug!(target: "evm-staking", "rebond: {:?}, {:?}, {:?}", from, to, value);
+
+                Self::dispatch(
+                    xpallet_mining_staking::Pallet::<T>::rebond(
+                        origin(),
+                        T::Lookup::unlookup(from),
+                        T::Lookup::unlookup(to),
+                        value,
+                    ),
+                    <T as xpallet_mining_staking::Config>::WeightInfo::rebond(),
+                )
+            }
+            // Unlock unbonded withdrawal
+            Some(3) if input.len() == 65 => {
+                let target = Self::account_from_pubkey(&input[1..33])?;
+                let unbonded_index = U256::from_big_endian(&input[33..65]);
+                frame_support::ensure!(
+                    unbonded_index <= U256::from(u32::MAX),
+                    Self::revert("invalid unbonded index")
+                );
+                log::debug!(target: "evm-staking", "unlock: {:?}, {:?}", target, unbonded_index);
+
+                Self::dispatch(
+                    xpallet_mining_staking::Pallet::<T>::unlock_unbonded_withdrawal(
+                        origin(),
+                        T::Lookup::unlookup(target),
+                        unbonded_index.low_u32(),
+                    ),
+                    <T as xpallet_mining_staking::Config>::WeightInfo::unlock_unbonded_withdrawal(),
+                )
+            }
+            // Claim
+            Some(4) if input.len() == 33 => {
+                let target = Self::account_from_pubkey(&input[1..33])?;
+                log::debug!(target: "evm-staking", "claim: {:?}", target);
+
+                Self::dispatch(
+                    xpallet_mining_staking::Pallet::<T>::claim(
+                        origin(),
+                        T::Lookup::unlookup(target),
+                    ),
+                    <T as xpallet_mining_staking::Config>::WeightInfo::claim(),
+                )
+            }
+            // Nomination ledger
+            Some(5) if input.len() == 53 => {
+                let nominator = T::AddressMapping::into_account_id(H160::from_slice(&input[1..21]));
+                let target = Self::account_from_pubkey(&input[21..53])?;
+
+                let ledger = xpallet_mining_staking::Pallet::<T>::nominations(nominator, target);
+                let unbonded = ledger.unbonded_chunks.iter().fold(
+                    Zero::zero(),
+                    |acc: xpallet_mining_staking::BalanceOf<T>, u| acc + u.value,
+                );
+
+                let nomination: u128 = ledger.nomination.unique_saturated_into();
+                let unbonded: u128 = unbonded.unique_saturated_into();
+
+                let mut out = [0u8; 96];
+                U256::from(nomination).to_big_endian(&mut out[0..32]);
+                U256::from(unbonded).to_big_endian(&mut out[32..64]);
+                U256::from(ledger.unbonded_chunks.len()).to_big_endian(&mut out[64..96]);
+
+                Ok((out.to_vec(), BASE_GAS_COST))
+            }
+            _ => {
+                log::warn!(target: "evm-staking", "invalid input: {:?}", input);
+
+                Err(Self::revert("invalid staking(0x404) input"))
+            }
+        }
+    }
+
+    fn dispatch(
+        result: DispatchResult,
+        weight: Weight,
+    ) -> Result<(Vec<u8>, u64), PrecompileFailure> {
+        result.map_err(|err| {
+            log::debug!(target: "evm-staking", "dispatch: {:?}", err);
+
+            Self::revert("staking failed")
+        })?;
+
+        // Refer: https://github.com/rust-ethereum/ethabi/blob/master/ethabi/src/encoder.rs#L144
+        let mut out = [0u8; 32];
+        out[31] = 1u8;
+
+        let cost = BASE_GAS_COST.saturating_add(T::GasWeightMapping::weight_to_gas(weight));
+        Ok((out.to_vec(), cost))
+    }
+
+    fn revert(output: &str) -> PrecompileFailure {
+        PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: output.into(),
+            cost: BASE_GAS_COST,
+        }
+    }
+
+    fn account_from_pubkey(pubkey: &[u8]) -> Result<T::AccountId, PrecompileFailure> {
+        let mut target = [0u8; 32];
+        target[0..32].copy_from_slice(&pubkey[0..32]);
+
+        T::AccountId::decode(&mut &AccountId32::new(target).encode()[..])
+            .map_err(|_| Self::revert("decode AccountId32 failed"))
+    }
+
+    fn balance(value: &[u8]) -> Result<xpallet_mining_staking::BalanceOf<T>, PrecompileFailure> {
+        let balance = U256::from_big_endian(&value[0..32]);
+        frame_support::ensure!(
+            balance <= U256::from(u128::MAX),
+            Self::revert("invalid balance")
+        );
+
+        Ok(balance.low_u128().saturated_into())
+    }
+}
+
+impl<T> Precompile for Staking<T>
+where
+    T: xpallet_mining_staking::Config + pallet_evm::Config,
+    T::AccountId: Decode,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+        is_static: bool,
+    ) -> PrecompileResult {
+        log::debug!(target: "evm-staking", "caller: {:?}", context.caller);
+
+        let (output, cost) = Self::process(&context.caller, input, is_static)?;
+        if let Some(gas) = target_gas {
+            frame_support::ensure!(
+                cost <= gas,
+                PrecompileFailure::Error {
+                    exit_status: fp_evm::ExitError::OutOfGas
+                }
+            );
+        }
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost,
+            output,
+            logs: Default::default(),
+        })
+    }
+}
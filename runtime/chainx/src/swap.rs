@@ -0,0 +1,235 @@
+use core::marker::PhantomData;
+use fp_evm::{
+    Context, ExitRevert, ExitSucceed, PrecompileFailure, PrecompileOutput, PrecompileResult,
+};
+use frame_support::{log, weights::Weight};
+use frame_system::RawOrigin;
+use pallet_evm::{AddressMapping, GasWeightMapping, Precompile};
+use sp_core::{H160, U256};
+use sp_runtime::{
+    traits::{SaturatedConversion, UniqueSaturatedInto},
+    DispatchResult,
+};
+use sp_std::vec::Vec;
+
+use chainx_primitives::AssetId;
+use xpallet_assets::BalanceOf;
+use xpallet_dex_swap::WeightInfo;
+
+const BASE_GAS_COST: u64 = 100_000;
+
+/// The swap precompile, through which the EVM contracts trade in the constant-product pools
+/// of `xpallet_dex_swap` with the assets of the account derived from the contract address.
+///
+/// input = (flag, 1 byte) + args, the asset ids and the values are passed as the 32 bytes
+/// big endian integers, the values are in the decimals of each asset:
+/// - 0 swap: asset_in(32 bytes) + asset_out(32 bytes) + amount_in(32 bytes)
+///   + amount_out_min(32 bytes)
+/// - 1 add liquidity: asset_a(32 bytes) + asset_b(32 bytes) + amount_a_desired(32 bytes)
+///   + amount_b_desired(32 bytes) + amount_a_min(32 bytes) + amount_b_min(32 bytes)
+/// - 2 remove liquidity: asset_a(32 bytes) + asset_b(32 bytes) + liquidity(32 bytes)
+///   + amount_a_min(32 bytes) + amount_b_min(32 bytes)
+/// - 3 quote: asset_in(32 bytes) + asset_out(32 bytes) + amount_in(32 bytes)
+///
+/// The quote is returned as amount_out(32 bytes) and can be queried by a static call,
+/// the others return true.
+pub struct Swap<T: xpallet_dex_swap::Config + pallet_evm::Config> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: xpallet_dex_swap::Config + pallet_evm::Config> Swap<T> {
+    fn process(
+        caller: &H160,
+        input: &[u8],
+        is_static: bool,
+    ) -> Result<(Vec<u8>, u64), PrecompileFailure> {
+        let flag = input.first().copied();
+        if flag != Some(3) {
+            frame_support::ensure!(!is_static, Self::revert("can not swap in a static call"));
+        }
+
+        let who = T::AddressMapping::into_account_id(*caller);
+        let origin = || RawOrigin::Signed(who.clone()).into();
+
+        match flag {
+            // Swap
+            Some(0) if input.len() == 129 => {
+                let asset_in = Self::asset_id(&input[1..33])?;
+                let asset_out = Self::asset_id(&input[33..65])?;
+                let amount_in = Self::balance(&input[65..97])?;
+                let amount_out_min = Self::balance(&input[97..129])?;
+                log::debug!(
+                    target: "evm-swap",
+                    "swap: {:?}, {:?}, {:?}, {:?}",
+                    asset_in,
+                    asset_out,
+                    amount_in,
+                    amount_out_min
+                );
+
+                Self::dispatch(
+                    xpallet_dex_swap::Pallet::<T>::swap(
+                        origin(),
+                        asset_in,
+                        asset_out,
+                        amount_in,
+                        amount_out_min,
+                    ),
+                    <T as xpallet_dex_swap::Config>::WeightInfo::swap(),
+                )
+            }
+            // Add liquidity
+            Some(1) if input.len() == 193 => {
+                let asset_a = Self::asset_id(&input[1..33])?;
+                let asset_b = Self::asset_id(&input[33..65])?;
+                let amount_a_desired = Self::balance(&input[65..97])?;
+                let amount_b_desired = Self::balance(&input[97..129])?;
+                let amount_a_min = Self::balance(&input[129..161])?;
+                let amount_b_min = Self::balance(&input[161..193])?;
+                log::debug!(
+                    target: "evm-swap",
+                    "add_liquidity: {:?}, {:?}, {:?}, {:?}",
+                    asset_a,
+                    asset_b,
+                    amount_a_desired,
+                    amount_b_desired
+                );
+
+                Self::dispatch(
+                    xpallet_dex_swap::Pallet::<T>::add_liquidity(
+                        origin(),
+                        asset_a,
+                        asset_b,
+                        amount_a_desired,
+                        amount_b_desired,
+                        amount_a_min,
+                        amount_b_min,
+                    ),
+                    <T as xpallet_dex_swap::Config>::WeightInfo::add_liquidity(),
+                )
+            }
+            // Remove liquidity
+            Some(2) if input.len() == 161 => {
+                let asset_a = Self::asset_id(&input[1..33])?;
+                let asset_b = Self::asset_id(&input[33..65])?;
+                let liquidity = Self::balance(&input[65..97])?;
+                let amount_a_min = Self::balance(&input[97..129])?;
+                let amount_b_min = Self::balance(&input[129..161])?;
+                log::debug!(
+                    target: "evm-swap",
+                    "remove_liquidity: {:?}, {:?}, {:?}",
+                    asset_a,
+                    asset_b,
+                    liquidity
+                );
+
+                Self::dispatch(
+                    xpallet_dex_swap::Pallet::<T>::remove_liquidity(
+                        origin(),
+                        asset_a,
+                        asset_b,
+                        liquidity,
+                        amount_a_min,
+                        amount_b_min,
+                    ),
+                    <T as xpallet_dex_swap::Config>::WeightInfo::remove_liquidity(),
+                )
+            }
+            // Quote
+            Some(3) if input.len() == 97 => {
+                let asset_in = Self::asset_id(&input[1..33])?;
+                let asset_out = Self::asset_id(&input[33..65])?;
+                let amount_in = Self::balance(&input[65..97])?;
+
+                let amount_out: u128 =
+                    xpallet_dex_swap::Pallet::<T>::quote(asset_in, asset_out, amount_in)
+                        .ok_or_else(|| Self::revert("no quote for the swap"))?
+                        .unique_saturated_into();
+
+                let mut out = [0u8; 32];
+                U256::from(amount_out).to_big_endian(&mut out);
+
+                Ok((out.to_vec(), BASE_GAS_COST))
+            }
+            _ => {
+                log::warn!(target: "evm-swap", "invalid input: {:?}", input);
+
+                Err(Self::revert("invalid swap(0x405) input"))
+            }
+        }
+    }
+
+    fn dispatch(
+        result: DispatchResult,
+        weight: Weight,
+    ) -> Result<(Vec<u8>, u64), PrecompileFailure> {
+        result.map_err(|err| {
+            log::debug!(target: "evm-swap", "dispatch: {:?}", err);
+
+            Self::revert("swap failed")
+        })?;
+
+        // Refer: https://github.com/rust-ethereum/ethabi/blob/master/ethabi/src/encoder.rs#L144
+        let mut out = [0u8; 32];
+        out[31] = 1u8;
+
+        let cost = BASE_GAS_COST.saturating_add(T::GasWeightMapping::weight_to_gas(weight));
+        Ok((out.to_vec(), cost))
+    }
+
+    fn revert(output: &str) -> PrecompileFailure {
+        PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: output.into(),
+            cost: BASE_GAS_COST,
+        }
+    }
+
+    fn asset_id(value: &[u8]) -> Result<AssetId, PrecompileFailure> {
+        let asset_id = U256::from_big_endian(&value[0..32]);
+        frame_support::ensure!(
+            asset_id <= U256::from(AssetId::MAX),
+            Self::revert("invalid asset id")
+        );
+
+        Ok(asset_id.low_u32())
+    }
+
+    fn balance(value: &[u8]) -> Result<BalanceOf<T>, PrecompileFailure> {
+        let balance = U256::from_big_endian(&value[0..32]);
+        frame_support::ensure!(
+            balance <= U256::from(u128::MAX),
+            Self::revert("invalid balance")
+        );
+
+        Ok(balance.low_u128().saturated_into())
+    }
+}
+
+impl<T: xpallet_dex_swap::Config + pallet_evm::Config> Precompile for Swap<T> {
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+        is_static: bool,
+    ) -> PrecompileResult {
+        log::debug!(target: "evm-swap", "caller: {:?}", context.caller);
+
+        let (output, cost) = Self::process(&context.caller, input, is_static)?;
+        if let Some(gas) = target_gas {
+            frame_support::ensure!(
+                cost <= gas,
+                PrecompileFailure::Error {
+                    exit_status: fp_evm::ExitError::OutOfGas
+                }
+            );
+        }
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost,
+            output,
+            logs: Default::default(),
+        })
+    }
+}
@@ -0,0 +1,108 @@
+//! The filter of the gateway calls that must be dispatched on their own.
+
+use frame_support::traits::IsSubType;
+
+use xpallet_gateway_bitcoin::Call as XGatewayBitcoinCall;
+use xpallet_gateway_common::Call as XGatewayCommonCall;
+use xpallet_gateway_ethereum::Call as XGatewayEthereumCall;
+use xpallet_gateway_records::Call as XGatewayRecordsCall;
+
+/// Returns true if the gateway call must be dispatched on its own rather than in a batch, i.e.
+/// the trustee transitions and the withdrawal finalizations, or any gateway call in the safe
+/// mode.
+pub fn is_unbatchable<T, Call>(call: &Call, safe_mode: bool) -> bool
+where
+    T: xpallet_gateway_common::Config
+        + xpallet_gateway_bitcoin::Config
+        + xpallet_gateway_records::Config
+        + xpallet_gateway_ethereum::Config,
+    Call: IsSubType<XGatewayCommonCall<T>>
+        + IsSubType<XGatewayBitcoinCall<T>>
+        + IsSubType<XGatewayRecordsCall<T>>
+        + IsSubType<XGatewayEthereumCall<T>>,
+{
+    if let Some(call) = IsSubType::<XGatewayCommonCall<T>>::is_sub_type(call) {
+        return safe_mode
+            || matches!(
+                call,
+                XGatewayCommonCall::excute_trustee_election { .. }
+                    | XGatewayCommonCall::cancel_trustee_election { .. }
+                    | XGatewayCommonCall::force_trustee_election { .. }
+                    | XGatewayCommonCall::force_update_trustee { .. }
+                    | XGatewayCommonCall::move_trust_into_black_room { .. }
+                    | XGatewayCommonCall::move_trust_out_black_room { .. }
+                    | XGatewayCommonCall::flush_withdrawal_batch { .. }
+                    | XGatewayCommonCall::submit_signature_share { .. }
+            );
+    }
+    if let Some(call) = IsSubType::<XGatewayBitcoinCall<T>>::is_sub_type(call) {
+        return safe_mode
+            || matches!(
+                call,
+                XGatewayBitcoinCall::create_taproot_withdraw_tx { .. }
+                    | XGatewayBitcoinCall::sign_withdrawal_proposal { .. }
+                    | XGatewayBitcoinCall::report_withdrawal_tx { .. }
+                    | XGatewayBitcoinCall::remove_proposal { .. }
+            );
+    }
+    safe_mode
+        && (IsSubType::<XGatewayRecordsCall<T>>::is_sub_type(call).is_some()
+            || IsSubType::<XGatewayEthereumCall<T>>::is_sub_type(call).is_some())
+}
+
+/// Returns true if `call` batches an unbatchable gateway call, looking through the proxy,
+/// the multisig and the derivative wrappers.
+///
+/// The multisig call that can not be decoded is not checked, it can not be dispatched either.
+/// The batches proposed by the committees are not signed extrinsics, they are not checked.
+pub fn batches_unbatchable<T, Call>(call: &Call, in_batch: bool, safe_mode: bool) -> bool
+where
+    T: pallet_utility::Config<Call = Call>
+        + pallet_proxy::Config<Call = Call>
+        + pallet_multisig::Config<Call = Call>
+        + xpallet_gateway_common::Config
+        + xpallet_gateway_bitcoin::Config
+        + xpallet_gateway_records::Config
+        + xpallet_gateway_ethereum::Config,
+    Call: IsSubType<pallet_utility::Call<T>>
+        + IsSubType<pallet_proxy::Call<T>>
+        + IsSubType<pallet_multisig::Call<T>>
+        + IsSubType<XGatewayCommonCall<T>>
+        + IsSubType<XGatewayBitcoinCall<T>>
+        + IsSubType<XGatewayRecordsCall<T>>
+        + IsSubType<XGatewayEthereumCall<T>>,
+{
+    if let Some(utility_call) = IsSubType::<pallet_utility::Call<T>>::is_sub_type(call) {
+        match utility_call {
+            pallet_utility::Call::batch { calls } | pallet_utility::Call::batch_all { calls } => {
+                return calls
+                    .iter()
+                    .any(|call| batches_unbatchable::<T, Call>(call, true, safe_mode))
+            }
+            pallet_utility::Call::as_derivative { call, .. } => {
+                return batches_unbatchable::<T, Call>(call, in_batch, safe_mode)
+            }
+            _ => {}
+        }
+    }
+    if let Some(
+        pallet_proxy::Call::proxy { call, .. } | pallet_proxy::Call::proxy_announced { call, .. },
+    ) = IsSubType::<pallet_proxy::Call<T>>::is_sub_type(call)
+    {
+        return batches_unbatchable::<T, Call>(call, in_batch, safe_mode);
+    }
+    if let Some(multisig_call) = IsSubType::<pallet_multisig::Call<T>>::is_sub_type(call) {
+        match multisig_call {
+            pallet_multisig::Call::as_multi_threshold_1 { call, .. } => {
+                return batches_unbatchable::<T, Call>(call, in_batch, safe_mode)
+            }
+            pallet_multisig::Call::as_multi { call, .. } => {
+                return call.try_decode().map_or(false, |call| {
+                    batches_unbatchable::<T, Call>(&call, in_batch, safe_mode)
+                })
+            }
+            _ => {}
+        }
+    }
+    in_batch && is_unbatchable::<T, Call>(call, safe_mode)
+}
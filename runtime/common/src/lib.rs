@@ -2,6 +2,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod batch_filter;
+
 use static_assertions::const_assert;
 
 use frame_support::{
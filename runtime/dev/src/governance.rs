@@ -0,0 +1,100 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Collects the governance state for the `GovernanceApi`.
+
+use codec::{Decode, Encode};
+use sp_std::prelude::*;
+
+use pallet_democracy::{ReferendumInfo, VoteThreshold};
+use xp_governance::{
+    GovernanceOverview, MotionOverview, ReferendumOverview, ReferendumThreshold,
+    TreasuryProposalOverview,
+};
+
+use chainx_primitives::{AccountId, Balance, BlockNumber, Hash};
+
+use crate::{CouncilCollective, Democracy, Runtime, TechnicalCollective, Treasury};
+
+/// Re-decodes a value of the upstream pallets whose fields are private into their layout.
+fn decode_as<T: Decode>(value: &impl Encode) -> Option<T> {
+    T::decode(&mut &value.encode()[..]).ok()
+}
+
+fn referenda() -> Vec<ReferendumOverview<BlockNumber, Hash, Balance>> {
+    // The referenda before `lowest_unbaked` are all finished.
+    (Democracy::lowest_unbaked()..Democracy::referendum_count())
+        .filter_map(|index| match Democracy::referendum_info(index)? {
+            ReferendumInfo::Ongoing(status) => Some(ReferendumOverview {
+                index,
+                end: status.end,
+                proposal_hash: status.proposal_hash,
+                threshold: match status.threshold {
+                    VoteThreshold::SuperMajorityApprove => {
+                        ReferendumThreshold::SuperMajorityApprove
+                    }
+                    VoteThreshold::SuperMajorityAgainst => {
+                        ReferendumThreshold::SuperMajorityAgainst
+                    }
+                    VoteThreshold::SimpleMajority => ReferendumThreshold::SimpleMajority,
+                },
+                delay: status.delay,
+                ayes: status.tally.ayes,
+                nays: status.tally.nays,
+                turnout: status.tally.turnout,
+            }),
+            ReferendumInfo::Finished { .. } => None,
+        })
+        .collect()
+}
+
+fn motions<I: 'static>() -> Vec<MotionOverview<AccountId, BlockNumber, Hash>>
+where
+    Runtime: pallet_collective::Config<I>,
+{
+    pallet_collective::Pallet::<Runtime, I>::proposals()
+        .into_iter()
+        .filter_map(|hash| {
+            let votes = pallet_collective::Pallet::<Runtime, I>::voting(&hash)?;
+            let (index, threshold, ayes, nays, end) =
+                decode_as::<(u32, u32, Vec<AccountId>, Vec<AccountId>, BlockNumber)>(&votes)?;
+            Some(MotionOverview {
+                hash,
+                index,
+                threshold,
+                ayes,
+                nays,
+                end,
+            })
+        })
+        .collect()
+}
+
+fn treasury_proposals() -> Vec<TreasuryProposalOverview<AccountId, Balance>> {
+    let approvals = Treasury::approvals();
+    let mut proposals = pallet_treasury::Proposals::<Runtime>::iter()
+        .filter_map(|(index, proposal)| {
+            let (proposer, value, beneficiary, bond) =
+                decode_as::<(AccountId, Balance, AccountId, Balance)>(&proposal)?;
+            Some(TreasuryProposalOverview {
+                index,
+                proposer,
+                value,
+                beneficiary,
+                bond,
+                approved: approvals.contains(&index),
+            })
+        })
+        .collect::<Vec<_>>();
+    proposals.sort_by_key(|proposal| proposal.index);
+    proposals
+}
+
+/// Returns the ongoing referenda, the open motions and the pending treasury proposals.
+pub fn governance_overview() -> GovernanceOverview<AccountId, Balance, BlockNumber, Hash> {
+    GovernanceOverview {
+        referenda: referenda(),
+        council_motions: motions::<CouncilCollective>(),
+        technical_motions: motions::<TechnicalCollective>(),
+        treasury_proposals: treasury_proposals(),
+    }
+}
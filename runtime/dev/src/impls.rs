@@ -9,22 +9,26 @@ use sp_runtime::{
     transaction_validity::{
         InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
     },
-    FixedPointNumber, Perquintill, RuntimeDebug,
+    FixedPointNumber, PerThing, Percent, Perquintill, RuntimeDebug,
 };
 
 use frame_support::{
     parameter_types,
-    traits::{Currency, ExistenceRequirement, Imbalance, OnUnbalanced, WithdrawReasons},
+    traits::{Contains, Currency, ExistenceRequirement, Imbalance, OnUnbalanced, WithdrawReasons},
 };
 
-use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use pallet_transaction_payment::Multiplier;
+use xpallet_transaction_fee::{FeePolicy, WithdrawalFee};
 
 use xpallet_gateway_common::Call as XGatewayCommonCall;
 use xpallet_mining_staking::Call as XStakingCall;
 
 use chainx_primitives::{AccountId, Balance};
 
-use crate::{Authorship, Balances, Call, Runtime, XBtcLedger};
+use crate::{
+    Authorship, Balances, Call, Runtime, Treasury, XAssetsBridge, XAssetsRegistrar, XBtcLedger,
+    XGatewayCommon, XMiningAsset, XStaking, XTransactionFee,
+};
 
 type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
@@ -42,11 +46,29 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 pub struct DealWithFees;
 impl OnUnbalanced<NegativeImbalance> for DealWithFees {
     fn on_nonzero_unbalanced(fees: NegativeImbalance) {
-        // for fees, 90% to the reward pot of author, 10% to author
-        let (to_reward_pot, to_author) = fees.ration(90, 10);
+        // split the fees by the fee policy, the rounding dust goes to the reward pot of author.
+        let policy = XTransactionFee::fee_policy();
+        let total = fees.peek();
+        let (to_author, fees) = fees.split(policy.author.mul_floor(total));
+        let (to_treasury, fees) = fees.split(policy.treasury.mul_floor(total));
+        let (to_burn, to_reward_pot) = fees.split(policy.burn.mul_floor(total));
 
         let to_author_numeric_amount = to_author.peek();
         let to_reward_pot_numeric_amount = to_reward_pot.peek();
+        let to_treasury_numeric_amount = to_treasury.peek();
+        let to_burn_numeric_amount = to_burn.peek();
+
+        // dropping the imbalance reduces the total issuance.
+        drop(to_burn);
+        Treasury::on_unbalanced(to_treasury);
+        if to_treasury_numeric_amount > 0 || to_burn_numeric_amount > 0 {
+            <frame_system::Pallet<Runtime>>::deposit_event(
+                xpallet_transaction_fee::Event::<Runtime>::FeeDistributed(
+                    to_treasury_numeric_amount,
+                    to_burn_numeric_amount,
+                ),
+            );
+        }
 
         if let Some(author) = <pallet_authorship::Pallet<Runtime>>::author() {
             let reward_pot = <xpallet_mining_staking::Pallet<Runtime>>::reward_pot_for(&author);
@@ -85,14 +107,24 @@ impl OnUnbalanced<BTCNegativeImbalance> for DealWithBTCFees {
     }
 }
 
+// The default parameters of the fee multiplier, which can be tuned via
+// `XTransactionFee::set_fee_multiplier_params`.
 parameter_types! {
     pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
     pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
     pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
 }
 
-pub type SlowAdjustingFeeUpdate<R> =
-    TargetedFeeAdjustment<R, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+// The default split of the fees and tips, which can be adjusted via
+// `XTransactionFee::set_fee_policy`.
+parameter_types! {
+    pub DefaultFeePolicy: FeePolicy = FeePolicy {
+        author: Percent::from_percent(10),
+        reward_pot: Percent::from_percent(90),
+        treasury: Percent::zero(),
+        burn: Percent::zero(),
+    };
+}
 
 /// A struct for charging additional fee for some special calls.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
@@ -118,6 +150,27 @@ impl ChargeExtraFee {
         extra_cofficient.map(|cofficient| Balance::from(cofficient) * BASE_EXTRA_FEE)
     }
 
+    /// Returns the fee deducted from the withdrawn asset if the given `call` is a withdrawal.
+    pub fn withdrawal_fee(call: &Call) -> Option<WithdrawalFee<Balance>> {
+        let (asset_id, outputs) = match call {
+            Call::XGatewayCommon(XGatewayCommonCall::withdraw { asset_id, .. }) => (*asset_id, 1),
+            Call::XGatewayCommon(XGatewayCommonCall::withdraw_to_many {
+                asset_id,
+                outputs,
+                ..
+            }) => (*asset_id, outputs.len() as Balance),
+            _ => return None,
+        };
+
+        // every withdrawal output pays the fee on the target chain.
+        XGatewayCommon::withdrawal_limit(&asset_id)
+            .ok()
+            .map(|limit| WithdrawalFee {
+                asset_id,
+                fee: limit.fee.saturating_mul(outputs),
+            })
+    }
+
     /// Actually withdraws the extra `fee` from account `who`.
     pub fn withdraw_fee(who: &AccountId, fee: Balance) -> TransactionValidity {
         match Balances::withdraw(
@@ -170,3 +223,18 @@ impl SignedExtension for ChargeExtraFee {
         Ok(ValidTransaction::default())
     }
 }
+
+/// The accounts whose dust can't be reaped besides the pallet accounts: the trustee multisig
+/// accounts, the accounts bound to an EVM address and the reward pots of the validators and the
+/// mining assets, which never send a transaction themselves.
+pub struct DustReapExempt;
+impl Contains<AccountId> for DustReapExempt {
+    fn contains(who: &AccountId) -> bool {
+        XGatewayCommon::trustee_multisigs()
+            .values()
+            .any(|multisig| multisig == who)
+            || XAssetsBridge::evm_accounts(who).is_some()
+            || XAssetsRegistrar::asset_ids().any(|id| XMiningAsset::reward_pot_for(&id) == *who)
+            || XStaking::validator_set().any(|v| XStaking::reward_pot_for(&v) == *who)
+    }
+}
@@ -44,10 +44,15 @@ use pallet_session::historical as pallet_session_historical;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_babe::AllowedSlots::PrimaryAndSecondaryPlainSlots;
 
-use chainx_runtime_common::{BlockLength, BlockWeights, BASE_FEE};
-use xpallet_dex_spot::{Depth, FullPairInfo, RpcOrder, TradingPairId};
-use xpallet_mining_asset::{MinerLedger, MiningAssetInfo, MiningDividendInfo};
-use xpallet_mining_staking::{NominatorInfo, NominatorLedger, ValidatorInfo};
+use chainx_runtime_common::{
+    batch_filter::batches_unbatchable, BlockLength, BlockWeights, BASE_FEE,
+};
+use xpallet_dex_spot::{Depth, FullPairInfo, OrderBook, RpcOrder, TradingPairId};
+use xpallet_mining_asset::{AssetEmissionRate, MinerLedger, MiningAssetInfo, MiningDividendInfo};
+use xpallet_mining_staking::{
+    NextElectionInfo, NominatorInfo, NominatorLedger, ReferralEarnings, ValidatorInfo,
+    ValidatorPerformanceInfo,
+};
 use xpallet_support::traits::MultisigAddressFor;
 
 // A few exports that help ease life for downstream crates.
@@ -76,32 +81,40 @@ pub use xp_runtime::Memo;
 
 // xpallet re-exports
 pub use xpallet_assets::{
-    AssetInfo, AssetRestrictions, AssetType, Chain, TotalAssetInfo, WithdrawalLimit,
+    AssetInfo, AssetMetadata, AssetRestrictions, AssetSupply, AssetType, Chain, TotalAssetInfo,
+    WithdrawalLimit,
 };
 #[cfg(feature = "std")]
 pub use xpallet_gateway_bitcoin::h256_rev;
 pub use xpallet_gateway_bitcoin::{
-    hash_rev, types::BtcHeaderInfo, BtcHeader, BtcNetwork, BtcParams, BtcTxVerifier,
-    BtcWithdrawalProposal, Compact, H256,
+    hash_rev,
+    types::{BtcGatewayMetrics, BtcGatewayParams, BtcHeaderIndex, BtcHeaderInfo, BtcTxInclusion},
+    BtcHeader, BtcNetwork, BtcParams, BtcTxVerifier, BtcWithdrawalProposal, Compact, H256,
 };
 pub use xpallet_gateway_common::{
     trustees,
     types::{
-        GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, ScriptInfo, TrusteeInfoConfig,
+        DepositAddress, GenericTrusteeIntentionProps, GenericTrusteeSessionDetails,
+        GenericTrusteeSessionInfo, MaintenanceStatus, ScriptInfo, TrusteeInfoConfig,
+        TrusteeSessionAt, WithdrawalBatchStatus,
     },
 };
-pub use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId};
+pub use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId, WithdrawalStatus};
 pub use xpallet_mining_asset::MiningWeight;
 pub use xpallet_mining_staking::VoteWeight;
 
 /// Constant values used within the runtime.
 pub mod constants;
+mod governance;
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
 mod migrations;
 
 use self::constants::{currency::*, time::*};
-use self::impls::{ChargeExtraFee, DealWithBTCFees, DealWithFees, SlowAdjustingFeeUpdate};
+use self::impls::{
+    AdjustmentVariable, ChargeExtraFee, DealWithBTCFees, DealWithFees, DefaultFeePolicy,
+    DustReapExempt, MinimumMultiplier, TargetBlockFullness,
+};
 
 // EVM
 use chainx_runtime_common::NORMAL_DISPATCH_RATIO;
@@ -116,6 +129,8 @@ use pallet_evm::{
 use sp_core::{H160, U256};
 use sp_runtime::traits::{Dispatchable, PostDispatchInfoOf};
 mod precompiles;
+mod staking;
+mod swap;
 mod withdraw;
 
 pub use precompiles::ChainXPrecompiles;
@@ -125,10 +140,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("chainx"),
     impl_name: create_runtime_str!("chainx-dev"),
     authoring_version: 1,
-    spec_version: 32,
+    spec_version: 33,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 7,
+    transaction_version: 8,
     state_version: 0,
 };
 
@@ -169,6 +184,7 @@ impl Contains<Call> for BaseFilter {
 
 pub const FORBIDDEN_CALL: u8 = 255;
 pub const FORBIDDEN_ACCOUNT: u8 = 254;
+pub const FORBIDDEN_BATCH: u8 = 253;
 
 impl SignedExtension for BaseFilter {
     const IDENTIFIER: &'static str = "BaseFilter";
@@ -203,6 +219,9 @@ impl SignedExtension for BaseFilter {
         if XSystem::blacklist(who) {
             return Err(InvalidTransaction::Custom(FORBIDDEN_ACCOUNT).into());
         }
+        if batches_unbatchable::<Runtime, Call>(call, false, XSystem::safe_mode()) {
+            return Err(InvalidTransaction::Custom(FORBIDDEN_BATCH).into());
+        }
         Ok(ValidTransaction::default())
     }
 }
@@ -312,7 +331,7 @@ impl pallet_authorship::Config for Runtime {
     type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Babe>;
     type UncleGenerations = UncleGenerations;
     type FilterUncle = ();
-    type EventHandler = ImOnline;
+    type EventHandler = (ImOnline, XStaking);
 }
 
 parameter_types! {
@@ -349,8 +368,11 @@ impl pallet_babe::Config for Runtime {
 
     type KeyOwnerProofSystem = Historical;
 
-    type HandleEquivocation =
-        pallet_babe::EquivocationHandler<Self::KeyOwnerIdentification, Offences, ReportLongevity>;
+    type HandleEquivocation = pallet_babe::EquivocationHandler<
+        Self::KeyOwnerIdentification,
+        xpallet_mining_staking::EquivocationReporter<Runtime, Offences>,
+        ReportLongevity,
+    >;
     type WeightInfo = ();
     type MaxAuthorities = MaxAuthorities;
 }
@@ -367,7 +389,7 @@ impl pallet_grandpa::Config for Runtime {
     type KeyOwnerProofSystem = Historical;
     type HandleEquivocation = pallet_grandpa::EquivocationHandler<
         Self::KeyOwnerIdentification,
-        Offences,
+        xpallet_mining_staking::EquivocationReporter<Runtime, Offences>,
         ReportLongevity,
     >;
     type WeightInfo = ();
@@ -444,11 +466,19 @@ impl pallet_transaction_payment::Config for Runtime {
     type TransactionByteFee = TransactionByteFee;
     type OperationalFeeMultiplier = OperationalFeeMultiplier;
     type WeightToFee = self::constants::fee::WeightToFee;
-    type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
+    type FeeMultiplierUpdate = xpallet_transaction_fee::DynamicFeeAdjustment<Self>;
 }
 
 impl xpallet_transaction_fee::Config for Runtime {
     type Event = Event;
+    type TargetBlockFullness = TargetBlockFullness;
+    type AdjustmentVariable = AdjustmentVariable;
+    type MinimumMultiplier = MinimumMultiplier;
+    type Signature = Signature;
+    type Signer = <Signature as traits::Verify>::Signer;
+    type MaxSponsoredCalls = ConstU32<64>;
+    type DefaultFeePolicy = DefaultFeePolicy;
+    type CouncilOrigin = EnsureRootOrHalfCouncil;
 }
 
 parameter_types! {
@@ -466,7 +496,8 @@ impl pallet_im_online::Config for Runtime {
     type Event = Event;
     type ValidatorSet = Self;
     type NextSessionRotation = Babe;
-    type ReportUnresponsiveness = Offences;
+    type ReportUnresponsiveness =
+        xpallet_mining_staking::UnresponsivenessReporter<Runtime, Offences>;
     type UnsignedPriority = ImOnlineUnsignedPriority;
     type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
     type MaxKeys = MaxKeys;
@@ -533,9 +564,10 @@ where
             frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
             frame_system::CheckNonce::<Runtime>::from(nonce),
             frame_system::CheckWeight::<Runtime>::new(),
-            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+            xpallet_transaction_fee::ChargeSponsoredTransactionPayment::<Runtime>::from(tip),
             BaseFilter,
             ChargeExtraFee,
+            xpallet_gateway_bitcoin::PrioritizeRelay::<Runtime>::new(),
         );
         let raw_payload = SignedPayload::new(call, extra)
             .map_err(|e| {
@@ -835,10 +867,38 @@ impl pallet_scheduler::Config for Runtime {
     type OriginPrivilegeCmp = EqualPrivilegeOnly;
     type MaxScheduledPerBlock = ConstU32<50>;
     type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
-    type PreimageProvider = ();
+    type PreimageProvider = Preimage;
     type NoPreimagePostponement = NoPreimagePostponement;
 }
 
+parameter_types! {
+    pub const PreimageMaxSize: u32 = 4096 * 1024;
+    pub const PreimageBaseDeposit: Balance = deposit(2, 64);
+}
+
+impl pallet_preimage::Config for Runtime {
+    type Event = Event;
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<AccountId>;
+    type MaxSize = PreimageMaxSize;
+    type BaseDeposit = PreimageBaseDeposit;
+    type ByteDeposit = PreimageByteDeposit;
+    type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    pub const MinVestedTransfer: Balance = 100 * DOLLARS;
+}
+
+impl pallet_vesting::Config for Runtime {
+    type Event = Event;
+    type Currency = Balances;
+    type BlockNumberToBalance = traits::ConvertInto;
+    type MinVestedTransfer = MinVestedTransfer;
+    type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+    const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
 parameter_types! {
     pub const BasicDeposit: Balance = 10 * DOLLARS;       // 258 bytes on-chain
     pub const FieldDeposit: Balance = 250 * CENTS;        // 66 bytes on-chain
@@ -895,6 +955,9 @@ pub enum ProxyType {
     Staking = 3,
     IdentityJudgement = 4,
     CancelProxy = 5,
+    /// The hot operations of the trustees, i.e. relaying, signing the withdrawals and
+    /// claiming the rewards, without the control of the trustee account itself.
+    Gateway = 6,
 }
 
 impl Default for ProxyType {
@@ -911,6 +974,7 @@ impl InstanceFilter<Call> for ProxyType {
                 c,
                 Call::System(..)
                     | Call::Scheduler(..)
+                    | Call::Preimage(..)
                     | Call::Babe(..)
                     | Call::Timestamp(..)
                     | Call::Indices(pallet_indices::Call::claim{..})
@@ -933,6 +997,8 @@ impl InstanceFilter<Call> for ProxyType {
                     | Call::Identity(..)
                     | Call::Proxy(..)
                     | Call::Multisig(..)
+                    | Call::Vesting(pallet_vesting::Call::vest { .. })
+                    | Call::Vesting(pallet_vesting::Call::vest_other { .. }) // Specifically omitting Vesting `vested_transfer`, and `force_vested_transfer`
             ),
             ProxyType::Governance => matches!(
                 c,
@@ -951,6 +1017,28 @@ impl InstanceFilter<Call> for ProxyType {
                 c,
                 Call::Identity(pallet_identity::Call::provide_judgement { .. }) | Call::Utility(..)
             ),
+            ProxyType::Gateway => matches!(
+                c,
+                Call::XGatewayCommon(xpallet_gateway_common::Call::submit_signature_share { .. })
+                    | Call::XGatewayCommon(
+                        xpallet_gateway_common::Call::claim_trustee_rewards { .. }
+                    )
+                    | Call::XGatewayBitcoin(xpallet_gateway_bitcoin::Call::push_header { .. })
+                    | Call::XGatewayBitcoin(xpallet_gateway_bitcoin::Call::push_transaction { .. })
+                    | Call::XGatewayBitcoin(
+                        xpallet_gateway_bitcoin::Call::create_taproot_withdraw_tx { .. }
+                    )
+                    | Call::XGatewayBitcoin(
+                        xpallet_gateway_bitcoin::Call::sign_withdrawal_proposal { .. }
+                    )
+                    | Call::XGatewayBitcoin(
+                        xpallet_gateway_bitcoin::Call::report_withdrawal_tx { .. }
+                    )
+                    | Call::XGatewayBitcoin(
+                        xpallet_gateway_bitcoin::Call::attest_wallet_balance { .. }
+                    )
+                    | Call::Utility(..)
+            ),
             ProxyType::CancelProxy => {
                 matches!(
                     c,
@@ -991,6 +1079,15 @@ impl pallet_proxy::Config for Runtime {
 impl xpallet_system::Config for Runtime {
     type Event = Event;
     type Currency = Balances;
+    type Proposal = Call;
+    type PalletsOrigin = OriginCaller;
+    type Scheduler = Scheduler;
+    type EnactmentOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type CancelEnactmentOrigin =
+        pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>;
+    type SafeModeOrigin =
+        pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>;
 }
 
 parameter_types! {
@@ -1001,15 +1098,25 @@ impl xpallet_assets_registrar::Config for Runtime {
     type Event = Event;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = XMiningAsset;
+    type TechnicalOrigin =
+        pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>;
     type WeightInfo = xpallet_assets_registrar::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const DustReapBounty: Percent = Percent::from_percent(10);
+}
+
 impl xpallet_assets::Config for Runtime {
     type Event = Event;
     type Currency = Balances;
     type TreasuryAccount = SimpleTreasuryAccount;
     type OnCreatedAccount = frame_system::Provider<Runtime>;
     type OnAssetChanged = XMiningAsset;
+    type RestrictionOrigin =
+        pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>;
+    type DustReapBounty = DustReapBounty;
+    type DustReapExempt = DustReapExempt;
     type WeightInfo = xpallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1025,39 +1132,97 @@ impl MultisigAddressFor<AccountId> for MultisigProvider {
     }
 }
 
+parameter_types! {
+    pub const StandbyTrusteeBond: Balance = 100 * DOLLARS;
+    pub const MaxSignatureShareLen: u32 = 16 * 1024;
+    pub const MaxArchivedTrusteeSessions: u32 = 64;
+}
+
 impl xpallet_gateway_common::Config for Runtime {
     type Event = Event;
     type Validator = XStaking;
     type DetermineMultisigAddress = MultisigProvider;
-    type CouncilOrigin =
-        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type CouncilOrigin = EnsureRootOrHalfCouncil;
+    // The council changes the gateway parameters via `XSystem::schedule_enactment` after the delay.
+    type ParameterOrigin = EnsureRoot<AccountId>;
+    type PauseOrigin =
+        pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>;
     type Bitcoin = XGatewayBitcoin;
     type BitcoinTrustee = XGatewayBitcoin;
     type BitcoinTrusteeSessionProvider = trustees::bitcoin::BtcTrusteeSessionManager<Runtime>;
     type BitcoinTotalSupply = XGatewayBitcoin;
     type BitcoinWithdrawalProposal = XGatewayBitcoin;
+    type Ethereum = XGatewayEthereum;
+    type StandbyTrusteeBond = StandbyTrusteeBond;
+    type MaxSignatureShareLen = MaxSignatureShareLen;
+    type MaxArchivedTrusteeSessions = MaxArchivedTrusteeSessions;
     type WeightInfo = xpallet_gateway_common::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const MaxPrioritizedRelays: u32 = 16;
+    pub const MaxFeelessRelays: u32 = 16;
+}
+
 impl xpallet_gateway_bitcoin::Config for Runtime {
     type Event = Event;
     type UnixTime = Timestamp;
-    type CouncilOrigin =
-        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type CouncilOrigin = EnsureRootOrHalfCouncil;
+    // The council changes the gateway parameters via `XSystem::schedule_enactment` after the delay.
+    type ParameterOrigin = EnsureRoot<AccountId>;
+    type AttesterOrigin = EnsureRootOrHalfCouncil;
     type AccountExtractor = xp_gateway_bitcoin::OpReturnExtractor;
     type TrusteeSessionProvider = trustees::bitcoin::BtcTrusteeSessionManager<Runtime>;
     type TrusteeInfoUpdate = XGatewayCommon;
+    type WithdrawalBatch = XGatewayCommon;
+    type OperationPause = XGatewayCommon;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type MaxPrioritizedRelays = MaxPrioritizedRelays;
+    type MaxFeelessRelays = MaxFeelessRelays;
     type WeightInfo = xpallet_gateway_bitcoin::weights::SubstrateWeight<Runtime>;
 }
 
+impl xpallet_gateway_ethereum::Config for Runtime {
+    type Event = Event;
+    type CouncilOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type TrusteeInfoConfig = XGatewayCommon;
+    type OperationPause = XGatewayCommon;
+    type WeightInfo = xpallet_gateway_ethereum::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    pub const SpotPalletId: PalletId = PalletId(*b"pcx/spot");
+}
+
 impl xpallet_dex_spot::Config for Runtime {
     type Event = Event;
     type Price = Balance;
+    type CouncilOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type PalletId = SpotPalletId;
     type WeightInfo = xpallet_dex_spot::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const SwapPalletId: PalletId = PalletId(*b"pcx/swap");
+    pub const SwapLpAssetIdBase: AssetId = 0xb0000000;
+    pub const SwapFee: Permill = Permill::from_parts(3_000);
+    pub const SwapMinimumLiquidity: Balance = 1_000;
+}
+
+impl xpallet_dex_swap::Config for Runtime {
+    type Event = Event;
+    type CouncilOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type PalletId = SwapPalletId;
+    type LpAssetIdBase = SwapLpAssetIdBase;
+    type SwapFee = SwapFee;
+    type MinimumLiquidity = SwapMinimumLiquidity;
+    type WeightInfo = xpallet_dex_swap::weights::SubstrateWeight<Runtime>;
+}
+
 pub struct SimpleTreasuryAccount;
 impl xpallet_support::traits::TreasuryAccount<AccountId> for SimpleTreasuryAccount {
     fn treasury_account() -> Option<AccountId> {
@@ -1071,6 +1236,11 @@ parameter_types! {
     pub const MigrationSessionOffset: SessionIndex = 55533;
     pub const MinimumReferralId: u32 = 2;
     pub const MaximumReferralId: u32 = 12;
+    pub const PerformanceHistoryDepth: u32 = 168;
+    pub const MaxAutoPayouts: u32 = 32;
+    // The equivocation slashes are applied one era after the report, leaving
+    // the council that much time to review and cancel them if needed.
+    pub const SlashDeferDuration: SessionIndex = 12;
 }
 
 impl xpallet_mining_staking::Config for Runtime {
@@ -1085,6 +1255,14 @@ impl xpallet_mining_staking::Config for Runtime {
     type DetermineRewardPotAccount =
         xpallet_mining_staking::SimpleValidatorRewardPotAccountDeterminer<Runtime>;
     type ValidatorRegistration = Session;
+    type PerformanceHistoryDepth = PerformanceHistoryDepth;
+    type MaxAutoPayouts = MaxAutoPayouts;
+    type SlashDeferDuration = SlashDeferDuration;
+    type SlashCancelOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type EvmStakingOrigin = EnsureRootOrHalfCouncil;
+    type ParametersOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
     type WeightInfo = xpallet_mining_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1096,6 +1274,10 @@ impl xpallet_mining_asset::GatewayInterface<AccountId> for ReferralGetter {
     }
 }
 
+parameter_types! {
+    pub const MaxAutoClaims: u32 = 32;
+}
+
 impl xpallet_mining_asset::Config for Runtime {
     type Event = Event;
     type StakingInterface = Self;
@@ -1103,6 +1285,9 @@ impl xpallet_mining_asset::Config for Runtime {
     type TreasuryAccount = SimpleTreasuryAccount;
     type DetermineRewardPotAccount =
         xpallet_mining_asset::SimpleAssetRewardPotAccountDeterminer<Runtime>;
+    type CommitteeOrigin =
+        pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>;
+    type MaxAutoClaims = MaxAutoClaims;
     type WeightInfo = xpallet_mining_asset::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1115,12 +1300,17 @@ impl pallet_sudo::Config for Runtime {
 
 impl xpallet_ethereum_chain_id::Config for Runtime {}
 
+parameter_types! {
+    pub const BtcLedgerBlocksPerYear: BlockNumber = 365 * DAYS;
+}
+
 impl xpallet_btc_ledger::Config for Runtime {
     type Balance = Balance;
     type Event = Event;
     type CouncilOrigin =
         pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
     type PalletId = TreasuryPalletId;
+    type BlocksPerYear = BtcLedgerBlocksPerYear;
 }
 
 /// Current approximation of the gas/s consumption considering
@@ -1201,6 +1391,49 @@ impl pallet_base_fee::Config for Runtime {
     type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
 }
 
+impl xpallet_base_fee_governance::Config for Runtime {
+    type CouncilOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type WeightInfo = xpallet_base_fee_governance::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    pub const ContractDepositPerItem: Balance = deposit(1, 0);
+    pub const ContractDepositPerByte: Balance = deposit(0, 1);
+    // The lazy deletion runs inside on_initialize.
+    pub ContractDeletionWeightLimit: Weight = chainx_runtime_common::AVERAGE_ON_INITIALIZE_RATIO *
+        BlockWeights::get().max_block;
+    // The weight needed for decoding the queue should be less or equal than a fifth
+    // of the overall weight dedicated to the lazy deletion.
+    pub ContractDeletionQueueDepth: u32 = ((ContractDeletionWeightLimit::get() / (
+            <Runtime as pallet_contracts::Config>::WeightInfo::on_initialize_per_queue_item(1) -
+            <Runtime as pallet_contracts::Config>::WeightInfo::on_initialize_per_queue_item(0)
+        )) / 5) as u32;
+    pub ContractSchedule: pallet_contracts::Schedule<Runtime> = Default::default();
+}
+
+// The ink! contracts are only enabled on the testnets for now, the ChainX mainnet implements
+// the `ContractsApi` without `pallet_contracts`.
+impl pallet_contracts::Config for Runtime {
+    type Time = Timestamp;
+    type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
+    type Currency = Balances;
+    type Event = Event;
+    type Call = Call;
+    // The contracts can not dispatch any runtime call.
+    type CallFilter = frame_support::traits::Nothing;
+    type DepositPerItem = ContractDepositPerItem;
+    type DepositPerByte = ContractDepositPerByte;
+    type CallStack = [pallet_contracts::Frame<Self>; 31];
+    type WeightPrice = pallet_transaction_payment::Pallet<Self>;
+    type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
+    type ChainExtension = ();
+    type DeletionQueueDepth = ContractDeletionQueueDepth;
+    type DeletionWeightLimit = ContractDeletionWeightLimit;
+    type Schedule = ContractSchedule;
+    type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
+}
+
 parameter_types! {
     // 0x1111111111111111111111111111111111111111
     pub EvmCaller: H160 = H160::from_slice(&[17u8;20][..]);
@@ -1253,7 +1486,7 @@ construct_runtime!(
         Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 23,
 
         // ChainX basics.
-        XSystem: xpallet_system::{Pallet, Call, Storage, Event<T>, Config} = 24,
+        XSystem: xpallet_system::{Pallet, Call, Storage, Event<T>, Config<T>} = 24,
         XAssetsRegistrar: xpallet_assets_registrar::{Pallet, Call, Storage, Event<T>, Config} = 25,
         XAssets: xpallet_assets::{Pallet, Call, Storage, Event<T>, Config<T>} = 26,
 
@@ -1264,7 +1497,7 @@ construct_runtime!(
         // Crypto gateway stuff.
         XGatewayRecords: xpallet_gateway_records::{Pallet, Call, Storage, Event<T>} = 29,
         XGatewayCommon: xpallet_gateway_common::{Pallet, Call, Storage, Event<T>, Config<T>} = 30,
-        XGatewayBitcoin: xpallet_gateway_bitcoin::{Pallet, Call, Storage, Event<T>, Config<T>} = 31,
+        XGatewayBitcoin: xpallet_gateway_bitcoin::{Pallet, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 31,
 
         // DEX
         XSpot: xpallet_dex_spot::{Pallet, Call, Storage, Event<T>, Config<T>} = 32,
@@ -1273,7 +1506,7 @@ construct_runtime!(
 
         // It might be possible to merge this module into pallet_transaction_payment in future, thus
         // we put it at the end for keeping the extrinsic ordering.
-        XTransactionFee: xpallet_transaction_fee::{Pallet, Event<T>} = 35,
+        XTransactionFee: xpallet_transaction_fee::{Pallet, Call, Storage, Event<T>} = 35,
 
         Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 36,
 
@@ -1293,6 +1526,20 @@ construct_runtime!(
         XAssetsBridge: xpallet_assets_bridge::{Pallet, Call, Storage, Config<T>, Event<T>} = 45,
 
         XBtcLedger: xpallet_btc_ledger::{Pallet, Call, Storage, Config<T>, Event<T>} = 46,
+        XGatewayEthereum: xpallet_gateway_ethereum::{Pallet, Call, Storage, Event<T>} = 47,
+
+        // Dependency on pallet_base_fee
+        XBaseFeeGovernance: xpallet_base_fee_governance::{Pallet, Call} = 48,
+
+        Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>} = 49,
+
+        Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>} = 50,
+
+        // Must be after XGenesisBuilder for the vested balances.
+        Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>, Config<T>} = 51,
+
+        // Dependency on xpallet_assets_registrar and xpallet_assets
+        XSwap: xpallet_dex_swap::{Pallet, Call, Storage, Event<T>} = 52,
     }
 );
 
@@ -1315,9 +1562,10 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    xpallet_transaction_fee::ChargeSponsoredTransactionPayment<Runtime>,
     BaseFilter,
     ChargeExtraFee,
+    xpallet_gateway_bitcoin::PrioritizeRelay<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -1334,7 +1582,10 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
-    AssetsBridgeMigration,
+    (
+        AssetsBridgeMigration,
+        migrations::XGatewayRecordsApplyingWithdrawalsMigration,
+    ),
 >;
 
 pub struct AssetsBridgeMigration;
@@ -1351,6 +1602,16 @@ impl OnRuntimeUpgrade for AssetsBridgeMigration {
 
         <Runtime as frame_system::Config>::DbWeight::get().writes(1)
     }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        use frame_support::storage::migration;
+        frame_support::ensure!(
+            !migration::have_storage_value(b"XAssetsBridge", b"HotAccount", b""),
+            "HotAccount still exists after migration"
+        );
+        Ok(())
+    }
 }
 
 pub struct TransactionConverter;
@@ -1618,6 +1879,28 @@ impl_runtime_apis! {
             let base = TransactionPayment::query_fee_details(uxt, len);
             xpallet_transaction_fee::FeeDetails::new(base, maybe_extra)
         }
+
+        fn fee_congestion() -> xpallet_transaction_fee::FeeCongestion {
+            XTransactionFee::fee_congestion()
+        }
+
+        fn fee_policy() -> xpallet_transaction_fee::FeePolicy {
+            XTransactionFee::fee_policy()
+        }
+
+        fn estimate_fee(
+            uxt: <Block as BlockT>::Extrinsic,
+            len: u32,
+        ) -> xpallet_transaction_fee::FeeEstimate<Balance> {
+            let withdrawal_fee = ChargeExtraFee::withdrawal_fee(&uxt.0.function);
+            let maybe_extra = ChargeExtraFee::has_extra_fee(&uxt.0.function);
+            let base = TransactionPayment::query_fee_details(uxt, len);
+            xpallet_transaction_fee::FeeEstimate::new(
+                xpallet_transaction_fee::FeeDetails::new(base, maybe_extra),
+                withdrawal_fee,
+                XTransactionFee::fee_congestion().level,
+            )
+        }
     }
 
     impl xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance> for Runtime {
@@ -1628,6 +1911,26 @@ impl_runtime_apis! {
         fn assets() -> BTreeMap<AssetId, TotalAssetInfo<Balance>> {
             XAssets::total_asset_infos()
         }
+
+        fn asset_metadata(asset_id: AssetId) -> Option<AssetMetadata> {
+            let mut metadata = XAssetsRegistrar::asset_metadata_of(asset_id);
+            // Fall back to the ERC-20 contract minted by the assets bridge.
+            if let Some(erc20) = XAssetsBridge::erc20s(asset_id) {
+                metadata
+                    .get_or_insert_with(Default::default)
+                    .erc20_address
+                    .get_or_insert(erc20);
+            }
+            metadata
+        }
+
+        fn asset_supply(asset_id: AssetId) -> AssetSupply<Balance> {
+            XAssets::asset_supply(&asset_id)
+        }
+
+        fn asset_restrictions(asset_id: AssetId) -> AssetRestrictions {
+            XAssets::asset_restrictions_of(asset_id)
+        }
     }
 
     impl xpallet_mining_staking_rpc_runtime_api::XStakingApi<Block, AccountId, Balance, VoteWeight, BlockNumber> for Runtime {
@@ -1646,6 +1949,15 @@ impl_runtime_apis! {
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber> {
             XStaking::nominator_info_of(who)
         }
+        fn validator_performance_of(who: AccountId) -> ValidatorPerformanceInfo {
+            XStaking::validator_performance_of(who)
+        }
+        fn next_election_of(who: AccountId) -> NextElectionInfo {
+            XStaking::next_election_of(who)
+        }
+        fn referral_earnings(referral_id: Vec<u8>) -> Option<ReferralEarnings<AccountId, Balance>> {
+            XStaking::referral_earnings(referral_id)
+        }
     }
 
     impl xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance> for Runtime {
@@ -1660,6 +1972,18 @@ impl_runtime_apis! {
         fn depth(pair_id: TradingPairId, depth_size: u32) -> Option<Depth<Balance, Balance>> {
             XSpot::depth(pair_id, depth_size)
         }
+
+        fn order_book(pair_id: TradingPairId, depth: u32) -> Option<OrderBook<Balance, Balance>> {
+            XSpot::order_book(pair_id, depth)
+        }
+
+        fn user_orders(who: AccountId) -> Vec<RpcOrder<TradingPairId, AccountId, Balance, Balance, BlockNumber>> {
+            XSpot::user_orders(who)
+        }
+
+        fn twap(pair_id: TradingPairId, window: BlockNumber) -> Option<Balance> {
+            XSpot::twap(pair_id, window)
+        }
     }
 
     impl xpallet_mining_asset_rpc_runtime_api::XMiningAssetApi<Block, AccountId, Balance, MiningWeight, BlockNumber> for Runtime {
@@ -1674,6 +1998,10 @@ impl_runtime_apis! {
         fn miner_ledger(who: AccountId) -> BTreeMap<AssetId, MinerLedger<MiningWeight, BlockNumber>> {
             XMiningAsset::miner_ledger(who)
         }
+
+        fn asset_emission_rates() -> BTreeMap<AssetId, AssetEmissionRate<Balance>> {
+            XMiningAsset::asset_emission_rates()
+        }
     }
 
     impl xpallet_gateway_records_rpc_runtime_api::XGatewayRecordsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
@@ -1684,6 +2012,10 @@ impl_runtime_apis! {
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<u32, Withdrawal<AccountId, Balance, BlockNumber>> {
             XGatewayRecords::withdrawals_list_by_chain(chain)
         }
+
+        fn withdrawal_status(id: WithdrawalRecordId) -> Option<WithdrawalStatus> {
+            XGatewayRecords::withdrawal_status(id)
+        }
     }
 
     impl xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, AccountId> for Runtime {
@@ -1695,6 +2027,14 @@ impl_runtime_apis! {
             XGatewayBitcoin::verify_tx_valid(raw_tx, withdrawal_id_list, full_amount)
         }
 
+        fn verify_tx_inclusion(
+            raw_tx: Vec<u8>,
+            merkle_proof: Vec<u8>,
+            block_hash: H256,
+        ) -> BtcTxInclusion {
+            XGatewayBitcoin::verify_tx_inclusion(raw_tx, merkle_proof, block_hash)
+        }
+
         fn get_withdrawal_proposal() -> Option<BtcWithdrawalProposal<AccountId>> {
             XGatewayBitcoin::get_withdrawal_proposal()
         }
@@ -1706,6 +2046,45 @@ impl_runtime_apis! {
         fn get_btc_block_header(txid: H256) -> Option<BtcHeaderInfo> {
             XGatewayBitcoin::get_btc_block_header(txid)
         }
+
+        fn get_best_index() -> BtcHeaderIndex {
+            XGatewayBitcoin::get_best_index()
+        }
+
+        fn get_gateway_metrics() -> BtcGatewayMetrics {
+            XGatewayBitcoin::get_gateway_metrics()
+        }
+
+        fn get_gateway_params() -> BtcGatewayParams {
+            XGatewayBitcoin::get_gateway_params()
+        }
+
+        fn get_unreported_withdrawal_tx() -> Option<(H256, Vec<u8>)> {
+            XGatewayBitcoin::get_unreported_withdrawal_tx()
+        }
+
+        fn withdrawal_broadcast_report(txid: H256) -> <Block as BlockT>::Extrinsic {
+            UncheckedExtrinsic::new_unsigned(
+                xpallet_gateway_bitcoin::Call::<Runtime>::report_withdrawal_broadcast { txid }.into(),
+            )
+        }
+    }
+
+    impl xp_governance::GovernanceApi<Block, AccountId, Balance, BlockNumber, Hash> for Runtime {
+        fn governance_overview() -> xp_governance::GovernanceOverview<AccountId, Balance, BlockNumber, Hash> {
+            governance::governance_overview()
+        }
+    }
+
+    impl xp_address::AddressApi<Block> for Runtime {
+        fn verify_address(chain: Chain, address: Vec<u8>) -> Result<xp_address::AddressFormat, xp_address::AddressError> {
+            let network = XSystem::network_props();
+            match chain {
+                Chain::ChainX => xp_address::verify_ss58_address(&address, network.ss58_addr_format_id().into()),
+                Chain::Bitcoin => XGatewayBitcoin::verify_withdrawal_address(&address, network),
+                _ => Err(xp_address::AddressError::UnsupportedChain),
+            }
+        }
     }
 
     impl xpallet_btc_ledger_runtime_api::BtcLedgerApi<Block, AccountId, Balance> for Runtime {
@@ -1715,6 +2094,12 @@ impl_runtime_apis! {
         fn get_total() -> Balance {
             XBtcLedger::get_total()
         }
+        fn get_interest(who: AccountId) -> Balance {
+            XBtcLedger::interest_of(&who)
+        }
+        fn get_interest_apy() -> u32 {
+            XBtcLedger::interest_apy()
+        }
     }
 
     impl xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<Block, AccountId, Balance, BlockNumber> for Runtime {
@@ -1722,6 +2107,13 @@ impl_runtime_apis! {
             XGatewayCommon::bound_addrs(&who)
         }
 
+        fn deposit_address(chain: Chain, who: AccountId) -> Option<DepositAddress<Balance>> {
+            match chain {
+                Chain::Bitcoin => XGatewayBitcoin::get_deposit_address(&who),
+                _ => None,
+            }
+        }
+
         fn withdrawal_limit(asset_id: AssetId) -> Result<WithdrawalLimit<Balance>, DispatchError> {
             XGatewayCommon::withdrawal_limit(&asset_id)
         }
@@ -1772,6 +2164,22 @@ impl_runtime_apis! {
 
         }
 
+        fn trustee_session_details(chain: Chain, session_number: i32) -> Option<GenericTrusteeSessionDetails<AccountId, BlockNumber>> {
+            XGatewayCommon::trustee_session_details(chain, session_number)
+        }
+
+        fn trustee_session_at(chain: Chain, at: TrusteeSessionAt<BlockNumber>) -> Option<GenericTrusteeSessionDetails<AccountId, BlockNumber>> {
+            XGatewayCommon::trustee_session_at(chain, at)
+        }
+
+        fn maintenance_status(chain: Chain) -> Option<MaintenanceStatus<BlockNumber>> {
+            XGatewayCommon::maintenance_status(chain)
+        }
+
+        fn withdrawal_batch(chain: Chain) -> Option<WithdrawalBatchStatus<BlockNumber>> {
+            XGatewayCommon::withdrawal_batch_status(chain)
+        }
+
         fn generate_trustee_session_info(chain: Chain, candidates: Vec<AccountId>) -> Result<(GenericTrusteeSessionInfo<AccountId, BlockNumber>, ScriptInfo<AccountId>), DispatchError> {
             let info = XGatewayCommon::try_generate_session_info(chain, candidates)?;
             // check multisig address
@@ -1788,6 +2196,174 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_contracts_rpc_runtime_api::ContractsApi<
+        Block, AccountId, Balance, BlockNumber, Hash,
+    > for Runtime {
+        fn call(
+            origin: AccountId,
+            dest: AccountId,
+            value: Balance,
+            gas_limit: u64,
+            storage_deposit_limit: Option<Balance>,
+            input_data: Vec<u8>,
+        ) -> pallet_contracts_primitives::ContractExecResult<Balance> {
+            Contracts::bare_call(origin, dest, value, gas_limit, storage_deposit_limit, input_data, true)
+        }
+
+        fn instantiate(
+            origin: AccountId,
+            value: Balance,
+            gas_limit: u64,
+            storage_deposit_limit: Option<Balance>,
+            code: pallet_contracts_primitives::Code<Hash>,
+            data: Vec<u8>,
+            salt: Vec<u8>,
+        ) -> pallet_contracts_primitives::ContractInstantiateResult<AccountId, Balance> {
+            Contracts::bare_instantiate(origin, value, gas_limit, storage_deposit_limit, code, data, salt, true)
+        }
+
+        fn upload_code(
+            origin: AccountId,
+            code: Vec<u8>,
+            storage_deposit_limit: Option<Balance>,
+        ) -> pallet_contracts_primitives::CodeUploadResult<Hash, Balance> {
+            Contracts::bare_upload_code(origin, code, storage_deposit_limit)
+        }
+
+        fn get_storage(
+            address: AccountId,
+            key: [u8; 32],
+        ) -> pallet_contracts_primitives::GetStorageResult {
+            Contracts::get_storage(address, key)
+        }
+    }
+
+    impl xp_evm_tracing::EvmTracingApi<Block> for Runtime {
+        fn trace_transaction(
+            extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+            transaction_hash: sp_core::H256,
+        ) -> Result<Option<xp_evm_tracing::CallTrace>, sp_runtime::DispatchError> {
+            #[cfg(feature = "evm-tracing")]
+            {
+                for ext in extrinsics {
+                    let hash = match &ext.0.function {
+                        Call::Ethereum(transact { transaction }) => Some(transaction.hash()),
+                        _ => None,
+                    };
+                    if hash == Some(transaction_hash) {
+                        let (_, trace) =
+                            xp_evm_tracing::CallTracer::trace(|| Executive::apply_extrinsic(ext));
+                        return Ok(trace);
+                    }
+                    let _ = Executive::apply_extrinsic(ext);
+                }
+                Ok(None)
+            }
+            #[cfg(not(feature = "evm-tracing"))]
+            {
+                let _ = (extrinsics, transaction_hash);
+                Err(sp_runtime::DispatchError::Other(
+                    "Missing `evm-tracing` compile time feature flag",
+                ))
+            }
+        }
+
+        fn trace_block(
+            extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+        ) -> Result<Vec<xp_evm_tracing::TransactionTrace>, sp_runtime::DispatchError> {
+            #[cfg(feature = "evm-tracing")]
+            {
+                let mut traces = Vec::new();
+                let mut transaction_index = 0;
+                for ext in extrinsics {
+                    let hash = match &ext.0.function {
+                        Call::Ethereum(transact { transaction }) => Some(transaction.hash()),
+                        _ => None,
+                    };
+                    match hash {
+                        Some(transaction_hash) => {
+                            let (_, trace) = xp_evm_tracing::CallTracer::trace(|| {
+                                Executive::apply_extrinsic(ext)
+                            });
+                            if let Some(trace) = trace {
+                                traces.push(xp_evm_tracing::TransactionTrace {
+                                    transaction_hash,
+                                    transaction_index,
+                                    trace,
+                                });
+                            }
+                            transaction_index += 1;
+                        }
+                        None => {
+                            let _ = Executive::apply_extrinsic(ext);
+                        }
+                    }
+                }
+                Ok(traces)
+            }
+            #[cfg(not(feature = "evm-tracing"))]
+            {
+                let _ = extrinsics;
+                Err(sp_runtime::DispatchError::Other(
+                    "Missing `evm-tracing` compile time feature flag",
+                ))
+            }
+        }
+
+        fn trace_call(
+            from: H160,
+            to: H160,
+            data: Vec<u8>,
+            value: U256,
+            gas_limit: U256,
+            max_fee_per_gas: Option<U256>,
+            max_priority_fee_per_gas: Option<U256>,
+            nonce: Option<U256>,
+            access_list: Vec<(H160, Vec<H256>)>,
+        ) -> Result<xp_evm_tracing::CallTrace, sp_runtime::DispatchError> {
+            #[cfg(feature = "evm-tracing")]
+            {
+                let is_transactional = false;
+                let (result, trace) = xp_evm_tracing::CallTracer::trace(|| {
+                    <Runtime as pallet_evm::Config>::Runner::call(
+                        from,
+                        to,
+                        data,
+                        value,
+                        gas_limit.low_u64(),
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                        nonce,
+                        access_list,
+                        is_transactional,
+                        <Runtime as pallet_evm::Config>::config(),
+                    )
+                });
+                if let Err(err) = result {
+                    return Err(err.into());
+                }
+                trace.ok_or(sp_runtime::DispatchError::Other("The call is not traced"))
+            }
+            #[cfg(not(feature = "evm-tracing"))]
+            {
+                let _ = (
+                    from,
+                    to,
+                    data,
+                    value,
+                    gas_limit,
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                    nonce,
+                    access_list,
+                );
+                Err(sp_runtime::DispatchError::Other(
+                    "Missing `evm-tracing` compile time feature flag",
+                ))
+            }
+        }
+    }
+
     impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
         fn chain_id() -> u64 {
             <Runtime as pallet_evm::Config>::ChainId::get()
@@ -0,0 +1,232 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Runtime upgrade tests against the committed state snapshots.
+//!
+//! For each runtime, the state snapshot under `snapshots/` is loaded, the runtime of this
+//! tree is applied on top of it by running all the runtime migrations, and then a scripted
+//! scenario (deposit, trade, withdraw, stake and claim) is executed. The invariants like the
+//! total issuance conservation are checked after every step.
+//!
+//! The snapshots are taken from a live node with `try-runtime`, see `snapshots/README.md`.
+//! A runtime without snapshot fails the test, unless `CHAINX_UPGRADE_TESTS_FROM_GENESIS` is set,
+//! in which case the scenario is run on the genesis state of the chain spec of the network.
+
+use std::path::PathBuf;
+
+use remote_externalities::{Builder, Mode, OfflineConfig, SnapshotConfig};
+use sp_io::TestExternalities;
+use sp_runtime::BuildStorage;
+
+use chainx_cli::chain_spec;
+use chainx_primitives::{AccountId, Block};
+
+/// The environment variable overriding the directory of the state snapshots.
+pub const SNAPSHOT_DIR_ENV: &str = "CHAINX_SNAPSHOT_DIR";
+
+/// The environment variable opting out of the snapshots, the upgrade tests are run on the
+/// genesis state of the chain specs instead.
+pub const FROM_GENESIS_ENV: &str = "CHAINX_UPGRADE_TESTS_FROM_GENESIS";
+
+/// 1 PCX, same as the one of BTC.
+pub const UNIT: u128 = 100_000_000;
+
+/// Returns the path of the state snapshot of `network`.
+pub fn snapshot_path(network: &str) -> PathBuf {
+    let dir = std::env::var_os(SNAPSHOT_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots"));
+    dir.join(format!("{}.snap", network))
+}
+
+/// Loads the state snapshot of `network`.
+///
+/// Panics if the snapshot does not exist, unless `FROM_GENESIS_ENV` is set, in which case the
+/// genesis state of the chain spec of `network` is loaded instead.
+pub fn load_snapshot(network: &str) -> TestExternalities {
+    let path = snapshot_path(network);
+    if !path.exists() {
+        if std::env::var_os(FROM_GENESIS_ENV).is_none() {
+            panic!(
+                "No snapshot of {} at {}, take it as described in snapshots/README.md \
+                or set {} to run the upgrade test on the genesis state",
+                network,
+                path.display(),
+                FROM_GENESIS_ENV
+            );
+        }
+        eprintln!(
+            "No snapshot of {} at {}, running the upgrade test on the genesis state",
+            network,
+            path.display()
+        );
+        return TestExternalities::new(genesis_storage(network));
+    }
+
+    futures::executor::block_on(
+        Builder::<Block>::new()
+            .mode(Mode::Offline(OfflineConfig {
+                state_snapshot: SnapshotConfig::new(path.clone()),
+            }))
+            .build(),
+    )
+    .unwrap_or_else(|e| panic!("Failed to load the snapshot {}: {}", path.display(), e))
+}
+
+/// Builds the genesis storage of the chain spec of `network`, i.e., the committed genesis
+/// state of the mainnet and malan, and the development genesis of dev.
+pub fn genesis_storage(network: &str) -> sp_core::storage::Storage {
+    let storage = match network {
+        "chainx" => chain_spec::mainnet_config().and_then(|spec| spec.build_storage()),
+        "malan" => chain_spec::malan_config().and_then(|spec| spec.build_storage()),
+        "dev" => chain_spec::development_config(None).and_then(|spec| spec.build_storage()),
+        _ => Err(format!("Unknown network {}", network)),
+    };
+    storage.unwrap_or_else(|e| panic!("Failed to build the genesis state of {}: {}", network, e))
+}
+
+/// Returns a fresh account of the scenario, which does not exist in any snapshot.
+pub fn account(index: u8) -> AccountId {
+    AccountId::new([index; 32])
+}
+
+/// Generates the upgrade test of a runtime.
+///
+/// The runtime must contain `Balances`, `XAssets`, `XSpot`, `XGatewayRecords` and `XStaking`.
+#[macro_export]
+macro_rules! upgrade_test {
+    ($test:ident, $runtime:ident) => {
+        #[test]
+        fn $test() {
+            use frame_support::traits::Currency;
+            use sp_runtime::traits::Zero;
+
+            use xp_protocol::X_BTC;
+            use xpallet_assets_registrar::Chain;
+            use xpallet_dex_spot::{OrderFlavor, OrderType, Side};
+            use $crate::{account, load_snapshot, UNIT};
+            use $runtime::{
+                Balances, Executive, Origin, Runtime, System, XAssets, XGatewayRecords, XSpot,
+                XStaking,
+            };
+
+            /// The expected total issuance of PCX and X-BTC.
+            struct Issuance {
+                pcx: u128,
+                btc: u128,
+            }
+
+            impl Issuance {
+                fn snapshot() -> Self {
+                    Self {
+                        pcx: Balances::total_issuance(),
+                        btc: XAssets::total_issuance(&X_BTC),
+                    }
+                }
+
+                fn check(&self, step: &str) {
+                    assert_eq!(
+                        Balances::total_issuance(),
+                        self.pcx,
+                        "PCX is not conserved after {}",
+                        step
+                    );
+                    assert_eq!(
+                        XAssets::total_issuance(&X_BTC),
+                        self.btc,
+                        "X-BTC is not conserved after {}",
+                        step
+                    );
+                }
+            }
+
+            let network = stringify!($runtime).trim_end_matches("_runtime");
+            let mut ext = load_snapshot(network);
+
+            ext.execute_with(|| {
+                let mut issuance = Issuance::snapshot();
+
+                // Upgrade
+                Executive::execute_on_runtime_upgrade();
+                issuance.check("the migrations");
+
+                let alice = account(1);
+                let bob = account(2);
+                let _ = Balances::deposit_creating(&alice, 1_000 * UNIT);
+                let _ = Balances::deposit_creating(&bob, 1_000 * UNIT);
+                issuance.pcx += 2_000 * UNIT;
+                issuance.check("the endowment");
+
+                // Deposit
+                XGatewayRecords::deposit(&bob, X_BTC, UNIT).expect("Deposit must succeed");
+                issuance.btc += UNIT;
+                issuance.check("the deposit");
+
+                // Trade
+                let pair = XSpot::trading_pair_of(0).expect("PCX/BTC pair exists");
+                if pair.tradable {
+                    let latest_price = XSpot::trading_pair_info_of(0)
+                        .map(|info| info.latest_price)
+                        .unwrap_or_default();
+                    let tick = u128::from(pair.tick());
+                    let price = latest_price / tick * tick;
+                    assert!(!price.is_zero(), "The latest price of PCX/BTC is zero");
+
+                    for (who, side) in [(&alice, Side::Sell), (&bob, Side::Buy)] {
+                        XSpot::put_order(
+                            Origin::signed(who.clone()),
+                            pair.id,
+                            OrderType::Limit,
+                            OrderFlavor::GoodTillCancel,
+                            side,
+                            10 * UNIT,
+                            price,
+                        )
+                        .unwrap_or_else(|e| panic!("Failed to put {:?} order: {:?}", side, e));
+                    }
+                    issuance.check("the trade");
+                }
+
+                // Withdraw
+                let free_btc = XAssets::usable_balance(&bob, &X_BTC);
+                let withdrawal = free_btc / 2;
+                let id = XGatewayRecords::id();
+                XGatewayRecords::withdraw(
+                    &bob,
+                    X_BTC,
+                    withdrawal,
+                    b"3PgYgJA6h5xPEc3HbnZrUNB9u1R3zqtq3t".to_vec(),
+                    b"upgrade test".to_vec().into(),
+                )
+                .expect("Withdrawal application must succeed");
+                XGatewayRecords::process_withdrawal(id, Chain::Bitcoin)
+                    .expect("Withdrawal processing must succeed");
+                XGatewayRecords::finish_withdrawal(id, Some(Chain::Bitcoin))
+                    .expect("Withdrawal finishing must succeed");
+                issuance.btc -= withdrawal;
+                issuance.check("the withdrawal");
+                assert_eq!(XAssets::usable_balance(&bob, &X_BTC), free_btc - withdrawal);
+
+                // Stake
+                let validator = xpallet_mining_staking::Validators::<Runtime>::iter_keys()
+                    .find(|validator| {
+                        XStaking::bond(
+                            Origin::signed(alice.clone()),
+                            validator.clone().into(),
+                            UNIT,
+                        )
+                        .is_ok()
+                    })
+                    .expect("At least one validator accepts the nomination");
+                issuance.check("the bond");
+
+                // Claim
+                System::set_block_number(System::block_number() + 100);
+                let free_pcx = Balances::free_balance(&alice);
+                XStaking::claim(Origin::signed(alice.clone()), validator.into())
+                    .expect("Claim must succeed");
+                assert!(Balances::free_balance(&alice) >= free_pcx);
+                issuance.check("the claim");
+            });
+        }
+    };
+}
@@ -0,0 +1,121 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_runtime::codec::Encode;
+
+use chainx_runtime_common::batch_filter::{batches_unbatchable, is_unbatchable};
+use dev_runtime::{Call, Runtime};
+use xp_assets_registrar::Chain;
+
+fn remark() -> Call {
+    Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn flush() -> Call {
+    Call::XGatewayCommon(xpallet_gateway_common::Call::flush_withdrawal_batch {
+        chain: Chain::Bitcoin,
+    })
+}
+
+fn records() -> Call {
+    Call::XGatewayRecords(xpallet_gateway_records::Call::set_withdrawal_state_list { item: vec![] })
+}
+
+fn batch(calls: Vec<Call>) -> Call {
+    Call::Utility(pallet_utility::Call::batch { calls })
+}
+
+fn batch_all(calls: Vec<Call>) -> Call {
+    Call::Utility(pallet_utility::Call::batch_all { calls })
+}
+
+fn as_derivative(call: Call) -> Call {
+    Call::Utility(pallet_utility::Call::as_derivative {
+        index: 0,
+        call: Box::new(call),
+    })
+}
+
+fn proxy(call: Call) -> Call {
+    Call::Proxy(pallet_proxy::Call::proxy {
+        real: [0u8; 32].into(),
+        force_proxy_type: None,
+        call: Box::new(call),
+    })
+}
+
+fn as_multi_threshold_1(call: Call) -> Call {
+    Call::Multisig(pallet_multisig::Call::as_multi_threshold_1 {
+        other_signatories: vec![],
+        call: Box::new(call),
+    })
+}
+
+fn as_multi(call: Vec<u8>) -> Call {
+    Call::Multisig(pallet_multisig::Call::as_multi {
+        threshold: 2,
+        other_signatories: vec![],
+        maybe_timepoint: None,
+        call: pallet_multisig::OpaqueCall::<Runtime>::from_encoded(call),
+        store_call: false,
+        max_weight: 0,
+    })
+}
+
+fn forbidden(call: &Call, safe_mode: bool) -> bool {
+    batches_unbatchable::<Runtime, Call>(call, false, safe_mode)
+}
+
+#[test]
+fn unbatchable_calls_should_be_classified() {
+    assert!(is_unbatchable::<Runtime, Call>(&flush(), false));
+    assert!(!is_unbatchable::<Runtime, Call>(&records(), false));
+    assert!(is_unbatchable::<Runtime, Call>(&records(), true));
+    assert!(!is_unbatchable::<Runtime, Call>(&remark(), false));
+    assert!(!is_unbatchable::<Runtime, Call>(&remark(), true));
+}
+
+#[test]
+fn batched_unbatchable_calls_should_be_forbidden() {
+    // on its own
+    assert!(!forbidden(&flush(), false));
+    assert!(!forbidden(&proxy(flush()), false));
+    assert!(!forbidden(&as_multi_threshold_1(flush()), false));
+    assert!(!forbidden(&as_multi(flush().encode()), false));
+
+    assert!(!forbidden(&batch(vec![remark(), remark()]), false));
+    assert!(forbidden(&batch(vec![remark(), flush()]), false));
+    assert!(forbidden(&batch_all(vec![flush()]), false));
+    assert!(forbidden(&batch(vec![batch(vec![flush()])]), false));
+    assert!(forbidden(&batch(vec![as_derivative(flush())]), false));
+    assert!(forbidden(&as_derivative(batch(vec![flush()])), false));
+
+    // the other gateway calls only in the safe mode
+    assert!(!forbidden(&batch(vec![records()]), false));
+    assert!(forbidden(&batch(vec![records()]), true));
+}
+
+#[test]
+fn wrapped_batches_should_be_forbidden() {
+    assert!(forbidden(&proxy(batch(vec![flush()])), false));
+    assert!(forbidden(&batch(vec![proxy(flush())]), false));
+    assert!(forbidden(
+        &as_multi_threshold_1(batch(vec![flush()])),
+        false
+    ));
+    assert!(forbidden(
+        &batch(vec![as_multi_threshold_1(flush())]),
+        false
+    ));
+    assert!(forbidden(&as_multi(batch(vec![flush()]).encode()), false));
+    assert!(forbidden(&batch(vec![as_multi(flush().encode())]), false));
+    assert!(forbidden(
+        &proxy(as_multi(
+            as_multi_threshold_1(batch(vec![flush()])).encode()
+        )),
+        false
+    ));
+
+    // the call that can not be decoded can not be dispatched either
+    assert!(!forbidden(&as_multi(vec![0xff; 4]), false));
+    assert!(!forbidden(&batch(vec![as_multi(vec![0xff; 4])]), false));
+}
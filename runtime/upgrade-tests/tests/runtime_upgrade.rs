@@ -0,0 +1,7 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use chainx_runtime_upgrade_tests::upgrade_test;
+
+upgrade_test!(chainx_runtime_upgrade_should_work, chainx_runtime);
+upgrade_test!(malan_runtime_upgrade_should_work, malan_runtime);
+upgrade_test!(dev_runtime_upgrade_should_work, dev_runtime);
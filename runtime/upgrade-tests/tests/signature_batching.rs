@@ -0,0 +1,134 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The signature checks of the extrinsics of each runtime, in batch as `Executive::execute_block`
+//! verifies them on the background tasks of the `TaskExecutorExt`.
+
+use sp_core::{ecdsa, ed25519, sr25519, testing::TaskExecutor, traits::TaskExecutorExt, Pair};
+use sp_runtime::{
+    codec::Encode,
+    generic::{Era, SignedPayload},
+    traits::{Checkable, IdentifyAccount, StaticLookup},
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+    MultiSignature, MultiSigner,
+};
+
+macro_rules! signature_batching_tests {
+    ($name:ident, $runtime:ident) => {
+        mod $name {
+            use super::*;
+
+            use $runtime::{
+                impls::ChargeExtraFee, BaseFilter, Call, Runtime, SignedExtra, UncheckedExtrinsic,
+            };
+
+            fn extra() -> SignedExtra {
+                (
+                    frame_system::CheckNonZeroSender::<Runtime>::new(),
+                    frame_system::CheckSpecVersion::<Runtime>::new(),
+                    frame_system::CheckTxVersion::<Runtime>::new(),
+                    frame_system::CheckGenesis::<Runtime>::new(),
+                    frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+                    frame_system::CheckNonce::<Runtime>::from(0),
+                    frame_system::CheckWeight::<Runtime>::new(),
+                    xpallet_transaction_fee::ChargeSponsoredTransactionPayment::<Runtime>::from(0),
+                    BaseFilter,
+                    ChargeExtraFee,
+                    xpallet_gateway_bitcoin::PrioritizeRelay::<Runtime>::new(),
+                )
+            }
+
+            fn remark_call(remark: &[u8]) -> Call {
+                Call::System(frame_system::Call::remark {
+                    remark: remark.to_vec(),
+                })
+            }
+
+            /// Returns the remark signed by `pair`, with the signature of another remark if
+            /// `forged`.
+            fn remark<P>(pair: &P, forged: bool) -> UncheckedExtrinsic
+            where
+                P: Pair,
+                P::Public: Into<MultiSigner>,
+                P::Signature: Into<MultiSignature>,
+            {
+                let signed = remark_call(if forged { b"forged" } else { b"remark" });
+                let payload = SignedPayload::new(signed, extra()).unwrap();
+                let signature = payload.using_encoded(|payload| pair.sign(payload));
+                let signer: MultiSigner = pair.public().into();
+                UncheckedExtrinsic::new_signed(
+                    remark_call(b"remark"),
+                    <Runtime as frame_system::Config>::Lookup::unlookup(signer.into_account()),
+                    signature.into(),
+                    extra(),
+                )
+            }
+
+            /// Checks the signature of `xt` as `Executive::apply_extrinsic` does.
+            fn check(xt: UncheckedExtrinsic) -> Result<(), TransactionValidityError> {
+                xt.check(&frame_system::ChainContext::<Runtime>::default())
+                    .map(|_| ())
+            }
+
+            fn new_test_ext() -> sp_io::TestExternalities {
+                let storage = frame_system::GenesisConfig::default()
+                    .build_storage::<Runtime>()
+                    .unwrap();
+                let mut ext = sp_io::TestExternalities::new(storage);
+                ext.register_extension(TaskExecutorExt::new(TaskExecutor::new()));
+                ext
+            }
+
+            #[test]
+            fn sr25519_and_ed25519_signatures_should_be_verified_in_batch() {
+                new_test_ext().execute_with(|| {
+                    let sr25519 = sr25519::Pair::from_string("//Alice", None).unwrap();
+                    let ed25519 = ed25519::Pair::from_string("//Alice", None).unwrap();
+
+                    // Checked on their own, the forged signatures are rejected right away.
+                    assert_eq!(
+                        check(remark(&sr25519, true)),
+                        Err(InvalidTransaction::BadProof.into())
+                    );
+                    assert_eq!(
+                        check(remark(&ed25519, true)),
+                        Err(InvalidTransaction::BadProof.into())
+                    );
+
+                    // Checked in batch, the signatures are verified at the end of the block.
+                    sp_io::crypto::start_batch_verify();
+                    assert_eq!(check(remark(&sr25519, false)), Ok(()));
+                    assert_eq!(check(remark(&ed25519, false)), Ok(()));
+                    assert!(sp_io::crypto::finish_batch_verify());
+
+                    sp_io::crypto::start_batch_verify();
+                    assert_eq!(check(remark(&sr25519, false)), Ok(()));
+                    assert_eq!(check(remark(&sr25519, true)), Ok(()));
+                    assert!(!sp_io::crypto::finish_batch_verify());
+
+                    sp_io::crypto::start_batch_verify();
+                    assert_eq!(check(remark(&ed25519, true)), Ok(()));
+                    assert!(!sp_io::crypto::finish_batch_verify());
+                });
+            }
+
+            #[test]
+            fn ecdsa_signatures_should_be_recovered_one_by_one() {
+                new_test_ext().execute_with(|| {
+                    let ecdsa = ecdsa::Pair::from_string("//Alice", None).unwrap();
+
+                    sp_io::crypto::start_batch_verify();
+                    assert_eq!(check(remark(&ecdsa, false)), Ok(()));
+                    assert_eq!(
+                        check(remark(&ecdsa, true)),
+                        Err(InvalidTransaction::BadProof.into())
+                    );
+                    assert!(sp_io::crypto::finish_batch_verify());
+                });
+            }
+        }
+    };
+}
+
+signature_batching_tests!(chainx, chainx_runtime);
+signature_batching_tests!(dev, dev_runtime);
+signature_batching_tests!(malan, malan_runtime);
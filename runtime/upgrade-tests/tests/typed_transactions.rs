@@ -0,0 +1,223 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The EIP-2930 and EIP-1559 transactions of each runtime, from the raw transaction decoded by
+//! `eth_sendRawTransaction` through the pool validation and the execution to the receipts.
+
+use ethereum::{
+    EIP1559Transaction, EIP1559TransactionMessage, EIP2930Transaction, EIP2930TransactionMessage,
+    EnvelopedDecodable, EnvelopedEncodable, ReceiptV3, TransactionAction, TransactionV2,
+};
+use frame_support::{
+    assert_ok,
+    storage::{with_transaction, TransactionOutcome},
+};
+use sp_core::{hashing::keccak_256, H160, H256, U256};
+use sp_runtime::transaction_validity::TransactionSource;
+
+const SECRET: [u8; 32] = [1; 32];
+const RECEIVER: H160 = H160::repeat_byte(2);
+const GAS_LIMIT: u64 = 21_000;
+
+fn secret() -> libsecp256k1::SecretKey {
+    libsecp256k1::SecretKey::parse(&SECRET).unwrap()
+}
+
+fn sender() -> H160 {
+    let public = libsecp256k1::PublicKey::from_secret_key(&secret());
+    H160::from(H256::from(keccak_256(&public.serialize()[1..])))
+}
+
+/// Returns the y parity, the r and the s of the signature of `hash`.
+fn sign(hash: H256) -> (bool, H256, H256) {
+    let message = libsecp256k1::Message::parse(hash.as_fixed_bytes());
+    let (signature, recovery_id) = libsecp256k1::sign(&message, &secret());
+    let signature = signature.serialize();
+    (
+        recovery_id.serialize() != 0,
+        H256::from_slice(&signature[..32]),
+        H256::from_slice(&signature[32..]),
+    )
+}
+
+fn eip2930(chain_id: u64, nonce: u64, gas_price: U256) -> TransactionV2 {
+    let message = EIP2930TransactionMessage {
+        chain_id,
+        nonce: nonce.into(),
+        gas_price,
+        gas_limit: GAS_LIMIT.into(),
+        action: TransactionAction::Call(RECEIVER),
+        value: U256::zero(),
+        input: vec![],
+        access_list: vec![],
+    };
+    let (odd_y_parity, r, s) = sign(message.hash());
+    TransactionV2::EIP2930(EIP2930Transaction {
+        chain_id: message.chain_id,
+        nonce: message.nonce,
+        gas_price: message.gas_price,
+        gas_limit: message.gas_limit,
+        action: message.action,
+        value: message.value,
+        input: message.input,
+        access_list: message.access_list,
+        odd_y_parity,
+        r,
+        s,
+    })
+}
+
+fn eip1559(chain_id: u64, nonce: u64, max_fee_per_gas: U256) -> TransactionV2 {
+    let message = EIP1559TransactionMessage {
+        chain_id,
+        nonce: nonce.into(),
+        max_priority_fee_per_gas: U256::zero(),
+        max_fee_per_gas,
+        gas_limit: GAS_LIMIT.into(),
+        action: TransactionAction::Call(RECEIVER),
+        value: U256::zero(),
+        input: vec![],
+        access_list: vec![],
+    };
+    let (odd_y_parity, r, s) = sign(message.hash());
+    TransactionV2::EIP1559(EIP1559Transaction {
+        chain_id: message.chain_id,
+        nonce: message.nonce,
+        max_priority_fee_per_gas: message.max_priority_fee_per_gas,
+        max_fee_per_gas: message.max_fee_per_gas,
+        gas_limit: message.gas_limit,
+        action: message.action,
+        value: message.value,
+        input: message.input,
+        access_list: message.access_list,
+        odd_y_parity,
+        r,
+        s,
+    })
+}
+
+/// Decodes `transaction` from its EIP-2718 envelope as `eth_sendRawTransaction` does.
+fn raw_transaction_roundtrip(transaction: &TransactionV2, transaction_type: u8) -> TransactionV2 {
+    let raw = EnvelopedEncodable::encode(transaction);
+    assert_eq!(raw[0], transaction_type);
+    let decoded: TransactionV2 = EnvelopedDecodable::decode(&raw).unwrap();
+    assert_eq!(&decoded, transaction);
+    decoded
+}
+
+fn status_code(receipt: &ReceiptV3) -> Option<u8> {
+    match receipt {
+        ReceiptV3::EIP2930(data) | ReceiptV3::EIP1559(data) => Some(data.status_code),
+        ReceiptV3::Legacy(_) => None,
+    }
+}
+
+macro_rules! typed_transaction_tests {
+    ($name:ident, $runtime:ident) => {
+        mod $name {
+            use super::*;
+
+            use frame_support::traits::{Currency, Get, OnFinalize};
+            use pallet_evm::{AddressMapping, FeeCalculator};
+
+            use $runtime::{
+                Ethereum, Evm, Executive, Runtime, System, TransactionConverter, XBtcLedger,
+            };
+
+            fn new_test_ext() -> sp_io::TestExternalities {
+                let storage = frame_system::GenesisConfig::default()
+                    .build_storage::<Runtime>()
+                    .unwrap();
+                let mut ext = sp_io::TestExternalities::new(storage);
+                ext.execute_with(|| {
+                    System::set_block_number(1);
+                    let account =
+                        <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(sender());
+                    XBtcLedger::make_free_balance_be(&account, 1_000_000_000_000_000_000);
+                });
+                ext
+            }
+
+            #[test]
+            fn typed_transactions_should_work() {
+                new_test_ext().execute_with(|| {
+                    let chain_id = <Runtime as pallet_evm::Config>::ChainId::get();
+                    let gas_price = <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+                    let transactions = vec![
+                        raw_transaction_roundtrip(&eip2930(chain_id, 0, gas_price), 1),
+                        raw_transaction_roundtrip(&eip1559(chain_id, 1, gas_price), 2),
+                    ];
+                    let extrinsics = transactions
+                        .iter()
+                        .cloned()
+                        .map(|transaction| {
+                            fp_rpc::ConvertTransaction::convert_transaction(
+                                &TransactionConverter,
+                                transaction,
+                            )
+                        })
+                        .collect::<Vec<_>>();
+
+                    // The pool validates the transactions on the state of the best block, the
+                    // future nonce included.
+                    with_transaction(|| {
+                        for extrinsic in &extrinsics {
+                            assert_ok!(Executive::validate_transaction(
+                                TransactionSource::External,
+                                extrinsic.clone(),
+                                Default::default(),
+                            ));
+                        }
+                        TransactionOutcome::Rollback(())
+                    });
+
+                    for extrinsic in extrinsics {
+                        assert_ok!(Executive::apply_extrinsic(extrinsic).unwrap());
+                    }
+                    assert_eq!(Evm::account_basic(&sender()).nonce, 2.into());
+
+                    <Ethereum as OnFinalize<_>>::on_finalize(System::block_number());
+                    assert_eq!(
+                        Ethereum::current_block().unwrap().transactions,
+                        transactions
+                    );
+                    let receipts = Ethereum::current_receipts().unwrap();
+                    assert!(matches!(receipts[0], ReceiptV3::EIP2930(_)));
+                    assert!(matches!(receipts[1], ReceiptV3::EIP1559(_)));
+                    assert!(receipts
+                        .iter()
+                        .all(|receipt| status_code(receipt) == Some(1)));
+                    let statuses = Ethereum::current_transaction_statuses().unwrap();
+                    assert_eq!(statuses.len(), 2);
+                    assert!(statuses.iter().all(|status| status.to == Some(RECEIVER)));
+                });
+            }
+
+            #[test]
+            fn typed_transactions_on_another_chain_should_be_rejected() {
+                new_test_ext().execute_with(|| {
+                    let chain_id = <Runtime as pallet_evm::Config>::ChainId::get() + 1;
+                    let gas_price = <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+                    for transaction in [
+                        eip2930(chain_id, 0, gas_price),
+                        eip1559(chain_id, 0, gas_price),
+                    ] {
+                        let extrinsic = fp_rpc::ConvertTransaction::convert_transaction(
+                            &TransactionConverter,
+                            transaction,
+                        );
+                        assert!(Executive::validate_transaction(
+                            TransactionSource::External,
+                            extrinsic,
+                            Default::default(),
+                        )
+                        .is_err());
+                    }
+                });
+            }
+        }
+    };
+}
+
+typed_transaction_tests!(chainx, chainx_runtime);
+typed_transaction_tests!(dev, dev_runtime);
+typed_transaction_tests!(malan, malan_runtime);
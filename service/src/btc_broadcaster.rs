@@ -0,0 +1,113 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The broadcaster of the fully signed bitcoin withdrawal txs.
+//!
+//! Once the trustees fully sign a withdrawal proposal, the tx has to be broadcasted to the
+//! bitcoin network by someone and the withdrawals stay `Processing` until a trustee reports
+//! it via `XGatewayBitcoin::report_withdrawal_tx`. With `--btc-broadcast-url`, the node pushes
+//! the signed tx found at each finalized block to the Esplora API, i.e. `POST {url}/tx`, and
+//! reports the accepted tx back via the unsigned `report_withdrawal_broadcast` extrinsic.
+
+use std::{collections::HashSet, sync::Arc};
+
+use futures::StreamExt;
+
+use sc_client_api::BlockchainEvents;
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::{generic::BlockId, transaction_validity::TransactionSource};
+
+use chainx_primitives::{AccountId, Block};
+use xpallet_gateway_bitcoin_rpc_runtime_api::{XGatewayBitcoinApi, H256};
+
+const LOG_TARGET: &str = "btc-broadcaster";
+
+/// Pushes the raw tx in hex to the Esplora API, returns the txid in the response.
+fn broadcast(url: &str, raw_tx: &[u8]) -> Result<String, String> {
+    let url = format!("{}/tx", url.trim_end_matches('/'));
+    match ureq::post(&url).send_string(&hex::encode(raw_tx)) {
+        Ok(response) => response.into_string().map_err(|e| e.to_string()),
+        Err(ureq::Error::Status(code, response)) => Err(format!(
+            "status {}: {}",
+            code,
+            response.into_string().unwrap_or_default()
+        )),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Broadcasts the fully signed withdrawal txs on the finalized blocks.
+pub async fn run_btc_broadcaster<C, P>(client: Arc<C>, pool: Arc<P>, url: String)
+where
+    C: ProvideRuntimeApi<Block> + BlockchainEvents<Block>,
+    C::Api: XGatewayBitcoinApi<Block, AccountId>,
+    P: TransactionPool<Block = Block>,
+{
+    // The txs broadcasted by this node, only the report is resubmitted until it's finalized.
+    let mut broadcasted = HashSet::<H256>::new();
+    let mut notifications = client.finality_notification_stream();
+    while let Some(notification) = notifications.next().await {
+        let at = BlockId::hash(notification.hash);
+        let api = client.runtime_api();
+        let (txid, raw_tx) = match api.get_unreported_withdrawal_tx(&at) {
+            Ok(Some(tx)) => tx,
+            Ok(None) => {
+                broadcasted.clear();
+                continue;
+            }
+            Err(e) => {
+                log::debug!(
+                    target: LOG_TARGET,
+                    "Failed to read the withdrawal tx at #{}: {:?}",
+                    notification.header.number,
+                    e
+                );
+                continue;
+            }
+        };
+        if !broadcasted.contains(&txid) {
+            match broadcast(&url, &raw_tx) {
+                Ok(response) => {
+                    log::info!(
+                        target: LOG_TARGET,
+                        "Broadcasted the withdrawal tx {:?}, response: {}",
+                        txid,
+                        response.trim()
+                    );
+                    broadcasted.insert(txid);
+                }
+                Err(e) => {
+                    log::warn!(
+                        target: LOG_TARGET,
+                        "Failed to broadcast the withdrawal tx {:?}: {}",
+                        txid,
+                        e
+                    );
+                    continue;
+                }
+            }
+        }
+
+        let xt = match api.withdrawal_broadcast_report(&at, txid) {
+            Ok(xt) => xt,
+            Err(e) => {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "Failed to build the broadcast report of {:?}: {:?}",
+                    txid,
+                    e
+                );
+                continue;
+            }
+        };
+        // The report submitted on the previous blocks is rejected as imported already.
+        if let Err(e) = pool.submit_one(&at, TransactionSource::Local, xt).await {
+            log::debug!(
+                target: LOG_TARGET,
+                "Failed to submit the broadcast report of {:?}: {:?}",
+                txid,
+                e
+            );
+        }
+    }
+}
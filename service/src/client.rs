@@ -2,7 +2,7 @@
 
 use sp_runtime::traits::BlakeTwo256;
 
-use chainx_primitives::{AccountId, Balance, Block, BlockNumber, Index};
+use chainx_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Index};
 use xpallet_mining_asset_rpc_runtime_api::MiningWeight;
 use xpallet_mining_staking_rpc_runtime_api::VoteWeight;
 
@@ -15,6 +15,7 @@ pub trait RuntimeApiCollection:
     + sp_block_builder::BlockBuilder<Block>
     + frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index>
     + pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
+    + pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash>
     + sp_api::Metadata<Block>
     + sp_offchain::OffchainWorkerApi<Block>
     + sp_session::SessionKeys<Block>
@@ -46,6 +47,9 @@ pub trait RuntimeApiCollection:
         BlockNumber,
     > + xpallet_transaction_fee_rpc_runtime_api::XTransactionFeeApi<Block, Balance>
     + xpallet_btc_ledger_runtime_api::BtcLedgerApi<Block, AccountId, Balance>
+    + xp_governance::GovernanceApi<Block, AccountId, Balance, BlockNumber, Hash>
+    + xp_address::AddressApi<Block>
+    + xp_evm_tracing::EvmTracingApi<Block>
     + fp_rpc::EthereumRuntimeRPCApi<Block>
     + fp_rpc::ConvertTransactionRuntimeApi<Block>
 where
@@ -62,6 +66,7 @@ where
         + sp_block_builder::BlockBuilder<Block>
         + frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index>
         + pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
+        + pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash>
         + sp_api::Metadata<Block>
         + sp_offchain::OffchainWorkerApi<Block>
         + sp_session::SessionKeys<Block>
@@ -93,6 +98,9 @@ where
             BlockNumber,
         > + xpallet_transaction_fee_rpc_runtime_api::XTransactionFeeApi<Block, Balance>
         + xpallet_btc_ledger_runtime_api::BtcLedgerApi<Block, AccountId, Balance>
+        + xp_governance::GovernanceApi<Block, AccountId, Balance, BlockNumber, Hash>
+        + xp_address::AddressApi<Block>
+        + xp_evm_tracing::EvmTracingApi<Block>
         + fp_rpc::EthereumRuntimeRPCApi<Block>
         + fp_rpc::ConvertTransactionRuntimeApi<Block>,
     <Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
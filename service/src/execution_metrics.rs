@@ -0,0 +1,186 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Prometheus metrics of the runtime execution of the imported blocks.
+//!
+//! The block import executes a block in one `Core_execute_block` call, which can't be split
+//! by the runtime hooks from the outside. Each imported best block is executed once more on
+//! top of its parent state the way the block builder does, i.e. `initialize_block` (the
+//! `on_initialize` hooks), `apply_extrinsic` of each extrinsic and `finalize_block` (the
+//! `on_idle` and `on_finalize` hooks), the time of each phase is recorded. The execution
+//! uses the strategy of the block import (`--execution-import-block`), so the metrics
+//! reflect the wasm executor unless the native runtime is preferred.
+//!
+//! The extra execution doubles the cost of importing a block, it's only enabled by
+//! `--execution-metrics`.
+
+use std::{sync::Arc, time::Instant};
+
+use futures::StreamExt;
+
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::ExecutionContext;
+use sp_runtime::{
+    generic::{BlockId, Digest},
+    traits::{Block as BlockT, Header as HeaderT},
+};
+use substrate_prometheus_endpoint::{
+    exponential_buckets, register, Gauge, HistogramOpts, HistogramVec, PrometheusError, Registry,
+    U64,
+};
+
+const LOG_TARGET: &str = "execution-metrics";
+
+/// The Prometheus metrics of the block execution.
+#[derive(Clone)]
+pub struct ExecutionMetrics {
+    phase_seconds: HistogramVec,
+    extrinsic_count: Gauge<U64>,
+    heap_pages: Gauge<U64>,
+    runtime_cache_size: Gauge<U64>,
+}
+
+impl ExecutionMetrics {
+    /// Registers the metrics into `registry`, along with the executor settings in effect.
+    pub fn register(
+        registry: &Registry,
+        heap_pages: Option<u64>,
+        runtime_cache_size: u8,
+    ) -> Result<Self, PrometheusError> {
+        let metrics = Self {
+            phase_seconds: register(
+                HistogramVec::new(
+                    HistogramOpts::new(
+                        "block_execution_phase_seconds",
+                        "Time of executing each phase of the imported best blocks",
+                    )
+                    .buckets(exponential_buckets(0.0001, 2.0, 16)?),
+                    &["phase"],
+                )?,
+                registry,
+            )?,
+            extrinsic_count: register(
+                Gauge::new(
+                    "block_execution_extrinsic_count",
+                    "Number of the extrinsics of the latest executed block",
+                )?,
+                registry,
+            )?,
+            heap_pages: register(
+                Gauge::new(
+                    "executor_heap_pages",
+                    "Number of the heap pages of the wasm executor, 0 for the runtime default",
+                )?,
+                registry,
+            )?,
+            runtime_cache_size: register(
+                Gauge::new(
+                    "executor_runtime_cache_size",
+                    "Number of the compiled runtimes cached by the wasm executor",
+                )?,
+                registry,
+            )?,
+        };
+        metrics.heap_pages.set(heap_pages.unwrap_or_default());
+        metrics.runtime_cache_size.set(runtime_cache_size.into());
+        Ok(metrics)
+    }
+
+    fn observe(&self, phase: &str, started: Instant) {
+        self.phase_seconds
+            .with_label_values(&[phase])
+            .observe(started.elapsed().as_secs_f64());
+    }
+}
+
+/// Executes `hash` again phase by phase on top of its parent state and records the timing.
+fn execute_block<Block, C>(
+    client: &C,
+    hash: Block::Hash,
+    metrics: &ExecutionMetrics,
+) -> Result<(), String>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+    C::Api: BlockBuilder<Block>,
+{
+    let id = BlockId::hash(hash);
+    let header = client
+        .header(id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Header not found")?;
+    let extrinsics = client
+        .block_body(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Block body not found")?;
+
+    // The seal is added after the execution when a block is authored.
+    let digest = Digest {
+        logs: header
+            .digest()
+            .logs()
+            .iter()
+            .filter(|item| item.as_seal().is_none())
+            .cloned()
+            .collect(),
+    };
+    let header = <Block::Header as HeaderT>::new(
+        *header.number(),
+        Default::default(),
+        Default::default(),
+        *header.parent_hash(),
+        digest,
+    );
+    let parent = BlockId::hash(*header.parent_hash());
+    let api = client.runtime_api();
+
+    let started = Instant::now();
+    api.initialize_block_with_context(&parent, ExecutionContext::Importing, &header)
+        .map_err(|e| e.to_string())?;
+    metrics.observe("on_initialize", started);
+
+    let started = Instant::now();
+    for extrinsic in &extrinsics {
+        api.apply_extrinsic_with_context(&parent, ExecutionContext::Importing, extrinsic.clone())
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("{:?}", e))?;
+    }
+    metrics.observe("extrinsics", started);
+
+    let started = Instant::now();
+    api.finalize_block_with_context(&parent, ExecutionContext::Importing)
+        .map_err(|e| e.to_string())?;
+    metrics.observe("on_finalize", started);
+
+    metrics.extrinsic_count.set(extrinsics.len() as u64);
+
+    Ok(())
+}
+
+/// Records the execution metrics of every imported best block.
+pub async fn run_execution_metrics_worker<Block, C>(client: Arc<C>, metrics: ExecutionMetrics)
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block>
+        + HeaderBackend<Block>
+        + BlockBackend<Block>
+        + BlockchainEvents<Block>,
+    C::Api: BlockBuilder<Block>,
+{
+    let mut notifications = client.import_notification_stream();
+    while let Some(notification) = notifications.next().await {
+        if !notification.is_new_best {
+            continue;
+        }
+        if let Err(e) = execute_block(&*client, notification.hash, &metrics) {
+            log::debug!(
+                target: LOG_TARGET,
+                "Failed to execute block {:?} for the execution metrics: {}",
+                notification.hash,
+                e
+            );
+        }
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The watchdog of the GRANDPA finality.
+//!
+//! On every imported best block, the number of the blocks the finalized block lags behind the
+//! best one is reported via Prometheus. Once the lag exceeds `--finality-lag-threshold`, the
+//! finality is considered stalled: `finality_stalled` is set to 1, which the alerting rules
+//! should page on, and an error is logged with the recovery hint, i.e. restarting the node
+//! after `chainx revert-finality-safe`. The lag is ignored while the node is major syncing,
+//! during which the justifications are only imported at the authority set changes.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+
+use sc_client_api::BlockchainEvents;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::SyncOracle;
+use sp_runtime::traits::{Block as BlockT, NumberFor, SaturatedConversion, Saturating};
+use substrate_prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+
+const LOG_TARGET: &str = "finality-watchdog";
+
+/// The Prometheus metrics of the finality watchdog.
+#[derive(Clone)]
+pub struct FinalityWatchdogMetrics {
+    lag_blocks: Gauge<U64>,
+    stalled: Gauge<U64>,
+}
+
+impl FinalityWatchdogMetrics {
+    /// Registers the metrics into `registry`.
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            lag_blocks: register(
+                Gauge::new(
+                    "finality_lag_blocks",
+                    "Number of the blocks the finalized block lags behind the best block",
+                )?,
+                registry,
+            )?,
+            stalled: register(
+                Gauge::new(
+                    "finality_stalled",
+                    "Whether the finality lag exceeds the threshold, 1 for a critical stall",
+                )?,
+                registry,
+            )?,
+        })
+    }
+}
+
+/// The stall state of the finality, updated with the lag of every imported best block.
+#[derive(Debug, Default)]
+struct StallTracker {
+    stalled: bool,
+}
+
+/// The change of the stall state.
+#[derive(Debug, PartialEq, Eq)]
+enum StallTransition {
+    Stalled,
+    Resumed,
+}
+
+impl StallTracker {
+    /// Records `lag`, returns the transition if the finality just stalled or resumed.
+    fn update<N: PartialOrd>(&mut self, lag: N, max_lag: N) -> Option<StallTransition> {
+        let stalled = lag > max_lag;
+        let transition = match (self.stalled, stalled) {
+            (false, true) => Some(StallTransition::Stalled),
+            (true, false) => Some(StallTransition::Resumed),
+            _ => None,
+        };
+        self.stalled = stalled;
+        transition
+    }
+}
+
+/// Watches the finality lag of every imported best block, `max_lag` blocks at most.
+pub async fn run_finality_watchdog<Block, C, S>(
+    client: Arc<C>,
+    mut sync_oracle: S,
+    metrics: Option<FinalityWatchdogMetrics>,
+    max_lag: u32,
+) where
+    Block: BlockT,
+    C: HeaderBackend<Block> + BlockchainEvents<Block>,
+    S: SyncOracle,
+{
+    let max_lag: NumberFor<Block> = max_lag.into();
+    let mut tracker = StallTracker::default();
+
+    let mut notifications = client.import_notification_stream();
+    while let Some(notification) = notifications.next().await {
+        if !notification.is_new_best || sync_oracle.is_major_syncing() {
+            continue;
+        }
+
+        let info = client.info();
+        let lag = info.best_number.saturating_sub(info.finalized_number);
+        if let Some(metrics) = metrics.as_ref() {
+            metrics.lag_blocks.set(lag.saturated_into());
+        }
+
+        match tracker.update(lag, max_lag) {
+            Some(StallTransition::Stalled) => log::error!(
+                target: LOG_TARGET,
+                "🚨 Finality stalled: the finalized block #{} lags {} blocks behind the best \
+                 block #{}, stop the node and run `chainx revert-finality-safe` if the \
+                 finality doesn't resume",
+                info.finalized_number,
+                lag,
+                info.best_number,
+            ),
+            Some(StallTransition::Resumed) => log::info!(
+                target: LOG_TARGET,
+                "Finality resumed at #{}, {} blocks behind the best block #{}",
+                info.finalized_number,
+                lag,
+                info.best_number,
+            ),
+            None => log::debug!(target: LOG_TARGET, "Finality lag: {} blocks", lag),
+        }
+
+        if let Some(metrics) = metrics.as_ref() {
+            metrics.stalled.set(tracker.stalled.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stall_should_be_reported_once() {
+        let mut tracker = StallTracker::default();
+        assert_eq!(tracker.update(0u32, 100), None);
+        // The lag equal to the threshold is not a stall yet.
+        assert_eq!(tracker.update(100u32, 100), None);
+        assert!(!tracker.stalled);
+
+        assert_eq!(tracker.update(101u32, 100), Some(StallTransition::Stalled));
+        assert!(tracker.stalled);
+        assert_eq!(tracker.update(150u32, 100), None);
+        assert!(tracker.stalled);
+
+        assert_eq!(tracker.update(100u32, 100), Some(StallTransition::Resumed));
+        assert!(!tracker.stalled);
+        assert_eq!(tracker.update(2u32, 100), None);
+
+        // A new stall is reported again.
+        assert_eq!(tracker.update(200u32, 100), Some(StallTransition::Stalled));
+    }
+
+    #[test]
+    fn metrics_should_be_registered() {
+        let registry = Registry::new();
+        let metrics = FinalityWatchdogMetrics::register(&registry).unwrap();
+        metrics.lag_blocks.set(3);
+        metrics.stalled.set(1);
+
+        let families = registry.gather();
+        let value = |name: &str| {
+            families
+                .iter()
+                .find(|family| family.get_name() == name)
+                .map(|family| family.get_metric()[0].get_gauge().get_value())
+        };
+        assert_eq!(value("finality_lag_blocks"), Some(3.0));
+        assert_eq!(value("finality_stalled"), Some(1.0));
+
+        // The metrics can't be registered twice into the same registry.
+        assert!(FinalityWatchdogMetrics::register(&registry).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The chain health summary reported to the telemetry.
+//!
+//! Every `HEALTH_TELEMETRY_INTERVAL` finalized blocks, the bitcoin gateway and the staking
+//! status at the finalized block are sent as a `chainx.health` message over the telemetry
+//! connections of the node, i.e. the ChainX telemetry of the chain spec unless overridden by
+//! `--telemetry-url`, so that the dashboard charts them without a separate indexer.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+
+use sc_client_api::BlockchainEvents;
+use sc_telemetry::{telemetry, TelemetryHandle, SUBSTRATE_INFO};
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::generic::BlockId;
+
+use chainx_primitives::{AccountId, Balance, Block, BlockNumber};
+use xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi;
+use xpallet_mining_staking_rpc_runtime_api::{VoteWeight, XStakingApi};
+
+const LOG_TARGET: &str = "health-telemetry";
+
+/// Report the health summary every 10 finalized blocks, i.e. about once a minute.
+const HEALTH_TELEMETRY_INTERVAL: BlockNumber = 10;
+
+/// Reports the chain health summary to the telemetry on the finalized blocks.
+pub async fn run_health_telemetry_worker<C>(client: Arc<C>, telemetry: TelemetryHandle)
+where
+    C: ProvideRuntimeApi<Block> + BlockchainEvents<Block>,
+    C::Api: XGatewayBitcoinApi<Block, AccountId>
+        + XStakingApi<Block, AccountId, Balance, VoteWeight, BlockNumber>,
+{
+    let telemetry = Some(telemetry);
+    let mut notifications = client.finality_notification_stream();
+    while let Some(notification) = notifications.next().await {
+        let number = notification.header.number;
+        if number % HEALTH_TELEMETRY_INTERVAL != 0 {
+            continue;
+        }
+
+        let at = BlockId::hash(notification.hash);
+        let api = client.runtime_api();
+        let (gateway, validators) = match (api.get_gateway_metrics(&at), api.validators(&at)) {
+            (Ok(gateway), Ok(validators)) => (gateway, validators),
+            (Err(e), _) | (_, Err(e)) => {
+                log::debug!(
+                    target: LOG_TARGET,
+                    "Failed to read the health summary at #{}: {:?}",
+                    number,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let validator_count = validators.iter().filter(|v| v.is_validating).count();
+        let total_staked = validators
+            .iter()
+            .map(|v| v.ledger.total_nomination)
+            .fold(Balance::default(), |acc, x| acc.saturating_add(x));
+
+        telemetry!(
+            telemetry;
+            SUBSTRATE_INFO;
+            "chainx.health";
+            "height" => number,
+            "best_hash" => ?notification.hash,
+            "btc_best_height" => gateway.best_height,
+            "btc_confirmed_height" => gateway.confirmed_height,
+            "btc_pending_withdrawals" => gateway.pending_withdrawal_count,
+            "validator_count" => validator_count,
+            // The balance exceeds the safe integers of the dashboard.
+            "total_staked" => total_staked.to_string(),
+        );
+    }
+}
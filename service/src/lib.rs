@@ -6,17 +6,23 @@ use sc_client_api::{BlockBackend, ExecutorProvider};
 use sc_consensus_babe::SlotProportion;
 use sc_executor::{NativeElseWasmExecutor, NativeExecutionDispatch};
 use sc_finality_grandpa::FinalityProofProvider as GrandpaFinalityProofProvider;
-use sc_network::{Event, NetworkService};
+use sc_network::{config::SyncMode, Event, NetworkService};
 use sc_service::{config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_api::ConstructRuntimeApi;
 use sp_runtime::traits::Block as BlockT;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
-use chainx_primitives::Block;
+use chainx_primitives::{AccountId, Block};
 
+mod btc_broadcaster;
 mod client;
+mod execution_metrics;
+mod finality_watchdog;
+mod health_telemetry;
+mod upgrade_guard;
 use client::RuntimeApiCollection;
 
 // EVM
@@ -57,7 +63,17 @@ pub type ConsensusResult<RuntimeApi, Executor> = (
     sc_finality_grandpa::LinkHalf<Block, FullClient<RuntimeApi, Executor>, FullSelectChain>,
 );
 
-pub fn frontier_database_dir(config: &Configuration) -> std::path::PathBuf {
+pub fn frontier_database_dir(
+    config: &Configuration,
+    custom_path: Option<&Path>,
+) -> std::path::PathBuf {
+    match custom_path {
+        Some(path) => path.to_path_buf(),
+        None => default_frontier_database_dir(config),
+    }
+}
+
+fn default_frontier_database_dir(config: &Configuration) -> std::path::PathBuf {
     let config_dir = config
         .base_path
         .as_ref()
@@ -68,11 +84,46 @@ pub fn frontier_database_dir(config: &Configuration) -> std::path::PathBuf {
     config_dir.join("frontier").join("db")
 }
 
-pub fn open_frontier_backend(config: &Configuration) -> Result<Arc<fc_db::Backend<Block>>, String> {
+/// Moves the frontier database of an existing node from the default location to the custom
+/// one, so that the ethereum mappings don't have to be synced from the genesis again.
+fn migrate_frontier_database(config: &Configuration, custom_path: &Path) -> Result<(), String> {
+    let default_path = default_frontier_database_dir(config);
+    if custom_path == default_path || custom_path.exists() || !default_path.exists() {
+        return Ok(());
+    }
+
+    if let Some(parent) = custom_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::rename(&default_path, custom_path).map_err(|e| {
+        format!(
+            "Failed to move the frontier database from {} to {}: {}, \
+             please move it manually or remove it to sync the ethereum mappings again",
+            default_path.display(),
+            custom_path.display(),
+            e
+        )
+    })?;
+    log::info!(
+        "Moved the frontier database from {} to {}",
+        default_path.display(),
+        custom_path.display()
+    );
+
+    Ok(())
+}
+
+pub fn open_frontier_backend(
+    config: &Configuration,
+    custom_path: Option<&Path>,
+) -> Result<Arc<fc_db::Backend<Block>>, String> {
+    if let Some(path) = custom_path {
+        migrate_frontier_database(config, path)?;
+    }
     Ok(Arc::new(fc_db::Backend::<Block>::new(
         &fc_db::DatabaseSettings {
             source: fc_db::DatabaseSettingsSrc::RocksDb {
-                path: frontier_database_dir(config),
+                path: frontier_database_dir(config, custom_path),
                 cache_size: 0,
             },
         },
@@ -93,6 +144,7 @@ fn set_prometheus_registry(config: &mut Configuration) -> Result<(), ServiceErro
 
 pub fn new_partial<RuntimeApi, Executor>(
     config: &mut Configuration,
+    options: &NodeOptions,
 ) -> Result<
     sc_service::PartialComponents<
         FullClient<RuntimeApi, Executor>,
@@ -184,7 +236,7 @@ where
 
     let filter_pool: Option<FilterPool> = Some(Arc::new(Mutex::new(BTreeMap::new())));
     let fee_history_cache: FeeHistoryCache = Arc::new(Mutex::new(BTreeMap::new()));
-    let frontier_backend = open_frontier_backend(config)?;
+    let frontier_backend = open_frontier_backend(config, options.frontier_backend_path.as_deref())?;
 
     let (grandpa_block_import, grandpa_link) = sc_finality_grandpa::block_import(
         client.clone(),
@@ -248,6 +300,59 @@ where
     })
 }
 
+/// ChainX specific options of the full node service.
+#[derive(Clone, Debug)]
+pub struct NodeOptions {
+    /// Archive the relayed bitcoin headers in the aux database, so that they can still be
+    /// queried once the state is pruned.
+    pub btc_header_archive: bool,
+    /// Tune the network for the peers with a high latency.
+    pub high_latency_network: bool,
+    /// Override the yamux window size of each substream in bytes.
+    pub yamux_window_size: Option<u32>,
+    /// Execute the imported best blocks once more to record the time of each phase.
+    pub execution_metrics: bool,
+    /// The number of the latest blocks kept in the fee history cache of the EVM RPC.
+    pub fee_history_limit: u64,
+    /// Enable the EVM tracing RPC, which requires the tracing runtime override.
+    pub evm_tracing: bool,
+    /// Store the frontier database in this directory instead of the one under the base path.
+    pub frontier_backend_path: Option<std::path::PathBuf>,
+    /// Start the node even though it predates the runtime upgrade enacted on chain.
+    pub allow_outdated_client: bool,
+    /// Broadcast the fully signed bitcoin withdrawal txs to this Esplora API.
+    pub btc_broadcast_url: Option<String>,
+    /// The number of the blocks the finalized block may lag behind the best block before the
+    /// finality is considered stalled.
+    pub finality_lag_threshold: u32,
+    /// The ACL file of the RPC methods per listening interface.
+    pub rpc_methods_acl: Option<std::path::PathBuf>,
+}
+
+/// The yamux window size used by the high latency network profile.
+///
+/// The default window (256 KiB) caps a substream at ~2.5 MiB/s with a 100ms round trip, the
+/// block responses of the EVM-heavy blocks are throttled across continents.
+const HIGH_LATENCY_YAMUX_WINDOW_SIZE: u32 = 8 * 1024 * 1024;
+
+/// The minimal number of peers to download the same block range from in parallel used by the
+/// high latency network profile.
+const HIGH_LATENCY_MAX_PARALLEL_DOWNLOADS: u32 = 8;
+
+impl NodeOptions {
+    fn apply_network_tuning(&self, network: &mut sc_network::config::NetworkConfiguration) {
+        if self.high_latency_network {
+            network.yamux_window_size = Some(HIGH_LATENCY_YAMUX_WINDOW_SIZE);
+            network.max_parallel_downloads = network
+                .max_parallel_downloads
+                .max(HIGH_LATENCY_MAX_PARALLEL_DOWNLOADS);
+        }
+        if let Some(size) = self.yamux_window_size {
+            network.yamux_window_size = Some(size);
+        }
+    }
+}
+
 pub struct NewFullBase<RuntimeApi, Executor>
 where
     RuntimeApi:
@@ -279,6 +384,7 @@ fn remote_keystore(_url: &str) -> Result<Arc<LocalKeystore>, &'static str> {
 /// Creates a full service from the configuration.
 pub fn new_full_base<RuntimeApi, Executor>(
     mut config: Configuration,
+    options: NodeOptions,
 ) -> Result<NewFullBase<RuntimeApi, Executor>, ServiceError>
 where
     RuntimeApi:
@@ -296,7 +402,16 @@ where
         select_chain,
         transaction_pool,
         other: (import_setup, mut telemetry, frontier_setup),
-    } = new_partial(&mut config)?;
+    } = new_partial(&mut config, &options)?;
+
+    upgrade_guard::check_runtime_upgrade(
+        &*client,
+        Executor::native_version().runtime_version.spec_version,
+        &config.impl_version,
+        options.allow_outdated_client,
+    )?;
+
+    options.apply_network_tuning(&mut config.network);
 
     if let Some(url) = &config.keystore_remote {
         match remote_keystore(url) {
@@ -330,6 +445,14 @@ where
             grandpa_protocol_name.clone(),
         ));
 
+    // Warp sync (`--sync warp`) downloads the GRANDPA finality proofs of the authority set
+    // changes up to the latest finalized block, which are served by the provider below.
+    if config.network.sync_mode == SyncMode::Warp && config.disable_grandpa {
+        return Err(ServiceError::Other(
+            "Warp sync relies on GRANDPA finality proofs and can not be used with `--no-grandpa`"
+                .into(),
+        ));
+    }
     let warp_sync = Arc::new(sc_finality_grandpa::warp_proof::NetworkProvider::new(
         backend.clone(),
         grandpa_link.shared_authority_set().clone(),
@@ -365,12 +488,15 @@ where
     let name = config.network.node_name.clone();
     let enable_grandpa = !config.disable_grandpa;
     let prometheus_registry = config.prometheus_registry().cloned();
+    let default_heap_pages = config.default_heap_pages;
+    let runtime_cache_size = config.runtime_cache_size;
 
     // EVM
     let subscription_task_executor =
         sc_rpc::SubscriptionTaskExecutor::new(task_manager.spawn_handle());
     let overrides = chainx_rpc::overrides_handle(client.clone());
-    let fee_history_limit = 2048;
+    let fee_history_limit = options.fee_history_limit;
+    let enable_evm_tracing = options.evm_tracing;
     let block_data_cache = Arc::new(fc_rpc::EthBlockDataCacheTask::new(
         task_manager.spawn_handle(),
         overrides.clone(),
@@ -379,6 +505,16 @@ where
         prometheus_registry.clone(),
     ));
 
+    let peer_bans = Arc::new(chainx_rpc::network::PeerBans::load(client.clone())?);
+    let rpc_access_control = options
+        .rpc_methods_acl
+        .as_deref()
+        .map(chainx_rpc::access_control::RpcAccessControl::load)
+        .transpose()
+        .map_err(ServiceError::Other)?
+        .map(Arc::new);
+    let rpc_max_payload = config.rpc_max_payload;
+
     let rpc_extensions_builder = {
         let justification_stream = grandpa_link.justification_stream();
         let shared_authority_set = grandpa_link.shared_authority_set().clone();
@@ -405,6 +541,8 @@ where
         let fee_history_cache = fee_history_cache.clone();
         let is_authority = false;
         let max_past_logs = 10000;
+        let peer_bans = peer_bans.clone();
+        let access_control = rpc_access_control.clone();
 
         Box::new(move |deny_unsafe, subscription_executor| {
             let deps = chainx_rpc::FullDeps {
@@ -436,7 +574,12 @@ where
                     fee_history_cache: fee_history_cache.clone(),
                     overrides: overrides.clone(),
                     block_data_cache: block_data_cache.clone(),
+                    enable_evm_tracing,
                 },
+                network: network.clone(),
+                peer_bans: peer_bans.clone(),
+                access_control: access_control.clone(),
+                rpc_max_payload,
             };
 
             chainx_rpc::create_full(deps, subscription_task_executor.clone()).map_err(Into::into)
@@ -501,6 +644,84 @@ where
         EthTask::ethereum_schema_cache_task(Arc::clone(&client), Arc::clone(&frontier_backend)),
     );
 
+    task_manager.spawn_handle().spawn(
+        "peer-bans",
+        Some("networking"),
+        chainx_rpc::network::run_peer_bans_worker(network.clone(), peer_bans),
+    );
+
+    if options.btc_header_archive {
+        task_manager.spawn_handle().spawn(
+            "btc-header-archive",
+            None,
+            xpallet_gateway_bitcoin_rpc::archive::run_header_archive_worker::<Block, _, AccountId>(
+                client.clone(),
+            ),
+        );
+    }
+
+    if let Some(registry) = prometheus_registry.as_ref() {
+        let metrics = xpallet_gateway_bitcoin_rpc::metrics::GatewayMetrics::register(registry)?;
+        task_manager.spawn_handle().spawn(
+            "btc-gateway-metrics",
+            None,
+            xpallet_gateway_bitcoin_rpc::metrics::run_gateway_metrics_worker::<Block, _, AccountId>(
+                client.clone(),
+                metrics,
+            ),
+        );
+    }
+
+    if enable_grandpa {
+        let metrics = prometheus_registry
+            .as_ref()
+            .map(finality_watchdog::FinalityWatchdogMetrics::register)
+            .transpose()?;
+        task_manager.spawn_handle().spawn(
+            "finality-watchdog",
+            None,
+            finality_watchdog::run_finality_watchdog(
+                client.clone(),
+                network.clone(),
+                metrics,
+                options.finality_lag_threshold,
+            ),
+        );
+    }
+
+    if let Some(telemetry) = telemetry.as_ref() {
+        task_manager.spawn_handle().spawn(
+            "health-telemetry",
+            None,
+            health_telemetry::run_health_telemetry_worker(client.clone(), telemetry.handle()),
+        );
+    }
+
+    if let Some(url) = options.btc_broadcast_url.clone() {
+        // The broadcast blocks on the HTTP request.
+        task_manager.spawn_handle().spawn_blocking(
+            "btc-broadcaster",
+            None,
+            btc_broadcaster::run_btc_broadcaster(client.clone(), transaction_pool.clone(), url),
+        );
+    }
+
+    if let Some(registry) = prometheus_registry
+        .as_ref()
+        .filter(|_| options.execution_metrics)
+    {
+        let metrics = execution_metrics::ExecutionMetrics::register(
+            registry,
+            default_heap_pages,
+            runtime_cache_size,
+        )?;
+        task_manager.spawn_handle().spawn_blocking(
+            "execution-metrics",
+            None,
+            execution_metrics::run_execution_metrics_worker(client.clone(), metrics),
+        );
+    }
+
     if let sc_service::config::Role::Authority { .. } = &role {
         let proposer = sc_basic_authorship::ProposerFactory::new(
             task_manager.spawn_handle(),
@@ -649,7 +870,10 @@ where
 }
 
 /// Builds a new service for a full client.
-pub fn new_full<RuntimeApi, Executor>(config: Configuration) -> Result<TaskManager, ServiceError>
+pub fn new_full<RuntimeApi, Executor>(
+    config: Configuration,
+    options: NodeOptions,
+) -> Result<TaskManager, ServiceError>
 where
     RuntimeApi:
         ConstructRuntimeApi<Block, FullClient<RuntimeApi, Executor>> + Send + Sync + 'static,
@@ -657,7 +881,7 @@ where
         RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
     Executor: NativeExecutionDispatch + 'static,
 {
-    new_full_base(config).map(|base: NewFullBase<RuntimeApi, Executor>| base.task_manager)
+    new_full_base(config, options).map(|base: NewFullBase<RuntimeApi, Executor>| base.task_manager)
 }
 
 /// Can be called for a `Configuration` to check if it is a configuration for the `ChainX` network.
@@ -684,12 +908,15 @@ impl IdentifyVariant for Box<dyn sc_service::ChainSpec> {
     }
 }
 
-pub fn build_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn build_full(
+    config: Configuration,
+    options: NodeOptions,
+) -> Result<TaskManager, ServiceError> {
     if config.chain_spec.is_chainx() {
-        new_full::<chainx_runtime::RuntimeApi, chainx_executor::ChainXExecutor>(config)
+        new_full::<chainx_runtime::RuntimeApi, chainx_executor::ChainXExecutor>(config, options)
     } else if config.chain_spec.is_malan() {
-        new_full::<malan_runtime::RuntimeApi, chainx_executor::MalanExecutor>(config)
+        new_full::<malan_runtime::RuntimeApi, chainx_executor::MalanExecutor>(config, options)
     } else {
-        new_full::<dev_runtime::RuntimeApi, chainx_executor::DevExecutor>(config)
+        new_full::<dev_runtime::RuntimeApi, chainx_executor::DevExecutor>(config, options)
     }
 }
@@ -0,0 +1,104 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The startup check against the runtime upgrade announced in `XSystem`.
+//!
+//! A runtime upgrade may rely on the new host functions or the new client side logic, the
+//! nodes running an old binary stall once it's enacted. The governance announces the minimum
+//! spec version of the native runtime and the minimum client version along with the
+//! enactment block via `XSystem::announce_runtime_upgrade`, an outdated node warns about it
+//! before the enactment block and refuses to start after it, unless
+//! `--allow-outdated-client` is given.
+
+use codec::Decode;
+
+use sc_client_api::StorageProvider;
+use sc_service::error::Error as ServiceError;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::generic::BlockId;
+
+use chainx_primitives::{Block, BlockNumber};
+use xpallet_system::{parse_client_version, RuntimeUpgrade};
+
+const LOG_TARGET: &str = "upgrade-guard";
+
+fn announcement_key() -> StorageKey {
+    let mut key = twox_128(b"XSystem").to_vec();
+    key.extend_from_slice(&twox_128(b"RuntimeUpgradeAnnouncement"));
+    StorageKey(key)
+}
+
+/// Checks the node against the runtime upgrade announced at the best block.
+///
+/// Returns an error if the node is outdated and the upgrade is enacted already.
+pub fn check_runtime_upgrade<Client, Backend>(
+    client: &Client,
+    native_spec_version: u32,
+    impl_version: &str,
+    allow_outdated: bool,
+) -> Result<(), ServiceError>
+where
+    Client: HeaderBackend<Block> + StorageProvider<Block, Backend>,
+    Backend: sc_client_api::Backend<Block>,
+{
+    let info = client.info();
+    let data = client
+        .storage(&BlockId::Hash(info.best_hash), &announcement_key())
+        .map_err(|e| ServiceError::Other(format!("Failed to read the runtime upgrade: {}", e)))?;
+    let upgrade = match data {
+        Some(data) => RuntimeUpgrade::<BlockNumber>::decode(&mut &data.0[..]).map_err(|e| {
+            ServiceError::Other(format!("Failed to decode the runtime upgrade: {}", e))
+        })?,
+        None => return Ok(()),
+    };
+
+    let min_client_version = String::from_utf8_lossy(&upgrade.min_client_version);
+    let client_outdated = match (
+        parse_client_version(impl_version.as_bytes()),
+        parse_client_version(&upgrade.min_client_version),
+    ) {
+        (Some(current), Some(min)) => current < min,
+        _ => false,
+    };
+    let runtime_outdated = native_spec_version < upgrade.min_spec_version;
+    if !client_outdated && !runtime_outdated {
+        log::info!(
+            target: LOG_TARGET,
+            "Ready for the runtime upgrade to spec version {} at block #{}",
+            upgrade.min_spec_version,
+            upgrade.enactment_block,
+        );
+        return Ok(());
+    }
+
+    let reason = format!(
+        "the runtime upgrade at block #{} requires the client version {} with the native \
+         spec version {}, while this node is {} with the native spec version {}",
+        upgrade.enactment_block,
+        min_client_version,
+        upgrade.min_spec_version,
+        impl_version,
+        native_spec_version,
+    );
+    if info.best_number < upgrade.enactment_block {
+        log::warn!(
+            target: LOG_TARGET,
+            "Upgrade the node within {} blocks, {}",
+            upgrade.enactment_block - info.best_number,
+            reason,
+        );
+        Ok(())
+    } else if allow_outdated {
+        log::warn!(
+            target: LOG_TARGET,
+            "Starting an outdated node as `--allow-outdated-client` is given, {}",
+            reason,
+        );
+        Ok(())
+    } else {
+        Err(ServiceError::Other(format!(
+            "The node is outdated, {}. Upgrade the node or start with `--allow-outdated-client`",
+            reason
+        )))
+    }
+}
@@ -109,6 +109,7 @@ impl xpallet_assets_registrar::Config for Test {
     type Event = Event;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type TechnicalOrigin = frame_system::EnsureRoot<AccountId32>;
     type WeightInfo = ();
 }
 
@@ -118,6 +119,9 @@ impl xpallet_assets::Config for Test {
     type TreasuryAccount = ();
     type OnCreatedAccount = frame_system::Provider<Test>;
     type OnAssetChanged = ();
+    type RestrictionOrigin = frame_system::EnsureRoot<AccountId32>;
+    type DustReapBounty = ();
+    type DustReapExempt = frame_support::traits::Nothing;
     type WeightInfo = ();
 }
 
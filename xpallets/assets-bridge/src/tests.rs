@@ -513,3 +513,52 @@ fn bridge_accounts_should_equal() {
 
     assert_eq!(bridge_admin1, bridge_admin2);
 }
+
+#[test]
+fn swap_frozen_xbtc_should_not_work() {
+    new_test_ext().execute_with(|| {
+        let xbtc_asset_id = 1;
+        assert_ok!(XAssetsRegistrar::register(
+            Origin::root(),
+            xbtc_asset_id,
+            xpallet_assets_registrar::AssetInfo::new::<Test>(
+                b"X-BTC".to_vec(),
+                b"X-BTC".to_vec(),
+                xpallet_assets_registrar::Chain::Bitcoin,
+                8,
+                b"ChainX's cross-chain Bitcoin".to_vec(),
+            )
+            .unwrap(),
+            true,
+            true
+        ));
+        assert_ok!(XAssets::issue(&xbtc_asset_id, &BOB.into(), 100, false));
+
+        assert_ok!(XAssets::freeze_account_asset(
+            Origin::root(),
+            BOB.into(),
+            xbtc_asset_id,
+            xpallet_assets::FreezeReason::Compromise
+        ));
+        assert_noop!(
+            XAssetsBridge::swap_xbtc_to_btc(
+                Origin::signed(BOB.into()),
+                10,
+                H160::from_slice(&EVM_ADDR)
+            ),
+            xpallet_assets::Error::<Test>::AccountAssetFrozen
+        );
+
+        assert_ok!(XAssets::thaw_account_asset(
+            Origin::root(),
+            BOB.into(),
+            xbtc_asset_id
+        ));
+        assert_ok!(XAssetsBridge::swap_xbtc_to_btc(
+            Origin::signed(BOB.into()),
+            10,
+            H160::from_slice(&EVM_ADDR)
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB.into(), &xbtc_asset_id), 90);
+    });
+}
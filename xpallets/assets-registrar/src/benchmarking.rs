@@ -5,7 +5,10 @@ use frame_system::RawOrigin;
 
 use chainx_primitives::AssetId;
 
-use crate::{AssetInfo, AssetInfoOf, AssetOnline, Call, Chain, Config, Pallet};
+use crate::{
+    AssetInfo, AssetInfoOf, AssetMetadata, AssetMetadataOf, AssetMinBalanceOf, AssetOnline, Call,
+    Chain, Config, Pallet,
+};
 
 const ASSET_ID: AssetId = 8888;
 
@@ -62,6 +65,28 @@ benchmarks! {
         new_asset_info.set_desc(b"new_desc".to_vec());
         assert_eq!(AssetInfoOf::<T>::get(ASSET_ID).unwrap(), new_asset_info);
     }
+
+    update_asset_metadata {
+        let asset_info = b_asset_info_test_data::<T>();
+        Pallet::<T>::register(RawOrigin::Root.into(), ASSET_ID, asset_info, true, true)?;
+        let metadata = AssetMetadata {
+            logo_uri_hash: Some([1u8; 32].into()),
+            decimals_override: Some(6),
+            erc20_address: Some([2u8; 20].into()),
+            coingecko_id: b"token-id".to_vec(),
+        };
+    }: _(RawOrigin::Root, ASSET_ID, metadata.clone())
+    verify {
+        assert_eq!(AssetMetadataOf::<T>::get(ASSET_ID), Some(metadata));
+    }
+
+    set_asset_min_balance {
+        let asset_info = b_asset_info_test_data::<T>();
+        Pallet::<T>::register(RawOrigin::Root.into(), ASSET_ID, asset_info, true, true)?;
+    }: _(RawOrigin::Root, ASSET_ID, 1_000)
+    verify {
+        assert_eq!(AssetMinBalanceOf::<T>::get(ASSET_ID), 1_000);
+    }
 }
 
 impl_benchmark_test_suite!(
@@ -26,7 +26,7 @@ use frame_support::{
 
 use chainx_primitives::{AssetId, Desc, Token};
 
-pub use self::types::AssetInfo;
+pub use self::types::{AssetInfo, AssetMetadata};
 pub use self::weights::WeightInfo;
 pub use xp_assets_registrar::{Chain, RegistrarHandler};
 
@@ -52,6 +52,9 @@ pub mod pallet {
         /// Handler for doing stuff after the asset is registered/deregistered.
         type RegistrarHandler: RegistrarHandler;
 
+        /// The origin allowed to update the asset metadata, e.g., the technical committee.
+        type TechnicalOrigin: EnsureOrigin<Self::Origin>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -164,6 +167,51 @@ pub mod pallet {
             AssetInfoOf::<T>::insert(id, info);
             Ok(())
         }
+
+        /// Update the metadata of an asset, the previous metadata is replaced.
+        ///
+        /// This is a technical committee or root operation.
+        #[pallet::weight(T::WeightInfo::update_asset_metadata())]
+        pub fn update_asset_metadata(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: AssetId,
+            metadata: AssetMetadata,
+        ) -> DispatchResult {
+            T::TechnicalOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            Self::ensure_asset_exists(&id)?;
+            metadata.is_valid::<T>()?;
+
+            AssetMetadataOf::<T>::insert(id, metadata);
+
+            Self::deposit_event(Event::MetadataUpdated(id));
+            Ok(())
+        }
+
+        /// Set the minimum balance of an asset, the usable balance below it is regarded as dust
+        /// and collected into the treasury, zero disables the dust handling of the asset.
+        ///
+        /// This is a technical committee or root operation.
+        #[pallet::weight(T::WeightInfo::set_asset_min_balance())]
+        pub fn set_asset_min_balance(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: AssetId,
+            #[pallet::compact] min_balance: u128,
+        ) -> DispatchResult {
+            T::TechnicalOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            ensure!(id != T::NativeAssetId::get(), Error::<T>::DenyNativeAsset);
+            Self::ensure_asset_exists(&id)?;
+
+            AssetMinBalanceOf::<T>::insert(id, min_balance);
+
+            Self::deposit_event(Event::MinBalanceSet(id, min_balance));
+            Ok(())
+        }
     }
 
     /// Event for the XAssetRegistrar Pallet
@@ -176,6 +224,10 @@ pub mod pallet {
         Recovered(AssetId, bool),
         /// An asset was deregistered. [asset_id]
         Deregistered(AssetId),
+        /// The metadata of an asset was updated. [asset_id]
+        MetadataUpdated(AssetId),
+        /// The minimum balance of an asset was set. [asset_id, min_balance]
+        MinBalanceSet(AssetId, u128),
     }
 
     /// Error for the XAssetRegistrar Pallet
@@ -199,6 +251,10 @@ pub mod pallet {
         AssetAlreadyValid,
         /// The asset is invalid (not online).
         AssetIsInvalid,
+        /// CoinGecko id is too long or contains chars other than lowercase ASCII alphanumeric and '-'
+        InvalidCoingeckoId,
+        /// The native asset has no minimum balance in the registrar.
+        DenyNativeAsset,
     }
 
     /// Asset id list for each Chain.
@@ -212,6 +268,18 @@ pub mod pallet {
     #[pallet::getter(fn asset_info_of)]
     pub(super) type AssetInfoOf<T: Config> = StorageMap<_, Twox64Concat, AssetId, AssetInfo>;
 
+    /// Optional metadata of each asset.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_metadata_of)]
+    pub(super) type AssetMetadataOf<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, AssetMetadata>;
+
+    /// The minimum balance of each asset, see `set_asset_min_balance`.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_min_balance)]
+    pub(super) type AssetMinBalanceOf<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, u128, ValueQuery>;
+
     /// The map of asset to the online state.
     #[pallet::storage]
     #[pallet::getter(fn asset_online)]
@@ -1,7 +1,7 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
 use frame_support::{assert_noop, assert_ok, parameter_types, sp_io, traits::GenesisBuild};
-use sp_core::H256;
+use sp_core::{H160, H256};
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
@@ -10,7 +10,7 @@ use sp_runtime::{
 use chainx_primitives::AssetId;
 use xp_protocol::X_BTC;
 
-use crate::{self as xpallet_assets_registrar, AssetInfo, Chain, Config, Error};
+use crate::{self as xpallet_assets_registrar, AssetInfo, AssetMetadata, Chain, Config, Error};
 
 /// The AccountId alias in this test module.
 pub(crate) type BlockNumber = u64;
@@ -69,6 +69,7 @@ impl Config for Test {
     type Event = Event;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type TechnicalOrigin = frame_system::EnsureRoot<u64>;
     type WeightInfo = ();
 }
 
@@ -176,3 +177,71 @@ fn test_register() {
         );
     })
 }
+
+#[test]
+fn test_update_asset_metadata() {
+    ExtBuilder::default().build_and_execute(|| {
+        let metadata = AssetMetadata {
+            logo_uri_hash: Some(H256::repeat_byte(1)),
+            decimals_override: Some(6),
+            erc20_address: Some(H160::repeat_byte(2)),
+            coingecko_id: b"bitcoin".to_vec(),
+        };
+
+        assert_noop!(
+            XAssetsRegistrar::update_asset_metadata(Origin::signed(1), X_BTC, metadata.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XAssetsRegistrar::update_asset_metadata(Origin::root(), 10000, metadata.clone()),
+            Err::AssetDoesNotExist
+        );
+        assert_noop!(
+            XAssetsRegistrar::update_asset_metadata(
+                Origin::root(),
+                X_BTC,
+                AssetMetadata {
+                    coingecko_id: b"Bitcoin".to_vec(),
+                    ..Default::default()
+                }
+            ),
+            Err::InvalidCoingeckoId
+        );
+
+        assert_ok!(XAssetsRegistrar::update_asset_metadata(
+            Origin::root(),
+            X_BTC,
+            metadata.clone()
+        ));
+        assert_eq!(XAssetsRegistrar::asset_metadata_of(X_BTC), Some(metadata));
+    })
+}
+
+#[test]
+fn test_set_asset_min_balance() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssetsRegistrar::set_asset_min_balance(Origin::signed(1), X_BTC, 1000),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XAssetsRegistrar::set_asset_min_balance(Origin::root(), 0, 1000),
+            Err::DenyNativeAsset
+        );
+        assert_noop!(
+            XAssetsRegistrar::set_asset_min_balance(Origin::root(), 10000, 1000),
+            Err::AssetDoesNotExist
+        );
+
+        assert_eq!(XAssetsRegistrar::asset_min_balance(X_BTC), 0);
+        assert_ok!(XAssetsRegistrar::set_asset_min_balance(
+            Origin::root(),
+            X_BTC,
+            1000
+        ));
+        assert_eq!(XAssetsRegistrar::asset_min_balance(X_BTC), 1000);
+        System::assert_last_event(Event::XAssetsRegistrar(crate::Event::MinBalanceSet(
+            X_BTC, 1000,
+        )));
+    })
+}
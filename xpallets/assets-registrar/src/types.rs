@@ -6,7 +6,9 @@ use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 
 use frame_support::dispatch::{DispatchError, DispatchResult};
-use sp_std::fmt;
+use sp_core::{H160, H256};
+use sp_runtime::RuntimeDebug;
+use sp_std::{fmt, prelude::*};
 
 use chainx_primitives::{Decimals, Desc, Token};
 use xp_assets_registrar::Chain;
@@ -102,3 +104,25 @@ impl AssetInfo {
         self.token_name = token_name
     }
 }
+
+/// Optional metadata of an asset, mainly used by the wallets.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetMetadata {
+    /// Hash of the asset logo, the logo itself is hosted off-chain.
+    pub logo_uri_hash: Option<H256>,
+    /// Decimals displayed by the wallets instead of the ones in `AssetInfo`.
+    pub decimals_override: Option<Decimals>,
+    /// Address of the ERC-20 contract minted by the assets bridge.
+    pub erc20_address: Option<H160>,
+    /// Id of the asset on CoinGecko, empty if not listed.
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
+    pub coingecko_id: Vec<u8>,
+}
+
+impl AssetMetadata {
+    pub fn is_valid<T: Config>(&self) -> DispatchResult {
+        is_valid_coingecko_id::<T>(&self.coingecko_id)
+    }
+}
@@ -1,6 +1,9 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
-use xp_protocol::{ASSET_DESC_MAX_LEN, ASSET_TOKEN_NAME_MAX_LEN, ASSET_TOKEN_SYMBOL_MAX_LEN};
+use xp_protocol::{
+    ASSET_COINGECKO_ID_MAX_LEN, ASSET_DESC_MAX_LEN, ASSET_TOKEN_NAME_MAX_LEN,
+    ASSET_TOKEN_SYMBOL_MAX_LEN,
+};
 
 use super::*;
 
@@ -46,6 +49,18 @@ pub fn is_valid_desc<T: Config>(desc: &[u8]) -> DispatchResult {
     Ok(())
 }
 
+/// A CoinGecko id can only use lowercase ASCII alphanumeric character or "-", empty means unset.
+pub fn is_valid_coingecko_id<T: Config>(id: &[u8]) -> DispatchResult {
+    if id.len() > ASSET_COINGECKO_ID_MAX_LEN {
+        return Err(Error::<T>::InvalidCoingeckoId.into());
+    }
+    let is_valid = |c: &u8| -> bool { c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'-' };
+    if !id.iter().all(is_valid) {
+        return Err(Error::<T>::InvalidCoingeckoId.into());
+    }
+    Ok(())
+}
+
 /// Visible ASCII char [0x20, 0x7E]
 #[inline]
 fn is_ascii_visible(c: &u8) -> bool {
@@ -35,6 +35,8 @@ pub trait WeightInfo {
     fn deregister() -> Weight;
     fn recover() -> Weight;
     fn update_asset_info() -> Weight;
+    fn update_asset_metadata() -> Weight;
+    fn set_asset_min_balance() -> Weight;
 }
 
 /// Weights for xpallet_assets_registrar using the Substrate node and recommended hardware.
@@ -60,6 +62,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn update_asset_metadata() -> Weight {
+        (14_125_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_asset_min_balance() -> Weight {
+        (13_988_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -84,4 +96,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn update_asset_metadata() -> Weight {
+        (14_125_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_asset_min_balance() -> Weight {
+        (13_988_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
@@ -8,7 +8,9 @@ use sp_std::collections::btree_map::BTreeMap;
 use codec::Codec;
 
 pub use chainx_primitives::AssetId;
-pub use xpallet_assets::{AssetType, TotalAssetInfo};
+pub use xpallet_assets::{
+    AssetMetadata, AssetRestrictions, AssetSupply, AssetType, TotalAssetInfo,
+};
 
 sp_api::decl_runtime_apis! {
     pub trait XAssetsApi<AccountId, Balance>
@@ -19,5 +21,12 @@ sp_api::decl_runtime_apis! {
         fn assets_for_account(who: AccountId) -> BTreeMap<AssetId, BTreeMap<AssetType, Balance>>;
 
         fn assets() -> BTreeMap<AssetId, TotalAssetInfo<Balance>>;
+
+        fn asset_metadata(asset_id: AssetId) -> Option<AssetMetadata>;
+
+        fn asset_supply(asset_id: AssetId) -> AssetSupply<Balance>;
+
+        /// Get the restrictions of an asset.
+        fn asset_restrictions(asset_id: AssetId) -> AssetRestrictions;
     }
 }
@@ -18,7 +18,8 @@ use sp_runtime::{
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance};
 
 use xpallet_assets_rpc_runtime_api::{
-    AssetId, AssetType, TotalAssetInfo, XAssetsApi as XAssetsRuntimeApi,
+    AssetId, AssetMetadata, AssetRestrictions, AssetSupply, AssetType, TotalAssetInfo,
+    XAssetsApi as XAssetsRuntimeApi,
 };
 
 pub struct Assets<C, B> {
@@ -55,6 +56,31 @@ where
         &self,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<AssetId, TotalAssetInfo<RpcBalance<Balance>>>>;
+
+    /// Return the metadata (logo hash, decimals override, ERC-20 address, CoinGecko id) of an asset.
+    #[rpc(name = "xassets_assetMetadata")]
+    fn asset_metadata(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<AssetMetadata>>;
+
+    /// Return the total issuance and the cumulative burned balance of an asset, the reserves
+    /// backing the asset on the other chain should cover the total issuance.
+    #[rpc(name = "xassets_getAssetSupply")]
+    fn asset_supply(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<AssetSupply<RpcBalance<Balance>>>;
+
+    /// Return the restrictions of an asset, i.e., the forbidden operations in bits.
+    #[rpc(name = "xassets_restrictions")]
+    fn asset_restrictions(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<AssetRestrictions>;
 }
 
 impl<C, Block, AccountId, Balance> XAssetsApi<<Block as BlockT>::Hash, AccountId, Balance>
@@ -126,4 +152,41 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn asset_metadata(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<AssetMetadata>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.asset_metadata(&at, asset_id)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn asset_supply(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<AssetSupply<RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.asset_supply(&at, asset_id)
+            .map(|supply| AssetSupply {
+                total_issuance: supply.total_issuance.into(),
+                total_burned: supply.total_burned.into(),
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn asset_restrictions(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<AssetRestrictions> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.asset_restrictions(&at, asset_id)
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
@@ -60,6 +60,45 @@ benchmarks! {
     verify {
         assert_eq!(XAssets::<T>::asset_restrictions_of(&ASSET_ID), res);
     }
+
+    freeze_account_asset {
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+    }: _(RawOrigin::Root, user_lookup, ASSET_ID, FreezeReason::Compromise)
+    verify {
+        assert_eq!(XAssets::<T>::frozen_account_asset(&user, &ASSET_ID), Some(FreezeReason::Compromise));
+    }
+
+    thaw_account_asset {
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+        FrozenAccountAssets::<T>::insert(&user, ASSET_ID, FreezeReason::Compromise);
+    }: _(RawOrigin::Root, user_lookup, ASSET_ID)
+    verify {
+        assert!(XAssets::<T>::frozen_account_asset(&user, &ASSET_ID).is_none());
+    }
+
+    burn {
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+        let burn_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &user, burn_amount, true).unwrap();
+    }: _(RawOrigin::Root, user_lookup, ASSET_ID, burn_amount, Some(H256::repeat_byte(1)))
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&user, &ASSET_ID), Zero::zero());
+        assert_eq!(XAssets::<T>::total_burned(&ASSET_ID), burn_amount);
+    }
+
+    reap_dust {
+        let caller = whitelisted_caller();
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+        xpallet_assets_registrar::Pallet::<T>::set_asset_min_balance(RawOrigin::Root.into(), ASSET_ID, 1_000)?;
+        XAssets::<T>::issue(&ASSET_ID, &user, 999u32.into(), true).unwrap();
+    }: _(RawOrigin::Signed(caller), user_lookup, ASSET_ID)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&user, &ASSET_ID), Zero::zero());
+    }
 }
 
 #[cfg(test)]
@@ -75,6 +114,10 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_force_transfer());
             assert_ok!(Pallet::<Test>::test_benchmark_set_balance());
             assert_ok!(Pallet::<Test>::test_benchmark_set_asset_limit());
+            assert_ok!(Pallet::<Test>::test_benchmark_freeze_account_asset());
+            assert_ok!(Pallet::<Test>::test_benchmark_thaw_account_asset());
+            assert_ok!(Pallet::<Test>::test_benchmark_burn());
+            assert_ok!(Pallet::<Test>::test_benchmark_reap_dust());
         });
     }
 }
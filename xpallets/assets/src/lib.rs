@@ -18,6 +18,8 @@ mod trigger;
 pub mod types;
 pub mod weights;
 
+use codec::Encode;
+
 use sp_std::collections::btree_map::{
     BTreeMap,
     Entry::{Occupied, Vacant},
@@ -28,11 +30,16 @@ use frame_support::{
     ensure,
     inherent::Vec,
     log::{debug, error, info},
-    traits::{Currency, Get, HandleLifetime, LockableCurrency, ReservableCurrency},
+    traits::{Contains, Currency, Get, HandleLifetime, LockableCurrency, ReservableCurrency},
+    PalletId,
 };
 
 use frame_system::{ensure_root, ensure_signed, AccountInfo};
-use sp_runtime::traits::{CheckedAdd, CheckedSub, Saturating, StaticLookup, Zero};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedSub, Saturating, StaticLookup, TypeId, Zero},
+    Percent, SaturatedConversion,
+};
 
 use self::trigger::AssetChangedTrigger;
 use chainx_primitives::AssetId;
@@ -40,10 +47,11 @@ use xpallet_support::traits::TreasuryAccount;
 
 pub use self::traits::{ChainT, OnAssetChanged};
 pub use self::types::{
-    AssetErr, AssetRestrictions, AssetType, BalanceLock, TotalAssetInfo, WithdrawalLimit,
+    AssetErr, AssetRestrictions, AssetSupply, AssetType, BalanceLock, FreezeReason, TotalAssetInfo,
+    WithdrawalLimit,
 };
 pub use self::weights::WeightInfo;
-pub use xpallet_assets_registrar::{AssetInfo, Chain};
+pub use xpallet_assets_registrar::{AssetInfo, AssetMetadata, Chain};
 
 pub type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -77,6 +85,19 @@ pub mod pallet {
         /// The hook triggered whenever the asset balance of an account is changed.
         type OnAssetChanged: OnAssetChanged<Self::AccountId, BalanceOf<Self>>;
 
+        /// The origin allowed to update the asset restrictions and to freeze the assets of an
+        /// account, e.g., the technical committee.
+        type RestrictionOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The portion of the reaped dust paid to the caller of `reap_dust`.
+        #[pallet::constant]
+        type DustReapBounty: Get<Percent>;
+
+        /// The accounts holding the assets on behalf of the others whose dust can't be reaped,
+        /// e.g., the reward pots and the multisig accounts, the pallet accounts derived from a
+        /// `PalletId` are always exempted.
+        type DustReapExempt: Contains<Self::AccountId>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -103,6 +124,7 @@ pub mod pallet {
 
             Self::move_usable_balance(&id, &transactor, &dest, value)
                 .map_err::<Error<T>, _>(Into::into)?;
+            Self::collect_dust(&id, &transactor);
 
             Ok(())
         }
@@ -124,6 +146,7 @@ pub mod pallet {
             Self::can_transfer(&id)?;
             Self::move_usable_balance(&id, &transactor, &dest, value)
                 .map_err::<Error<T>, _>(Into::into)?;
+            Self::collect_dust(&id, &transactor);
             Ok(())
         }
 
@@ -151,7 +174,121 @@ pub mod pallet {
             restrictions: AssetRestrictions,
         ) -> DispatchResult {
             ensure_root(origin)?;
-            Self::set_asset_restrictions(id, restrictions)
+            Self::apply_asset_restrictions(id, restrictions)
+        }
+
+        /// Set the restrictions of an asset, e.g., forbid the transfers of a compromised
+        /// bridged asset at once, reserved for the technical committee and root.
+        #[pallet::weight(<T as Config>::WeightInfo::set_asset_limit())]
+        pub fn set_asset_restrictions(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: AssetId,
+            restrictions: AssetRestrictions,
+        ) -> DispatchResult {
+            T::RestrictionOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            Self::ensure_not_native_asset(&id)?;
+            Self::apply_asset_restrictions(id, restrictions)
+        }
+
+        /// Freeze the usable balance of an asset of an account, e.g., hold the X-BTC of an
+        /// exchange hot wallet during an incident, reserved for the technical committee and root.
+        ///
+        /// The frozen balance can't be transferred, withdrawn or used to place orders, the
+        /// reserved balances are not affected.
+        #[pallet::weight(<T as Config>::WeightInfo::freeze_account_asset())]
+        pub fn freeze_account_asset(
+            origin: OriginFor<T>,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] id: AssetId,
+            reason: FreezeReason,
+        ) -> DispatchResult {
+            T::RestrictionOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            Self::ensure_not_native_asset(&id)?;
+            xpallet_assets_registrar::Pallet::<T>::ensure_asset_exists(&id)?;
+
+            let who = T::Lookup::lookup(who)?;
+            FrozenAccountAssets::<T>::insert(&who, id, reason);
+            Self::deposit_event(Event::<T>::AccountAssetFrozen(who, id, reason));
+            Ok(())
+        }
+
+        /// Thaw the asset of an account frozen by `freeze_account_asset`.
+        #[pallet::weight(<T as Config>::WeightInfo::thaw_account_asset())]
+        pub fn thaw_account_asset(
+            origin: OriginFor<T>,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] id: AssetId,
+        ) -> DispatchResult {
+            T::RestrictionOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            let who = T::Lookup::lookup(who)?;
+            ensure!(
+                FrozenAccountAssets::<T>::take(&who, id).is_some(),
+                Error::<T>::AccountAssetNotFrozen
+            );
+            Self::deposit_event(Event::<T>::AccountAssetThawed(who, id));
+            Ok(())
+        }
+
+        /// Burn the usable balance of an account, reserved for root.
+        ///
+        /// `proof` is the hash of the transaction burning the backing on the other chain, e.g.,
+        /// an unspendable Bitcoin output, each proof can only be used once.
+        #[pallet::weight(<T as Config>::WeightInfo::burn())]
+        pub fn burn(
+            origin: OriginFor<T>,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] id: AssetId,
+            #[pallet::compact] value: BalanceOf<T>,
+            proof: Option<H256>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let who = T::Lookup::lookup(who)?;
+            Self::apply_burn(&id, &who, AssetType::Usable, value, proof)
+        }
+
+        /// Collect the dust of an asset of an account into the treasury, callable by anyone.
+        ///
+        /// The dust is the usable balance below the minimum balance of the asset in the
+        /// registrar while the account has no other balance of the asset. Only the dust of the
+        /// accounts that never sent a transaction can be reaped, the others collect their dust
+        /// by their own transfers, and never the dust of the pallet accounts and the accounts in
+        /// `DustReapExempt`. The caller is paid the `DustReapBounty` portion of the dust.
+        #[pallet::weight(<T as Config>::WeightInfo::reap_dust())]
+        pub fn reap_dust(
+            origin: OriginFor<T>,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] id: AssetId,
+        ) -> DispatchResult {
+            let reaper = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            Self::ensure_not_native_asset(&id)?;
+            ensure!(
+                frame_system::Pallet::<T>::account_nonce(&who).is_zero(),
+                Error::<T>::ActiveAccount
+            );
+            ensure!(
+                !Self::is_pallet_account(&who) && !T::DustReapExempt::contains(&who),
+                Error::<T>::ReapExemptAccount
+            );
+
+            let treasury = T::TreasuryAccount::treasury_account().ok_or(Error::<T>::NotDust)?;
+            let dust = Self::collect_dust(&id, &who).ok_or(Error::<T>::NotDust)?;
+
+            let bounty = T::DustReapBounty::get() * dust;
+            let bounty = Self::move_usable_balance(&id, &treasury, &reaper, bounty)
+                .map(|_| bounty)
+                .unwrap_or_else(|_| Zero::zero());
+
+            Self::deposit_event(Event::<T>::DustReaped(id, who, dust, reaper, bounty));
+            Ok(())
         }
     }
 
@@ -174,6 +311,25 @@ pub mod pallet {
         Destroyed(AssetId, T::AccountId, BalanceOf<T>),
         /// Set asset balance of an account by root. [asset_id, who, asset_type, amount]
         BalanceSet(AssetId, T::AccountId, AssetType, BalanceOf<T>),
+        /// Some balances of an asset were burned. [asset_id, who, amount, proof]
+        Burned(AssetId, T::AccountId, BalanceOf<T>, Option<H256>),
+        /// The restrictions of an asset were set. [asset_id, restrictions]
+        AssetRestrictionsSet(AssetId, AssetRestrictions),
+        /// The asset of an account was frozen. [who, asset_id, reason]
+        AccountAssetFrozen(T::AccountId, AssetId, FreezeReason),
+        /// The asset of an account was thawed. [who, asset_id]
+        AccountAssetThawed(T::AccountId, AssetId),
+        /// The dust of an account was collected into the treasury. [asset_id, who, amount]
+        DustCollected(AssetId, T::AccountId, BalanceOf<T>),
+        /// The dust of an account was reaped by someone, the bounty is paid out of the dust.
+        /// [asset_id, who, amount, reaper, bounty]
+        DustReaped(
+            AssetId,
+            T::AccountId,
+            BalanceOf<T>,
+            T::AccountId,
+            BalanceOf<T>,
+        ),
     }
 
     /// Error for the Assets Pallet
@@ -203,6 +359,18 @@ pub mod pallet {
         /// reference exists to allow a non-zero balance of a non-self-sufficient asset, or the
         /// maximum number of consumers has been reached.
         NoProvider,
+        /// The proof-of-burn has been recorded already.
+        DuplicateBurnProof,
+        /// The usable balance of the asset is frozen for the account.
+        AccountAssetFrozen,
+        /// The asset of the account is not frozen.
+        AccountAssetNotFrozen,
+        /// The asset balance of the account is not dust.
+        NotDust,
+        /// The account has sent transactions, its dust is only collected by its own transfers.
+        ActiveAccount,
+        /// The account holds the assets on behalf of the others, its dust can't be reaped.
+        ReapExemptAccount,
     }
 
     /// asset extend limit properties, set asset "can do", example, `CanTransfer`, `CanDestroyWithdrawal`
@@ -213,6 +381,12 @@ pub mod pallet {
     pub type AssetRestrictionsOf<T: Config> =
         StorageMap<_, Twox64Concat, AssetId, AssetRestrictions, ValueQuery>;
 
+    /// The assets frozen for an account along with the reason, see `freeze_account_asset`.
+    #[pallet::storage]
+    #[pallet::getter(fn frozen_account_asset)]
+    pub type FrozenAccountAssets<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, AssetId, FreezeReason>;
+
     /// asset balance for user&asset_id, use btree_map to accept different asset type
     #[pallet::storage]
     #[pallet::getter(fn asset_balance)]
@@ -232,6 +406,17 @@ pub mod pallet {
     pub type TotalAssetBalance<T: Config> =
         StorageMap<_, Twox64Concat, AssetId, BTreeMap<AssetType, BalanceOf<T>>, ValueQuery>;
 
+    /// The cumulative burned balance of an asset.
+    #[pallet::storage]
+    #[pallet::getter(fn total_burned)]
+    pub type TotalBurned<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, BalanceOf<T>, ValueQuery>;
+
+    /// The recorded proofs-of-burn, proof => (asset_id, burned amount).
+    #[pallet::storage]
+    #[pallet::getter(fn burn_proof_of)]
+    pub type BurnProofOf<T: Config> = StorageMap<_, Identity, H256, (AssetId, BalanceOf<T>)>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub assets_restrictions: Vec<(AssetId, AssetRestrictions)>,
@@ -262,7 +447,7 @@ pub mod pallet {
                 }
                 for (id, restrictions) in &config.assets_restrictions {
                     if *id != T::NativeAssetId::get() {
-                        Pallet::<T>::set_asset_restrictions(*id, *restrictions)
+                        Pallet::<T>::apply_asset_restrictions(*id, *restrictions)
                             .expect("should not fail in genesis, qed");
                     }
                 }
@@ -273,12 +458,13 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
-    fn set_asset_restrictions(
+    fn apply_asset_restrictions(
         asset_id: AssetId,
         restrictions: AssetRestrictions,
     ) -> DispatchResult {
         xpallet_assets_registrar::Pallet::<T>::ensure_asset_exists(&asset_id)?;
         AssetRestrictionsOf::<T>::insert(asset_id, restrictions);
+        Self::deposit_event(Event::<T>::AssetRestrictionsSet(asset_id, restrictions));
         Ok(())
     }
 
@@ -372,6 +558,14 @@ impl<T: Config> Pallet<T> {
         map.values().fold(Zero::zero(), |acc, &x| acc + x)
     }
 
+    /// Returns the total issuance and the cumulative burned balance of asset `id`.
+    pub fn asset_supply(id: &AssetId) -> AssetSupply<BalanceOf<T>> {
+        AssetSupply {
+            total_issuance: Self::total_issuance(id),
+            total_burned: Self::total_burned(id),
+        }
+    }
+
     /// Returns the total balance of asset `id` given the specific asset type `ty`.
     pub fn total_asset_balance_of(id: &AssetId, ty: AssetType) -> BalanceOf<T> {
         Self::total_asset_balance(id)
@@ -451,6 +645,42 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Burns the `ty` balance of `who` given the asset `id` and records the burned amount.
+    ///
+    /// Unlike the destroy operations, the backing of the burned balance is never released,
+    /// this is used by the governance and the withdrawal flow.
+    pub fn apply_burn(
+        id: &AssetId,
+        who: &T::AccountId,
+        ty: AssetType,
+        value: BalanceOf<T>,
+        proof: Option<H256>,
+    ) -> DispatchResult {
+        Self::ensure_not_native_asset(id)?;
+        xpallet_assets_registrar::Pallet::<T>::ensure_asset_is_valid(id)?;
+        if let Some(proof) = proof {
+            ensure!(
+                !BurnProofOf::<T>::contains_key(proof),
+                Error::<T>::DuplicateBurnProof
+            );
+        }
+
+        Self::inner_destroy(id, who, ty, value)?;
+
+        TotalBurned::<T>::mutate(id, |burned| *burned = burned.saturating_add(value));
+        if let Some(proof) = proof {
+            BurnProofOf::<T>::insert(proof, (*id, value));
+        }
+
+        info!(
+            target: "runtime::assets",
+            "[burn] who:{:?}, id:{}, type:{:?}, value:{:?}, proof:{:?}",
+            who, id, ty, value, proof
+        );
+        Self::deposit_event(Event::<T>::Burned(*id, who.clone(), value, proof));
+        Ok(())
+    }
+
     pub fn move_balance(
         id: &AssetId,
         from: &T::AccountId,
@@ -463,6 +693,9 @@ impl<T: Config> Pallet<T> {
         xpallet_assets_registrar::Pallet::<T>::ensure_asset_is_valid(id)
             .map_err(|_| AssetErr::InvalidAsset)?;
         Self::can_move(id).map_err(|_| AssetErr::NotAllow)?;
+        if from_type == AssetType::Usable && FrozenAccountAssets::<T>::contains_key(from, id) {
+            return Err(AssetErr::Frozen);
+        }
 
         if value == Zero::zero() {
             // value is zero, do not read storage, no event
@@ -513,6 +746,33 @@ impl<T: Config> Pallet<T> {
         Self::move_balance(id, from, AssetType::Usable, to, AssetType::Usable, value)
     }
 
+    /// Moves the usable balance of `who` below the minimum balance of asset `id` into the
+    /// treasury, returns the collected dust.
+    ///
+    /// The balance is not regarded as dust if the account has any other type of balance of
+    /// the asset, e.g. the reserved balance of an order, or if the move is not allowed.
+    pub fn collect_dust(id: &AssetId, who: &T::AccountId) -> Option<BalanceOf<T>> {
+        let min_balance: BalanceOf<T> =
+            xpallet_assets_registrar::Pallet::<T>::asset_min_balance(id).saturated_into();
+        let dust = Self::usable_balance(who, id);
+        if dust.is_zero() || dust >= min_balance || dust != Self::all_type_asset_balance(who, id) {
+            return None;
+        }
+
+        let treasury = T::TreasuryAccount::treasury_account().filter(|treasury| treasury != who)?;
+        if let Err(e) = Self::move_usable_balance(id, who, &treasury, dust) {
+            debug!(
+                target: "runtime::assets",
+                "[collect_dust] Failed to collect the dust, who:{:?}, id:{}, dust:{:?}, err:{:?}",
+                who, id, dust, e
+            );
+            return None;
+        }
+
+        Self::deposit_event(Event::<T>::DustCollected(*id, who.clone(), dust));
+        Some(dust)
+    }
+
     pub fn set_balance_impl(
         who: &T::AccountId,
         id: &AssetId,
@@ -546,6 +806,11 @@ impl<T: Config> Pallet<T> {
         let _ = T::OnCreatedAccount::created(who);
     }
 
+    /// Returns true if `who` is derived from a `PalletId`, e.g. the treasury or the swap pool.
+    fn is_pallet_account(who: &T::AccountId) -> bool {
+        who.using_encoded(|encoded| encoded.starts_with(&PalletId::TYPE_ID))
+    }
+
     fn is_dead_account(who: &T::AccountId) -> bool {
         let AccountInfo {
             providers,
@@ -656,6 +921,9 @@ impl<T: Config> Pallet<T> {
         type_: AssetType,
         value: BalanceOf<T>,
     ) -> Result<(), DispatchError> {
+        if type_ == AssetType::Usable && FrozenAccountAssets::<T>::contains_key(who, id) {
+            return Err(Error::<T>::AccountAssetFrozen.into());
+        }
         let current = Self::asset_typed_balance(who, id, type_);
 
         debug!(
@@ -5,10 +5,15 @@ use std::collections::BTreeMap;
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
+    Percent,
 };
 
-use frame_support::{parameter_types, sp_io, traits::GenesisBuild};
+use frame_support::{
+    parameter_types, sp_io,
+    traits::{Contains, GenesisBuild},
+    PalletId,
+};
 
 use chainx_primitives::AssetId;
 pub use xp_protocol::X_BTC;
@@ -91,15 +96,37 @@ impl xpallet_assets_registrar::Config for Test {
     type Event = Event;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type TechnicalOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
+pub struct SimpleTreasuryAccount;
+impl xpallet_support::traits::TreasuryAccount<AccountId> for SimpleTreasuryAccount {
+    fn treasury_account() -> Option<AccountId> {
+        Some(TREASURY)
+    }
+}
+
+parameter_types! {
+    pub const DustReapBounty: Percent = Percent::from_percent(10);
+}
+
+pub struct DustReapExempt;
+impl Contains<AccountId> for DustReapExempt {
+    fn contains(who: &AccountId) -> bool {
+        *who == REWARD_POT
+    }
+}
+
 impl Config for Test {
     type Event = Event;
     type Currency = Balances;
-    type TreasuryAccount = ();
+    type TreasuryAccount = SimpleTreasuryAccount;
     type OnCreatedAccount = frame_system::Provider<Test>;
     type OnAssetChanged = ();
+    type RestrictionOrigin = frame_system::EnsureRoot<AccountId>;
+    type DustReapBounty = DustReapBounty;
+    type DustReapExempt = DustReapExempt;
     type WeightInfo = ();
 }
 
@@ -190,3 +217,9 @@ pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const CHARLIE: AccountId = 3;
 pub const DAVE: AccountId = 4;
+pub const TREASURY: AccountId = 100;
+pub const REWARD_POT: AccountId = 200;
+
+pub(crate) fn pallet_account() -> AccountId {
+    PalletId(*b"pcx/swap").into_account()
+}
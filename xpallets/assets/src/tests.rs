@@ -3,12 +3,17 @@
 use std::collections::BTreeMap;
 
 use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
 use xp_protocol::X_BTC;
 
 pub use super::mock::{ExtBuilder, Test};
 use crate::{
-    mock::{Balance, Origin, XAssets, XAssetsErr},
-    AssetBalance, AssetErr, AssetInfo, AssetRestrictions, AssetType, Chain, TotalAssetBalance,
+    mock::{
+        pallet_account, Balance, Event, Origin, System, XAssets, XAssetsErr, XAssetsRegistrar,
+        REWARD_POT, TREASURY,
+    },
+    AssetBalance, AssetErr, AssetInfo, AssetRestrictions, AssetSupply, AssetType, Chain,
+    FreezeReason, TotalAssetBalance,
 };
 
 #[test]
@@ -170,6 +175,44 @@ fn test_normal_issue_and_destroy() {
     })
 }
 
+#[test]
+fn test_burn() {
+    ExtBuilder::default().build_and_execute(|| {
+        let a: u64 = 1; // accountid
+        let btc_id = X_BTC;
+        let proof = H256::repeat_byte(1);
+
+        let usable = XAssets::usable_balance(&a, &btc_id);
+        let issuance = XAssets::total_issuance(&btc_id);
+
+        assert_noop!(
+            XAssets::burn(Origin::signed(a), a, btc_id, 10, None),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(XAssets::burn(Origin::root(), a, btc_id, 10, Some(proof)));
+        assert_eq!(XAssets::usable_balance(&a, &btc_id), usable - 10);
+        assert_eq!(XAssets::total_issuance(&btc_id), issuance - 10);
+        assert_eq!(XAssets::total_burned(&btc_id), 10);
+        assert_eq!(XAssets::burn_proof_of(proof), Some((btc_id, 10)));
+
+        // The same proof can not be used twice.
+        assert_noop!(
+            XAssets::burn(Origin::root(), a, btc_id, 5, Some(proof)),
+            XAssetsErr::DuplicateBurnProof
+        );
+
+        assert_ok!(XAssets::burn(Origin::root(), a, btc_id, 5, None));
+        assert_eq!(
+            XAssets::asset_supply(&btc_id),
+            AssetSupply {
+                total_issuance: issuance - 15,
+                total_burned: 15,
+            }
+        );
+    })
+}
+
 #[test]
 fn test_unlock_issue_and_destroy2() {
     ExtBuilder::default().build_no_endowed_and_execute(|| {
@@ -496,6 +539,213 @@ fn test_transfer_to_self() {
     })
 }
 
+#[test]
+fn test_set_asset_restrictions() {
+    ExtBuilder::default().build_and_execute(|| {
+        let btc_id = X_BTC;
+        assert_ok!(XAssets::transfer(Origin::signed(1), 2, btc_id, 10));
+
+        assert_noop!(
+            XAssets::set_asset_restrictions(Origin::signed(1), btc_id, AssetRestrictions::TRANSFER),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XAssets::set_asset_restrictions(Origin::root(), 999, AssetRestrictions::TRANSFER),
+            xpallet_assets_registrar::Error::<Test>::AssetDoesNotExist
+        );
+        assert_ok!(XAssets::set_asset_restrictions(
+            Origin::root(),
+            btc_id,
+            AssetRestrictions::TRANSFER | AssetRestrictions::DESTROY_USABLE
+        ));
+        System::assert_last_event(Event::XAssets(crate::Event::AssetRestrictionsSet(
+            btc_id,
+            AssetRestrictions::TRANSFER | AssetRestrictions::DESTROY_USABLE,
+        )));
+        assert_noop!(
+            XAssets::transfer(Origin::signed(1), 2, btc_id, 10),
+            XAssetsErr::ActionNotAllowed
+        );
+
+        // lift the freeze
+        assert_ok!(XAssets::set_asset_restrictions(
+            Origin::root(),
+            btc_id,
+            AssetRestrictions::DESTROY_USABLE
+        ));
+        assert_eq!(
+            XAssets::asset_restrictions_of(btc_id),
+            AssetRestrictions::DESTROY_USABLE
+        );
+        assert_ok!(XAssets::transfer(Origin::signed(1), 2, btc_id, 10));
+    })
+}
+
+#[test]
+fn test_freeze_account_asset() {
+    ExtBuilder::default().build_and_execute(|| {
+        let btc_id = X_BTC;
+        assert_noop!(
+            XAssets::freeze_account_asset(Origin::signed(2), 1, btc_id, FreezeReason::Compromise),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XAssets::freeze_account_asset(
+            Origin::root(),
+            1,
+            btc_id,
+            FreezeReason::Compromise
+        ));
+        System::assert_last_event(Event::XAssets(crate::Event::AccountAssetFrozen(
+            1,
+            btc_id,
+            FreezeReason::Compromise,
+        )));
+        assert_eq!(
+            XAssets::frozen_account_asset(1, btc_id),
+            Some(FreezeReason::Compromise)
+        );
+
+        // the usable balance can't leave the frozen account
+        assert_noop!(
+            XAssets::transfer(Origin::signed(1), 2, btc_id, 10),
+            XAssetsErr::AccountAssetFrozen
+        );
+        assert_noop!(
+            XAssets::move_balance(
+                &btc_id,
+                &1,
+                AssetType::Usable,
+                &1,
+                AssetType::ReservedWithdrawal,
+                10
+            ),
+            AssetErr::Frozen
+        );
+        // but can still be received
+        assert_ok!(XAssets::transfer(Origin::signed(2), 1, btc_id, 10));
+        assert_eq!(XAssets::usable_balance(&1, &btc_id), 110);
+        // nor be destroyed, e.g. swapped through the assets bridge
+        assert_ok!(XAssets::set_asset_limit(
+            Origin::root(),
+            btc_id,
+            AssetRestrictions::empty()
+        ));
+        assert_noop!(
+            XAssets::destroy_usable(&btc_id, &1, 10),
+            XAssetsErr::AccountAssetFrozen
+        );
+
+        assert_ok!(XAssets::thaw_account_asset(Origin::root(), 1, btc_id));
+        System::assert_last_event(Event::XAssets(crate::Event::AccountAssetThawed(1, btc_id)));
+        assert_noop!(
+            XAssets::thaw_account_asset(Origin::root(), 1, btc_id),
+            XAssetsErr::AccountAssetNotFrozen
+        );
+        assert_ok!(XAssets::transfer(Origin::signed(1), 2, btc_id, 10));
+    })
+}
+
+#[test]
+fn test_collect_dust() {
+    ExtBuilder::default().build_and_execute(|| {
+        let btc_id = X_BTC;
+        // no dust handling without the min balance
+        assert_ok!(XAssets::transfer(Origin::signed(1), 2, btc_id, 99));
+        assert_eq!(XAssets::usable_balance(&1, &btc_id), 1);
+        assert_ok!(XAssets::transfer(Origin::signed(2), 1, btc_id, 99));
+
+        assert_ok!(XAssetsRegistrar::set_asset_min_balance(
+            Origin::root(),
+            btc_id,
+            50
+        ));
+        // the balance left above the min balance is kept
+        assert_ok!(XAssets::transfer(Origin::signed(1), 2, btc_id, 50));
+        assert_eq!(XAssets::usable_balance(&1, &btc_id), 50);
+        // the dust left is collected into the treasury
+        assert_ok!(XAssets::transfer(Origin::signed(1), 2, btc_id, 10));
+        System::assert_last_event(Event::XAssets(crate::Event::DustCollected(btc_id, 1, 40)));
+        assert_eq!(XAssets::usable_balance(&1, &btc_id), 0);
+        assert_eq!(XAssets::usable_balance(&TREASURY, &btc_id), 40);
+        assert_eq!(XAssets::usable_balance(&2, &btc_id), 260);
+    })
+}
+
+#[test]
+fn test_reap_dust() {
+    ExtBuilder::default().build_and_execute(|| {
+        let btc_id = X_BTC;
+        assert_noop!(
+            XAssets::reap_dust(Origin::signed(3), 1, btc_id),
+            XAssetsErr::NotDust
+        );
+        assert_ok!(XAssetsRegistrar::set_asset_min_balance(
+            Origin::root(),
+            btc_id,
+            300
+        ));
+        assert_noop!(
+            XAssets::reap_dust(Origin::signed(3), 3, btc_id),
+            XAssetsErr::NotDust
+        );
+        // the balance with the reserved part is not dust
+        assert_ok!(XAssets::move_balance(
+            &btc_id,
+            &2,
+            AssetType::Usable,
+            &2,
+            AssetType::ReservedWithdrawal,
+            10
+        ));
+        assert_noop!(
+            XAssets::reap_dust(Origin::signed(3), 2, btc_id),
+            XAssetsErr::NotDust
+        );
+        // the account that has sent transactions collects its dust by its own transfers
+        System::inc_account_nonce(&1);
+        assert_noop!(
+            XAssets::reap_dust(Origin::signed(3), 1, btc_id),
+            XAssetsErr::ActiveAccount
+        );
+
+        // the bounty is paid out of the dust
+        assert_ok!(XAssetsRegistrar::set_asset_min_balance(
+            Origin::root(),
+            btc_id,
+            500
+        ));
+        assert_ok!(XAssets::reap_dust(Origin::signed(3), 4, btc_id));
+        System::assert_last_event(Event::XAssets(crate::Event::DustReaped(
+            btc_id, 4, 400, 3, 40,
+        )));
+        assert_eq!(XAssets::usable_balance(&4, &btc_id), 0);
+        assert_eq!(XAssets::usable_balance(&TREASURY, &btc_id), 360);
+        assert_eq!(XAssets::usable_balance(&3, &btc_id), 340);
+    })
+}
+
+#[test]
+fn test_reap_dust_of_pallet_and_pot_accounts_should_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        let btc_id = X_BTC;
+        assert_ok!(XAssetsRegistrar::set_asset_min_balance(
+            Origin::root(),
+            btc_id,
+            300
+        ));
+        // neither the pool nor the pot ever sends a transaction
+        for who in [pallet_account(), REWARD_POT] {
+            assert_ok!(XAssets::move_usable_balance(&btc_id, &1, &who, 10));
+            assert_eq!(System::account_nonce(&who), 0);
+            assert_noop!(
+                XAssets::reap_dust(Origin::signed(3), who, btc_id),
+                XAssetsErr::ReapExemptAccount
+            );
+            assert_eq!(XAssets::usable_balance(&who, &btc_id), 10);
+        }
+    })
+}
+
 #[test]
 fn test_move() {
     ExtBuilder::default().build_and_execute(|| {
@@ -92,6 +92,18 @@ pub struct TotalAssetInfo<Balance> {
     pub restrictions: AssetRestrictions,
 }
 
+/// Supply of an asset, the burned balances are no longer part of the total issuance.
+///
+/// The assets backed by the gateways are solvent as long as the reserves on the other chain
+/// cover `total_issuance`, the burned part stays in the reserves without any claim on it.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetSupply<Balance> {
+    pub total_issuance: Balance,
+    pub total_burned: Balance,
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum AssetErr {
@@ -101,6 +113,7 @@ pub enum AssetErr {
     TotalAssetOverFlow,
     InvalidAsset,
     NotAllow,
+    Frozen,
 }
 
 impl<T: Config> From<AssetErr> for Error<T> {
@@ -112,10 +125,23 @@ impl<T: Config> From<AssetErr> for Error<T> {
             AssetErr::TotalAssetOverFlow => Error::<T>::TotalAssetOverflow,
             AssetErr::InvalidAsset => Error::<T>::InvalidAsset,
             AssetErr::NotAllow => Error::<T>::ActionNotAllowed,
+            AssetErr::Frozen => Error::<T>::AccountAssetFrozen,
         }
     }
 }
 
+/// The reason of freezing the asset of an account.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum FreezeReason {
+    /// Required by the regulations, e.g., a sanctioned account.
+    Compliance,
+    /// The account is suspected of being compromised.
+    Compromise,
+    /// Held until the governance decides, e.g., during an incident.
+    GovernanceHold,
+}
+
 /// A single lock on a balance. There can be many of these on an account and
 /// they "overlap", so the same balance is frozen by multiple locks.
 #[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -35,6 +35,10 @@ pub trait WeightInfo {
     fn force_transfer() -> Weight;
     fn set_balance(n: u32) -> Weight;
     fn set_asset_limit() -> Weight;
+    fn freeze_account_asset() -> Weight;
+    fn thaw_account_asset() -> Weight;
+    fn burn() -> Weight;
+    fn reap_dust() -> Weight;
 }
 
 /// Weights for xpallet_assets using the Substrate node and recommended hardware.
@@ -60,6 +64,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn freeze_account_asset() -> Weight {
+        (14_612_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn thaw_account_asset() -> Weight {
+        (12_874_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn burn() -> Weight {
+        (98_614_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn reap_dust() -> Weight {
+        (185_319_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -84,4 +108,24 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn freeze_account_asset() -> Weight {
+        (14_612_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn thaw_account_asset() -> Weight {
+        (12_874_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn burn() -> Weight {
+        (98_614_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn reap_dust() -> Weight {
+        (185_319_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
 }
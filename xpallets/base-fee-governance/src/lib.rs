@@ -0,0 +1,86 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! # Base Fee Governance Module
+//!
+//! Lets the council adjust the EIP-1559 fee market of `pallet_base_fee`, whose calls are
+//! only dispatchable by root.
+//!
+//! The calls are forwarded to `pallet_base_fee`, which emits the events of the changes.
+//! There is no way to emit an EVM log outside of an Ethereum transaction, the EVM tooling
+//! notices the new base fee via the `baseFeePerGas` of the following blocks instead.
+//!
+//! `DefaultBaseFeePerGas` of the runtime only seeds the base fee at genesis, the base fee
+//! in effect is adjusted by `set_base_fee_per_gas`.
+//!
+//! The base fee is not pegged to a fiat gas cost: the EVM gas is charged in BTC through
+//! `XBtcLedger` rather than in PCX, and there is no on-chain BTC/USD price feed to target
+//! such a cost with, so the base fee stays fixed until the council adjusts it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use sp_core::U256;
+use sp_runtime::Permill;
+
+use frame_system::RawOrigin;
+
+pub use self::weights::WeightInfo;
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_base_fee::Config {
+        /// A majority of the council can adjust the fee market.
+        type CouncilOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Set the base fee per gas in effect.
+        #[pallet::weight(<T as Config>::WeightInfo::set_base_fee_per_gas())]
+        pub fn set_base_fee_per_gas(origin: OriginFor<T>, fee: U256) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            pallet_base_fee::Pallet::<T>::set_base_fee_per_gas(RawOrigin::Root.into(), fee)
+        }
+
+        /// Toggle whether the base fee is adjusted by the block fullness at the end of block.
+        #[pallet::weight(<T as Config>::WeightInfo::set_is_active())]
+        pub fn set_is_active(origin: OriginFor<T>, is_active: bool) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            pallet_base_fee::Pallet::<T>::set_is_active(RawOrigin::Root.into(), is_active)
+        }
+
+        /// Set how much the base fee can change between two blocks.
+        #[pallet::weight(<T as Config>::WeightInfo::set_elasticity())]
+        pub fn set_elasticity(origin: OriginFor<T>, elasticity: Permill) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            pallet_base_fee::Pallet::<T>::set_elasticity(RawOrigin::Root.into(), elasticity)
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{assert_noop, assert_ok};
+use sp_core::U256;
+use sp_runtime::{DispatchError, Permill};
+
+use crate::mock::*;
+
+#[test]
+fn set_base_fee_per_gas_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(BaseFee::base_fee_per_gas(), DefaultBaseFeePerGas::get());
+
+        assert_ok!(BaseFeeGovernance::set_base_fee_per_gas(
+            Origin::signed(COUNCIL),
+            U256::from(2_000_000_000u64)
+        ));
+        assert_eq!(BaseFee::base_fee_per_gas(), U256::from(2_000_000_000u64));
+
+        assert_ok!(BaseFeeGovernance::set_base_fee_per_gas(
+            Origin::root(),
+            U256::from(500_000_000u64)
+        ));
+        assert_eq!(BaseFee::base_fee_per_gas(), U256::from(500_000_000u64));
+    });
+}
+
+#[test]
+fn set_is_active_and_elasticity_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(BaseFeeGovernance::set_is_active(
+            Origin::signed(COUNCIL),
+            true
+        ));
+        assert!(BaseFee::is_active());
+
+        assert_ok!(BaseFeeGovernance::set_elasticity(
+            Origin::signed(COUNCIL),
+            Permill::from_percent(25)
+        ));
+        assert_eq!(BaseFee::elasticity(), Permill::from_percent(25));
+    });
+}
+
+#[test]
+fn non_council_origin_should_not_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            BaseFeeGovernance::set_base_fee_per_gas(Origin::signed(ALICE), U256::zero()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            BaseFeeGovernance::set_is_active(Origin::signed(ALICE), true),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            BaseFeeGovernance::set_elasticity(Origin::signed(ALICE), Permill::zero()),
+            DispatchError::BadOrigin
+        );
+    });
+}
@@ -0,0 +1,47 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_base_fee_governance
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_base_fee_governance.
+pub trait WeightInfo {
+    fn set_base_fee_per_gas() -> Weight;
+    fn set_is_active() -> Weight;
+    fn set_elasticity() -> Weight;
+}
+
+/// Weights for xpallet_base_fee_governance using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn set_base_fee_per_gas() -> Weight {
+        (14_560_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_is_active() -> Weight {
+        (13_870_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_elasticity() -> Weight {
+        (13_910_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn set_base_fee_per_gas() -> Weight {
+        (14_560_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_is_active() -> Weight {
+        (13_870_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_elasticity() -> Weight {
+        (13_910_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
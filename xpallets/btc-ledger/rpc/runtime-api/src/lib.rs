@@ -12,5 +12,9 @@ sp_api::decl_runtime_apis! {
     {
         fn get_balance(who: AccountId) -> Balance;
         fn get_total() -> Balance;
+        /// Returns the claimable interest of the account.
+        fn get_interest(who: AccountId) -> Balance;
+        /// Returns the annual percentage yield of the locked balances in parts per million.
+        fn get_interest_apy() -> u32;
     }
 }
@@ -49,6 +49,18 @@ where
     /// Return total incoming balance of BTC
     #[rpc(name = "btcledger_getTotalInComing")]
     fn btcledger_total(&self, at: Option<BlockHash>) -> Result<RpcBalance<Balance>>;
+
+    /// Return the claimable interest of an account
+    #[rpc(name = "btcledger_getInterest")]
+    fn btcledger_interest(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<RpcBalance<Balance>>;
+
+    /// Return the annual percentage yield of the locked balances in parts per million
+    #[rpc(name = "btcledger_getInterestApy")]
+    fn btcledger_interest_apy(&self, at: Option<BlockHash>) -> Result<u32>;
 }
 
 impl<C, Block, AccountId, Balance> BtcLedgerApi<<Block as BlockT>::Hash, AccountId, Balance>
@@ -79,4 +91,23 @@ where
             .map(|b| b.into())
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn btcledger_interest(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<RpcBalance<Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_interest(&at, who)
+            .map(|b| b.into())
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn btcledger_interest_apy(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.get_interest_apy(&at)
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
@@ -0,0 +1,157 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The interest accrued by the locked X-BTC.
+//!
+//! The `InterestPerBlock` is shared by all the locked balances in proportion to their
+//! amounts and paid out of the pallet account, i.e., the treasury in the runtime.
+
+use sp_runtime::{traits::SaturatedConversion, FixedPointNumber};
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// The account holding the balances locked for the interest.
+    pub fn interest_locked_account() -> T::AccountId {
+        T::PalletId::get().into_sub_account(b"locked")
+    }
+
+    /// Returns `InterestPerUnit` brought up to the current block.
+    fn current_interest_per_unit() -> FixedU128 {
+        let now = frame_system::Pallet::<T>::block_number();
+        let last = LastInterestAccrual::<T>::get();
+        let total_locked = TotalLocked::<T>::get();
+        let interest_per_unit = InterestPerUnit::<T>::get();
+        if now <= last || total_locked.is_zero() {
+            return interest_per_unit;
+        }
+
+        let blocks: u128 = (now - last).saturated_into();
+        let interest = InterestPerBlock::<T>::get()
+            .saturated_into::<u128>()
+            .saturating_mul(blocks);
+        interest_per_unit.saturating_add(FixedU128::saturating_from_rational(
+            interest,
+            total_locked.saturated_into::<u128>(),
+        ))
+    }
+
+    /// Accrues the interest of all the locked balances up to the current block.
+    pub(crate) fn accrue_interest() {
+        InterestPerUnit::<T>::put(Self::current_interest_per_unit());
+        LastInterestAccrual::<T>::put(frame_system::Pallet::<T>::block_number());
+    }
+
+    /// Moves the interest accrued by `ledger` since it was last settled to `unclaimed`.
+    fn settle_interest(ledger: &mut InterestLedger<T::Balance>, interest_per_unit: FixedU128) {
+        let accrued = interest_per_unit
+            .saturating_sub(ledger.interest_per_unit)
+            .saturating_mul_int(ledger.locked.saturated_into::<u128>());
+        ledger.unclaimed = ledger.unclaimed.saturating_add(accrued.saturated_into());
+        ledger.interest_per_unit = interest_per_unit;
+    }
+
+    /// Returns the interest of `who` that can be claimed at the current block.
+    pub fn interest_of(who: &T::AccountId) -> T::Balance {
+        let mut ledger = Self::interest_ledger_of(who);
+        Self::settle_interest(&mut ledger, Self::current_interest_per_unit());
+        ledger.unclaimed
+    }
+
+    /// Returns the annual percentage yield of the locked balances in parts per million.
+    pub fn interest_apy() -> u32 {
+        let total_locked = TotalLocked::<T>::get().saturated_into::<u128>();
+        if total_locked.is_zero() {
+            return 0;
+        }
+        let blocks_per_year: u128 = T::BlocksPerYear::get().saturated_into();
+        let annual_interest = InterestPerBlock::<T>::get()
+            .saturated_into::<u128>()
+            .saturating_mul(blocks_per_year);
+        (annual_interest.saturating_mul(1_000_000) / total_locked).saturated_into()
+    }
+
+    pub(crate) fn lock_for_interest(who: &T::AccountId, value: T::Balance) -> DispatchResult {
+        ensure!(!value.is_zero(), Error::<T>::ZeroAmount);
+
+        <Self as Currency<_>>::transfer(
+            who,
+            &Self::interest_locked_account(),
+            value,
+            ExistenceRequirement::AllowDeath,
+        )?;
+
+        Self::accrue_interest();
+        InterestLedgers::<T>::mutate(who, |ledger| {
+            Self::settle_interest(ledger, InterestPerUnit::<T>::get());
+            ledger.locked = ledger.locked.saturating_add(value);
+        });
+        TotalLocked::<T>::mutate(|total| *total = total.saturating_add(value));
+
+        Self::deposit_event(Event::InterestDeposited {
+            who: who.clone(),
+            amount: value,
+        });
+        Ok(())
+    }
+
+    pub(crate) fn unlock_from_interest(who: &T::AccountId, value: T::Balance) -> DispatchResult {
+        ensure!(!value.is_zero(), Error::<T>::ZeroAmount);
+        let mut ledger = Self::interest_ledger_of(who);
+        ensure!(ledger.locked >= value, Error::<T>::InsufficientLocked);
+
+        <Self as Currency<_>>::transfer(
+            &Self::interest_locked_account(),
+            who,
+            value,
+            ExistenceRequirement::AllowDeath,
+        )?;
+
+        Self::accrue_interest();
+        Self::settle_interest(&mut ledger, InterestPerUnit::<T>::get());
+        ledger.locked -= value;
+        Self::put_interest_ledger(who, ledger);
+        TotalLocked::<T>::mutate(|total| *total = total.saturating_sub(value));
+
+        Self::deposit_event(Event::InterestWithdrawn {
+            who: who.clone(),
+            amount: value,
+        });
+        Ok(())
+    }
+
+    pub(crate) fn apply_claim_interest(who: &T::AccountId) -> DispatchResult {
+        Self::accrue_interest();
+        let mut ledger = Self::interest_ledger_of(who);
+        Self::settle_interest(&mut ledger, InterestPerUnit::<T>::get());
+        let interest = ledger.unclaimed;
+        ensure!(!interest.is_zero(), Error::<T>::NoInterest);
+        ensure!(
+            Self::free_balance(Self::account_id()) >= interest,
+            Error::<T>::InsufficientInterestFund
+        );
+
+        <Self as Currency<_>>::transfer(
+            &Self::account_id(),
+            who,
+            interest,
+            ExistenceRequirement::AllowDeath,
+        )?;
+
+        ledger.unclaimed = Zero::zero();
+        Self::put_interest_ledger(who, ledger);
+
+        Self::deposit_event(Event::InterestClaimed {
+            who: who.clone(),
+            amount: interest,
+        });
+        Ok(())
+    }
+
+    fn put_interest_ledger(who: &T::AccountId, ledger: InterestLedger<T::Balance>) {
+        if ledger.locked.is_zero() && ledger.unclaimed.is_zero() {
+            InterestLedgers::<T>::remove(who);
+        } else {
+            InterestLedgers::<T>::insert(who, ledger);
+        }
+    }
+}
@@ -8,6 +8,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::comparison_chain)]
 
+mod interest;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -32,7 +33,7 @@ use sp_runtime::{
         AccountIdConversion, AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub,
         MaybeSerializeDeserialize, Saturating, StaticLookup, Zero,
     },
-    ArithmeticError, DispatchError, RuntimeDebug,
+    ArithmeticError, DispatchError, FixedU128, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, mem, prelude::*};
 
@@ -67,6 +68,10 @@ pub mod pallet {
         /// The btc-ledger's pallet id, used for deriving its sovereign account ID.
         #[pallet::constant]
         type PalletId: Get<PalletId>;
+
+        /// The expected number of blocks per year, used to report the interest APY.
+        #[pallet::constant]
+        type BlocksPerYear: Get<Self::BlockNumber>;
     }
 
     #[pallet::pallet]
@@ -162,6 +167,54 @@ pub mod pallet {
             )?;
             Ok(().into())
         }
+
+        /// Lock some free balance to accrue the interest.
+        #[pallet::weight(60_000_000 + T::DbWeight::get().reads_writes(6, 5))]
+        pub fn deposit(
+            origin: OriginFor<T>,
+            #[pallet::compact] value: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::lock_for_interest(&who, value)?;
+            Ok(().into())
+        }
+
+        /// Unlock some of the balance locked for the interest.
+        ///
+        /// The interest accrued so far stays claimable.
+        #[pallet::weight(60_000_000 + T::DbWeight::get().reads_writes(6, 5))]
+        pub fn withdraw(
+            origin: OriginFor<T>,
+            #[pallet::compact] value: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::unlock_from_interest(&who, value)?;
+            Ok(().into())
+        }
+
+        /// Claim the accrued interest, paid out of the pallet account.
+        #[pallet::weight(50_000_000 + T::DbWeight::get().reads_writes(6, 5))]
+        pub fn claim_interest(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::apply_claim_interest(&who)?;
+            Ok(().into())
+        }
+
+        /// Set the interest shared by all the locked balances per block.
+        #[pallet::weight(T::DbWeight::get().reads_writes(4, 3))]
+        pub fn set_interest_per_block(
+            origin: OriginFor<T>,
+            #[pallet::compact] interest_per_block: T::Balance,
+        ) -> DispatchResultWithPostInfo {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            Self::accrue_interest();
+            InterestPerBlock::<T>::put(interest_per_block);
+            Self::deposit_event(Event::InterestPerBlockSet { interest_per_block });
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -190,6 +243,23 @@ pub mod pallet {
             who: T::AccountId,
             amount: T::Balance,
         },
+        /// Some amount was locked to accrue the interest.
+        InterestDeposited {
+            who: T::AccountId,
+            amount: T::Balance,
+        },
+        /// Some amount locked for the interest was unlocked.
+        InterestWithdrawn {
+            who: T::AccountId,
+            amount: T::Balance,
+        },
+        /// The accrued interest was claimed.
+        InterestClaimed {
+            who: T::AccountId,
+            amount: T::Balance,
+        },
+        /// The interest per block was set by the council.
+        InterestPerBlockSet { interest_per_block: T::Balance },
     }
 
     #[pallet::error]
@@ -198,6 +268,14 @@ pub mod pallet {
         InsufficientBalance,
         /// Beneficiary account must pre-exist
         DeadAccount,
+        /// The amount to lock or unlock is zero
+        ZeroAmount,
+        /// The locked balance is too low to unlock the value
+        InsufficientLocked,
+        /// There is no interest to claim
+        NoInterest,
+        /// The pallet account can not afford the interest
+        InsufficientInterestFund,
     }
 
     /// The total units issued in the system.
@@ -216,6 +294,30 @@ pub mod pallet {
         GetDefault,
     >;
 
+    /// The balances locked for the interest and their accrued interest.
+    #[pallet::storage]
+    #[pallet::getter(fn interest_ledger_of)]
+    pub type InterestLedgers<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, InterestLedger<T::Balance>, ValueQuery>;
+
+    /// The total balance locked for the interest.
+    #[pallet::storage]
+    #[pallet::getter(fn total_locked)]
+    pub type TotalLocked<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+
+    /// The interest shared by all the locked balances per block.
+    #[pallet::storage]
+    #[pallet::getter(fn interest_per_block)]
+    pub type InterestPerBlock<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+
+    /// The accumulated interest per locked unit since the genesis.
+    #[pallet::storage]
+    pub(super) type InterestPerUnit<T: Config> = StorageValue<_, FixedU128, ValueQuery>;
+
+    /// The block number at which `InterestPerUnit` was last updated.
+    #[pallet::storage]
+    pub(super) type LastInterestAccrual<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
     /// Storage version of the pallet.
     ///
     #[pallet::storage]
@@ -224,6 +326,7 @@ pub mod pallet {
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub balances: Vec<(T::AccountId, T::Balance)>,
+        pub interest_per_block: T::Balance,
     }
 
     #[cfg(feature = "std")]
@@ -231,6 +334,7 @@ pub mod pallet {
         fn default() -> Self {
             Self {
                 balances: Default::default(),
+                interest_per_block: Default::default(),
             }
         }
     }
@@ -244,6 +348,7 @@ pub mod pallet {
                 .fold(Zero::zero(), |acc: T::Balance, &(_, n)| acc + n);
 
             <TotalInComing<T>>::put(total);
+            <InterestPerBlock<T>>::put(self.interest_per_block);
             <StorageVersion<T>>::put(Releases::V1_0_0);
 
             // ensure no duplicates exist.
@@ -291,6 +396,17 @@ pub struct AccountData<Balance> {
     pub free: Balance,
 }
 
+/// The balance locked for the interest by an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct InterestLedger<Balance> {
+    /// The balance accruing the interest.
+    pub locked: Balance,
+    /// `InterestPerUnit` at the time the interest was last settled.
+    pub interest_per_unit: FixedU128,
+    /// The interest settled but not claimed yet.
+    pub unclaimed: Balance,
+}
+
 impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
     fn total(&self) -> Balance {
         self.free
@@ -70,6 +70,7 @@ impl crate::Config for Test {
     type Event = Event;
     type CouncilOrigin = EnsureRoot<AccountId>;
     type PalletId = BtcLedgerPalletId;
+    type BlocksPerYear = ConstU64<1_000>;
 }
 
 pub const ALICE: [u8; 32] = [1u8; 32];
@@ -83,6 +84,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
     btc_ledger::GenesisConfig::<Test> {
         balances: vec![(ALICE.into(), 10), (BOB.into(), 20)],
+        interest_per_block: 0,
     }
     .assimilate_storage(&mut t)
     .unwrap();
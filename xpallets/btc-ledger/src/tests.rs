@@ -167,7 +167,12 @@ fn force_transfer_works() {
 fn withdrawing_balance_should_work() {
     new_test_ext().execute_with(|| {
         let _ = BtcLedger::deposit_creating(&BOB.into(), 100);
-        let _ = BtcLedger::withdraw(&BOB.into(), 20, WithdrawReasons::TRANSFER, AllowDeath);
+        let _ = <BtcLedger as Currency<_>>::withdraw(
+            &BOB.into(),
+            20,
+            WithdrawReasons::TRANSFER,
+            AllowDeath,
+        );
 
         System::assert_last_event(Event::BtcLedger(crate::Event::Withdraw {
             who: BOB.into(),
@@ -177,7 +182,12 @@ fn withdrawing_balance_should_work() {
         assert_eq!(BtcLedger::free_balance(AccountId32::from(BOB)), 100);
         assert_eq!(btc_ledger::TotalInComing::<Test>::get(), 110);
 
-        let _ = BtcLedger::withdraw(&ALICE.into(), 10, WithdrawReasons::TRANSFER, KeepAlive);
+        let _ = <BtcLedger as Currency<_>>::withdraw(
+            &ALICE.into(),
+            10,
+            WithdrawReasons::TRANSFER,
+            KeepAlive,
+        );
 
         System::assert_last_event(Event::BtcLedger(crate::Event::Withdraw {
             who: ALICE.into(),
@@ -282,3 +292,65 @@ fn set_balance_handles_total_issuance() {
         assert_eq!(BtcLedger::free_balance(&CHARLIE.into()), 69);
     });
 }
+
+#[test]
+fn interest_should_accrue_per_block() {
+    new_test_ext().execute_with(|| {
+        let alice: AccountId = ALICE.into();
+        let bob: AccountId = BOB.into();
+        assert_ok!(BtcLedger::set_balance(
+            Origin::root(),
+            BtcLedger::account_id(),
+            1000
+        ));
+        assert_noop!(
+            BtcLedger::set_interest_per_block(Origin::signed(alice.clone()), 3),
+            BadOrigin
+        );
+        assert_ok!(BtcLedger::set_interest_per_block(Origin::root(), 3));
+
+        assert_noop!(
+            BtcLedger::deposit(Origin::signed(alice.clone()), 0),
+            btc_ledger::Error::<Test>::ZeroAmount
+        );
+        assert_ok!(BtcLedger::deposit(Origin::signed(alice.clone()), 10));
+        assert_ok!(BtcLedger::deposit(Origin::signed(bob.clone()), 20));
+        assert_eq!(BtcLedger::free_balance(&alice), 0);
+        assert_eq!(
+            BtcLedger::free_balance(BtcLedger::interest_locked_account()),
+            30
+        );
+        assert_eq!(BtcLedger::total_locked(), 30);
+        // 3 * 1000 blocks / 30 locked
+        assert_eq!(BtcLedger::interest_apy(), 100_000_000);
+
+        // The interest of 10 blocks is shared in proportion to the locked balances.
+        System::set_block_number(11);
+        assert_eq!(BtcLedger::interest_of(&alice), 10);
+        assert_eq!(BtcLedger::interest_of(&bob), 20);
+
+        assert_ok!(BtcLedger::claim_interest(Origin::signed(alice.clone())));
+        assert_eq!(BtcLedger::free_balance(&alice), 10);
+        assert_eq!(BtcLedger::free_balance(BtcLedger::account_id()), 990);
+        assert_noop!(
+            BtcLedger::claim_interest(Origin::signed(alice.clone())),
+            btc_ledger::Error::<Test>::NoInterest
+        );
+
+        assert_noop!(
+            BtcLedger::withdraw(Origin::signed(bob.clone()), 21),
+            btc_ledger::Error::<Test>::InsufficientLocked
+        );
+        assert_ok!(BtcLedger::withdraw(Origin::signed(bob.clone()), 20));
+        assert_eq!(BtcLedger::free_balance(&bob), 20);
+        assert_eq!(BtcLedger::total_locked(), 10);
+
+        // Alice takes all the interest once Bob unlocked, Bob keeps the accrued interest.
+        System::set_block_number(21);
+        assert_eq!(BtcLedger::interest_of(&alice), 30);
+        assert_eq!(BtcLedger::interest_of(&bob), 20);
+        assert_ok!(BtcLedger::claim_interest(Origin::signed(bob.clone())));
+        assert_eq!(BtcLedger::free_balance(&bob), 40);
+        assert!(!btc_ledger::InterestLedgers::<Test>::contains_key(&bob));
+    });
+}
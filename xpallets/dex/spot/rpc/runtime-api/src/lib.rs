@@ -10,7 +10,8 @@ use sp_std::prelude::*;
 use codec::Codec;
 
 pub use xpallet_dex_spot::{
-    Depth, FullPairInfo, Handicap, OrderProperty, RpcOrder, TradingPairId, TradingPairInfo,
+    Depth, FullPairInfo, Handicap, OrderBook, OrderBookLevel, OrderProperty, RpcOrder,
+    TradingPairId, TradingPairInfo,
 };
 
 sp_api::decl_runtime_apis! {
@@ -30,5 +31,14 @@ sp_api::decl_runtime_apis! {
 
         /// Get the depth of a trading pair.
         fn depth(pair_id: TradingPairId, depth_size: u32) -> Option<Depth<Price, Balance>>;
+
+        /// Get the order book of a trading pair aggregated by price level.
+        fn order_book(pair_id: TradingPairId, depth: u32) -> Option<OrderBook<Price, Balance>>;
+
+        /// Get all the open orders of an account.
+        fn user_orders(who: AccountId) -> Vec<RpcOrder<TradingPairId, AccountId, Balance, Price, BlockNumber>>;
+
+        /// Get the time-weighted average price of a trading pair over the last `window` blocks.
+        fn twap(pair_id: TradingPairId, window: BlockNumber) -> Option<Price>;
     }
 }
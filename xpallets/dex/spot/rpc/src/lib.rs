@@ -19,8 +19,8 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcPrice};
 
 use xpallet_dex_spot_rpc_runtime_api::{
-    Depth, FullPairInfo, Handicap, OrderProperty, RpcOrder, TradingPairId, TradingPairInfo,
-    XSpotApi as XSpotRuntimeApi,
+    Depth, FullPairInfo, Handicap, OrderBook, OrderBookLevel, OrderProperty, RpcOrder,
+    TradingPairId, TradingPairInfo, XSpotApi as XSpotRuntimeApi,
 };
 
 /// XSpot RPC methods.
@@ -67,6 +67,34 @@ where
         depth_size: u32,
         at: Option<BlockHash>,
     ) -> Result<Option<Depth<RpcPrice<Price>, RpcBalance<Balance>>>>;
+
+    /// Get the order book of a trading pair, at most `depth` price levels on each side.
+    #[rpc(name = "xspot_orderBook")]
+    fn order_book(
+        &self,
+        pair_id: TradingPairId,
+        depth: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Option<OrderBook<RpcPrice<Price>, RpcBalance<Balance>>>>;
+
+    /// Get all the open orders of an account.
+    #[rpc(name = "xspot_userOrders")]
+    fn user_orders(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<
+        Vec<RpcOrder<TradingPairId, AccountId, RpcBalance<Balance>, RpcPrice<Price>, BlockNumber>>,
+    >;
+
+    /// Get the time-weighted average price of a trading pair over the last `window` blocks.
+    #[rpc(name = "xspot_twap")]
+    fn twap(
+        &self,
+        pair_id: TradingPairId,
+        window: BlockNumber,
+        at: Option<BlockHash>,
+    ) -> Result<Option<RpcPrice<Price>>>;
 }
 
 /// A struct that implements the [`XSpotApi`].
@@ -119,6 +147,7 @@ where
                             },
                             max_valid_bid: trading_pairs.max_valid_bid.into(),
                             min_valid_ask: trading_pairs.min_valid_ask.into(),
+                            trading_fee: trading_pairs.trading_fee,
                         },
                     )
                     .collect::<Vec<_>>()
@@ -149,29 +178,7 @@ where
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
         let data = api
             .orders(&at, who, page_index, page_size)
-            .map(|orders| {
-                orders
-                    .into_iter()
-                    .map(|order| RpcOrder {
-                        props: OrderProperty {
-                            id: order.props.id,
-                            side: order.props.side,
-                            price: order.props.price.into(),
-                            amount: order.props.amount.into(),
-                            pair_id: order.props.pair_id,
-                            submitter: order.props.submitter,
-                            order_type: order.props.order_type,
-                            created_at: order.props.created_at,
-                        },
-                        status: order.status,
-                        remaining: order.remaining.into(),
-                        executed_indices: order.executed_indices,
-                        already_filled: order.already_filled.into(),
-                        reserved_balance: order.reserved_balance.into(),
-                        last_update_at: order.last_update_at,
-                    })
-                    .collect::<Vec<_>>()
-            })
+            .map(|orders| orders.into_iter().map(into_rpc_order).collect::<Vec<_>>())
             .map_err(runtime_error_into_rpc_err)?;
         Ok(Page {
             page_index,
@@ -206,6 +213,93 @@ where
             Err(err) => Err(runtime_error_into_rpc_err(err)),
         }
     }
+
+    fn order_book(
+        &self,
+        pair_id: TradingPairId,
+        depth: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<OrderBook<RpcPrice<Price>, RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let into_rpc_levels = |levels: Vec<OrderBookLevel<Price, Balance>>| {
+            levels
+                .into_iter()
+                .map(|level| OrderBookLevel {
+                    price: level.price.into(),
+                    amount: level.amount.into(),
+                    order_count: level.order_count,
+                })
+                .collect::<Vec<_>>()
+        };
+        api.order_book(&at, pair_id, depth)
+            .map(|order_book| {
+                order_book.map(|order_book| OrderBook {
+                    pair_id: order_book.pair_id,
+                    handicap: Handicap {
+                        highest_bid: order_book.handicap.highest_bid.into(),
+                        lowest_ask: order_book.handicap.lowest_ask.into(),
+                    },
+                    asks: into_rpc_levels(order_book.asks),
+                    bids: into_rpc_levels(order_book.bids),
+                })
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn user_orders(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<
+        Vec<RpcOrder<TradingPairId, AccountId, RpcBalance<Balance>, RpcPrice<Price>, BlockNumber>>,
+    > {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.user_orders(&at, who)
+            .map(|orders| orders.into_iter().map(into_rpc_order).collect::<Vec<_>>())
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn twap(
+        &self,
+        pair_id: TradingPairId,
+        window: BlockNumber,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<RpcPrice<Price>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.twap(&at, pair_id, window)
+            .map(|price| price.map(Into::into))
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
+
+fn into_rpc_order<AccountId, Balance, Price, BlockNumber>(
+    order: RpcOrder<TradingPairId, AccountId, Balance, Price, BlockNumber>,
+) -> RpcOrder<TradingPairId, AccountId, RpcBalance<Balance>, RpcPrice<Price>, BlockNumber>
+where
+    Balance: Display + FromStr,
+    Price: Display + FromStr,
+{
+    RpcOrder {
+        props: OrderProperty {
+            id: order.props.id,
+            side: order.props.side,
+            price: order.props.price.into(),
+            amount: order.props.amount.into(),
+            pair_id: order.props.pair_id,
+            submitter: order.props.submitter,
+            order_type: order.props.order_type,
+            created_at: order.props.created_at,
+        },
+        status: order.status,
+        remaining: order.remaining.into(),
+        executed_indices: order.executed_indices,
+        already_filled: order.already_filled.into(),
+        reserved_balance: order.reserved_balance.into(),
+        last_update_at: order.last_update_at,
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
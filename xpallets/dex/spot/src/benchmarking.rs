@@ -3,6 +3,7 @@
 pub use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
 use xp_protocol::X_BTC;
+use xpallet_assets_registrar::{AssetInfo, Chain};
 
 use super::*;
 
@@ -35,6 +36,7 @@ fn b_put_order<T: Config>(
         RawOrigin::Signed(user).into(),
         PAIR_ID,
         OrderType::Limit,
+        OrderFlavor::GoodTillCancel,
         Side::Buy,
         pcx_value.into(),
         price.into(),
@@ -42,6 +44,48 @@ fn b_put_order<T: Config>(
     Ok(())
 }
 
+fn b_register_asset<T: Config>(asset_id: AssetId, token: &[u8]) -> DispatchResult {
+    let asset = AssetInfo::new::<T>(
+        token.to_vec(),
+        token.to_vec(),
+        Chain::Ethereum,
+        8,
+        token.to_vec(),
+    )?;
+    xpallet_assets_registrar::Pallet::<T>::register(
+        RawOrigin::Root.into(),
+        asset_id,
+        asset,
+        true,
+        false,
+    )
+}
+
+/// Adds the EOS/ETH trading pair and returns its id.
+fn b_add_trading_pair<T: Config>() -> Result<TradingPairId, DispatchError> {
+    b_register_asset::<T>(EOS, b"EOS")?;
+    b_register_asset::<T>(ETH, b"ETH")?;
+    let pair = CurrencyPair::new(EOS, ETH);
+    Pallet::<T>::add_trading_pair(RawOrigin::Root.into(), pair, 2, 1, 100u32.into(), true)?;
+    Ok(Pallet::<T>::trading_pair_count() - 1)
+}
+
+/// Puts a sell order of `amount` at `price` by a fresh maker.
+fn b_put_maker_order<T: Config>(index: u32, amount: u32, price: u32) -> DispatchResult {
+    let maker: T::AccountId = account("maker", index, SEED);
+    b_prepare_put_order::<T>(&maker, amount * 2, 100)?;
+    Pallet::<T>::put_order(
+        RawOrigin::Signed(maker).into(),
+        PAIR_ID,
+        OrderType::Limit,
+        OrderFlavor::GoodTillCancel,
+        Side::Sell,
+        amount.into(),
+        price.into(),
+    )?;
+    Ok(())
+}
+
 benchmarks! {
     // TODO: put_order with matching.
     put_order {
@@ -49,11 +93,36 @@ benchmarks! {
 
         b_prepare_put_order::<T>(&user, 1000, 100)?;
 
-    }: put_order(RawOrigin::Signed(user.clone()), PAIR_ID, OrderType::Limit, Side::Buy, 1000u32.into(), 1_000_200u32.into())
+    }: put_order(RawOrigin::Signed(user.clone()), PAIR_ID, OrderType::Limit, OrderFlavor::GoodTillCancel, Side::Buy, 1000u32.into(), 1_000_200u32.into())
     verify {
         assert!(OrderInfoOf::<T>::get(user, 0).is_some());
     }
 
+    put_order_fill_or_kill {
+        let user: T::AccountId = account("taker", 0, SEED);
+
+        b_put_maker_order::<T>(0, 1000, 1_000_300)?;
+        b_prepare_put_order::<T>(&user, 1000, 100)?;
+
+    }: put_order(RawOrigin::Signed(user.clone()), PAIR_ID, OrderType::Limit, OrderFlavor::FillOrKill, Side::Buy, 1000u32.into(), 1_000_300u32.into())
+    verify {
+        assert_eq!(OrderCountOf::<T>::get(&user), 1);
+        assert!(OrderInfoOf::<T>::get(user, 0).is_none());
+    }
+
+    // The order gets partially filled and the rest is canceled.
+    put_order_immediate_or_cancel {
+        let user: T::AccountId = account("taker", 1, SEED);
+
+        b_put_maker_order::<T>(1, 500, 1_000_300)?;
+        b_prepare_put_order::<T>(&user, 1000, 100)?;
+
+    }: put_order(RawOrigin::Signed(user.clone()), PAIR_ID, OrderType::Limit, OrderFlavor::ImmediateOrCancel, Side::Buy, 1000u32.into(), 1_000_300u32.into())
+    verify {
+        assert_eq!(OrderCountOf::<T>::get(&user), 1);
+        assert!(OrderInfoOf::<T>::get(user, 0).is_none());
+    }
+
     cancel_order {
         let user: T::AccountId = account("user", 0, SEED);
 
@@ -89,6 +158,8 @@ benchmarks! {
     }
 
     add_trading_pair {
+        b_register_asset::<T>(EOS, b"EOS")?;
+        b_register_asset::<T>(ETH, b"ETH")?;
         let pair = CurrencyPair::new(EOS, ETH);
     }: _(RawOrigin::Root, pair.clone(), 2, 1, 100u32.into(), true)
     verify {
@@ -105,13 +176,57 @@ benchmarks! {
     }
 
     update_trading_pair {
-        let pair = CurrencyPair::new(EOS, ETH);
-        Pallet::<T>::add_trading_pair(RawOrigin::Root.into(), pair, 2, 1, 100u32.into(), true)?;
+        b_add_trading_pair::<T>()?;
     }: _(RawOrigin::Root, PAIR_ID, 888, false)
     verify {
         assert_eq!(Pallet::<T>::trading_pair_of(PAIR_ID).unwrap().tick_decimals, 888);
         assert!(!Pallet::<T>::trading_pair_of(PAIR_ID).unwrap().tradable);
     }
+
+    update_trading_pair_precision {
+        let pair_id = b_add_trading_pair::<T>()?;
+    }: _(RawOrigin::Root, pair_id, 4, 2)
+    verify {
+        let pair = Pallet::<T>::trading_pair_of(pair_id).unwrap();
+        assert_eq!((pair.pip_decimals, pair.tick_decimals), (4, 2));
+    }
+
+    set_pair_online {
+        let pair_id = b_add_trading_pair::<T>()?;
+        Pallet::<T>::set_pair_online(RawOrigin::Root.into(), pair_id, false)?;
+    }: _(RawOrigin::Root, pair_id, true)
+    verify {
+        assert!(Pallet::<T>::trading_pair_of(pair_id).unwrap().tradable);
+    }
+
+    set_trading_fee {
+        let pair_id = b_add_trading_pair::<T>()?;
+        let fee = TradingFee {
+            maker_bps: 10,
+            taker_bps: 30,
+            destination: FeeDestination::RewardPot,
+        };
+    }: _(RawOrigin::Root, pair_id, fee)
+    verify {
+        assert_eq!(Pallet::<T>::trading_fee_of(pair_id), fee);
+    }
+
+    // All the resting orders are canceled and refunded.
+    migrate_resting_orders {
+        let o in 1 .. MAX_MIGRATED_ORDERS;
+        for i in 0..o {
+            b_put_maker_order::<T>(i, 1000, 1_100_100 + i * 10_000)?;
+        }
+        let pair = Pallet::<T>::trading_pair(PAIR_ID)?;
+        let mut new_pair = pair.clone();
+        new_pair.tick_decimals = 4;
+        TradingPairOf::<T>::insert(PAIR_ID, &new_pair);
+    }: {
+        Pallet::<T>::migrate_resting_orders(&pair, &new_pair)?;
+    }
+    verify {
+        assert_eq!(Pallet::<T>::resting_orders_count(PAIR_ID), 0);
+    }
 }
 
 #[cfg(test)]
@@ -137,12 +252,18 @@ mod tests {
             t_issue_pcx(who, 1000);
 
             assert_ok!(Pallet::<Test>::test_benchmark_put_order());
+            assert_ok!(Pallet::<Test>::test_benchmark_put_order_fill_or_kill());
+            assert_ok!(Pallet::<Test>::test_benchmark_put_order_immediate_or_cancel());
             assert_ok!(Pallet::<Test>::test_benchmark_cancel_order());
             assert_ok!(Pallet::<Test>::test_benchmark_force_cancel_order());
             assert_ok!(Pallet::<Test>::test_benchmark_set_handicap());
             assert_ok!(Pallet::<Test>::test_benchmark_set_price_fluctuation());
             assert_ok!(Pallet::<Test>::test_benchmark_add_trading_pair());
             assert_ok!(Pallet::<Test>::test_benchmark_update_trading_pair());
+            assert_ok!(Pallet::<Test>::test_benchmark_update_trading_pair_precision());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_pair_online());
+            assert_ok!(Pallet::<Test>::test_benchmark_migrate_resting_orders());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_trading_fee());
         });
     }
 }
@@ -7,6 +7,9 @@ use xpallet_assets::AssetType::{self, ReservedDexSpot, Usable};
 
 impl<T: Config> Pallet<T> {
     /// Delivery the assets to maker and taker respectively when executing the order.
+    ///
+    /// Returns the amounts delivered by the maker and the taker, and the (maker, taker) fees
+    /// deducted from the assets they receive.
     pub(super) fn delivery_asset_to_each_other(
         maker_order_side: Side,
         pair: &TradingPairProfile,
@@ -14,13 +17,17 @@ impl<T: Config> Pallet<T> {
         price: T::Price,
         maker_order: &mut OrderInfo<T>,
         taker_order: &mut OrderInfo<T>,
-    ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>, (BalanceOf<T>, BalanceOf<T>)), DispatchError> {
         let maker = &maker_order.submitter();
         let taker = &taker_order.submitter();
 
         let base = pair.base();
         let quote = pair.quote();
 
+        let fee = Self::trading_fee_of(pair.id);
+        let fee_receiver = Self::fee_receiver_of(pair.id, &fee);
+        let fee_receiver = fee_receiver.as_ref();
+
         let turnover_in_quote =
             Self::convert_base_to_quote(turnover, price, pair).unwrap_or_else(|_| Zero::zero());
 
@@ -31,10 +38,28 @@ impl<T: Config> Pallet<T> {
                 let maker_turnover_amount = turnover;
                 let taker_turnover_amount = turnover_in_quote;
 
-                Self::apply_delivery(base, maker_turnover_amount, maker, taker)?;
-                Self::apply_delivery(quote, taker_turnover_amount, taker, maker)?;
-
-                Ok((maker_turnover_amount, taker_turnover_amount))
+                let taker_fee = Self::apply_delivery_with_fee(
+                    base,
+                    maker_turnover_amount,
+                    maker,
+                    taker,
+                    fee.taker_bps,
+                    fee_receiver,
+                )?;
+                let maker_fee = Self::apply_delivery_with_fee(
+                    quote,
+                    taker_turnover_amount,
+                    taker,
+                    maker,
+                    fee.maker_bps,
+                    fee_receiver,
+                )?;
+
+                Ok((
+                    maker_turnover_amount,
+                    taker_turnover_amount,
+                    (maker_fee, taker_fee),
+                ))
             }
             Side::Buy => {
                 // maker(buyer): unreserve the quote currency and move to the taker.
@@ -42,10 +67,28 @@ impl<T: Config> Pallet<T> {
                 let maker_turnover_amount = turnover_in_quote;
                 let taker_turnover_amount = turnover;
 
-                Self::apply_delivery(base, taker_turnover_amount, taker, maker)?;
-                Self::apply_delivery(quote, maker_turnover_amount, maker, taker)?;
-
-                Ok((maker_turnover_amount, taker_turnover_amount))
+                let maker_fee = Self::apply_delivery_with_fee(
+                    base,
+                    taker_turnover_amount,
+                    taker,
+                    maker,
+                    fee.maker_bps,
+                    fee_receiver,
+                )?;
+                let taker_fee = Self::apply_delivery_with_fee(
+                    quote,
+                    maker_turnover_amount,
+                    maker,
+                    taker,
+                    fee.taker_bps,
+                    fee_receiver,
+                )?;
+
+                Ok((
+                    maker_turnover_amount,
+                    taker_turnover_amount,
+                    (maker_fee, taker_fee),
+                ))
             }
         }
     }
@@ -71,6 +114,44 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Move the locked balance in Spot of account `from` to another account's Free, the fee
+    /// of `fee_bps` is deducted and moved to `fee_receiver`, returns the fee.
+    ///
+    /// No fee is charged if there is no fee receiver or the fee receiver can't hold the fee,
+    /// e.g., the native fee is less than the existential deposit of a new account.
+    fn apply_delivery_with_fee(
+        asset_id: AssetId,
+        value: BalanceOf<T>,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        fee_bps: u32,
+        fee_receiver: Option<&T::AccountId>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let fee = TradingFee::fee_of(fee_bps, value);
+        match fee_receiver {
+            Some(receiver) if !fee.is_zero() && Self::can_receive_fee(asset_id, receiver, fee) => {
+                Self::apply_delivery(asset_id, value - fee, from, to)?;
+                Self::apply_delivery(asset_id, fee, from, receiver)?;
+                Ok(fee)
+            }
+            _ => {
+                Self::apply_delivery(asset_id, value, from, to)?;
+                Ok(Zero::zero())
+            }
+        }
+    }
+
+    /// Returns true if `receiver` can hold the fee of `asset_id`.
+    fn can_receive_fee(asset_id: AssetId, receiver: &T::AccountId, fee: BalanceOf<T>) -> bool {
+        if Self::is_native_asset(asset_id) {
+            let currency_total = <T as xpallet_assets::Config>::Currency::total_balance(receiver);
+            currency_total.saturating_add(fee)
+                >= <T as xpallet_assets::Config>::Currency::minimum_balance()
+        } else {
+            true
+        }
+    }
+
     /// Unreserve the locked balances in Spot in general.
     pub(crate) fn generic_unreserve(
         who: &T::AccountId,
@@ -0,0 +1,136 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! This module handles the OCO (one-cancels-other) linked orders.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Inserts the linked orders and then tries to match them one by one.
+    ///
+    /// The shared reserve must have been locked before.
+    pub(crate) fn apply_put_linked_orders(
+        who: T::AccountId,
+        pair: &TradingPairProfile,
+        side: Side,
+        legs: [LinkedOrderLeg<BalanceOf<T>, T::Price>; 2],
+        reserves: [BalanceOf<T>; 2],
+    ) -> Result<(), Error<T>> {
+        info!(
+            target: "runtime::dex::spot",
+            "[apply_put_linked_orders] transactor:{:?}, pair_id:{:}, side:{:?}, legs:{:?}",
+            who, pair.id, side, legs
+        );
+
+        let mut orders = [0, 1].map(|i| {
+            Self::inject_order(
+                who.clone(),
+                pair.id,
+                legs[i].price,
+                OrderType::Limit,
+                side,
+                legs[i].amount,
+                reserves[i],
+            )
+        });
+
+        let link_id = Self::order_link_count();
+        OrderLinkCount::<T>::put(link_id + 1);
+
+        let order_ids = [orders[0].id(), orders[1].id()];
+        for order_id in order_ids.iter() {
+            OrderLinkIdOf::<T>::insert(&who, order_id, link_id);
+        }
+        OrderLinkOf::<T>::insert(
+            link_id,
+            OrderLink {
+                submitter: who.clone(),
+                pair_id: pair.id,
+                side,
+                orders: order_ids,
+                reserves,
+            },
+        );
+
+        Self::deposit_event(Event::<T>::OrdersLinked(
+            link_id,
+            who.clone(),
+            order_ids[0],
+            order_ids[1],
+        ));
+
+        for order in orders.iter_mut() {
+            // The second order has been canceled if the first one was executed immediately.
+            if OrderInfoOf::<T>::contains_key(&who, order.id()) {
+                Self::try_match_order(pair, order, pair.id, side, order.price());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Cancels the counterpart of a linked order and refunds the excess of the shared reserve.
+    ///
+    /// This happens when the linked order gets executed or canceled, does nothing if the order
+    /// is not linked.
+    pub(crate) fn resolve_order_link(who: &T::AccountId, order_id: OrderId) -> DispatchResult {
+        let link_id = match OrderLinkIdOf::<T>::take(who, order_id) {
+            Some(link_id) => link_id,
+            None => return Ok(()),
+        };
+        let link = match OrderLinkOf::<T>::take(link_id) {
+            Some(link) => link,
+            None => return Ok(()),
+        };
+        let index = match link.position(order_id) {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+        let counterpart_id = link.orders[1 - index];
+        OrderLinkIdOf::<T>::remove(who, counterpart_id);
+
+        info!(
+            target: "runtime::dex::spot",
+            "[resolve_order_link] link_id:{}, who:{:?}, order_id:{}, counterpart_id:{}",
+            link_id, who, order_id, counterpart_id
+        );
+
+        let pair = Self::trading_pair(link.pair_id)?;
+
+        // The triggering order keeps its own reserve, the rest is no longer needed.
+        let excess = link.shared_reserve() - link.reserves[index];
+        if !excess.is_zero() {
+            Self::generic_unreserve(who, Self::reserve_asset_of(&pair, link.side), excess)?;
+        }
+
+        Self::cancel_linked_order(who, &pair, counterpart_id);
+
+        Self::deposit_event(Event::<T>::OrderLinkResolved(
+            link_id,
+            who.clone(),
+            order_id,
+            counterpart_id,
+        ));
+
+        Ok(())
+    }
+
+    /// Cancels a linked order without unreserving, its reserve is shared with the counterpart.
+    fn cancel_linked_order(who: &T::AccountId, pair: &TradingPairProfile, order_id: OrderId) {
+        if let Some(mut order) = OrderInfoOf::<T>::get(who, order_id) {
+            order.update_status_on_cancel();
+            order.decrease_remaining_on_cancel(order.remaining);
+            order.last_update_at = <frame_system::Pallet<T>>::block_number();
+
+            Self::deposit_event(Event::<T>::CanceledOrderUpdated(order.clone()));
+
+            Self::kill_order(
+                pair.id,
+                order.price(),
+                who.clone(),
+                order_id,
+                pair.clone(),
+                order.side(),
+            );
+        }
+    }
+}
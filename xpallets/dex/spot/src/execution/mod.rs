@@ -1,7 +1,9 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
 mod asset;
+mod link;
 mod order;
+mod precision;
 mod state;
 
 use frame_support::log::debug;
@@ -96,6 +98,43 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Ensures a limit order of `side` at `price` can be put on the trading pair.
+    pub(crate) fn ensure_valid_limit_order(
+        pair: &TradingPairProfile,
+        side: Side,
+        price: T::Price,
+    ) -> Result<(), Error<T>> {
+        ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
+        ensure!(pair.is_valid_price(price), Error::<T>::InvalidPrice);
+
+        Self::is_valid_quote(price, side, pair.id)?;
+        Self::has_too_many_backlog_orders(pair.id, price, side)?;
+
+        Ok(())
+    }
+
+    /// Returns the asset reserved by the orders of `side`.
+    pub(crate) fn reserve_asset_of(pair: &TradingPairProfile, side: Side) -> AssetId {
+        match side {
+            Side::Buy => pair.quote(),
+            Side::Sell => pair.base(),
+        }
+    }
+
+    /// Returns the asset and the amount that should be reserved for putting an order.
+    pub(crate) fn order_reserve(
+        pair: &TradingPairProfile,
+        side: Side,
+        amount: BalanceOf<T>,
+        price: T::Price,
+    ) -> Result<(AssetId, BalanceOf<T>), Error<T>> {
+        let reserve_amount = match side {
+            Side::Buy => Self::convert_base_to_quote(amount, price, pair)?,
+            Side::Sell => amount,
+        };
+        Ok((Self::reserve_asset_of(pair, side), reserve_amount))
+    }
+
     fn currency_decimals_of(asset_id: AssetId) -> Option<u8> {
         <xpallet_assets_registrar::Pallet<T>>::asset_info_of(asset_id).map(|x| x.decimals())
     }
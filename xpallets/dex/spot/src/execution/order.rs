@@ -3,7 +3,7 @@
 //! This module takes care of the order processing.
 
 use super::*;
-use sp_runtime::traits::CheckedAdd;
+use sp_runtime::traits::{CheckedAdd, Saturating};
 use sp_std::cmp::Ordering;
 
 impl<T: Config> Pallet<T> {
@@ -24,10 +24,7 @@ impl<T: Config> Pallet<T> {
 
         // If the price is too low or too high, we only need to check if the handicap should be updated,
         // otherwise we should match this order.
-        let skip_match_order = match side {
-            Side::Buy => lowest_ask.is_zero() || price < lowest_ask,
-            Side::Sell => highest_bid.is_zero() || price > highest_bid,
-        };
+        let skip_match_order = !Self::would_match(&handicap, side, price);
 
         // If there is no chance to match order, we only have to insert this quote and update handicap.
         if skip_match_order {
@@ -49,6 +46,78 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Returns true if an order of `side` at `price` crosses the handicap.
+    pub(crate) fn would_match(handicap: &HandicapInfo<T>, side: Side, price: T::Price) -> bool {
+        match side {
+            Side::Buy => !handicap.lowest_ask.is_zero() && price >= handicap.lowest_ask,
+            Side::Sell => !handicap.highest_bid.is_zero() && price <= handicap.highest_bid,
+        }
+    }
+
+    /// Returns the unfilled amount of the counterparty orders that an order of `side` at `price`
+    /// can be matched with, measured by the base currency.
+    ///
+    /// The iteration stops once the amount reaches `wanted`.
+    pub(crate) fn available_liquidity(
+        pair: &TradingPairProfile,
+        side: Side,
+        price: T::Price,
+        wanted: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let handicap = <HandicapOf<T>>::get(pair.id);
+        if !Self::would_match(&handicap, side, price) {
+            return Zero::zero();
+        }
+
+        let tick = pair.tick();
+        let (counterparty_side, mut counterparty_price) = match side {
+            Side::Buy => (Side::Sell, handicap.lowest_ask),
+            Side::Sell => (Side::Buy, handicap.highest_bid),
+        };
+        let in_range = |counterparty_price: T::Price| match side {
+            Side::Buy => counterparty_price <= price,
+            Side::Sell => counterparty_price >= price,
+        };
+
+        let mut liquidity: BalanceOf<T> = Zero::zero();
+        while !counterparty_price.is_zero() && in_range(counterparty_price) && liquidity < wanted {
+            liquidity = <QuotationsOf<T>>::get(pair.id, counterparty_price)
+                .iter()
+                .filter_map(|(who, order_id)| <OrderInfoOf<T>>::get(who, order_id))
+                .filter(|order| order.side() == counterparty_side)
+                .fold(liquidity, |acc, order| {
+                    acc.saturating_add(order.remaining_in_base())
+                });
+            counterparty_price = match side {
+                Side::Buy => Self::tick_up(counterparty_price, tick),
+                Side::Sell => Self::tick_down(counterparty_price, tick),
+            };
+        }
+        liquidity
+    }
+
+    /// Matches an order of `FillOrKill` or `ImmediateOrCancel` against the order book,
+    /// the unfilled part is canceled instead of being put on the order book.
+    pub(crate) fn fill_immediately(
+        pair: &TradingPairProfile,
+        order: &mut OrderInfo<T>,
+    ) -> DispatchResult {
+        let handicap = <HandicapOf<T>>::get(pair.id);
+        if Self::would_match(&handicap, order.side(), order.price()) {
+            Self::apply_match_order(order, pair, &handicap);
+        }
+
+        if order.is_fulfilled() {
+            order.status = OrderStatus::Filled;
+        } else {
+            let who = order.submitter();
+            Self::update_order_and_unreserve_on_cancel(order, pair, &who)?;
+        }
+        <OrderInfoOf<T>>::remove(order.submitter(), order.id());
+
+        Ok(())
+    }
+
     /// Insert a fresh order and return the inserted result.
     pub(crate) fn inject_order(
         who: T::AccountId,
@@ -358,14 +427,15 @@ impl<T: Config> Pallet<T> {
         Self::update_order_on_execute(maker_order, &turnover, trading_history_idx);
         Self::update_order_on_execute(taker_order, &turnover, trading_history_idx);
 
-        let (maker_turnover_amount, taker_turnover_amount) = Self::delivery_asset_to_each_other(
-            maker_order.side(),
-            &pair,
-            turnover,
-            price,
-            maker_order,
-            taker_order,
-        )?;
+        let (maker_turnover_amount, taker_turnover_amount, fees) =
+            Self::delivery_asset_to_each_other(
+                maker_order.side(),
+                &pair,
+                turnover,
+                price,
+                maker_order,
+                taker_order,
+            )?;
 
         maker_order.decrease_remaining_on_execute(maker_turnover_amount);
         taker_order.decrease_remaining_on_execute(taker_turnover_amount);
@@ -389,11 +459,16 @@ impl<T: Config> Pallet<T> {
             pair_id,
             price,
             turnover,
+            fees,
             maker_order,
             taker_order,
             <frame_system::Pallet<T>>::block_number(),
         )));
 
+        // The counterparts of the linked orders are canceled once they get executed.
+        Self::resolve_order_link(&maker_order.submitter(), maker_order.id())?;
+        Self::resolve_order_link(&taker_order.submitter(), taker_order.id())?;
+
         Ok(())
     }
 
@@ -0,0 +1,110 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! This module handles the resting orders when the precision of a trading pair changes.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the number of orders resting on the order book of `pair_id`.
+    pub(crate) fn resting_orders_count(pair_id: TradingPairId) -> u32 {
+        QuotationsOf::<T>::iter_prefix_values(pair_id)
+            .map(|quotations| quotations.len() as u32)
+            .sum()
+    }
+
+    /// Converts `price` measured in `from_pip` decimals to the one in `to_pip` decimals.
+    ///
+    /// Returns the converted price rounded down and whether the conversion is lossless.
+    pub(crate) fn rescale_price(price: T::Price, from_pip: u32, to_pip: u32) -> (T::Price, bool) {
+        let price = price.saturated_into::<u128>();
+        if to_pip >= from_pip {
+            let scaled = price.saturating_mul(10_u128.saturating_pow(to_pip - from_pip));
+            (scaled.saturated_into(), T::Price::try_from(scaled).is_ok())
+        } else {
+            let divisor = 10_u128.saturating_pow(from_pip - to_pip);
+            (
+                (price / divisor).saturated_into(),
+                (price % divisor).is_zero(),
+            )
+        }
+    }
+
+    /// Moves the resting orders of `pair` to the precision of `new_pair`.
+    ///
+    /// The orders whose price can be represented in the new precision are re-quantized in
+    /// place with their time priority preserved, the others are canceled and refunded.
+    /// `new_pair` must have been stored before.
+    pub(crate) fn migrate_resting_orders(
+        pair: &TradingPairProfile,
+        new_pair: &TradingPairProfile,
+    ) -> DispatchResult {
+        info!(
+            target: "runtime::dex::spot",
+            "[migrate_resting_orders] pair_id:{}, pip_decimals:{}->{}, tick_decimals:{}->{}",
+            pair.id,
+            pair.pip_decimals,
+            new_pair.pip_decimals,
+            pair.tick_decimals,
+            new_pair.tick_decimals
+        );
+
+        let handicap = HandicapOf::<T>::get(pair.id);
+        let quotations = QuotationsOf::<T>::drain_prefix(pair.id).collect::<Vec<_>>();
+        let current_block = <frame_system::Pallet<T>>::block_number();
+
+        for (price, orders) in quotations {
+            let (new_price, exact) =
+                Self::rescale_price(price, pair.pip_decimals, new_pair.pip_decimals);
+            let compatible = exact && !new_price.is_zero() && new_pair.is_valid_price(new_price);
+
+            for (who, order_id) in orders {
+                // The order may have been canceled along with its linked counterpart.
+                let mut order = match OrderInfoOf::<T>::get(&who, order_id) {
+                    Some(order) => order,
+                    None => continue,
+                };
+
+                if compatible {
+                    order.props.price = new_price;
+                    order.last_update_at = current_block;
+                    OrderInfoOf::<T>::insert(&who, order_id, &order);
+                    QuotationsOf::<T>::mutate(pair.id, new_price, |quotations| {
+                        quotations.push((who.clone(), order_id))
+                    });
+                    Self::deposit_event(Event::<T>::OrderRequantized(
+                        who, order_id, price, new_price,
+                    ));
+                } else {
+                    Self::update_order_and_unreserve_on_cancel(&mut order, new_pair, &who)?;
+                    OrderInfoOf::<T>::remove(&who, order_id);
+                    Self::resolve_order_link(&who, order_id)?;
+                }
+            }
+        }
+
+        // The handicap stays on the same side of the re-quantized orders.
+        let tick = new_pair.tick().saturated_into::<u128>();
+        let (highest_bid, _) = Self::rescale_price(
+            handicap.highest_bid,
+            pair.pip_decimals,
+            new_pair.pip_decimals,
+        );
+        let highest_bid = highest_bid.saturated_into::<u128>() / tick * tick;
+        let (lowest_ask, exact) = Self::rescale_price(
+            handicap.lowest_ask,
+            pair.pip_decimals,
+            new_pair.pip_decimals,
+        );
+        let mut lowest_ask = lowest_ask.saturated_into::<u128>();
+        if !exact {
+            lowest_ask = lowest_ask.saturating_add(1);
+        }
+        let lowest_ask = lowest_ask.saturating_add(tick - 1) / tick * tick;
+        HandicapOf::<T>::insert(
+            pair.id,
+            HandicapInfo::<T>::new(highest_bid.saturated_into(), lowest_ask.saturated_into()),
+        );
+
+        Ok(())
+    }
+}
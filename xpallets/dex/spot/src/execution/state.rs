@@ -190,5 +190,27 @@ impl<T: Config> Pallet<T> {
                 last_updated: current_block,
             },
         );
+
+        Self::record_price_observation(pair_index, current_block, latest);
+    }
+
+    /// Records the latest price of the block for the TWAP, the oldest one is dropped once
+    /// there are `MAX_PRICE_OBSERVATIONS` records.
+    fn record_price_observation(
+        pair_index: TradingPairId,
+        block_number: T::BlockNumber,
+        latest: T::Price,
+    ) {
+        <PriceObservationsOf<T>>::mutate(pair_index, |observations| {
+            match observations.last_mut() {
+                Some((block, price)) if *block == block_number => *price = latest,
+                _ => {
+                    if observations.len() >= MAX_PRICE_OBSERVATIONS {
+                        observations.remove(0);
+                    }
+                    observations.push((block_number, latest));
+                }
+            }
+        });
     }
 }
@@ -20,8 +20,8 @@ mod tests;
 use codec::Codec;
 
 use sp_runtime::traits::{
-    AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, SaturatedConversion, StaticLookup,
-    Zero,
+    AccountIdConversion, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member,
+    SaturatedConversion, StaticLookup, Zero,
 };
 use sp_std::prelude::*;
 use sp_std::{cmp, fmt::Debug};
@@ -30,13 +30,16 @@ use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
     log::info,
-    traits::{Currency, Get, ReservableCurrency},
-    Parameter,
+    traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+    transactional,
+    weights::Weight,
+    PalletId, Parameter,
 };
 use frame_system::{ensure_root, ensure_signed};
 
 use chainx_primitives::AssetId;
 use xpallet_assets::AssetErr;
+use xpallet_support::traits::TreasuryAccount;
 
 pub use self::rpc::*;
 pub use self::types::*;
@@ -57,6 +60,15 @@ const MAX_BACKLOG_ORDER: usize = 1000;
 /// more time than the Block time to finish.
 const DEFAULT_FLUCTUATION: u32 = 100;
 
+/// Maximum of resting orders migrated when the precision of a trading pair changes.
+const MAX_MIGRATED_ORDERS: u32 = 300;
+
+/// Maximum of the prices kept for the TWAP of a trading pair, at most one per block.
+const MAX_PRICE_OBSERVATIONS: usize = 600;
+
+/// Maximum of the maker and taker fees in basis points, i.e., 10%.
+pub const MAX_TRADING_FEE_BPS: u32 = 1_000;
+
 pub type BalanceOf<T> = <<T as xpallet_assets::Config>::Currency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::Balance;
@@ -71,6 +83,8 @@ pub type OrderInfo<T> = Order<
 
 pub type HandicapInfo<T> = Handicap<<T as Config>::Price>;
 
+pub type OrderLinkInfo<T> = OrderLink<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -94,6 +108,13 @@ pub mod pallet {
             + MaybeSerializeDeserialize
             + Debug;
 
+        /// A majority of the council can list and manage the trading pairs.
+        type CouncilOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The id used for deriving the fee reward pot account of each trading pair.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -104,11 +125,14 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        #[pallet::weight(<T as Config>::WeightInfo::put_order())]
+        /// Put a limit order, `flavor` decides what happens to the part that can not be
+        /// filled right away.
+        #[pallet::weight(Pallet::<T>::put_order_weight(*flavor))]
         pub fn put_order(
             origin: OriginFor<T>,
             #[pallet::compact] pair_id: TradingPairId,
             order_type: OrderType,
+            flavor: OrderFlavor,
             side: Side,
             #[pallet::compact] amount: BalanceOf<T>,
             #[pallet::compact] price: T::Price,
@@ -121,25 +145,28 @@ pub mod pallet {
 
             let pair = Self::trading_pair(pair_id)?;
 
-            ensure!(pair.tradable, Error::<T>::TradingPairUntradable);
-            ensure!(pair.is_valid_price(price), Error::<T>::InvalidPrice);
+            Self::ensure_valid_limit_order(&pair, side, price)?;
 
-            Self::is_valid_quote(price, side, pair_id)?;
-            Self::has_too_many_backlog_orders(pair_id, price, side)?;
+            match flavor {
+                OrderFlavor::PostOnly => ensure!(
+                    !Self::would_match(&Self::handicap_of(pair_id), side, price),
+                    Error::<T>::PostOnlyWouldMatch
+                ),
+                OrderFlavor::FillOrKill => ensure!(
+                    Self::available_liquidity(&pair, side, price, amount) >= amount,
+                    Error::<T>::FillOrKillUnfilled
+                ),
+                OrderFlavor::GoodTillCancel | OrderFlavor::ImmediateOrCancel => {}
+            }
 
             // Reserve the token according to the order side.
-            let (reserve_asset, reserve_amount) = match side {
-                Side::Buy => (
-                    pair.quote(),
-                    Self::convert_base_to_quote(amount, price, &pair)?,
-                ),
-                Side::Sell => (pair.base(), amount),
-            };
+            let (reserve_asset, reserve_amount) = Self::order_reserve(&pair, side, amount, price)?;
             Self::put_order_reserve(&who, reserve_asset, reserve_amount)?;
             Self::apply_put_order(
                 who,
                 pair_id,
                 order_type,
+                flavor,
                 side,
                 amount,
                 price,
@@ -148,6 +175,37 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Put two linked limit orders of the same side, e.g., a take-profit and a stop-loss.
+        ///
+        /// Only the larger one of the reserves of two orders is locked. Once either order
+        /// gets executed or canceled, the other one will be canceled automatically.
+        #[pallet::weight(<T as Config>::WeightInfo::put_order().saturating_mul(2))]
+        pub fn put_linked_orders(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            side: Side,
+            first: LinkedOrderLeg<BalanceOf<T>, T::Price>,
+            second: LinkedOrderLeg<BalanceOf<T>, T::Price>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let pair = Self::trading_pair(pair_id)?;
+
+            let mut reserves = [Zero::zero(); 2];
+            for (leg, reserve) in [&first, &second].into_iter().zip(reserves.iter_mut()) {
+                ensure!(!leg.price.is_zero(), Error::<T>::InvalidPrice);
+                ensure!(!leg.amount.is_zero(), Error::<T>::ZeroAmount);
+                Self::ensure_valid_limit_order(&pair, side, leg.price)?;
+                *reserve = Self::order_reserve(&pair, side, leg.amount, leg.price)?.1;
+            }
+
+            // The linked orders share the reserve since at most one of them can be executed.
+            let reserve_asset = Self::reserve_asset_of(&pair, side);
+            Self::put_order_reserve(&who, reserve_asset, cmp::max(reserves[0], reserves[1]))?;
+            Self::apply_put_linked_orders(who, &pair, side, [first, second], reserves)?;
+            Ok(())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
         pub fn cancel_order(
             origin: OriginFor<T>,
@@ -197,7 +255,7 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Add a new trading pair.
+        /// Add a new trading pair, the base and quote assets must be online in the registrar.
         #[pallet::weight(<T as Config>::WeightInfo::add_trading_pair())]
         pub fn add_trading_pair(
             origin: OriginFor<T>,
@@ -207,7 +265,13 @@ pub mod pallet {
             #[pallet::compact] latest_price: T::Price,
             tradable: bool,
         ) -> DispatchResult {
-            ensure_root(origin)?;
+            Self::ensure_council_or_root(origin)?;
+            ensure!(
+                currency_pair.base != currency_pair.quote,
+                Error::<T>::InvalidCurrencyPair
+            );
+            Self::ensure_valid_pair_assets(&currency_pair)?;
+            ensure!(tick_decimals <= pip_decimals, Error::<T>::InvalidPrecision);
             ensure!(
                 Self::get_trading_pair_by_currency_pair(&currency_pair).is_none(),
                 Error::<T>::TradingPairAlreadyExists
@@ -223,20 +287,125 @@ pub mod pallet {
         }
 
         /// Update the trading pair profile.
-        #[pallet::weight(<T as Config>::WeightInfo::update_trading_pair())]
+        ///
+        /// The resting orders whose price is not a multiple of the new tick are canceled.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::update_trading_pair()
+                .saturating_add(<T as Config>::WeightInfo::migrate_resting_orders(MAX_MIGRATED_ORDERS))
+        )]
+        #[transactional]
         pub fn update_trading_pair(
             origin: OriginFor<T>,
             #[pallet::compact] pair_id: TradingPairId,
             #[pallet::compact] tick_decimals: u32,
             tradable: bool,
-        ) -> DispatchResult {
-            ensure_root(origin)?;
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_council_or_root(origin)?;
             let pair = Self::trading_pair(pair_id)?;
             ensure!(
                 tick_decimals >= pair.tick_decimals,
                 Error::<T>::InvalidTickdecimals
             );
+
+            let orders = if tick_decimals == pair.tick_decimals {
+                0
+            } else {
+                Self::ensure_migratable(&pair)?
+            };
+            if orders > 0 {
+                ensure!(
+                    tick_decimals <= pair.pip_decimals,
+                    Error::<T>::InvalidPrecision
+                );
+            }
+
             Self::apply_update_trading_pair(pair_id, tick_decimals, tradable);
+            if orders > 0 {
+                Self::migrate_resting_orders(&pair, &Self::trading_pair(pair_id)?)?;
+            }
+            Ok(Some(
+                <T as Config>::WeightInfo::update_trading_pair()
+                    .saturating_add(<T as Config>::WeightInfo::migrate_resting_orders(orders)),
+            )
+            .into())
+        }
+
+        /// Update the pip decimals and tick decimals of a trading pair.
+        ///
+        /// The latest price, the handicap and the resting orders are rescaled to the new pip
+        /// decimals, the orders whose price can not be represented by the new precision are
+        /// canceled and refunded.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::update_trading_pair_precision()
+                .saturating_add(<T as Config>::WeightInfo::migrate_resting_orders(MAX_MIGRATED_ORDERS))
+        )]
+        #[transactional]
+        pub fn update_trading_pair_precision(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            #[pallet::compact] pip_decimals: u32,
+            #[pallet::compact] tick_decimals: u32,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_council_or_root(origin)?;
+            let pair = Self::trading_pair(pair_id)?;
+            ensure!(tick_decimals <= pip_decimals, Error::<T>::InvalidPrecision);
+            let orders = Self::ensure_migratable(&pair)?;
+
+            let new_pair = Self::apply_update_trading_pair_precision(
+                pair.clone(),
+                pip_decimals,
+                tick_decimals,
+            );
+            if orders > 0 {
+                Self::migrate_resting_orders(&pair, &new_pair)?;
+            }
+            Ok(Some(
+                <T as Config>::WeightInfo::update_trading_pair_precision()
+                    .saturating_add(<T as Config>::WeightInfo::migrate_resting_orders(orders)),
+            )
+            .into())
+        }
+
+        /// Open or suspend the trading of a trading pair.
+        ///
+        /// A pair can only be online when both of its assets are online in the registrar.
+        #[pallet::weight(<T as Config>::WeightInfo::set_pair_online())]
+        pub fn set_pair_online(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            online: bool,
+        ) -> DispatchResult {
+            Self::ensure_council_or_root(origin)?;
+            let mut pair = Self::trading_pair(pair_id)?;
+            if online {
+                Self::ensure_valid_pair_assets(&pair.currency_pair)?;
+            }
+
+            pair.tradable = online;
+            TradingPairOf::<T>::insert(pair_id, &pair);
+            Self::deposit_event(Event::<T>::TradingPairOnlineSet(pair_id, online));
+            Ok(())
+        }
+
+        /// Set the maker and taker fees of a trading pair and where the fees go.
+        ///
+        /// The fees are charged on the assets received by the maker and the taker of each
+        /// execution, zero fees make the pair feeless.
+        #[pallet::weight(<T as Config>::WeightInfo::set_trading_fee())]
+        pub fn set_trading_fee(
+            origin: OriginFor<T>,
+            #[pallet::compact] pair_id: TradingPairId,
+            fee: TradingFee,
+        ) -> DispatchResult {
+            Self::ensure_council_or_root(origin)?;
+            Self::trading_pair(pair_id)?;
+            ensure!(
+                fee.maker_bps <= MAX_TRADING_FEE_BPS && fee.taker_bps <= MAX_TRADING_FEE_BPS,
+                Error::<T>::InvalidTradingFee
+            );
+
+            TradingFeeOf::<T>::insert(pair_id, fee);
+            Self::deposit_event(Event::<T>::TradingFeeSet(pair_id, fee));
             Ok(())
         }
     }
@@ -266,6 +435,20 @@ pub mod pallet {
         TradingPairUpdated(TradingPairProfile),
         /// Price fluctuation of trading pair has been updated. [pair_id, price_fluctuation]
         PriceFluctuationUpdated(TradingPairId, PriceFluctuation),
+        /// Two orders have been linked, one cancels the other. [link_id, who, first_order_id, second_order_id]
+        OrdersLinked(OrderLinkId, T::AccountId, OrderId, OrderId),
+        /// One of the linked orders was executed or canceled, the other one has been canceled.
+        /// [link_id, who, triggering_order_id, canceled_order_id]
+        OrderLinkResolved(OrderLinkId, T::AccountId, OrderId, OrderId),
+        /// The precision of a trading pair has been updated. [pair_id, pip_decimals, tick_decimals]
+        TradingPairPrecisionUpdated(TradingPairId, u32, u32),
+        /// A trading pair has been set online or offline. [pair_id, online]
+        TradingPairOnlineSet(TradingPairId, bool),
+        /// A resting order has been moved to the new precision of its trading pair.
+        /// [who, order_id, old_price, new_price]
+        OrderRequantized(T::AccountId, OrderId, T::Price, T::Price),
+        /// The trading fee of a trading pair has been set. [pair_id, trading_fee]
+        TradingFeeSet(TradingPairId, TradingFee),
     }
 
     /// Error for the spot module.
@@ -307,6 +490,18 @@ pub mod pallet {
         InvalidOrderId,
         /// Error from assets module.
         AssetError,
+        /// The post-only order would be matched right away.
+        PostOnlyWouldMatch,
+        /// The fill-or-kill order can not be filled completely right away.
+        FillOrKillUnfilled,
+        /// The base and quote currency of a trading pair must be different.
+        InvalidCurrencyPair,
+        /// tick_decimals can not exceed pip_decimals.
+        InvalidPrecision,
+        /// Too many resting orders to migrate to the new precision at once.
+        TooManyOrdersToMigrate,
+        /// The maker or taker fee exceeds `MAX_TRADING_FEE_BPS`.
+        InvalidTradingFee,
     }
 
     /// How many trading pairs so far.
@@ -350,6 +545,23 @@ pub mod pallet {
     pub(crate) type OrderInfoOf<T: Config> =
         StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, OrderId, OrderInfo<T>>;
 
+    /// How many order links so far.
+    #[pallet::storage]
+    #[pallet::getter(fn order_link_count)]
+    pub(crate) type OrderLinkCount<T: Config> = StorageValue<_, OrderLinkId, ValueQuery>;
+
+    /// Details of the linked orders given the link ID.
+    #[pallet::storage]
+    #[pallet::getter(fn order_link_of)]
+    pub(crate) type OrderLinkOf<T: Config> =
+        StorageMap<_, Twox64Concat, OrderLinkId, OrderLinkInfo<T>>;
+
+    /// The link ID of a linked order given the account ID and order ID.
+    #[pallet::storage]
+    #[pallet::getter(fn order_link_id_of)]
+    pub(crate) type OrderLinkIdOf<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, OrderId, OrderLinkId>;
+
     /// All the accounts and the order number given the trading pair ID and price.
     #[pallet::storage]
     #[pallet::getter(fn quotations_of)]
@@ -369,6 +581,19 @@ pub mod pallet {
     pub(crate) type HandicapOf<T: Config> =
         StorageMap<_, Twox64Concat, TradingPairId, HandicapInfo<T>, ValueQuery>;
 
+    /// The maker and taker fees of each trading pair, the pairs are feeless by default.
+    #[pallet::storage]
+    #[pallet::getter(fn trading_fee_of)]
+    pub(crate) type TradingFeeOf<T: Config> =
+        StorageMap<_, Twox64Concat, TradingPairId, TradingFee, ValueQuery>;
+
+    /// The last executed price of each block in which a trading pair was traded, in the
+    /// ascending order of block, at most `MAX_PRICE_OBSERVATIONS` are kept.
+    #[pallet::storage]
+    #[pallet::getter(fn price_observations_of)]
+    pub(crate) type PriceObservationsOf<T: Config> =
+        StorageMap<_, Twox64Concat, TradingPairId, Vec<(T::BlockNumber, T::Price)>, ValueQuery>;
+
     #[pallet::type_value]
     pub fn DefaultForPriceFluctuationOf() -> PriceFluctuation {
         DEFAULT_FLUCTUATION
@@ -443,11 +668,46 @@ impl<T: Config> Pallet<T> {
         None
     }
 
+    fn ensure_council_or_root(origin: T::Origin) -> DispatchResult {
+        T::CouncilOrigin::try_origin(origin)
+            .map(|_| ())
+            .or_else(ensure_root)?;
+        Ok(())
+    }
+
+    /// Ensures both assets of `currency_pair` are online in the assets registrar.
+    fn ensure_valid_pair_assets(currency_pair: &CurrencyPair) -> Result<(), Error<T>> {
+        ensure!(
+            xpallet_assets_registrar::Pallet::<T>::is_valid(&currency_pair.base)
+                && xpallet_assets_registrar::Pallet::<T>::is_valid(&currency_pair.quote),
+            Error::<T>::InvalidTradingPairAsset
+        );
+        Ok(())
+    }
+
     #[inline]
     fn trading_pair(pair_id: TradingPairId) -> Result<TradingPairProfile, Error<T>> {
         TradingPairOf::<T>::get(pair_id).ok_or(Error::<T>::InvalidTradingPair)
     }
 
+    /// Returns the account of the fee reward pot of a trading pair.
+    pub fn reward_pot_of(pair_id: TradingPairId) -> T::AccountId {
+        T::PalletId::get().into_sub_account(pair_id)
+    }
+
+    /// Returns the account receiving the trading fees of a trading pair, if any.
+    pub(crate) fn fee_receiver_of(
+        pair_id: TradingPairId,
+        fee: &TradingFee,
+    ) -> Option<T::AccountId> {
+        match fee.destination {
+            FeeDestination::Treasury => {
+                <T as xpallet_assets::Config>::TreasuryAccount::treasury_account()
+            }
+            FeeDestination::RewardPot => Some(Self::reward_pot_of(pair_id)),
+        }
+    }
+
     fn get_order(who: &T::AccountId, order_id: OrderId) -> Result<OrderInfo<T>, Error<T>> {
         Self::order_info_of(who, order_id).ok_or(Error::<T>::InvalidOrderId)
     }
@@ -501,19 +761,79 @@ impl<T: Config> Pallet<T> {
         });
     }
 
+    fn apply_update_trading_pair_precision(
+        mut pair: TradingPairProfile,
+        pip_decimals: u32,
+        tick_decimals: u32,
+    ) -> TradingPairProfile {
+        info!(
+            target: "runtime::dex::spot",
+            "[update_trading_pair_precision] pair_id: {:}, pip_decimals: {:}, tick_decimals: {:}",
+            pair.id, pip_decimals, tick_decimals
+        );
+
+        TradingPairInfoOf::<T>::mutate(pair.id, |info| {
+            if let Some(info) = info {
+                let (latest_price, _) =
+                    Self::rescale_price(info.latest_price, pair.pip_decimals, pip_decimals);
+                info.latest_price = latest_price;
+            }
+        });
+        if Self::resting_orders_count(pair.id) == 0 {
+            HandicapOf::<T>::remove(pair.id);
+        }
+
+        pair.pip_decimals = pip_decimals;
+        pair.tick_decimals = tick_decimals;
+        TradingPairOf::<T>::insert(pair.id, &pair);
+
+        Self::deposit_event(Event::<T>::TradingPairPrecisionUpdated(
+            pair.id,
+            pip_decimals,
+            tick_decimals,
+        ));
+        pair
+    }
+
+    /// Returns the number of resting orders of `pair` to migrate, which is bounded by
+    /// `MAX_MIGRATED_ORDERS`.
+    fn ensure_migratable(pair: &TradingPairProfile) -> Result<u32, DispatchError> {
+        let orders = Self::resting_orders_count(pair.id);
+        ensure!(
+            orders <= MAX_MIGRATED_ORDERS,
+            Error::<T>::TooManyOrdersToMigrate
+        );
+        Ok(orders)
+    }
+
+    /// Returns the weight of `put_order` given the order flavor.
+    fn put_order_weight(flavor: OrderFlavor) -> Weight {
+        match flavor {
+            OrderFlavor::GoodTillCancel | OrderFlavor::PostOnly => {
+                <T as Config>::WeightInfo::put_order()
+            }
+            OrderFlavor::FillOrKill => <T as Config>::WeightInfo::put_order_fill_or_kill(),
+            OrderFlavor::ImmediateOrCancel => {
+                <T as Config>::WeightInfo::put_order_immediate_or_cancel()
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn apply_put_order(
         who: T::AccountId,
         pair_id: TradingPairId,
         order_type: OrderType,
+        flavor: OrderFlavor,
         side: Side,
         amount: BalanceOf<T>,
         price: T::Price,
         reserve_amount: BalanceOf<T>,
-    ) -> Result<(), Error<T>> {
+    ) -> DispatchResult {
         info!(
             target: "runtime::dex::spot",
-            "transactor:{:?}, pair_id:{:}, type:{:?}, side:{:?}, amount:{:?}, price:{:?}",
-            who, pair_id, order_type, side, amount, price
+            "transactor:{:?}, pair_id:{:}, type:{:?}, flavor:{:?}, side:{:?}, amount:{:?}, price:{:?}",
+            who, pair_id, order_type, flavor, side, amount, price
         );
 
         let pair = Self::trading_pair(pair_id)?;
@@ -528,7 +848,11 @@ impl<T: Config> Pallet<T> {
             reserve_amount,
         );
 
-        Self::try_match_order(&pair, &mut order, pair_id, side, price);
+        if flavor.is_immediate() {
+            Self::fill_immediately(&pair, &mut order)?;
+        } else {
+            Self::try_match_order(&pair, &mut order, pair_id, side, price);
+        }
 
         Ok(())
     }
@@ -548,6 +872,7 @@ impl<T: Config> Pallet<T> {
         );
 
         Self::apply_cancel_order(who, pair_id, order_id)?;
+        Self::resolve_order_link(who, order_id)?;
 
         Ok(())
     }
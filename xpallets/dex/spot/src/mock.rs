@@ -103,9 +103,15 @@ impl pallet_balances::Config for Test {
     type MaxReserves = MaxReserves;
 }
 
+parameter_types! {
+    pub const SpotPalletId: PalletId = PalletId(*b"pcx/spot");
+}
+
 impl Config for Test {
     type Event = ();
     type Price = Price;
+    type CouncilOrigin = frame_system::EnsureRoot<AccountId>;
+    type PalletId = SpotPalletId;
     type WeightInfo = ();
 }
 
@@ -117,18 +123,31 @@ impl xpallet_assets_registrar::Config for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = XSpot;
+    type TechnicalOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
 impl xpallet_assets::Config for Test {
     type Event = ();
     type Currency = Balances;
-    type TreasuryAccount = ();
+    type TreasuryAccount = SimpleTreasuryAccount;
     type OnCreatedAccount = frame_system::Provider<Test>;
     type OnAssetChanged = ();
+    type RestrictionOrigin = frame_system::EnsureRoot<AccountId>;
+    type DustReapBounty = ();
+    type DustReapExempt = frame_support::traits::Nothing;
     type WeightInfo = ();
 }
 
+pub(crate) const TREASURY: AccountId = 100;
+
+pub struct SimpleTreasuryAccount;
+impl xpallet_support::traits::TreasuryAccount<AccountId> for SimpleTreasuryAccount {
+    fn treasury_account() -> Option<AccountId> {
+        Some(TREASURY)
+    }
+}
+
 thread_local! {
     static SESSION: RefCell<(Vec<AccountId>, HashSet<AccountId>)> = RefCell::new(Default::default());
     static EXISTENTIAL_DEPOSIT: RefCell<Balance> = RefCell::new(0);
@@ -5,6 +5,7 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::collections::btree_map::BTreeMap;
 
 #[derive(PartialEq, Eq, Clone, Default, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -20,6 +21,8 @@ pub struct FullPairInfo<Price, BlockNumber> {
     pub max_valid_bid: Price,
     /// The minimum valid ask price.
     pub min_valid_ask: Price,
+    /// The maker and taker fees of the trading pair.
+    pub trading_fee: TradingFee,
 }
 
 /// Details of an order.
@@ -57,6 +60,30 @@ pub struct Depth<Price, Balance> {
     pub bids: Vec<(Price, Balance)>,
 }
 
+/// An aggregated price level of the order book.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OrderBookLevel<Price, Balance> {
+    pub price: Price,
+    /// Sum of the unfilled amounts at this price, measured by the **base** currency.
+    pub amount: Balance,
+    /// Number of the open orders at this price.
+    pub order_count: u32,
+}
+
+/// Order book of a trading pair, asks in ascending and bids in descending order of price.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OrderBook<Price, Balance> {
+    pub pair_id: TradingPairId,
+    #[cfg_attr(feature = "std", serde(flatten))]
+    pub handicap: Handicap<Price>,
+    pub asks: Vec<OrderBookLevel<Price, Balance>>,
+    pub bids: Vec<OrderBookLevel<Price, Balance>>,
+}
+
 impl<T: Config> Pallet<T> {
     /// Returns the range of a valid quotation for a trading pair.
     fn get_quotation_range(profile: &TradingPairProfile) -> (T::Price, T::Price) {
@@ -91,6 +118,7 @@ impl<T: Config> Pallet<T> {
                     pair_info,
                     max_valid_bid,
                     min_valid_ask,
+                    trading_fee: Self::trading_fee_of(pair_id),
                 });
             }
         }
@@ -108,33 +136,46 @@ impl<T: Config> Pallet<T> {
         page_size: u32,
     ) -> Vec<RpcOrder<TradingPairId, T::AccountId, BalanceOf<T>, T::Price, T::BlockNumber>> {
         OrderInfoOf::<T>::iter_prefix_values(who)
-            .flat_map(|order| {
-                Self::trading_pair(order.pair_id())
-                    .ok()
-                    .and_then(|pair| match order.props.side {
-                        Side::Buy => Self::convert_base_to_quote(
-                            order.remaining_in_base(),
-                            order.props.price,
-                            &pair,
-                        )
-                        .ok(),
-                        Side::Sell => Some(order.remaining),
-                    })
-                    .map(|reserved_balance| RpcOrder {
-                        props: order.props,
-                        status: order.status,
-                        remaining: order.remaining,
-                        executed_indices: order.executed_indices,
-                        already_filled: order.already_filled,
-                        reserved_balance,
-                        last_update_at: order.last_update_at,
-                    })
-            })
+            .flat_map(Self::rpc_order)
             .skip((page_index * page_size) as usize)
             .take(page_size as usize)
             .collect()
     }
 
+    /// Get all the open orders of an account, sorted by the trading pair and the order ID.
+    pub fn user_orders(
+        who: T::AccountId,
+    ) -> Vec<RpcOrder<TradingPairId, T::AccountId, BalanceOf<T>, T::Price, T::BlockNumber>> {
+        let mut orders = OrderInfoOf::<T>::iter_prefix_values(who)
+            .flat_map(Self::rpc_order)
+            .collect::<Vec<_>>();
+        orders.sort_by_key(|order| (order.props.pair_id, order.props.id));
+        orders
+    }
+
+    fn rpc_order(
+        order: OrderInfo<T>,
+    ) -> Option<RpcOrder<TradingPairId, T::AccountId, BalanceOf<T>, T::Price, T::BlockNumber>> {
+        Self::trading_pair(order.pair_id())
+            .ok()
+            .and_then(|pair| match order.props.side {
+                Side::Buy => {
+                    Self::convert_base_to_quote(order.remaining_in_base(), order.props.price, &pair)
+                        .ok()
+                }
+                Side::Sell => Some(order.remaining),
+            })
+            .map(|reserved_balance| RpcOrder {
+                props: order.props,
+                status: order.status,
+                remaining: order.remaining,
+                executed_indices: order.executed_indices,
+                already_filled: order.already_filled,
+                reserved_balance,
+                last_update_at: order.last_update_at,
+            })
+    }
+
     /// Returns the sum of unfilled quantities at `price` of a trading pair `pair_id`.
     fn get_commulative_qty(pair_id: TradingPairId, price: T::Price) -> u128 {
         QuotationsOf::<T>::get(pair_id, price)
@@ -188,13 +229,87 @@ impl<T: Config> Pallet<T> {
             Depth { asks, bids }
         })
     }
+
+    /// Get the order book of a trading pair, at most `depth` price levels on each side.
+    ///
+    /// Only the price levels with quotations are visited, unlike [`Self::depth`] which walks
+    /// every tick between the handicap and the valid quotation range.
+    pub fn order_book(
+        pair_id: TradingPairId,
+        depth: u32,
+    ) -> Option<OrderBook<T::Price, BalanceOf<T>>> {
+        Self::trading_pair_of(pair_id).map(|_| {
+            let mut asks = BTreeMap::<T::Price, OrderBookLevel<T::Price, BalanceOf<T>>>::new();
+            let mut bids = BTreeMap::<T::Price, OrderBookLevel<T::Price, BalanceOf<T>>>::new();
+            for (price, quotations) in QuotationsOf::<T>::iter_prefix(pair_id) {
+                for order in quotations
+                    .iter()
+                    .filter_map(|(trader, order_id)| OrderInfoOf::<T>::get(trader, order_id))
+                {
+                    let levels = match order.props.side {
+                        Side::Sell => &mut asks,
+                        Side::Buy => &mut bids,
+                    };
+                    let level = levels.entry(price).or_insert_with(|| OrderBookLevel {
+                        price,
+                        ..Default::default()
+                    });
+                    level.amount = level.amount.saturating_add(order.remaining_in_base());
+                    level.order_count = level.order_count.saturating_add(1);
+                }
+            }
+
+            OrderBook {
+                pair_id,
+                handicap: Self::handicap_of(pair_id),
+                asks: asks.into_values().take(depth as usize).collect(),
+                bids: bids.into_values().rev().take(depth as usize).collect(),
+            }
+        })
+    }
+
+    /// Get the time-weighted average price of a trading pair over the last `window` blocks.
+    ///
+    /// Each executed price is weighted by the number of blocks it lasted, the window is cut
+    /// to the oldest kept price. Returns the latest price if no block has elapsed in the
+    /// window, or `None` if the pair has never been traded.
+    pub fn twap(pair_id: TradingPairId, window: T::BlockNumber) -> Option<T::Price> {
+        let observations = Self::price_observations_of(pair_id);
+        let (_, latest_price) = *observations.last()?;
+
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        let window_start = current_block.saturating_sub(window);
+
+        let ends = observations
+            .iter()
+            .skip(1)
+            .map(|(block, _)| *block)
+            .chain(Some(current_block));
+
+        let (mut weighted_sum, mut elapsed) = (0u128, 0u128);
+        for ((block, price), end) in observations.iter().zip(ends) {
+            let start = cmp::max(*block, window_start);
+            if end > start {
+                let blocks = (end - start).saturated_into::<u128>();
+                weighted_sum = weighted_sum
+                    .saturating_add(price.saturated_into::<u128>().saturating_mul(blocks));
+                elapsed += blocks;
+            }
+        }
+
+        if elapsed.is_zero() {
+            Some(latest_price)
+        } else {
+            Some((weighted_sum / elapsed).saturated_into())
+        }
+    }
 }
 
 #[cfg(test)]
 mod rpc_tests {
     use super::*;
     use crate::mock::*;
-    use crate::tests::{t_issue_pcx, t_put_order_sell, t_set_handicap};
+    use crate::tests::{t_issue_pcx, t_put_order_buy, t_put_order_sell, t_set_handicap};
     use frame_support::assert_ok;
 
     #[test]
@@ -219,4 +334,88 @@ mod rpc_tests {
             });
         });
     }
+
+    #[test]
+    fn rpc_order_book_should_work() {
+        ExtBuilder::default().build_and_execute(|| {
+            let pair_id = 0;
+            let trading_pair = XSpot::trading_pair_of(pair_id).unwrap();
+
+            t_set_handicap(pair_id, 1_000_000, 1_100_000);
+
+            t_issue_pcx(1, 1000);
+            t_issue_pcx(2, 1000);
+            assert_ok!(t_put_order_sell(1, pair_id, 100, 1_109_000));
+            assert_ok!(t_put_order_sell(1, pair_id, 100, 1_108_000));
+            assert_ok!(t_put_order_sell(2, pair_id, 200, 1_108_000));
+            // Out of the valid quotation range, still on the order book.
+            assert_ok!(t_put_order_sell(2, pair_id, 100, 1_210_000));
+
+            assert_ok!(XAssets::issue(&trading_pair.quote(), &3, 10, true));
+            assert_ok!(t_put_order_buy(3, pair_id, 2000, 1_000_000));
+            assert_ok!(t_put_order_buy(3, pair_id, 1000, 1_000_200));
+
+            let level = |price, amount, order_count| OrderBookLevel {
+                price,
+                amount,
+                order_count,
+            };
+            assert_eq!(
+                XSpot::order_book(pair_id, 2).unwrap(),
+                OrderBook {
+                    pair_id,
+                    handicap: XSpot::handicap_of(pair_id),
+                    asks: vec![level(1_108_000, 300, 2), level(1_109_000, 100, 1)],
+                    bids: vec![level(1_000_200, 1000, 1), level(1_000_000, 2000, 1)],
+                }
+            );
+
+            let book = XSpot::order_book(pair_id, 1).unwrap();
+            assert_eq!(book.asks, vec![level(1_108_000, 300, 2)]);
+            assert_eq!(book.bids, vec![level(1_000_200, 1000, 1)]);
+
+            assert_eq!(XSpot::order_book(pair_id + 100, 2), None);
+
+            let orders = XSpot::user_orders(1);
+            assert_eq!(
+                orders
+                    .iter()
+                    .map(|order| (order.props.id, order.props.price))
+                    .collect::<Vec<_>>(),
+                vec![(0, 1_109_000), (1, 1_108_000)]
+            );
+            assert!(XSpot::user_orders(4).is_empty());
+        });
+    }
+
+    #[test]
+    fn rpc_twap_should_work() {
+        ExtBuilder::default().build_and_execute(|| {
+            let pair_id = 0;
+            let trading_pair = XSpot::trading_pair_of(pair_id).unwrap();
+
+            t_set_handicap(pair_id, 1_000_000, 1_100_000);
+            t_issue_pcx(2, 2000);
+            assert_ok!(XAssets::issue(&trading_pair.quote(), &1, 10, true));
+
+            assert_eq!(XSpot::twap(pair_id, 10), None);
+
+            assert_ok!(t_put_order_sell(2, pair_id, 1000, 1_000_000));
+            assert_ok!(t_put_order_buy(1, pair_id, 1000, 1_000_000));
+            assert_eq!(XSpot::twap(pair_id, 10), Some(1_000_000));
+
+            System::set_block_number(11);
+            assert_ok!(t_put_order_sell(2, pair_id, 1000, 1_000_100));
+            assert_ok!(t_put_order_buy(1, pair_id, 1000, 1_000_100));
+            assert_eq!(XSpot::price_observations_of(pair_id).len(), 2);
+
+            System::set_block_number(21);
+            // The first price lasted for blocks [1, 11), the second one for [11, 21).
+            assert_eq!(XSpot::twap(pair_id, 20), Some(1_000_050));
+            assert_eq!(XSpot::twap(pair_id, 100), Some(1_000_050));
+            assert_eq!(XSpot::twap(pair_id, 15), Some(1_000_066));
+            assert_eq!(XSpot::twap(pair_id, 5), Some(1_000_100));
+            assert_eq!(XSpot::twap(pair_id, 0), Some(1_000_100));
+        });
+    }
 }
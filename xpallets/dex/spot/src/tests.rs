@@ -5,7 +5,7 @@
 use sp_std::collections::btree_map::BTreeMap;
 
 use frame_support::{assert_noop, assert_ok};
-use xpallet_assets::AssetType;
+use xpallet_assets::{AssetInfo, AssetType, Chain};
 
 use super::mock::*;
 use super::*;
@@ -33,13 +33,33 @@ pub(crate) fn t_generic_free_balance(who: AccountId, asset_id: AssetId) -> Balan
     }
 }
 
+pub(crate) fn t_register_asset(asset_id: AssetId, token: &[u8]) {
+    let asset = AssetInfo::new::<Test>(
+        token.to_vec(),
+        token.to_vec(),
+        Chain::Ethereum,
+        8,
+        token.to_vec(),
+    )
+    .unwrap();
+    assert_ok!(XAssetsRegistrar::register(
+        Origin::root(),
+        asset_id,
+        asset,
+        true,
+        false
+    ));
+}
+
 fn t_trading_pair_of(idx: TradingPairId) -> TradingPairProfile {
     XSpot::trading_pair_of(idx).unwrap()
 }
 
-fn t_put_order_buy(
+fn t_put_order(
     who: AccountId,
     pair_idx: TradingPairId,
+    flavor: OrderFlavor,
+    side: Side,
     amount: Balance,
     price: Price,
 ) -> DispatchResult {
@@ -47,6 +67,23 @@ fn t_put_order_buy(
         Origin::signed(who),
         pair_idx,
         OrderType::Limit,
+        flavor,
+        side,
+        amount,
+        price,
+    )
+}
+
+pub(crate) fn t_put_order_buy(
+    who: AccountId,
+    pair_idx: TradingPairId,
+    amount: Balance,
+    price: Price,
+) -> DispatchResult {
+    t_put_order(
+        who,
+        pair_idx,
+        OrderFlavor::GoodTillCancel,
         Side::Buy,
         amount,
         price,
@@ -59,10 +96,10 @@ pub(crate) fn t_put_order_sell(
     amount: Balance,
     price: Price,
 ) -> DispatchResult {
-    XSpot::put_order(
-        Origin::signed(who),
+    t_put_order(
+        who,
         pair_idx,
-        OrderType::Limit,
+        OrderFlavor::GoodTillCancel,
         Side::Sell,
         amount,
         price,
@@ -109,6 +146,8 @@ pub(crate) fn t_add_trading_pair(
 #[test]
 fn add_trading_pair_should_work() {
     ExtBuilder::default().build_and_execute(|| {
+        t_register_asset(EOS, b"EOS");
+        t_register_asset(ETH, b"ETH");
         let pair = CurrencyPair::new(EOS, ETH);
         t_add_trading_pair(pair.clone(), 2, 1, 100, true);
         assert_eq!(XSpot::trading_pair_count(), 3);
@@ -124,6 +163,8 @@ fn add_trading_pair_should_work() {
 #[test]
 fn update_trading_pair_should_work() {
     ExtBuilder::default().build_and_execute(|| {
+        t_register_asset(EOS, b"EOS");
+        t_register_asset(ETH, b"ETH");
         let pair = CurrencyPair::new(EOS, ETH);
         t_add_trading_pair(pair, 2, 1, 100, true);
         assert_eq!(t_trading_pair_of(2).tick_decimals, 1);
@@ -135,6 +176,189 @@ fn update_trading_pair_should_work() {
     })
 }
 
+#[test]
+fn add_trading_pair_should_validate_the_assets_and_precision() {
+    ExtBuilder::default().build_and_execute(|| {
+        let add_trading_pair = |base, quote, pip_decimals, tick_decimals| {
+            XSpot::add_trading_pair(
+                Origin::root(),
+                CurrencyPair::new(base, quote),
+                pip_decimals,
+                tick_decimals,
+                100,
+                true,
+            )
+        };
+
+        assert_noop!(
+            add_trading_pair(EOS, ETH, 2, 1),
+            Error::<Test>::InvalidTradingPairAsset
+        );
+        assert_noop!(
+            add_trading_pair(EOS, EOS, 2, 1),
+            Error::<Test>::InvalidCurrencyPair
+        );
+
+        t_register_asset(EOS, b"EOS");
+        t_register_asset(ETH, b"ETH");
+        assert_noop!(
+            add_trading_pair(EOS, ETH, 1, 2),
+            Error::<Test>::InvalidPrecision
+        );
+        assert_noop!(
+            add_trading_pair(PCX, X_BTC, 9, 2),
+            Error::<Test>::TradingPairAlreadyExists
+        );
+        assert_noop!(
+            XSpot::add_trading_pair(
+                Origin::signed(1),
+                CurrencyPair::new(EOS, ETH),
+                2,
+                1,
+                100,
+                true
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(XAssetsRegistrar::deregister(Origin::root(), ETH));
+        assert_noop!(
+            add_trading_pair(EOS, ETH, 2, 1),
+            Error::<Test>::InvalidTradingPairAsset
+        );
+    })
+}
+
+#[test]
+fn update_trading_pair_precision_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let pair_id = 0;
+        assert_eq!(
+            XSpot::trading_pair_info_of(pair_id).unwrap().latest_price,
+            100000
+        );
+
+        assert_noop!(
+            XSpot::update_trading_pair_precision(Origin::root(), pair_id, 2, 3),
+            Error::<Test>::InvalidPrecision
+        );
+
+        t_set_handicap(pair_id, 1_000_000, 1_100_000);
+        t_generic_issue(X_BTC, 1, 10);
+        t_issue_pcx(2, 2_000);
+        assert_ok!(t_put_order_buy(1, pair_id, 1000, 1_000_000));
+        assert_ok!(t_put_order_buy(1, pair_id, 1000, 1_000_100));
+        assert_ok!(t_put_order_sell(2, pair_id, 1000, 1_100_000));
+        let refund = t_convert_base_to_quote(1000, 1_000_100, &t_trading_pair_of(pair_id));
+        let free_btc = t_generic_free_balance(1, X_BTC);
+
+        // 1_000_100 can not be represented by 7 pip decimals, the others are re-quantized.
+        assert_ok!(XSpot::update_trading_pair_precision(
+            Origin::root(),
+            pair_id,
+            7,
+            1
+        ));
+        let pair = t_trading_pair_of(pair_id);
+        assert_eq!((pair.pip_decimals, pair.tick_decimals), (7, 1));
+        assert_eq!(
+            XSpot::trading_pair_info_of(pair_id).unwrap().latest_price,
+            1000
+        );
+
+        assert_eq!(XSpot::order_info_of(1, 0).unwrap().price(), 10_000);
+        assert_eq!(XSpot::quotations_of(pair_id, 10_000), vec![(1, 0)]);
+        assert!(XSpot::quotations_of(pair_id, 1_000_000).is_empty());
+        assert_eq!(XSpot::order_info_of(2, 0).unwrap().price(), 11_000);
+        assert_eq!(XSpot::native_reserves(&2), 1000);
+
+        assert!(XSpot::order_info_of(1, 1).is_none());
+        assert!(XSpot::quotations_of(pair_id, 1_000_100).is_empty());
+        assert_eq!(t_generic_free_balance(1, X_BTC), free_btc + refund);
+        assert_eq!(XSpot::handicap_of(pair_id), Handicap::new(10_000, 11_000));
+
+        // The re-quantized orders can still be matched.
+        t_issue_pcx(3, 2_000);
+        assert_ok!(t_put_order_sell(3, pair_id, 1000, 10_000));
+        assert!(XSpot::order_info_of(1, 0).is_none());
+        assert!(XSpot::quotations_of(pair_id, 10_000).is_empty());
+    })
+}
+
+#[test]
+fn update_trading_pair_should_cancel_the_incompatible_orders() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        t_set_handicap(0, 1_000_000, 1_200_000);
+
+        t_issue_pcx(1, 3_000);
+        assert_ok!(XSpot::put_linked_orders(
+            Origin::signed(1),
+            0,
+            Side::Sell,
+            LinkedOrderLeg {
+                amount: 1_000,
+                price: 1_210_000
+            },
+            LinkedOrderLeg {
+                amount: 2_000,
+                price: 1_200_000
+            },
+        ));
+        assert_ok!(t_put_order_sell(1, 0, 500, 1_300_000));
+        assert_eq!(XSpot::native_reserves(&1), 2_500);
+
+        assert_noop!(
+            XSpot::update_trading_pair(Origin::root(), 0, 10, true),
+            Error::<Test>::InvalidPrecision
+        );
+
+        // 1_210_000 is not a multiple of the new tick, its linked order is canceled as well.
+        assert_ok!(XSpot::update_trading_pair(Origin::root(), 0, 4, true));
+        assert!(XSpot::order_info_of(1, 0).is_none());
+        assert!(XSpot::order_info_of(1, 1).is_none());
+        assert!(XSpot::order_link_of(0).is_none());
+        assert!(XSpot::quotations_of(0, 1_210_000).is_empty());
+        assert!(XSpot::quotations_of(0, 1_200_000).is_empty());
+        assert_eq!(XSpot::quotations_of(0, 1_300_000), vec![(1, 2)]);
+
+        assert_eq!(XSpot::native_reserves(&1), 500);
+        assert_eq!(t_generic_free_balance(1, trading_pair.base()), 2_500);
+        assert_eq!(XSpot::handicap_of(0), Handicap::new(1_000_000, 1_200_000));
+    })
+}
+
+#[test]
+fn set_pair_online_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let pair_id = 0;
+        t_set_handicap(pair_id, 1_000_000, 1_100_000);
+        t_generic_issue(X_BTC, 1, 10);
+
+        assert_ok!(XSpot::set_pair_online(Origin::root(), pair_id, false));
+        assert!(!t_trading_pair_of(pair_id).tradable);
+        assert_noop!(
+            t_put_order_buy(1, pair_id, 1000, 1_000_000),
+            Error::<Test>::TradingPairUntradable
+        );
+
+        assert_ok!(XSpot::set_pair_online(Origin::root(), pair_id, true));
+        assert!(t_trading_pair_of(pair_id).tradable);
+        assert_ok!(t_put_order_buy(1, pair_id, 1000, 1_000_000));
+
+        // Deregistering an asset sets its pairs offline, which can not be undone until the
+        // asset is recovered.
+        assert_ok!(XAssetsRegistrar::deregister(Origin::root(), X_BTC));
+        assert!(!t_trading_pair_of(pair_id).tradable);
+        assert_noop!(
+            XSpot::set_pair_online(Origin::root(), pair_id, true),
+            Error::<Test>::InvalidTradingPairAsset
+        );
+        assert_ok!(XAssetsRegistrar::recover(Origin::root(), X_BTC, true));
+        assert_ok!(XSpot::set_pair_online(Origin::root(), pair_id, true));
+    })
+}
+
 #[test]
 fn convert_base_to_quote_should_work() {
     ExtBuilder::default().build_and_execute(|| {
@@ -507,3 +731,243 @@ fn quotations_order_should_be_preserved_when_removing_orders_and_quotations() {
         assert_eq!(XSpot::quotations_of(0, 2_000_000), [(2, 1), (5, 0), (6, 0)]);
     })
 }
+
+#[test]
+fn linked_orders_should_cancel_each_other() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        t_set_handicap(0, 1_000_000, 1_200_000);
+
+        t_issue_pcx(1, 3_000);
+        t_generic_issue(trading_pair.quote(), 2, 10);
+
+        let t_put_linked_orders = |first: (Balance, Price), second: (Balance, Price)| {
+            XSpot::put_linked_orders(
+                Origin::signed(1),
+                0,
+                Side::Sell,
+                LinkedOrderLeg {
+                    amount: first.0,
+                    price: first.1,
+                },
+                LinkedOrderLeg {
+                    amount: second.0,
+                    price: second.1,
+                },
+            )
+        };
+
+        // Only the larger reserve of the linked orders is locked.
+        assert_ok!(t_put_linked_orders((1_000, 1_210_000), (2_000, 1_200_000)));
+        assert_eq!(XSpot::native_reserves(&1), 2_000);
+        assert_eq!(t_generic_free_balance(1, trading_pair.base()), 1_000);
+        assert_eq!(XSpot::order_link_of(0).unwrap().orders, [0, 1]);
+        assert_eq!(XSpot::order_link_id_of(1, 1), Some(0));
+
+        // Canceling one of the linked orders cancels the other.
+        assert_ok!(t_cancel_order(1, 0, 0));
+        assert!(XSpot::order_info_of(1, 0).is_none());
+        assert!(XSpot::order_info_of(1, 1).is_none());
+        assert!(XSpot::order_link_of(0).is_none());
+        assert!(XSpot::order_link_id_of(1, 1).is_none());
+        assert!(XSpot::quotations_of(0, 1_200_000).is_empty());
+        assert_eq!(XSpot::native_reserves(&1), 0);
+        assert_eq!(t_generic_free_balance(1, trading_pair.base()), 3_000);
+
+        // Executing one of the linked orders cancels the other.
+        assert_ok!(t_put_linked_orders((1_000, 1_210_000), (2_000, 1_200_000)));
+        assert_ok!(t_put_order_buy(2, 0, 1_000, 1_200_000));
+
+        assert!(XSpot::order_info_of(1, 2).is_none());
+        assert!(XSpot::quotations_of(0, 1_210_000).is_empty());
+        assert!(XSpot::order_link_of(1).is_none());
+        assert_eq!(
+            XSpot::order_info_of(1, 3).unwrap().status,
+            OrderStatus::PartialFill
+        );
+        assert_eq!(XSpot::native_reserves(&1), 1_000);
+        assert_eq!(t_generic_free_balance(1, trading_pair.base()), 1_000);
+        assert_eq!(t_generic_free_balance(2, trading_pair.base()), 1_000);
+    })
+}
+
+#[test]
+fn post_only_order_should_never_be_matched() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        t_set_handicap(0, 1_000_000, 1_200_000);
+
+        t_issue_pcx(1, 3_000);
+        t_generic_issue(trading_pair.quote(), 2, 10);
+
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_100_000));
+
+        assert_noop!(
+            t_put_order(2, 0, OrderFlavor::PostOnly, Side::Buy, 1_000, 1_100_000),
+            Error::<Test>::PostOnlyWouldMatch
+        );
+
+        assert_ok!(t_put_order(
+            2,
+            0,
+            OrderFlavor::PostOnly,
+            Side::Buy,
+            1_000,
+            1_000_000
+        ));
+        assert_eq!(
+            XSpot::order_info_of(2, 0).unwrap().status,
+            OrderStatus::Created
+        );
+        assert_eq!(XSpot::quotations_of(0, 1_000_000), vec![(2, 0)]);
+        assert_eq!(XSpot::order_info_of(1, 0).unwrap().already_filled, 0);
+    })
+}
+
+#[test]
+fn fill_or_kill_order_should_be_filled_completely_or_rejected() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        t_set_handicap(0, 1_000_000, 1_200_000);
+
+        t_issue_pcx(1, 3_000);
+        t_generic_issue(trading_pair.quote(), 2, 10);
+
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_100_000));
+        assert_ok!(t_put_order_sell(1, 0, 500, 1_100_100));
+
+        // There is no order to be matched with.
+        assert_noop!(
+            t_put_order(2, 0, OrderFlavor::FillOrKill, Side::Buy, 1_000, 1_000_000),
+            Error::<Test>::FillOrKillUnfilled
+        );
+        // Only 1_500 can be filled at the price of 1_100_100.
+        assert_noop!(
+            t_put_order(2, 0, OrderFlavor::FillOrKill, Side::Buy, 2_000, 1_100_100),
+            Error::<Test>::FillOrKillUnfilled
+        );
+
+        assert_ok!(t_put_order(
+            2,
+            0,
+            OrderFlavor::FillOrKill,
+            Side::Buy,
+            1_500,
+            1_100_100
+        ));
+        assert!(XSpot::order_info_of(2, 0).is_none());
+        assert!(XSpot::order_info_of(1, 0).is_none());
+        assert!(XSpot::order_info_of(1, 1).is_none());
+        assert!(XSpot::quotations_of(0, 1_100_100).is_empty());
+        assert_eq!(t_generic_free_balance(2, trading_pair.base()), 1_500);
+    })
+}
+
+#[test]
+fn immediate_or_cancel_order_should_cancel_the_unfilled_part() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+        t_set_handicap(0, 1_000_000, 1_200_000);
+
+        t_issue_pcx(1, 3_000);
+        t_generic_issue(trading_pair.quote(), 2, 10);
+
+        assert_ok!(t_put_order_sell(1, 0, 1_000, 1_100_000));
+
+        // Partially filled, the rest is canceled instead of being put on the order book.
+        assert_ok!(t_put_order(
+            2,
+            0,
+            OrderFlavor::ImmediateOrCancel,
+            Side::Buy,
+            2_000,
+            1_100_000
+        ));
+        assert!(XSpot::order_info_of(2, 0).is_none());
+        assert!(XSpot::order_info_of(1, 0).is_none());
+        assert!(XSpot::quotations_of(0, 1_100_000).is_empty());
+        assert_eq!(XSpot::handicap_of(0).highest_bid, 1_000_000);
+
+        let paid = XSpot::convert_base_to_quote(1_000, 1_100_000, &trading_pair).unwrap();
+        assert_eq!(t_generic_free_balance(2, trading_pair.base()), 1_000);
+        assert_eq!(t_generic_free_balance(2, trading_pair.quote()), 10 - paid);
+        assert_eq!(t_generic_free_balance(1, trading_pair.quote()), paid);
+
+        // Nothing to be matched with, the order is canceled right away.
+        assert_ok!(t_put_order(
+            2,
+            0,
+            OrderFlavor::ImmediateOrCancel,
+            Side::Buy,
+            1_000,
+            1_000_000
+        ));
+        assert!(XSpot::order_info_of(2, 1).is_none());
+        assert!(XSpot::quotations_of(0, 1_000_000).is_empty());
+        assert_eq!(t_generic_free_balance(2, trading_pair.quote()), 10 - paid);
+    })
+}
+
+#[test]
+fn trading_fee_should_be_charged_on_the_received_assets() {
+    ExtBuilder::default().build_and_execute(|| {
+        let fee = TradingFee {
+            maker_bps: 10,
+            taker_bps: 30,
+            destination: FeeDestination::Treasury,
+        };
+        assert_noop!(
+            XSpot::set_trading_fee(Origin::signed(1), 0, fee),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XSpot::set_trading_fee(Origin::root(), 99, fee),
+            Error::<Test>::InvalidTradingPair
+        );
+        assert_noop!(
+            XSpot::set_trading_fee(
+                Origin::root(),
+                0,
+                TradingFee {
+                    taker_bps: MAX_TRADING_FEE_BPS + 1,
+                    ..fee
+                }
+            ),
+            Error::<Test>::InvalidTradingFee
+        );
+        assert_ok!(XSpot::set_trading_fee(Origin::root(), 0, fee));
+        assert_eq!(XSpot::trading_fee_of(0), fee);
+
+        t_set_handicap(0, 1_000_000, 1_100_000);
+        t_issue_pcx(2, 2_000_000_000);
+        t_generic_issue(X_BTC, 1, 2_000_000);
+
+        // The maker sells 10 PCX for 0.01 BTC.
+        assert_ok!(t_put_order_sell(2, 0, 1_000_000_000, 1_000_000));
+        assert_ok!(t_put_order_buy(1, 0, 1_000_000_000, 1_000_000));
+
+        assert_eq!(t_generic_free_balance(2, X_BTC), 999_000);
+        assert_eq!(t_generic_free_balance(1, PCX), 997_000_000);
+        assert_eq!(t_generic_free_balance(TREASURY, X_BTC), 1_000);
+        assert_eq!(t_generic_free_balance(TREASURY, PCX), 3_000_000);
+
+        // The fees go to the reward pot of the pair.
+        assert_ok!(XSpot::set_trading_fee(
+            Origin::root(),
+            0,
+            TradingFee {
+                destination: FeeDestination::RewardPot,
+                ..fee
+            }
+        ));
+        assert_ok!(t_put_order_sell(2, 0, 1_000_000_000, 1_000_000));
+        assert_ok!(t_put_order_buy(1, 0, 1_000_000_000, 1_000_000));
+
+        let pot = XSpot::reward_pot_of(0);
+        assert_eq!(t_generic_free_balance(pot, X_BTC), 1_000);
+        assert_eq!(t_generic_free_balance(pot, PCX), 3_000_000);
+        assert_eq!(t_generic_free_balance(TREASURY, X_BTC), 1_000);
+        assert_eq!(t_generic_free_balance(2, X_BTC), 1_998_000);
+        assert_eq!(t_generic_free_balance(1, PCX), 1_994_000_000);
+    })
+}
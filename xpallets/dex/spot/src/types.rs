@@ -7,7 +7,7 @@ use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_arithmetic::traits::BaseArithmetic;
+use sp_arithmetic::traits::{BaseArithmetic, UniqueSaturatedFrom};
 use sp_runtime::RuntimeDebug;
 
 /// Type for counting the number of user orders.
@@ -25,6 +25,9 @@ pub type Tick = u64;
 /// The number of ticks the price fluctuation.
 pub type PriceFluctuation = u32;
 
+/// Type for counting the number of order links.
+pub type OrderLinkId = u64;
+
 /// Type of an order.
 ///
 /// Currently only Limit Order is supported.
@@ -41,6 +44,33 @@ impl Default for OrderType {
     }
 }
 
+/// Time in force of an order, i.e., how long the order stays on the order book.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum OrderFlavor {
+    /// The unfilled part stays on the order book until it's canceled.
+    GoodTillCancel,
+    /// The order has to be filled completely right away, otherwise it's rejected.
+    FillOrKill,
+    /// The order is filled as much as possible right away, the unfilled part is canceled.
+    ImmediateOrCancel,
+    /// The order is rejected if it would be matched right away, so it's always a maker.
+    PostOnly,
+}
+
+impl Default for OrderFlavor {
+    fn default() -> Self {
+        Self::GoodTillCancel
+    }
+}
+
+impl OrderFlavor {
+    /// Returns true if the unfilled part of the order never enters the order book.
+    pub fn is_immediate(self) -> bool {
+        matches!(self, Self::FillOrKill | Self::ImmediateOrCancel)
+    }
+}
+
 /// Direction of an order.
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -381,6 +411,87 @@ where
     }
 }
 
+/// One of the two orders submitted via `put_linked_orders`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LinkedOrderLeg<Balance, Price> {
+    /// The amount of order, measured in the base currency.
+    pub amount: Balance,
+    /// The price of order.
+    pub price: Price,
+}
+
+/// Two orders of the same side linked together as an OCO (one-cancels-other) pair, e.g.,
+/// a take-profit and a stop-loss.
+///
+/// Both orders reserve the same asset, only the larger one of their reserves is actually
+/// locked. As soon as either order gets executed or canceled, the other one is canceled.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OrderLink<AccountId, Balance> {
+    /// The account that submitted the linked orders.
+    pub submitter: AccountId,
+    /// The trading pair identifier.
+    pub pair_id: TradingPairId,
+    /// The direction of both orders.
+    pub side: Side,
+    /// The identifiers of the linked orders.
+    pub orders: [OrderId; 2],
+    /// The amount each order would have reserved on its own.
+    pub reserves: [Balance; 2],
+}
+
+impl<AccountId, Balance: Copy + Ord> OrderLink<AccountId, Balance> {
+    /// Returns the amount actually reserved for the linked orders.
+    pub fn shared_reserve(&self) -> Balance {
+        cmp::max(self.reserves[0], self.reserves[1])
+    }
+
+    /// Returns the index of the given order in the link.
+    pub fn position(&self, order_id: OrderId) -> Option<usize> {
+        self.orders.iter().position(|id| *id == order_id)
+    }
+}
+
+/// Where the trading fees of a trading pair go.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum FeeDestination {
+    /// The treasury account.
+    Treasury,
+    /// The reward pot account of the trading pair, see `Pallet::reward_pot_of`.
+    RewardPot,
+}
+
+impl Default for FeeDestination {
+    fn default() -> Self {
+        Self::Treasury
+    }
+}
+
+/// The trading fees of a trading pair.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TradingFee {
+    /// Fee of the maker in basis points of the asset it receives.
+    pub maker_bps: u32,
+    /// Fee of the taker in basis points of the asset it receives.
+    pub taker_bps: u32,
+    /// Where the fees go.
+    pub destination: FeeDestination,
+}
+
+impl TradingFee {
+    /// Returns the fee of `amount` given the fee rate in basis points.
+    pub fn fee_of<Balance: BaseArithmetic + Copy>(bps: u32, amount: Balance) -> Balance {
+        amount.saturating_mul(Balance::unique_saturated_from(bps))
+            / Balance::unique_saturated_from(10_000u32)
+    }
+}
+
 /// Latest price of a trading pair.
 #[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -403,6 +514,10 @@ pub struct OrderExecutedInfo<AccountId, Balance, BlockNumber, Price> {
     maker_order_id: OrderId,
     taker_order_id: OrderId,
     turnover: Balance,
+    /// Fee charged on the asset received by the maker.
+    maker_fee: Balance,
+    /// Fee charged on the asset received by the taker.
+    taker_fee: Balance,
     executed_at: BlockNumber,
 }
 
@@ -414,6 +529,7 @@ impl<AccountId: Clone, Balance: Copy + Ord + BaseArithmetic, BlockNumber: Copy,
         pair_id: TradingPairId,
         price: Price,
         turnover: Balance,
+        (maker_fee, taker_fee): (Balance, Balance),
         maker_order: &Order<TradingPairId, AccountId, Balance, Price, BlockNumber>,
         taker_order: &Order<TradingPairId, AccountId, Balance, Price, BlockNumber>,
         executed_at: BlockNumber,
@@ -423,6 +539,8 @@ impl<AccountId: Clone, Balance: Copy + Ord + BaseArithmetic, BlockNumber: Copy,
             pair_id,
             price,
             turnover,
+            maker_fee,
+            taker_fee,
             executed_at,
             maker: maker_order.submitter(),
             taker: taker_order.submitter(),
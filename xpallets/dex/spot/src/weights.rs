@@ -32,12 +32,18 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for xpallet_dex_spot.
 pub trait WeightInfo {
     fn put_order() -> Weight;
+    fn put_order_fill_or_kill() -> Weight;
+    fn put_order_immediate_or_cancel() -> Weight;
     fn cancel_order() -> Weight;
     fn force_cancel_order() -> Weight;
     fn set_handicap() -> Weight;
     fn set_price_fluctuation() -> Weight;
     fn add_trading_pair() -> Weight;
     fn update_trading_pair() -> Weight;
+    fn update_trading_pair_precision() -> Weight;
+    fn set_pair_online() -> Weight;
+    fn migrate_resting_orders(o: u32) -> Weight;
+    fn set_trading_fee() -> Weight;
 }
 
 /// Weights for xpallet_dex_spot using the Substrate node and recommended hardware.
@@ -48,6 +54,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(11 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn put_order_fill_or_kill() -> Weight {
+        (171_402_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+    fn put_order_immediate_or_cancel() -> Weight {
+        (168_517_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
     fn cancel_order() -> Weight {
         (133_946_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(8 as Weight))
@@ -65,8 +81,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
         (19_612_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
     fn add_trading_pair() -> Weight {
-        (38_706_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+        (41_925_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
     fn update_trading_pair() -> Weight {
@@ -74,6 +90,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn update_trading_pair_precision() -> Weight {
+        (35_148_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn set_pair_online() -> Weight {
+        (28_790_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn migrate_resting_orders(o: u32) -> Weight {
+        (12_604_000 as Weight)
+            .saturating_add((61_238_000 as Weight).saturating_mul(o as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(o as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(o as Weight)))
+    }
+    fn set_trading_fee() -> Weight {
+        (17_342_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -83,6 +122,16 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(11 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    fn put_order_fill_or_kill() -> Weight {
+        (171_402_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    fn put_order_immediate_or_cancel() -> Weight {
+        (168_517_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
     fn cancel_order() -> Weight {
         (133_946_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(8 as Weight))
@@ -100,8 +149,8 @@ impl WeightInfo for () {
         (19_612_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
     fn add_trading_pair() -> Weight {
-        (38_706_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+        (41_925_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
     fn update_trading_pair() -> Weight {
@@ -109,4 +158,27 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn update_trading_pair_precision() -> Weight {
+        (35_148_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn set_pair_online() -> Weight {
+        (28_790_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn migrate_resting_orders(o: u32) -> Weight {
+        (12_604_000 as Weight)
+            .saturating_add((61_238_000 as Weight).saturating_mul(o as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(o as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(o as Weight)))
+    }
+    fn set_trading_fee() -> Weight {
+        (17_342_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
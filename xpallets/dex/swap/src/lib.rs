@@ -0,0 +1,597 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! # Swap Module
+//!
+//! The constant-product pools of the registrar assets and PCX, complementing the order book
+//! of `xpallet_dex_spot` whose liquidity is too thin for the small conversions.
+//!
+//! The pools are created by the council, each pool issues its own LP token registered in
+//! `xpallet_assets_registrar` and minted/burned through `xpallet_assets`. The reserves of all
+//! pools are held by the account derived from `PalletId`, a `SwapFee` of the input amount is
+//! kept in the pool for the liquidity providers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+pub mod weights;
+
+use sp_core::U256;
+use sp_runtime::{
+    traits::{AccountIdConversion, SaturatedConversion, Zero},
+    DispatchError, PerThing, Permill,
+};
+use sp_std::prelude::*;
+
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Currency, ExistenceRequirement, Get},
+    transactional, PalletId,
+};
+use frame_system::{ensure_root, ensure_signed, RawOrigin};
+
+use chainx_primitives::AssetId;
+use xpallet_assets::{AssetInfo, BalanceOf, Chain};
+
+pub use self::types::PoolInfo;
+pub use self::weights::WeightInfo;
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + xpallet_assets::Config {
+        /// The overarching event type.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// A majority of the council can create the pools.
+        type CouncilOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The id used for deriving the account holding the reserves of all pools.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// The LP token of the n-th pool is registered as the asset `LpAssetIdBase + n`.
+        #[pallet::constant]
+        type LpAssetIdBase: Get<AssetId>;
+
+        /// The fee deducted from the input amount of a swap, it stays in the pool.
+        #[pallet::constant]
+        type SwapFee: Get<Permill>;
+
+        /// The LP tokens locked in the pool account forever on the first liquidity,
+        /// so that the reserves of a pool can never be drained to zero.
+        #[pallet::constant]
+        type MinimumLiquidity: Get<BalanceOf<Self>>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create the pool of `asset_a` and `asset_b`, and register its LP token.
+        #[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+        #[transactional]
+        pub fn create_pool(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_a: AssetId,
+            #[pallet::compact] asset_b: AssetId,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+            ensure!(
+                xpallet_assets_registrar::Pallet::<T>::is_valid(&asset_a)
+                    && xpallet_assets_registrar::Pallet::<T>::is_valid(&asset_b),
+                Error::<T>::InvalidAsset
+            );
+
+            let (asset_0, asset_1) = Self::sort_assets(asset_a, asset_b);
+            ensure!(
+                !Pools::<T>::contains_key((asset_0, asset_1)),
+                Error::<T>::PoolAlreadyExists
+            );
+
+            let index = Self::pool_count();
+            let lp_asset_id = Self::register_lp_asset(index, asset_0, asset_1)?;
+
+            Pools::<T>::insert(
+                (asset_0, asset_1),
+                PoolInfo {
+                    lp_asset_id,
+                    reserve_0: Zero::zero(),
+                    reserve_1: Zero::zero(),
+                },
+            );
+            PoolCount::<T>::put(index + 1);
+
+            Self::deposit_event(Event::<T>::PoolCreated(asset_0, asset_1, lp_asset_id));
+            Ok(())
+        }
+
+        /// Add the liquidity to the pool of `asset_a` and `asset_b`.
+        ///
+        /// The first liquidity sets the price of the pool, the following liquidity is added
+        /// at the current price, at most the desired amounts and at least the min amounts.
+        #[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+        #[transactional]
+        pub fn add_liquidity(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_a: AssetId,
+            #[pallet::compact] asset_b: AssetId,
+            #[pallet::compact] amount_a_desired: BalanceOf<T>,
+            #[pallet::compact] amount_b_desired: BalanceOf<T>,
+            #[pallet::compact] amount_a_min: BalanceOf<T>,
+            #[pallet::compact] amount_b_min: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let (asset_0, asset_1) = Self::sort_assets(asset_a, asset_b);
+            let ((amount_0_desired, amount_1_desired), (amount_0_min, amount_1_min)) =
+                if asset_a == asset_0 {
+                    (
+                        (amount_a_desired, amount_b_desired),
+                        (amount_a_min, amount_b_min),
+                    )
+                } else {
+                    (
+                        (amount_b_desired, amount_a_desired),
+                        (amount_b_min, amount_a_min),
+                    )
+                };
+
+            let (amount_0, amount_1, liquidity) = Self::apply_add_liquidity(
+                &who,
+                (asset_0, asset_1),
+                (amount_0_desired, amount_1_desired),
+                (amount_0_min, amount_1_min),
+            )?;
+
+            Self::deposit_event(Event::<T>::LiquidityAdded(
+                who, asset_0, asset_1, amount_0, amount_1, liquidity,
+            ));
+            Ok(())
+        }
+
+        /// Burn `liquidity` of the LP token to withdraw the share of the pool reserves.
+        #[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+        #[transactional]
+        pub fn remove_liquidity(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_a: AssetId,
+            #[pallet::compact] asset_b: AssetId,
+            #[pallet::compact] liquidity: BalanceOf<T>,
+            #[pallet::compact] amount_a_min: BalanceOf<T>,
+            #[pallet::compact] amount_b_min: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let (asset_0, asset_1) = Self::sort_assets(asset_a, asset_b);
+            let (amount_0_min, amount_1_min) = if asset_a == asset_0 {
+                (amount_a_min, amount_b_min)
+            } else {
+                (amount_b_min, amount_a_min)
+            };
+
+            let (amount_0, amount_1) = Self::apply_remove_liquidity(
+                &who,
+                (asset_0, asset_1),
+                liquidity,
+                (amount_0_min, amount_1_min),
+            )?;
+
+            Self::deposit_event(Event::<T>::LiquidityRemoved(
+                who, asset_0, asset_1, amount_0, amount_1, liquidity,
+            ));
+            Ok(())
+        }
+
+        /// Swap the exact `amount_in` of `asset_in` for at least `amount_out_min` of `asset_out`.
+        #[pallet::weight(<T as Config>::WeightInfo::swap())]
+        #[transactional]
+        pub fn swap(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_in: AssetId,
+            #[pallet::compact] asset_out: AssetId,
+            #[pallet::compact] amount_in: BalanceOf<T>,
+            #[pallet::compact] amount_out_min: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let amount_out =
+                Self::apply_swap(&who, asset_in, asset_out, amount_in, amount_out_min)?;
+
+            Self::deposit_event(Event::<T>::Swapped(
+                who, asset_in, asset_out, amount_in, amount_out,
+            ));
+            Ok(())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A new pool was created. [asset_0, asset_1, lp_asset_id]
+        PoolCreated(AssetId, AssetId, AssetId),
+        /// The liquidity was added to a pool. [who, asset_0, asset_1, amount_0, amount_1, liquidity]
+        LiquidityAdded(
+            T::AccountId,
+            AssetId,
+            AssetId,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        /// The liquidity was removed from a pool. [who, asset_0, asset_1, amount_0, amount_1, liquidity]
+        LiquidityRemoved(
+            T::AccountId,
+            AssetId,
+            AssetId,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        /// An asset was swapped for another. [who, asset_in, asset_out, amount_in, amount_out]
+        Swapped(T::AccountId, AssetId, AssetId, BalanceOf<T>, BalanceOf<T>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The two assets of a pool must be different.
+        IdenticalAssets,
+        /// The asset does not exist or is offline.
+        InvalidAsset,
+        /// The pool of the asset pair already exists.
+        PoolAlreadyExists,
+        /// The pool of the asset pair does not exist.
+        PoolNotFound,
+        /// The amount can not be zero.
+        ZeroAmount,
+        /// The pool has no liquidity for the swap.
+        InsufficientLiquidity,
+        /// The liquidity added is too small to mint any LP token.
+        InsufficientLiquidityMinted,
+        /// The liquidity removed is too small to withdraw any reserve.
+        InsufficientLiquidityBurned,
+        /// The amount is less than the minimum accepted by the caller.
+        SlippageExceeded,
+        /// The amount overflows the balance type.
+        Overflow,
+    }
+
+    /// The number of the pools created, the LP asset id of a new pool is derived from it.
+    #[pallet::storage]
+    #[pallet::getter(fn pool_count)]
+    pub(crate) type PoolCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// The pools keyed by the asset pair in ascending order.
+    #[pallet::storage]
+    #[pallet::getter(fn pools)]
+    pub(crate) type Pools<T: Config> =
+        StorageMap<_, Twox64Concat, (AssetId, AssetId), PoolInfo<BalanceOf<T>>>;
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the account holding the reserves of all pools.
+    pub fn pool_account() -> T::AccountId {
+        T::PalletId::get().into_account()
+    }
+
+    /// Returns the amount of `asset_out` received by swapping `amount_in` of `asset_in`.
+    pub fn quote(
+        asset_in: AssetId,
+        asset_out: AssetId,
+        amount_in: BalanceOf<T>,
+    ) -> Option<BalanceOf<T>> {
+        let (pool, is_in_0) = Self::pool_of(asset_in, asset_out).ok()?;
+        let (reserve_in, reserve_out) = if is_in_0 {
+            (pool.reserve_0, pool.reserve_1)
+        } else {
+            (pool.reserve_1, pool.reserve_0)
+        };
+        Self::get_amount_out(amount_in, reserve_in, reserve_out).ok()
+    }
+
+    /// Returns the pool of `asset_in` and `asset_out`, and whether `asset_in` is `asset_0`.
+    fn pool_of(
+        asset_in: AssetId,
+        asset_out: AssetId,
+    ) -> Result<(PoolInfo<BalanceOf<T>>, bool), DispatchError> {
+        ensure!(asset_in != asset_out, Error::<T>::IdenticalAssets);
+        let pool =
+            Self::pools(Self::sort_assets(asset_in, asset_out)).ok_or(Error::<T>::PoolNotFound)?;
+        Ok((pool, asset_in < asset_out))
+    }
+
+    fn sort_assets(asset_a: AssetId, asset_b: AssetId) -> (AssetId, AssetId) {
+        if asset_a < asset_b {
+            (asset_a, asset_b)
+        } else {
+            (asset_b, asset_a)
+        }
+    }
+
+    /// Register the LP token of the `index`-th pool, e.g., `LP-0` for the first pool.
+    fn register_lp_asset(
+        index: u32,
+        asset_0: AssetId,
+        asset_1: AssetId,
+    ) -> Result<AssetId, DispatchError> {
+        let lp_asset_id = T::LpAssetIdBase::get()
+            .checked_add(index)
+            .ok_or(Error::<T>::Overflow)?;
+
+        let info_0 = xpallet_assets_registrar::Pallet::<T>::asset_info_of(asset_0)
+            .ok_or(Error::<T>::InvalidAsset)?;
+        let info_1 = xpallet_assets_registrar::Pallet::<T>::asset_info_of(asset_1)
+            .ok_or(Error::<T>::InvalidAsset)?;
+
+        let mut token = b"LP-".to_vec();
+        let mut digits = index;
+        let start = token.len();
+        loop {
+            token.insert(start, b'0' + (digits % 10) as u8);
+            digits /= 10;
+            if digits == 0 {
+                break;
+            }
+        }
+
+        let mut desc = b"LP token of the ".to_vec();
+        desc.extend_from_slice(info_0.token());
+        desc.push(b'/');
+        desc.extend_from_slice(info_1.token());
+        desc.extend_from_slice(b" pool");
+
+        let decimals = ((u16::from(info_0.decimals()) + u16::from(info_1.decimals())) / 2) as u8;
+        let info = AssetInfo::new::<T>(token.clone(), token, Chain::ChainX, decimals, desc)?;
+        xpallet_assets_registrar::Pallet::<T>::register(
+            RawOrigin::Root.into(),
+            lp_asset_id,
+            info,
+            true,
+            false,
+        )?;
+
+        Ok(lp_asset_id)
+    }
+
+    fn apply_add_liquidity(
+        who: &T::AccountId,
+        (asset_0, asset_1): (AssetId, AssetId),
+        (amount_0_desired, amount_1_desired): (BalanceOf<T>, BalanceOf<T>),
+        (amount_0_min, amount_1_min): (BalanceOf<T>, BalanceOf<T>),
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        ensure!(
+            !amount_0_desired.is_zero() && !amount_1_desired.is_zero(),
+            Error::<T>::ZeroAmount
+        );
+        let mut pool = Self::pools((asset_0, asset_1)).ok_or(Error::<T>::PoolNotFound)?;
+
+        let (amount_0, amount_1) = if pool.reserve_0.is_zero() && pool.reserve_1.is_zero() {
+            (amount_0_desired, amount_1_desired)
+        } else {
+            let amount_1_optimal = Self::mul_div(amount_0_desired, pool.reserve_1, pool.reserve_0)?;
+            if amount_1_optimal <= amount_1_desired {
+                ensure!(
+                    amount_1_optimal >= amount_1_min,
+                    Error::<T>::SlippageExceeded
+                );
+                (amount_0_desired, amount_1_optimal)
+            } else {
+                let amount_0_optimal =
+                    Self::mul_div(amount_1_desired, pool.reserve_0, pool.reserve_1)?;
+                ensure!(
+                    amount_0_optimal >= amount_0_min,
+                    Error::<T>::SlippageExceeded
+                );
+                (amount_0_optimal, amount_1_desired)
+            }
+        };
+
+        let total_supply = xpallet_assets::Pallet::<T>::total_issuance(&pool.lp_asset_id);
+        let liquidity = if total_supply.is_zero() {
+            let liquidity = Self::from_u256(
+                (Self::to_u256(amount_0) * Self::to_u256(amount_1)).integer_sqrt(),
+            )?;
+            let minimum = T::MinimumLiquidity::get();
+            ensure!(liquidity > minimum, Error::<T>::InsufficientLiquidityMinted);
+            if !minimum.is_zero() {
+                xpallet_assets::Pallet::<T>::issue(
+                    &pool.lp_asset_id,
+                    &Self::pool_account(),
+                    minimum,
+                    false,
+                )?;
+            }
+            liquidity - minimum
+        } else {
+            sp_std::cmp::min(
+                Self::mul_div(amount_0, total_supply, pool.reserve_0)?,
+                Self::mul_div(amount_1, total_supply, pool.reserve_1)?,
+            )
+        };
+        ensure!(
+            !liquidity.is_zero(),
+            Error::<T>::InsufficientLiquidityMinted
+        );
+
+        Self::transfer_in(asset_0, who, amount_0)?;
+        Self::transfer_in(asset_1, who, amount_1)?;
+        xpallet_assets::Pallet::<T>::issue(&pool.lp_asset_id, who, liquidity, false)?;
+
+        pool.reserve_0 += amount_0;
+        pool.reserve_1 += amount_1;
+        Pools::<T>::insert((asset_0, asset_1), pool);
+
+        Ok((amount_0, amount_1, liquidity))
+    }
+
+    fn apply_remove_liquidity(
+        who: &T::AccountId,
+        (asset_0, asset_1): (AssetId, AssetId),
+        liquidity: BalanceOf<T>,
+        (amount_0_min, amount_1_min): (BalanceOf<T>, BalanceOf<T>),
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        ensure!(!liquidity.is_zero(), Error::<T>::ZeroAmount);
+        let mut pool = Self::pools((asset_0, asset_1)).ok_or(Error::<T>::PoolNotFound)?;
+
+        let total_supply = xpallet_assets::Pallet::<T>::total_issuance(&pool.lp_asset_id);
+        ensure!(
+            !total_supply.is_zero(),
+            Error::<T>::InsufficientLiquidityBurned
+        );
+        let amount_0 = Self::mul_div(liquidity, pool.reserve_0, total_supply)?;
+        let amount_1 = Self::mul_div(liquidity, pool.reserve_1, total_supply)?;
+        ensure!(
+            !amount_0.is_zero() && !amount_1.is_zero(),
+            Error::<T>::InsufficientLiquidityBurned
+        );
+        ensure!(
+            amount_0 >= amount_0_min && amount_1 >= amount_1_min,
+            Error::<T>::SlippageExceeded
+        );
+
+        xpallet_assets::Pallet::<T>::destroy_usable(&pool.lp_asset_id, who, liquidity)?;
+        Self::transfer_out(asset_0, who, amount_0)?;
+        Self::transfer_out(asset_1, who, amount_1)?;
+
+        pool.reserve_0 -= amount_0;
+        pool.reserve_1 -= amount_1;
+        Pools::<T>::insert((asset_0, asset_1), pool);
+
+        Ok((amount_0, amount_1))
+    }
+
+    fn apply_swap(
+        who: &T::AccountId,
+        asset_in: AssetId,
+        asset_out: AssetId,
+        amount_in: BalanceOf<T>,
+        amount_out_min: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let (mut pool, is_in_0) = Self::pool_of(asset_in, asset_out)?;
+        let (reserve_in, reserve_out) = if is_in_0 {
+            (pool.reserve_0, pool.reserve_1)
+        } else {
+            (pool.reserve_1, pool.reserve_0)
+        };
+
+        let amount_out = Self::get_amount_out(amount_in, reserve_in, reserve_out)?;
+        ensure!(!amount_out.is_zero(), Error::<T>::InsufficientLiquidity);
+        ensure!(amount_out >= amount_out_min, Error::<T>::SlippageExceeded);
+
+        Self::transfer_in(asset_in, who, amount_in)?;
+        Self::transfer_out(asset_out, who, amount_out)?;
+
+        if is_in_0 {
+            pool.reserve_0 += amount_in;
+            pool.reserve_1 -= amount_out;
+        } else {
+            pool.reserve_1 += amount_in;
+            pool.reserve_0 -= amount_out;
+        }
+        Pools::<T>::insert(Self::sort_assets(asset_in, asset_out), pool);
+
+        Ok(amount_out)
+    }
+
+    /// Returns the output amount keeping the product of the reserves, the fee excluded.
+    fn get_amount_out(
+        amount_in: BalanceOf<T>,
+        reserve_in: BalanceOf<T>,
+        reserve_out: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        ensure!(!amount_in.is_zero(), Error::<T>::ZeroAmount);
+        ensure!(
+            !reserve_in.is_zero() && !reserve_out.is_zero(),
+            Error::<T>::InsufficientLiquidity
+        );
+
+        let fee_denominator = U256::from(Permill::ACCURACY);
+        let amount_in_with_fee = Self::to_u256(amount_in)
+            * (fee_denominator - U256::from(T::SwapFee::get().deconstruct()));
+        let numerator = amount_in_with_fee * Self::to_u256(reserve_out);
+        let denominator = Self::to_u256(reserve_in) * fee_denominator + amount_in_with_fee;
+
+        Self::from_u256(numerator / denominator)
+    }
+
+    /// Returns `a * b / c` without the intermediate overflow, `c` must not be zero.
+    fn mul_div(
+        a: BalanceOf<T>,
+        b: BalanceOf<T>,
+        c: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        Self::from_u256(Self::to_u256(a) * Self::to_u256(b) / Self::to_u256(c))
+    }
+
+    fn to_u256(value: BalanceOf<T>) -> U256 {
+        U256::from(value.saturated_into::<u128>())
+    }
+
+    fn from_u256(value: U256) -> Result<BalanceOf<T>, DispatchError> {
+        ensure!(value <= U256::from(u128::MAX), Error::<T>::Overflow);
+        Ok(value.low_u128().saturated_into())
+    }
+
+    fn is_native_asset(asset_id: AssetId) -> bool {
+        asset_id == <T as xpallet_assets_registrar::Config>::NativeAssetId::get()
+    }
+
+    /// Move `value` of `asset_id` from `who` to the pool account.
+    fn transfer_in(asset_id: AssetId, who: &T::AccountId, value: BalanceOf<T>) -> DispatchResult {
+        if Self::is_native_asset(asset_id) {
+            <T as xpallet_assets::Config>::Currency::transfer(
+                who,
+                &Self::pool_account(),
+                value,
+                ExistenceRequirement::KeepAlive,
+            )
+        } else {
+            xpallet_assets::Pallet::<T>::move_usable_balance(
+                &asset_id,
+                who,
+                &Self::pool_account(),
+                value,
+            )
+            .map_err(|err| xpallet_assets::Error::<T>::from(err).into())
+        }
+    }
+
+    /// Move `value` of `asset_id` from the pool account to `who`.
+    fn transfer_out(asset_id: AssetId, who: &T::AccountId, value: BalanceOf<T>) -> DispatchResult {
+        if Self::is_native_asset(asset_id) {
+            <T as xpallet_assets::Config>::Currency::transfer(
+                &Self::pool_account(),
+                who,
+                value,
+                ExistenceRequirement::AllowDeath,
+            )
+        } else {
+            xpallet_assets::Pallet::<T>::move_usable_balance(
+                &asset_id,
+                &Self::pool_account(),
+                who,
+                value,
+            )
+            .map_err(|err| xpallet_assets::Error::<T>::from(err).into())
+        }
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::collections::BTreeMap;
+
+use frame_support::{
+    construct_runtime, ord_parameter_types, parameter_types,
+    traits::{ConstU32, ConstU64, GenesisBuild},
+    PalletId,
+};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Permill,
+};
+
+use chainx_primitives::AssetId;
+use xp_protocol::{BTC_DECIMALS, PCX, PCX_DECIMALS, X_BTC};
+use xpallet_assets::{AssetInfo, Chain};
+
+use crate::{self as xpallet_dex_swap};
+
+pub(crate) type AccountId = u64;
+pub(crate) type Balance = u128;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        XAssetsRegistrar: xpallet_assets_registrar::{Pallet, Call, Config, Storage, Event<T>},
+        XAssets: xpallet_assets::{Pallet, Call, Storage, Event<T>, Config<T>},
+        XSwap: xpallet_dex_swap::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Call = Call;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type Event = ();
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type ReserveIdentifier = [u8; 8];
+    type MaxReserves = ConstU32<50>;
+}
+
+parameter_types! {
+    pub const ChainXAssetId: AssetId = PCX;
+}
+
+impl xpallet_assets_registrar::Config for Test {
+    type Event = ();
+    type NativeAssetId = ChainXAssetId;
+    type RegistrarHandler = ();
+    type TechnicalOrigin = frame_system::EnsureRoot<AccountId>;
+    type WeightInfo = ();
+}
+
+pub(crate) const TREASURY: AccountId = 100;
+
+pub struct SimpleTreasuryAccount;
+impl xpallet_support::traits::TreasuryAccount<AccountId> for SimpleTreasuryAccount {
+    fn treasury_account() -> Option<AccountId> {
+        Some(TREASURY)
+    }
+}
+
+impl xpallet_assets::Config for Test {
+    type Event = ();
+    type Currency = Balances;
+    type TreasuryAccount = SimpleTreasuryAccount;
+    type OnCreatedAccount = frame_system::Provider<Test>;
+    type OnAssetChanged = ();
+    type RestrictionOrigin = frame_system::EnsureRoot<AccountId>;
+    type DustReapBounty = ();
+    type DustReapExempt = frame_support::traits::Nothing;
+    type WeightInfo = ();
+}
+
+ord_parameter_types! {
+    pub const Council: AccountId = COUNCIL;
+}
+
+parameter_types! {
+    pub const SwapPalletId: PalletId = PalletId(*b"pcx/swap");
+    pub const LpAssetIdBase: AssetId = LP_ASSET_ID_BASE;
+    pub const SwapFee: Permill = Permill::from_parts(3_000);
+    pub const MinimumLiquidity: Balance = 1_000;
+}
+
+impl xpallet_dex_swap::Config for Test {
+    type Event = ();
+    type CouncilOrigin = EnsureSignedBy<Council, AccountId>;
+    type PalletId = SwapPalletId;
+    type LpAssetIdBase = LpAssetIdBase;
+    type SwapFee = SwapFee;
+    type MinimumLiquidity = MinimumLiquidity;
+    type WeightInfo = ();
+}
+
+pub const COUNCIL: AccountId = 1;
+pub const ALICE: AccountId = 2;
+pub const BOB: AccountId = 3;
+
+pub const LP_ASSET_ID_BASE: AssetId = 0xb0000000;
+
+pub const INITIAL_BALANCE: Balance = 1_000_000_000_000;
+
+fn pcx() -> (AssetId, AssetInfo) {
+    (
+        PCX,
+        AssetInfo::new::<Test>(
+            b"PCX".to_vec(),
+            b"Polkadot ChainX".to_vec(),
+            Chain::ChainX,
+            PCX_DECIMALS,
+            b"ChainX's crypto currency in Polkadot ecology".to_vec(),
+        )
+        .unwrap(),
+    )
+}
+
+fn btc() -> (AssetId, AssetInfo) {
+    (
+        X_BTC,
+        AssetInfo::new::<Test>(
+            b"X-BTC".to_vec(),
+            b"X-BTC".to_vec(),
+            Chain::Bitcoin,
+            BTC_DECIMALS,
+            b"ChainX's cross-chain Bitcoin".to_vec(),
+        )
+        .unwrap(),
+    )
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let (pcx_id, pcx_info) = pcx();
+    let (btc_id, btc_info) = btc();
+    GenesisBuild::<Test>::assimilate_storage(
+        &xpallet_assets_registrar::GenesisConfig {
+            assets: vec![
+                (pcx_id, pcx_info, true, false),
+                (btc_id, btc_info, true, false),
+            ],
+        },
+        &mut t,
+    )
+    .unwrap();
+
+    let mut endowed = BTreeMap::new();
+    endowed.insert(
+        X_BTC,
+        vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)],
+    );
+    xpallet_assets::GenesisConfig::<Test> {
+        assets_restrictions: vec![],
+        endowed,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
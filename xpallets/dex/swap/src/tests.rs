@@ -0,0 +1,207 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+use xp_protocol::{PCX, X_BTC};
+
+use crate::mock::*;
+use crate::{Error, PoolInfo};
+
+fn create_pcx_btc_pool() {
+    assert_ok!(XSwap::create_pool(Origin::signed(COUNCIL), X_BTC, PCX));
+}
+
+#[test]
+fn create_pool_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            XSwap::create_pool(Origin::signed(ALICE), PCX, X_BTC),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XSwap::create_pool(Origin::signed(COUNCIL), PCX, PCX),
+            Error::<Test>::IdenticalAssets
+        );
+        assert_noop!(
+            XSwap::create_pool(Origin::signed(COUNCIL), PCX, 9),
+            Error::<Test>::InvalidAsset
+        );
+
+        create_pcx_btc_pool();
+        assert_eq!(
+            XSwap::pools((PCX, X_BTC)),
+            Some(PoolInfo {
+                lp_asset_id: LP_ASSET_ID_BASE,
+                reserve_0: 0,
+                reserve_1: 0,
+            })
+        );
+        assert_eq!(XSwap::pool_count(), 1);
+
+        let lp_info = XAssetsRegistrar::asset_info_of(LP_ASSET_ID_BASE).unwrap();
+        assert_eq!(lp_info.token(), b"LP-0");
+        assert!(XAssetsRegistrar::is_valid(&LP_ASSET_ID_BASE));
+
+        assert_noop!(
+            XSwap::create_pool(Origin::root(), PCX, X_BTC),
+            Error::<Test>::PoolAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn add_and_remove_liquidity_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            XSwap::add_liquidity(Origin::signed(ALICE), PCX, X_BTC, 1, 1, 0, 0),
+            Error::<Test>::PoolNotFound
+        );
+        create_pcx_btc_pool();
+
+        // The first liquidity sets the price, the minimum liquidity is locked in the pool.
+        assert_noop!(
+            XSwap::add_liquidity(Origin::signed(ALICE), PCX, X_BTC, 10, 10, 0, 0),
+            Error::<Test>::InsufficientLiquidityMinted
+        );
+        assert_ok!(XSwap::add_liquidity(
+            Origin::signed(ALICE),
+            PCX,
+            X_BTC,
+            1_000_000,
+            4_000_000,
+            0,
+            0
+        ));
+        let pool_account = XSwap::pool_account();
+        assert_eq!(
+            XAssets::usable_balance(&ALICE, &LP_ASSET_ID_BASE),
+            1_999_000
+        );
+        assert_eq!(
+            XAssets::usable_balance(&pool_account, &LP_ASSET_ID_BASE),
+            1_000
+        );
+        assert_eq!(Balances::free_balance(&pool_account), 1_000_000);
+        assert_eq!(XAssets::usable_balance(&pool_account, &X_BTC), 4_000_000);
+
+        // The following liquidity is added at the current price.
+        assert_noop!(
+            XSwap::add_liquidity(
+                Origin::signed(BOB),
+                X_BTC,
+                PCX,
+                2_000_000,
+                1_000_000,
+                0,
+                500_001
+            ),
+            Error::<Test>::SlippageExceeded
+        );
+        assert_ok!(XSwap::add_liquidity(
+            Origin::signed(BOB),
+            X_BTC,
+            PCX,
+            2_000_000,
+            1_000_000,
+            0,
+            0
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB, &LP_ASSET_ID_BASE), 1_000_000);
+        assert_eq!(Balances::free_balance(&BOB), INITIAL_BALANCE - 500_000);
+        assert_eq!(
+            XAssets::usable_balance(&BOB, &X_BTC),
+            INITIAL_BALANCE - 2_000_000
+        );
+        let pool = XSwap::pools((PCX, X_BTC)).unwrap();
+        assert_eq!((pool.reserve_0, pool.reserve_1), (1_500_000, 6_000_000));
+
+        // Withdraw the share of the reserves.
+        assert_noop!(
+            XSwap::remove_liquidity(Origin::signed(BOB), PCX, X_BTC, 1_000_000, 500_001, 0),
+            Error::<Test>::SlippageExceeded
+        );
+        assert_ok!(XSwap::remove_liquidity(
+            Origin::signed(BOB),
+            PCX,
+            X_BTC,
+            1_000_000,
+            500_000,
+            2_000_000
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB, &LP_ASSET_ID_BASE), 0);
+        assert_eq!(Balances::free_balance(&BOB), INITIAL_BALANCE);
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), INITIAL_BALANCE);
+        let pool = XSwap::pools((PCX, X_BTC)).unwrap();
+        assert_eq!((pool.reserve_0, pool.reserve_1), (1_000_000, 4_000_000));
+        assert_eq!(XAssets::total_issuance(&LP_ASSET_ID_BASE), 2_000_000);
+    });
+}
+
+#[test]
+fn swap_should_work() {
+    new_test_ext().execute_with(|| {
+        create_pcx_btc_pool();
+        assert_noop!(
+            XSwap::swap(Origin::signed(BOB), PCX, X_BTC, 10_000, 0),
+            Error::<Test>::InsufficientLiquidity
+        );
+        assert_ok!(XSwap::add_liquidity(
+            Origin::signed(ALICE),
+            PCX,
+            X_BTC,
+            1_000_000,
+            4_000_000,
+            0,
+            0
+        ));
+
+        assert_noop!(
+            XSwap::swap(Origin::signed(BOB), PCX, X_BTC, 0, 0),
+            Error::<Test>::ZeroAmount
+        );
+        assert_noop!(
+            XSwap::swap(Origin::signed(BOB), PCX, PCX, 10_000, 0),
+            Error::<Test>::IdenticalAssets
+        );
+
+        // 10_000 * 0.997 * 4_000_000 / (1_000_000 + 10_000 * 0.997)
+        assert_eq!(XSwap::quote(PCX, X_BTC, 10_000), Some(39_486));
+        assert_noop!(
+            XSwap::swap(Origin::signed(BOB), PCX, X_BTC, 10_000, 39_487),
+            Error::<Test>::SlippageExceeded
+        );
+        assert_ok!(XSwap::swap(Origin::signed(BOB), PCX, X_BTC, 10_000, 39_486));
+        assert_eq!(Balances::free_balance(&BOB), INITIAL_BALANCE - 10_000);
+        assert_eq!(
+            XAssets::usable_balance(&BOB, &X_BTC),
+            INITIAL_BALANCE + 39_486
+        );
+        let pool = XSwap::pools((PCX, X_BTC)).unwrap();
+        assert_eq!(
+            (pool.reserve_0, pool.reserve_1),
+            (1_010_000, 4_000_000 - 39_486)
+        );
+
+        // Swap back in the other direction.
+        let amount_out = XSwap::quote(X_BTC, PCX, 39_486).unwrap();
+        assert!(amount_out < 10_000);
+        assert_ok!(XSwap::swap(
+            Origin::signed(BOB),
+            X_BTC,
+            PCX,
+            39_486,
+            amount_out
+        ));
+        assert_eq!(
+            Balances::free_balance(&BOB),
+            INITIAL_BALANCE - 10_000 + amount_out
+        );
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), INITIAL_BALANCE);
+        let pool = XSwap::pools((PCX, X_BTC)).unwrap();
+        assert_eq!(
+            (pool.reserve_0, pool.reserve_1),
+            (1_010_000 - amount_out, 4_000_000)
+        );
+    });
+}
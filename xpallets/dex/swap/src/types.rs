@@ -0,0 +1,20 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! This module defines all the types used in Swap Module.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+use chainx_primitives::AssetId;
+
+/// A constant-product pool of the asset pair in ascending order, i.e., `asset_0 < asset_1`.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct PoolInfo<Balance> {
+    /// The asset id of the LP token issued to the liquidity providers.
+    pub lp_asset_id: AssetId,
+    /// The reserve of `asset_0` held by the pool account.
+    pub reserve_0: Balance,
+    /// The reserve of `asset_1` held by the pool account.
+    pub reserve_1: Balance,
+}
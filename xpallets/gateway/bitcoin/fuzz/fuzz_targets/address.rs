@@ -0,0 +1,7 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| xpallet_gateway_bitcoin_fuzz::address(data));
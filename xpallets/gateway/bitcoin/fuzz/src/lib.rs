@@ -0,0 +1,168 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Fuzzing harnesses of the BTC transaction and script verification used by
+//! `xpallet-gateway-bitcoin`.
+//!
+//! Each harness is deterministic and shared by the `cargo fuzz` target of the same name in
+//! `fuzz_targets/` and the regression tests below, which replay every input saved in
+//! `regressions/<target>/`.
+
+use light_bitcoin::{
+    chain::Transaction,
+    keys::{Address, Network},
+    merkle::PartialMerkleTree,
+    primitives::H256,
+    script::Script,
+    serialization::{deserialize, Reader},
+};
+use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormatRegistry};
+
+use xp_gateway_bitcoin::{
+    extract_opreturn_data, extract_output_addr, match_withdrawal_outputs, verify_merkle_proof,
+    AccountExtractor, BtcTxTypeDetector, OpReturnExtractor,
+};
+
+const NETWORK: Network = Network::Mainnet;
+const WITHDRAWAL_FEE: u64 = 1_000;
+
+/// Parses the input as an OP_RETURN script and as a deposit transaction.
+pub fn opreturn(data: &[u8]) {
+    set_default_ss58_version(Ss58AddressFormatRegistry::ChainxAccount.into());
+
+    if let Some(opreturn) = extract_opreturn_data(&Script::from(data.to_vec())) {
+        assert!(opreturn.len() < data.len());
+        let _ = OpReturnExtractor::extract_account(&opreturn);
+    }
+
+    if let Ok(tx) = deserialize::<_, Transaction>(Reader::new(data)) {
+        let detector = BtcTxTypeDetector::new(NETWORK, 0);
+        let trustee_pair = (Address::default(), Address::default());
+        let (_, deposit_value) = detector.parse_deposit_transaction_outputs(
+            &tx,
+            OpReturnExtractor::extract_account,
+            trustee_pair,
+        );
+        let total_value = tx
+            .outputs
+            .iter()
+            .fold(0u64, |total, output| total.saturating_add(output.value));
+        assert!(deposit_value <= total_value);
+    }
+}
+
+/// Parses the input as `merkle_root ++ tx_hash ++ partial merkle tree` and verifies the proof.
+pub fn merkle_proof(data: &[u8]) {
+    if data.len() < 64 {
+        return;
+    }
+    let (hashes, proof) = data.split_at(64);
+    let proof = match deserialize::<_, PartialMerkleTree>(Reader::new(proof)) {
+        Ok(proof) => proof,
+        Err(_) => return,
+    };
+    let merkle_root = H256::from_slice(&hashes[..32]);
+    let tx_hash = H256::from_slice(&hashes[32..]);
+    let _ = verify_merkle_proof(&proof, merkle_root, tx_hash);
+
+    // a well-formed proof must prove every matched tx against its own root.
+    let mut matches = Vec::new();
+    let mut indexes = Vec::new();
+    if let Ok(root) = proof.extract_matches(&mut matches, &mut indexes) {
+        assert_eq!(matches.len(), indexes.len());
+        for tx_hash in matches {
+            assert!(verify_merkle_proof(&proof, root, tx_hash));
+        }
+    }
+}
+
+/// Parses the input as a base58 or bech32 address, the same as `verify_btc_address`.
+pub fn address(data: &[u8]) {
+    let addr = bs58::decode(data)
+        .into_vec()
+        .ok()
+        .and_then(|layout| Address::from_layout(&layout).ok())
+        .or_else(|| {
+            core::str::from_utf8(data)
+                .ok()
+                .and_then(|addr| addr.parse::<Address>().ok())
+        });
+
+    // the trustee addresses are stored as strings and expected to be parsed back.
+    if let Some(addr) = addr {
+        let parsed = addr.to_string().parse::<Address>().ok();
+        assert_eq!(parsed.map(|parsed| parsed.hash), Some(addr.hash));
+    }
+}
+
+/// Parses the input as a withdrawal transaction and matches it against the withdrawal
+/// applications paid by its own outputs.
+pub fn withdrawal_outputs(data: &[u8]) {
+    let tx = match deserialize::<_, Transaction>(Reader::new(data)) {
+        Ok(tx) => tx,
+        Err(_) => return,
+    };
+    let hot_addr = Address::default();
+    let check = |applications| {
+        match_withdrawal_outputs(&tx, applications, &hot_addr, NETWORK, WITHDRAWAL_FEE)
+    };
+
+    let mut applications = Vec::new();
+    for output in &tx.outputs {
+        match extract_output_addr(output, NETWORK) {
+            Some(addr) if addr.hash != hot_addr.hash => {
+                applications.push((addr, output.value.saturating_add(WITHDRAWAL_FEE)))
+            }
+            Some(_) => {}
+            None => {
+                assert!(check(applications).is_err());
+                return;
+            }
+        }
+    }
+
+    assert_eq!(check(applications.clone()), Ok(true));
+    if let Some((addr, balance)) = applications.first().copied() {
+        let mut extra = applications.clone();
+        extra.push((addr, balance));
+        assert_eq!(check(extra), Ok(false));
+
+        applications[0].1 = balance.wrapping_add(1);
+        assert_eq!(check(applications), Ok(false));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    fn replay(target: &str, harness: fn(&[u8])) {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("regressions")
+            .join(target);
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            println!("replay {}", path.display());
+            harness(&fs::read(&path).unwrap());
+        }
+    }
+
+    #[test]
+    fn opreturn_regressions() {
+        replay("opreturn", super::opreturn);
+    }
+
+    #[test]
+    fn merkle_proof_regressions() {
+        replay("merkle_proof", super::merkle_proof);
+    }
+
+    #[test]
+    fn address_regressions() {
+        replay("address", super::address);
+    }
+
+    #[test]
+    fn withdrawal_outputs_regressions() {
+        replay("withdrawal_outputs", super::withdrawal_outputs);
+    }
+}
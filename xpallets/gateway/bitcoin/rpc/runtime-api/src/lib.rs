@@ -6,7 +6,10 @@
 
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
-pub use xpallet_gateway_bitcoin::{types::BtcHeaderInfo, BtcHeader, BtcWithdrawalProposal, H256};
+pub use xpallet_gateway_bitcoin::{
+    types::{BtcGatewayMetrics, BtcGatewayParams, BtcHeaderIndex, BtcHeaderInfo, BtcTxInclusion},
+    BtcHeader, BtcWithdrawalProposal, H256,
+};
 
 sp_api::decl_runtime_apis! {
     pub trait XGatewayBitcoinApi<AccountId>
@@ -18,10 +21,29 @@ sp_api::decl_runtime_apis! {
             full_amount: bool,
         ) -> Result<bool, DispatchError>;
 
+        fn verify_tx_inclusion(
+            raw_tx: Vec<u8>,
+            merkle_proof: Vec<u8>,
+            block_hash: H256,
+        ) -> BtcTxInclusion;
+
         fn get_withdrawal_proposal() -> Option<BtcWithdrawalProposal<AccountId>>;
 
         fn get_genesis_info() -> (BtcHeader, u32);
 
         fn get_btc_block_header(txid: H256) -> Option<BtcHeaderInfo>;
+
+        fn get_best_index() -> BtcHeaderIndex;
+
+        fn get_gateway_metrics() -> BtcGatewayMetrics;
+
+        fn get_gateway_params() -> BtcGatewayParams;
+
+        /// Get the txid and the raw tx of the fully signed withdrawal proposal not reported as
+        /// broadcasted.
+        fn get_unreported_withdrawal_tx() -> Option<(H256, Vec<u8>)>;
+
+        /// Build the unsigned extrinsic reporting the broadcasted withdrawal tx `txid`.
+        fn withdrawal_broadcast_report(txid: H256) -> Block::Extrinsic;
     }
 }
@@ -0,0 +1,327 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Mirror of the relayed bitcoin headers in the client aux storage.
+//!
+//! The bitcoin headers relayed by `XGatewayBitcoin` live in the runtime state, which is
+//! dropped once the state of a block is pruned. When the archive is enabled, the main chain
+//! headers are copied into the aux storage of the client as soon as the block relaying them
+//! is finalized, so that a pruned node can still serve `xgatewaybitcoin_getBtcBlockHeader`.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use futures::StreamExt;
+
+use sc_client_api::{AuxStore, BlockchainEvents};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::{HeaderBackend, Result as ClientResult};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xpallet_gateway_bitcoin_rpc_runtime_api::{
+    BtcHeaderInfo, XGatewayBitcoinApi as XGatewayBitcoinRuntimeApi, H256,
+};
+
+const LOG_TARGET: &str = "btc-header-archive";
+
+/// Aux storage key prefix of the archived bitcoin headers.
+const HEADER_PREFIX: &[u8] = b"xgatewaybitcoin:header:";
+/// Aux storage key of the best archived bitcoin header hash.
+const BEST_HEADER_KEY: &[u8] = b"xgatewaybitcoin:best";
+
+fn header_key(hash: &H256) -> Vec<u8> {
+    [HEADER_PREFIX, hash.as_ref()].concat()
+}
+
+fn load_decode<C: AuxStore, T: Decode>(client: &C, key: &[u8]) -> ClientResult<Option<T>> {
+    match client.get_aux(key)? {
+        Some(raw) => T::decode(&mut &raw[..]).map(Some).map_err(|e| {
+            sp_blockchain::Error::Backend(format!("BTC header archive is corrupted: {}", e))
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Returns the archived bitcoin header of the given hash.
+pub fn load_header<C: AuxStore>(client: &C, hash: &H256) -> ClientResult<Option<BtcHeaderInfo>> {
+    load_decode(client, &header_key(hash))
+}
+
+/// Returns the hash of the best archived bitcoin header.
+pub fn load_best_header<C: AuxStore>(client: &C) -> ClientResult<Option<H256>> {
+    load_decode(client, BEST_HEADER_KEY)
+}
+
+/// Archives all the bitcoin main chain headers up to the best header at block `at` which
+/// are not archived yet, returns the number of the newly archived headers.
+pub fn archive_headers<Block, C, AccountId>(
+    client: &C,
+    at: &BlockId<Block>,
+) -> Result<usize, String>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + AuxStore,
+    C::Api: XGatewayBitcoinRuntimeApi<Block, AccountId>,
+    AccountId: codec::Codec,
+{
+    let api = client.runtime_api();
+    let best = api.get_best_index(at).map_err(|e| format!("{:?}", e))?;
+    let archived_best = load_best_header(client).map_err(|e| format!("{:?}", e))?;
+    if archived_best == Some(best.hash) {
+        return Ok(0);
+    }
+
+    let mut headers = Vec::new();
+    let mut hash = best.hash;
+    while Some(hash) != archived_best
+        && load_header(client, &hash)
+            .map_err(|e| format!("{:?}", e))?
+            .is_none()
+    {
+        // Stop at the relay genesis, its parent is not relayed.
+        let info = match api
+            .get_btc_block_header(at, hash)
+            .map_err(|e| format!("{:?}", e))?
+        {
+            Some(info) => info,
+            None => break,
+        };
+        let parent = info.header.previous_header_hash;
+        headers.push((header_key(&hash), info.encode()));
+        hash = parent;
+    }
+
+    let count = headers.len();
+    let best_hash = best.hash.encode();
+    let insert = headers
+        .iter()
+        .map(|(k, v)| (&k[..], &v[..]))
+        .chain(std::iter::once((BEST_HEADER_KEY, &best_hash[..])))
+        .collect::<Vec<_>>();
+    client
+        .insert_aux(&insert, &[])
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(count)
+}
+
+/// Archives the relayed bitcoin headers on every finalized block.
+pub async fn run_header_archive_worker<Block, C, AccountId>(client: Arc<C>)
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + BlockchainEvents<Block> + HeaderBackend<Block> + AuxStore,
+    C::Api: XGatewayBitcoinRuntimeApi<Block, AccountId>,
+    AccountId: codec::Codec,
+{
+    // Catch up with the finalized block before following the finality notifications.
+    let finalized = BlockId::hash(client.info().finalized_hash);
+    if let Err(e) = archive_headers::<Block, C, AccountId>(&*client, &finalized) {
+        log::warn!(target: LOG_TARGET, "Failed to archive BTC headers: {}", e);
+    }
+
+    let mut notifications = client.finality_notification_stream();
+    while let Some(notification) = notifications.next().await {
+        let at = BlockId::hash(notification.hash);
+        match archive_headers::<Block, C, AccountId>(&*client, &at) {
+            Ok(0) => {}
+            Ok(count) => log::debug!(
+                target: LOG_TARGET,
+                "Archived {} BTC headers at {:?}",
+                count,
+                notification.hash
+            ),
+            Err(e) => log::warn!(target: LOG_TARGET, "Failed to archive BTC headers: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use sp_api::ApiRef;
+    use sp_runtime::{
+        testing::{Block as TestBlock, ExtrinsicWrapper},
+        DispatchError,
+    };
+
+    use xpallet_gateway_bitcoin_rpc_runtime_api::{
+        BtcGatewayMetrics, BtcGatewayParams, BtcHeader, BtcHeaderIndex, BtcTxInclusion,
+        BtcWithdrawalProposal,
+    };
+
+    type Block = TestBlock<ExtrinsicWrapper<u64>>;
+    type AccountId = u64;
+
+    /// The relayed headers in the runtime state.
+    #[derive(Clone, Default)]
+    struct TestRuntimeApi {
+        headers: HashMap<H256, BtcHeaderInfo>,
+        best: BtcHeaderIndex,
+    }
+
+    sp_api::mock_impl_runtime_apis! {
+        impl XGatewayBitcoinRuntimeApi<Block, AccountId> for TestRuntimeApi {
+            fn verify_tx_valid(
+                _raw_tx: Vec<u8>,
+                _withdrawal_id_list: Vec<u32>,
+                _full_amount: bool,
+            ) -> Result<bool, DispatchError> {
+                unimplemented!()
+            }
+
+            fn verify_tx_inclusion(
+                _raw_tx: Vec<u8>,
+                _merkle_proof: Vec<u8>,
+                _block_hash: H256,
+            ) -> BtcTxInclusion {
+                unimplemented!()
+            }
+
+            fn get_withdrawal_proposal() -> Option<BtcWithdrawalProposal<AccountId>> {
+                unimplemented!()
+            }
+
+            fn get_genesis_info() -> (BtcHeader, u32) {
+                unimplemented!()
+            }
+
+            fn get_btc_block_header(&self, txid: H256) -> Option<BtcHeaderInfo> {
+                self.headers.get(&txid).cloned()
+            }
+
+            fn get_best_index(&self) -> BtcHeaderIndex {
+                self.best
+            }
+
+            fn get_gateway_metrics() -> BtcGatewayMetrics {
+                unimplemented!()
+            }
+
+            fn get_gateway_params() -> BtcGatewayParams {
+                unimplemented!()
+            }
+
+            fn get_unreported_withdrawal_tx() -> Option<(H256, Vec<u8>)> {
+                unimplemented!()
+            }
+
+            fn withdrawal_broadcast_report(_txid: H256) -> <Block as BlockT>::Extrinsic {
+                unimplemented!()
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct TestClient {
+        api: Mutex<TestRuntimeApi>,
+        aux: Mutex<HashMap<Vec<u8>, Vec<u8>>>,
+    }
+
+    impl TestClient {
+        /// Relays `count` headers on top of the best one.
+        fn relay(&self, count: u32) -> Vec<BtcHeaderInfo> {
+            let mut api = self.api.lock().unwrap();
+            (0..count)
+                .map(|_| {
+                    let height = api.best.height + 1;
+                    let header = BtcHeader {
+                        previous_header_hash: api.best.hash,
+                        time: height,
+                        ..Default::default()
+                    };
+                    let hash = header.hash();
+                    let info = BtcHeaderInfo { header, height };
+                    api.headers.insert(hash, info.clone());
+                    api.best = BtcHeaderIndex { hash, height };
+                    info
+                })
+                .collect()
+        }
+
+        /// Drops the relayed headers from the runtime state, as the state pruning does.
+        fn prune(&self) {
+            self.api.lock().unwrap().headers.clear();
+        }
+
+        fn best_hash(&self) -> H256 {
+            self.api.lock().unwrap().best.hash
+        }
+    }
+
+    impl ProvideRuntimeApi<Block> for TestClient {
+        type Api = TestRuntimeApi;
+
+        fn runtime_api(&self) -> ApiRef<'_, Self::Api> {
+            self.api.lock().unwrap().clone().into()
+        }
+    }
+
+    impl AuxStore for TestClient {
+        fn insert_aux<
+            'a,
+            'b: 'a,
+            'c: 'a,
+            I: IntoIterator<Item = &'a (&'c [u8], &'c [u8])>,
+            D: IntoIterator<Item = &'a &'b [u8]>,
+        >(
+            &self,
+            insert: I,
+            delete: D,
+        ) -> ClientResult<()> {
+            let mut aux = self.aux.lock().unwrap();
+            for (key, value) in insert {
+                aux.insert(key.to_vec(), value.to_vec());
+            }
+            for key in delete {
+                aux.remove(*key);
+            }
+            Ok(())
+        }
+
+        fn get_aux(&self, key: &[u8]) -> ClientResult<Option<Vec<u8>>> {
+            Ok(self.aux.lock().unwrap().get(key).cloned())
+        }
+    }
+
+    fn archive(client: &TestClient) -> Result<usize, String> {
+        archive_headers::<Block, _, AccountId>(client, &BlockId::Number(0))
+    }
+
+    #[test]
+    fn archived_headers_should_be_read_back() {
+        let client = TestClient::default();
+        assert_eq!(archive(&client), Ok(0));
+        assert_eq!(load_best_header(&client).unwrap(), Some(H256::default()));
+
+        let mut relayed = client.relay(3);
+        assert_eq!(archive(&client), Ok(3));
+        assert_eq!(archive(&client), Ok(0));
+
+        // Only the headers relayed since the last archive are archived.
+        relayed.extend(client.relay(2));
+        assert_eq!(archive(&client), Ok(2));
+        assert_eq!(load_best_header(&client).unwrap(), Some(client.best_hash()));
+
+        // The archived headers outlive the pruned state.
+        client.prune();
+        for info in relayed {
+            assert_eq!(
+                load_header(&client, &info.header.hash()).unwrap(),
+                Some(info)
+            );
+        }
+        assert_eq!(load_header(&client, &H256::repeat_byte(1)).unwrap(), None);
+    }
+
+    #[test]
+    fn corrupted_archive_should_be_reported() {
+        let client = TestClient::default();
+        let hash = client.relay(1)[0].header.hash();
+        client
+            .insert_aux(&[(&header_key(&hash)[..], &b"corrupted"[..])], &[])
+            .unwrap();
+        assert!(load_header(&client, &hash).is_err());
+    }
+}
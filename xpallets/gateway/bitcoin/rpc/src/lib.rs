@@ -1,18 +1,23 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
 //! RPC interface for the transaction verification.
+
+pub mod archive;
+pub mod metrics;
+
 use codec::Codec;
 use jsonrpc_derive::rpc;
 use std::sync::Arc;
 use std::vec::Vec;
 
+use sc_client_api::AuxStore;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
 use xp_rpc::{runtime_error_into_rpc_err, Result};
 use xpallet_gateway_bitcoin_rpc_runtime_api::{
-    BtcHeader, BtcHeaderInfo, BtcWithdrawalProposal,
+    BtcHeader, BtcHeaderInfo, BtcTxInclusion, BtcWithdrawalProposal,
     XGatewayBitcoinApi as XGatewayBitcoinRuntimeApi, H256,
 };
 
@@ -43,6 +48,19 @@ pub trait XGatewayBitcoinApi<BlockHash, AccountId> {
         at: Option<BlockHash>,
     ) -> Result<bool>;
 
+    /// Check whether the transaction is included in the block by the merkle proof and the
+    /// block is confirmed by the relay, so that `push_transaction` would accept it.
+    ///
+    /// `raw_tx` and `merkle_proof` are hex encoded in the bitcoin serialization.
+    #[rpc(name = "xgatewaybitcoin_verifyTxInclusion")]
+    fn verify_tx_inclusion(
+        &self,
+        raw_tx: String,
+        merkle_proof: String,
+        block_hash: H256,
+        at: Option<BlockHash>,
+    ) -> Result<BtcTxInclusion>;
+
     /// Get withdrawal proposal
     #[rpc(name = "xgatewaybitcoin_getWithdrawalProposal")]
     fn get_withdrawal_proposal(
@@ -55,6 +73,8 @@ pub trait XGatewayBitcoinApi<BlockHash, AccountId> {
     fn get_genesis_info(&self, at: Option<BlockHash>) -> Result<(BtcHeader, u32)>;
 
     /// Get block header
+    ///
+    /// Falls back to the header archive if the state of `at` has been pruned.
     #[rpc(name = "xgatewaybitcoin_getBtcBlockHeader")]
     fn get_btc_block_header(
         &self,
@@ -67,7 +87,7 @@ impl<C, Block, AccountId> XGatewayBitcoinApi<<Block as BlockT>::Hash, AccountId>
     for XGatewayBitcoin<C, Block, AccountId>
 where
     Block: BlockT,
-    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
     C::Api: XGatewayBitcoinRuntimeApi<Block, AccountId>,
     AccountId: Codec + Send + Sync + 'static,
 {
@@ -88,6 +108,23 @@ where
         Ok(result)
     }
 
+    fn verify_tx_inclusion(
+        &self,
+        raw_tx: String,
+        merkle_proof: String,
+        block_hash: H256,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BtcTxInclusion> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let raw_tx = hex::decode(raw_tx).map_err(runtime_error_into_rpc_err)?;
+        let merkle_proof = hex::decode(merkle_proof).map_err(runtime_error_into_rpc_err)?;
+        let result = api
+            .verify_tx_inclusion(&at, raw_tx, merkle_proof, block_hash)
+            .map_err(runtime_error_into_rpc_err)?;
+        Ok(result)
+    }
+
     fn get_withdrawal_proposal(
         &self,
         at: Option<<Block as BlockT>::Hash>,
@@ -116,9 +153,17 @@ where
     ) -> Result<Option<BtcHeaderInfo>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
-        let reslut = api
-            .get_btc_block_header(&at, txid)
-            .map_err(runtime_error_into_rpc_err)?;
-        Ok(reslut)
+        match api.get_btc_block_header(&at, txid) {
+            Ok(Some(header)) => Ok(Some(header)),
+            Ok(None) => {
+                archive::load_header(&*self.client, &txid).map_err(runtime_error_into_rpc_err)
+            }
+            Err(e) => archive::load_header(&*self.client, &txid)
+                .map_err(runtime_error_into_rpc_err)?
+                .map_or_else(
+                    || Err(runtime_error_into_rpc_err(e)),
+                    |header| Ok(Some(header)),
+                ),
+        }
     }
 }
@@ -0,0 +1,104 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Prometheus metrics of the bitcoin gateway.
+//!
+//! The gauges are refreshed from the runtime state of each imported best block.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+
+use sc_client_api::BlockchainEvents;
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use substrate_prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+
+use xpallet_gateway_bitcoin_rpc_runtime_api::{
+    BtcGatewayMetrics, XGatewayBitcoinApi as XGatewayBitcoinRuntimeApi,
+};
+
+const LOG_TARGET: &str = "btc-gateway-metrics";
+
+/// The Prometheus gauges of the bitcoin gateway.
+#[derive(Clone)]
+pub struct GatewayMetrics {
+    relay_best_height: Gauge<U64>,
+    confirmed_height: Gauge<U64>,
+    pending_withdrawal_count: Gauge<U64>,
+    hot_address_balance_sats: Gauge<U64>,
+}
+
+impl GatewayMetrics {
+    /// Registers the gauges into `registry`.
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            relay_best_height: register(
+                Gauge::new(
+                    "btc_relay_best_height",
+                    "Height of the best relayed bitcoin header",
+                )?,
+                registry,
+            )?,
+            confirmed_height: register(
+                Gauge::new(
+                    "btc_confirmed_height",
+                    "Height of the latest confirmed bitcoin header",
+                )?,
+                registry,
+            )?,
+            pending_withdrawal_count: register(
+                Gauge::new(
+                    "btc_pending_withdrawal_count",
+                    "Number of the pending bitcoin withdrawals",
+                )?,
+                registry,
+            )?,
+            hot_address_balance_sats: register(
+                Gauge::new(
+                    "btc_hot_address_balance_sats",
+                    "Balance of the trustee hot address accounted on chain in satoshis",
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    fn update(&self, metrics: &BtcGatewayMetrics) {
+        self.relay_best_height.set(metrics.best_height.into());
+        self.confirmed_height.set(metrics.confirmed_height.into());
+        self.pending_withdrawal_count
+            .set(metrics.pending_withdrawal_count.into());
+        self.hot_address_balance_sats
+            .set(metrics.hot_address_balance);
+    }
+}
+
+/// Refreshes the gateway metrics on every imported best block.
+pub async fn run_gateway_metrics_worker<Block, C, AccountId>(
+    client: Arc<C>,
+    metrics: GatewayMetrics,
+) where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + BlockchainEvents<Block>,
+    C::Api: XGatewayBitcoinRuntimeApi<Block, AccountId>,
+    AccountId: codec::Codec,
+{
+    let mut notifications = client.import_notification_stream();
+    while let Some(notification) = notifications.next().await {
+        if !notification.is_new_best {
+            continue;
+        }
+        match client
+            .runtime_api()
+            .get_gateway_metrics(&BlockId::hash(notification.hash))
+        {
+            Ok(status) => metrics.update(&status),
+            Err(e) => log::debug!(
+                target: LOG_TARGET,
+                "Failed to read the BTC gateway metrics at {:?}: {:?}",
+                notification.hash,
+                e
+            ),
+        }
+    }
+}
@@ -3,13 +3,13 @@
 use codec::{Decode, Encode};
 use frame_benchmarking::{benchmarks, whitelisted_caller};
 use frame_system::RawOrigin;
-use sp_runtime::AccountId32;
+use sp_runtime::{AccountId32, Permill};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use xp_gateway_bitcoin::{BtcTxType, OpReturnAccount};
 use xp_protocol::X_BTC;
-use xpallet_assets::BalanceOf;
-use xpallet_gateway_records::{Pallet as XGatewayRecords, WithdrawalState};
+use xpallet_assets::{BalanceOf, Chain};
+use xpallet_gateway_records::{Pallet as XGatewayRecords, WithdrawalState, WithdrawalStatus};
 
 use light_bitcoin::{
     chain::{BlockHeader, Transaction},
@@ -18,7 +18,10 @@ use light_bitcoin::{
     serialization::{self, Reader, SERIALIZE_TRANSACTION_WITNESS},
 };
 
-use crate::{types::*, Call, Config, Pallet, PendingDeposits, TxState, WithdrawalProposal};
+use crate::{
+    types::*, Call, Config, ConfirmationNumber, DeferredDeposits, LastAttestedBtcHeight, Pallet,
+    PendingDeposits, PendingWalletAttestations, TxState, WalletAttestations, WithdrawalProposal,
+};
 
 fn generate_blocks_63290_63310() -> BTreeMap<u32, BlockHeader> {
     let bytes = include_bytes!("./res/headers-63290-63310.raw");
@@ -153,7 +156,7 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(caller), vec![0], tx_raw)
     verify {
-        assert_eq!(WithdrawalProposal::<T>::get().unwrap().sig_state, VoteResult::Finish);
+        assert_eq!(WithdrawalProposal::<T>::get().unwrap().sig_state, VoteResult::Unfinish);
     }
 
     set_best_index {
@@ -200,6 +203,22 @@ benchmarks! {
         // assert_eq!(XAssets::<T>::usable_balance(&receiver, &AssetId::default()), (100000000u32 + 200000000u32 + 300000000u32).into());
     }
 
+    claim_pending_deposits {
+        let n in 1 .. 100;
+        let addr = b"3AWmpzJ1kSF1cktFTDEb3qmLcdN8YydxA7".to_vec();
+        let v = (0..n)
+            .map(|i| BtcDepositCache {
+                txid: H256::repeat_byte(i as u8),
+                balance: 100000000,
+            })
+            .collect::<Vec<_>>();
+        PendingDeposits::<T>::insert(&addr, v);
+        let receiver: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Root, addr.clone(), OpReturnAccount::Wasm(receiver), n)
+    verify {
+        assert!(Pallet::<T>::pending_deposits(&addr).is_empty());
+    }
+
     remove_proposal {
         let caller = alice::<T>();
         let amount: BalanceOf<T> = 1_000_000_000u32.into();
@@ -243,6 +262,162 @@ benchmarks! {
     }: _(RawOrigin::Root,  Some(caller))
     verify {
     }
+
+    set_withdrawal_fee_policy {
+        let policy = WithdrawalFeePolicy::FeeRate {
+            vsize: 200,
+            min_fee: 10000,
+            max_fee: 1000000,
+        };
+    }: _(RawOrigin::Root, policy)
+    verify {
+        assert_eq!(Pallet::<T>::btc_withdrawal_fee_policy(), policy);
+    }
+
+    set_btc_fee_rate {
+    }: _(RawOrigin::Root, 20)
+    verify {
+        assert_eq!(Pallet::<T>::btc_fee_rate(), 20);
+    }
+
+    set_deposit_attestation {
+        let config = DepositAttestationConfig {
+            threshold: 100000000,
+            expiry: 100u32.into(),
+        };
+    }: _(RawOrigin::Root, Some(config))
+    verify {
+        assert_eq!(Pallet::<T>::deposit_attestation(), Some(config));
+    }
+
+    attest_deposit {
+        let txid = H256::repeat_byte(1);
+        let receiver: T::AccountId = whitelisted_caller();
+        DeferredDeposits::<T>::insert(txid, DeferredDeposit {
+            who: OpReturnAccount::Wasm(receiver),
+            balance: 100000000,
+            expire_at: 100u32.into(),
+        });
+    }: _(RawOrigin::Root, txid)
+    verify {
+        assert!(Pallet::<T>::deferred_deposits(txid).is_none());
+    }
+
+    attest_wallet_balance {
+        let trustee_session = crate::trustee::current_trustee_session::<T>().unwrap();
+        let threshold = trustee_session.threshold as usize;
+        let trustees = trustee_session
+            .trustee_list
+            .into_iter()
+            .map(|(trustee, _)| trustee)
+            .collect::<Vec<_>>();
+        let caller = trustees[threshold - 1].clone();
+        let btc_height = Pallet::<T>::best_index().height;
+        PendingWalletAttestations::<T>::put(vec![WalletBalanceAttestation {
+            btc_height,
+            hot_balance: 100000000,
+            cold_balance: 100000000,
+            signers: trustees[..threshold - 1].to_vec(),
+        }]);
+    }: _(RawOrigin::Signed(caller), btc_height, 100000000, 100000000)
+    verify {
+        assert!(Pallet::<T>::wallet_attestations(btc_height).is_some());
+    }
+
+    set_wallet_deviation_tolerance {
+        let tolerance = Permill::from_percent(5);
+    }: _(RawOrigin::Root, tolerance)
+    verify {
+        assert_eq!(Pallet::<T>::wallet_deviation_tolerance(), tolerance);
+    }
+
+    sign_withdrawal_proposal {
+        let trustee_session = crate::trustee::current_trustee_session::<T>().unwrap();
+        let threshold = trustee_session.threshold as usize;
+        let trustee_list = trustee_session
+            .trustee_list
+            .into_iter()
+            .enumerate()
+            .map(|(i, (trustee, _))| (trustee, i < threshold - 1))
+            .collect::<Vec<_>>();
+        let caller = trustee_list[threshold - 1].0.clone();
+        let (tx, _, _) = withdraw_tx();
+        WithdrawalProposal::<T>::put(BtcWithdrawalProposal::<T::AccountId> {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list: vec![0],
+            tx,
+            trustee_list,
+        });
+    }: _(RawOrigin::Signed(caller))
+    verify {
+        assert_eq!(WithdrawalProposal::<T>::get().unwrap().sig_state, VoteResult::Finish);
+    }
+
+    set_withdrawal_proposal_expiry {
+        let expiry: T::BlockNumber = 100u32.into();
+    }: _(RawOrigin::Root, Some(expiry))
+    verify {
+        assert_eq!(Pallet::<T>::withdrawal_proposal_expiry(), Some(expiry));
+    }
+
+    set_max_reorg_depth {
+        ConfirmationNumber::<T>::put(11);
+    }: _(RawOrigin::Root, 10)
+    verify {
+        assert_eq!(Pallet::<T>::max_reorg_depth(), 10);
+    }
+
+    reset_relay {
+        let header = generate_blocks_63290_63310()[&63300];
+        let hash = header.hash();
+        let header_raw = serialization::serialize(&header).into();
+        let best_height = Pallet::<T>::best_index().height;
+        LastAttestedBtcHeight::<T>::put(best_height);
+        WalletAttestations::<T>::insert(best_height, AttestedWalletBalance {
+            attestation: WalletBalanceAttestation {
+                btc_height: best_height,
+                hot_balance: 0,
+                cold_balance: 0,
+                signers: vec![],
+            },
+            expected_balance: 0,
+        });
+    }: _(RawOrigin::Root, header_raw, 63300)
+    verify {
+        assert_eq!(Pallet::<T>::best_index(), BtcHeaderIndex { hash, height: 63300 });
+    }
+
+    report_withdrawal_tx {
+        let caller = alice::<T>();
+        let (tx, _, _) = withdraw_tx();
+        let tx_hash = tx.hash();
+
+        let amount: BalanceOf<T> = 1_000_000_000u32.into();
+        let withdrawal = 550000u32.into();
+        XGatewayRecords::<T>::deposit(&caller, X_BTC, amount).unwrap();
+        XGatewayRecords::<T>::withdraw(&caller, X_BTC, withdrawal, b"tb1pexff2s7l58sthpyfrtx500ax234stcnt0gz2lr4kwe0ue95a2e0srxsc68".to_vec(), b"".to_vec().into()).unwrap();
+        XGatewayRecords::<T>::process_withdrawals(&[0], Chain::Bitcoin).unwrap();
+
+        WithdrawalProposal::<T>::put(BtcWithdrawalProposal::<T::AccountId> {
+            sig_state: VoteResult::Finish,
+            withdrawal_id_list: vec![0],
+            tx,
+            trustee_list: vec![],
+        });
+    }: _(RawOrigin::Signed(caller), tx_hash)
+    verify {
+        assert_eq!(
+            XGatewayRecords::<T>::withdrawal_status(0),
+            Some(WithdrawalStatus::Broadcasted(tx_hash))
+        );
+    }
+
+    set_relayer {
+        let who = alice::<T>();
+    }: _(RawOrigin::Root, who.clone(), true)
+    verify {
+        assert!(Pallet::<T>::is_relayer(&who));
+    }
 }
 
 #[cfg(test)]
@@ -260,9 +435,22 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_set_best_index());
             assert_ok!(Pallet::<Test>::test_benchmark_set_confirmed_index());
             assert_ok!(Pallet::<Test>::test_benchmark_remove_pending());
+            assert_ok!(Pallet::<Test>::test_benchmark_claim_pending_deposits());
             assert_ok!(Pallet::<Test>::test_benchmark_set_btc_withdrawal_fee());
             assert_ok!(Pallet::<Test>::test_benchmark_set_btc_deposit_limit());
             assert_ok!(Pallet::<Test>::test_benchmark_set_coming_bot());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_withdrawal_fee_policy());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_btc_fee_rate());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_deposit_attestation());
+            assert_ok!(Pallet::<Test>::test_benchmark_attest_deposit());
+            assert_ok!(Pallet::<Test>::test_benchmark_attest_wallet_balance());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_wallet_deviation_tolerance());
+            assert_ok!(Pallet::<Test>::test_benchmark_sign_withdrawal_proposal());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_withdrawal_proposal_expiry());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_max_reorg_depth());
+            assert_ok!(Pallet::<Test>::test_benchmark_reset_relay());
+            assert_ok!(Pallet::<Test>::test_benchmark_report_withdrawal_tx());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_relayer());
         });
     }
 }
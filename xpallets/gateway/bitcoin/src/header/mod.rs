@@ -3,17 +3,16 @@
 mod header_proof;
 
 use frame_support::log::{error, info};
-use sp_runtime::DispatchResult;
-use sp_std::{cmp::Ordering, prelude::*};
+use sp_std::prelude::*;
 
-use light_bitcoin::primitives::{hash_rev, H256};
+use light_bitcoin::primitives::{hash_rev, Compact, H256, U256};
 
 use crate::{
     types::{BtcHeaderIndex, BtcHeaderInfo},
     Config, ConfirmedIndex, Error, MainChain, Pallet,
 };
 
-pub use self::header_proof::HeaderVerifier;
+pub use self::header_proof::{is_valid_proof_of_work, HeaderVerifier};
 
 /// Look back the headers to pick the confirmed index,
 /// return the header indexes on the look back path.
@@ -92,51 +91,122 @@ fn set_main_chain<T: Config>(height: u32, main_hash: H256) {
     }
 }
 
-pub fn check_confirmed_header<T: Config>(header_info: &BtcHeaderInfo) -> DispatchResult {
-    let (confirmed, _) = look_back_confirmed_header::<T>(header_info);
-    if let Some(current_confirmed) = ConfirmedIndex::<T>::get() {
-        if let Some(now_confirmed) = confirmed {
-            return match current_confirmed.height.cmp(&now_confirmed.height) {
-                Ordering::Greater => {
-                    // e.g:
-                    //          current_confirmed
-                    // b  ---------------- b  ------ b --- b --- b(best)
-                    // |(now_confirmed)--- b  ------ b --- b(now)
-                    // 99              100       101  102    103
-                    // current_confirmed > now_confirmed
-                    Ok(())
-                }
-                Ordering::Equal => {
-                    // e.g:
-                    //current_confirmed
-                    // b --------------- b  ------ b --- b(best)
-                    // |(now_confirmed)- b  ------ b --- b(now)
-                    // 99              100       101  102    103
-                    // current_confirmed = now_confirmed
-                    if current_confirmed.hash == now_confirmed.hash {
-                        Ok(())
-                    } else {
-                        // e.g:
-                        //
-                        //  b --------- b(current_confirmed) b  ------ b --- b(best)
-                        //  | --------- b(now_confirmed) --- b  ------ b --- b(now)
-                        // 99              100       101  102    103
-                        // current_confirmed = now_confirmed
-                        Err(Error::<T>::AncientFork.into())
-                    }
-                }
-                Ordering::Less => {
-                    // normal should not happen, for call `check_confirmed_header` should under
-                    // current <= best
-                    error!(
-                        "[check_confirmed_header] Should not happen, current confirmed is less than confirmed for this header, \
-                        current:{:?}, now:{:?}", current_confirmed, now_confirmed
-                    );
-                    Err(Error::<T>::AncientFork.into())
-                }
+/// The expected number of hashes to find a header of `bits`, i.e., 2^256 / (target + 1).
+fn header_work(bits: Compact) -> U256 {
+    match bits.to_u256() {
+        Ok(target) if !target.is_zero() => (!target / (target + 1)) + 1,
+        _ => U256::zero(),
+    }
+}
+
+/// The branch of a header leaving the main chain, all in the ascending order.
+pub struct SideBranch {
+    /// The last main chain header of the branch.
+    pub fork_point: BtcHeaderIndex,
+    /// The side branch headers from the fork point (exclusive) to the new header (inclusive).
+    pub headers: Vec<BtcHeaderIndex>,
+    /// The main chain headers from the fork point (exclusive) to the best (inclusive).
+    pub main_headers: Vec<BtcHeaderIndex>,
+    /// The cumulative work of the side branch headers.
+    pub work: U256,
+    /// The cumulative work of the main chain headers.
+    pub main_work: U256,
+}
+
+/// Look back the side branch of the header to the main chain.
+///
+/// Returns `AncientFork` if the fork point is more than `MaxReorgDepth` blocks below the best,
+/// or below the confirmed header, whose deposits and withdrawals were already processed.
+pub fn look_back_side_branch<T: Config>(
+    header_info: &BtcHeaderInfo,
+) -> Result<SideBranch, Error<T>> {
+    let best = Pallet::<T>::best_index();
+    let mut lowest_fork_height = best.height.saturating_sub(Pallet::<T>::max_reorg_depth());
+    if let Some(confirmed) = Pallet::<T>::confirmed_index() {
+        lowest_fork_height = lowest_fork_height.max(confirmed.height);
+    }
+
+    let mut headers = vec![];
+    let mut work = U256::zero();
+    let mut current = header_info.clone();
+    let fork_point = loop {
+        let hash = current.header.hash();
+        if Pallet::<T>::main_chain(&hash) {
+            break BtcHeaderIndex {
+                hash,
+                height: current.height,
             };
         }
+        if current.height <= lowest_fork_height {
+            error!(
+                target: "runtime::bitcoin",
+                "[look_back_side_branch] The fork of header ({:?}) is below height {}",
+                hash_rev(header_info.header.hash()),
+                lowest_fork_height
+            );
+            return Err(Error::<T>::AncientFork);
+        }
+        headers.push(BtcHeaderIndex {
+            hash,
+            height: current.height,
+        });
+        work = work.saturating_add(header_work(current.header.bits));
+        current = Pallet::<T>::headers(&current.header.previous_header_hash)
+            .ok_or(Error::<T>::PrevHeaderNotExisted)?;
+    };
+    headers.reverse();
+
+    let mut main_headers = vec![];
+    let mut main_work = U256::zero();
+    let mut hash = best.hash;
+    while hash != fork_point.hash {
+        let info = Pallet::<T>::headers(&hash).ok_or(Error::<T>::PrevHeaderNotExisted)?;
+        main_headers.push(BtcHeaderIndex {
+            hash,
+            height: info.height,
+        });
+        main_work = main_work.saturating_add(header_work(info.header.bits));
+        hash = info.header.previous_header_hash;
+    }
+    main_headers.reverse();
+
+    Ok(SideBranch {
+        fork_point,
+        headers,
+        main_headers,
+        work,
+        main_work,
+    })
+}
+
+/// Switches the main chain to the side branch which has more cumulative work.
+///
+/// The main chain headers after the fork point are orphaned and the confirmed index is
+/// re-evaluated on the new main chain. The txs of the confirmed headers were processed, so the
+/// reorg is rejected if the confirmed index wouldn't stay or move above the last confirmed one.
+pub fn reorg<T: Config>(branch: &SideBranch, header_info: &BtcHeaderInfo) -> Result<(), Error<T>> {
+    let (confirmed, _) = look_back_confirmed_header::<T>(header_info);
+    if let Some(last) = Pallet::<T>::confirmed_index() {
+        match confirmed {
+            Some(index) if index == last || index.height > last.height => {}
+            _ => {
+                error!(
+                    target: "runtime::bitcoin",
+                    "[reorg] Confirmed index would move from {:?} to {:?}",
+                    last,
+                    confirmed
+                );
+                return Err(Error::<T>::ConfirmedIndexRollback);
+            }
+        }
+    }
+
+    for index in &branch.main_headers {
+        MainChain::<T>::remove(&index.hash);
+    }
+    for index in &branch.headers {
+        MainChain::<T>::insert(&index.hash, true);
     }
-    // do not have confirmed yet.
+    update_confirmed_header::<T>(header_info);
     Ok(())
 }
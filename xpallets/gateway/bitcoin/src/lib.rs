@@ -5,6 +5,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod header;
+pub mod priority;
+mod reserve;
 pub mod trustee;
 mod tx;
 pub mod types;
@@ -18,7 +20,7 @@ mod mock;
 mod tests;
 
 use sp_core::sp_std::str::FromStr;
-use sp_runtime::SaturatedConversion;
+use sp_runtime::{Permill, SaturatedConversion};
 use sp_std::prelude::*;
 
 #[cfg(feature = "std")]
@@ -30,19 +32,23 @@ pub use light_bitcoin::{
 };
 use light_bitcoin::{
     chain::Transaction,
-    keys::{Address, DisplayLayout},
-    serialization::{deserialize, Reader},
+    keys::{Address, AddressTypes, DisplayLayout},
+    merkle::PartialMerkleTree,
+    serialization::{deserialize, serialize_with_flags, Reader, SERIALIZE_TRANSACTION_WITNESS},
 };
 
 use chainx_primitives::{AssetId, ReferralId};
-use xp_gateway_common::AccountExtractor;
+use xp_address::{AddressError, AddressFormat};
+use xp_gateway_common::{to_ss58_check, AccountExtractor};
+use xp_protocol::NetworkType;
 use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
 use xpallet_gateway_common::{
     traits::{
-        AddressBinding, ProposalProvider, ReferralBinding, TotalSupply, TrusteeInfoUpdate,
-        TrusteeSession,
+        AddressBinding, ChainGateway, OperationPauseProvider, ProposalProvider, ReferralBinding,
+        TotalSupply, TrusteeInfoUpdate, TrusteeSession, WithdrawalBatchProvider,
     },
     trustees::bitcoin::BtcTrusteeAddrInfo,
+    types::{DepositAddress, GatewayOperation},
 };
 use xpallet_support::try_addr;
 
@@ -50,13 +56,16 @@ use self::{
     trustee::{get_current_trustee_address_pair, get_last_trustee_address_pair},
     tx::remove_pending_deposit,
     types::{
-        BtcDepositCache, BtcHeaderIndex, BtcHeaderInfo, BtcRelayedTx, BtcRelayedTxInfo,
-        BtcTxResult, BtcTxState,
+        BtcDepositCache, BtcGatewayMetrics, BtcGatewayParams, BtcHeaderIndex, BtcHeaderInfo,
+        BtcRelayedTx, BtcRelayedTxInfo, BtcTxInclusion, BtcTxResult, BtcTxState, DeferredDeposit,
+        DepositAttestationConfig, WalletBalanceAttestation, WithdrawalFeePolicy,
     },
 };
 
 pub use self::{
-    types::{BtcAddress, BtcParams, BtcTxVerifier, BtcWithdrawalProposal},
+    header::is_valid_proof_of_work,
+    priority::PrioritizeRelay,
+    types::{AttestedWalletBalance, BtcAddress, BtcParams, BtcTxVerifier, BtcWithdrawalProposal},
     weights::WeightInfo,
 };
 pub use pallet::*;
@@ -77,11 +86,14 @@ pub mod pallet {
     use sp_std::marker::PhantomData;
 
     use frame_support::{
-        dispatch::DispatchResult, pallet_prelude::*, traits::UnixTime, transactional,
+        dispatch::{DispatchErrorWithPostInfo, DispatchResult},
+        pallet_prelude::*,
+        traits::UnixTime,
+        transactional,
     };
     use frame_system::pallet_prelude::*;
     use sp_core::H160;
-    use sp_runtime::traits::Saturating;
+    use sp_runtime::traits::{Saturating, Zero};
     use xp_gateway_bitcoin::OpReturnAccount;
 
     use super::*;
@@ -107,6 +119,13 @@ pub mod pallet {
         /// A majority of the council can excute some transactions.
         type CouncilOrigin: EnsureOrigin<Self::Origin>;
 
+        /// The origin which can change the parameters of the gateway, e.g., root dispatched
+        /// after the enactment delay.
+        type ParameterOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The origin attesting the deposits deferred by the `DepositAttestation`.
+        type AttesterOrigin: EnsureOrigin<Self::Origin>;
+
         /// Extract the account and possible extra from the data.
         type AccountExtractor: AccountExtractor<Self::AccountId, ReferralId>;
 
@@ -120,18 +139,47 @@ pub mod pallet {
         /// Update information about the trustee.
         type TrusteeInfoUpdate: TrusteeInfoUpdate;
 
+        /// Restrict the withdrawal proposal to the sealed withdrawal batch.
+        type WithdrawalBatch: WithdrawalBatchProvider;
+
+        /// Check whether the gateway operations are paused in an emergency.
+        type OperationPause: OperationPauseProvider;
+
         /// Handle referral of assets across chains.
         type ReferralBinding: ReferralBinding<Self::AccountId>;
 
         /// Handle address binding about pending deposit.
         type AddressBinding: AddressBinding<Self::AccountId, BtcAddress>;
 
+        /// The maximum number of the relay extrinsics of a sender prioritized by
+        /// `PrioritizeRelay` in a block.
+        #[pallet::constant]
+        type MaxPrioritizedRelays: Get<u32>;
+
+        /// The maximum number of the feeless relay extrinsics of an allowlisted relayer in a
+        /// block.
+        #[pallet::constant]
+        type MaxFeelessRelays: Get<u32>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let (expiring, expired) = tx::expire_deferred_deposits::<T>(now);
+            let mut weight = T::DbWeight::get().reads_writes(
+                1 + expiring as Weight + expired as Weight,
+                1 + 2 * expired as Weight,
+            );
+            weight += T::DbWeight::get().reads(1);
+            if trustee::expire_withdrawal_proposal::<T>(now) {
+                weight += <T as Config>::WeightInfo::remove_proposal();
+            }
+            weight
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -139,14 +187,16 @@ pub mod pallet {
         #[pallet::weight(<T as Config>::WeightInfo::push_header())]
         pub fn push_header(origin: OriginFor<T>, header: Vec<u8>) -> DispatchResultWithPostInfo {
             let from = ensure_signed(origin)?;
-            let header: BtcHeader =
-                deserialize(header.as_slice()).map_err(|_| Error::<T>::DeserializeErr)?;
-            log!(debug, "[push_header] from:{:?}, header:{:?}", from, header);
+            log!(debug, "[push_header] from:{:?}", from);
 
-            Self::apply_push_header(header)?;
+            // The allowlisted relayer does not pay a fee.
+            let pays_fee = Self::relay_pays_fee(&from);
+            Self::relay_header(&header).map_err(|error| DispatchErrorWithPostInfo {
+                post_info: pays_fee.into(),
+                error,
+            })?;
 
-            // Relayer does not pay a fee.
-            Ok(Pays::No.into())
+            Ok(pays_fee.into())
         }
 
         /// if use `RelayTx` struct would export in metadata, cause complex in front-end
@@ -157,27 +207,18 @@ pub mod pallet {
             relayed_info: Vec<u8>,
             prev_tx: Option<Vec<u8>>,
         ) -> DispatchResultWithPostInfo {
-            let _from = ensure_signed(origin)?;
-            let raw_tx = Self::deserialize_tx(raw_tx.as_slice())?;
-            let relayed_info: BtcRelayedTxInfo =
-                Decode::decode(&mut &relayed_info[..]).map_err(|_| Error::<T>::DeserializeErr)?;
-            let prev_tx = if let Some(prev_tx) = prev_tx {
-                Some(Self::deserialize_tx(prev_tx.as_slice())?)
-            } else {
-                None
-            };
-            let relay_tx = relayed_info.into_relayed_tx(raw_tx);
-            log!(
-                debug,
-                "[push_transaction] from:{:?}, relay_tx:{:?}, prev_tx:{:?}",
-                _from,
-                relay_tx,
-                prev_tx
-            );
+            let from = ensure_signed(origin)?;
+            log!(debug, "[push_transaction] from:{:?}", from);
 
-            Self::apply_push_transaction(relay_tx, prev_tx)?;
+            let pays_fee = Self::relay_pays_fee(&from);
+            Self::relay_transaction(&raw_tx, &relayed_info, prev_tx.as_deref()).map_err(
+                |error| DispatchErrorWithPostInfo {
+                    post_info: pays_fee.into(),
+                    error,
+                },
+            )?;
 
-            Ok(Pays::No.into())
+            Ok(pays_fee.into())
         }
 
         /// Trustee create a proposal for a withdrawal list. `tx` is the proposal withdrawal transaction.
@@ -188,6 +229,7 @@ pub mod pallet {
             tx: Vec<u8>,
         ) -> DispatchResultWithPostInfo {
             let from = ensure_signed(origin)?;
+            T::OperationPause::ensure_not_paused(GatewayOperation::Withdrawal)?;
 
             ensure!(
                 !T::TrusteeSessionProvider::trustee_transition_state(),
@@ -197,16 +239,7 @@ pub mod pallet {
             // committer must be in the trustee list
             Self::ensure_trustee_or_bot(&from)?;
 
-            let tx = Self::deserialize_tx(tx.as_slice())?;
-            log!(
-                debug,
-                "[create_withdraw_tx] from:{:?}, withdrawal list:{:?}, tx:{:?}",
-                from,
-                withdrawal_id_list,
-                tx
-            );
-
-            Self::apply_create_taproot_withdraw(from, tx, withdrawal_id_list)?;
+            Self::propose_withdrawal(from, withdrawal_id_list, &tx)?;
             Ok(Pays::No.into())
         }
 
@@ -232,6 +265,34 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Allow root or council to claim up to `limit` pending deposits of `btc_address` to
+        /// `account` in one call, the pending deposits are claimed in the order of relaying.
+        #[pallet::weight(<T as Config>::WeightInfo::claim_pending_deposits(*limit))]
+        pub fn claim_pending_deposits(
+            origin: OriginFor<T>,
+            btc_address: BtcAddress,
+            account: OpReturnAccount<T::AccountId>,
+            limit: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            T::OperationPause::ensure_not_paused(GatewayOperation::Deposit)?;
+            ensure!(
+                limit > 0 && !Self::pending_deposits(&btc_address).is_empty(),
+                Error::<T>::NoPendingDeposit
+            );
+
+            let (count, total) = tx::claim_pending_deposits::<T>(&btc_address, &account, limit);
+            Self::deposit_event(Event::<T>::PendingDepositsClaimed(
+                account,
+                total.saturated_into(),
+                count,
+                btc_address,
+            ));
+            Ok(Some(<T as Config>::WeightInfo::claim_pending_deposits(count)).into())
+        }
+
         /// Dangerous! remove current withdrawal proposal directly. Please check business logic before
         /// do this operation.
         #[pallet::weight(<T as Config>::WeightInfo::remove_proposal())]
@@ -262,39 +323,195 @@ pub mod pallet {
         /// Dangerous! Be careful to set ConfirmationNumber
         #[pallet::weight(0u64)]
         pub fn set_confirmed_number(origin: OriginFor<T>, number: u32) -> DispatchResult {
-            T::CouncilOrigin::try_origin(origin)
+            T::ParameterOrigin::try_origin(origin)
                 .map(|_| ())
                 .or_else(ensure_root)?;
             ConfirmationNumber::<T>::put(number);
             Ok(())
         }
 
+        /// Set the max number of the main chain blocks a side branch could orphan.
+        ///
+        /// The depth must be below the confirmation number, the confirmed blocks can't be
+        /// orphaned since their deposits and withdrawals were already processed.
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_reorg_depth())]
+        pub fn set_max_reorg_depth(
+            origin: OriginFor<T>,
+            #[pallet::compact] depth: u32,
+        ) -> DispatchResult {
+            T::ParameterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            ensure!(
+                depth < Self::confirmation_number(),
+                Error::<T>::ReorgDepthTooLarge
+            );
+            MaxReorgDepth::<T>::put(depth);
+            Self::deposit_event(Event::<T>::MaxReorgDepthSet(depth));
+            Ok(())
+        }
+
+        /// Re-anchor the relay at a newer bitcoin checkpoint, i.e., reset the genesis header.
+        ///
+        /// The checkpoint must be above the best header, and nothing must be orphaned by the
+        /// reset, i.e. no withdrawal proposal or pending withdrawal record, no pending or
+        /// deferred deposit, and the wallet balance must be attested at the best header without
+        /// any balance above the backed supply, which would be the deposits in the unconfirmed
+        /// blocks.
+        ///
+        /// NOTE: The blocks skipped between the best header and the checkpoint are unknown to
+        /// the relay, the deposits in them show up as the deviation of the next wallet balance
+        /// attestation and must be credited by the council.
+        #[pallet::weight(<T as Config>::WeightInfo::reset_relay())]
+        #[transactional]
+        pub fn reset_relay(
+            origin: OriginFor<T>,
+            genesis_header: Vec<u8>,
+            #[pallet::compact] height: u32,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            let header: BtcHeader =
+                deserialize(genesis_header.as_slice()).map_err(|_| Error::<T>::DeserializeErr)?;
+            Self::apply_reset_relay(header, height)
+        }
+
         /// Set bitcoin withdrawal fee
         #[pallet::weight(<T as Config>::WeightInfo::set_btc_withdrawal_fee())]
         pub fn set_btc_withdrawal_fee(
             origin: OriginFor<T>,
             #[pallet::compact] fee: u64,
         ) -> DispatchResult {
-            T::CouncilOrigin::try_origin(origin)
+            T::ParameterOrigin::try_origin(origin)
                 .map(|_| ())
                 .or_else(ensure_root)?;
             BtcWithdrawalFee::<T>::put(fee);
             Ok(())
         }
 
+        /// Set how the bitcoin withdrawal fee is charged.
+        #[pallet::weight(<T as Config>::WeightInfo::set_withdrawal_fee_policy())]
+        pub fn set_withdrawal_fee_policy(
+            origin: OriginFor<T>,
+            policy: WithdrawalFeePolicy,
+        ) -> DispatchResult {
+            T::ParameterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            if let WithdrawalFeePolicy::FeeRate {
+                vsize,
+                min_fee,
+                max_fee,
+            } = policy
+            {
+                ensure!(
+                    vsize > 0 && min_fee <= max_fee,
+                    Error::<T>::InvalidWithdrawalFeePolicy
+                );
+            }
+            BtcWithdrawalFeePolicy::<T>::put(policy);
+            Ok(())
+        }
+
+        /// Set the bitcoin fee rate (sats/vB) for the `WithdrawalFeePolicy::FeeRate`.
+        ///
+        /// The trustees and the bot could report the rate besides the council.
+        #[pallet::weight(<T as Config>::WeightInfo::set_btc_fee_rate())]
+        pub fn set_btc_fee_rate(
+            origin: OriginFor<T>,
+            #[pallet::compact] fee_rate: u64,
+        ) -> DispatchResult {
+            match ensure_signed(origin.clone()) {
+                Ok(who) if Self::ensure_trustee_or_bot(&who).is_ok() => {}
+                _ => T::ParameterOrigin::try_origin(origin)
+                    .map(|_| ())
+                    .or_else(ensure_root)?,
+            }
+            let old_fee_rate = BtcFeeRate::<T>::mutate(|rate| sp_std::mem::replace(rate, fee_rate));
+            if old_fee_rate != fee_rate {
+                Self::deposit_event(Event::<T>::BtcFeeRateChanged(
+                    old_fee_rate,
+                    fee_rate,
+                    Self::withdrawal_fee(),
+                ));
+            }
+            Ok(())
+        }
+
         /// Set bitcoin deposit limit
         #[pallet::weight(<T as Config>::WeightInfo::set_btc_deposit_limit())]
         pub fn set_btc_deposit_limit(
             origin: OriginFor<T>,
             #[pallet::compact] value: u64,
         ) -> DispatchResult {
-            T::CouncilOrigin::try_origin(origin)
+            T::ParameterOrigin::try_origin(origin)
                 .map(|_| ())
                 .or_else(ensure_root)?;
             BtcMinDeposit::<T>::put(value);
             Ok(())
         }
 
+        /// Set the attestation required by the large deposits, `None` credits all the deposits
+        /// directly. The deposits deferred already are not affected.
+        #[pallet::weight(<T as Config>::WeightInfo::set_deposit_attestation())]
+        pub fn set_deposit_attestation(
+            origin: OriginFor<T>,
+            config: Option<DepositAttestationConfig<T::BlockNumber>>,
+        ) -> DispatchResult {
+            T::ParameterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            if let Some(config) = config {
+                ensure!(
+                    !config.expiry.is_zero(),
+                    Error::<T>::InvalidAttestationConfig
+                );
+                DepositAttestation::<T>::put(config);
+            } else {
+                DepositAttestation::<T>::kill();
+            }
+            Ok(())
+        }
+
+        /// Attest the deferred deposit of `txid` and credit it to the depositor.
+        #[pallet::weight(<T as Config>::WeightInfo::attest_deposit())]
+        #[transactional]
+        pub fn attest_deposit(origin: OriginFor<T>, txid: H256) -> DispatchResult {
+            T::AttesterOrigin::ensure_origin(origin)?;
+            T::OperationPause::ensure_not_paused(GatewayOperation::Deposit)?;
+            tx::attest_deferred_deposit::<T>(txid)
+        }
+
+        /// Trustees co-sign the BTC balances held at the hot and cold addresses at `btc_height`.
+        ///
+        /// Once signed by enough trustees, the attestation is stored and compared against
+        /// the X-BTC supply, `WalletBalanceDeviated` is emitted if it deviates too much.
+        #[pallet::weight(<T as Config>::WeightInfo::attest_wallet_balance())]
+        pub fn attest_wallet_balance(
+            origin: OriginFor<T>,
+            #[pallet::compact] btc_height: u32,
+            #[pallet::compact] hot_balance: u64,
+            #[pallet::compact] cold_balance: u64,
+        ) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+            reserve::sign_wallet_balance::<T>(from, btc_height, hot_balance, cold_balance)?;
+            Ok(Pays::No.into())
+        }
+
+        /// Set the proportion of the X-BTC supply the attested wallet balance could deviate by.
+        #[pallet::weight(<T as Config>::WeightInfo::set_wallet_deviation_tolerance())]
+        pub fn set_wallet_deviation_tolerance(
+            origin: OriginFor<T>,
+            tolerance: Permill,
+        ) -> DispatchResult {
+            T::ParameterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            WalletDeviationTolerance::<T>::put(tolerance);
+            Ok(())
+        }
+
         /// Set coming bot
         #[pallet::weight(<T as Config>::WeightInfo::set_coming_bot())]
         pub fn set_coming_bot(origin: OriginFor<T>, bot: Option<T::AccountId>) -> DispatchResult {
@@ -307,6 +524,88 @@ pub mod pallet {
             }
             Ok(())
         }
+
+        /// Add `who` to or remove it from the relayer allowlist.
+        ///
+        /// The header and tx pushes of an allowlisted relayer are feeless, whether they succeed
+        /// or not, up to `MaxFeelessRelays` per block. The others pay the fees.
+        #[pallet::weight(<T as Config>::WeightInfo::set_relayer())]
+        pub fn set_relayer(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            allowed: bool,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            if allowed {
+                Relayers::<T>::insert(&who, true);
+            } else {
+                Relayers::<T>::remove(&who);
+                FeelessRelays::<T>::remove(&who);
+                PrioritizedRelays::<T>::remove(&who);
+            }
+            Self::deposit_event(Event::<T>::RelayerSet(who, allowed));
+            Ok(())
+        }
+
+        /// Trustee acknowledges the signature of the current withdrawal proposal.
+        #[pallet::weight(<T as Config>::WeightInfo::sign_withdrawal_proposal())]
+        pub fn sign_withdrawal_proposal(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+            trustee::sign_withdrawal_proposal::<T>(from)?;
+            Ok(Pays::No.into())
+        }
+
+        /// Trustee reports the txid of the fully signed withdrawal proposal once broadcasted,
+        /// the withdrawals of which become `Broadcasted` until the tx is relayed.
+        ///
+        /// The `txid` is in the byte order of the `Withdrawn` event, i.e., not reversed.
+        #[pallet::weight(<T as Config>::WeightInfo::report_withdrawal_tx())]
+        pub fn report_withdrawal_tx(
+            origin: OriginFor<T>,
+            txid: H256,
+        ) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+            Self::ensure_trustee_or_bot(&from)?;
+            trustee::report_withdrawal_tx::<T>(txid)?;
+            Ok(Pays::No.into())
+        }
+
+        /// Report the txid of the fully signed withdrawal proposal broadcasted by a node,
+        /// see `report_withdrawal_tx`.
+        ///
+        /// This is an unsigned extrinsic, only the txid of the proposal not reported yet is
+        /// valid.
+        #[pallet::weight(<T as Config>::WeightInfo::report_withdrawal_tx())]
+        pub fn report_withdrawal_broadcast(
+            origin: OriginFor<T>,
+            txid: H256,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            trustee::report_withdrawal_tx::<T>(txid)?;
+            Ok(Pays::No.into())
+        }
+
+        /// Set the number of blocks a withdrawal proposal could wait for the trustee
+        /// signatures, `None` never expires the proposals. The current proposal is not affected.
+        #[pallet::weight(<T as Config>::WeightInfo::set_withdrawal_proposal_expiry())]
+        pub fn set_withdrawal_proposal_expiry(
+            origin: OriginFor<T>,
+            expiry: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            T::ParameterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            match expiry {
+                Some(expiry) => {
+                    ensure!(!expiry.is_zero(), Error::<T>::InvalidProposalExpiry);
+                    WithdrawalProposalExpiry::<T>::put(expiry);
+                }
+                None => WithdrawalProposalExpiry::<T>::kill(),
+            }
+            Ok(())
+        }
     }
 
     /// Error for the XBridge Bitcoin module
@@ -370,6 +669,35 @@ pub mod pallet {
         TxOutputNotColdAddr,
         /// The total amount of the trust must be transferred out in full
         TxNotFullAmount,
+        /// No pending deposit to claim for the address
+        NoPendingDeposit,
+        /// The expiry of the deposit attestation must not be zero
+        InvalidAttestationConfig,
+        /// No deferred deposit waiting for the attestation
+        NoDeferredDeposit,
+        /// The fee rate policy needs a non-zero vsize and min fee not above max fee
+        InvalidWithdrawalFeePolicy,
+        /// The attested height must be above the last attested one and not above the best height
+        InvalidAttestationHeight,
+        /// The expiry of the withdrawal proposal must not be zero
+        InvalidProposalExpiry,
+        /// The checkpoint must be above the best header
+        InvalidCheckpoint,
+        /// The deferred deposits must be attested or expired before resetting the relay
+        UnsettledDeferredDeposits,
+        /// The reported txid does not match the withdrawal proposal
+        WithdrawalTxMismatch,
+        /// The max reorg depth must be below the confirmation number
+        ReorgDepthTooLarge,
+        /// The pending deposits must be claimed before resetting the relay
+        UnsettledPendingDeposits,
+        /// The bitcoin withdrawals must be finished or canceled before resetting the relay
+        UnsettledWithdrawals,
+        /// The wallet balance must be attested at the best height without any balance above the
+        /// backed supply before resetting the relay
+        UnattestedWallet,
+        /// The reorg would move the confirmed header at or below the last confirmed one
+        ConfirmedIndexRollback,
     }
 
     #[pallet::event]
@@ -391,6 +719,8 @@ pub mod pallet {
         WithdrawalProposalCreated(T::AccountId, Vec<u32>),
         /// A trustee voted/vetoed a withdrawal proposal. [trustee, vote_status]
         WithdrawalProposalVoted(T::AccountId, bool),
+        /// The withdrawal proposal was fully signed, the tx is ready to broadcast. [txid]
+        WithdrawalProposalSigned(H256),
         /// A fatal error happened during the withdrawal process. [tx_hash, proposal_hash]
         WithdrawalFatalErr(H256, H256),
         /// An account deposited some token for evm address. [tx_hash, who, amount]
@@ -405,6 +735,32 @@ pub mod pallet {
         DepositedNamed(H256, Vec<u8>, Vec<u8>, BalanceOf<T>),
         /// A unclaimed deposit record was removed for named address. [prefix, depositor, deposit_amount, tx_hash, btc_address]
         PendingDepositNamedRemoved(Vec<u8>, Vec<u8>, BalanceOf<T>, H256, BtcAddress),
+        /// Some pending deposits were claimed in a batch. [depositor, total_credited, deposit_count, btc_address]
+        PendingDepositsClaimed(OpReturnAccount<T::AccountId>, BalanceOf<T>, u32, BtcAddress),
+        /// A deposit was deferred until attested. [tx_hash, deposit_amount, expire_at]
+        DepositDeferred(H256, BalanceOf<T>, T::BlockNumber),
+        /// A deferred deposit was attested and credited. [tx_hash, deposit_amount]
+        DeferredDepositAttested(H256, BalanceOf<T>),
+        /// A deferred deposit expired without the attestation, the tx could be relayed again. [tx_hash, deposit_amount]
+        DeferredDepositExpired(H256, BalanceOf<T>),
+        /// The bitcoin fee rate (sats/vB) was changed. [old_fee_rate, new_fee_rate, withdrawal_fee]
+        BtcFeeRateChanged(u64, u64, u64),
+        /// A trustee signed the wallet balances. [trustee, btc_height, hot_balance, cold_balance]
+        WalletBalanceSigned(T::AccountId, u32, u64, u64),
+        /// The wallet balances were attested by the trustees. [btc_height, hot_balance, cold_balance, expected_balance]
+        WalletBalanceAttested(u32, u64, u64, u64),
+        /// The attested wallet balance deviates from the X-BTC supply beyond the tolerance. [btc_height, attested_balance, expected_balance]
+        WalletBalanceDeviated(u32, u64, u64),
+        /// A withdrawal proposal expired before fully signed, the withdrawals return to the pending queue. [withdrawal_ids, absent_trustees]
+        WithdrawalProposalExpired(Vec<u32>, Vec<T::AccountId>),
+        /// The main chain switched to a side branch with more work. [old_best_hash, new_best_hash, depth]
+        BtcReorg(H256, H256, u32),
+        /// The relay was re-anchored at a bitcoin checkpoint. [genesis_hash, genesis_height]
+        RelayReset(H256, u32),
+        /// An account was added to or removed from the relayer allowlist. [who, allowed]
+        RelayerSet(T::AccountId, bool),
+        /// The max number of the main chain blocks a side branch could orphan was set. [depth]
+        MaxReorgDepthSet(u32),
     }
 
     /// best header info
@@ -444,12 +800,81 @@ pub mod pallet {
     pub(crate) type PendingDeposits<T: Config> =
         StorageMap<_, Blake2_128Concat, BtcAddress, Vec<BtcDepositCache>, ValueQuery>;
 
+    #[pallet::type_value]
+    pub fn DefaultForMaxReorgDepth<T: Config>() -> u32 {
+        6
+    }
+
+    /// The max number of the main chain blocks a side branch could orphan, the side branch
+    /// headers forking below are rejected, as well as the ones forking at or below the
+    /// confirmed header.
+    #[pallet::storage]
+    #[pallet::getter(fn max_reorg_depth)]
+    pub(crate) type MaxReorgDepth<T: Config> =
+        StorageValue<_, u32, ValueQuery, DefaultForMaxReorgDepth<T>>;
+
+    /// The attestation required by the large deposits, the deposits are credited directly if not set.
+    #[pallet::storage]
+    #[pallet::getter(fn deposit_attestation)]
+    pub(crate) type DepositAttestation<T: Config> =
+        StorageValue<_, DepositAttestationConfig<T::BlockNumber>>;
+
+    /// The deposits waiting for the attestation, tx_hash => deferred deposit
+    #[pallet::storage]
+    #[pallet::getter(fn deferred_deposits)]
+    pub(crate) type DeferredDeposits<T: Config> =
+        StorageMap<_, Identity, H256, DeferredDeposit<T::AccountId, T::BlockNumber>>;
+
+    /// The deferred deposits expiring at a block, block number => tx_hash list
+    #[pallet::storage]
+    pub(crate) type DeferredDepositExpiries<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<H256>, ValueQuery>;
+
+    /// The wallet balance attestations waiting for enough trustee signatures.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_wallet_attestations)]
+    pub(crate) type PendingWalletAttestations<T: Config> =
+        StorageValue<_, Vec<WalletBalanceAttestation<T::AccountId>>, ValueQuery>;
+
+    /// The finalized wallet balance attestations, btc_height => attestation
+    #[pallet::storage]
+    #[pallet::getter(fn wallet_attestations)]
+    pub(crate) type WalletAttestations<T: Config> =
+        StorageMap<_, Twox64Concat, u32, AttestedWalletBalance<T::AccountId>>;
+
+    /// The bitcoin height of the last finalized wallet balance attestation.
+    #[pallet::storage]
+    #[pallet::getter(fn last_attested_btc_height)]
+    pub(crate) type LastAttestedBtcHeight<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultForWalletDeviationTolerance<T: Config>() -> Permill {
+        Permill::from_percent(1)
+    }
+
+    /// The proportion of the X-BTC supply the attested wallet balance could deviate by, 1% by default.
+    #[pallet::storage]
+    #[pallet::getter(fn wallet_deviation_tolerance)]
+    pub(crate) type WalletDeviationTolerance<T: Config> =
+        StorageValue<_, Permill, ValueQuery, DefaultForWalletDeviationTolerance<T>>;
+
     /// withdrawal tx outs for account, tx_hash => outs ( out index => withdrawal account )
     #[pallet::storage]
     #[pallet::getter(fn withdrawal_proposal)]
     pub(crate) type WithdrawalProposal<T: Config> =
         StorageValue<_, BtcWithdrawalProposal<T::AccountId>>;
 
+    /// The number of blocks a withdrawal proposal could wait for the trustee signatures, the
+    /// proposals never expire if not set.
+    #[pallet::storage]
+    #[pallet::getter(fn withdrawal_proposal_expiry)]
+    pub(crate) type WithdrawalProposalExpiry<T: Config> = StorageValue<_, T::BlockNumber>;
+
+    /// The block the current withdrawal proposal expires at unless fully signed.
+    #[pallet::storage]
+    #[pallet::getter(fn withdrawal_proposal_expire_at)]
+    pub(crate) type WithdrawalProposalExpireAt<T: Config> = StorageValue<_, T::BlockNumber>;
+
     /// get GenesisInfo (header, height)
     #[pallet::storage]
     #[pallet::getter(fn genesis_info)]
@@ -475,6 +900,17 @@ pub mod pallet {
     #[pallet::getter(fn btc_withdrawal_fee)]
     pub(crate) type BtcWithdrawalFee<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+    /// How the withdrawal fee is charged, the fixed `BtcWithdrawalFee` by default.
+    #[pallet::storage]
+    #[pallet::getter(fn btc_withdrawal_fee_policy)]
+    pub(crate) type BtcWithdrawalFeePolicy<T: Config> =
+        StorageValue<_, WithdrawalFeePolicy, ValueQuery>;
+
+    /// The bitcoin fee rate (sats/vB) reported by the trustees or the council.
+    #[pallet::storage]
+    #[pallet::getter(fn btc_fee_rate)]
+    pub(crate) type BtcFeeRate<T: Config> = StorageValue<_, u64, ValueQuery>;
+
     #[pallet::type_value]
     pub fn DefaultForMinDeposit<T: Config>() -> u64 {
         100000
@@ -500,6 +936,45 @@ pub mod pallet {
     #[pallet::getter(fn coming_bot)]
     pub(crate) type ComingBot<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+    /// The block and the number of the prioritized relay extrinsics of each sender in it.
+    #[pallet::storage]
+    pub(crate) type PrioritizedRelays<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+    /// The relayer allowlist, whose relays are feeless and prioritized.
+    #[pallet::storage]
+    #[pallet::getter(fn is_relayer)]
+    pub(crate) type Relayers<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+    /// The block and the number of the feeless relays of each allowlisted relayer in it.
+    #[pallet::storage]
+    pub(crate) type FeelessRelays<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            match call {
+                Call::report_withdrawal_broadcast { txid } => {
+                    match trustee::unreported_withdrawal_tx::<T>() {
+                        Some(tx) if tx.hash() == *txid => {}
+                        _ => return InvalidTransaction::Stale.into(),
+                    }
+                    ValidTransaction::with_tag_prefix("XGatewayBitcoinBroadcast")
+                        .priority(TransactionPriority::max_value())
+                        .and_provides(txid)
+                        .longevity(64)
+                        .propagate(true)
+                        .build()
+                }
+                _ => InvalidTransaction::Call.into(),
+            }
+        }
+    }
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub genesis_hash: H256,
@@ -534,22 +1009,8 @@ pub mod pallet {
     #[cfg(feature = "std")]
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
-            let genesis_hash = &self.genesis_hash.clone();
-            let (genesis_header, genesis_height) = &self.genesis_info.clone();
-            let genesis_index = BtcHeaderIndex {
-                hash: *genesis_hash,
-                height: *genesis_height,
-            };
-            let header_info = BtcHeaderInfo {
-                header: *genesis_header,
-                height: *genesis_height,
-            };
-
-            Headers::<T>::insert(&genesis_hash, header_info);
-            BlockHashFor::<T>::insert(&genesis_index.height, vec![genesis_hash]);
-            MainChain::<T>::insert(&genesis_hash, true);
-            BestIndex::<T>::put(genesis_index);
-            GenesisInfo::<T>::put(self.genesis_info);
+            let (genesis_header, genesis_height) = self.genesis_info;
+            Pallet::<T>::anchor_relay(self.genesis_hash, genesis_header, genesis_height);
             ParamsInfo::<T>::put(self.params_info);
             NetworkId::<T>::put(self.network_id);
             ConfirmationNumber::<T>::put(self.confirmation_number);
@@ -585,19 +1046,7 @@ pub mod pallet {
                 err
             })?;
 
-            match get_current_trustee_address_pair::<T>() {
-                Ok((hot_addr, cold_addr)) => {
-                    // do not allow withdraw from trustee address
-                    if address == hot_addr || address == cold_addr {
-                        return Err(Error::<T>::InvalidAddress.into());
-                    }
-                }
-                Err(err) => {
-                    log!(error, "[check_addr] Can not get trustee addr:{:?}", err);
-                }
-            }
-
-            Ok(())
+            Self::ensure_not_trustee_address(&address)
         }
 
         fn withdrawal_limit(
@@ -606,7 +1055,7 @@ pub mod pallet {
             if *asset_id != Self::ASSET_ID {
                 return Err(xpallet_assets::Error::<T>::ActionNotAllowed.into());
             }
-            let fee = Self::btc_withdrawal_fee().saturated_into();
+            let fee = Self::withdrawal_fee().saturated_into();
             let limit = WithdrawalLimit::<BalanceOf<T>> {
                 minimal_withdrawal: fee * 3u32.saturated_into() / 2u32.saturated_into(),
                 fee,
@@ -627,8 +1076,15 @@ pub mod pallet {
                 .sum::<u64>()
                 .saturated_into();
 
+            let deferred_deposits: BalanceOf<T> = DeferredDeposits::<T>::iter_values()
+                .map(|deposit| deposit.balance)
+                .sum::<u64>()
+                .saturated_into();
+
             let asset_supply = xpallet_assets::Pallet::<T>::total_issuance(&xp_protocol::X_BTC);
-            asset_supply.saturating_add(pending_deposits)
+            asset_supply
+                .saturating_add(pending_deposits)
+                .saturating_add(deferred_deposits)
         }
     }
 
@@ -637,9 +1093,79 @@ pub mod pallet {
         fn get_withdrawal_proposal() -> Option<Self::WithdrawalProposal> {
             Self::withdrawal_proposal()
         }
+
+        fn withdrawal_proposal_hash() -> Option<H256> {
+            Self::withdrawal_proposal().map(|proposal| proposal.tx.hash())
+        }
+
+        fn max_withdrawal_count() -> u32 {
+            Self::max_withdrawal_count()
+        }
+    }
+
+    impl<T: Config> ChainGateway<T::AccountId> for Pallet<T> {
+        fn relay_header(header: &[u8]) -> DispatchResult {
+            let header: BtcHeader = deserialize(header).map_err(|_| Error::<T>::DeserializeErr)?;
+            log!(debug, "[relay_header] header:{:?}", header);
+            Self::apply_push_header(header)
+        }
+
+        fn relay_transaction(tx: &[u8], proof: &[u8], prev_tx: Option<&[u8]>) -> DispatchResult {
+            let raw_tx = Self::deserialize_tx(tx)?;
+            let relayed_info: BtcRelayedTxInfo =
+                Decode::decode(&mut &proof[..]).map_err(|_| Error::<T>::DeserializeErr)?;
+            let prev_tx = prev_tx.map(Self::deserialize_tx).transpose()?;
+            let relay_tx = relayed_info.into_relayed_tx(raw_tx);
+            log!(
+                debug,
+                "[relay_transaction] relay_tx:{:?}, prev_tx:{:?}",
+                relay_tx,
+                prev_tx
+            );
+            Self::apply_push_transaction(relay_tx, prev_tx)
+        }
+
+        fn propose_withdrawal(
+            who: T::AccountId,
+            withdrawal_id_list: Vec<u32>,
+            tx: &[u8],
+        ) -> DispatchResult {
+            let tx = Self::deserialize_tx(tx)?;
+            log!(
+                debug,
+                "[propose_withdrawal] from:{:?}, withdrawal list:{:?}, tx:{:?}",
+                who,
+                withdrawal_id_list,
+                tx
+            );
+            Self::apply_create_taproot_withdraw(who, tx, withdrawal_id_list)
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Returns whether the relay of `relayer` pays the fee, the relay is feeless if
+        /// `relayer` is allowlisted and hasn't reached `MaxFeelessRelays` in the current block.
+        fn relay_pays_fee(relayer: &T::AccountId) -> Pays {
+            let feeless = Self::is_relayer(relayer)
+                && FeelessRelays::<T>::mutate(relayer, |(block, count)| {
+                    let now = frame_system::Pallet::<T>::block_number();
+                    if *block != now {
+                        *block = now;
+                        *count = 0;
+                    }
+                    if *count >= T::MaxFeelessRelays::get() {
+                        return false;
+                    }
+                    *count += 1;
+                    true
+                });
+            if feeless {
+                Pays::No
+            } else {
+                Pays::Yes
+            }
+        }
+
         /// Helper function for deserializing the slice of raw tx.
         #[inline]
         pub(crate) fn deserialize_tx(input: &[u8]) -> Result<Transaction, Error<T>> {
@@ -696,7 +1222,7 @@ pub mod pallet {
 
             let best_index = Self::best_index();
 
-            if header_info.height > best_index.height {
+            if header_info.header.previous_header_hash == best_index.hash {
                 // note update_confirmed_header would mutate other storage depend on BlockHashFor
                 let confirmed_index = header::update_confirmed_header::<T>(&header_info);
                 log!(
@@ -714,14 +1240,32 @@ pub mod pallet {
                 };
                 BestIndex::<T>::put(new_best_index);
             } else {
-                // forked chain
-                log!(
-                    info,
-                    "[apply_push_header] Best index {} larger than this height {}",
-                    best_index.height,
-                    header_info.height
-                );
-                header::check_confirmed_header::<T>(&header_info)?;
+                // forked chain, switch to it once it has more work than the main chain
+                let branch = header::look_back_side_branch::<T>(&header_info)?;
+                if branch.work > branch.main_work {
+                    let depth = best_index.height - branch.fork_point.height;
+                    log!(
+                        warn,
+                        "[apply_push_header] Reorg from {:?} to {:?}, fork point:{:?}, depth:{}",
+                        best_index,
+                        hash,
+                        branch.fork_point,
+                        depth
+                    );
+                    header::reorg::<T>(&branch, &header_info)?;
+                    BestIndex::<T>::put(BtcHeaderIndex {
+                        hash,
+                        height: header_info.height,
+                    });
+                    Self::deposit_event(Event::<T>::BtcReorg(best_index.hash, hash, depth));
+                } else {
+                    log!(
+                        info,
+                        "[apply_push_header] Side branch of header {:?} has less work than the best {:?}",
+                        hash,
+                        best_index
+                    );
+                }
             };
             Self::deposit_event(Event::<T>::HeaderInserted(hash));
             Ok(())
@@ -793,7 +1337,77 @@ pub mod pallet {
             }
         }
 
+        /// Puts the genesis header of the relay, which is also the best one.
+        pub(crate) fn anchor_relay(hash: H256, header: BtcHeader, height: u32) {
+            Headers::<T>::insert(&hash, BtcHeaderInfo { header, height });
+            BlockHashFor::<T>::insert(&height, vec![hash]);
+            MainChain::<T>::insert(&hash, true);
+            BestIndex::<T>::put(BtcHeaderIndex { hash, height });
+            GenesisInfo::<T>::put((header, height));
+        }
+
+        pub(crate) fn apply_reset_relay(header: BtcHeader, height: u32) -> DispatchResult {
+            let hash = header.hash();
+            ensure!(
+                height > Self::best_index().height && Self::block_hash_for(height).is_empty(),
+                Error::<T>::InvalidCheckpoint
+            );
+            ensure!(
+                is_valid_proof_of_work(Self::params_info().max_bits(), header.bits, hash),
+                Error::<T>::InvalidPoW
+            );
+            ensure!(
+                Self::withdrawal_proposal().is_none(),
+                Error::<T>::NotFinishProposal
+            );
+            ensure!(
+                DeferredDeposits::<T>::iter_keys().next().is_none(),
+                Error::<T>::UnsettledDeferredDeposits
+            );
+            ensure!(
+                PendingDeposits::<T>::iter_keys().next().is_none(),
+                Error::<T>::UnsettledPendingDeposits
+            );
+            ensure!(
+                xpallet_gateway_records::Pallet::<T>::withdrawals_list_by_chain(Chain::Bitcoin)
+                    .is_empty(),
+                Error::<T>::UnsettledWithdrawals
+            );
+            let best = Self::best_index();
+            let attested = Self::wallet_attestations(Self::last_attested_btc_height())
+                .filter(|attested| attested.attestation.btc_height >= best.height)
+                .ok_or(Error::<T>::UnattestedWallet)?;
+            ensure!(
+                attested.attestation.total_balance() <= attested.expected_balance,
+                Error::<T>::UnattestedWallet
+            );
+
+            // The confirmed headers are kept, the txs of which could still be relayed. The
+            // unconfirmed ones are removed from the main chain since they can't be confirmed
+            // any more, the confirmation restarts from the checkpoint.
+            let confirmed_height = Self::confirmed_index()
+                .map_or(Self::genesis_info().1, |confirmed| confirmed.height);
+            for h in confirmed_height.saturating_add(1)..=best.height {
+                for hash in Self::block_hash_for(h) {
+                    MainChain::<T>::remove(&hash);
+                }
+            }
+            ConfirmedIndex::<T>::kill();
+            Self::anchor_relay(hash, header, height);
+
+            log!(
+                warn,
+                "[apply_reset_relay] Relay reset to the checkpoint {:?}#{}",
+                hash_rev(hash),
+                height
+            );
+            Self::deposit_event(Event::<T>::RelayReset(hash, height));
+            Ok(())
+        }
+
+        #[transactional]
         pub(crate) fn apply_remove_proposal() -> DispatchResult {
+            WithdrawalProposalExpireAt::<T>::kill();
             if let Some(proposal) = WithdrawalProposal::<T>::take() {
                 for id in proposal.withdrawal_id_list.iter() {
                     xpallet_gateway_records::Pallet::<T>::set_withdrawal_state_by_root(
@@ -828,6 +1442,48 @@ pub mod pallet {
             Self::verify_bech32_address(data)
         }
 
+        /// Do not allow to withdraw to the current trustee addresses.
+        pub fn ensure_not_trustee_address(address: &Address) -> DispatchResult {
+            match get_current_trustee_address_pair::<T>() {
+                Ok((hot_addr, cold_addr)) => {
+                    if *address == hot_addr || *address == cold_addr {
+                        return Err(Error::<T>::InvalidAddress.into());
+                    }
+                }
+                Err(err) => {
+                    log!(error, "[check_addr] Can not get trustee addr:{:?}", err);
+                }
+            }
+            Ok(())
+        }
+
+        /// Verify the withdrawal address the same as `check_addr`, besides, the address must
+        /// belong to the bitcoin network of ChainX `network`, the signet addresses are the
+        /// same as the testnet ones.
+        pub fn verify_withdrawal_address(
+            data: &[u8],
+            network: NetworkType,
+        ) -> Result<AddressFormat, AddressError> {
+            let address =
+                Self::verify_btc_address(data).map_err(|_| AddressError::InvalidEncoding)?;
+            let expected_network = match network {
+                NetworkType::Mainnet => BtcNetwork::Mainnet,
+                NetworkType::Testnet => BtcNetwork::Testnet,
+            };
+            if address.network != expected_network {
+                return Err(AddressError::NetworkMismatch);
+            }
+            Self::ensure_not_trustee_address(&address).map_err(|_| AddressError::TrusteeAddress)?;
+
+            Ok(match address.hash {
+                AddressTypes::Legacy(_) => AddressFormat::Base58,
+                AddressTypes::WitnessV0KeyHash(_) | AddressTypes::WitnessV0ScriptHash(_) => {
+                    AddressFormat::Bech32
+                }
+                AddressTypes::WitnessV1Taproot(_) => AddressFormat::Bech32m,
+            })
+        }
+
         pub fn verify_tx_valid(
             raw_tx: Vec<u8>,
             withdrawal_id_list: Vec<u32>,
@@ -885,15 +1541,87 @@ pub mod pallet {
                 Ok(true)
             }
         }
+
+        /// Check whether `raw_tx` is included in the block `block_hash` by `merkle_proof` and
+        /// the block is confirmed, i.e., whether `push_transaction` would accept the proof.
+        ///
+        /// `raw_tx` and `merkle_proof` are in the bitcoin serialization.
+        pub fn verify_tx_inclusion(
+            raw_tx: Vec<u8>,
+            merkle_proof: Vec<u8>,
+            block_hash: H256,
+        ) -> BtcTxInclusion {
+            let tx = match Self::deserialize_tx(raw_tx.as_slice()) {
+                Ok(tx) => tx,
+                Err(_) => return BtcTxInclusion::DeserializeErr,
+            };
+            let merkle_proof: PartialMerkleTree = match deserialize(Reader::new(&merkle_proof)) {
+                Ok(merkle_proof) => merkle_proof,
+                Err(_) => return BtcTxInclusion::DeserializeErr,
+            };
+            let header_info = match Self::headers(&block_hash) {
+                Some(header_info) => header_info,
+                None => return BtcTxInclusion::UnknownBlock,
+            };
+
+            let tx_hash = tx.hash();
+            let relay_tx = BtcRelayedTx {
+                block_hash,
+                raw: tx,
+                merkle_proof,
+            };
+            let merkle_root = header_info.header.merkle_root_hash;
+            if tx::validate_transaction::<T>(&relay_tx, merkle_root, None).is_err() {
+                return BtcTxInclusion::BadMerkleProof;
+            }
+            if !Self::main_chain(&block_hash) {
+                return BtcTxInclusion::NotInMainChain;
+            }
+            let height = header_info.height;
+            let confirmed_height = Self::confirmed_index().map(|index| index.height);
+            if confirmed_height.map_or(true, |confirmed| height > confirmed) {
+                return BtcTxInclusion::Unconfirmed {
+                    height,
+                    confirmed_height,
+                };
+            }
+            match Self::tx_state(&tx_hash) {
+                Some(state) if state.result == BtcTxResult::Success => BtcTxInclusion::ProcessedTx,
+                _ => BtcTxInclusion::Confirmed { height },
+            }
+        }
     }
 
     /// Storage Query RPCs
     impl<T: Config> Pallet<T> {
+        /// Get the withdrawal fee charged under the current `WithdrawalFeePolicy`
+        pub fn withdrawal_fee() -> u64 {
+            match Self::btc_withdrawal_fee_policy() {
+                WithdrawalFeePolicy::Fixed => Self::btc_withdrawal_fee(),
+                WithdrawalFeePolicy::FeeRate {
+                    vsize,
+                    min_fee,
+                    max_fee,
+                } => Self::btc_fee_rate()
+                    .saturating_mul(vsize)
+                    .clamp(min_fee, max_fee),
+            }
+        }
+
         /// Get withdrawal proposal
         pub fn get_withdrawal_proposal() -> Option<BtcWithdrawalProposal<T::AccountId>> {
             Self::withdrawal_proposal()
         }
 
+        /// Get the txid and the raw tx of the fully signed withdrawal proposal not reported as
+        /// broadcasted.
+        pub fn get_unreported_withdrawal_tx() -> Option<(H256, Vec<u8>)> {
+            trustee::unreported_withdrawal_tx::<T>().map(|tx| {
+                let raw = serialize_with_flags(&tx, SERIALIZE_TRANSACTION_WITNESS);
+                (tx.hash(), raw.into())
+            })
+        }
+
         /// Get genesis info
         pub fn get_genesis_info() -> (BtcHeader, u32) {
             Self::genesis_info()
@@ -903,5 +1631,51 @@ pub mod pallet {
         pub fn get_btc_block_header(txid: H256) -> Option<BtcHeaderInfo> {
             Self::headers(txid)
         }
+
+        /// Get best btc block header index
+        pub fn get_best_index() -> BtcHeaderIndex {
+            Self::best_index()
+        }
+
+        /// Get the gateway status exported to the node metrics
+        pub fn get_gateway_metrics() -> BtcGatewayMetrics {
+            let pending_withdrawal_count =
+                xpallet_gateway_records::Pallet::<T>::withdrawals_list_by_chain(Chain::Bitcoin)
+                    .len() as u32;
+            BtcGatewayMetrics {
+                best_height: Self::best_index().height,
+                confirmed_height: Self::confirmed_index()
+                    .map(|index| index.height)
+                    .unwrap_or_default(),
+                pending_withdrawal_count,
+                hot_address_balance: <Self as TotalSupply<BalanceOf<T>>>::total_supply()
+                    .saturated_into(),
+            }
+        }
+
+        /// Get the gateway parameters for the wallets
+        pub fn get_gateway_params() -> BtcGatewayParams {
+            BtcGatewayParams {
+                network: Self::network_id(),
+                confirmation_number: Self::confirmation_number(),
+                min_deposit: Self::btc_min_deposit(),
+                withdrawal_fee: Self::withdrawal_fee(),
+                max_withdrawal_count: Self::max_withdrawal_count(),
+                hot_address: trustee::current_trustee_session::<T>()
+                    .ok()
+                    .map(|session| session.hot_address.addr),
+            }
+        }
+
+        /// Get the trustee hot address and the OP_RETURN data to deposit to `who`.
+        pub fn get_deposit_address(who: &T::AccountId) -> Option<DepositAddress<BalanceOf<T>>> {
+            let session = trustee::current_trustee_session::<T>().ok()?;
+            let format = <T as frame_system::Config>::SS58Prefix::get();
+            Some(DepositAddress {
+                address: session.hot_address.addr,
+                op_return: to_ss58_check(&who.encode(), format),
+                min_deposit: Self::btc_min_deposit().saturated_into(),
+            })
+        }
     }
 }
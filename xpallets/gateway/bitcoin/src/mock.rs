@@ -69,7 +69,7 @@ frame_support::construct_runtime!(
         XAssetsBridge: xpallet_assets_bridge::{Pallet, Call, Storage, Config<T>, Event<T>},
         XGatewayRecords: xpallet_gateway_records::{Pallet, Call, Storage, Event<T>},
         XGatewayCommon: xpallet_gateway_common::{Pallet, Call, Storage, Event<T>, Config<T>},
-        XGatewayBitcoin: xpallet_gateway_bitcoin::{Pallet, Call, Storage, Event<T>, Config<T>},
+        XGatewayBitcoin: xpallet_gateway_bitcoin::{Pallet, Call, Storage, Event<T>, Config<T>, ValidateUnsigned},
     }
 );
 
@@ -94,7 +94,7 @@ impl frame_system::Config for Test {
     type AccountId = AccountId;
     type Lookup = IdentityLookup<Self::AccountId>;
     type Header = Header;
-    type Event = ();
+    type Event = Event;
     type BlockHashCount = BlockHashCount;
     type DbWeight = ();
     type Version = ();
@@ -166,6 +166,7 @@ impl xpallet_assets_registrar::Config for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type TechnicalOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -186,6 +187,9 @@ impl xpallet_assets::Config for Test {
     type TreasuryAccount = SimpleTreasuryAccount;
     type OnCreatedAccount = frame_system::Provider<Test>;
     type OnAssetChanged = ();
+    type RestrictionOrigin = frame_system::EnsureRoot<AccountId>;
+    type DustReapBounty = ();
+    type DustReapExempt = frame_support::traits::Nothing;
     type WeightInfo = ();
 }
 
@@ -207,19 +211,40 @@ impl MultisigAddressFor<AccountId> for MultisigAddr {
     }
 }
 
+parameter_types! {
+    pub const StandbyTrusteeBond: Balance = 10;
+    pub const MaxSignatureShareLen: u32 = 1024;
+    pub const MaxArchivedTrusteeSessions: u32 = 2;
+}
+
 impl xpallet_gateway_common::Config for Test {
     type Event = ();
     type Validator = ();
     type DetermineMultisigAddress = MultisigAddr;
     type CouncilOrigin = EnsureSigned<AccountId>;
+    type ParameterOrigin = EnsureSigned<AccountId>;
+    type PauseOrigin = frame_system::EnsureRoot<AccountId>;
     type Bitcoin = XGatewayBitcoin;
     type BitcoinTrustee = XGatewayBitcoin;
     type BitcoinTrusteeSessionProvider = trustees::bitcoin::BtcTrusteeSessionManager<Test>;
     type BitcoinTotalSupply = XGatewayBitcoin;
     type BitcoinWithdrawalProposal = XGatewayBitcoin;
+    type Ethereum = MockEthereum;
+    type StandbyTrusteeBond = StandbyTrusteeBond;
+    type MaxSignatureShareLen = MaxSignatureShareLen;
+    type MaxArchivedTrusteeSessions = MaxArchivedTrusteeSessions;
     type WeightInfo = ();
 }
 
+pub struct MockEthereum;
+impl xpallet_assets::ChainT<Balance> for MockEthereum {
+    const ASSET_ID: AssetId = X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+
 thread_local! {
     pub static NOW: RefCell<Option<Duration>> = RefCell::new(None);
 }
@@ -288,9 +313,15 @@ impl Config for Test {
     type TrusteeSessionProvider =
         xpallet_gateway_common::trustees::bitcoin::BtcTrusteeSessionManager<Test>;
     type CouncilOrigin = EnsureSigned<AccountId>;
+    type ParameterOrigin = EnsureSigned<AccountId>;
+    type AttesterOrigin = frame_system::EnsureRoot<AccountId>;
     type TrusteeInfoUpdate = XGatewayCommon;
+    type WithdrawalBatch = XGatewayCommon;
+    type OperationPause = XGatewayCommon;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type MaxPrioritizedRelays = frame_support::traits::ConstU32<2>;
+    type MaxFeelessRelays = frame_support::traits::ConstU32<2>;
     type WeightInfo = ();
 }
 
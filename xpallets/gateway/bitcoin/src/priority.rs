@@ -0,0 +1,154 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Transaction pool priority of the relay extrinsics.
+//!
+//! The bitcoin header and tx pushes and the trustee signatures of the withdrawal proposal keep
+//! the bridge alive, they must not get stuck behind a flooded pool. [`PrioritizeRelay`] boosts
+//! their priority and shortens their longevity, since a stale relay is useless. Only the
+//! pushes of the allowlisted relayers and the signatures of the trustees of the proposal are
+//! boosted, the others are ordinary extrinsics paying the fees.
+//!
+//! No more than `MaxPrioritizedRelays` boosted extrinsics of a sender are included in a block,
+//! so that the boost can't be abused to take over the blocks. The validation of the ones over
+//! the cap fails with `ExhaustsResources`, the block builder skips them and the sender's
+//! following extrinsics, they stay in the pool for the following blocks.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::marker::PhantomData;
+
+use frame_support::traits::{Get, IsSubType};
+use sp_runtime::{
+    traits::{DispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        InvalidTransaction, TransactionLongevity, TransactionPriority, TransactionValidity,
+        TransactionValidityError, ValidTransaction,
+    },
+};
+
+use crate::{Call, Config, Pallet, PrioritizedRelays};
+
+/// The priority added to the relay extrinsics, above any fee based priority.
+pub const RELAY_PRIORITY_BOOST: TransactionPriority = TransactionPriority::max_value() / 2;
+
+/// The relay extrinsics are dropped from the pool after 64 blocks, i.e. about 6 minutes.
+pub const RELAY_LONGEVITY: TransactionLongevity = 64;
+
+/// Returns true if `call` of `who` is a relay extrinsic prioritized by [`PrioritizeRelay`].
+pub fn is_prioritized_relay<T: Config>(
+    who: &T::AccountId,
+    call: &<T as frame_system::Config>::Call,
+) -> bool
+where
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+    match call.is_sub_type() {
+        Some(Call::push_header { .. } | Call::push_transaction { .. }) => {
+            Pallet::<T>::is_relayer(who)
+        }
+        Some(Call::sign_withdrawal_proposal { .. }) => {
+            Pallet::<T>::withdrawal_proposal().map_or(false, |proposal| {
+                proposal
+                    .trustee_list
+                    .iter()
+                    .any(|(trustee, _)| trustee == who)
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Returns the number of the prioritized relays of `who` included in the current block.
+fn prioritized_relays<T: Config>(who: &T::AccountId) -> u32 {
+    let (block, count) = PrioritizedRelays::<T>::get(who);
+    if block == frame_system::Pallet::<T>::block_number() {
+        count
+    } else {
+        0
+    }
+}
+
+/// Boosts the priority of the relay extrinsics, with a per-block cap.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct PrioritizeRelay<T>(PhantomData<T>);
+
+impl<T> PrioritizeRelay<T> {
+    /// Create new `PrioritizeRelay`.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for PrioritizeRelay<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> sp_std::fmt::Debug for PrioritizeRelay<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "PrioritizeRelay")
+    }
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for PrioritizeRelay<T>
+where
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+    const IDENTIFIER: &'static str = "PrioritizeRelay";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if !is_prioritized_relay::<T>(who, call) {
+            return Ok(ValidTransaction::default());
+        }
+        if prioritized_relays::<T>(who) >= T::MaxPrioritizedRelays::get() {
+            return Err(InvalidTransaction::ExhaustsResources.into());
+        }
+        Ok(ValidTransaction {
+            priority: RELAY_PRIORITY_BOOST,
+            longevity: RELAY_LONGEVITY,
+            ..Default::default()
+        })
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        if !is_prioritized_relay::<T>(who, call) {
+            return Ok(());
+        }
+
+        let count = prioritized_relays::<T>(who);
+        if count >= T::MaxPrioritizedRelays::get() {
+            // Left in the pool by the block builder.
+            return Err(InvalidTransaction::ExhaustsResources.into());
+        }
+        let now = frame_system::Pallet::<T>::block_number();
+        PrioritizedRelays::<T>::insert(who, (now, count + 1));
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The trustee attestations of the BTC balances actually held at the trustee addresses.
+
+use frame_support::{dispatch::DispatchResult, ensure};
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use xpallet_gateway_common::traits::TotalSupply;
+
+use crate::{
+    log,
+    trustee::current_trustee_session,
+    types::{AttestedWalletBalance, WalletBalanceAttestation},
+    Config, Error, Event, LastAttestedBtcHeight, Pallet, PendingWalletAttestations,
+    WalletAttestations, WalletDeviationTolerance,
+};
+
+/// Signs the wallet balances at `btc_height` on behalf of the trustee `who`.
+///
+/// A trustee supports only one pending attestation at a time, the attestation is
+/// finalized once signed by the trustee threshold and then compared against the
+/// X-BTC supply.
+pub fn sign_wallet_balance<T: Config>(
+    who: T::AccountId,
+    btc_height: u32,
+    hot_balance: u64,
+    cold_balance: u64,
+) -> DispatchResult {
+    let trustee_session = current_trustee_session::<T>()?;
+    ensure!(
+        trustee_session.trustee_list.iter().any(|(n, _)| n == &who),
+        Error::<T>::NotTrustee
+    );
+    ensure!(
+        btc_height > Pallet::<T>::last_attested_btc_height()
+            && btc_height <= Pallet::<T>::best_index().height,
+        Error::<T>::InvalidAttestationHeight
+    );
+
+    let mut pending = Pallet::<T>::pending_wallet_attestations();
+    let index = match pending.iter().position(|attestation| {
+        attestation.btc_height == btc_height
+            && attestation.hot_balance == hot_balance
+            && attestation.cold_balance == cold_balance
+    }) {
+        Some(index) => {
+            ensure!(
+                !pending[index].signers.contains(&who),
+                Error::<T>::DuplicateVote
+            );
+            index
+        }
+        None => {
+            pending.push(WalletBalanceAttestation {
+                btc_height,
+                hot_balance,
+                cold_balance,
+                signers: vec![],
+            });
+            pending.len() - 1
+        }
+    };
+
+    // Withdraw the signature of the previously supported attestation.
+    for attestation in pending.iter_mut() {
+        attestation.signers.retain(|signer| signer != &who);
+    }
+    pending[index].signers.push(who.clone());
+    let signed = pending[index].clone();
+    pending.retain(|attestation| !attestation.signers.is_empty());
+
+    Pallet::<T>::deposit_event(Event::<T>::WalletBalanceSigned(
+        who,
+        btc_height,
+        hot_balance,
+        cold_balance,
+    ));
+
+    if signed.signers.len() >= usize::from(trustee_session.threshold) {
+        pending.retain(|attestation| attestation.btc_height > btc_height);
+        finalize_wallet_attestation::<T>(signed);
+    }
+    PendingWalletAttestations::<T>::put(pending);
+
+    Ok(())
+}
+
+/// Stores the attestation and alerts if the attested balance deviates from the
+/// X-BTC supply by more than the `WalletDeviationTolerance`.
+fn finalize_wallet_attestation<T: Config>(attestation: WalletBalanceAttestation<T::AccountId>) {
+    let btc_height = attestation.btc_height;
+    let actual_balance = attestation.total_balance();
+    let expected_balance: u64 =
+        <Pallet<T> as TotalSupply<_>>::total_supply().saturated_into::<u64>();
+
+    Pallet::<T>::deposit_event(Event::<T>::WalletBalanceAttested(
+        btc_height,
+        attestation.hot_balance,
+        attestation.cold_balance,
+        expected_balance,
+    ));
+
+    let deviation = if actual_balance > expected_balance {
+        actual_balance - expected_balance
+    } else {
+        expected_balance - actual_balance
+    };
+    if deviation > WalletDeviationTolerance::<T>::get() * expected_balance {
+        log!(
+            warn,
+            "[finalize_wallet_attestation] The attested balance {} at height {} deviates from the expected {}",
+            actual_balance,
+            btc_height,
+            expected_balance
+        );
+        Pallet::<T>::deposit_event(Event::<T>::WalletBalanceDeviated(
+            btc_height,
+            actual_balance,
+            expected_balance,
+        ));
+    }
+
+    LastAttestedBtcHeight::<T>::put(btc_height);
+    WalletAttestations::<T>::insert(
+        btc_height,
+        AttestedWalletBalance {
+            attestation,
+            expected_balance,
+        },
+    );
+}
@@ -5,16 +5,23 @@ use frame_support::{assert_noop, assert_ok};
 use light_bitcoin::{
     chain::{h256, BlockHeader},
     keys::Network,
+    primitives::H256,
     serialization,
 };
+use xp_gateway_bitcoin::OpReturnAccount;
+use xp_protocol::X_BTC;
 
 use crate::mock::alice;
 use crate::{
     mock::{
-        generate_blocks_478557_478563, generate_blocks_63290_63310, ExtBuilder, XGatewayBitcoin,
-        XGatewayBitcoinErr,
+        generate_blocks_478557_478563, generate_blocks_63290_63310, Event, ExtBuilder, System,
+        Test, XGatewayBitcoin, XGatewayBitcoinErr, XGatewayRecords,
     },
-    types::BtcHeaderIndex,
+    types::{
+        AttestedWalletBalance, BtcDepositCache, BtcHeaderIndex, DeferredDeposit,
+        WalletBalanceAttestation,
+    },
+    DeferredDeposits, LastAttestedBtcHeight, PendingDeposits, WalletAttestations,
 };
 
 #[test]
@@ -35,6 +42,10 @@ fn test_genesis() {
                 height: 63290
             }
         );
+
+        let metrics = XGatewayBitcoin::get_gateway_metrics();
+        assert_eq!(metrics.best_height, 63290);
+        assert_eq!(metrics.pending_withdrawal_count, 0);
     })
 }
 
@@ -78,6 +89,7 @@ fn test_insert_forked_headers_from_genesis_height() {
     ExtBuilder::default()
         .build_mock((*c1.get(1).unwrap(), base_height + 1), Network::Mainnet)
         .execute_with(|| {
+            System::set_block_number(1);
             // note: confirm block is 4
             assert_noop!(
                 XGatewayBitcoin::apply_push_header(*c1.get(1).unwrap()),
@@ -105,6 +117,18 @@ fn test_insert_forked_headers_from_genesis_height() {
             should_in_mainchain(&c1[2..3], false);
             should_in_mainchain(&forked[1..4], true);
 
+            let events = System::events()
+                .into_iter()
+                .map(|record| record.event)
+                .collect::<Vec<_>>();
+            assert!(
+                events.contains(&Event::XGatewayBitcoin(crate::Event::BtcReorg(
+                    c1.get(2).unwrap().hash(),
+                    forked.get(3).unwrap().hash(),
+                    1
+                )))
+            );
+
             // start insert normal
             assert_ok!(XGatewayBitcoin::apply_push_header(*c1.get(3).unwrap()));
             // because forked 3 insert before, so that even receive normal 3, best still forked 3
@@ -131,15 +155,31 @@ fn test_insert_forked_headers_from_genesis_height() {
             should_in_mainchain(&forked[2..4], false);
             println!("current confirmed height:{:?}", confirmed_index.height);
 
-            // add a forked block exceed confirmed height, this forked block would mark as AncientFork,
-            // but now this forked block is less then best, so do not do confirmed check
-            // and on the other hand, confirmed block for this forked block is before current normal
-            assert_ok!(XGatewayBitcoin::apply_push_header(*forked.get(4).unwrap()));
-            // but when add a more forked block, would try to move confirmed
+            // the side branch forking at or below the confirmed header is rejected, even
+            // within `MaxReorgDepth`
+            assert_eq!(XGatewayBitcoin::max_reorg_depth(), 6);
             assert_noop!(
-                XGatewayBitcoin::apply_push_header(*forked.get(5).unwrap()),
+                XGatewayBitcoin::apply_push_header(*forked.get(4).unwrap()),
                 XGatewayBitcoinErr::AncientFork,
             );
+
+            // `MaxReorgDepth` must be below the confirmation number
+            assert_noop!(
+                XGatewayBitcoin::set_max_reorg_depth(frame_system::RawOrigin::Root.into(), 4),
+                XGatewayBitcoinErr::ReorgDepthTooLarge,
+            );
+            assert_ok!(XGatewayBitcoin::set_max_reorg_depth(
+                frame_system::RawOrigin::Root.into(),
+                3
+            ));
+            System::assert_last_event(Event::XGatewayBitcoin(crate::Event::MaxReorgDepthSet(3)));
+            assert_noop!(
+                XGatewayBitcoin::apply_push_header(*forked.get(4).unwrap()),
+                XGatewayBitcoinErr::AncientFork,
+            );
+            let best_index = XGatewayBitcoin::best_index();
+            assert_eq!(best_index.hash, c1.get(5).unwrap().hash());
+            should_in_mainchain(&c1[1..6], true);
         })
 }
 
@@ -192,6 +232,162 @@ fn test_insert_forked_headers() {
         });
 }
 
+#[test]
+fn test_reorg_rolling_back_confirmed_index() {
+    // e.g.
+    // b0
+    // b --- b --- b --- b
+    //       |---- b --- b --- b
+    let (base_height, c1, forked) = generate_blocks_478557_478563();
+    ExtBuilder::default()
+        .build_mock((*c1.get(0).unwrap(), base_height), Network::Mainnet)
+        .execute_with(|| {
+            assert_ok!(XGatewayBitcoin::apply_push_header(*c1.get(1).unwrap()));
+            assert_ok!(XGatewayBitcoin::apply_push_header(*c1.get(2).unwrap()));
+            assert_ok!(XGatewayBitcoin::apply_push_header(*c1.get(3).unwrap()));
+            assert_ok!(XGatewayBitcoin::apply_push_header(*forked.get(2).unwrap()));
+            assert_ok!(XGatewayBitcoin::apply_push_header(*forked.get(3).unwrap()));
+            let confirmed_index = XGatewayBitcoin::confirmed_index().unwrap();
+            assert_eq!(confirmed_index.hash, c1.get(0).unwrap().hash());
+
+            // the side branch has no confirmed header under the raised confirmation number,
+            // the confirmed index is kept rather than removed
+            assert_ok!(XGatewayBitcoin::set_confirmed_number(
+                frame_system::RawOrigin::Root.into(),
+                6
+            ));
+            assert_noop!(
+                XGatewayBitcoin::apply_push_header(*forked.get(4).unwrap()),
+                XGatewayBitcoinErr::ConfirmedIndexRollback,
+            );
+            assert_eq!(XGatewayBitcoin::confirmed_index(), Some(confirmed_index));
+            let best_index = XGatewayBitcoin::best_index();
+            assert_eq!(best_index.hash, c1.get(3).unwrap().hash());
+            should_in_mainchain(&c1[0..4], true);
+            should_in_mainchain(&forked[2..4], false);
+
+            assert_ok!(XGatewayBitcoin::set_confirmed_number(
+                frame_system::RawOrigin::Root.into(),
+                4
+            ));
+            assert_ok!(XGatewayBitcoin::apply_push_header(*forked.get(4).unwrap()));
+            let best_index = XGatewayBitcoin::best_index();
+            assert_eq!(best_index.hash, forked.get(4).unwrap().hash());
+            let confirmed_index = XGatewayBitcoin::confirmed_index().unwrap();
+            assert_eq!(confirmed_index.hash, c1.get(1).unwrap().hash());
+        });
+}
+
+#[test]
+fn test_reset_relay() {
+    ExtBuilder::default().build_and_execute(|| {
+        let headers = generate_blocks_63290_63310();
+        let reset = |height: u32| {
+            XGatewayBitcoin::reset_relay(
+                frame_system::RawOrigin::Root.into(),
+                serialization::serialize(&headers[&height]).take(),
+                height,
+            )
+        };
+        for height in 63291..63295 {
+            assert_ok!(XGatewayBitcoin::apply_push_header(headers[&height]));
+        }
+
+        // the checkpoint must be above the best
+        assert_noop!(reset(63294), XGatewayBitcoinErr::InvalidCheckpoint);
+        let mut invalid = headers[&63300];
+        invalid.nonce += 1;
+        assert_noop!(
+            XGatewayBitcoin::apply_reset_relay(invalid, 63300),
+            XGatewayBitcoinErr::InvalidPoW
+        );
+        // the deferred deposits would be orphaned
+        let txid = H256::repeat_byte(1);
+        DeferredDeposits::<Test>::insert(
+            txid,
+            DeferredDeposit {
+                who: OpReturnAccount::Wasm(alice()),
+                balance: 100000000,
+                expire_at: 10,
+            },
+        );
+        assert_noop!(reset(63300), XGatewayBitcoinErr::UnsettledDeferredDeposits);
+        DeferredDeposits::<Test>::remove(txid);
+        // the pending deposits would be orphaned
+        let addr = b"1KPc1PEyd9ZiGcXa3NhQR1tHcMpfTFAKT2".to_vec();
+        PendingDeposits::<Test>::insert(
+            &addr,
+            vec![BtcDepositCache {
+                txid,
+                balance: 100000000,
+            }],
+        );
+        assert_noop!(reset(63300), XGatewayBitcoinErr::UnsettledPendingDeposits);
+        PendingDeposits::<Test>::remove(&addr);
+        // the withdrawals would be orphaned
+        assert_ok!(XGatewayRecords::deposit(&alice(), X_BTC, 1000000));
+        assert_ok!(XGatewayRecords::withdraw(
+            &alice(),
+            X_BTC,
+            100000,
+            b"tb1pexff2s7l58sthpyfrtx500ax234stcnt0gz2lr4kwe0ue95a2e0srxsc68".to_vec(),
+            b"".to_vec().into()
+        ));
+        assert_noop!(reset(63300), XGatewayBitcoinErr::UnsettledWithdrawals);
+        assert_ok!(XGatewayRecords::cancel_withdrawal(0, &alice()));
+        // the wallet balance must be attested at the best height without the unrelayed deposits
+        assert_noop!(reset(63300), XGatewayBitcoinErr::UnattestedWallet);
+        let attest = |btc_height: u32, balance: u64| {
+            LastAttestedBtcHeight::<Test>::put(btc_height);
+            WalletAttestations::<Test>::insert(
+                btc_height,
+                AttestedWalletBalance {
+                    attestation: WalletBalanceAttestation {
+                        btc_height,
+                        hot_balance: balance,
+                        cold_balance: 0,
+                        signers: vec![],
+                    },
+                    expected_balance: 1000000,
+                },
+            );
+        };
+        attest(63293, 1000000);
+        assert_noop!(reset(63300), XGatewayBitcoinErr::UnattestedWallet);
+        attest(63294, 1000001);
+        assert_noop!(reset(63300), XGatewayBitcoinErr::UnattestedWallet);
+        attest(63294, 1000000);
+
+        // the unconfirmed headers can't be confirmed after the reset
+        let confirmed_height = XGatewayBitcoin::confirmed_index().map_or(63290, |i| i.height);
+        assert_ok!(reset(63300));
+        for height in 63291..63295 {
+            let hash = headers[&height].hash();
+            assert_eq!(
+                XGatewayBitcoin::main_chain(&hash),
+                height <= confirmed_height
+            );
+        }
+        let hash = headers[&63300].hash();
+        assert_eq!(
+            XGatewayBitcoin::best_index(),
+            BtcHeaderIndex {
+                hash,
+                height: 63300
+            }
+        );
+        assert_eq!(XGatewayBitcoin::genesis_info(), (headers[&63300], 63300));
+        assert_eq!(XGatewayBitcoin::confirmed_index(), None);
+
+        // the relay continues from the checkpoint
+        for height in 63301..63305 {
+            assert_ok!(XGatewayBitcoin::apply_push_header(headers[&height]));
+        }
+        let confirmed_index = XGatewayBitcoin::confirmed_index().unwrap();
+        assert_eq!(confirmed_index.height, 63301);
+    })
+}
+
 #[test]
 fn test_change_difficulty() {
     ExtBuilder::default().build_and_execute(|| {
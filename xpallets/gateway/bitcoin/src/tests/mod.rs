@@ -1,18 +1,27 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
 mod header;
+mod priority;
 mod trustee;
 mod tx;
 
 use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormatRegistry};
 
+use xp_address::{AddressError, AddressFormat};
 use xp_gateway_common::AccountExtractor;
+use xp_protocol::NetworkType;
 
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
 use light_bitcoin::script::Script;
 
 use crate::{
-    mock::{Test, XGatewayBitcoin},
-    Config,
+    mock::{
+        alice, bob, charlie, ExtBuilder, Origin, System, Test, XGatewayBitcoin, XGatewayBitcoinErr,
+        XGatewayRecords, X_BTC,
+    },
+    types::{BtcHeaderIndex, WithdrawalFeePolicy},
+    Config, Event,
 };
 
 #[test]
@@ -21,6 +30,42 @@ pub fn test_verify_btc_address() {
     assert!(XGatewayBitcoin::verify_btc_address(&address).is_ok());
 }
 
+#[test]
+pub fn test_verify_withdrawal_address() {
+    ExtBuilder::default().build().execute_with(|| {
+        let verify =
+            |address: &[u8], network| XGatewayBitcoin::verify_withdrawal_address(address, network);
+        let testnet_p2pkh = b"mqVznxoxdeSNYgDCg6ZVE5pc6476BY6zHK";
+        let mainnet_p2wpkh = b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        let mainnet_p2tr = b"bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297";
+
+        assert_eq!(
+            verify(testnet_p2pkh, NetworkType::Testnet),
+            Ok(AddressFormat::Base58)
+        );
+        assert_eq!(
+            verify(mainnet_p2wpkh, NetworkType::Mainnet),
+            Ok(AddressFormat::Bech32)
+        );
+        assert_eq!(
+            verify(mainnet_p2tr, NetworkType::Mainnet),
+            Ok(AddressFormat::Bech32m)
+        );
+        assert_eq!(
+            verify(testnet_p2pkh, NetworkType::Mainnet),
+            Err(AddressError::NetworkMismatch)
+        );
+        assert_eq!(
+            verify(mainnet_p2tr, NetworkType::Testnet),
+            Err(AddressError::NetworkMismatch)
+        );
+        assert_eq!(
+            verify(b"invalid", NetworkType::Mainnet),
+            Err(AddressError::InvalidEncoding)
+        );
+    });
+}
+
 #[test]
 fn test_account_ss58_version() {
     set_default_ss58_version(Ss58AddressFormatRegistry::ChainxAccount.into());
@@ -32,3 +77,163 @@ fn test_account_ss58_version() {
     let data = script.to_bytes();
     assert!(<Test as Config>::AccountExtractor::extract_account(&data).is_some());
 }
+
+#[test]
+fn test_withdrawal_fee_policy() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayBitcoin::set_btc_withdrawal_fee(
+            RawOrigin::Root.into(),
+            500000
+        ));
+        assert_eq!(XGatewayBitcoin::withdrawal_fee(), 500000);
+
+        assert_noop!(
+            XGatewayBitcoin::set_withdrawal_fee_policy(
+                RawOrigin::Root.into(),
+                WithdrawalFeePolicy::FeeRate {
+                    vsize: 200,
+                    min_fee: 20000,
+                    max_fee: 10000,
+                }
+            ),
+            XGatewayBitcoinErr::InvalidWithdrawalFeePolicy
+        );
+        assert_ok!(XGatewayBitcoin::set_withdrawal_fee_policy(
+            RawOrigin::Root.into(),
+            WithdrawalFeePolicy::FeeRate {
+                vsize: 200,
+                min_fee: 10000,
+                max_fee: 1000000,
+            }
+        ));
+
+        // bounded by the min fee before any rate is reported
+        assert_eq!(XGatewayBitcoin::withdrawal_fee(), 10000);
+        assert_ok!(XGatewayBitcoin::set_btc_fee_rate(
+            RawOrigin::Root.into(),
+            100
+        ));
+        assert_eq!(XGatewayBitcoin::withdrawal_fee(), 20000);
+        assert_ok!(XGatewayBitcoin::set_btc_fee_rate(
+            RawOrigin::Root.into(),
+            100000
+        ));
+        assert_eq!(XGatewayBitcoin::withdrawal_fee(), 1000000);
+
+        assert_ok!(XGatewayBitcoin::set_withdrawal_fee_policy(
+            RawOrigin::Root.into(),
+            WithdrawalFeePolicy::Fixed
+        ));
+        assert_eq!(XGatewayBitcoin::withdrawal_fee(), 500000);
+    });
+}
+
+#[test]
+fn test_wallet_balance_attestation() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayRecords::deposit(&alice(), X_BTC, 100000000));
+        let height = XGatewayBitcoin::best_index().height;
+
+        assert_noop!(
+            XGatewayBitcoin::attest_wallet_balance(
+                Origin::signed(sp_runtime::AccountId32::new([9; 32])),
+                height,
+                60000000,
+                40000000
+            ),
+            XGatewayBitcoinErr::NotTrustee
+        );
+        assert_noop!(
+            XGatewayBitcoin::attest_wallet_balance(
+                Origin::signed(alice()),
+                height + 1,
+                60000000,
+                40000000
+            ),
+            XGatewayBitcoinErr::InvalidAttestationHeight
+        );
+
+        // finalized once signed by 2 of the 3 trustees
+        assert_ok!(XGatewayBitcoin::attest_wallet_balance(
+            Origin::signed(alice()),
+            height,
+            60000000,
+            40000000
+        ));
+        assert_noop!(
+            XGatewayBitcoin::attest_wallet_balance(
+                Origin::signed(alice()),
+                height,
+                60000000,
+                40000000
+            ),
+            XGatewayBitcoinErr::DuplicateVote
+        );
+        assert!(XGatewayBitcoin::wallet_attestations(height).is_none());
+        assert_ok!(XGatewayBitcoin::attest_wallet_balance(
+            Origin::signed(bob()),
+            height,
+            60000000,
+            40000000
+        ));
+        let attested = XGatewayBitcoin::wallet_attestations(height).unwrap();
+        assert_eq!(attested.attestation.signers, vec![alice(), bob()]);
+        assert_eq!(attested.expected_balance, 100000000);
+        assert_eq!(XGatewayBitcoin::last_attested_btc_height(), height);
+        assert!(XGatewayBitcoin::pending_wallet_attestations().is_empty());
+        assert_noop!(
+            XGatewayBitcoin::attest_wallet_balance(
+                Origin::signed(charlie()),
+                height,
+                60000000,
+                40000000
+            ),
+            XGatewayBitcoinErr::InvalidAttestationHeight
+        );
+
+        // a trustee supports only one pending attestation
+        let best = XGatewayBitcoin::best_index();
+        assert_ok!(XGatewayBitcoin::set_best_index(
+            RawOrigin::Root.into(),
+            BtcHeaderIndex {
+                hash: best.hash,
+                height: height + 1,
+            }
+        ));
+        assert_ok!(XGatewayBitcoin::attest_wallet_balance(
+            Origin::signed(alice()),
+            height + 1,
+            95000000,
+            0
+        ));
+        assert_ok!(XGatewayBitcoin::attest_wallet_balance(
+            Origin::signed(alice()),
+            height + 1,
+            90000000,
+            0
+        ));
+        let pending = XGatewayBitcoin::pending_wallet_attestations();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].hot_balance, 90000000);
+
+        // the deviation beyond the tolerance is alerted
+        assert_ok!(XGatewayBitcoin::attest_wallet_balance(
+            Origin::signed(charlie()),
+            height + 1,
+            90000000,
+            0
+        ));
+        let deviated = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                crate::mock::Event::XGatewayBitcoin(Event::WalletBalanceDeviated(
+                    btc_height,
+                    actual,
+                    expected,
+                )) => Some((btc_height, actual, expected)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(deviated, vec![(height + 1, 90000000, 100000000)]);
+    });
+}
@@ -0,0 +1,107 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    assert_ok,
+    weights::{DispatchInfo, Pays},
+};
+use sp_runtime::{
+    traits::SignedExtension,
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+use light_bitcoin::{chain::Transaction, serialization};
+
+use crate::{
+    mock::{
+        alice, bob, charlie, generate_blocks_63290_63310, Call, ExtBuilder, Origin, System, Test,
+        XGatewayBitcoin,
+    },
+    priority::{PrioritizeRelay, RELAY_LONGEVITY, RELAY_PRIORITY_BOOST},
+    types::{BtcWithdrawalProposal, VoteResult},
+    WithdrawalProposal,
+};
+
+#[test]
+pub fn test_prioritize_relay() {
+    ExtBuilder::default().build_and_execute(|| {
+        let info = DispatchInfo::default();
+        let relay = Call::XGatewayBitcoin(crate::Call::push_header { header: vec![] });
+        let sign = Call::XGatewayBitcoin(crate::Call::sign_withdrawal_proposal {});
+        let other = Call::System(frame_system::Call::remark { remark: vec![] });
+        let priority = |who, call| {
+            PrioritizeRelay::<Test>::new()
+                .validate(&who, call, &info, 0)
+                .map(|valid| valid.priority)
+        };
+        let pre_dispatch =
+            |who, call| PrioritizeRelay::<Test>::new().pre_dispatch(&who, call, &info, 0);
+
+        // Only the relays of the allowlisted relayers are prioritized.
+        assert_eq!(priority(alice(), &relay), Ok(0));
+        assert_ok!(XGatewayBitcoin::set_relayer(Origin::root(), alice(), true));
+        assert_ok!(XGatewayBitcoin::set_relayer(Origin::root(), bob(), true));
+        let valid = PrioritizeRelay::<Test>::new()
+            .validate(&alice(), &relay, &info, 0)
+            .unwrap();
+        assert_eq!(valid.priority, RELAY_PRIORITY_BOOST);
+        assert_eq!(valid.longevity, RELAY_LONGEVITY);
+        assert_eq!(priority(alice(), &other), Ok(0));
+
+        // Only the signatures of the trustees of the proposal are prioritized.
+        assert_eq!(priority(charlie(), &sign), Ok(0));
+        WithdrawalProposal::<Test>::put(BtcWithdrawalProposal {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list: vec![0],
+            tx: Transaction::default(),
+            trustee_list: vec![(charlie(), false)],
+        });
+        assert_eq!(priority(charlie(), &sign), Ok(RELAY_PRIORITY_BOOST));
+
+        // At most 2 relay extrinsics of a sender are prioritized in a block.
+        let exhausted = TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources);
+        assert!(pre_dispatch(alice(), &relay).is_ok());
+        assert!(pre_dispatch(alice(), &relay).is_ok());
+        assert_eq!(pre_dispatch(alice(), &relay), Err(exhausted));
+        assert_eq!(priority(alice(), &relay), Err(exhausted));
+        assert!(pre_dispatch(alice(), &other).is_ok());
+        assert!(pre_dispatch(bob(), &relay).is_ok());
+
+        System::set_block_number(2);
+        assert!(pre_dispatch(alice(), &relay).is_ok());
+    });
+}
+
+#[test]
+pub fn test_feeless_relayer() {
+    ExtBuilder::default().build_and_execute(|| {
+        let headers = generate_blocks_63290_63310();
+        let header = |height| serialization::serialize(&headers[&height]).take();
+        let pays_fee = |who| {
+            XGatewayBitcoin::push_header(Origin::signed(who), vec![])
+                .unwrap_err()
+                .post_info
+                .pays_fee
+        };
+
+        // The relays of the others pay the fees, whether they succeed or not.
+        let post_info = XGatewayBitcoin::push_header(Origin::signed(bob()), header(63291)).unwrap();
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+        assert_eq!(pays_fee(bob()), Pays::Yes);
+
+        assert_ok!(XGatewayBitcoin::set_relayer(Origin::root(), alice(), true));
+        assert!(XGatewayBitcoin::is_relayer(&alice()));
+
+        // At most 2 relays of an allowlisted relayer are feeless in a block.
+        let post_info =
+            XGatewayBitcoin::push_header(Origin::signed(alice()), header(63292)).unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(pays_fee(alice()), Pays::No);
+        assert_eq!(pays_fee(alice()), Pays::Yes);
+
+        System::set_block_number(2);
+        assert_eq!(pays_fee(alice()), Pays::No);
+
+        assert_ok!(XGatewayBitcoin::set_relayer(Origin::root(), alice(), false));
+        assert_eq!(pays_fee(alice()), Pays::Yes);
+    });
+}
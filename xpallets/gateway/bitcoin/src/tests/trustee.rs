@@ -156,3 +156,28 @@ fn test_create_taproot_address() {
         )
     })
 }
+
+#[test]
+fn test_get_deposit_address() {
+    use sp_core::crypto::Ss58Codec;
+    use xp_gateway_bitcoin::{AccountExtractor, OpReturnAccount, OpReturnExtractor};
+
+    use crate::mock::alice;
+
+    ExtBuilder::default().build_and_execute(|| {
+        let deposit = XGatewayBitcoin::get_deposit_address(&alice()).unwrap();
+        assert_eq!(
+            Some(deposit.address),
+            XGatewayBitcoin::get_gateway_params().hot_address
+        );
+        assert_eq!(
+            deposit.op_return,
+            alice().to_ss58check_with_version(42u16.into()).into_bytes()
+        );
+        assert_eq!(deposit.min_deposit, 100000);
+        assert_eq!(
+            OpReturnExtractor::extract_account(&deposit.op_return),
+            Some((OpReturnAccount::Wasm(alice()), None))
+        );
+    });
+}
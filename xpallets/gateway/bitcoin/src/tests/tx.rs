@@ -3,29 +3,37 @@
 #![allow(non_upper_case_globals)]
 
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use sp_core::{
     crypto::{set_default_ss58_version, Ss58AddressFormatRegistry},
     H160,
 };
+use sp_runtime::{
+    traits::ValidateUnsigned,
+    transaction_validity::{InvalidTransaction, TransactionSource},
+};
 
 use light_bitcoin::{
     chain::Transaction,
     keys::{Address, Network},
     merkle::PartialMerkleTree,
+    primitives::H256,
     serialization::{self, Reader},
 };
 
-use xp_gateway_bitcoin::{AccountExtractor, BtcTxMetaType, BtcTxType, BtcTxTypeDetector};
+use xp_gateway_bitcoin::{
+    AccountExtractor, BtcTxMetaType, BtcTxType, BtcTxTypeDetector, OpReturnAccount,
+};
+use xpallet_gateway_records::WithdrawalStatus;
 
 use crate::{
     mock::*,
     tx::process_tx,
     types::{
-        BtcDepositCache, BtcRelayedTxInfo, BtcTxResult, BtcTxState, BtcWithdrawalProposal,
-        VoteResult,
+        BtcDepositCache, BtcRelayedTxInfo, BtcTxInclusion, BtcTxResult, BtcTxState,
+        BtcWithdrawalProposal, DepositAttestationConfig, VoteResult,
     },
-    Config, WithdrawalProposal,
+    Config, PendingDeposits, WithdrawalProposal, WithdrawalProposalExpireAt,
 };
 
 // Tyoe is p2tr. Address farmat is Mainnet.:
@@ -355,3 +363,403 @@ fn test_push_tx_call() {
         );
     });
 }
+
+#[test]
+fn test_verify_tx_inclusion() {
+    set_default_ss58_version(Ss58AddressFormatRegistry::ChainxAccount.into());
+    // https://blockchain.info/rawtx/f1a9161a045a01db7ae02b8c0531e2fe2e9740efe30afe6d84a12e3cac251344?format=hex
+    let normal_deposit: Transaction = "02000000000101aeee49e0bbf7a36f78ea4321b5c8bae0b8c72bdf2c024d2484b137fa7d0f8e1f01000000000000000003a0860100000000002251209a9ea267884f5549c206b2aec2bd56d98730f90532ea7f7154d4d4f923b7e3bb0000000000000000326a3035516a706f3772516e7751657479736167477a6334526a376f737758534c6d4d7141754332416255364c464646476a38801a060000000000225120c9929543dfa1e0bb84891acd47bfa6546b05e26b7a04af8eb6765fcc969d565f01409e325889515ed47099fdd7098e6fafdc880b21456d3f368457de923f4229286e34cef68816348a0581ae5885ede248a35ac4b09da61a7b9b90f34c200872d2e300000000".parse().unwrap();
+    let tx: Vec<u8> = serialization::serialize(&normal_deposit).into();
+    let headers = generate_blocks_63290_63310();
+    let block_hash = headers[&63299].hash();
+
+    let raw_proof = hex::decode("0a000000050a59b195a68a29037580798ca0414941eb46eaf7607db2d0da1ff89e9570ce455fea22ec1a3e3e7e1167fa220cc8376225f07bd20aa194e7f3c4ac68c7375d8e0a35e47541de7d0aa7312dabcf3bc9f06603e832427b8e4fe9a97a309f8cd7141687d11a3fd8f21e2105a52a3c36a17ea870e326ecddb23221d4cc0398b6c44bdcce3f191919a31f4cfaca5a786cc8315db76683ad6b8008f2ed9b348df76a0d022f00").unwrap();
+
+    ExtBuilder::default().build_and_execute(|| {
+        let verify = |raw_tx: &[u8], block_hash| {
+            XGatewayBitcoin::verify_tx_inclusion(raw_tx.to_vec(), raw_proof.clone(), block_hash)
+        };
+
+        assert_eq!(verify(&[0], block_hash), BtcTxInclusion::DeserializeErr);
+        assert_eq!(verify(&tx, block_hash), BtcTxInclusion::UnknownBlock);
+
+        for i in 63291..=63299 {
+            assert_ok!(XGatewayBitcoin::apply_push_header(headers[&i]));
+        }
+        assert!(matches!(
+            verify(&tx, block_hash),
+            BtcTxInclusion::Unconfirmed { height: 63299, .. }
+        ));
+        // the proof is not for the block
+        assert_eq!(
+            verify(&tx, headers[&63298].hash()),
+            BtcTxInclusion::BadMerkleProof
+        );
+
+        let confirmed = XGatewayBitcoin::confirmation_number();
+        for i in 63300..=63299 + confirmed {
+            assert_ok!(XGatewayBitcoin::apply_push_header(headers[&i]));
+        }
+        assert_eq!(
+            verify(&tx, block_hash),
+            BtcTxInclusion::Confirmed { height: 63299 }
+        );
+
+        let proof: PartialMerkleTree = serialization::deserialize(Reader::new(&raw_proof)).unwrap();
+        let info = BtcRelayedTxInfo {
+            block_hash,
+            merkle_proof: proof,
+        }
+        .encode();
+        assert_ok!(XGatewayBitcoin::push_transaction(
+            frame_system::RawOrigin::Signed(alice()).into(),
+            tx.clone(),
+            info,
+            None,
+        ));
+        assert_eq!(verify(&tx, block_hash), BtcTxInclusion::ProcessedTx);
+    });
+}
+
+#[test]
+fn test_claim_pending_deposits() {
+    ExtBuilder::default().build_and_execute(|| {
+        let addr = b"3AWmpzJ1kSF1cktFTDEb3qmLcdN8YydxA7".to_vec();
+        let records = (1..=3)
+            .map(|i| BtcDepositCache {
+                txid: H256::repeat_byte(i),
+                balance: 100000 * i as u64,
+            })
+            .collect::<Vec<_>>();
+        PendingDeposits::<Test>::insert(&addr, records.clone());
+        let who = OpReturnAccount::Wasm(bob());
+
+        assert_noop!(
+            XGatewayBitcoin::claim_pending_deposits(
+                frame_system::RawOrigin::Root.into(),
+                addr.clone(),
+                who.clone(),
+                0
+            ),
+            XGatewayBitcoinErr::NoPendingDeposit,
+        );
+
+        assert_ok!(XGatewayBitcoin::claim_pending_deposits(
+            frame_system::RawOrigin::Root.into(),
+            addr.clone(),
+            who.clone(),
+            2
+        ));
+        assert_eq!(XAssets::usable_balance(&bob(), &X_BTC), 300000);
+        assert_eq!(
+            XGatewayBitcoin::pending_deposits(&addr),
+            records[2..].to_vec()
+        );
+
+        assert_ok!(XGatewayBitcoin::claim_pending_deposits(
+            frame_system::RawOrigin::Root.into(),
+            addr.clone(),
+            who.clone(),
+            10
+        ));
+        assert_eq!(XAssets::usable_balance(&bob(), &X_BTC), 600000);
+        assert!(XGatewayBitcoin::pending_deposits(&addr).is_empty());
+
+        assert_noop!(
+            XGatewayBitcoin::claim_pending_deposits(
+                frame_system::RawOrigin::Root.into(),
+                addr,
+                who,
+                10
+            ),
+            XGatewayBitcoinErr::NoPendingDeposit,
+        );
+    });
+}
+
+#[test]
+fn test_deferred_deposit_attestation() {
+    set_default_ss58_version(Ss58AddressFormatRegistry::ChainxAccount.into());
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = deposit_taproot2.hash();
+        let config = DepositAttestationConfig {
+            threshold: 50000,
+            expiry: 10,
+        };
+        assert_noop!(
+            XGatewayBitcoin::set_deposit_attestation(
+                frame_system::RawOrigin::Root.into(),
+                Some(DepositAttestationConfig {
+                    threshold: 50000,
+                    expiry: 0,
+                })
+            ),
+            XGatewayBitcoinErr::InvalidAttestationConfig,
+        );
+        assert_ok!(XGatewayBitcoin::set_deposit_attestation(
+            frame_system::RawOrigin::Root.into(),
+            Some(config)
+        ));
+
+        // the deposit above the threshold is deferred until attested
+        let r = mock_process_tx::<Test>(deposit_taproot2.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+        assert_eq!(XAssets::usable_balance(&op_account, &X_BTC), 0);
+        assert!(XGatewayBitcoin::deferred_deposits(txid).is_some());
+
+        assert_noop!(
+            XGatewayBitcoin::attest_deposit(Origin::signed(alice()), txid),
+            sp_runtime::DispatchError::BadOrigin,
+        );
+        assert_ok!(XGatewayBitcoin::attest_deposit(
+            frame_system::RawOrigin::Root.into(),
+            txid
+        ));
+        assert_eq!(XAssets::usable_balance(&op_account, &X_BTC), 100000);
+        assert_noop!(
+            XGatewayBitcoin::attest_deposit(frame_system::RawOrigin::Root.into(), txid),
+            XGatewayBitcoinErr::NoDeferredDeposit,
+        );
+
+        // the deposit expires if not attested in time
+        let r = mock_process_tx::<Test>(deposit_taproot2.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+        let expire_at = XGatewayBitcoin::deferred_deposits(txid).unwrap().expire_at;
+        XGatewayBitcoin::on_initialize(expire_at);
+        assert!(XGatewayBitcoin::deferred_deposits(txid).is_none());
+        assert_eq!(XAssets::usable_balance(&op_account, &X_BTC), 100000);
+
+        // the deposit below the threshold is credited directly
+        assert_ok!(XGatewayBitcoin::set_deposit_attestation(
+            frame_system::RawOrigin::Root.into(),
+            Some(DepositAttestationConfig {
+                threshold: 100000,
+                expiry: 10,
+            })
+        ));
+        let r = mock_process_tx::<Test>(deposit_taproot2.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Success);
+        assert_eq!(XAssets::usable_balance(&op_account, &X_BTC), 200000);
+    });
+}
+
+#[test]
+fn test_withdrawal_proposal_expiry() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XGatewayBitcoin::set_withdrawal_proposal_expiry(
+                frame_system::RawOrigin::Root.into(),
+                Some(0)
+            ),
+            XGatewayBitcoinErr::InvalidProposalExpiry,
+        );
+        assert_ok!(XGatewayBitcoin::set_withdrawal_proposal_expiry(
+            frame_system::RawOrigin::Root.into(),
+            Some(10)
+        ));
+
+        assert_ok!(XGatewayRecords::deposit(&alice(), X_BTC, 1000000));
+        for _ in 0..2 {
+            assert_ok!(XGatewayRecords::withdraw(
+                &alice(),
+                X_BTC,
+                100000,
+                b"tb1pexff2s7l58sthpyfrtx500ax234stcnt0gz2lr4kwe0ue95a2e0srxsc68".to_vec(),
+                b"".to_vec().into()
+            ));
+        }
+        let propose = || {
+            assert_ok!(XGatewayRecords::process_withdrawals(
+                &[0, 1],
+                xpallet_assets::Chain::Bitcoin
+            ));
+            WithdrawalProposal::<Test>::put(BtcWithdrawalProposal {
+                sig_state: VoteResult::Unfinish,
+                withdrawal_id_list: vec![0, 1],
+                tx: withdraw_taproot2.clone(),
+                trustee_list: vec![(alice(), false), (bob(), false), (charlie(), false)],
+            });
+            WithdrawalProposalExpireAt::<Test>::put(System::block_number() + 10);
+        };
+
+        // the proposal not fully signed is dissolved once expired
+        propose();
+        assert_noop!(
+            XGatewayBitcoin::sign_withdrawal_proposal(Origin::signed(AccountId::new([9; 32]))),
+            XGatewayBitcoinErr::NotTrustee,
+        );
+        assert_ok!(XGatewayBitcoin::sign_withdrawal_proposal(Origin::signed(
+            alice()
+        )));
+        assert_noop!(
+            XGatewayBitcoin::sign_withdrawal_proposal(Origin::signed(alice())),
+            XGatewayBitcoinErr::DuplicateVote,
+        );
+        let expire_at = XGatewayBitcoin::withdrawal_proposal_expire_at().unwrap();
+        XGatewayBitcoin::on_initialize(expire_at - 1);
+        assert!(XGatewayBitcoin::withdrawal_proposal().is_some());
+        XGatewayBitcoin::on_initialize(expire_at);
+        assert!(XGatewayBitcoin::withdrawal_proposal().is_none());
+        assert!(XGatewayBitcoin::withdrawal_proposal_expire_at().is_none());
+        for id in [0, 1] {
+            assert_eq!(
+                XGatewayRecords::state_of(id),
+                Some(xpallet_gateway_records::WithdrawalState::Applying)
+            );
+        }
+        assert!(System::events().into_iter().any(|record| record.event
+            == Event::XGatewayBitcoin(crate::Event::WithdrawalProposalExpired(
+                vec![0, 1],
+                vec![bob(), charlie()]
+            ))));
+
+        // the proposal fully signed never expires
+        propose();
+        assert_ok!(XGatewayBitcoin::sign_withdrawal_proposal(Origin::signed(
+            alice()
+        )));
+        assert_ok!(XGatewayBitcoin::sign_withdrawal_proposal(Origin::signed(
+            bob()
+        )));
+        assert_eq!(
+            XGatewayBitcoin::withdrawal_proposal().unwrap().sig_state,
+            VoteResult::Finish
+        );
+        XGatewayBitcoin::on_initialize(XGatewayBitcoin::withdrawal_proposal_expire_at().unwrap());
+        assert!(XGatewayBitcoin::withdrawal_proposal().is_some());
+    });
+}
+
+#[test]
+fn test_report_withdrawal_tx() {
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = withdraw_taproot2.hash();
+        assert_noop!(
+            XGatewayBitcoin::report_withdrawal_tx(Origin::signed(alice()), txid),
+            XGatewayBitcoinErr::NoProposal,
+        );
+
+        assert_ok!(XGatewayRecords::deposit(&alice(), X_BTC, 1000000));
+        for _ in 0..2 {
+            assert_ok!(XGatewayRecords::withdraw(
+                &alice(),
+                X_BTC,
+                100000,
+                b"tb1pexff2s7l58sthpyfrtx500ax234stcnt0gz2lr4kwe0ue95a2e0srxsc68".to_vec(),
+                b"".to_vec().into()
+            ));
+        }
+        assert_ok!(XGatewayRecords::process_withdrawals(
+            &[0, 1],
+            xpallet_assets::Chain::Bitcoin
+        ));
+        WithdrawalProposal::<Test>::put(BtcWithdrawalProposal {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list: vec![0, 1],
+            tx: withdraw_taproot2.clone(),
+            trustee_list: vec![(alice(), true), (bob(), false), (charlie(), false)],
+        });
+        assert_noop!(
+            XGatewayBitcoin::report_withdrawal_tx(Origin::signed(alice()), txid),
+            XGatewayBitcoinErr::NotFinishProposal,
+        );
+        WithdrawalProposal::<Test>::mutate(|proposal| {
+            proposal.as_mut().unwrap().sig_state = VoteResult::Finish
+        });
+
+        assert_noop!(
+            XGatewayBitcoin::report_withdrawal_tx(Origin::signed(AccountId::new([9; 32])), txid),
+            XGatewayBitcoinErr::NotTrustee,
+        );
+        assert_noop!(
+            XGatewayBitcoin::report_withdrawal_tx(Origin::signed(alice()), H256::repeat_byte(1)),
+            XGatewayBitcoinErr::WithdrawalTxMismatch,
+        );
+        assert_ok!(XGatewayBitcoin::report_withdrawal_tx(
+            Origin::signed(alice()),
+            txid
+        ));
+        for id in [0, 1] {
+            assert_eq!(
+                XGatewayRecords::withdrawal_status(id),
+                Some(WithdrawalStatus::Broadcasted(txid))
+            );
+        }
+
+        // the withdrawals are confirmed once the tx is relayed
+        let r = mock_process_tx::<Test>(
+            withdraw_taproot2.clone(),
+            Some(withdraw_taproot2_prev.clone()),
+        );
+        assert_eq!(r.result, BtcTxResult::Success);
+        for id in [0, 1] {
+            assert_eq!(
+                XGatewayRecords::withdrawal_status(id),
+                Some(WithdrawalStatus::Confirmed(Some(txid)))
+            );
+        }
+    });
+}
+
+#[test]
+fn test_report_withdrawal_broadcast() {
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = withdraw_taproot2.hash();
+        let validate = |txid| {
+            XGatewayBitcoin::validate_unsigned(
+                TransactionSource::External,
+                &crate::Call::report_withdrawal_broadcast { txid },
+            )
+        };
+
+        assert_ok!(XGatewayRecords::deposit(&alice(), X_BTC, 1000000));
+        for _ in 0..2 {
+            assert_ok!(XGatewayRecords::withdraw(
+                &alice(),
+                X_BTC,
+                100000,
+                b"tb1pexff2s7l58sthpyfrtx500ax234stcnt0gz2lr4kwe0ue95a2e0srxsc68".to_vec(),
+                b"".to_vec().into()
+            ));
+        }
+        assert_ok!(XGatewayRecords::process_withdrawals(
+            &[0, 1],
+            xpallet_assets::Chain::Bitcoin
+        ));
+        WithdrawalProposal::<Test>::put(BtcWithdrawalProposal {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list: vec![0, 1],
+            tx: withdraw_taproot2.clone(),
+            trustee_list: vec![(alice(), true), (bob(), false), (charlie(), false)],
+        });
+        assert!(XGatewayBitcoin::get_unreported_withdrawal_tx().is_none());
+        assert_eq!(validate(txid), InvalidTransaction::Stale.into());
+
+        WithdrawalProposal::<Test>::mutate(|proposal| {
+            proposal.as_mut().unwrap().sig_state = VoteResult::Finish
+        });
+        assert_eq!(
+            XGatewayBitcoin::get_unreported_withdrawal_tx().map(|(txid, _)| txid),
+            Some(txid)
+        );
+        assert_eq!(
+            validate(H256::repeat_byte(1)),
+            InvalidTransaction::Stale.into()
+        );
+        assert_ok!(validate(txid));
+
+        assert_ok!(XGatewayBitcoin::report_withdrawal_broadcast(
+            Origin::none(),
+            txid
+        ));
+        for id in [0, 1] {
+            assert_eq!(
+                XGatewayRecords::withdrawal_status(id),
+                Some(WithdrawalStatus::Broadcasted(txid))
+            );
+        }
+        // reported already
+        assert!(XGatewayBitcoin::get_unreported_withdrawal_tx().is_none());
+        assert_eq!(validate(txid), InvalidTransaction::Stale.into());
+    });
+}
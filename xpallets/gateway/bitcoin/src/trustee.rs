@@ -2,8 +2,11 @@
 extern crate alloc;
 
 use alloc::string::ToString;
-use frame_support::dispatch::{DispatchError, DispatchResult};
-use sp_runtime::SaturatedConversion;
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+};
+use sp_runtime::{traits::Saturating, SaturatedConversion};
 use sp_std::{
     cmp::max,
     convert::{TryFrom, TryInto},
@@ -15,23 +18,24 @@ use light_bitcoin::{
     crypto::dhash160,
     keys::{Address, AddressTypes, Public, Type},
     mast::{compute_min_threshold, key::PublicKey, Mast},
-    primitives::Bytes,
+    primitives::{Bytes, H256},
     script::{Builder, Opcode},
 };
 
-use xp_gateway_bitcoin::extract_output_addr;
+use xp_gateway_bitcoin::match_withdrawal_outputs;
 use xpallet_assets::Chain;
 use xpallet_gateway_common::{
-    traits::{TrusteeForChain, TrusteeSession},
+    traits::{TrusteeForChain, TrusteeSession, WithdrawalBatchProvider},
     trustees::bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeType},
     types::{ScriptInfo, TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
     utils::two_thirds_unsafe,
 };
+use xpallet_gateway_records::WithdrawalStatus;
 
 use crate::{
     log,
     types::{BtcWithdrawalProposal, VoteResult},
-    Config, Error, Event, Pallet, WithdrawalProposal,
+    Config, Error, Event, Pallet, WithdrawalProposal, WithdrawalProposalExpireAt,
 };
 
 pub fn current_trustee_session<T: Config>(
@@ -340,6 +344,21 @@ impl<T: Config> Pallet<T> {
         withdrawal_id_list.sort_unstable();
         withdrawal_id_list.dedup();
 
+        // A withdrawal paying out to multiple addresses has an output for each of them.
+        let output_count = withdrawal_id_list
+            .iter()
+            .map(|id| xpallet_gateway_records::Pallet::<T>::withdrawal_output_count(*id))
+            .sum::<u32>();
+        if output_count > withdraw_amount {
+            log!(
+                error,
+                "[apply_create_withdraw] Current outputs (len:{}) exceeding the max withdrawal amount {}",
+                output_count, withdraw_amount
+            );
+            return Err(Error::<T>::WrongWithdrawalCount.into());
+        }
+
+        T::WithdrawalBatch::ensure_sealed_batch(Chain::Bitcoin, &withdrawal_id_list)?;
         check_withdraw_tx::<T>(&tx, &withdrawal_id_list)?;
         log!(
             info,
@@ -352,11 +371,17 @@ impl<T: Config> Pallet<T> {
             Chain::Bitcoin,
         )?;
 
+        // The trustees acknowledge their signatures by `sign_withdrawal_proposal`.
+        let trustee_list = current_trustee_session::<T>()?
+            .trustee_list
+            .into_iter()
+            .map(|(trustee, _)| (trustee, false))
+            .collect();
         let proposal = BtcWithdrawalProposal::new(
-            VoteResult::Finish,
+            VoteResult::Unfinish,
             withdrawal_id_list.clone(),
             tx,
-            Vec::new(),
+            trustee_list,
         );
 
         log!(
@@ -370,11 +395,135 @@ impl<T: Config> Pallet<T> {
         ));
 
         WithdrawalProposal::<T>::put(proposal);
+        T::WithdrawalBatch::on_batch_proposed(Chain::Bitcoin);
+
+        match Self::withdrawal_proposal_expiry() {
+            Some(expiry) => {
+                let now = frame_system::Pallet::<T>::block_number();
+                WithdrawalProposalExpireAt::<T>::put(now.saturating_add(expiry));
+            }
+            None => WithdrawalProposalExpireAt::<T>::kill(),
+        }
 
         Ok(())
     }
 }
 
+/// Records that the trustee `who` signed the current withdrawal proposal.
+///
+/// The proposal is fully signed once signed by the trustee threshold, it won't expire
+/// since then.
+pub fn sign_withdrawal_proposal<T: Config>(who: T::AccountId) -> DispatchResult {
+    let mut proposal = Pallet::<T>::withdrawal_proposal().ok_or(Error::<T>::NoProposal)?;
+    let signed = proposal
+        .trustee_list
+        .iter_mut()
+        .find(|(trustee, _)| trustee == &who)
+        .map(|(_, signed)| signed)
+        .ok_or(Error::<T>::NotTrustee)?;
+    ensure!(!*signed, Error::<T>::DuplicateVote);
+    *signed = true;
+
+    let threshold = current_trustee_session::<T>()?.threshold;
+    let sig_count = proposal
+        .trustee_list
+        .iter()
+        .filter(|(_, signed)| *signed)
+        .count();
+    let finished = sig_count >= usize::from(threshold);
+    if finished {
+        proposal.sig_state = VoteResult::Finish;
+    }
+    let txid = proposal.tx.hash();
+    WithdrawalProposal::<T>::put(proposal);
+
+    Pallet::<T>::deposit_event(Event::<T>::WithdrawalProposalVoted(who, true));
+    if finished {
+        Pallet::<T>::deposit_event(Event::<T>::WithdrawalProposalSigned(txid));
+    }
+    Ok(())
+}
+
+/// Marks the withdrawals of the fully signed proposal as broadcasted by the tx `txid`.
+pub fn report_withdrawal_tx<T: Config>(txid: H256) -> DispatchResult {
+    let proposal = Pallet::<T>::withdrawal_proposal().ok_or(Error::<T>::NoProposal)?;
+    ensure!(
+        proposal.sig_state == VoteResult::Finish,
+        Error::<T>::NotFinishProposal
+    );
+    ensure!(proposal.tx.hash() == txid, Error::<T>::WithdrawalTxMismatch);
+
+    xpallet_gateway_records::Pallet::<T>::broadcast_withdrawals(
+        &proposal.withdrawal_id_list,
+        Chain::Bitcoin,
+        txid,
+    )
+}
+
+/// Returns the tx of the fully signed withdrawal proposal if it's not reported as
+/// broadcasted yet.
+pub fn unreported_withdrawal_tx<T: Config>() -> Option<Transaction> {
+    let proposal = Pallet::<T>::withdrawal_proposal()?;
+    if proposal.sig_state != VoteResult::Finish {
+        return None;
+    }
+    let txid = proposal.tx.hash();
+    let reported = proposal.withdrawal_id_list.iter().all(|id| {
+        matches!(
+            xpallet_gateway_records::Pallet::<T>::withdrawal_status(*id),
+            Some(WithdrawalStatus::Broadcasted(prev)) if prev == txid
+        )
+    });
+    if reported {
+        None
+    } else {
+        Some(proposal.tx)
+    }
+}
+
+/// Dissolves the withdrawal proposal not fully signed before its expiry, the withdrawals
+/// of which return to the pending queue.
+///
+/// Returns whether the proposal was dissolved.
+pub fn expire_withdrawal_proposal<T: Config>(now: T::BlockNumber) -> bool {
+    match WithdrawalProposalExpireAt::<T>::get() {
+        Some(expire_at) if now >= expire_at => {}
+        _ => return false,
+    }
+    WithdrawalProposalExpireAt::<T>::kill();
+
+    let proposal = match Pallet::<T>::withdrawal_proposal() {
+        Some(proposal) if proposal.sig_state == VoteResult::Unfinish => proposal,
+        _ => return false,
+    };
+    if let Err(err) = Pallet::<T>::apply_remove_proposal() {
+        log!(
+            error,
+            "[expire_withdrawal_proposal] Failed to dissolve the proposal {:?}: {:?}",
+            proposal.withdrawal_id_list,
+            err
+        );
+        return false;
+    }
+
+    let absent_trustees = proposal
+        .trustee_list
+        .into_iter()
+        .filter_map(|(trustee, signed)| if signed { None } else { Some(trustee) })
+        .collect::<Vec<_>>();
+    log!(
+        warn,
+        "[expire_withdrawal_proposal] The proposal {:?} expired, absent trustees:{:?}",
+        proposal.withdrawal_id_list,
+        absent_trustees
+    );
+    Pallet::<T>::deposit_event(Event::<T>::WithdrawalProposalExpired(
+        proposal.withdrawal_id_list,
+        absent_trustees,
+    ));
+    true
+}
+
 /// Get the required number of signatures
 /// sig_num: Number of signatures required
 /// trustee_num: Total number of multiple signatures
@@ -456,70 +605,33 @@ fn check_withdraw_tx_impl<T: Config>(
     // withdrawal addr list for account withdrawal application
     let mut appl_withdrawal_list: Vec<(Address, u64)> = Vec::new();
     for withdraw_index in withdrawal_id_list.iter() {
-        let record = xpallet_gateway_records::Pallet::<T>::pending_withdrawals(withdraw_index)
+        let outputs = xpallet_gateway_records::Pallet::<T>::withdrawal_outputs(*withdraw_index)
             .ok_or(Error::<T>::NoWithdrawalRecord)?;
-        // record.addr() is base58
-        // verify btc address would conveRelayedTx a base58 addr to Address
-        let addr: Address = Pallet::<T>::verify_btc_address(record.addr())?;
+        for (addr, balance) in outputs {
+            // addr is base58
+            // verify btc address would conveRelayedTx a base58 addr to Address
+            let addr: Address = Pallet::<T>::verify_btc_address(&addr)?;
 
-        appl_withdrawal_list.push((addr, record.balance().saturated_into::<u64>()));
+            appl_withdrawal_list.push((addr, balance.saturated_into::<u64>()));
+        }
     }
     // not allow deposit directly to cold address, only hot address allow
     let hot_trustee_address: Address = get_hot_trustee_address::<T>()?;
-    // withdrawal addr list for tx outputs
-    let btc_withdrawal_fee = Pallet::<T>::btc_withdrawal_fee();
-    let btc_network = Pallet::<T>::network_id();
-    let mut tx_withdraw_list = Vec::new();
-    for output in &tx.outputs {
-        let addr = extract_output_addr(output, btc_network).ok_or("not found addr in this out")?;
-        if addr.hash != hot_trustee_address.hash {
-            // expect change to trustee_addr output
-            tx_withdraw_list.push((addr, output.value + btc_withdrawal_fee));
-        }
-    }
-
-    tx_withdraw_list.sort();
-    appl_withdrawal_list.sort();
-
-    // appl_withdrawal_list must match to tx_withdraw_list
-    if appl_withdrawal_list.len() != tx_withdraw_list.len() {
+    let outputs_match = match_withdrawal_outputs(
+        tx,
+        appl_withdrawal_list,
+        &hot_trustee_address,
+        Pallet::<T>::network_id(),
+        Pallet::<T>::withdrawal_fee(),
+    )?;
+    if !outputs_match {
         log!(
             error,
-            "Withdrawal tx's outputs (len:{}) != withdrawal application list (len:{}), \
-            withdrawal tx's outputs:{:?}, withdrawal application list:{:?}",
-            tx_withdraw_list.len(),
-            appl_withdrawal_list.len(),
-            tx_withdraw_list,
+            "Withdrawal tx's outputs not match to withdrawal application list:{:?}",
             withdrawal_id_list
-                .iter()
-                .zip(appl_withdrawal_list)
-                .collect::<Vec<_>>()
         );
         return Err(Error::<T>::TxOutputsNotMatch.into());
     }
 
-    let count = appl_withdrawal_list
-        .iter()
-        .zip(tx_withdraw_list)
-        .filter(|(a, b)| {
-            if a.0.hash == b.0.hash && a.1 == b.1 {
-                true
-            } else {
-                log!(
-                    error,
-                    "Withdrawal tx's output not match to withdrawal application. \
-                    withdrawal application:{:?}, tx withdrawal output:{:?}",
-                    a,
-                    b
-                );
-                false
-            }
-        })
-        .count();
-
-    if count != appl_withdrawal_list.len() {
-        return Err(Error::<T>::TxOutputsNotMatch.into());
-    }
-
     Ok(())
 }
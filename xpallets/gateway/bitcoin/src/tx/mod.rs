@@ -9,7 +9,10 @@ use frame_support::{
     dispatch::DispatchResult,
     log::{self, debug, error, info, warn},
 };
-use sp_runtime::{traits::Zero, SaturatedConversion};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
 use sp_std::prelude::*;
 
 use light_bitcoin::{
@@ -23,13 +26,17 @@ use chainx_primitives::AssetId;
 use xp_gateway_bitcoin::{BtcDepositInfo, BtcTxMetaType, BtcTxTypeDetector, OpReturnAccount};
 use xp_gateway_common::{AccountExtractor, DstChain};
 use xpallet_assets::ChainT;
-use xpallet_gateway_common::traits::{AddressBinding, ReferralBinding, TrusteeInfoUpdate};
+use xpallet_gateway_common::{
+    traits::{AddressBinding, OperationPauseProvider, ReferralBinding, TrusteeInfoUpdate},
+    types::GatewayOperation,
+};
 use xpallet_support::try_str;
 
 pub use self::validator::validate_transaction;
 use crate::{
-    types::{AccountInfo, BtcAddress, BtcDepositCache, BtcTxResult, BtcTxState},
-    BalanceOf, Config, Event, Pallet, PendingDeposits, WithdrawalProposal,
+    types::{AccountInfo, BtcAddress, BtcDepositCache, BtcTxResult, BtcTxState, DeferredDeposit},
+    BalanceOf, Config, DeferredDepositExpiries, DeferredDeposits, Error, Event, Pallet,
+    PendingDeposits, TxState, WithdrawalProposal, WithdrawalProposalExpireAt,
 };
 
 pub fn process_tx<T: Config>(
@@ -51,6 +58,17 @@ pub fn process_tx<T: Config>(
 
     let tx_type = meta_type.ref_into();
     let result = match meta_type {
+        BtcTxMetaType::<_>::Deposit(_)
+            if T::OperationPause::ensure_not_paused(GatewayOperation::Deposit).is_err() =>
+        {
+            // mark the paused deposit be `Failure` so that it could be replayed after resuming
+            warn!(
+                target: "runtime::bitcoin",
+                "[process_tx] Deposits are paused, reject deposit tx ({:?})",
+                hash_rev(tx.hash())
+            );
+            BtcTxResult::Failure
+        }
         BtcTxMetaType::<_>::Deposit(deposit_info) => deposit::<T>(tx.hash(), deposit_info),
         BtcTxMetaType::<_>::Withdrawal => withdraw::<T>(tx),
         BtcTxMetaType::TrusteeTransition => trustee_transition::<T>(tx),
@@ -120,6 +138,13 @@ fn deposit<T: Config>(txid: H256, deposit_info: BtcDepositInfo<T::AccountId>) ->
                 );
             }
 
+            if let Some(config) = Pallet::<T>::deposit_attestation() {
+                if deposit_info.deposit_value > config.threshold {
+                    defer_deposit::<T>(txid, account, deposit_info.deposit_value, config.expiry);
+                    return BtcTxResult::Success;
+                }
+            }
+
             match deposit_token::<T>(txid, &account, deposit_info.deposit_value) {
                 Ok(_) => {
                     info!(
@@ -161,6 +186,79 @@ fn deposit_token<T: Config>(
     }
 }
 
+fn defer_deposit<T: Config>(
+    txid: H256,
+    who: OpReturnAccount<T::AccountId>,
+    balance: u64,
+    expiry: T::BlockNumber,
+) {
+    let expire_at = frame_system::Pallet::<T>::block_number().saturating_add(expiry);
+    info!(
+        target: "runtime::bitcoin",
+        "[deposit] Deposit tx ({:?}) deferred until attested, who:{:?}, balance:{}, expire_at:{:?}",
+        hash_rev(txid),
+        who,
+        balance,
+        expire_at
+    );
+    DeferredDeposits::<T>::insert(
+        txid,
+        DeferredDeposit {
+            who,
+            balance,
+            expire_at,
+        },
+    );
+    DeferredDepositExpiries::<T>::append(expire_at, txid);
+    Pallet::<T>::deposit_event(Event::<T>::DepositDeferred(
+        txid,
+        balance.saturated_into(),
+        expire_at,
+    ));
+}
+
+/// Credits the deferred deposit of `txid` to its depositor.
+pub fn attest_deferred_deposit<T: Config>(txid: H256) -> DispatchResult {
+    let deposit = DeferredDeposits::<T>::take(txid).ok_or(Error::<T>::NoDeferredDeposit)?;
+    deposit_token::<T>(txid, &deposit.who, deposit.balance)?;
+    Pallet::<T>::deposit_event(Event::<T>::DeferredDepositAttested(
+        txid,
+        deposit.balance.saturated_into(),
+    ));
+    Ok(())
+}
+
+/// Expires the deferred deposits not attested before `now`.
+///
+/// The tx of an expired deposit is marked as failed so that it could be relayed again.
+/// Returns the number of the expiring deposits and the number of the expired ones.
+pub fn expire_deferred_deposits<T: Config>(now: T::BlockNumber) -> (u32, u32) {
+    let expiring = DeferredDepositExpiries::<T>::take(now);
+    let mut expired = 0u32;
+    for txid in expiring.iter() {
+        // the attested deposits are removed already
+        if let Some(deposit) = DeferredDeposits::<T>::take(txid) {
+            TxState::<T>::mutate(txid, |state| {
+                if let Some(state) = state {
+                    state.result = BtcTxResult::Failure;
+                }
+            });
+            warn!(
+                target: "runtime::bitcoin",
+                "[expire_deferred_deposits] Deposit tx ({:?}) expired without attestation, balance:{}",
+                hash_rev(*txid),
+                deposit.balance
+            );
+            Pallet::<T>::deposit_event(Event::<T>::DeferredDepositExpired(
+                *txid,
+                deposit.balance.saturated_into(),
+            ));
+            expired += 1;
+        }
+    }
+    (expiring.len() as u32, expired)
+}
+
 fn deposit_wasm<T: Config>(txid: H256, who: &T::AccountId, balance: u64) -> DispatchResult {
     let id: AssetId = <Pallet<T> as ChainT<_>>::ASSET_ID;
 
@@ -308,6 +406,38 @@ pub fn remove_pending_deposit<T: Config>(
     }
 }
 
+/// Deposits the first `limit` pending deposits of `input_address` to `who`.
+///
+/// Returns the number of the claimed deposits and the total credited balance.
+pub fn claim_pending_deposits<T: Config>(
+    input_address: &BtcAddress,
+    who: &OpReturnAccount<T::AccountId>,
+    limit: u32,
+) -> (u32, u64) {
+    let mut records = PendingDeposits::<T>::get(input_address);
+    let count = records.len().min(limit as usize);
+    let claimed = records.drain(..count).collect::<Vec<_>>();
+    if records.is_empty() {
+        PendingDeposits::<T>::remove(input_address);
+    } else {
+        PendingDeposits::<T>::insert(input_address, records);
+    }
+
+    let mut total = 0u64;
+    for record in claimed {
+        // the failed deposit is dropped like in `remove_pending_deposit`
+        match deposit_token::<T>(record.txid, who, record.balance) {
+            Ok(()) => total = total.saturating_add(record.balance),
+            Err(err) => error!(
+                target: "runtime::bitcoin",
+                "[claim_pending_deposits] Claim pending deposit error:{:?}, who:{:?}, balance:{}, cached_tx:{:?}",
+                err, who, record.balance, record.txid,
+            ),
+        }
+    }
+    (count as u32, total)
+}
+
 fn insert_pending_deposit<T: Config>(input_addr: &Address, txid: H256, balance: u64) {
     let addr_bytes = input_addr.to_string().into_bytes();
 
@@ -353,7 +483,9 @@ fn withdraw<T: Config>(tx: Transaction) -> BtcTxResult {
                 return BtcTxResult::Failure;
             }
 
+            let btc_withdrawal_fee = Pallet::<T>::btc_withdrawal_fee();
             let mut total = BalanceOf::<T>::zero();
+            let mut withdrawal_fees = Vec::with_capacity(proposal.withdrawal_id_list.len());
             for number in proposal.withdrawal_id_list.iter() {
                 // just for event record
                 let withdraw_balance =
@@ -361,8 +493,24 @@ fn withdraw<T: Config>(tx: Transaction) -> BtcTxResult {
                         .map(|record| record.balance())
                         .unwrap_or_else(BalanceOf::<T>::zero);
                 total += withdraw_balance;
+                let output_count = u64::from(
+                    xpallet_gateway_records::Pallet::<T>::withdrawal_output_count(*number),
+                );
+                withdrawal_fees.push((*number, output_count * btc_withdrawal_fee));
+            }
 
-                match xpallet_gateway_records::Pallet::<T>::finish_withdrawal(*number, None) {
+            // Reward the signers with the trustee slice of the withdrawal fee, held back from
+            // the destroyed withdrawals
+            T::TrusteeInfoUpdate::deposit_trustee_fee(
+                Pallet::<T>::chain(),
+                input.script_witness[1].as_slice(),
+                &withdrawal_fees,
+            );
+
+            for number in proposal.withdrawal_id_list.iter() {
+                match xpallet_gateway_records::Pallet::<T>::finish_withdrawal_by_tx(
+                    *number, tx_hash,
+                ) {
                     Ok(_) => {
                         info!(target: "runtime::bitcoin", "[withdraw] Withdrawal ({}) completion", *number);
                     }
@@ -376,10 +524,9 @@ fn withdraw<T: Config>(tx: Transaction) -> BtcTxResult {
                 }
             }
 
-            let btc_withdrawal_fee = Pallet::<T>::btc_withdrawal_fee();
+            let withdrawal_fee: u64 = withdrawal_fees.iter().map(|(_, fee)| fee).sum();
             // real withdraw value would reduce withdraw_fee
-            total -=
-                (proposal.withdrawal_id_list.len() as u64 * btc_withdrawal_fee).saturated_into();
+            total -= withdrawal_fee.saturated_into();
 
             // Record trustee signature
             T::TrusteeInfoUpdate::update_trustee_sig_record(
@@ -388,6 +535,7 @@ fn withdraw<T: Config>(tx: Transaction) -> BtcTxResult {
                 total.saturated_into(),
             );
 
+            WithdrawalProposalExpireAt::<T>::kill();
             Pallet::<T>::deposit_event(Event::<T>::Withdrawn(
                 tx_hash,
                 proposal.withdrawal_id_list,
@@ -4,10 +4,11 @@ use frame_support::{
     dispatch::DispatchResult,
     log::{debug, error},
 };
-use sp_std::prelude::Vec;
 
 use light_bitcoin::{chain::Transaction, primitives::H256};
 
+use xp_gateway_bitcoin::verify_merkle_proof;
+
 use crate::{types::BtcRelayedTx, Config, Error};
 
 pub fn validate_transaction<T: Config>(
@@ -23,25 +24,7 @@ pub fn validate_transaction<T: Config>(
     );
 
     // verify merkle proof
-    let mut matches = Vec::new();
-    let mut _indexes = Vec::new();
-    let hash = tx
-        .merkle_proof
-        .extract_matches(&mut matches, &mut _indexes)
-        .map_err(|_| Error::<T>::BadMerkleProof)?;
-    if merkle_root != hash {
-        error!(
-            target: "runtime::bitcoin",
-            "[validate_transaction] Check merkle tree proof error, merkle_root:{:?}, hash:{:?}",
-            merkle_root, hash
-        );
-        return Err(Error::<T>::BadMerkleProof.into());
-    }
-    if !matches.iter().any(|h| *h == tx_hash) {
-        error!(
-            target: "runtime::bitcoin",
-            "[validate_transaction] Tx hash should in matches of partial merkle tree"
-        );
+    if !verify_merkle_proof(&tx.merkle_proof, merkle_root, tx_hash) {
         return Err(Error::<T>::BadMerkleProof.into());
     }
 
@@ -49,7 +32,12 @@ pub fn validate_transaction<T: Config>(
         // verify prev tx for input
         // only check the first(0) input in transaction
         let previous_txid = prev.hash();
-        let expected_id = tx.raw.inputs[0].previous_output.txid;
+        let expected_id = tx
+            .raw
+            .inputs
+            .first()
+            .map(|input| input.previous_output.txid)
+            .ok_or(Error::<T>::InvalidPrevTx)?;
         if previous_txid != expected_id {
             error!(
                 target: "runtime::bitcoin",
@@ -10,7 +10,7 @@ use sp_std::prelude::*;
 
 use light_bitcoin::{
     chain::{BlockHeader as BtcHeader, Transaction as BtcTransaction},
-    keys::Address,
+    keys::{Address, Network as BtcNetwork},
     merkle::PartialMerkleTree,
     primitives::{Compact, H256},
 };
@@ -60,6 +60,67 @@ pub struct BtcHeaderIndex {
     pub height: u32,
 }
 
+/// The gateway status exported to the node metrics.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BtcGatewayMetrics {
+    /// Height of the best relayed header
+    pub best_height: u32,
+    /// Height of the latest confirmed header
+    pub confirmed_height: u32,
+    /// Number of the pending bitcoin withdrawals
+    pub pending_withdrawal_count: u32,
+    /// Balance of the trustee hot address in satoshis.
+    ///
+    /// The runtime does not track the UTXOs, this is the balance accounted on chain, i.e. the
+    /// X-BTC total issuance plus the pending deposits.
+    pub hot_address_balance: u64,
+}
+
+/// The gateway parameters a wallet needs to deposit and withdraw.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BtcGatewayParams {
+    pub network: BtcNetwork,
+    /// Number of the confirmations required by a relayed transaction
+    pub confirmation_number: u32,
+    /// Minimal deposit in satoshis
+    pub min_deposit: u64,
+    /// Current fee of each withdrawal output in satoshis
+    pub withdrawal_fee: u64,
+    /// Maximal number of the outputs of a withdrawal proposal
+    pub max_withdrawal_count: u32,
+    /// Deposit address of the current trustees, `None` before the trustees are set.
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_option_text"))]
+    pub hot_address: Option<BtcAddress>,
+}
+
+/// Whether a transaction is confirmed by the relayed headers, checked before `push_transaction`.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum BtcTxInclusion {
+    /// The transaction is in the confirmed block at `height` and can be pushed.
+    Confirmed { height: u32 },
+    /// The raw transaction or the merkle proof can not be deserialized.
+    DeserializeErr,
+    /// The block has not been relayed.
+    UnknownBlock,
+    /// The merkle proof does not match the block or does not contain the transaction.
+    BadMerkleProof,
+    /// The block has been relayed but is not in the main chain.
+    NotInMainChain,
+    /// The block at `height` is above the confirmed height of the relay.
+    Unconfirmed {
+        height: u32,
+        confirmed_height: Option<u32>,
+    },
+    /// The transaction has been processed successfully.
+    ProcessedTx,
+}
+
 #[derive(PartialEq, Clone, Copy, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct BtcTxState {
     pub tx_type: BtcTxType,
@@ -85,6 +146,74 @@ pub struct BtcDepositCache {
     pub balance: u64,
 }
 
+/// How the withdrawal fee is charged.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum WithdrawalFeePolicy {
+    /// Charge the fixed `BtcWithdrawalFee`.
+    Fixed,
+    /// Charge `vsize` virtual bytes at the `BtcFeeRate` (sats/vB), the fee is bounded by
+    /// `min_fee` and `max_fee`.
+    FeeRate {
+        vsize: u64,
+        min_fee: u64,
+        max_fee: u64,
+    },
+}
+
+impl Default for WithdrawalFeePolicy {
+    fn default() -> Self {
+        Self::Fixed
+    }
+}
+
+/// Deposits above `threshold` satoshis are credited only after attested by the
+/// `AttesterOrigin`, they expire if not attested within `expiry` blocks.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DepositAttestationConfig<BlockNumber> {
+    pub threshold: u64,
+    pub expiry: BlockNumber,
+}
+
+/// A deposit waiting for the attestation before crediting.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DeferredDeposit<AccountId, BlockNumber> {
+    pub who: OpReturnAccount<AccountId>,
+    pub balance: u64,
+    pub expire_at: BlockNumber,
+}
+
+/// The BTC balances held at the trustee hot and cold addresses at a Bitcoin height,
+/// co-signed by the trustees.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WalletBalanceAttestation<AccountId> {
+    pub btc_height: u32,
+    pub hot_balance: u64,
+    pub cold_balance: u64,
+    pub signers: Vec<AccountId>,
+}
+
+impl<AccountId> WalletBalanceAttestation<AccountId> {
+    /// Returns the total balance of the hot and cold addresses.
+    pub fn total_balance(&self) -> u64 {
+        self.hot_balance.saturating_add(self.cold_balance)
+    }
+}
+
+/// A wallet balance attestation signed by enough trustees, along with the
+/// X-BTC supply expected to be backed when it was finalized.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AttestedWalletBalance<AccountId> {
+    pub attestation: WalletBalanceAttestation<AccountId>,
+    pub expected_balance: u64,
+}
+
 #[derive(PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct BtcWithdrawalProposal<AccountId> {
@@ -37,10 +37,23 @@ pub trait WeightInfo {
     fn set_best_index() -> Weight;
     fn set_confirmed_index() -> Weight;
     fn remove_pending() -> Weight;
+    fn claim_pending_deposits(n: u32) -> Weight;
     fn remove_proposal() -> Weight;
     fn set_btc_withdrawal_fee() -> Weight;
     fn set_btc_deposit_limit() -> Weight;
+    fn set_withdrawal_fee_policy() -> Weight;
+    fn set_btc_fee_rate() -> Weight;
     fn set_coming_bot() -> Weight;
+    fn set_deposit_attestation() -> Weight;
+    fn attest_deposit() -> Weight;
+    fn attest_wallet_balance() -> Weight;
+    fn set_wallet_deviation_tolerance() -> Weight;
+    fn sign_withdrawal_proposal() -> Weight;
+    fn set_withdrawal_proposal_expiry() -> Weight;
+    fn set_max_reorg_depth() -> Weight;
+    fn reset_relay() -> Weight;
+    fn report_withdrawal_tx() -> Weight;
+    fn set_relayer() -> Weight;
 }
 
 /// Weights for xpallet_gateway_bitcoin using the Substrate node and recommended hardware.
@@ -72,6 +85,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(9 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn claim_pending_deposits(n: u32) -> Weight {
+        (31_402_000 as Weight)
+            .saturating_add((115_131_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
     fn remove_proposal() -> Weight {
         (60_645_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(5 as Weight))
@@ -83,9 +104,59 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn set_btc_deposit_limit() -> Weight {
         (2_575_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_withdrawal_fee_policy() -> Weight {
+        (2_612_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_btc_fee_rate() -> Weight {
+        (21_348_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn set_coming_bot() -> Weight {
         (2_887_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_deposit_attestation() -> Weight {
+        (2_796_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn attest_deposit() -> Weight {
+        (101_563_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn attest_wallet_balance() -> Weight {
+        (74_219_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn set_wallet_deviation_tolerance() -> Weight {
+        (2_703_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn sign_withdrawal_proposal() -> Weight {
+        (38_512_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_withdrawal_proposal_expiry() -> Weight {
+        (2_741_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_reorg_depth() -> Weight {
+        (4_105_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn reset_relay() -> Weight {
+        (58_924_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn report_withdrawal_tx() -> Weight {
+        (33_734_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_relayer() -> Weight {
+        (3_542_000 as Weight).saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -116,6 +187,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(9 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    fn claim_pending_deposits(n: u32) -> Weight {
+        (31_402_000 as Weight)
+            .saturating_add((115_131_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
     fn remove_proposal() -> Weight {
         (60_645_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(5 as Weight))
@@ -127,7 +206,57 @@ impl WeightInfo for () {
     fn set_btc_deposit_limit() -> Weight {
         (2_575_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_withdrawal_fee_policy() -> Weight {
+        (2_612_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_btc_fee_rate() -> Weight {
+        (21_348_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
     fn set_coming_bot() -> Weight {
         (2_887_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_deposit_attestation() -> Weight {
+        (2_796_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn attest_deposit() -> Weight {
+        (101_563_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn attest_wallet_balance() -> Weight {
+        (74_219_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn set_wallet_deviation_tolerance() -> Weight {
+        (2_703_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn sign_withdrawal_proposal() -> Weight {
+        (38_512_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_withdrawal_proposal_expiry() -> Weight {
+        (2_741_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_reorg_depth() -> Weight {
+        (4_105_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn reset_relay() -> Weight {
+        (58_924_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn report_withdrawal_tx() -> Weight {
+        (33_734_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_relayer() -> Weight {
+        (3_542_000 as Weight).saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
 }
@@ -16,7 +16,11 @@ pub use xp_runtime::Memo;
 pub use xpallet_assets::WithdrawalLimit;
 pub use xpallet_gateway_common::{
     trustees,
-    types::{GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, ScriptInfo},
+    types::{
+        DepositAddress, GenericTrusteeIntentionProps, GenericTrusteeSessionDetails,
+        GenericTrusteeSessionInfo, MaintenanceStatus, ScriptInfo, TrusteeSessionAt,
+        WithdrawalBatchStatus,
+    },
 };
 pub use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId, WithdrawalState};
 sp_api::decl_runtime_apis! {
@@ -29,6 +33,9 @@ sp_api::decl_runtime_apis! {
     {
         fn bound_addrs(who: AccountId) -> BTreeMap<Chain, Vec<ChainAddress>>;
 
+        /// Get the address and the data to attach to deposit to `who` on `chain`.
+        fn deposit_address(chain: Chain, who: AccountId) -> Option<DepositAddress<Balance>>;
+
         fn withdrawal_limit(asset_id: AssetId) -> Result<WithdrawalLimit<Balance>, DispatchError>;
 
         #[allow(clippy::type_complexity)]
@@ -52,6 +59,18 @@ sp_api::decl_runtime_apis! {
 
         fn trustee_session_info(chain: Chain, session_number: i32) -> Option<GenericTrusteeSessionInfo<AccountId, BlockNumber>>;
 
+        /// Get the trustee session info with the public keys of each member.
+        fn trustee_session_details(chain: Chain, session_number: i32) -> Option<GenericTrusteeSessionDetails<AccountId, BlockNumber>>;
+
+        /// Get the trustee session of the given number or the one active at the given block.
+        fn trustee_session_at(chain: Chain, at: TrusteeSessionAt<BlockNumber>) -> Option<GenericTrusteeSessionDetails<AccountId, BlockNumber>>;
+
+        /// Get the scheduled maintenance window of the gateway along with its countdown.
+        fn maintenance_status(chain: Chain) -> Option<MaintenanceStatus<BlockNumber>>;
+
+        /// Get the withdrawal batch of the gateway if the batching is enabled.
+        fn withdrawal_batch(chain: Chain) -> Option<WithdrawalBatchStatus<BlockNumber>>;
+
         fn generate_trustee_session_info(chain: Chain, Vec<AccountId>) -> Result<(GenericTrusteeSessionInfo<AccountId, BlockNumber>, ScriptInfo<AccountId>), DispatchError>;
     }
 }
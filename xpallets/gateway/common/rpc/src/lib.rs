@@ -22,11 +22,12 @@ use xp_rpc::{
 };
 
 use xpallet_gateway_common_rpc_runtime_api::trustees::bitcoin::{
-    BtcTrusteeIntentionProps, BtcTrusteeSessionInfo,
+    BtcTrusteeIntentionProps, BtcTrusteeSessionDetails, BtcTrusteeSessionInfo,
 };
 use xpallet_gateway_common_rpc_runtime_api::{
-    AssetId, Chain, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, ScriptInfo,
-    Withdrawal, WithdrawalLimit, WithdrawalRecordId, WithdrawalState,
+    AssetId, Chain, DepositAddress, GenericTrusteeIntentionProps, GenericTrusteeSessionDetails,
+    GenericTrusteeSessionInfo, MaintenanceStatus, ScriptInfo, TrusteeSessionAt, Withdrawal,
+    WithdrawalBatchStatus, WithdrawalLimit, WithdrawalRecordId, WithdrawalState,
     XGatewayCommonApi as XGatewayCommonRuntimeApi,
 };
 
@@ -44,6 +45,15 @@ where
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<Chain, Vec<String>>>;
 
+    /// Return the current trustee hot address and the OP_RETURN data to attach to the bitcoin
+    /// deposit of an account, `None` if the trustees are not elected yet.
+    #[rpc(name = "xgatewaycommon_depositAddress")]
+    fn deposit_address(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<RpcDepositAddress<Balance>>>;
+
     /// Get withdrawal limit(minimal_withdrawal&fee) for an AssetId
     #[rpc(name = "xgatewaycommon_withdrawalLimit")]
     fn withdrawal_limit(
@@ -99,6 +109,43 @@ where
         at: Option<BlockHash>,
     ) -> Result<BtcTrusteeSessionInfo<AccountId, BlockNumber>>;
 
+    /// Return the bitcoin trustee session details, including the member public keys, threshold,
+    /// hot/cold addresses and their redeem scripts, so that the multisig addresses can be re-derived.
+    #[rpc(name = "xgatewaycommon_trusteeSessionInfo")]
+    fn trustee_session_details(
+        &self,
+        session_number: i32,
+        at: Option<BlockHash>,
+    ) -> Result<BtcTrusteeSessionDetails<AccountId, BlockNumber>>;
+
+    /// Return the bitcoin trustee session details of the given session number, e.g.
+    /// `{"session": 1}`, or of the session controlling the funds at the given block, e.g.
+    /// `{"block": 100}`, including the past sessions kept in the archive.
+    #[rpc(name = "xgatewaycommon_trusteeSessionAt")]
+    fn trustee_session_at(
+        &self,
+        session_at: TrusteeSessionAt<BlockNumber>,
+        at: Option<BlockHash>,
+    ) -> Result<BtcTrusteeSessionDetails<AccountId, BlockNumber>>;
+
+    /// Return the scheduled maintenance window of the gateway for `chain` with its countdown,
+    /// `None` if no maintenance is scheduled.
+    #[rpc(name = "xgatewaycommon_maintenanceWindow")]
+    fn maintenance_window(
+        &self,
+        chain: Chain,
+        at: Option<BlockHash>,
+    ) -> Result<Option<MaintenanceStatus<BlockNumber>>>;
+
+    /// Return the withdrawal batch of the gateway for `chain`, i.e., the sealed withdrawals
+    /// waiting for a proposal and the ones still accumulating, `None` if the batching is disabled.
+    #[rpc(name = "xgatewaycommon_withdrawalBatch")]
+    fn withdrawal_batch(
+        &self,
+        chain: Chain,
+        at: Option<BlockHash>,
+    ) -> Result<Option<WithdrawalBatchStatus<BlockNumber>>>;
+
     /// Try to generate bitcoin trustee info for a list of candidates. (this api is used to check the trustee info which would be generated by those candidates)
     #[rpc(name = "xgatewaycommon_bitcoinGenerateTrusteeSessionInfo")]
     fn btc_generate_trustee_session_info(
@@ -168,6 +215,23 @@ where
         Ok(result)
     }
 
+    fn generic_trustee_session_details(
+        &self,
+        chain: Chain,
+        session_number: i32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<GenericTrusteeSessionDetails<AccountId, BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let result = api
+            .trustee_session_details(&at, chain, session_number)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(trustee_inexistent_rpc_err)?;
+
+        Ok(result)
+    }
+
     fn generate_generic_trustee_session_info(
         &self,
         chain: Chain,
@@ -232,6 +296,20 @@ where
         Ok(result)
     }
 
+    fn deposit_address(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<RpcDepositAddress<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let result = api
+            .deposit_address(&at, Chain::Bitcoin, who)
+            .map_err(runtime_error_into_rpc_err)?;
+        Ok(result.map(Into::into))
+    }
+
     fn withdrawal_limit(
         &self,
         asset_id: AssetId,
@@ -351,6 +429,56 @@ where
         BtcTrusteeSessionInfo::<_, _>::try_from(info).map_err(trustee_decode_error_into_rpc_err)
     }
 
+    fn trustee_session_details(
+        &self,
+        session_number: i32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BtcTrusteeSessionDetails<AccountId, BlockNumber>> {
+        let details = self.generic_trustee_session_details(Chain::Bitcoin, session_number, at)?;
+        BtcTrusteeSessionDetails::<_, _>::try_from(details)
+            .map_err(trustee_decode_error_into_rpc_err)
+    }
+
+    fn trustee_session_at(
+        &self,
+        session_at: TrusteeSessionAt<BlockNumber>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BtcTrusteeSessionDetails<AccountId, BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let details = api
+            .trustee_session_at(&at, Chain::Bitcoin, session_at)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(trustee_inexistent_rpc_err)?;
+        BtcTrusteeSessionDetails::<_, _>::try_from(details)
+            .map_err(trustee_decode_error_into_rpc_err)
+    }
+
+    fn maintenance_window(
+        &self,
+        chain: Chain,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<MaintenanceStatus<BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.maintenance_status(&at, chain)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn withdrawal_batch(
+        &self,
+        chain: Chain,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<WithdrawalBatchStatus<BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.withdrawal_batch(&at, chain)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
     fn btc_generate_trustee_session_info(
         &self,
         candidates: Vec<AccountId>,
@@ -372,6 +500,15 @@ pub struct RpcWithdrawalRecord<AccountId, Balance: Display + FromStr, BlockNumbe
     pub ext: String,
     pub height: BlockNumber,
     pub state: WithdrawalState,
+    pub outputs: Vec<RpcWithdrawalOutput<Balance>>,
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWithdrawalOutput<Balance: Display + FromStr> {
+    pub addr: String,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub balance: Balance,
 }
 
 impl<AccountId, Balance: Display + FromStr, BlockNumber>
@@ -387,6 +524,38 @@ impl<AccountId, Balance: Display + FromStr, BlockNumber>
             ext: String::from_utf8_lossy(record.ext.as_ref()).into_owned(),
             height: record.height,
             state: record.state,
+            outputs: record
+                .outputs
+                .into_iter()
+                .map(|(addr, balance)| RpcWithdrawalOutput {
+                    addr: String::from_utf8_lossy(addr.as_ref()).into_owned(),
+                    balance,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDepositAddress<Balance: Display + FromStr> {
+    /// The address to deposit to
+    pub address: String,
+    /// The OP_RETURN data in text
+    pub op_return: String,
+    /// The OP_RETURN data in hex, i.e. the script is `OP_RETURN <opReturnHex>`
+    pub op_return_hex: String,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub min_deposit: Balance,
+}
+
+impl<Balance: Display + FromStr> From<DepositAddress<Balance>> for RpcDepositAddress<Balance> {
+    fn from(deposit: DepositAddress<Balance>) -> Self {
+        Self {
+            address: String::from_utf8_lossy(&deposit.address).into_owned(),
+            op_return: String::from_utf8_lossy(&deposit.op_return).into_owned(),
+            op_return_hex: format!("0x{}", hex::encode(&deposit.op_return)),
+            min_deposit: deposit.min_deposit,
         }
     }
 }
@@ -2,12 +2,12 @@
 
 use codec::{Decode, Encode};
 use frame_benchmarking::benchmarks;
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, Get};
 use frame_system::RawOrigin;
-use sp_core::crypto::AccountId32;
+use sp_core::{crypto::AccountId32, H256};
 #[cfg(feature = "runtime-benchmarks")]
 use sp_runtime::traits::CheckedDiv;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::{traits::StaticLookup, Percent};
 use sp_std::prelude::*;
 
 use xp_assets_registrar::Chain;
@@ -16,9 +16,9 @@ use xpallet_assets::BalanceOf;
 use xpallet_gateway_records::{Pallet as XGatewayRecords, WithdrawalRecordId, WithdrawalState};
 
 use crate::{
-    traits::TrusteeSession, types::*, Call, Config, LittleBlackHouse, Pallet,
-    TrusteeIntentionPropertiesOf, TrusteeMultiSigAddr, TrusteeSessionInfoLen, TrusteeSessionInfoOf,
-    TrusteeTransitionStatus,
+    traits::TrusteeSession, types::*, Call, Config, LittleBlackHouse, Pallet, SignatureRoundOf,
+    TrusteeFeePotOf, TrusteeFeeSignatures, TrusteeIntentionPropertiesOf, TrusteeMultiSigAddr,
+    TrusteeSessionInfoLen, TrusteeSessionInfoOf, TrusteeTransitionStatus,
 };
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -95,6 +95,16 @@ fn new_trustees<T: Config>() -> Vec<(T::AccountId, Vec<u8>, Vec<u8>, Vec<u8>)> {
     ]
 }
 
+/// applies `n` bitcoin withdrawals on behalf of `who`.
+fn apply_withdrawals<T: Config>(who: &T::AccountId, n: u32) {
+    let amount: BalanceOf<T> = 1_000_000_000u32.into();
+    XGatewayRecords::<T>::deposit(who, X_BTC, amount * n.into()).unwrap();
+    for _ in 0..n {
+        let addr = b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec();
+        XGatewayRecords::<T>::withdraw(who, X_BTC, amount, addr, b"".to_vec().into()).unwrap();
+    }
+}
+
 /// removes all the storage items to reverse any genesis state.
 fn clean<T: Config>() {
     <LittleBlackHouse<T>>::remove_all(None);
@@ -119,6 +129,20 @@ benchmarks! {
         );
     }
 
+    withdraw_to_many {
+        let n in 1 .. 100;
+        let caller: T::AccountId = alice::<T>();
+        let amount: BalanceOf<T> = 100_000_000u32.into();
+        XGatewayRecords::<T>::deposit(&caller, X_BTC, amount * n.into()).unwrap();
+        let outputs = (0..n)
+            .map(|_| (b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec(), amount))
+            .collect::<Vec<_>>();
+        let memo = b"".to_vec().into();
+    }: _(RawOrigin::Signed(caller.clone()), X_BTC, outputs, memo)
+    verify {
+        assert_eq!(XGatewayRecords::<T>::withdrawal_output_count(0), n);
+    }
+
     cancel_withdrawal {
         let caller: T::AccountId = alice::<T>();
         let amount: BalanceOf<T> = 1_000_000_000_u32.into();
@@ -270,6 +294,135 @@ benchmarks! {
     verify {
         assert_eq!(Pallet::<T>::referral_binding_of(&who, Chain::Bitcoin), Some(who));
     }
+
+    add_standby_trustee {
+        let who: T::AccountId = frame_benchmarking::account("standby", 0, 0);
+        let bond = T::StandbyTrusteeBond::get();
+        <T as xpallet_assets::Config>::Currency::make_free_balance_be(&who, bond + bond);
+        let who_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(who.clone());
+    }: _(RawOrigin::Root, Chain::Bitcoin, who_lookup)
+    verify {
+        assert!(Pallet::<T>::is_standby_trustee(Chain::Bitcoin, &who));
+    }
+
+    remove_standby_trustee {
+        let who: T::AccountId = frame_benchmarking::account("standby", 0, 0);
+        let bond = T::StandbyTrusteeBond::get();
+        <T as xpallet_assets::Config>::Currency::make_free_balance_be(&who, bond + bond);
+        let who_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(who.clone());
+        Pallet::<T>::add_standby_trustee(RawOrigin::Root.into(), Chain::Bitcoin, who_lookup.clone())?;
+    }: _(RawOrigin::Signed(who.clone()), Chain::Bitcoin, who_lookup)
+    verify {
+        assert!(!Pallet::<T>::is_standby_trustee(Chain::Bitcoin, &who));
+    }
+
+    set_withdrawal_batch_policy {
+        let policy = WithdrawalBatchPolicy {
+            max_withdrawal_count: 10,
+            window: 100u32.into(),
+        };
+    }: _(RawOrigin::Root, Chain::Bitcoin, Some(policy.clone()))
+    verify {
+        assert_eq!(Pallet::<T>::withdrawal_batch_policy_of(Chain::Bitcoin), Some(policy));
+    }
+
+    flush_withdrawal_batch {
+        let caller: T::AccountId = alice::<T>();
+        let policy = WithdrawalBatchPolicy {
+            max_withdrawal_count: 10,
+            window: 100u32.into(),
+        };
+        Pallet::<T>::set_withdrawal_batch_policy(RawOrigin::Root.into(), Chain::Bitcoin, Some(policy))?;
+        apply_withdrawals::<T>(&caller, 1);
+    }: _(RawOrigin::Root, Chain::Bitcoin)
+    verify {
+        assert_eq!(Pallet::<T>::sealed_withdrawal_ids(Chain::Bitcoin), Some(vec![0]));
+    }
+
+    seal_withdrawal_batch {
+        let n in 1 .. 100;
+        let caller: T::AccountId = alice::<T>();
+        let policy = WithdrawalBatchPolicy {
+            max_withdrawal_count: n,
+            window: 100u32.into(),
+        };
+        Pallet::<T>::set_withdrawal_batch_policy(RawOrigin::Root.into(), Chain::Bitcoin, Some(policy))?;
+        apply_withdrawals::<T>(&caller, n);
+        let now = frame_system::Pallet::<T>::block_number();
+    }: {
+        Pallet::<T>::try_seal_withdrawal_batch(Chain::Bitcoin, now, false);
+    }
+    verify {
+        assert_eq!(
+            Pallet::<T>::sealed_withdrawal_ids(Chain::Bitcoin).map(|ids| ids.len() as u32),
+            Some(n)
+        );
+    }
+
+    pause {
+    }: _(RawOrigin::Root, GatewayOperation::Withdrawal)
+    verify {
+        assert!(Pallet::<T>::is_paused(GatewayOperation::Withdrawal));
+    }
+
+    unpause {
+        Pallet::<T>::pause(RawOrigin::Root.into(), GatewayOperation::Withdrawal)?;
+    }: _(RawOrigin::Root, GatewayOperation::Withdrawal)
+    verify {
+        assert!(!Pallet::<T>::is_paused(GatewayOperation::Withdrawal));
+    }
+
+    submit_signature_share {
+        let n in 1 .. T::MaxSignatureShareLen::get();
+        if T::BitcoinTrusteeSessionProvider::current_trustee_session().is_err() {
+            Pallet::<T>::do_trustee_election(Chain::Bitcoin)?;
+        }
+        let session = T::BitcoinTrusteeSessionProvider::current_trustee_session().unwrap();
+        let threshold = session.threshold as usize;
+        let proposal_hash = H256::repeat_byte(1);
+        // The share of the caller finalizes the round.
+        let caller = session.trustee_list[threshold - 1].0.clone();
+        SignatureRoundOf::<T>::insert(Chain::Bitcoin, SignatureRound {
+            proposal_hash,
+            threshold: session.threshold,
+            shares: session.trustee_list[..threshold - 1]
+                .iter()
+                .map(|(trustee, _)| (trustee.clone(), vec![0; n as usize]))
+                .collect(),
+            finalized: false,
+        });
+    }: {
+        Pallet::<T>::collect_signature_share(Chain::Bitcoin, proposal_hash, caller, vec![0; n as usize])?;
+    }
+    verify {
+        assert!(Pallet::<T>::signature_round_of(Chain::Bitcoin).unwrap().finalized);
+    }
+
+    set_trustee_fee_share {
+        let share = Percent::from_percent(20);
+    }: _(RawOrigin::Root, Chain::Bitcoin, share)
+    verify {
+        assert_eq!(Pallet::<T>::trustee_fee_share_of(Chain::Bitcoin), share);
+    }
+
+    claim_trustee_rewards {
+        let caller: T::AccountId = alice::<T>();
+        let fee: BalanceOf<T> = 100_000u32.into();
+        TrusteeSessionInfoLen::<T>::insert(Chain::Bitcoin, 1);
+        TrusteeFeePotOf::<T>::insert(Chain::Bitcoin, 0, TrusteeFeePot {
+            total_fee: fee,
+            total_signatures: 2,
+        });
+        TrusteeFeeSignatures::<T>::insert((Chain::Bitcoin, 0), caller.clone(), 1);
+        let pot = Pallet::<T>::trustee_fee_pot_account(Chain::Bitcoin);
+        xpallet_assets::Pallet::<T>::issue(&X_BTC, &pot, fee, false)?;
+    }: _(RawOrigin::Signed(caller.clone()), Chain::Bitcoin, 0)
+    verify {
+        assert_eq!(
+            xpallet_assets::Pallet::<T>::usable_balance(&caller, &X_BTC),
+            50_000u32.into()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -282,6 +435,7 @@ mod tests {
     fn test_benchmarks() {
         ExtBuilder::default().build().execute_with(|| {
             assert_ok!(Pallet::<Test>::test_benchmark_withdraw());
+            assert_ok!(Pallet::<Test>::test_benchmark_withdraw_to_many());
             assert_ok!(Pallet::<Test>::test_benchmark_cancel_withdrawal());
             assert_ok!(Pallet::<Test>::test_benchmark_setup_trustee());
             assert_ok!(Pallet::<Test>::test_benchmark_set_trustee_proxy());
@@ -292,6 +446,16 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_force_trustee_election());
             assert_ok!(Pallet::<Test>::test_benchmark_force_update_trustee());
             assert_ok!(Pallet::<Test>::test_benchmark_force_set_referral_binding());
+            assert_ok!(Pallet::<Test>::test_benchmark_add_standby_trustee());
+            assert_ok!(Pallet::<Test>::test_benchmark_remove_standby_trustee());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_withdrawal_batch_policy());
+            assert_ok!(Pallet::<Test>::test_benchmark_flush_withdrawal_batch());
+            assert_ok!(Pallet::<Test>::test_benchmark_seal_withdrawal_batch());
+            assert_ok!(Pallet::<Test>::test_benchmark_pause());
+            assert_ok!(Pallet::<Test>::test_benchmark_unpause());
+            assert_ok!(Pallet::<Test>::test_benchmark_submit_signature_share());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_trustee_fee_share());
+            assert_ok!(Pallet::<Test>::test_benchmark_claim_trustee_rewards());
         });
     }
 }
@@ -29,13 +29,18 @@ use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
     log::{error, info},
-    traits::{ChangeMembers, Currency, ExistenceRequirement, Get},
+    traits::{ChangeMembers, Currency, ExistenceRequirement, Get, ReservableCurrency},
+    weights::Weight,
+    PalletId,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::OriginFor};
 
+use sp_core::H256;
 use sp_runtime::{
-    traits::{CheckedDiv, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
-    SaturatedConversion,
+    traits::{
+        AccountIdConversion, CheckedDiv, Saturating, StaticLookup, UniqueSaturatedInto, Zero,
+    },
+    Percent, SaturatedConversion,
 };
 use sp_std::{collections::btree_map::BTreeMap, convert::TryFrom, prelude::*};
 
@@ -47,25 +52,35 @@ use xp_runtime::Memo;
 
 /// ChainX pallets
 use xpallet_assets::{AssetRestrictions, BalanceOf, Chain, ChainT, WithdrawalLimit};
-use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId};
+use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId, WithdrawalState};
 use xpallet_support::traits::{MultisigAddressFor, Validator};
 
 use self::{
-    traits::{ProposalProvider, TotalSupply, TrusteeForChain, TrusteeInfoUpdate, TrusteeSession},
+    traits::{
+        OperationPauseProvider, ProposalProvider, TotalSupply, TrusteeForChain,
+        TrusteeInfoConfigProvider, TrusteeInfoUpdate, TrusteeSession, WithdrawalBatchProvider,
+    },
     trustees::bitcoin::BtcTrusteeAddrInfo,
     types::{
-        GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, RewardInfo, ScriptInfo,
-        TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo,
+        GatewayOperation, GenericTrusteeIntentionProps, GenericTrusteeSessionDetails,
+        GenericTrusteeSessionInfo, MaintenanceStatus, MaintenanceWindow, RewardInfo, ScriptInfo,
+        SealedWithdrawalBatch, SignatureRound, TrusteeFeePot, TrusteeInfoConfig,
+        TrusteeIntentionProps, TrusteeMemberInfo, TrusteeSessionAt, TrusteeSessionDetails,
+        TrusteeSessionInfo, WithdrawalBatchPolicy, WithdrawalBatchStatus,
     },
 };
 
 pub use pallet::*;
 pub use weights::WeightInfo;
 
+/// The id of the pot holding the trustee slice of the withdrawal fees, one account per chain.
+const TRUSTEE_FEE_POT_ID: PalletId = PalletId(*b"pcx/trfe");
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use frame_support::{pallet_prelude::*, transactional};
+    use frame_system::pallet_prelude::BlockNumberFor;
     use xp_gateway_common::DstChainConfig;
 
     #[pallet::config]
@@ -84,6 +99,13 @@ pub mod pallet {
         /// A majority of the council can excute some transactions.
         type CouncilOrigin: EnsureOrigin<Self::Origin>;
 
+        /// The origin which can change the parameters of the gateway, e.g., root dispatched
+        /// after the enactment delay.
+        type ParameterOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The origin which can pause and unpause the gateway operations in an emergency.
+        type PauseOrigin: EnsureOrigin<Self::Origin>;
+
         /// Get btc chain info.
         type Bitcoin: ChainT<BalanceOf<Self>>;
 
@@ -109,6 +131,21 @@ pub mod pallet {
         /// Get btc withdrawal proposal.
         type BitcoinWithdrawalProposal: ProposalProvider;
 
+        /// Get eth chain info.
+        type Ethereum: ChainT<BalanceOf<Self>>;
+
+        /// The bond reserved from a standby trustee until it's promoted or removed.
+        #[pallet::constant]
+        type StandbyTrusteeBond: Get<BalanceOf<Self>>;
+
+        /// The maximum length of a partial signature published by a trustee.
+        #[pallet::constant]
+        type MaxSignatureShareLen: Get<u32>;
+
+        /// The number of the latest trustee sessions of a chain kept in the archive.
+        #[pallet::constant]
+        type MaxArchivedTrusteeSessions: Get<u32>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -118,6 +155,17 @@ pub mod pallet {
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            WithdrawalBatchPolicyOf::<T>::iter_keys()
+                .map(|chain| Self::try_seal_withdrawal_batch(chain, now, false))
+                .fold(T::DbWeight::get().reads(1), |acc: Weight, weight| {
+                    acc.saturating_add(weight)
+                })
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create a withdrawal.
@@ -142,11 +190,45 @@ pub mod pallet {
                 xpallet_assets::Error::<T>::ActionNotAllowed,
             );
             Self::verify_withdrawal(asset_id, value, &addr, &ext)?;
+            let chain = xpallet_assets_registrar::Pallet::<T>::chain_of(&asset_id)?;
+            Self::ensure_not_in_maintenance(chain, GatewayOperation::Withdrawal)?;
 
             xpallet_gateway_records::Pallet::<T>::withdraw(&who, asset_id, value, addr, ext)?;
             Ok(())
         }
 
+        /// Create a withdrawal paying out to multiple addresses.
+        /// Withdraws the sum of `outputs` of `asset_id`, each (address, value) output is
+        /// paid out individually on the target chain.
+        ///
+        /// WithdrawalRecord State: `Applying`
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_to_many(outputs.len() as u32))]
+        #[transactional]
+        pub fn withdraw_to_many(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: AssetId,
+            outputs: Vec<(AddrStr, BalanceOf<T>)>,
+            ext: Memo,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                xpallet_assets::Pallet::<T>::can_do(&asset_id, AssetRestrictions::WITHDRAW),
+                xpallet_assets::Error::<T>::ActionNotAllowed,
+            );
+            ensure!(
+                outputs.len() as u32 <= T::BitcoinWithdrawalProposal::max_withdrawal_count(),
+                Error::<T>::TooManyWithdrawalOutputs
+            );
+            for (addr, value) in outputs.iter() {
+                Self::verify_withdrawal(asset_id, *value, addr, &ext)?;
+            }
+            let chain = xpallet_assets_registrar::Pallet::<T>::chain_of(&asset_id)?;
+            Self::ensure_not_in_maintenance(chain, GatewayOperation::Withdrawal)?;
+
+            xpallet_gateway_records::Pallet::<T>::withdraw_to_many(&who, asset_id, outputs, ext)
+        }
+
         /// Cancel the withdrawal by the applicant.
         ///
         /// WithdrawalRecord State: `Applying` ==> `NormalCancel`
@@ -194,7 +276,8 @@ pub mod pallet {
             // or the trustee is in little black house
             ensure!(
                 Self::generate_trustee_pool().contains(&who)
-                    || Self::little_black_house(chain).contains(&who),
+                    || Self::little_black_house(chain).contains(&who)
+                    || Self::is_standby_trustee(chain, &who),
                 Error::<T>::NotTrusteePreselectedMember
             );
 
@@ -456,7 +539,7 @@ pub mod pallet {
             chain: Chain,
             multiply: u64,
         ) -> DispatchResult {
-            T::CouncilOrigin::try_origin(origin)
+            T::ParameterOrigin::try_origin(origin)
                 .map(|_| ())
                 .or_else(ensure_root)?;
 
@@ -495,7 +578,7 @@ pub mod pallet {
             dst_chain: DstChain,
             proxy_account: T::AccountId,
         ) -> DispatchResult {
-            T::CouncilOrigin::try_origin(origin)
+            T::ParameterOrigin::try_origin(origin)
                 .map(|_| ())
                 .or_else(ensure_root)?;
 
@@ -510,7 +593,7 @@ pub mod pallet {
             prefix: Vec<u8>,
             length: u32,
         ) -> DispatchResult {
-            T::CouncilOrigin::try_origin(origin)
+            T::ParameterOrigin::try_origin(origin)
                 .map(|_| ())
                 .or_else(ensure_root)?;
 
@@ -527,7 +610,7 @@ pub mod pallet {
             prefix: Vec<u8>,
             length: u32,
         ) -> DispatchResult {
-            T::CouncilOrigin::try_origin(origin)
+            T::ParameterOrigin::try_origin(origin)
                 .map(|_| ())
                 .or_else(ensure_root)?;
 
@@ -537,6 +620,265 @@ pub mod pallet {
             });
             Ok(())
         }
+
+        /// Schedule a maintenance window of the gateway for `chain`.
+        ///
+        /// The affected operations are rejected (withdrawals) or delayed (deposits) from block
+        /// `start` for `duration` blocks, the scheduled window is exposed via rpc so that the
+        /// wallets can warn users in advance. A new schedule replaces the previous one.
+        #[pallet::weight(0u64)]
+        pub fn schedule_maintenance(
+            origin: OriginFor<T>,
+            chain: Chain,
+            start: T::BlockNumber,
+            duration: T::BlockNumber,
+            operations: Vec<GatewayOperation>,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                start >= now && !duration.is_zero() && !operations.is_empty(),
+                Error::<T>::InvalidMaintenanceWindow
+            );
+
+            let window = MaintenanceWindow {
+                start,
+                duration,
+                operations,
+            };
+            MaintenanceWindowOf::<T>::insert(chain, window.clone());
+            Self::deposit_event(Event::<T>::MaintenanceScheduled(chain, window));
+            Ok(())
+        }
+
+        /// Cancel the scheduled maintenance window of the gateway for `chain`.
+        #[pallet::weight(0u64)]
+        pub fn cancel_maintenance(origin: OriginFor<T>, chain: Chain) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            ensure!(
+                MaintenanceWindowOf::<T>::take(chain).is_some(),
+                Error::<T>::NoMaintenanceWindow
+            );
+            Self::deposit_event(Event::<T>::MaintenanceCancelled(chain));
+            Ok(())
+        }
+
+        /// Add a vetted member into the standby trustee pool of `chain`.
+        ///
+        /// When the trustee election lacks members, e.g. some trustees were moved into the
+        /// little black room, the standby trustees who have set their hot and cold keys are
+        /// promoted in order. The `StandbyTrusteeBond` of the member is reserved until it's
+        /// promoted or removed.
+        ///
+        /// This is called by the trustee admin, the council and root.
+        #[pallet::weight(< T as Config >::WeightInfo::add_standby_trustee())]
+        pub fn add_standby_trustee(
+            origin: OriginFor<T>,
+            chain: Chain,
+            who: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(|o| Self::try_ensure_trustee_admin(o, chain))
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            let who = T::Lookup::lookup(who)?;
+            Self::add_standby_trustee_impl(chain, who)
+        }
+
+        /// Remove a member from the standby trustee pool of `chain` and unreserve its bond.
+        ///
+        /// This is called by the member itself, the trustee admin, the council and root.
+        #[pallet::weight(< T as Config >::WeightInfo::remove_standby_trustee())]
+        pub fn remove_standby_trustee(
+            origin: OriginFor<T>,
+            chain: Chain,
+            who: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            let who = T::Lookup::lookup(who)?;
+            if ensure_signed(origin.clone()).map_or(true, |signer| signer != who) {
+                T::CouncilOrigin::try_origin(origin)
+                    .map(|_| ())
+                    .or_else(|o| Self::try_ensure_trustee_admin(o, chain))
+                    .map(|_| ())
+                    .or_else(ensure_root)?;
+            }
+
+            Self::remove_standby_trustee_impl(chain, &who)?;
+            Self::deposit_event(Event::<T>::StandbyTrusteeRemoved(who, chain));
+            Ok(())
+        }
+
+        /// Pause `operation` of all the gateways in an emergency, e.g., an exploit is found.
+        ///
+        /// This is called by the technical committee and root.
+        #[pallet::weight(< T as Config >::WeightInfo::pause())]
+        pub fn pause(origin: OriginFor<T>, operation: GatewayOperation) -> DispatchResult {
+            T::PauseOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            ensure!(
+                !Self::is_paused(operation),
+                Error::<T>::OperationAlreadyPaused
+            );
+            OperationPause::<T>::mutate(|flags| *flags |= operation.flag());
+            Self::deposit_event(Event::<T>::OperationPaused(operation));
+            Ok(())
+        }
+
+        /// Resume the paused `operation` of all the gateways.
+        ///
+        /// This is called by the technical committee and root.
+        #[pallet::weight(< T as Config >::WeightInfo::unpause())]
+        pub fn unpause(origin: OriginFor<T>, operation: GatewayOperation) -> DispatchResult {
+            T::PauseOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            ensure!(Self::is_paused(operation), Error::<T>::OperationNotPaused);
+            OperationPause::<T>::mutate(|flags| *flags &= !operation.flag());
+            Self::deposit_event(Event::<T>::OperationUnpaused(operation));
+            Ok(())
+        }
+
+        /// Set the withdrawal batching policy of `chain`, `None` to disable the batching.
+        ///
+        /// When enabled, the applying withdrawals are sealed into a batch once it has
+        /// `max_withdrawal_count` withdrawals or its oldest withdrawal has waited for `window`
+        /// blocks, the next withdrawal proposal of `chain` must contain exactly the sealed batch.
+        #[pallet::weight(< T as Config >::WeightInfo::set_withdrawal_batch_policy())]
+        pub fn set_withdrawal_batch_policy(
+            origin: OriginFor<T>,
+            chain: Chain,
+            policy: Option<WithdrawalBatchPolicy<T::BlockNumber>>,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            ensure!(chain == Chain::Bitcoin, Error::<T>::NotSupportedChain);
+            match policy {
+                Some(policy) => {
+                    ensure!(
+                        policy.max_withdrawal_count > 0
+                            && policy.max_withdrawal_count
+                                <= T::BitcoinWithdrawalProposal::max_withdrawal_count()
+                            && !policy.window.is_zero(),
+                        Error::<T>::InvalidWithdrawalBatchPolicy
+                    );
+                    WithdrawalBatchPolicyOf::<T>::insert(chain, policy.clone());
+                    Self::deposit_event(Event::<T>::WithdrawalBatchPolicySet(chain, Some(policy)));
+                }
+                None => {
+                    WithdrawalBatchPolicyOf::<T>::remove(chain);
+                    SealedWithdrawalBatchOf::<T>::remove(chain);
+                    Self::deposit_event(Event::<T>::WithdrawalBatchPolicySet(chain, None));
+                }
+            }
+            Ok(())
+        }
+
+        /// Seal the applying withdrawals of `chain` into a batch right away without waiting
+        /// for the batching window.
+        ///
+        /// This is called by the trustees, the council and root.
+        #[pallet::weight(< T as Config >::WeightInfo::flush_withdrawal_batch())]
+        #[transactional]
+        pub fn flush_withdrawal_batch(origin: OriginFor<T>, chain: Chain) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(|o| Self::try_ensure_trustee(o, chain))
+                .or_else(ensure_root)?;
+
+            ensure!(
+                WithdrawalBatchPolicyOf::<T>::contains_key(chain),
+                Error::<T>::NoWithdrawalBatchPolicy
+            );
+            ensure!(
+                Self::sealed_withdrawal_ids(chain).is_none(),
+                Error::<T>::WithdrawalBatchAlreadySealed
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            Self::try_seal_withdrawal_batch(chain, now, true);
+            ensure!(
+                SealedWithdrawalBatchOf::<T>::contains_key(chain),
+                Error::<T>::EmptyWithdrawalBatch
+            );
+            Ok(())
+        }
+
+        /// Publish the partial signature (or the PSBT fragment) of the trustee for the
+        /// current withdrawal proposal of `chain` identified by `proposal_hash`.
+        ///
+        /// The shares of a proposal are collected in a round, which is finalized once the
+        /// shares reach the signature threshold of the trustees. The round is replaced by
+        /// the first share of the next proposal.
+        #[pallet::weight(< T as Config >::WeightInfo::submit_signature_share(share.len() as u32))]
+        pub fn submit_signature_share(
+            origin: OriginFor<T>,
+            chain: Chain,
+            proposal_hash: H256,
+            share: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(chain == Chain::Bitcoin, Error::<T>::NotSupportedChain);
+            ensure!(
+                share.len() as u32 <= T::MaxSignatureShareLen::get(),
+                Error::<T>::SignatureShareTooLong
+            );
+            ensure!(
+                T::BitcoinWithdrawalProposal::withdrawal_proposal_hash() == Some(proposal_hash),
+                Error::<T>::WithdrawalProposalMismatch
+            );
+
+            Self::collect_signature_share(chain, proposal_hash, who, share)
+        }
+
+        /// Set the slice of the withdrawal fee put into the trustee reward pot of `chain`.
+        ///
+        /// This is called by the council.
+        #[pallet::weight(< T as Config >::WeightInfo::set_trustee_fee_share())]
+        pub fn set_trustee_fee_share(
+            origin: OriginFor<T>,
+            chain: Chain,
+            share: Percent,
+        ) -> DispatchResult {
+            T::ParameterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            TrusteeFeeShareOf::<T>::insert(chain, share);
+            Ok(())
+        }
+
+        /// Claim the trustee fee reward of a finished trustee session.
+        ///
+        /// The fees put into the pot of the session are shared out by the number of the
+        /// completed withdrawals each trustee signed.
+        #[pallet::weight(< T as Config >::WeightInfo::claim_trustee_rewards())]
+        #[transactional]
+        pub fn claim_trustee_rewards(
+            origin: OriginFor<T>,
+            chain: Chain,
+            session_num: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                session_num < Self::trustee_session_info_len(chain),
+                Error::<T>::TrusteeSessionNotFinished
+            );
+
+            Self::apply_claim_trustee_fee_reward(chain, session_num, who)
+        }
     }
 
     #[pallet::event]
@@ -567,6 +909,32 @@ pub mod pallet {
         AllocNativeReward(T::AccountId, u32, BalanceOf<T>),
         /// The not native asset of trustee multi_account is assigned. [multi_account, session_number, asset_id, total_reward]
         AllocNotNativeReward(T::AccountId, u32, AssetId, BalanceOf<T>),
+        /// A maintenance window of the gateway was scheduled. [chain, window]
+        MaintenanceScheduled(Chain, MaintenanceWindow<T::BlockNumber>),
+        /// The maintenance window of the gateway was cancelled. [chain]
+        MaintenanceCancelled(Chain),
+        /// A member was added into the standby trustee pool. [who, chain]
+        StandbyTrusteeAdded(T::AccountId, Chain),
+        /// A member was removed from the standby trustee pool. [who, chain]
+        StandbyTrusteeRemoved(T::AccountId, Chain),
+        /// A standby trustee was promoted into the trustee election. [who, chain]
+        StandbyTrusteePromoted(T::AccountId, Chain),
+        /// The withdrawal batching policy was set. [chain, policy]
+        WithdrawalBatchPolicySet(Chain, Option<WithdrawalBatchPolicy<T::BlockNumber>>),
+        /// The applying withdrawals were sealed for the next withdrawal proposal. [chain, withdrawal_ids]
+        WithdrawalBatchSealed(Chain, Vec<WithdrawalRecordId>),
+        /// The gateway operation was paused in an emergency. [operation]
+        OperationPaused(GatewayOperation),
+        /// The paused gateway operation was resumed. [operation]
+        OperationUnpaused(GatewayOperation),
+        /// A trustee published a partial signature for the withdrawal proposal. [who, chain, proposal_hash]
+        SignatureShareSubmitted(T::AccountId, Chain, H256),
+        /// The partial signatures of the withdrawal proposal reached the threshold. [chain, proposal_hash, signers]
+        SignatureRoundFinalized(Chain, H256, Vec<T::AccountId>),
+        /// The trustee slice of a withdrawal fee was put into the reward pot. [chain, session_number, fee]
+        TrusteeFeeDeposited(Chain, u32, BalanceOf<T>),
+        /// The trustee claimed the fee reward of the session. [who, chain, session_number, reward]
+        TrusteeFeeRewardClaimed(T::AccountId, Chain, u32, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -605,6 +973,48 @@ pub mod pallet {
         TrusteeMembersNotEnough,
         /// exist in current trustee
         ExistCurrentTrustee,
+        /// the maintenance window must start in the future and affect some operations
+        InvalidMaintenanceWindow,
+        /// no maintenance window is scheduled
+        NoMaintenanceWindow,
+        /// the operation is suspended by the ongoing maintenance
+        InMaintenance,
+        /// the account is a standby trustee already
+        AlreadyStandbyTrustee,
+        /// the account is not a standby trustee
+        NotStandbyTrustee,
+        /// the standby trustee pool is full
+        TooManyStandbyTrustees,
+        /// the batch must have a positive window and count within the proposal limit
+        InvalidWithdrawalBatchPolicy,
+        /// the withdrawal batching is not enabled for the chain
+        NoWithdrawalBatchPolicy,
+        /// there are no applying withdrawals to be batched
+        EmptyWithdrawalBatch,
+        /// the sealed batch is still waiting for a withdrawal proposal
+        WithdrawalBatchAlreadySealed,
+        /// the withdrawal proposal does not match the sealed batch
+        WithdrawalBatchMismatch,
+        /// the operation is paused in an emergency
+        OperationIsPaused,
+        /// the operation is paused already
+        OperationAlreadyPaused,
+        /// the operation is not paused
+        OperationNotPaused,
+        /// the withdrawal has more outputs than a withdrawal proposal can pay out
+        TooManyWithdrawalOutputs,
+        /// the partial signature exceeds the maximum length
+        SignatureShareTooLong,
+        /// the proposal hash is not the current withdrawal proposal
+        WithdrawalProposalMismatch,
+        /// the trustee published the partial signature already
+        DuplicateSignatureShare,
+        /// the signature round of the proposal was finalized already
+        SignatureRoundAlreadyFinalized,
+        /// the trustee session is still in progress
+        TrusteeSessionNotFinished,
+        /// no trustee fee reward to claim in the session
+        NoTrusteeFeeReward,
     }
 
     #[pallet::storage]
@@ -646,6 +1056,33 @@ pub mod pallet {
         GenericTrusteeSessionInfo<T::AccountId, T::BlockNumber>,
     >;
 
+    /// The trustee session details of the corresponding chain and number, snapshotted when
+    /// the session is generated, so that the member entities are kept after the members
+    /// update them. Only the latest `MaxArchivedTrusteeSessions` sessions are kept.
+    #[pallet::storage]
+    #[pallet::getter(fn trustee_session_archive_of)]
+    pub(crate) type TrusteeSessionArchiveOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        Chain,
+        Twox64Concat,
+        u32,
+        GenericTrusteeSessionDetails<T::AccountId, T::BlockNumber>,
+    >;
+
+    /// The members of the trustee session of the corresponding chain and number, along with
+    /// the hot/cold entities they registered when the session was generated.
+    #[pallet::storage]
+    #[pallet::getter(fn trustee_session_members_of)]
+    pub(crate) type TrusteeSessionMembersOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        Chain,
+        Twox64Concat,
+        u32,
+        Vec<TrusteeMemberInfo<T::AccountId, Vec<u8>>>,
+    >;
+
     /// Trustee intention properties of the corresponding account and chain.
     #[pallet::storage]
     #[pallet::getter(fn trustee_intention_props_of)]
@@ -788,6 +1225,76 @@ pub mod pallet {
     pub(crate) type PreTotalSupply<T: Config> =
         StorageDoubleMap<_, Twox64Concat, Chain, Twox64Concat, u32, BalanceOf<T>, ValueQuery>;
 
+    /// The scheduled maintenance window of the gateway of each chain.
+    #[pallet::storage]
+    #[pallet::getter(fn maintenance_window_of)]
+    pub(crate) type MaintenanceWindowOf<T: Config> =
+        StorageMap<_, Twox64Concat, Chain, MaintenanceWindow<T::BlockNumber>, OptionQuery>;
+
+    /// The vetted standby trustees of the corresponding chain with their reserved bonds.
+    ///
+    /// The standby trustees are promoted in order when the trustee election lacks members.
+    #[pallet::storage]
+    #[pallet::getter(fn standby_trustees_of)]
+    pub(crate) type StandbyTrusteesOf<T: Config> =
+        StorageMap<_, Twox64Concat, Chain, Vec<(T::AccountId, BalanceOf<T>)>, ValueQuery>;
+
+    /// The bit flags of the gateway operations paused in an emergency.
+    #[pallet::storage]
+    #[pallet::getter(fn operation_pause)]
+    pub(crate) type OperationPause<T: Config> = StorageValue<_, u8, ValueQuery>;
+
+    /// The withdrawal batching policy of the corresponding chain.
+    #[pallet::storage]
+    #[pallet::getter(fn withdrawal_batch_policy_of)]
+    pub(crate) type WithdrawalBatchPolicyOf<T: Config> =
+        StorageMap<_, Twox64Concat, Chain, WithdrawalBatchPolicy<T::BlockNumber>, OptionQuery>;
+
+    /// The withdrawals of the corresponding chain sealed for the next withdrawal proposal.
+    #[pallet::storage]
+    #[pallet::getter(fn sealed_withdrawal_batch_of)]
+    pub(crate) type SealedWithdrawalBatchOf<T: Config> =
+        StorageMap<_, Twox64Concat, Chain, SealedWithdrawalBatch<T::BlockNumber>, OptionQuery>;
+
+    /// The partial signatures collected for the latest withdrawal proposal of the chain.
+    #[pallet::storage]
+    #[pallet::getter(fn signature_round_of)]
+    pub(crate) type SignatureRoundOf<T: Config> =
+        StorageMap<_, Twox64Concat, Chain, SignatureRound<T::AccountId>, OptionQuery>;
+
+    /// The slice of the withdrawal fee put into the trustee reward pot of the chain.
+    #[pallet::storage]
+    #[pallet::getter(fn trustee_fee_share_of)]
+    pub(crate) type TrusteeFeeShareOf<T: Config> =
+        StorageMap<_, Twox64Concat, Chain, Percent, ValueQuery>;
+
+    /// The trustee reward pot of each trustee session.
+    #[pallet::storage]
+    #[pallet::getter(fn trustee_fee_pot_of)]
+    pub(crate) type TrusteeFeePotOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        Chain,
+        Twox64Concat,
+        u32,
+        TrusteeFeePot<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// The number of the completed withdrawals signed by the trustee in a trustee session,
+    /// removed once the reward is claimed.
+    #[pallet::storage]
+    #[pallet::getter(fn trustee_fee_signatures)]
+    pub(crate) type TrusteeFeeSignatures<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        (Chain, u32),
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
+        ValueQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub trustees: Vec<(
@@ -840,6 +1347,7 @@ impl<T: Config> Pallet<T> {
         addr: &[u8],
         ext: &Memo,
     ) -> DispatchResult {
+        Self::ensure_not_paused(GatewayOperation::Withdrawal)?;
         ext.check_validity()?;
 
         let chain = xpallet_assets_registrar::Pallet::<T>::chain_of(&asset_id)?;
@@ -848,6 +1356,9 @@ impl<T: Config> Pallet<T> {
                 // bitcoin do not need memo
                 T::Bitcoin::check_addr(addr, b"")?;
             }
+            Chain::Ethereum => {
+                T::Ethereum::check_addr(addr, b"")?;
+            }
             _ => return Err(Error::<T>::NotSupportedChain.into()),
         };
         // we could only split withdrawal limit due to a runtime-api would call `withdrawal_limit`
@@ -862,6 +1373,309 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+// Maintenance
+impl<T: Config> Pallet<T> {
+    /// Returns the maintenance window of `chain` which is not expired along with its countdown.
+    pub fn maintenance_status(chain: Chain) -> Option<MaintenanceStatus<T::BlockNumber>> {
+        let now = frame_system::Pallet::<T>::block_number();
+        Self::maintenance_window_of(chain)
+            .filter(|window| !window.is_expired(now))
+            .map(|window| window.status(now))
+    }
+
+    pub fn ensure_not_in_maintenance(chain: Chain, operation: GatewayOperation) -> DispatchResult {
+        let now = frame_system::Pallet::<T>::block_number();
+        if let Some(window) = Self::maintenance_window_of(chain) {
+            ensure!(
+                !(window.is_active(now) && window.affects(operation)),
+                Error::<T>::InMaintenance
+            );
+        }
+        Ok(())
+    }
+}
+
+// Emergency pause
+impl<T: Config> Pallet<T> {
+    pub fn is_paused(operation: GatewayOperation) -> bool {
+        Self::operation_pause() & operation.flag() != 0
+    }
+}
+
+impl<T: Config> OperationPauseProvider for Pallet<T> {
+    fn ensure_not_paused(operation: GatewayOperation) -> DispatchResult {
+        ensure!(!Self::is_paused(operation), Error::<T>::OperationIsPaused);
+        Ok(())
+    }
+}
+
+impl<T: Config> TrusteeInfoConfigProvider for Pallet<T> {
+    fn trustee_info_config(chain: Chain) -> TrusteeInfoConfig {
+        Self::trustee_info_config_of(chain)
+    }
+}
+
+// Withdrawal batch
+impl<T: Config> Pallet<T> {
+    /// Returns the applying withdrawals of `chain` which are not sealed yet along with the
+    /// block number they were applied at, in the order of id, and the number of the pending
+    /// withdrawals read.
+    fn unsealed_withdrawals(chain: Chain) -> (u32, Vec<(WithdrawalRecordId, T::BlockNumber)>) {
+        let sealed = Self::sealed_withdrawal_ids(chain).unwrap_or_default();
+        let mut scanned = 0u32;
+        let mut unsealed = xpallet_gateway_records::Pallet::<T>::pending_withdrawal_set()
+            .inspect(|_| scanned = scanned.saturating_add(1))
+            .filter(|(id, record)| {
+                xpallet_gateway_records::Pallet::<T>::state_of(id)
+                    == Some(WithdrawalState::Applying)
+                    && !sealed.contains(id)
+                    && xpallet_assets_registrar::Pallet::<T>::chain_of(&record.asset_id()).ok()
+                        == Some(chain)
+            })
+            .map(|(id, record)| (id, record.height()))
+            .collect::<Vec<_>>();
+        unsealed.sort_unstable_by_key(|(id, _)| *id);
+        (scanned, unsealed)
+    }
+
+    /// Returns the sealed withdrawals of `chain` which are still applying, `None` if there
+    /// is no such withdrawal, e.g. all of them were cancelled.
+    pub fn sealed_withdrawal_ids(chain: Chain) -> Option<Vec<WithdrawalRecordId>> {
+        Self::sealed_withdrawal_batch_of(chain)
+            .map(|batch| {
+                batch
+                    .withdrawal_ids
+                    .into_iter()
+                    .filter(|id| {
+                        xpallet_gateway_records::Pallet::<T>::state_of(id)
+                            == Some(WithdrawalState::Applying)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|ids| !ids.is_empty())
+    }
+
+    /// Seals the applying withdrawals of `chain` into a batch when the batching policy is met
+    /// or `force` is true, and there is no sealed batch waiting for a proposal.
+    ///
+    /// The policy is checked against the running totals of the applying withdrawals kept by
+    /// the records, the pending withdrawals are only read when the batch is about to be sealed.
+    ///
+    /// Returns the weight consumed.
+    pub(crate) fn try_seal_withdrawal_batch(
+        chain: Chain,
+        now: T::BlockNumber,
+        force: bool,
+    ) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let policy = match Self::withdrawal_batch_policy_of(chain) {
+            Some(policy) => policy,
+            None => return db_weight.reads(1),
+        };
+
+        let mut weight = db_weight.reads(2);
+        if let Some(batch) = Self::sealed_withdrawal_batch_of(chain) {
+            weight = weight.saturating_add(db_weight.reads(batch.withdrawal_ids.len() as Weight));
+            if Self::sealed_withdrawal_ids(chain).is_some() {
+                return weight;
+            }
+            // All the sealed withdrawals were cancelled or processed.
+            SealedWithdrawalBatchOf::<T>::remove(chain);
+            weight = weight.saturating_add(db_weight.writes(1));
+        }
+
+        // No batch is sealed, all the applying withdrawals are pending.
+        let applying = xpallet_gateway_records::Pallet::<T>::applying_withdrawals_of(chain);
+        weight = weight.saturating_add(db_weight.reads(1));
+        let is_full = applying.output_count >= policy.max_withdrawal_count;
+        let is_due = applying
+            .since
+            .map_or(false, |since| now >= since.saturating_add(policy.window));
+        if applying.output_count.is_zero() || !(force || is_full || is_due) {
+            return weight;
+        }
+
+        let (scanned, pending) = Self::unsealed_withdrawals(chain);
+        weight = weight.saturating_add(<T as Config>::WeightInfo::seal_withdrawal_batch(scanned));
+        let oldest = match pending.iter().map(|(_, height)| *height).min() {
+            Some(oldest) => oldest,
+            None => return weight,
+        };
+
+        // A withdrawal paying out to multiple addresses counts as many withdrawals.
+        let output_counts = pending
+            .iter()
+            .map(|(id, _)| xpallet_gateway_records::Pallet::<T>::withdrawal_output_count(*id))
+            .collect::<Vec<_>>();
+        let is_full = output_counts.iter().sum::<u32>() >= policy.max_withdrawal_count;
+        let is_due = now >= oldest.saturating_add(policy.window);
+        if !(force || is_full || is_due) {
+            // The oldest withdrawal was processed or canceled, wait for the current one.
+            xpallet_gateway_records::Pallet::<T>::refresh_applying_since(chain, Some(oldest));
+            return weight;
+        }
+
+        let mut outputs = 0u32;
+        let withdrawal_ids = pending
+            .into_iter()
+            .zip(output_counts)
+            .take_while(|(_, count)| {
+                outputs = outputs.saturating_add(*count);
+                // Always seal the first withdrawal, or it could never be batched.
+                outputs == *count || outputs <= policy.max_withdrawal_count
+            })
+            .map(|((id, _), _)| id)
+            .collect::<Vec<_>>();
+        let rest_since = pending
+            .iter()
+            .filter(|(id, _)| !withdrawal_ids.contains(id))
+            .map(|(_, height)| *height)
+            .min();
+        xpallet_gateway_records::Pallet::<T>::refresh_applying_since(chain, rest_since);
+        info!(
+            target: "runtime::gateway::common",
+            "[try_seal_withdrawal_batch] chain:{:?}, withdrawal_ids:{:?}, force:{}",
+            chain, withdrawal_ids, force
+        );
+        SealedWithdrawalBatchOf::<T>::insert(
+            chain,
+            SealedWithdrawalBatch {
+                sealed_at: now,
+                withdrawal_ids: withdrawal_ids.clone(),
+            },
+        );
+        Self::deposit_event(Event::<T>::WithdrawalBatchSealed(chain, withdrawal_ids));
+        weight
+    }
+
+    /// Adds the partial signature of the trustee `who` into the signature round of the
+    /// withdrawal proposal `proposal_hash`, a new round is started for a new proposal.
+    pub(crate) fn collect_signature_share(
+        chain: Chain,
+        proposal_hash: H256,
+        who: T::AccountId,
+        share: Vec<u8>,
+    ) -> DispatchResult {
+        let session = T::BitcoinTrusteeSessionProvider::current_trustee_session()?;
+        ensure!(
+            session
+                .trustee_list
+                .iter()
+                .any(|(trustee, _)| trustee == &who),
+            Error::<T>::NotRegistered
+        );
+
+        let mut round = match Self::signature_round_of(chain) {
+            Some(round) if round.proposal_hash == proposal_hash => round,
+            _ => SignatureRound {
+                proposal_hash,
+                threshold: session.threshold,
+                shares: Vec::new(),
+                finalized: false,
+            },
+        };
+        ensure!(!round.finalized, Error::<T>::SignatureRoundAlreadyFinalized);
+        ensure!(
+            round.shares.iter().all(|(signer, _)| signer != &who),
+            Error::<T>::DuplicateSignatureShare
+        );
+
+        round.shares.push((who.clone(), share));
+        Self::deposit_event(Event::<T>::SignatureShareSubmitted(
+            who,
+            chain,
+            proposal_hash,
+        ));
+        if round.shares.len() >= usize::from(round.threshold) {
+            round.finalized = true;
+            info!(
+                target: "runtime::gateway::common",
+                "[collect_signature_share] chain:{:?}, proposal:{:?}, the round is finalized",
+                chain, proposal_hash
+            );
+            let signers = round
+                .shares
+                .iter()
+                .map(|(signer, _)| signer.clone())
+                .collect();
+            Self::deposit_event(Event::<T>::SignatureRoundFinalized(
+                chain,
+                proposal_hash,
+                signers,
+            ));
+        }
+        SignatureRoundOf::<T>::insert(chain, round);
+        Ok(())
+    }
+
+    /// Returns the withdrawal batch of `chain` if the batching is enabled.
+    pub fn withdrawal_batch_status(chain: Chain) -> Option<WithdrawalBatchStatus<T::BlockNumber>> {
+        let policy = Self::withdrawal_batch_policy_of(chain)?;
+        let sealed = Self::sealed_withdrawal_batch_of(chain)
+            .filter(|_| Self::sealed_withdrawal_ids(chain).is_some());
+        let (_, pending) = Self::unsealed_withdrawals(chain);
+        let seal_at = pending
+            .iter()
+            .map(|(_, height)| *height)
+            .min()
+            .map(|oldest| oldest.saturating_add(policy.window));
+        Some(WithdrawalBatchStatus {
+            policy,
+            sealed,
+            pending: pending.into_iter().map(|(id, _)| id).collect(),
+            seal_at,
+        })
+    }
+
+    fn try_ensure_trustee(origin: OriginFor<T>, chain: Chain) -> Result<(), OriginFor<T>> {
+        let who = match ensure_signed(origin.clone()) {
+            Ok(who) => who,
+            Err(_) => return Err(origin),
+        };
+
+        let is_trustee = match chain {
+            Chain::Bitcoin => {
+                T::BitcoinTrusteeSessionProvider::current_trustee_session()
+                    .map(|info| info.trustee_list.iter().any(|n| n.0 == who))
+                    .unwrap_or(false)
+                    || T::BitcoinTrusteeSessionProvider::current_proxy_account()
+                        .map(|proxies| proxies.contains(&who))
+                        .unwrap_or(false)
+            }
+            _ => false,
+        };
+
+        if is_trustee {
+            Ok(())
+        } else {
+            Err(origin)
+        }
+    }
+}
+
+impl<T: Config> WithdrawalBatchProvider for Pallet<T> {
+    fn ensure_sealed_batch(chain: Chain, withdrawal_id_list: &[u32]) -> DispatchResult {
+        if !WithdrawalBatchPolicyOf::<T>::contains_key(chain) {
+            return Ok(());
+        }
+
+        let mut sealed = Self::sealed_withdrawal_ids(chain).unwrap_or_default();
+        sealed.sort_unstable();
+        let mut withdrawal_id_list = withdrawal_id_list.to_vec();
+        withdrawal_id_list.sort_unstable();
+        withdrawal_id_list.dedup();
+        ensure!(
+            !sealed.is_empty() && sealed == withdrawal_id_list,
+            Error::<T>::WithdrawalBatchMismatch
+        );
+        Ok(())
+    }
+
+    fn on_batch_proposed(chain: Chain) {
+        SealedWithdrawalBatchOf::<T>::remove(chain);
+    }
+}
+
 /// Trustee setup
 impl<T: Config> Pallet<T> {
     pub fn setup_trustee_impl(
@@ -921,7 +1735,7 @@ impl<T: Config> Pallet<T> {
         ));
     }
 
-    fn set_referral_binding(chain: Chain, who: T::AccountId, referral: T::AccountId) {
+    pub fn set_referral_binding(chain: Chain, who: T::AccountId, referral: T::AccountId) {
         ReferralBindingOf::<T>::insert(&who, &chain, referral.clone());
         Self::deposit_event(Event::<T>::ReferralBinded(who, chain, referral))
     }
@@ -958,6 +1772,73 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+/// Trustee standby
+impl<T: Config> Pallet<T> {
+    pub fn is_standby_trustee(chain: Chain, who: &T::AccountId) -> bool {
+        Self::standby_trustees_of(chain)
+            .iter()
+            .any(|(standby, _)| standby == who)
+    }
+
+    fn add_standby_trustee_impl(chain: Chain, who: T::AccountId) -> DispatchResult {
+        ensure!(chain == Chain::Bitcoin, Error::<T>::NotSupportedChain);
+        ensure!(
+            Self::ensure_not_current_trustee(&who),
+            Error::<T>::ExistCurrentTrustee
+        );
+
+        let mut standby_trustees = Self::standby_trustees_of(chain);
+        ensure!(
+            !standby_trustees.iter().any(|(standby, _)| *standby == who),
+            Error::<T>::AlreadyStandbyTrustee
+        );
+        ensure!(
+            standby_trustees.len() < Self::trustee_info_config_of(chain).max_trustee_count as usize,
+            Error::<T>::TooManyStandbyTrustees
+        );
+
+        let bond = T::StandbyTrusteeBond::get();
+        <T as xpallet_assets::Config>::Currency::reserve(&who, bond)?;
+        standby_trustees.push((who.clone(), bond));
+        StandbyTrusteesOf::<T>::insert(chain, standby_trustees);
+
+        Self::deposit_event(Event::<T>::StandbyTrusteeAdded(who, chain));
+        Ok(())
+    }
+
+    fn remove_standby_trustee_impl(chain: Chain, who: &T::AccountId) -> DispatchResult {
+        let mut standby_trustees = Self::standby_trustees_of(chain);
+        let index = standby_trustees
+            .iter()
+            .position(|(standby, _)| standby == who)
+            .ok_or(Error::<T>::NotStandbyTrustee)?;
+        let (_, bond) = standby_trustees.remove(index);
+        StandbyTrusteesOf::<T>::insert(chain, standby_trustees);
+
+        <T as xpallet_assets::Config>::Currency::unreserve(who, bond);
+        Ok(())
+    }
+
+    /// Returns the standby trustees to be promoted in order to fill `pool` up to `desired_members`.
+    fn standby_promotions(
+        chain: Chain,
+        pool: &[T::AccountId],
+        filter_members: &[T::AccountId],
+        desired_members: usize,
+    ) -> Vec<T::AccountId> {
+        Self::standby_trustees_of(chain)
+            .into_iter()
+            .map(|(who, _)| who)
+            .filter(|who| {
+                !pool.contains(who)
+                    && !filter_members.contains(who)
+                    && Self::ensure_set_address(who, chain)
+            })
+            .take(desired_members.saturating_sub(pool.len()))
+            .collect()
+    }
+}
+
 /// Trustee transition
 impl<T: Config> Pallet<T> {
     // Make sure the hot and cold pubkey are set and do not check the validity of the address
@@ -972,6 +1853,7 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn do_trustee_election(chain: Chain) -> DispatchResult {
+        Self::ensure_not_paused(GatewayOperation::TrusteeTransition)?;
         ensure!(
             !Self::trustee_transition_status(chain),
             Error::<T>::LastTransitionNotCompleted
@@ -994,7 +1876,7 @@ impl<T: Config> Pallet<T> {
 
         let all_trustee_pool = Self::generate_trustee_pool();
 
-        let new_trustee_pool: Vec<T::AccountId> = all_trustee_pool
+        let mut new_trustee_pool: Vec<T::AccountId> = all_trustee_pool
             .iter()
             .filter_map(|who| {
                 match filter_members.contains(who) || !Self::ensure_set_address(who, chain) {
@@ -1015,6 +1897,10 @@ impl<T: Config> Pallet<T> {
         let desired_members =
             (<T as pallet_elections_phragmen::Config>::DesiredMembers::get() - 1) as usize;
 
+        let promoted =
+            Self::standby_promotions(chain, &new_trustee_pool, &filter_members, desired_members);
+        new_trustee_pool.extend(promoted.iter().cloned());
+
         ensure!(
             new_trustee_pool.len() >= desired_members,
             Error::<T>::TrusteeMembersNotEnough
@@ -1039,6 +1925,10 @@ impl<T: Config> Pallet<T> {
 
         Self::transition_trustee_session_impl(chain, new_trustee_candidate)?;
         LittleBlackHouse::<T>::insert(chain, remain_filter_members);
+        for who in promoted {
+            Self::remove_standby_trustee_impl(chain, &who)?;
+            Self::deposit_event(Event::<T>::StandbyTrusteePromoted(who, chain));
+        }
         if Self::trustee_session_info_len(chain) != 1 {
             TrusteeTransitionStatus::<T>::insert(chain, true);
             let total_supply = T::BitcoinTotalSupply::total_supply();
@@ -1119,6 +2009,8 @@ impl<T: Config> Pallet<T> {
         TrusteeSessionInfoLen::<T>::insert(chain, session_number);
         TrusteeSessionInfoOf::<T>::insert(chain, session_number, session_info.0.clone());
         TrusteeMultiSigAddr::<T>::insert(chain, multi_addr);
+        Self::record_trustee_session_members(chain, session_number, &session_info.0);
+        Self::archive_trustee_session(chain, session_number, session_info.0.clone());
         // Remove the information of the previous aggregate public key，Withdrawal is prohibited at this time.
         AggPubkeyInfo::<T>::remove_all(None);
         for index in 0..session_info.1.agg_pubkeys.len() {
@@ -1165,10 +2057,64 @@ impl<T: Config> Pallet<T> {
 
         let mut session_info = Self::try_generate_session_info(chain, trustees)?;
         session_info.0 = trustee_info;
+        TrusteeSessionArchiveOf::<T>::remove(chain, session_number.saturating_add(1));
+        TrusteeSessionMembersOf::<T>::remove(chain, session_number.saturating_add(1));
 
         Self::alter_trustee_session(chain, session_number, &mut session_info)
     }
 
+    /// Record the members of the trustee session with their current entities unless recorded
+    /// already, i.e. when the session is generated.
+    fn record_trustee_session_members(
+        chain: Chain,
+        session_number: u32,
+        info: &GenericTrusteeSessionInfo<T::AccountId, T::BlockNumber>,
+    ) {
+        if !TrusteeSessionMembersOf::<T>::contains_key(chain, session_number) {
+            let members = Self::current_trustee_members(chain, info);
+            TrusteeSessionMembersOf::<T>::insert(chain, session_number, members);
+        }
+    }
+
+    /// Returns the members of the trustee session with the entities they currently registered.
+    pub(crate) fn current_trustee_members(
+        chain: Chain,
+        info: &GenericTrusteeSessionInfo<T::AccountId, T::BlockNumber>,
+    ) -> Vec<TrusteeMemberInfo<T::AccountId, Vec<u8>>> {
+        info.0
+            .trustee_list
+            .iter()
+            .map(|(account, _)| {
+                let props = Self::trustee_intention_props_of(account, chain).map(|props| props.0);
+                TrusteeMemberInfo {
+                    account: account.clone(),
+                    hot_entity: props
+                        .as_ref()
+                        .map(|props| props.hot_entity.clone())
+                        .unwrap_or_default(),
+                    cold_entity: props.map(|props| props.cold_entity).unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+
+    /// Archive the details of the trustee session unless archived already, and prune the
+    /// session out of the archive range.
+    fn archive_trustee_session(
+        chain: Chain,
+        session_number: u32,
+        info: GenericTrusteeSessionInfo<T::AccountId, T::BlockNumber>,
+    ) {
+        if !TrusteeSessionArchiveOf::<T>::contains_key(chain, session_number) {
+            if let Some(details) = Self::build_session_details(chain, session_number, info) {
+                TrusteeSessionArchiveOf::<T>::insert(chain, session_number, details);
+            }
+        }
+        if let Some(expired) = session_number.checked_sub(T::MaxArchivedTrusteeSessions::get()) {
+            TrusteeSessionArchiveOf::<T>::remove(chain, expired);
+        }
+    }
+
     pub fn generate_multisig_addr(
         chain: Chain,
         session_info: &GenericTrusteeSessionInfo<T::AccountId, T::BlockNumber>,
@@ -1336,6 +2282,51 @@ impl<T: Config> Pallet<T> {
         }
         Ok(())
     }
+
+    /// The account holding the trustee slice of the withdrawal fees of `chain`.
+    pub fn trustee_fee_pot_account(chain: Chain) -> T::AccountId {
+        TRUSTEE_FEE_POT_ID.into_sub_account(chain)
+    }
+
+    fn apply_claim_trustee_fee_reward(
+        chain: Chain,
+        session_num: u32,
+        who: T::AccountId,
+    ) -> DispatchResult {
+        let signatures = TrusteeFeeSignatures::<T>::take((chain, session_num), &who);
+        ensure!(signatures > 0, Error::<T>::NoTrusteeFeeReward);
+
+        let pot = Self::trustee_fee_pot_of(chain, session_num);
+        let reward = pot
+            .total_fee
+            .saturating_mul(signatures.saturated_into())
+            .checked_div(&pot.total_signatures.saturated_into())
+            .ok_or(Error::<T>::NoTrusteeFeeReward)?;
+        if !reward.is_zero() {
+            xpallet_assets::Pallet::<T>::move_usable_balance(
+                &X_BTC,
+                &Self::trustee_fee_pot_account(chain),
+                &who,
+                reward,
+            )
+            .map_err(|e| {
+                error!(
+                    target: "runtime::gateway::common",
+                    "[apply_claim_trustee_fee_reward] error {:?}, who:{:?}, session_num:{}, reward:{:?}.",
+                    e, who, session_num, reward
+                );
+                xpallet_assets::Error::<T>::InsufficientBalance
+            })?;
+        }
+
+        Self::deposit_event(Event::<T>::TrusteeFeeRewardClaimed(
+            who,
+            chain,
+            session_num,
+            reward,
+        ));
+        Ok(())
+    }
 }
 
 /// Ensure trustee admin
@@ -1362,6 +2353,7 @@ impl<T: Config> Pallet<T> {
         let chain = xpallet_assets_registrar::Pallet::<T>::chain_of(asset_id)?;
         match chain {
             Chain::Bitcoin => T::Bitcoin::withdrawal_limit(asset_id),
+            Chain::Ethereum => T::Ethereum::withdrawal_limit(asset_id),
             _ => Err(Error::<T>::NotSupportedChain.into()),
         }
     }
@@ -1391,10 +2383,7 @@ impl<T: Config> Pallet<T> {
                 (
                     id,
                     (
-                        Withdrawal::new(
-                            record,
-                            xpallet_gateway_records::Pallet::<T>::state_of(id).unwrap_or_default(),
-                        ),
+                        xpallet_gateway_records::Pallet::<T>::withdrawal_info_of(id, record),
                         limit.clone(),
                     ),
                 )
@@ -1402,4 +2391,72 @@ impl<T: Config> Pallet<T> {
             .collect();
         Ok(result)
     }
+
+    /// Resolve the session number used by rpc calls.
+    ///
+    /// `-1` means the latest session and `-2` means the previous one.
+    pub fn resolve_session_number(chain: Chain, session_number: i32) -> Option<u32> {
+        match session_number {
+            -1i32 => Some(Self::trustee_session_info_len(chain)),
+            -2i32 => Self::trustee_session_info_len(chain).checked_sub(1),
+            n if n >= 0 => Some(n as u32),
+            _ => None,
+        }
+    }
+
+    /// Get the trustee session info along with the hot/cold entities each member registered
+    /// when the session was generated.
+    pub fn trustee_session_details(
+        chain: Chain,
+        session_number: i32,
+    ) -> Option<GenericTrusteeSessionDetails<T::AccountId, T::BlockNumber>> {
+        let session_number = Self::resolve_session_number(chain, session_number)?;
+        let info = Self::trustee_session_info_of(chain, session_number)?;
+        Self::build_session_details(chain, session_number, info)
+    }
+
+    /// Get the trustee session of the given number or the one active at the given block.
+    ///
+    /// The archived details are returned if any, i.e. the member entities at the time the
+    /// session was generated. During a transition, the block belongs to the previous session
+    /// until the transition completes, as it still controls the funds.
+    pub fn trustee_session_at(
+        chain: Chain,
+        at: TrusteeSessionAt<T::BlockNumber>,
+    ) -> Option<GenericTrusteeSessionDetails<T::AccountId, T::BlockNumber>> {
+        let session_number = match at {
+            TrusteeSessionAt::Session(session_number) => session_number,
+            TrusteeSessionAt::Block(block) => {
+                (0..=Self::trustee_session_info_len(chain)).find(|&session_number| {
+                    Self::trustee_session_info_of(chain, session_number).map_or(false, |info| {
+                        info.0.start_height.map_or(false, |start| start <= block)
+                            && info.0.end_height.map_or(true, |end| block < end)
+                    })
+                })?
+            }
+        };
+        Self::trustee_session_archive_of(chain, session_number).or_else(|| {
+            let info = Self::trustee_session_info_of(chain, session_number)?;
+            Self::build_session_details(chain, session_number, info)
+        })
+    }
+
+    fn build_session_details(
+        chain: Chain,
+        session_number: u32,
+        info: GenericTrusteeSessionInfo<T::AccountId, T::BlockNumber>,
+    ) -> Option<GenericTrusteeSessionDetails<T::AccountId, T::BlockNumber>> {
+        let members = Self::trustee_session_members_of(chain, session_number)?;
+        let info = info.0;
+        Some(GenericTrusteeSessionDetails(TrusteeSessionDetails {
+            session_number,
+            members,
+            threshold: info.threshold,
+            hot_address: info.hot_address,
+            cold_address: info.cold_address,
+            multi_account: info.multi_account,
+            start_height: info.start_height,
+            end_height: info.end_height,
+        }))
+    }
 }
@@ -2,4 +2,5 @@
 
 //! All migrations of this pallet.
 
+pub mod session_members;
 pub mod taproot;
@@ -0,0 +1,49 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use crate::{
+    Config, Pallet, TrusteeSessionArchiveOf, TrusteeSessionInfoOf, TrusteeSessionMembersOf,
+};
+use frame_support::{log::info, traits::Get, weights::Weight};
+
+/// Record the members of the trustee sessions generated before `TrusteeSessionMembersOf`.
+///
+/// The archived members are used if any, otherwise the members with the entities they
+/// currently registered. The sessions already recorded are left untouched.
+pub fn apply<T: Config>() -> Weight {
+    let mut reads = 0;
+    let mut writes = 0;
+    for (chain, session_number, session_info) in TrusteeSessionInfoOf::<T>::iter() {
+        reads += 2;
+        if TrusteeSessionMembersOf::<T>::contains_key(chain, session_number) {
+            continue;
+        }
+        let members = match TrusteeSessionArchiveOf::<T>::get(chain, session_number) {
+            Some(details) => details.0.members,
+            None => {
+                reads += session_info.0.trustee_list.len() as Weight;
+                Pallet::<T>::current_trustee_members(chain, &session_info)
+            }
+        };
+        reads += 1;
+        writes += 1;
+        TrusteeSessionMembersOf::<T>::insert(chain, session_number, members);
+    }
+    info!(
+        target: "runtime::gateway::common",
+        "recorded the members of {} trustee sessions.",
+        writes,
+    );
+    <T as frame_system::Config>::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Check that the members of every trustee session are recorded after the migration.
+#[cfg(feature = "try-runtime")]
+pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+    frame_support::ensure!(
+        TrusteeSessionInfoOf::<T>::iter_keys().all(|(chain, session_number)| {
+            TrusteeSessionMembersOf::<T>::contains_key(chain, session_number)
+        }),
+        "the members of a trustee session are not recorded after migration"
+    );
+    Ok(())
+}
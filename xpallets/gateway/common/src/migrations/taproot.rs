@@ -117,3 +117,51 @@ pub fn migrate_trustee_intention_properties<T: Config>() -> Weight {
     <T as frame_system::Config>::DbWeight::get()
         .reads_writes(count as Weight + 1, count as Weight + 1)
 }
+
+/// Check that the trustee storages are still in the old format before the migration.
+#[cfg(feature = "try-runtime")]
+pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+    use frame_support::{storage::migration::storage_iter, traits::PalletInfoAccess};
+
+    let pallet = <crate::Pallet<T> as PalletInfoAccess>::name().as_bytes();
+    let old_session_infos =
+        storage_iter::<OldGenericTrusteeSessionInfo<T::AccountId>>(pallet, b"TrusteeSessionInfoOf")
+            .count();
+    frame_support::ensure!(
+        old_session_infos == TrusteeSessionInfoOf::<T>::iter_keys().count(),
+        "TrusteeSessionInfoOf is not in the old format"
+    );
+
+    let old_props =
+        storage_iter::<OldGenericTrusteeIntentionProps>(pallet, b"TrusteeIntentionPropertiesOf")
+            .count();
+    frame_support::ensure!(
+        old_props == TrusteeIntentionPropertiesOf::<T>::iter_keys().count(),
+        "TrusteeIntentionPropertiesOf is not in the old format"
+    );
+    Ok(())
+}
+
+/// Check that all the trustee storages can be decoded after the migration.
+#[cfg(feature = "try-runtime")]
+pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+    frame_support::ensure!(
+        TrusteeSessionInfoOf::<T>::iter_keys()
+            .all(|(chain, number)| TrusteeSessionInfoOf::<T>::try_get(chain, number).is_ok()),
+        "TrusteeSessionInfoOf can not be decoded after migration"
+    );
+    frame_support::ensure!(
+        TrusteeIntentionPropertiesOf::<T>::iter_keys()
+            .all(|(who, chain)| TrusteeIntentionPropertiesOf::<T>::try_get(who, chain).is_ok()),
+        "TrusteeIntentionPropertiesOf can not be decoded after migration"
+    );
+
+    let len = TrusteeSessionInfoLen::<T>::get(Chain::Bitcoin);
+    if len != 0 {
+        frame_support::ensure!(
+            TrusteeSessionInfoOf::<T>::contains_key(Chain::Bitcoin, len),
+            "the latest bitcoin trustee session does not exist after migration"
+        );
+    }
+    Ok(())
+}
@@ -10,7 +10,6 @@ use frame_support::{
     traits::{ChangeMembers, GenesisBuild, LockIdentifier, UnixTime},
     PalletId,
 };
-use frame_system::EnsureSigned;
 use light_bitcoin::keys::{Address, Public};
 use light_bitcoin::mast::{compute_min_threshold, Mast};
 use light_bitcoin::script::{Builder, Bytes, Opcode};
@@ -23,7 +22,7 @@ use sp_runtime::{
     AccountId32, DispatchError, DispatchResult,
 };
 
-use crate::traits::TotalSupply;
+use crate::traits::{ProposalProvider, TotalSupply};
 use crate::utils::{two_thirds_unsafe, MAX_TAPROOT_NODES};
 use crate::{
     self as xpallet_gateway_common,
@@ -211,6 +210,7 @@ impl xpallet_assets_registrar::Config for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type TechnicalOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -267,6 +267,9 @@ impl xpallet_assets::Config for Test {
     type TreasuryAccount = SimpleTreasuryAccount;
     type OnCreatedAccount = frame_system::Provider<Test>;
     type OnAssetChanged = ();
+    type RestrictionOrigin = frame_system::EnsureRoot<AccountId>;
+    type DustReapBounty = ();
+    type DustReapExempt = frame_support::traits::Nothing;
     type WeightInfo = ();
 }
 
@@ -308,11 +311,17 @@ impl xpallet_gateway_bitcoin::Config for Test {
     type Event = ();
     type UnixTime = CustomTimestamp;
     type AccountExtractor = xp_gateway_bitcoin::OpReturnExtractor;
-    type CouncilOrigin = EnsureSigned<AccountId>;
+    type CouncilOrigin = frame_system::EnsureRoot<AccountId>;
+    type ParameterOrigin = frame_system::EnsureRoot<AccountId>;
+    type AttesterOrigin = frame_system::EnsureRoot<AccountId>;
     type TrusteeSessionProvider = ();
     type TrusteeInfoUpdate = ();
+    type WithdrawalBatch = ();
+    type OperationPause = ();
     type ReferralBinding = ();
     type AddressBinding = ();
+    type MaxPrioritizedRelays = frame_support::traits::ConstU32<16>;
+    type MaxFeelessRelays = frame_support::traits::ConstU32<16>;
     type WeightInfo = ();
 }
 
@@ -358,6 +367,35 @@ impl<T: xpallet_gateway_bitcoin::Config> TotalSupply<BalanceOf<T>> for MockBitco
     }
 }
 
+pub struct MockEthereum;
+impl ChainT<Balance> for MockEthereum {
+    const ASSET_ID: u32 = X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+
+thread_local! {
+    pub static PROPOSAL_HASH: RefCell<Option<H256>> = RefCell::new(None);
+}
+pub struct MockProposal;
+impl ProposalProvider for MockProposal {
+    type WithdrawalProposal = H256;
+
+    fn get_withdrawal_proposal() -> Option<Self::WithdrawalProposal> {
+        PROPOSAL_HASH.with(|hash| *hash.borrow())
+    }
+
+    fn withdrawal_proposal_hash() -> Option<H256> {
+        PROPOSAL_HASH.with(|hash| *hash.borrow())
+    }
+
+    fn max_withdrawal_count() -> u32 {
+        u32::MAX
+    }
+}
+
 const EC_P: [u8; 32] = [
     255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
     255, 255, 255, 255, 255, 255, 255, 255, 254, 255, 255, 252, 47,
@@ -517,16 +555,28 @@ impl<T: xpallet_gateway_bitcoin::Config>
     }
 }
 
+parameter_types! {
+    pub const StandbyTrusteeBond: Balance = 10;
+    pub const MaxSignatureShareLen: u32 = 1024;
+    pub const MaxArchivedTrusteeSessions: u32 = 2;
+}
+
 impl crate::Config for Test {
     type Event = ();
     type Validator = AlwaysValidator;
     type DetermineMultisigAddress = MultisigAddr;
-    type CouncilOrigin = EnsureSigned<AccountId>;
+    type CouncilOrigin = frame_system::EnsureRoot<AccountId>;
+    type ParameterOrigin = frame_system::EnsureRoot<AccountId>;
+    type PauseOrigin = frame_system::EnsureRoot<AccountId>;
     type Bitcoin = MockBitcoin<Test>;
     type BitcoinTrustee = MockBitcoin<Test>;
     type BitcoinTrusteeSessionProvider = trustees::bitcoin::BtcTrusteeSessionManager<Test>;
     type BitcoinTotalSupply = MockBitcoin<Test>;
-    type BitcoinWithdrawalProposal = ();
+    type BitcoinWithdrawalProposal = MockProposal;
+    type Ethereum = MockEthereum;
+    type StandbyTrusteeBond = StandbyTrusteeBond;
+    type MaxSignatureShareLen = MaxSignatureShareLen;
+    type MaxArchivedTrusteeSessions = MaxArchivedTrusteeSessions;
     type WeightInfo = ();
 }
 
@@ -557,6 +607,9 @@ pub fn charlie() -> AccountId32 {
 pub fn dave() -> AccountId32 {
     sr25519::Keyring::Dave.to_account_id()
 }
+pub fn eve() -> AccountId32 {
+    sr25519::Keyring::Eve.to_account_id()
+}
 
 pub struct ExtBuilder;
 impl Default for ExtBuilder {
@@ -1,10 +1,21 @@
 use frame_system::RawOrigin;
 
 use crate::{
-    mock::{bob, charlie, dave, ExtBuilder, Test, XAssets, XGatewayCommon, XGatewayRecords},
-    Pallet, TrusteeSessionInfoLen, TrusteeSessionInfoOf, TrusteeSigRecord,
+    mock::{
+        bob, charlie, dave, eve, Balances, ExtBuilder, System, Test, XAssets, XGatewayCommon,
+        XGatewayRecords, PROPOSAL_HASH,
+    },
+    traits::{TrusteeInfoUpdate, WithdrawalBatchProvider},
+    types::{GatewayOperation, TrusteeSessionAt, WithdrawalBatchPolicy},
+    AggPubkeyInfo, Error, Pallet, TrusteeIntentionPropertiesOf, TrusteeSessionInfoLen,
+    TrusteeSessionInfoOf, TrusteeSigRecord,
 };
-use frame_support::assert_ok;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, Get, Hooks},
+};
+use sp_core::H256;
+use sp_runtime::{AccountId32, DispatchError, Percent};
 use xp_assets_registrar::Chain;
 use xp_protocol::X_BTC;
 
@@ -83,3 +94,591 @@ fn test_claim_not_native_asset_reward() {
         assert_eq!(XAssets::usable_balance(&charlie(), &X_BTC), 1);
     });
 }
+
+#[test]
+fn test_trustee_session_details() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(XGatewayCommon::do_trustee_election(Chain::Bitcoin), Ok(()));
+
+        let info = XGatewayCommon::trustee_session_info_of(Chain::Bitcoin, 1).unwrap();
+        let details = XGatewayCommon::trustee_session_details(Chain::Bitcoin, -1).unwrap();
+        assert_eq!(details.0.session_number, 1);
+        assert_eq!(details.0.threshold, info.0.threshold);
+        assert_eq!(details.0.hot_address, info.0.hot_address);
+        assert_eq!(details.0.cold_address, info.0.cold_address);
+        assert_eq!(details.0.members.len(), info.0.trustee_list.len());
+        for (member, (account, _)) in details.0.members.iter().zip(info.0.trustee_list.iter()) {
+            let props =
+                XGatewayCommon::trustee_intention_props_of(account, Chain::Bitcoin).unwrap();
+            assert_eq!(&member.account, account);
+            assert_eq!(member.hot_entity, props.0.hot_entity);
+            assert_eq!(member.cold_entity, props.0.cold_entity);
+        }
+
+        assert_eq!(
+            XGatewayCommon::trustee_session_details(Chain::Bitcoin, 1),
+            Some(details.clone())
+        );
+
+        // the entities registered when the session was generated are kept after the members
+        // rotate their keys or leave
+        TrusteeIntentionPropertiesOf::<Test>::mutate(bob(), Chain::Bitcoin, |props| {
+            if let Some(props) = props {
+                props.0.hot_entity = vec![1; 33];
+                props.0.cold_entity = vec![2; 33];
+            }
+        });
+        TrusteeIntentionPropertiesOf::<Test>::remove(charlie(), Chain::Bitcoin);
+        assert_eq!(
+            XGatewayCommon::trustee_session_details(Chain::Bitcoin, 1),
+            Some(details)
+        );
+        assert!(XGatewayCommon::trustee_session_details(Chain::Bitcoin, -2).is_none());
+        assert!(XGatewayCommon::trustee_session_details(Chain::Bitcoin, -3).is_none());
+    });
+}
+
+#[test]
+fn test_trustee_session_at() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(10);
+        assert_eq!(XGatewayCommon::do_trustee_election(Chain::Bitcoin), Ok(()));
+        let session_1 = XGatewayCommon::trustee_session_details(Chain::Bitcoin, 1).unwrap();
+        assert_eq!(
+            XGatewayCommon::trustee_session_archive_of(Chain::Bitcoin, 1),
+            Some(session_1.clone())
+        );
+        assert_eq!(
+            XGatewayCommon::trustee_session_at(Chain::Bitcoin, TrusteeSessionAt::Session(1)),
+            Some(session_1.clone())
+        );
+        assert_eq!(
+            XGatewayCommon::trustee_session_at(Chain::Bitcoin, TrusteeSessionAt::Block(10)),
+            Some(session_1.clone())
+        );
+        assert!(
+            XGatewayCommon::trustee_session_at(Chain::Bitcoin, TrusteeSessionAt::Block(9))
+                .is_none()
+        );
+
+        // the previous session controls the funds until the transition completes
+        System::set_block_number(20);
+        assert_ok!(XGatewayCommon::move_trust_into_black_room(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            Some(vec![bob()]),
+        ));
+        let session_2 = XGatewayCommon::trustee_session_archive_of(Chain::Bitcoin, 2).unwrap();
+        assert_eq!(session_2.0.start_height, Some(20));
+        assert_eq!(
+            XGatewayCommon::trustee_session_at(Chain::Bitcoin, TrusteeSessionAt::Block(25)),
+            Some(session_1)
+        );
+
+        System::set_block_number(30);
+        XGatewayCommon::update_transition_status(Chain::Bitcoin, false, None);
+        let session_1 = XGatewayCommon::trustee_session_archive_of(Chain::Bitcoin, 1).unwrap();
+        assert_eq!(session_1.0.end_height, Some(30));
+        assert_eq!(
+            XGatewayCommon::trustee_session_at(Chain::Bitcoin, TrusteeSessionAt::Block(29)),
+            Some(session_1)
+        );
+        assert_eq!(
+            XGatewayCommon::trustee_session_at(Chain::Bitcoin, TrusteeSessionAt::Block(30)),
+            Some(session_2)
+        );
+    });
+}
+
+#[test]
+fn test_maintenance_window() {
+    ExtBuilder::default().build().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(10);
+
+        assert_noop!(
+            XGatewayCommon::schedule_maintenance(
+                RawOrigin::Root.into(),
+                Chain::Bitcoin,
+                9,
+                5,
+                vec![GatewayOperation::Withdrawal]
+            ),
+            Error::<Test>::InvalidMaintenanceWindow
+        );
+        assert_noop!(
+            XGatewayCommon::schedule_maintenance(
+                RawOrigin::Root.into(),
+                Chain::Bitcoin,
+                20,
+                0,
+                vec![GatewayOperation::Withdrawal]
+            ),
+            Error::<Test>::InvalidMaintenanceWindow
+        );
+        assert_ok!(XGatewayCommon::schedule_maintenance(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            20,
+            5,
+            vec![GatewayOperation::Withdrawal]
+        ));
+
+        let status = XGatewayCommon::maintenance_status(Chain::Bitcoin).unwrap();
+        assert!(!status.active);
+        assert_eq!(status.blocks_until_start, 10);
+        assert_eq!(status.blocks_until_end, 15);
+        assert_ok!(XGatewayCommon::ensure_not_in_maintenance(
+            Chain::Bitcoin,
+            GatewayOperation::Withdrawal
+        ));
+
+        frame_system::Pallet::<Test>::set_block_number(22);
+        let status = XGatewayCommon::maintenance_status(Chain::Bitcoin).unwrap();
+        assert!(status.active);
+        assert_eq!(status.blocks_until_start, 0);
+        assert_eq!(status.blocks_until_end, 3);
+        assert_noop!(
+            XGatewayCommon::ensure_not_in_maintenance(Chain::Bitcoin, GatewayOperation::Withdrawal),
+            Error::<Test>::InMaintenance
+        );
+        assert_ok!(XGatewayCommon::ensure_not_in_maintenance(
+            Chain::Bitcoin,
+            GatewayOperation::Deposit
+        ));
+
+        frame_system::Pallet::<Test>::set_block_number(25);
+        assert!(XGatewayCommon::maintenance_status(Chain::Bitcoin).is_none());
+        assert_ok!(XGatewayCommon::ensure_not_in_maintenance(
+            Chain::Bitcoin,
+            GatewayOperation::Withdrawal
+        ));
+
+        assert_ok!(XGatewayCommon::cancel_maintenance(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin
+        ));
+        assert_noop!(
+            XGatewayCommon::cancel_maintenance(RawOrigin::Root.into(), Chain::Bitcoin),
+            Error::<Test>::NoMaintenanceWindow
+        );
+    });
+}
+
+#[test]
+fn test_promote_standby_trustee() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(XGatewayCommon::do_trustee_election(Chain::Bitcoin), Ok(()));
+
+        Balances::make_free_balance_be(&eve(), 100);
+        assert_ok!(XGatewayCommon::add_standby_trustee(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            eve()
+        ));
+        assert_noop!(
+            XGatewayCommon::add_standby_trustee(RawOrigin::Root.into(), Chain::Bitcoin, eve()),
+            Error::<Test>::AlreadyStandbyTrustee
+        );
+        assert!(XGatewayCommon::is_standby_trustee(Chain::Bitcoin, &eve()));
+        assert_eq!(Balances::reserved_balance(&eve()), 10);
+
+        // The standby trustee is allowed to set its hot and cold keys.
+        assert_ok!(XGatewayCommon::setup_trustee(
+            RawOrigin::Signed(eve()).into(),
+            None,
+            Chain::Bitcoin,
+            b"".to_vec(),
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap(),
+            hex::decode("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")
+                .unwrap(),
+        ));
+
+        // Only alice and dave are left in the trustee pool, so eve is promoted.
+        assert_ok!(XGatewayCommon::move_trust_into_black_room(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            Some(vec![bob(), charlie()]),
+        ));
+
+        let info = XGatewayCommon::trustee_session_info_of(Chain::Bitcoin, 2).unwrap();
+        assert!(info.0.trustee_list.iter().any(|(who, _)| *who == eve()));
+        assert!(!XGatewayCommon::is_standby_trustee(Chain::Bitcoin, &eve()));
+        assert_eq!(Balances::reserved_balance(&eve()), 0);
+        assert_noop!(
+            XGatewayCommon::remove_standby_trustee(RawOrigin::Root.into(), Chain::Bitcoin, eve()),
+            Error::<Test>::NotStandbyTrustee
+        );
+    });
+}
+
+#[test]
+fn test_withdrawal_batch() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(XGatewayCommon::do_trustee_election(Chain::Bitcoin), Ok(()));
+        frame_system::Pallet::<Test>::set_block_number(10);
+
+        let apply_withdrawal = || {
+            let addr = b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec();
+            assert_ok!(XGatewayRecords::withdraw(
+                &eve(),
+                X_BTC,
+                100,
+                addr,
+                b"".to_vec().into()
+            ));
+        };
+        assert_ok!(XGatewayRecords::deposit(&eve(), X_BTC, 1000));
+
+        assert_noop!(
+            XGatewayCommon::set_withdrawal_batch_policy(
+                RawOrigin::Root.into(),
+                Chain::Bitcoin,
+                Some(WithdrawalBatchPolicy {
+                    max_withdrawal_count: 0,
+                    window: 5,
+                })
+            ),
+            Error::<Test>::InvalidWithdrawalBatchPolicy
+        );
+        assert_noop!(
+            XGatewayCommon::flush_withdrawal_batch(RawOrigin::Root.into(), Chain::Bitcoin),
+            Error::<Test>::NoWithdrawalBatchPolicy
+        );
+        assert_ok!(XGatewayCommon::set_withdrawal_batch_policy(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            Some(WithdrawalBatchPolicy {
+                max_withdrawal_count: 3,
+                window: 5,
+            })
+        ));
+
+        // The batch is sealed once it's full, the pending withdrawals are not read before.
+        apply_withdrawal();
+        apply_withdrawal();
+        let applying = XGatewayRecords::applying_withdrawals_of(Chain::Bitcoin);
+        assert_eq!(
+            (applying.output_count, applying.balance, applying.since),
+            (2, 200, Some(10))
+        );
+        assert_eq!(
+            XGatewayCommon::try_seal_withdrawal_batch(Chain::Bitcoin, 11, false),
+            <Test as frame_system::Config>::DbWeight::get().reads(3)
+        );
+        assert!(XGatewayCommon::sealed_withdrawal_ids(Chain::Bitcoin).is_none());
+        apply_withdrawal();
+        apply_withdrawal();
+        XGatewayCommon::on_initialize(11);
+        assert_eq!(
+            XGatewayCommon::sealed_withdrawal_ids(Chain::Bitcoin),
+            Some(vec![0, 1, 2])
+        );
+        let status = XGatewayCommon::withdrawal_batch_status(Chain::Bitcoin).unwrap();
+        assert_eq!(status.pending, vec![3]);
+        assert_eq!(status.seal_at, Some(15));
+
+        // The proposal must contain exactly the sealed batch.
+        assert_noop!(
+            XGatewayCommon::ensure_sealed_batch(Chain::Bitcoin, &[0, 1]),
+            Error::<Test>::WithdrawalBatchMismatch
+        );
+        assert_ok!(XGatewayCommon::ensure_sealed_batch(
+            Chain::Bitcoin,
+            &[2, 1, 0]
+        ));
+        assert_ok!(XGatewayRecords::process_withdrawals(
+            &[0, 1, 2],
+            Chain::Bitcoin
+        ));
+        XGatewayCommon::on_batch_proposed(Chain::Bitcoin);
+        assert_eq!(
+            XGatewayRecords::applying_withdrawals_of(Chain::Bitcoin).output_count,
+            1
+        );
+
+        // The rest is sealed once the window elapses.
+        XGatewayCommon::on_initialize(14);
+        assert!(XGatewayCommon::sealed_withdrawal_ids(Chain::Bitcoin).is_none());
+        XGatewayCommon::on_initialize(15);
+        assert_eq!(
+            XGatewayCommon::sealed_withdrawal_ids(Chain::Bitcoin),
+            Some(vec![3])
+        );
+        assert_noop!(
+            XGatewayCommon::flush_withdrawal_batch(RawOrigin::Root.into(), Chain::Bitcoin),
+            Error::<Test>::WithdrawalBatchAlreadySealed
+        );
+        assert_ok!(XGatewayRecords::process_withdrawals(&[3], Chain::Bitcoin));
+        XGatewayCommon::on_batch_proposed(Chain::Bitcoin);
+        assert_eq!(
+            XGatewayRecords::applying_withdrawals_of(Chain::Bitcoin),
+            Default::default()
+        );
+
+        // Trustees are able to seal the batch right away.
+        apply_withdrawal();
+        assert_noop!(
+            XGatewayCommon::flush_withdrawal_batch(RawOrigin::Signed(eve()).into(), Chain::Bitcoin),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(XGatewayCommon::flush_withdrawal_batch(
+            RawOrigin::Signed(bob()).into(),
+            Chain::Bitcoin
+        ));
+        assert_eq!(
+            XGatewayCommon::sealed_withdrawal_ids(Chain::Bitcoin),
+            Some(vec![4])
+        );
+
+        assert_ok!(XGatewayCommon::set_withdrawal_batch_policy(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            None
+        ));
+        assert!(XGatewayCommon::sealed_withdrawal_batch_of(Chain::Bitcoin).is_none());
+        assert_ok!(XGatewayCommon::ensure_sealed_batch(Chain::Bitcoin, &[0]));
+    });
+}
+
+#[test]
+fn test_operation_pause() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XGatewayCommon::pause(
+                RawOrigin::Signed(bob()).into(),
+                GatewayOperation::Withdrawal
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XGatewayCommon::unpause(RawOrigin::Root.into(), GatewayOperation::Withdrawal),
+            Error::<Test>::OperationNotPaused
+        );
+        assert_ok!(XGatewayCommon::pause(
+            RawOrigin::Root.into(),
+            GatewayOperation::Withdrawal
+        ));
+        assert_noop!(
+            XGatewayCommon::pause(RawOrigin::Root.into(), GatewayOperation::Withdrawal),
+            Error::<Test>::OperationAlreadyPaused
+        );
+        assert_ok!(XGatewayCommon::pause(
+            RawOrigin::Root.into(),
+            GatewayOperation::TrusteeTransition
+        ));
+        assert!(!XGatewayCommon::is_paused(GatewayOperation::Deposit));
+
+        assert_ok!(XGatewayRecords::deposit(&eve(), X_BTC, 1000));
+        assert_noop!(
+            XGatewayCommon::withdraw(
+                RawOrigin::Signed(eve()).into(),
+                X_BTC,
+                100,
+                b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec(),
+                b"".to_vec().into()
+            ),
+            Error::<Test>::OperationIsPaused
+        );
+        assert_noop!(
+            XGatewayCommon::do_trustee_election(Chain::Bitcoin),
+            Error::<Test>::OperationIsPaused
+        );
+
+        assert_ok!(XGatewayCommon::unpause(
+            RawOrigin::Root.into(),
+            GatewayOperation::TrusteeTransition
+        ));
+        assert!(XGatewayCommon::is_paused(GatewayOperation::Withdrawal));
+        assert_eq!(XGatewayCommon::do_trustee_election(Chain::Bitcoin), Ok(()));
+    });
+}
+
+#[test]
+fn test_signature_round() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(XGatewayCommon::do_trustee_election(Chain::Bitcoin), Ok(()));
+        let info = XGatewayCommon::trustee_session_info_of(Chain::Bitcoin, 1).unwrap();
+        let trustees = info
+            .0
+            .trustee_list
+            .iter()
+            .map(|(trustee, _)| trustee.clone())
+            .collect::<Vec<_>>();
+        let threshold = usize::from(info.0.threshold);
+
+        let proposal = H256::repeat_byte(1);
+        let submit = |who: &AccountId32, proposal: H256| {
+            XGatewayCommon::submit_signature_share(
+                RawOrigin::Signed(who.clone()).into(),
+                Chain::Bitcoin,
+                proposal,
+                b"share".to_vec(),
+            )
+        };
+
+        assert_noop!(
+            submit(&trustees[0], proposal),
+            Error::<Test>::WithdrawalProposalMismatch
+        );
+        PROPOSAL_HASH.with(|hash| *hash.borrow_mut() = Some(proposal));
+        assert_noop!(
+            XGatewayCommon::submit_signature_share(
+                RawOrigin::Signed(trustees[0].clone()).into(),
+                Chain::Ethereum,
+                proposal,
+                b"share".to_vec(),
+            ),
+            Error::<Test>::NotSupportedChain
+        );
+        assert_noop!(
+            XGatewayCommon::submit_signature_share(
+                RawOrigin::Signed(trustees[0].clone()).into(),
+                Chain::Bitcoin,
+                proposal,
+                vec![0; 1025],
+            ),
+            Error::<Test>::SignatureShareTooLong
+        );
+        assert_noop!(
+            submit(&AccountId32::new([9; 32]), proposal),
+            Error::<Test>::NotRegistered
+        );
+
+        // The round is finalized once the shares reach the threshold.
+        for who in &trustees[..threshold - 1] {
+            assert_ok!(submit(who, proposal));
+        }
+        assert_noop!(
+            submit(&trustees[0], proposal),
+            Error::<Test>::DuplicateSignatureShare
+        );
+        assert!(
+            !XGatewayCommon::signature_round_of(Chain::Bitcoin)
+                .unwrap()
+                .finalized
+        );
+        assert_ok!(submit(&trustees[threshold - 1], proposal));
+        let round = XGatewayCommon::signature_round_of(Chain::Bitcoin).unwrap();
+        assert!(round.finalized);
+        assert_eq!(round.shares.len(), threshold);
+        if let Some(who) = trustees.get(threshold) {
+            assert_noop!(
+                submit(who, proposal),
+                Error::<Test>::SignatureRoundAlreadyFinalized
+            );
+        }
+
+        // The next proposal starts a new round.
+        let next_proposal = H256::repeat_byte(2);
+        PROPOSAL_HASH.with(|hash| *hash.borrow_mut() = Some(next_proposal));
+        assert_ok!(submit(&trustees[0], next_proposal));
+        let round = XGatewayCommon::signature_round_of(Chain::Bitcoin).unwrap();
+        assert_eq!(round.proposal_hash, next_proposal);
+        assert_eq!(round.shares, vec![(trustees[0].clone(), b"share".to_vec())]);
+        assert!(!round.finalized);
+    });
+}
+
+#[test]
+fn test_claim_trustee_rewards() {
+    ExtBuilder::default().build().execute_with(|| {
+        let script_a = b"script_a".to_vec();
+        let script_b = b"script_b".to_vec();
+        AggPubkeyInfo::<Test>::insert(Chain::Bitcoin, &script_a, vec![bob(), charlie()]);
+        AggPubkeyInfo::<Test>::insert(Chain::Bitcoin, &script_b, vec![bob()]);
+
+        let addr = b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec();
+        assert_ok!(XGatewayRecords::deposit(&eve(), X_BTC, 30000));
+        for _ in 0..3 {
+            assert_ok!(XGatewayRecords::withdraw(
+                &eve(),
+                X_BTC,
+                10000,
+                addr.clone(),
+                b"".to_vec().into()
+            ));
+        }
+        assert_ok!(XGatewayRecords::process_withdrawals(
+            &[0, 1, 2],
+            Chain::Bitcoin
+        ));
+        let total_issuance = XAssets::total_issuance(&X_BTC);
+
+        // Nothing is put into the pot without a fee share.
+        XGatewayCommon::deposit_trustee_fee(Chain::Bitcoin, &script_a, &[(0, 10000)]);
+        assert_eq!(
+            XGatewayCommon::trustee_fee_pot_of(Chain::Bitcoin, 0).total_fee,
+            0
+        );
+
+        assert_ok!(XGatewayCommon::set_trustee_fee_share(
+            RawOrigin::Root.into(),
+            Chain::Bitcoin,
+            Percent::from_percent(10),
+        ));
+        XGatewayCommon::deposit_trustee_fee(Chain::Bitcoin, &script_a, &[(0, 10000)]);
+        XGatewayCommon::deposit_trustee_fee(Chain::Bitcoin, &script_b, &[(1, 10000)]);
+        // The slice of the fee can't exceed the withdrawal.
+        XGatewayCommon::deposit_trustee_fee(Chain::Bitcoin, &script_b, &[(2, 200000)]);
+
+        // The slice of the fee is held back from the withdrawn X-BTC instead of being issued.
+        let pot_account = XGatewayCommon::trustee_fee_pot_account(Chain::Bitcoin);
+        assert_eq!(XAssets::usable_balance(&pot_account, &X_BTC), 2000);
+        assert_eq!(XAssets::total_issuance(&X_BTC), total_issuance);
+        for id in 0..3 {
+            assert_ok!(XGatewayRecords::finish_withdrawal(id, None));
+        }
+        // Only the paid out X-BTC is destroyed.
+        assert_eq!(XAssets::total_issuance(&X_BTC), total_issuance - 28000);
+
+        let pot = XGatewayCommon::trustee_fee_pot_of(Chain::Bitcoin, 0);
+        assert_eq!(pot.total_fee, 2000);
+        assert_eq!(pot.total_signatures, 3);
+        assert_eq!(
+            XGatewayCommon::trustee_fee_signatures((Chain::Bitcoin, 0), bob()),
+            2
+        );
+
+        assert_noop!(
+            XGatewayCommon::claim_trustee_rewards(
+                RawOrigin::Signed(bob()).into(),
+                Chain::Bitcoin,
+                0
+            ),
+            Error::<Test>::TrusteeSessionNotFinished
+        );
+        TrusteeSessionInfoLen::<Test>::insert(Chain::Bitcoin, 1);
+
+        assert_ok!(XGatewayCommon::claim_trustee_rewards(
+            RawOrigin::Signed(bob()).into(),
+            Chain::Bitcoin,
+            0
+        ));
+        assert_ok!(XGatewayCommon::claim_trustee_rewards(
+            RawOrigin::Signed(charlie()).into(),
+            Chain::Bitcoin,
+            0
+        ));
+        assert_eq!(XAssets::usable_balance(&bob(), &X_BTC), 1333);
+        assert_eq!(XAssets::usable_balance(&charlie(), &X_BTC), 666);
+        assert_eq!(XAssets::usable_balance(&pot_account, &X_BTC), 1);
+
+        assert_noop!(
+            XGatewayCommon::claim_trustee_rewards(
+                RawOrigin::Signed(bob()).into(),
+                Chain::Bitcoin,
+                0
+            ),
+            Error::<Test>::NoTrusteeFeeReward
+        );
+        assert_noop!(
+            XGatewayCommon::claim_trustee_rewards(
+                RawOrigin::Signed(dave()).into(),
+                Chain::Bitcoin,
+                0
+            ),
+            Error::<Test>::NoTrusteeFeeReward
+        );
+    });
+}
@@ -1,14 +1,18 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::dispatch::DispatchError;
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_core::H256;
 use sp_std::{convert::TryFrom, prelude::Vec};
 
 use chainx_primitives::{AssetId, ReferralId};
 use xpallet_assets::Chain;
 
-use crate::types::{ScriptInfo, TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo};
+use crate::types::{
+    GatewayOperation, ScriptInfo, TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo,
+};
 use xp_gateway_bitcoin::{BtcDepositInfo, OpReturnAccount};
 use xp_gateway_common::DstChain;
+use xpallet_gateway_records::WithdrawalRecordId;
 
 pub trait BytesLike: Into<Vec<u8>> + TryFrom<Vec<u8>> {}
 impl<T: Into<Vec<u8>> + TryFrom<Vec<u8>>> BytesLike for T {}
@@ -21,6 +25,12 @@ pub trait ProposalProvider {
     type WithdrawalProposal;
 
     fn get_withdrawal_proposal() -> Option<Self::WithdrawalProposal>;
+
+    /// The hash of the current withdrawal proposal, i.e., the txid of its transaction.
+    fn withdrawal_proposal_hash() -> Option<H256>;
+
+    /// The maximum number of withdrawals in a proposal.
+    fn max_withdrawal_count() -> u32;
 }
 
 impl ProposalProvider for () {
@@ -29,6 +39,78 @@ impl ProposalProvider for () {
     fn get_withdrawal_proposal() -> Option<Self::WithdrawalProposal> {
         None
     }
+
+    fn withdrawal_proposal_hash() -> Option<H256> {
+        None
+    }
+
+    fn max_withdrawal_count() -> u32 {
+        u32::MAX
+    }
+}
+
+/// The gateway of a Bitcoin-like chain, the relayed chain is identified by `ChainT::chain`.
+///
+/// The inputs are the raw data of the chain, so that the implementation decides how to
+/// deserialize and verify them.
+///
+/// Only Bitcoin implements it for now, the header verification of `light-bitcoin` is specific
+/// to the SHA256d proof of work, while Litecoin and Dogecoin use scrypt (with AuxPoW for the
+/// merged-mined Dogecoin blocks).
+pub trait ChainGateway<AccountId> {
+    /// Verify the raw block `header` and append it to the relayed headers.
+    fn relay_header(header: &[u8]) -> DispatchResult;
+
+    /// Verify that the raw transaction `tx` is included in a relayed block by the inclusion
+    /// `proof`, then process it as a deposit or a withdrawal of the chain.
+    fn relay_transaction(tx: &[u8], proof: &[u8], prev_tx: Option<&[u8]>) -> DispatchResult;
+
+    /// Check the raw withdrawal transaction `tx` of `withdrawal_id_list` constructed by the
+    /// trustee `who` and create the withdrawal proposal for it.
+    fn propose_withdrawal(
+        who: AccountId,
+        withdrawal_id_list: Vec<u32>,
+        tx: &[u8],
+    ) -> DispatchResult;
+}
+
+pub trait WithdrawalBatchProvider {
+    /// Ensure the withdrawals of a new proposal are exactly the sealed batch of `chain`
+    /// when the withdrawal batching is enabled for `chain`.
+    fn ensure_sealed_batch(chain: Chain, withdrawal_id_list: &[u32]) -> DispatchResult;
+
+    /// Clear the sealed batch of `chain` once the proposal for it was created.
+    fn on_batch_proposed(chain: Chain);
+}
+
+impl WithdrawalBatchProvider for () {
+    fn ensure_sealed_batch(_: Chain, _: &[u32]) -> DispatchResult {
+        Ok(())
+    }
+
+    fn on_batch_proposed(_: Chain) {}
+}
+
+pub trait OperationPauseProvider {
+    /// Ensure `operation` of the gateway is not paused by the emergency switch.
+    fn ensure_not_paused(operation: GatewayOperation) -> DispatchResult;
+}
+
+impl OperationPauseProvider for () {
+    fn ensure_not_paused(_: GatewayOperation) -> DispatchResult {
+        Ok(())
+    }
+}
+
+pub trait TrusteeInfoConfigProvider {
+    /// The limits of the trustee count of `chain`.
+    fn trustee_info_config(chain: Chain) -> TrusteeInfoConfig;
+}
+
+impl TrusteeInfoConfigProvider for () {
+    fn trustee_info_config(_: Chain) -> TrusteeInfoConfig {
+        TrusteeInfoConfig::default()
+    }
 }
 
 pub trait TotalSupply<Balance> {
@@ -111,12 +193,21 @@ pub trait TrusteeInfoUpdate {
     fn update_transition_status(chain: Chain, status: bool, trans_amount: Option<u64>);
     /// Each withdrawal is completed to record the weight of the signer
     fn update_trustee_sig_record(chain: Chain, script: &[u8], withdraw_amout: u64);
+    /// Before the withdrawals are completed, hold back the trustee slice of their withdrawal
+    /// fees from the destroyed balance into the reward pot of the signers
+    fn deposit_trustee_fee(
+        chain: Chain,
+        script: &[u8],
+        withdrawal_fees: &[(WithdrawalRecordId, u64)],
+    );
 }
 
 impl TrusteeInfoUpdate for () {
     fn update_transition_status(_: Chain, _: bool, _: Option<u64>) {}
 
     fn update_trustee_sig_record(_: Chain, _: &[u8], _: u64) {}
+
+    fn deposit_trustee_fee(_: Chain, _: &[u8], _: &[(WithdrawalRecordId, u64)]) {}
 }
 
 pub trait ReferralBinding<AccountId> {
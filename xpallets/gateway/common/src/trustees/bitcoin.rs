@@ -10,7 +10,7 @@ use sp_std::{convert::TryFrom, fmt, prelude::Vec};
 use super::TrusteeSessionManager;
 use crate::{
     traits::ChainProvider,
-    types::{TrusteeIntentionProps, TrusteeSessionInfo},
+    types::{TrusteeIntentionProps, TrusteeSessionDetails, TrusteeSessionInfo},
 };
 use xp_assets_registrar::Chain;
 
@@ -18,6 +18,8 @@ pub type BtcAddress = Vec<u8>;
 pub type BtcTrusteeSessionInfo<AccountId, BlockNumber> =
     TrusteeSessionInfo<AccountId, BlockNumber, BtcTrusteeAddrInfo>;
 pub type BtcTrusteeIntentionProps<AccountId> = TrusteeIntentionProps<AccountId, BtcTrusteeType>;
+pub type BtcTrusteeSessionDetails<AccountId, BlockNumber> =
+    TrusteeSessionDetails<AccountId, BlockNumber, BtcTrusteeType, BtcTrusteeAddrInfo>;
 pub type BtcTrusteeSessionManager<T> = TrusteeSessionManager<T, BtcTrusteeAddrInfo>;
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode)]
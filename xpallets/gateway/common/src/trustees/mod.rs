@@ -6,14 +6,21 @@ use frame_support::{
     dispatch::DispatchError,
     log::{error, warn},
 };
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
 use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
 
 use xp_assets_registrar::Chain;
+use xpallet_assets::BalanceOf;
+use xpallet_gateway_records::WithdrawalRecordId;
 
 use crate::{
     traits::{BytesLike, ChainProvider, TrusteeInfoUpdate, TrusteeSession},
     types::TrusteeSessionInfo,
-    Config, Error, Pallet, TrusteeSessionInfoOf, TrusteeSigRecord, TrusteeTransitionStatus,
+    Config, Error, Event, Pallet, TrusteeFeePotOf, TrusteeFeeSignatures, TrusteeSessionArchiveOf,
+    TrusteeSessionInfoOf, TrusteeSigRecord, TrusteeTransitionStatus,
 };
 
 pub struct TrusteeSessionManager<T: Config, TrusteeAddress>(
@@ -119,6 +126,11 @@ impl<T: Config> TrusteeInfoUpdate for Pallet<T> {
 
                     let end_height = frame_system::Pallet::<T>::block_number();
                     trustee.0.end_height = Some(end_height);
+                    TrusteeSessionArchiveOf::<T>::mutate(chain, last_session_num, |details| {
+                        if let Some(details) = details {
+                            details.0.end_height = Some(end_height);
+                        }
+                    });
                 }
             });
             TrusteeSigRecord::<T>::remove_prefix(chain, None);
@@ -145,4 +157,52 @@ impl<T: Config> TrusteeInfoUpdate for Pallet<T> {
             }
         });
     }
+
+    fn deposit_trustee_fee(
+        chain: Chain,
+        script: &[u8],
+        withdrawal_fees: &[(WithdrawalRecordId, u64)],
+    ) {
+        let share = Self::trustee_fee_share_of(chain);
+        let signed_trustees = Self::agg_pubkey_info(chain, script);
+        if share.is_zero() || signed_trustees.is_empty() {
+            return;
+        }
+
+        // The withdrawal fee pays the miner fee and the rest stays in the trustee wallet, so the
+        // slice of the fee is held back from the X-BTC destroyed by the withdrawal.
+        let pot = Self::trustee_fee_pot_account(chain);
+        let mut fee = BalanceOf::<T>::zero();
+        for (id, withdrawal_fee) in withdrawal_fees {
+            let slice: BalanceOf<T> = (share * *withdrawal_fee).saturated_into();
+            if slice.is_zero() {
+                continue;
+            }
+            match xpallet_gateway_records::Pallet::<T>::hold_back_withdrawal(*id, &pot, slice) {
+                Ok(()) => fee = fee.saturating_add(slice),
+                Err(err) => error!(
+                    target: "runtime::gateway::common",
+                    "[deposit_trustee_fee] Hold back the trustee fee:{:?} of withdrawal:{} error:{:?}",
+                    slice, id, err
+                ),
+            }
+        }
+        if fee.is_zero() {
+            return;
+        }
+
+        let session_num = Self::trustee_session_info_len(chain);
+        TrusteeFeePotOf::<T>::mutate(chain, session_num, |pot| {
+            pot.total_fee = pot.total_fee.saturating_add(fee);
+            pot.total_signatures = pot
+                .total_signatures
+                .saturating_add(signed_trustees.len() as u32);
+        });
+        for trustee in signed_trustees {
+            TrusteeFeeSignatures::<T>::mutate((chain, session_num), trustee, |signatures| {
+                *signatures = signatures.saturating_add(1)
+            });
+        }
+        Self::deposit_event(Event::<T>::TrusteeFeeDeposited(chain, session_num, fee));
+    }
 }
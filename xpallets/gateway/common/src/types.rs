@@ -5,10 +5,12 @@ use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use sp_runtime::RuntimeDebug;
+use sp_core::H256;
+use sp_runtime::{traits::AtLeast32BitUnsigned, RuntimeDebug};
 use sp_std::{convert::TryFrom, prelude::Vec};
 
 use chainx_primitives::Text;
+use xpallet_gateway_records::WithdrawalRecordId;
 
 use crate::traits::BytesLike;
 
@@ -156,3 +158,246 @@ impl<AccountId, TrusteeEntity: BytesLike> TryFrom<GenericTrusteeIntentionProps<A
         })
     }
 }
+
+/// The registered hot/cold entities of a trustee session member.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TrusteeMemberInfo<AccountId, TrusteeEntity: BytesLike> {
+    pub account: AccountId,
+    pub hot_entity: TrusteeEntity,
+    pub cold_entity: TrusteeEntity,
+}
+
+/// The trustee session details.
+///
+/// Contains everything needed to re-derive the hot/cold multisig addresses of a session.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TrusteeSessionDetails<
+    AccountId,
+    BlockNumber,
+    TrusteeEntity: BytesLike,
+    TrusteeAddress: BytesLike,
+> {
+    /// Session number
+    pub session_number: u32,
+    /// Trustee members with their public keys
+    pub members: Vec<TrusteeMemberInfo<AccountId, TrusteeEntity>>,
+    /// Threshold value
+    pub threshold: u16,
+    /// Hot address
+    pub hot_address: TrusteeAddress,
+    /// Cold address
+    pub cold_address: TrusteeAddress,
+    /// Trustee multi account to receive congressional multi-signature rewards
+    pub multi_account: Option<AccountId>,
+    /// The height of trustee start
+    pub start_height: Option<BlockNumber>,
+    /// The height of trustee end
+    pub end_height: Option<BlockNumber>,
+}
+
+/// The generic trustee session details.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct GenericTrusteeSessionDetails<AccountId, BlockNumber>(
+    pub TrusteeSessionDetails<AccountId, BlockNumber, Vec<u8>, Vec<u8>>,
+);
+
+impl<AccountId, BlockNumber, TrusteeEntity: BytesLike, TrusteeAddress: BytesLike>
+    TryFrom<GenericTrusteeSessionDetails<AccountId, BlockNumber>>
+    for TrusteeSessionDetails<AccountId, BlockNumber, TrusteeEntity, TrusteeAddress>
+{
+    // TODO, may use a better error
+    type Error = ();
+
+    fn try_from(
+        details: GenericTrusteeSessionDetails<AccountId, BlockNumber>,
+    ) -> Result<Self, Self::Error> {
+        let mut members = Vec::with_capacity(details.0.members.len());
+        for member in details.0.members {
+            members.push(TrusteeMemberInfo {
+                account: member.account,
+                hot_entity: TrusteeEntity::try_from(member.hot_entity).map_err(|_| ())?,
+                cold_entity: TrusteeEntity::try_from(member.cold_entity).map_err(|_| ())?,
+            });
+        }
+        Ok(TrusteeSessionDetails {
+            session_number: details.0.session_number,
+            members,
+            threshold: details.0.threshold,
+            hot_address: TrusteeAddress::try_from(details.0.hot_address).map_err(|_| ())?,
+            cold_address: TrusteeAddress::try_from(details.0.cold_address).map_err(|_| ())?,
+            multi_account: details.0.multi_account,
+            start_height: details.0.start_height,
+            end_height: details.0.end_height,
+        })
+    }
+}
+
+/// The trustee session to look up, by its number or by a block within it.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum TrusteeSessionAt<BlockNumber> {
+    Session(u32),
+    Block(BlockNumber),
+}
+
+/// The gateway operation affected by a maintenance window or an emergency pause.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum GatewayOperation {
+    /// The deposits may be delayed, they are still credited once the window ends.
+    Deposit,
+    /// The withdrawal applications are rejected.
+    Withdrawal,
+    /// The trustee election can not be started, only used by the emergency pause.
+    TrusteeTransition,
+}
+
+impl GatewayOperation {
+    /// The bit of the operation in the `OperationPause` flags.
+    pub fn flag(self) -> u8 {
+        match self {
+            GatewayOperation::Deposit => 1,
+            GatewayOperation::Withdrawal => 1 << 1,
+            GatewayOperation::TrusteeTransition => 1 << 2,
+        }
+    }
+}
+
+/// A scheduled maintenance window of the gateway.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MaintenanceWindow<BlockNumber> {
+    /// The block number when the maintenance starts
+    pub start: BlockNumber,
+    /// The number of blocks the maintenance lasts
+    pub duration: BlockNumber,
+    /// The affected operations
+    pub operations: Vec<GatewayOperation>,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> MaintenanceWindow<BlockNumber> {
+    /// The block number when the maintenance ends (exclusive).
+    pub fn end(&self) -> BlockNumber {
+        self.start.saturating_add(self.duration)
+    }
+
+    pub fn is_active(&self, now: BlockNumber) -> bool {
+        self.start <= now && now < self.end()
+    }
+
+    pub fn is_expired(&self, now: BlockNumber) -> bool {
+        now >= self.end()
+    }
+
+    pub fn affects(&self, operation: GatewayOperation) -> bool {
+        self.operations.contains(&operation)
+    }
+
+    /// Returns the countdown of the window at block `now`.
+    pub fn status(self, now: BlockNumber) -> MaintenanceStatus<BlockNumber> {
+        MaintenanceStatus {
+            active: self.is_active(now),
+            blocks_until_start: self.start.saturating_sub(now),
+            blocks_until_end: self.end().saturating_sub(now),
+            window: self,
+        }
+    }
+}
+
+/// The maintenance window along with its countdown at some block.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MaintenanceStatus<BlockNumber> {
+    pub window: MaintenanceWindow<BlockNumber>,
+    /// Whether the maintenance is in progress
+    pub active: bool,
+    /// The number of blocks before the maintenance starts, zero if started
+    pub blocks_until_start: BlockNumber,
+    /// The number of blocks before the maintenance ends
+    pub blocks_until_end: BlockNumber,
+}
+
+/// The policy to batch the applying withdrawals of a chain into a withdrawal proposal.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalBatchPolicy<BlockNumber> {
+    /// The batch is sealed once it has this many withdrawals
+    pub max_withdrawal_count: u32,
+    /// The batch is sealed once its oldest withdrawal has waited for this many blocks
+    pub window: BlockNumber,
+}
+
+/// The withdrawals sealed for the next withdrawal proposal.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SealedWithdrawalBatch<BlockNumber> {
+    /// The block number when the batch was sealed
+    pub sealed_at: BlockNumber,
+    /// The withdrawals the next proposal must contain
+    pub withdrawal_ids: Vec<WithdrawalRecordId>,
+}
+
+/// The withdrawal batch of a chain at some block.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalBatchStatus<BlockNumber> {
+    pub policy: WithdrawalBatchPolicy<BlockNumber>,
+    /// The sealed batch waiting for a withdrawal proposal
+    pub sealed: Option<SealedWithdrawalBatch<BlockNumber>>,
+    /// The applying withdrawals accumulated for the next batch
+    pub pending: Vec<WithdrawalRecordId>,
+    /// The block number when the pending withdrawals are due to be sealed
+    pub seal_at: Option<BlockNumber>,
+}
+
+/// The deposit address of an account on a chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DepositAddress<Balance> {
+    /// The current trustee hot address to deposit to
+    pub address: Vec<u8>,
+    /// The OP_RETURN data binding the deposit to the account, i.e. its SS58Check address
+    pub op_return: Vec<u8>,
+    /// The deposit below this value is not credited
+    pub min_deposit: Balance,
+}
+
+/// The partial signatures of the trustees collected for a withdrawal proposal.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SignatureRound<AccountId> {
+    /// The hash of the withdrawal proposal signed in this round
+    pub proposal_hash: H256,
+    /// The number of shares required to finalize the round
+    pub threshold: u16,
+    /// The partial signatures (or PSBT fragments) published by the trustees
+    pub shares: Vec<(AccountId, Vec<u8>)>,
+    /// Whether the shares reached the threshold
+    pub finalized: bool,
+}
+
+/// The slice of the withdrawal fees put into the trustee reward pot during a trustee session.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TrusteeFeePot<Balance> {
+    /// The total fees put into the pot
+    pub total_fee: Balance,
+    /// The total signatures of the completed withdrawals, the fees are shared out by them
+    pub total_signatures: u32,
+}
@@ -32,6 +32,7 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for xpallet_gateway_common.
 pub trait WeightInfo {
     fn withdraw() -> Weight;
+    fn withdraw_to_many(n: u32) -> Weight;
     fn cancel_withdrawal() -> Weight;
     fn setup_trustee() -> Weight;
     fn set_trustee_proxy() -> Weight;
@@ -42,6 +43,16 @@ pub trait WeightInfo {
     fn force_trustee_election() -> Weight;
     fn force_update_trustee() -> Weight;
     fn force_set_referral_binding() -> Weight;
+    fn add_standby_trustee() -> Weight;
+    fn remove_standby_trustee() -> Weight;
+    fn set_withdrawal_batch_policy() -> Weight;
+    fn flush_withdrawal_batch() -> Weight;
+    fn seal_withdrawal_batch(n: u32) -> Weight;
+    fn pause() -> Weight;
+    fn unpause() -> Weight;
+    fn submit_signature_share(n: u32) -> Weight;
+    fn set_trustee_fee_share() -> Weight;
+    fn claim_trustee_rewards() -> Weight;
 }
 
 /// Weights for xpallet_gateway_common using the Substrate node and recommended hardware.
@@ -52,6 +63,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(9 as Weight))
             .saturating_add(T::DbWeight::get().writes(5 as Weight))
     }
+    fn withdraw_to_many(n: u32) -> Weight {
+        (151_427_000 as Weight)
+            .saturating_add((23_518_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
     fn cancel_withdrawal() -> Weight {
         (98_146_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(6 as Weight))
@@ -94,6 +111,57 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn force_set_referral_binding() -> Weight {
         (19_517_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn add_standby_trustee() -> Weight {
+        (36_420_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn remove_standby_trustee() -> Weight {
+        (31_105_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_withdrawal_batch_policy() -> Weight {
+        (18_214_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn flush_withdrawal_batch() -> Weight {
+        (86_342_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn seal_withdrawal_batch(n: u32) -> Weight {
+        (9_823_000 as Weight)
+            .saturating_add((4_127_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn pause() -> Weight {
+        (12_106_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn unpause() -> Weight {
+        (12_341_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn submit_signature_share(n: u32) -> Weight {
+        (29_604_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_trustee_fee_share() -> Weight {
+        (3_412_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn claim_trustee_rewards() -> Weight {
+        (68_215_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -103,6 +171,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(9 as Weight))
             .saturating_add(RocksDbWeight::get().writes(5 as Weight))
     }
+    fn withdraw_to_many(n: u32) -> Weight {
+        (151_427_000 as Weight)
+            .saturating_add((23_518_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
     fn cancel_withdrawal() -> Weight {
         (98_146_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(6 as Weight))
@@ -145,4 +219,55 @@ impl WeightInfo for () {
     fn force_set_referral_binding() -> Weight {
         (19_517_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn add_standby_trustee() -> Weight {
+        (36_420_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn remove_standby_trustee() -> Weight {
+        (31_105_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_withdrawal_batch_policy() -> Weight {
+        (18_214_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn flush_withdrawal_batch() -> Weight {
+        (86_342_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn seal_withdrawal_batch(n: u32) -> Weight {
+        (9_823_000 as Weight)
+            .saturating_add((4_127_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn pause() -> Weight {
+        (12_106_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn unpause() -> Weight {
+        (12_341_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn submit_signature_share(n: u32) -> Weight {
+        (29_604_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_trustee_fee_share() -> Weight {
+        (3_412_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn claim_trustee_rewards() -> Weight {
+        (68_215_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
 }
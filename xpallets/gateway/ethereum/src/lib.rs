@@ -0,0 +1,495 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! # Ethereum Gateway Pallet
+//!
+//! Bridges the ether and ERC-20 tokens of Ethereum to the assets of `Chain::Ethereum`.
+//!
+//! The Ethereum receipts are verified by a threshold committee rather than a header relay, as
+//! the light client of the Ethereum beacon chain needs the BLS12-381 host functions which are
+//! not available in the runtime. The size of the committee is limited by the
+//! `TrusteeInfoConfig` of `Chain::Ethereum` in `xpallet_gateway_common`, and 2/3 of the
+//! committee is required to:
+//!
+//! - attest a deposit log of Ethereum, which mints the asset of the deposited token.
+//! - sign a withdrawal, the signatures are submitted to the multisig contract on Ethereum.
+//! - confirm the Ethereum tx paying out a withdrawal, which destroys the withdrawn asset.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod types;
+pub mod weights;
+
+use codec::Encode;
+use sp_core::{ecdsa, H160, H256};
+use sp_runtime::{traits::Zero, SaturatedConversion};
+use sp_std::prelude::*;
+
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    log::{debug, error},
+};
+use frame_system::{ensure_root, ensure_signed};
+
+use chainx_primitives::AssetId;
+use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_gateway_common::{
+    traits::{OperationPauseProvider, TrusteeInfoConfigProvider},
+    types::GatewayOperation,
+    utils::two_thirds_unsafe,
+};
+use xpallet_gateway_records::{WithdrawalRecordId, WithdrawalState};
+
+use self::types::{EthDepositAttestation, EthDepositId};
+
+pub use self::weights::WeightInfo;
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{pallet_prelude::*, transactional};
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config:
+        frame_system::Config + xpallet_assets::Config + xpallet_gateway_records::Config
+    {
+        /// The overarching event type.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// A majority of the council can excute some transactions.
+        type CouncilOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Get the limits of the committee size.
+        type TrusteeInfoConfig: TrusteeInfoConfigProvider;
+
+        /// Check whether the gateway operations are paused in an emergency.
+        type OperationPause: OperationPauseProvider;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Set the committee members along with their Ethereum addresses.
+        #[pallet::weight(<T as Config>::WeightInfo::set_committee(members.len() as u32))]
+        pub fn set_committee(
+            origin: OriginFor<T>,
+            members: Vec<(T::AccountId, H160)>,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            let config = T::TrusteeInfoConfig::trustee_info_config(Chain::Ethereum);
+            let count = members.len() as u32;
+            ensure!(
+                count >= config.min_trustee_count && count <= config.max_trustee_count,
+                Error::<T>::InvalidCommitteeCount
+            );
+            for (i, (who, address)) in members.iter().enumerate() {
+                ensure!(
+                    !members[..i].iter().any(|(w, a)| w == who || a == address),
+                    Error::<T>::DuplicateCommitteeMember
+                );
+            }
+
+            Committee::<T>::put(members);
+            Self::deposit_event(Event::<T>::CommitteeChanged(count));
+            Ok(())
+        }
+
+        /// Map the Ethereum token contract to the asset of `Chain::Ethereum`, the zero address
+        /// stands for the ether.
+        #[pallet::weight(<T as Config>::WeightInfo::register_token())]
+        pub fn register_token(
+            origin: OriginFor<T>,
+            token: H160,
+            #[pallet::compact] asset_id: AssetId,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            ensure!(
+                xpallet_assets_registrar::Pallet::<T>::chain_of(&asset_id)? == Chain::Ethereum,
+                Error::<T>::NotEthereumAsset
+            );
+            ensure!(
+                !AssetOfToken::<T>::contains_key(token)
+                    && !TokenOfAsset::<T>::contains_key(asset_id),
+                Error::<T>::TokenAlreadyRegistered
+            );
+
+            AssetOfToken::<T>::insert(token, asset_id);
+            TokenOfAsset::<T>::insert(asset_id, token);
+            Self::deposit_event(Event::<T>::TokenRegistered(token, asset_id));
+            Ok(())
+        }
+
+        /// Attest the deposit log `log_index` of the Ethereum tx `tx_hash`, the asset is minted
+        /// to `who` once 2/3 of the committee attested the same deposit.
+        #[pallet::weight(<T as Config>::WeightInfo::attest_deposit())]
+        #[transactional]
+        pub fn attest_deposit(
+            origin: OriginFor<T>,
+            tx_hash: H256,
+            log_index: u32,
+            token: H160,
+            who: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let attester = ensure_signed(origin)?;
+            Self::ensure_committee_member(&attester)?;
+            T::OperationPause::ensure_not_paused(GatewayOperation::Deposit)?;
+
+            let deposit_id = (tx_hash, log_index);
+            ensure!(
+                !ProcessedDeposits::<T>::contains_key(deposit_id),
+                Error::<T>::DepositAlreadyProcessed
+            );
+            let asset_id = Self::asset_of_token(token).ok_or(Error::<T>::UnregisteredToken)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+            // the members may attest different deposits for the same log, each one is
+            // counted on its own.
+            let content = H256(sp_io::hashing::blake2_256(&(token, &who, amount).encode()));
+            let mut attestation =
+                Self::deposit_attestations(deposit_id, content).unwrap_or_else(|| {
+                    EthDepositAttestation {
+                        token,
+                        who: who.clone(),
+                        amount,
+                        attesters: Vec::new(),
+                    }
+                });
+            ensure!(
+                !attestation.attesters.contains(&attester),
+                Error::<T>::DuplicateAttestation
+            );
+            attestation.attesters.push(attester.clone());
+            Self::deposit_event(Event::<T>::DepositAttested(attester, tx_hash, log_index));
+
+            if Self::reach_threshold(&attestation.attesters) {
+                xpallet_gateway_records::Pallet::<T>::deposit(&who, asset_id, amount)?;
+                DepositAttestations::<T>::remove_prefix(deposit_id, None);
+                ProcessedDeposits::<T>::insert(
+                    deposit_id,
+                    frame_system::Pallet::<T>::block_number(),
+                );
+                Self::deposit_event(Event::<T>::DepositMinted(
+                    tx_hash, log_index, who, asset_id, amount,
+                ));
+            } else {
+                DepositAttestations::<T>::insert(deposit_id, content, attestation);
+            }
+            Ok(())
+        }
+
+        /// Sign the applying withdrawal `withdrawal_id` for the multisig contract on Ethereum,
+        /// the withdrawal is processing once 2/3 of the committee signed it.
+        ///
+        /// The signature is of `withdrawal_digest` by the Ethereum address of the member.
+        #[pallet::weight(<T as Config>::WeightInfo::sign_withdrawal())]
+        #[transactional]
+        pub fn sign_withdrawal(
+            origin: OriginFor<T>,
+            #[pallet::compact] withdrawal_id: WithdrawalRecordId,
+            signature: ecdsa::Signature,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let address = Self::ensure_committee_member(&signer)?;
+            T::OperationPause::ensure_not_paused(GatewayOperation::Withdrawal)?;
+
+            ensure!(
+                xpallet_gateway_records::Pallet::<T>::state_of(withdrawal_id)
+                    == Some(WithdrawalState::Applying),
+                Error::<T>::NotApplyingWithdrawal
+            );
+            let digest = Self::withdrawal_digest(withdrawal_id)?;
+            ensure!(
+                recover_eth_address(&signature, &digest) == Some(address),
+                Error::<T>::InvalidSignature
+            );
+
+            let mut signatures = Self::withdrawal_signatures(withdrawal_id);
+            ensure!(
+                !signatures.iter().any(|(who, _)| *who == signer),
+                Error::<T>::DuplicateAttestation
+            );
+            signatures.push((signer.clone(), signature));
+            Self::deposit_event(Event::<T>::WithdrawalSignatureAdded(signer, withdrawal_id));
+
+            let signers = signatures
+                .iter()
+                .map(|(who, _)| who.clone())
+                .collect::<Vec<_>>();
+            if Self::reach_threshold(&signers) {
+                xpallet_gateway_records::Pallet::<T>::process_withdrawal(
+                    withdrawal_id,
+                    Chain::Ethereum,
+                )?;
+                Self::deposit_event(Event::<T>::WithdrawalSigned(withdrawal_id));
+            }
+            WithdrawalSignatures::<T>::insert(withdrawal_id, signatures);
+            Ok(())
+        }
+
+        /// Confirm that the processing withdrawal `withdrawal_id` was paid out by the Ethereum
+        /// tx `tx_hash`, the withdrawal is finished once 2/3 of the committee confirmed it.
+        #[pallet::weight(<T as Config>::WeightInfo::confirm_withdrawal())]
+        #[transactional]
+        pub fn confirm_withdrawal(
+            origin: OriginFor<T>,
+            #[pallet::compact] withdrawal_id: WithdrawalRecordId,
+            tx_hash: H256,
+        ) -> DispatchResult {
+            let confirmer = ensure_signed(origin)?;
+            Self::ensure_committee_member(&confirmer)?;
+
+            ensure!(
+                xpallet_gateway_records::Pallet::<T>::state_of(withdrawal_id)
+                    == Some(WithdrawalState::Processing),
+                Error::<T>::NotProcessingWithdrawal
+            );
+
+            let mut confirmers = Self::withdrawal_confirmations(withdrawal_id, tx_hash);
+            ensure!(
+                !confirmers.contains(&confirmer),
+                Error::<T>::DuplicateAttestation
+            );
+            confirmers.push(confirmer.clone());
+            Self::deposit_event(Event::<T>::WithdrawalConfirmationAdded(
+                confirmer,
+                withdrawal_id,
+            ));
+
+            if Self::reach_threshold(&confirmers) {
+                xpallet_gateway_records::Pallet::<T>::finish_withdrawal(
+                    withdrawal_id,
+                    Some(Chain::Ethereum),
+                )?;
+                WithdrawalSignatures::<T>::remove(withdrawal_id);
+                WithdrawalConfirmations::<T>::remove_prefix(withdrawal_id, None);
+                Self::deposit_event(Event::<T>::WithdrawalFinished(withdrawal_id, tx_hash));
+            } else {
+                WithdrawalConfirmations::<T>::insert(withdrawal_id, tx_hash, confirmers);
+            }
+            Ok(())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The committee was changed. [member_count]
+        CommitteeChanged(u32),
+        /// The Ethereum token was mapped to the asset. [token, asset_id]
+        TokenRegistered(H160, AssetId),
+        /// A member attested the deposit. [who, tx_hash, log_index]
+        DepositAttested(T::AccountId, H256, u32),
+        /// The attested deposit was minted. [tx_hash, log_index, who, asset_id, amount]
+        DepositMinted(H256, u32, T::AccountId, AssetId, BalanceOf<T>),
+        /// A member signed the withdrawal. [who, withdrawal_id]
+        WithdrawalSignatureAdded(T::AccountId, WithdrawalRecordId),
+        /// The withdrawal was signed by the committee and is processing. [withdrawal_id]
+        WithdrawalSigned(WithdrawalRecordId),
+        /// A member confirmed the payout of the withdrawal. [who, withdrawal_id]
+        WithdrawalConfirmationAdded(T::AccountId, WithdrawalRecordId),
+        /// The withdrawal was paid out on Ethereum. [withdrawal_id, tx_hash]
+        WithdrawalFinished(WithdrawalRecordId, H256),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The committee size is out of the trustee info config of Ethereum.
+        InvalidCommitteeCount,
+        /// The account or the Ethereum address occurs more than once in the committee.
+        DuplicateCommitteeMember,
+        /// The caller is not a committee member.
+        NotCommitteeMember,
+        /// The asset does not belong to Ethereum.
+        NotEthereumAsset,
+        /// The token or the asset was already registered.
+        TokenAlreadyRegistered,
+        /// The token is not registered.
+        UnregisteredToken,
+        /// The deposit amount is zero.
+        ZeroAmount,
+        /// The deposit was already minted.
+        DepositAlreadyProcessed,
+        /// The member already attested it.
+        DuplicateAttestation,
+        /// The withdrawal does not exist or is not applying.
+        NotApplyingWithdrawal,
+        /// The withdrawal does not exist or is not processing.
+        NotProcessingWithdrawal,
+        /// The withdrawal pays more than one output.
+        MultipleWithdrawalOutputs,
+        /// The address is not a hex encoded Ethereum address.
+        InvalidAddress,
+        /// The signature is not signed by the Ethereum address of the member.
+        InvalidSignature,
+    }
+
+    /// The committee members along with their Ethereum addresses.
+    #[pallet::storage]
+    #[pallet::getter(fn committee)]
+    pub(crate) type Committee<T: Config> = StorageValue<_, Vec<(T::AccountId, H160)>, ValueQuery>;
+
+    /// The asset of the Ethereum token contract.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_of_token)]
+    pub(crate) type AssetOfToken<T: Config> = StorageMap<_, Twox64Concat, H160, AssetId>;
+
+    /// The Ethereum token contract of the asset.
+    #[pallet::storage]
+    #[pallet::getter(fn token_of_asset)]
+    pub(crate) type TokenOfAsset<T: Config> = StorageMap<_, Twox64Concat, AssetId, H160>;
+
+    /// The attestations of the deposits not minted yet, by the hash of the attested deposit.
+    #[pallet::storage]
+    #[pallet::getter(fn deposit_attestations)]
+    pub(crate) type DepositAttestations<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EthDepositId,
+        Identity,
+        H256,
+        EthDepositAttestation<T::AccountId, BalanceOf<T>>,
+    >;
+
+    /// The block number at which the deposit was minted, to reject the replayed deposits.
+    #[pallet::storage]
+    #[pallet::getter(fn processed_deposits)]
+    pub(crate) type ProcessedDeposits<T: Config> =
+        StorageMap<_, Twox64Concat, EthDepositId, T::BlockNumber>;
+
+    /// The signatures of the committee for the withdrawal.
+    #[pallet::storage]
+    #[pallet::getter(fn withdrawal_signatures)]
+    pub(crate) type WithdrawalSignatures<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        WithdrawalRecordId,
+        Vec<(T::AccountId, ecdsa::Signature)>,
+        ValueQuery,
+    >;
+
+    /// The members confirmed that the withdrawal was paid out by the Ethereum tx.
+    #[pallet::storage]
+    #[pallet::getter(fn withdrawal_confirmations)]
+    pub(crate) type WithdrawalConfirmations<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        WithdrawalRecordId,
+        Identity,
+        H256,
+        Vec<T::AccountId>,
+        ValueQuery,
+    >;
+}
+
+impl<T: Config> ChainT<BalanceOf<T>> for Pallet<T> {
+    const ASSET_ID: AssetId = xp_protocol::X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        parse_eth_address(addr).ok_or(Error::<T>::InvalidAddress)?;
+        Ok(())
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        ensure!(
+            Self::token_of_asset(asset_id).is_some(),
+            xpallet_assets::Error::<T>::ActionNotAllowed
+        );
+        Ok(WithdrawalLimit::default())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the Ethereum address of `who` if it's a committee member.
+    fn ensure_committee_member(who: &T::AccountId) -> Result<H160, DispatchError> {
+        Self::committee()
+            .into_iter()
+            .find(|(member, _)| member == who)
+            .map(|(_, address)| address)
+            .ok_or_else(|| Error::<T>::NotCommitteeMember.into())
+    }
+
+    /// Returns whether the current members in `accounts` are at least 2/3 of the committee.
+    fn reach_threshold(accounts: &[T::AccountId]) -> bool {
+        let committee = Self::committee();
+        let count = accounts
+            .iter()
+            .filter(|who| committee.iter().any(|(member, _)| member == *who))
+            .count() as u32;
+        count >= two_thirds_unsafe(committee.len() as u32)
+    }
+
+    /// Returns the digest of the withdrawal signed by the committee, which is
+    /// `keccak256(abi.encodePacked(uint32 withdrawal_id, address token, address to, uint128 amount))`.
+    pub fn withdrawal_digest(withdrawal_id: WithdrawalRecordId) -> Result<[u8; 32], DispatchError> {
+        let record = xpallet_gateway_records::Pallet::<T>::pending_withdrawals(withdrawal_id)
+            .ok_or(Error::<T>::NotApplyingWithdrawal)?;
+        ensure!(
+            xpallet_gateway_records::Pallet::<T>::withdrawal_output_count(withdrawal_id) == 1,
+            Error::<T>::MultipleWithdrawalOutputs
+        );
+        let token = Self::token_of_asset(record.asset_id()).ok_or(Error::<T>::UnregisteredToken)?;
+        let to = parse_eth_address(record.addr()).ok_or(Error::<T>::InvalidAddress)?;
+        let amount = record.balance().saturated_into::<u128>();
+
+        let mut data = Vec::with_capacity(4 + 20 + 20 + 16);
+        data.extend_from_slice(&withdrawal_id.to_be_bytes());
+        data.extend_from_slice(token.as_bytes());
+        data.extend_from_slice(to.as_bytes());
+        data.extend_from_slice(&amount.to_be_bytes());
+        debug!(
+            target: "runtime::gateway::ethereum",
+            "[withdrawal_digest] id:{}, token:{:?}, to:{:?}, amount:{}",
+            withdrawal_id, token, to, amount
+        );
+        Ok(sp_io::hashing::keccak_256(&data))
+    }
+}
+
+/// Parse the hex encoded Ethereum address, with or without the `0x` prefix.
+pub fn parse_eth_address(addr: &[u8]) -> Option<H160> {
+    let addr = addr.strip_prefix(b"0x").unwrap_or(addr);
+    let mut address = [0u8; 20];
+    hex::decode_to_slice(addr, &mut address).ok()?;
+    Some(H160(address))
+}
+
+/// Recover the Ethereum address that signed `digest`.
+pub fn recover_eth_address(signature: &ecdsa::Signature, digest: &[u8; 32]) -> Option<H160> {
+    match sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, digest) {
+        Ok(public) => Some(H160::from_slice(&sp_io::hashing::keccak_256(&public)[12..])),
+        Err(err) => {
+            error!(
+                target: "runtime::gateway::ethereum",
+                "[recover_eth_address] Recover failed, error:{:?}",
+                err
+            );
+            None
+        }
+    }
+}
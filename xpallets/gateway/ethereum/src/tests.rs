@@ -0,0 +1,201 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_core::{H160, H256};
+
+use xpallet_gateway_records::WithdrawalState;
+
+use super::mock::*;
+
+fn setup_committee() {
+    assert_ok!(XGatewayEthereum::set_committee(
+        RawOrigin::Root.into(),
+        vec![
+            (ALICE, eth_address(ALICE)),
+            (BOB, eth_address(BOB)),
+            (CHARLIE, eth_address(CHARLIE)),
+        ]
+    ));
+    assert_ok!(XGatewayEthereum::register_token(
+        RawOrigin::Root.into(),
+        USDT,
+        X_ETH
+    ));
+}
+
+#[test]
+fn set_committee_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XGatewayEthereum::set_committee(
+                RawOrigin::Signed(ALICE).into(),
+                vec![(ALICE, eth_address(ALICE))]
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XGatewayEthereum::set_committee(
+                RawOrigin::Root.into(),
+                vec![(ALICE, eth_address(ALICE)), (BOB, eth_address(BOB))]
+            ),
+            XGatewayEthereumErr::InvalidCommitteeCount
+        );
+        assert_noop!(
+            XGatewayEthereum::set_committee(
+                RawOrigin::Root.into(),
+                vec![
+                    (ALICE, eth_address(ALICE)),
+                    (BOB, eth_address(BOB)),
+                    (CHARLIE, eth_address(BOB)),
+                ]
+            ),
+            XGatewayEthereumErr::DuplicateCommitteeMember
+        );
+
+        assert_noop!(
+            XGatewayEthereum::register_token(RawOrigin::Root.into(), USDT, X_BTC),
+            XGatewayEthereumErr::NotEthereumAsset
+        );
+        setup_committee();
+        assert_eq!(XGatewayEthereum::committee().len(), 3);
+        assert_eq!(XGatewayEthereum::asset_of_token(USDT), Some(X_ETH));
+        assert_noop!(
+            XGatewayEthereum::register_token(RawOrigin::Root.into(), H160::zero(), X_ETH),
+            XGatewayEthereumErr::TokenAlreadyRegistered
+        );
+    })
+}
+
+#[test]
+fn attest_deposit_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup_committee();
+        let tx_hash = H256::repeat_byte(1);
+        let attest = |who, amount| {
+            XGatewayEthereum::attest_deposit(
+                RawOrigin::Signed(who).into(),
+                tx_hash,
+                0,
+                USDT,
+                DAVE,
+                amount,
+            )
+        };
+
+        assert_noop!(attest(DAVE, 100), XGatewayEthereumErr::NotCommitteeMember);
+        assert_ok!(attest(ALICE, 100));
+        assert_noop!(
+            attest(ALICE, 100),
+            XGatewayEthereumErr::DuplicateAttestation
+        );
+        // a different deposit of the same log doesn't count for the first one.
+        assert_ok!(attest(BOB, 200));
+        assert_eq!(XAssets::usable_balance(&DAVE, &X_ETH), 400);
+
+        assert_ok!(attest(CHARLIE, 100));
+        assert_eq!(XAssets::usable_balance(&DAVE, &X_ETH), 400 + 100);
+        assert!(XGatewayEthereum::processed_deposits((tx_hash, 0)).is_some());
+        assert_noop!(
+            attest(ALICE, 200),
+            XGatewayEthereumErr::DepositAlreadyProcessed
+        );
+    })
+}
+
+#[test]
+fn withdrawal_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup_committee();
+        let to = b"0x1111111111111111111111111111111111111111".to_vec();
+        assert_ok!(XGatewayRecords::withdraw(
+            &DAVE,
+            X_ETH,
+            300,
+            to,
+            b"".to_vec().into()
+        ));
+        let id = 0;
+        let digest = XGatewayEthereum::withdrawal_digest(id).unwrap();
+
+        // the signature must be signed by the Ethereum address of the member.
+        assert_noop!(
+            XGatewayEthereum::sign_withdrawal(
+                RawOrigin::Signed(ALICE).into(),
+                id,
+                eth_pair(BOB).sign_prehashed(&digest)
+            ),
+            XGatewayEthereumErr::InvalidSignature
+        );
+        assert_noop!(
+            XGatewayEthereum::confirm_withdrawal(
+                RawOrigin::Signed(ALICE).into(),
+                id,
+                H256::repeat_byte(2)
+            ),
+            XGatewayEthereumErr::NotProcessingWithdrawal
+        );
+        for who in [ALICE, BOB] {
+            assert_ok!(XGatewayEthereum::sign_withdrawal(
+                RawOrigin::Signed(who).into(),
+                id,
+                eth_pair(who).sign_prehashed(&digest)
+            ));
+        }
+        assert_eq!(XGatewayEthereum::withdrawal_signatures(id).len(), 2);
+        assert_eq!(
+            XGatewayRecords::state_of(id),
+            Some(WithdrawalState::Processing)
+        );
+        assert_noop!(
+            XGatewayEthereum::sign_withdrawal(
+                RawOrigin::Signed(CHARLIE).into(),
+                id,
+                eth_pair(CHARLIE).sign_prehashed(&digest)
+            ),
+            XGatewayEthereumErr::NotApplyingWithdrawal
+        );
+
+        let tx_hash = H256::repeat_byte(2);
+        assert_ok!(XGatewayEthereum::confirm_withdrawal(
+            RawOrigin::Signed(ALICE).into(),
+            id,
+            H256::repeat_byte(3)
+        ));
+        assert_ok!(XGatewayEthereum::confirm_withdrawal(
+            RawOrigin::Signed(BOB).into(),
+            id,
+            tx_hash
+        ));
+        assert_eq!(
+            XGatewayRecords::state_of(id),
+            Some(WithdrawalState::Processing)
+        );
+        assert_ok!(XGatewayEthereum::confirm_withdrawal(
+            RawOrigin::Signed(CHARLIE).into(),
+            id,
+            tx_hash
+        ));
+        assert_eq!(XGatewayRecords::state_of(id), None);
+        assert!(XGatewayEthereum::withdrawal_signatures(id).is_empty());
+        assert_eq!(XAssets::usable_balance(&DAVE, &X_ETH), 400 - 300);
+    })
+}
+
+#[test]
+fn parse_eth_address_should_work() {
+    let address = H160::repeat_byte(0x11);
+    assert_eq!(
+        crate::parse_eth_address(b"0x1111111111111111111111111111111111111111"),
+        Some(address)
+    );
+    assert_eq!(
+        crate::parse_eth_address(b"1111111111111111111111111111111111111111"),
+        Some(address)
+    );
+    assert_eq!(crate::parse_eth_address(b"0x1111"), None);
+    assert_eq!(
+        crate::parse_eth_address(b"0x111111111111111111111111111111111111111g"),
+        None
+    );
+}
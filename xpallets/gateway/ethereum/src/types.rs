@@ -0,0 +1,26 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_core::{H160, H256};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// The deposit on Ethereum, identified by the tx hash and the index of the deposit log.
+pub type EthDepositId = (H256, u32);
+
+/// The attestations of a deposit on Ethereum by the committee.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthDepositAttestation<AccountId, Balance> {
+    /// The deposited token contract, zero for the ether.
+    pub token: H160,
+    /// The receiver of the deposit on ChainX.
+    pub who: AccountId,
+    pub amount: Balance,
+    pub attesters: Vec<AccountId>,
+}
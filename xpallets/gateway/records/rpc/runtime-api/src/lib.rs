@@ -9,7 +9,9 @@ use codec::Codec;
 
 pub use chainx_primitives::{AssetId, Decimals};
 pub use xpallet_assets::Chain;
-pub use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId, WithdrawalState};
+pub use xpallet_gateway_records::{
+    Withdrawal, WithdrawalRecordId, WithdrawalState, WithdrawalStatus,
+};
 
 sp_api::decl_runtime_apis! {
     pub trait XGatewayRecordsApi<AccountId, Balance, BlockNumber>
@@ -21,5 +23,7 @@ sp_api::decl_runtime_apis! {
         fn withdrawal_list() -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
 
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
+
+        fn withdrawal_status(id: WithdrawalRecordId) -> Option<WithdrawalStatus>;
     }
 }
@@ -16,7 +16,7 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result};
 
 use xpallet_gateway_records_rpc_runtime_api::{
-    AssetId, Chain, Withdrawal, WithdrawalRecordId, WithdrawalState,
+    AssetId, Chain, Withdrawal, WithdrawalRecordId, WithdrawalState, WithdrawalStatus,
     XGatewayRecordsApi as GatewayRecordsRuntimeApi,
 };
 
@@ -62,6 +62,14 @@ where
         chain: Chain,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<WithdrawalRecordId, RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>>;
+
+    /// Return the lifecycle status of a withdrawal, including the finished and canceled ones
+    #[rpc(name = "xgatewayrecords_withdrawalStatus")]
+    fn withdrawal_status(
+        &self,
+        id: WithdrawalRecordId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<WithdrawalStatus>>;
 }
 
 impl<C, Block, AccountId, Balance, BlockNumber>
@@ -127,6 +135,17 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn withdrawal_status(
+        &self,
+        id: WithdrawalRecordId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<WithdrawalStatus>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.withdrawal_status(&at, id)
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -140,6 +159,15 @@ pub struct RpcWithdrawalRecord<AccountId, Balance: Display + FromStr, BlockNumbe
     pub ext: String,
     pub height: BlockNumber,
     pub state: WithdrawalState,
+    pub outputs: Vec<RpcWithdrawalOutput<Balance>>,
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWithdrawalOutput<Balance: Display + FromStr> {
+    pub addr: String,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub balance: Balance,
 }
 
 impl<AccountId, Balance: Display + FromStr, BlockNumber>
@@ -155,6 +183,14 @@ impl<AccountId, Balance: Display + FromStr, BlockNumber>
             ext: String::from_utf8_lossy(record.ext.as_ref()).into_owned(),
             height: record.height,
             state: record.state,
+            outputs: record
+                .outputs
+                .into_iter()
+                .map(|(addr, balance)| RpcWithdrawalOutput {
+                    addr: String::from_utf8_lossy(addr.as_ref()).into_owned(),
+                    balance,
+                })
+                .collect(),
         }
     }
 }
@@ -5,6 +5,7 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -21,14 +22,18 @@ use frame_support::{
     transactional,
 };
 use frame_system::ensure_root;
-use sp_runtime::traits::StaticLookup;
+use sp_core::H256;
+use sp_runtime::traits::{CheckedSub, Saturating, StaticLookup, Zero};
 
 use chainx_primitives::{AddrStr, AssetId};
 use xp_runtime::Memo;
 use xpallet_assets::{AssetType, BalanceOf, Chain};
 use xpallet_support::try_addr;
 
-pub use self::types::{Withdrawal, WithdrawalRecord, WithdrawalRecordId, WithdrawalState};
+pub use self::types::{
+    ApplyingWithdrawals, Withdrawal, WithdrawalRecord, WithdrawalRecordId, WithdrawalState,
+    WithdrawalStatus,
+};
 pub use self::weights::WeightInfo;
 
 pub type WithdrawalRecordOf<T> = WithdrawalRecord<
@@ -131,6 +136,8 @@ pub mod pallet {
         Deposited(T::AccountId, AssetId, BalanceOf<T>),
         /// A withdrawal application was created. [withdrawal_id, record_info]
         WithdrawalCreated(WithdrawalRecordId, WithdrawalRecordOf<T>),
+        /// A withdrawal application was created to pay out multiple outputs. [withdrawal_id, outputs]
+        WithdrawalOutputsCreated(WithdrawalRecordId, Vec<(AddrStr, BalanceOf<T>)>),
         /// A withdrawal proposal was processed. [withdrawal_id]
         WithdrawalProcessed(WithdrawalRecordId),
         /// A withdrawal proposal was recovered. [withdrawal_id]
@@ -139,6 +146,8 @@ pub mod pallet {
         WithdrawalCanceled(WithdrawalRecordId, WithdrawalState),
         /// A withdrawal proposal was finished successfully. [withdrawal_id, withdrawal_state]
         WithdrawalFinished(WithdrawalRecordId, WithdrawalState),
+        /// The lifecycle status of a withdrawal changed. [withdrawal_id, withdrawal_status]
+        WithdrawalStatusChanged(WithdrawalRecordId, WithdrawalStatus),
     }
 
     #[pallet::error]
@@ -155,6 +164,12 @@ pub mod pallet {
         InvalidState,
         /// Meet unexpected chain
         UnexpectedChain,
+        /// The withdrawal has no output
+        EmptyWithdrawalOutputs,
+        /// WithdrawalRecord status not `Processing` or `Broadcasted`
+        NotBroadcastableStatus,
+        /// The held back balance exceeds the balance of the withdrawal
+        InsufficientWithdrawalBalance,
     }
 
     #[pallet::type_value]
@@ -179,6 +194,31 @@ pub mod pallet {
     #[pallet::getter(fn state_of)]
     pub(crate) type WithdrawalStateOf<T: Config> =
         StorageMap<_, Twox64Concat, WithdrawalRecordId, WithdrawalState>;
+
+    /// The outputs of the withdrawals paid out to multiple addresses.
+    ///
+    /// The `addr` of such a withdrawal record is the address of the first output and
+    /// the `balance` is the sum of all the outputs.
+    #[pallet::storage]
+    pub(crate) type WithdrawalOutputs<T: Config> =
+        StorageMap<_, Twox64Concat, WithdrawalRecordId, Vec<(AddrStr, BalanceOf<T>)>>;
+
+    /// The lifecycle status of the withdrawals, kept after the withdrawals are finished or
+    /// canceled so that the indexers could track them.
+    #[pallet::storage]
+    pub(crate) type WithdrawalLifecycle<T: Config> =
+        StorageMap<_, Twox64Concat, WithdrawalRecordId, WithdrawalStatus>;
+
+    /// The running totals of the applying withdrawals of the corresponding chain.
+    #[pallet::storage]
+    #[pallet::getter(fn applying_withdrawals_of)]
+    pub(crate) type ApplyingWithdrawalsOf<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        Chain,
+        ApplyingWithdrawals<BalanceOf<T>, T::BlockNumber>,
+        ValueQuery,
+    >;
 }
 
 impl<T: Config> Pallet<T> {
@@ -208,6 +248,46 @@ impl<T: Config> Pallet<T> {
         let state = Self::state_of(id).ok_or(Error::<T>::NotExisted)?;
         Ok((record, state))
     }
+
+    fn set_withdrawal_status(id: WithdrawalRecordId, status: WithdrawalStatus) {
+        WithdrawalLifecycle::<T>::insert(id, status);
+        Self::deposit_event(Event::<T>::WithdrawalStatusChanged(id, status));
+    }
+
+    /// Adds the withdrawal entering the `Applying` state into the totals of its chain.
+    pub(crate) fn add_applying(id: WithdrawalRecordId, record: &WithdrawalRecordOf<T>) {
+        let chain = match xpallet_assets_registrar::Pallet::<T>::chain_of(&record.asset_id()) {
+            Ok(chain) => chain,
+            Err(_) => return,
+        };
+        let output_count = Self::withdrawal_output_count(id);
+        ApplyingWithdrawalsOf::<T>::mutate(chain, |applying| {
+            applying.output_count = applying.output_count.saturating_add(output_count);
+            applying.balance = applying.balance.saturating_add(record.balance());
+            applying.since = Some(
+                applying
+                    .since
+                    .map_or(record.height(), |since| since.min(record.height())),
+            );
+        });
+    }
+
+    /// Removes the withdrawal leaving the `Applying` state from the totals of its chain.
+    fn remove_applying(id: WithdrawalRecordId, record: &WithdrawalRecordOf<T>) {
+        let chain = match xpallet_assets_registrar::Pallet::<T>::chain_of(&record.asset_id()) {
+            Ok(chain) => chain,
+            Err(_) => return,
+        };
+        let output_count = Self::withdrawal_output_count(id);
+        ApplyingWithdrawalsOf::<T>::mutate_exists(chain, |maybe_applying| {
+            let applying = maybe_applying.get_or_insert_with(Default::default);
+            applying.output_count = applying.output_count.saturating_sub(output_count);
+            applying.balance = applying.balance.saturating_sub(record.balance());
+            if applying.output_count.is_zero() {
+                *maybe_applying = None;
+            }
+        });
+    }
 }
 
 impl<T: Config> Pallet<T> {
@@ -240,6 +320,41 @@ impl<T: Config> Pallet<T> {
         addr: AddrStr,
         ext: Memo,
     ) -> DispatchResult {
+        Self::apply_withdrawal(who, asset_id, balance, addr, ext, None).map(|_| ())
+    }
+
+    /// Withdrawal asset to multiple addresses with a single withdrawal record.
+    ///
+    /// Each output is paid out individually by the chain.
+    ///
+    /// WithdrawalRecord State: `Applying`
+    pub fn withdraw_to_many(
+        who: &T::AccountId,
+        asset_id: AssetId,
+        outputs: Vec<(AddrStr, BalanceOf<T>)>,
+        ext: Memo,
+    ) -> DispatchResult {
+        let addr = match outputs.first() {
+            Some((addr, _)) => addr.clone(),
+            None => return Err(Error::<T>::EmptyWithdrawalOutputs.into()),
+        };
+        let balance = outputs
+            .iter()
+            .fold(BalanceOf::<T>::zero(), |acc, (_, value)| {
+                acc.saturating_add(*value)
+            });
+
+        Self::apply_withdrawal(who, asset_id, balance, addr, ext, Some(outputs)).map(|_| ())
+    }
+
+    fn apply_withdrawal(
+        who: &T::AccountId,
+        asset_id: AssetId,
+        balance: BalanceOf<T>,
+        addr: AddrStr,
+        ext: Memo,
+        outputs: Option<Vec<(AddrStr, BalanceOf<T>)>>,
+    ) -> Result<WithdrawalRecordId, DispatchError> {
         xpallet_assets::Pallet::<T>::ensure_not_native_asset(&asset_id)?;
         Self::ensure_withdrawal_available_balance(who, asset_id, balance)?;
 
@@ -267,8 +382,31 @@ impl<T: Config> Pallet<T> {
         let next_id = id.checked_add(1_u32).unwrap_or(0);
         NextWithdrawalRecordId::<T>::put(next_id);
 
+        if let Some(outputs) = outputs.clone() {
+            WithdrawalOutputs::<T>::insert(id, outputs);
+        }
+        Self::add_applying(id, &record);
+
         Self::deposit_event(Event::<T>::WithdrawalCreated(id, record));
-        Ok(())
+        Self::set_withdrawal_status(id, WithdrawalStatus::Applied);
+        if let Some(outputs) = outputs {
+            Self::deposit_event(Event::<T>::WithdrawalOutputsCreated(id, outputs));
+        }
+        Ok(id)
+    }
+
+    /// Returns the (address, balance) outputs of the withdrawal.
+    pub fn withdrawal_outputs(id: WithdrawalRecordId) -> Option<Vec<(AddrStr, BalanceOf<T>)>> {
+        let record = Self::pending_withdrawals(id)?;
+        Some(
+            WithdrawalOutputs::<T>::get(id)
+                .unwrap_or_else(|| vec![(record.addr().clone(), record.balance())]),
+        )
+    }
+
+    /// Returns the number of outputs of the withdrawal.
+    pub fn withdrawal_output_count(id: WithdrawalRecordId) -> u32 {
+        WithdrawalOutputs::<T>::decode_len(id).unwrap_or(1) as u32
     }
 
     /// Process withdrawal (cannot be canceled, but can be recovered).
@@ -277,11 +415,12 @@ impl<T: Config> Pallet<T> {
     pub fn process_withdrawal(id: WithdrawalRecordId, chain: Chain) -> DispatchResult {
         let (record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
         Self::ensure_asset_belongs_to_chain(record.asset_id(), chain)?;
-        Self::process_withdrawal_impl(id, curr_state)
+        Self::process_withdrawal_impl(id, &record, curr_state)
     }
 
     fn process_withdrawal_impl(
         id: WithdrawalRecordId,
+        record: &WithdrawalRecordOf<T>,
         curr_state: WithdrawalState,
     ) -> DispatchResult {
         if curr_state != WithdrawalState::Applying {
@@ -293,7 +432,9 @@ impl<T: Config> Pallet<T> {
             return Err(Error::<T>::NotApplyingState.into());
         }
         WithdrawalStateOf::<T>::insert(id, WithdrawalState::Processing);
+        Self::remove_applying(id, record);
         Self::deposit_event(Event::<T>::WithdrawalProcessed(id));
+        Self::set_withdrawal_status(id, WithdrawalStatus::Processing);
         Ok(())
     }
 
@@ -312,11 +453,12 @@ impl<T: Config> Pallet<T> {
     pub fn recover_withdrawal(id: WithdrawalRecordId, chain: Chain) -> DispatchResult {
         let (record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
         Self::ensure_asset_belongs_to_chain(record.asset_id(), chain)?;
-        Self::recover_withdrawal_impl(id, curr_state)
+        Self::recover_withdrawal_impl(id, &record, curr_state)
     }
 
     fn recover_withdrawal_impl(
         id: WithdrawalRecordId,
+        record: &WithdrawalRecordOf<T>,
         curr_state: WithdrawalState,
     ) -> DispatchResult {
         if curr_state != WithdrawalState::Processing {
@@ -328,7 +470,9 @@ impl<T: Config> Pallet<T> {
             return Err(Error::<T>::NotProcessingState.into());
         }
         WithdrawalStateOf::<T>::insert(id, WithdrawalState::Applying);
+        Self::add_applying(id, record);
         Self::deposit_event(Event::<T>::WithdrawalRecovered(id));
+        Self::set_withdrawal_status(id, WithdrawalStatus::Applied);
         Ok(())
     }
 
@@ -368,12 +512,53 @@ impl<T: Config> Pallet<T> {
 
         // Unlock reserved asset
         Self::unlock(record.applicant(), record.asset_id(), record.balance())?;
+        Self::remove_applying(id, &record);
 
         // Remove storage
         PendingWithdrawals::<T>::remove(id);
         WithdrawalStateOf::<T>::remove(id);
+        WithdrawalOutputs::<T>::remove(id);
 
         Self::deposit_event(Event::<T>::WithdrawalCanceled(id, new_state));
+        Self::set_withdrawal_status(id, WithdrawalStatus::Dropped);
+        Ok(())
+    }
+
+    /// Hold back `value` of the withdrawal from being destroyed, the held back balance is moved
+    /// to the usable balance of `to` and only the rest is destroyed when the withdrawal is finished.
+    ///
+    /// WithdrawalRecord State: `Processing`
+    pub fn hold_back_withdrawal(
+        id: WithdrawalRecordId,
+        to: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        let (mut record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
+        if curr_state != WithdrawalState::Processing {
+            error!(
+                target: "runtime::gateway::records",
+                "[hold_back_withdrawal] id:{}, current withdrawal state ({:?}) must be `Processing`",
+                id, curr_state
+            );
+            return Err(Error::<T>::NotProcessingState.into());
+        }
+        let balance = record
+            .balance()
+            .checked_sub(&value)
+            .ok_or(Error::<T>::InsufficientWithdrawalBalance)?;
+
+        xpallet_assets::Pallet::<T>::move_balance(
+            &record.asset_id(),
+            record.applicant(),
+            AssetType::ReservedWithdrawal,
+            to,
+            AssetType::Usable,
+            value,
+        )
+        .map_err::<xpallet_assets::Error<T>, _>(Into::into)?;
+
+        record.set_balance(balance);
+        PendingWithdrawals::<T>::insert(id, record);
         Ok(())
     }
 
@@ -398,7 +583,21 @@ impl<T: Config> Pallet<T> {
         if let Some(chain) = expected_chain {
             Self::ensure_asset_belongs_to_chain(record.asset_id(), chain)?;
         }
-        Self::finish_withdrawal_impl(id, record, curr_state, WithdrawalState::NormalFinish)
+        Self::finish_withdrawal_impl(id, record, curr_state, WithdrawalState::NormalFinish, None)
+    }
+
+    /// Finish withdrawal paid out by the transaction `txid` of the chain.
+    ///
+    /// WithdrawalRecord State: `Processing` ==> `NormalFinish`
+    pub fn finish_withdrawal_by_tx(id: WithdrawalRecordId, txid: H256) -> DispatchResult {
+        let (record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
+        Self::finish_withdrawal_impl(
+            id,
+            record,
+            curr_state,
+            WithdrawalState::NormalFinish,
+            Some(txid),
+        )
     }
 
     fn finish_withdrawal_impl(
@@ -406,6 +605,7 @@ impl<T: Config> Pallet<T> {
         record: WithdrawalRecordOf<T>,
         curr_state: WithdrawalState,
         new_state: WithdrawalState,
+        txid: Option<H256>,
     ) -> DispatchResult {
         if curr_state != WithdrawalState::Processing {
             error!(
@@ -422,8 +622,43 @@ impl<T: Config> Pallet<T> {
         // Remove storage
         PendingWithdrawals::<T>::remove(id);
         WithdrawalStateOf::<T>::remove(id);
+        WithdrawalOutputs::<T>::remove(id);
 
         Self::deposit_event(Event::<T>::WithdrawalFinished(id, new_state));
+        // The reported txid is taken if the withdrawal was not finished by a transaction.
+        let txid = txid.or_else(|| WithdrawalLifecycle::<T>::get(id).and_then(|s| s.txid()));
+        Self::set_withdrawal_status(id, WithdrawalStatus::Confirmed(txid));
+        Ok(())
+    }
+
+    /// Record the transaction `txid` of the chain broadcasted to pay out the withdrawals.
+    ///
+    /// WithdrawalRecord Status: `Processing`|`Broadcasted` ==> `Broadcasted`
+    #[transactional]
+    pub fn broadcast_withdrawals(
+        ids: &[WithdrawalRecordId],
+        chain: Chain,
+        txid: H256,
+    ) -> DispatchResult {
+        for id in ids {
+            let (record, curr_state) = Self::ensure_withdrawal_records_exists(*id)?;
+            Self::ensure_asset_belongs_to_chain(record.asset_id(), chain)?;
+            let curr_status = Self::withdrawal_status(*id).unwrap_or_else(|| curr_state.into());
+            match curr_status {
+                WithdrawalStatus::Processing => {}
+                WithdrawalStatus::Broadcasted(prev) if prev != txid => {}
+                WithdrawalStatus::Broadcasted(_) => continue,
+                _ => {
+                    error!(
+                        target: "runtime::gateway::records",
+                        "[broadcast_withdrawals] id:{}, current withdrawal status ({:?}) must be `Processing` or `Broadcasted`",
+                        id, curr_status
+                    );
+                    return Err(Error::<T>::NotBroadcastableStatus.into());
+                }
+            }
+            Self::set_withdrawal_status(*id, WithdrawalStatus::Broadcasted(txid));
+        }
         Ok(())
     }
 
@@ -448,11 +683,11 @@ impl<T: Config> Pallet<T> {
             (curr, new) if curr == new => Ok(()),
             (WithdrawalState::Applying, WithdrawalState::Processing) => {
                 // State: `Applying` ==> `Processing`
-                Self::process_withdrawal_impl(id, curr_state)
+                Self::process_withdrawal_impl(id, &record, curr_state)
             }
             (WithdrawalState::Processing, WithdrawalState::Applying) => {
                 // State: `Processing` ==> `Applying`
-                Self::recover_withdrawal_impl(id, curr_state)
+                Self::recover_withdrawal_impl(id, &record, curr_state)
             }
             (WithdrawalState::Applying, WithdrawalState::NormalCancel)
             | (WithdrawalState::Applying, WithdrawalState::RootCancel) => {
@@ -462,14 +697,14 @@ impl<T: Config> Pallet<T> {
             (WithdrawalState::Applying, WithdrawalState::NormalFinish)
             | (WithdrawalState::Applying, WithdrawalState::RootFinish) => {
                 // State: `Applying` ==> `Processing` ==> `NormalFinish`|`RootFinish`
-                Self::process_withdrawal_impl(id, curr_state)?;
+                Self::process_withdrawal_impl(id, &record, curr_state)?;
                 let curr_state = Self::state_of(id).ok_or(Error::<T>::NotExisted)?;
-                Self::finish_withdrawal_impl(id, record, curr_state, new_state)
+                Self::finish_withdrawal_impl(id, record, curr_state, new_state, None)
             }
             (WithdrawalState::Processing, WithdrawalState::NormalFinish)
             | (WithdrawalState::Processing, WithdrawalState::RootFinish) => {
                 // State: `Processing` ==> `NormalFinish`|`RootFinish`
-                Self::finish_withdrawal_impl(id, record, curr_state, new_state)
+                Self::finish_withdrawal_impl(id, record, curr_state, new_state, None)
             }
             _ => {
                 error!(
@@ -555,15 +790,20 @@ impl<T: Config> Pallet<T> {
     pub fn withdrawal_list(
     ) -> BTreeMap<WithdrawalRecordId, Withdrawal<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
         PendingWithdrawals::<T>::iter()
-            .map(|(id, record)| {
-                (
-                    id,
-                    Withdrawal::new(record, Self::state_of(id).unwrap_or_default()),
-                )
-            })
+            .map(|(id, record)| (id, Self::withdrawal_info_of(id, record)))
             .collect()
     }
 
+    /// Returns the withdrawal info of the pending withdrawal `record` with the given `id`.
+    pub fn withdrawal_info_of(
+        id: WithdrawalRecordId,
+        record: WithdrawalRecordOf<T>,
+    ) -> Withdrawal<T::AccountId, BalanceOf<T>, T::BlockNumber> {
+        let mut withdrawal = Withdrawal::new(record, Self::state_of(id).unwrap_or_default());
+        withdrawal.outputs = WithdrawalOutputs::<T>::get(id).unwrap_or_default();
+        withdrawal
+    }
+
     pub fn withdrawals_list_by_chain(
         chain: Chain,
     ) -> BTreeMap<WithdrawalRecordId, Withdrawal<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
@@ -575,7 +815,25 @@ impl<T: Config> Pallet<T> {
             .collect()
     }
 
+    /// Tightens the block before which no withdrawal of `chain` is applying, e.g. after the
+    /// oldest applying withdrawal is processed or canceled.
+    pub fn refresh_applying_since(chain: Chain, since: Option<T::BlockNumber>) {
+        ApplyingWithdrawalsOf::<T>::mutate_exists(chain, |maybe_applying| {
+            if let Some(applying) = maybe_applying {
+                applying.since = since;
+            }
+        });
+    }
+
     pub fn withdrawal_state_insert(id: WithdrawalRecordId, state: WithdrawalState) {
         WithdrawalStateOf::<T>::insert(id, state)
     }
+
+    /// Returns the lifecycle status of the withdrawal.
+    ///
+    /// The pending withdrawals applied before the lifecycle was tracked derive the status
+    /// from their state.
+    pub fn withdrawal_status(id: WithdrawalRecordId) -> Option<WithdrawalStatus> {
+        WithdrawalLifecycle::<T>::get(id).or_else(|| Self::state_of(id).map(Into::into))
+    }
 }
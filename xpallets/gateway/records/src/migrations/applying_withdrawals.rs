@@ -0,0 +1,55 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{log::info, traits::Get, weights::Weight};
+
+use crate::{
+    ApplyingWithdrawalsOf, Chain, Config, Pallet, PendingWithdrawals, WithdrawalState,
+    WithdrawalStateOf,
+};
+
+/// Compute the totals of the withdrawals applied before `ApplyingWithdrawalsOf`.
+///
+/// The totals are computed from scratch out of the pending withdrawals, so it's fine to
+/// apply it more than once.
+pub fn apply<T: Config>() -> Weight {
+    let mut reads = 0;
+    let mut writes = 0;
+    for chain in Chain::iter() {
+        writes += 1;
+        ApplyingWithdrawalsOf::<T>::remove(chain);
+    }
+    let mut applying = 0;
+    for (id, record) in PendingWithdrawals::<T>::iter() {
+        reads += 2;
+        if WithdrawalStateOf::<T>::get(id) != Some(WithdrawalState::Applying) {
+            continue;
+        }
+        reads += 3;
+        writes += 1;
+        applying += 1;
+        Pallet::<T>::add_applying(id, &record);
+    }
+    info!(
+        target: "runtime::gateway::records",
+        "computed the totals of {} applying withdrawals.",
+        applying,
+    );
+    <T as frame_system::Config>::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Check that the totals cover all the applying withdrawals after the migration.
+#[cfg(feature = "try-runtime")]
+pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+    let applying = PendingWithdrawals::<T>::iter_keys()
+        .filter(|id| WithdrawalStateOf::<T>::get(id) == Some(WithdrawalState::Applying))
+        .map(Pallet::<T>::withdrawal_output_count)
+        .sum::<u32>();
+    let counted = ApplyingWithdrawalsOf::<T>::iter_values()
+        .map(|totals| totals.output_count)
+        .sum::<u32>();
+    frame_support::ensure!(
+        applying == counted,
+        "the applying withdrawals are not all counted after migration"
+    );
+    Ok(())
+}
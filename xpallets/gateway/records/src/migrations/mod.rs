@@ -0,0 +1,5 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! All migrations of this pallet.
+
+pub mod applying_withdrawals;
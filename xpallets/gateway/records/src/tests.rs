@@ -206,3 +206,171 @@ fn test_withdrawal_chainx() {
         );
     })
 }
+
+#[test]
+fn test_withdraw_to_many() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XGatewayRecords::withdraw_to_many(&ALICE, X_BTC, vec![], b"ext".to_vec().into()),
+            Error::<Test>::EmptyWithdrawalOutputs
+        );
+
+        let outputs = vec![(b"addr1".to_vec(), 30), (b"addr2".to_vec(), 20)];
+        assert_ok!(XGatewayRecords::withdraw_to_many(
+            &ALICE,
+            X_BTC,
+            outputs.clone(),
+            b"ext".to_vec().into()
+        ));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100 - 50);
+
+        // A single record with the first address and the total balance.
+        let record = XGatewayRecords::pending_withdrawals(0).unwrap();
+        assert_eq!(record.addr(), &b"addr1".to_vec());
+        assert_eq!(record.balance(), 50);
+        assert_eq!(XGatewayRecords::withdrawal_output_count(0), 2);
+        assert_eq!(
+            XGatewayRecords::withdrawal_outputs(0),
+            Some(outputs.clone())
+        );
+        assert_eq!(
+            XGatewayRecords::withdrawal_list()
+                .get(&0)
+                .map(|withdrawal| withdrawal.outputs.clone()),
+            Some(outputs)
+        );
+
+        assert_ok!(XGatewayRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            10,
+            b"addr3".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_eq!(XGatewayRecords::withdrawal_output_count(1), 1);
+        assert_eq!(
+            XGatewayRecords::withdrawal_outputs(1),
+            Some(vec![(b"addr3".to_vec(), 10)])
+        );
+
+        assert_ok!(XGatewayRecords::process_withdrawals(&[0], Chain::Bitcoin));
+        assert_ok!(XGatewayRecords::finish_withdrawal(0, None));
+        assert_eq!(XGatewayRecords::withdrawal_outputs(0), None);
+        assert_eq!(XGatewayRecords::withdrawal_output_count(0), 1);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100 - 50 - 10);
+    })
+}
+
+#[test]
+fn test_applying_withdrawals() {
+    ExtBuilder::default().build_and_execute(|| {
+        let applying = || {
+            let applying = XGatewayRecords::applying_withdrawals_of(Chain::Bitcoin);
+            (applying.output_count, applying.balance, applying.since)
+        };
+
+        let outputs = vec![(b"addr1".to_vec(), 30), (b"addr2".to_vec(), 20)];
+        assert_ok!(XGatewayRecords::withdraw_to_many(
+            &ALICE,
+            X_BTC,
+            outputs,
+            b"ext".to_vec().into()
+        ));
+        System::set_block_number(2);
+        assert_ok!(XGatewayRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            10,
+            b"addr3".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_eq!(applying(), (3, 60, Some(1)));
+
+        // The processing withdrawals are not applying, unless they are recovered.
+        assert_ok!(XGatewayRecords::process_withdrawals(&[0], Chain::Bitcoin));
+        assert_eq!(applying(), (1, 10, Some(1)));
+        XGatewayRecords::refresh_applying_since(Chain::Bitcoin, Some(2));
+        assert_ok!(XGatewayRecords::recover_withdrawal(0, Chain::Bitcoin));
+        assert_eq!(applying(), (3, 60, Some(1)));
+
+        assert_ok!(XGatewayRecords::cancel_withdrawal(1, &ALICE));
+        assert_eq!(applying(), (2, 50, Some(1)));
+        assert_ok!(XGatewayRecords::set_withdrawal_state_by_root(
+            0,
+            WithdrawalState::RootCancel
+        ));
+        assert_eq!(applying(), (0, 0, None));
+    })
+}
+
+#[test]
+fn test_withdrawal_lifecycle() {
+    ExtBuilder::default().build_and_execute(|| {
+        let txid = H256::repeat_byte(1);
+        let new_txid = H256::repeat_byte(2);
+        for _ in 0..2 {
+            assert_ok!(XGatewayRecords::withdraw(
+                &ALICE,
+                X_BTC,
+                10,
+                b"addr".to_vec(),
+                b"ext".to_vec().into()
+            ));
+        }
+        assert_eq!(
+            XGatewayRecords::withdrawal_status(0),
+            Some(WithdrawalStatus::Applied)
+        );
+
+        // Only the processing withdrawals could be broadcasted.
+        assert_noop!(
+            XGatewayRecords::broadcast_withdrawals(&[0], Chain::Bitcoin, txid),
+            XRecordsErr::NotBroadcastableStatus
+        );
+        assert_ok!(XGatewayRecords::process_withdrawals(
+            &[0, 1],
+            Chain::Bitcoin
+        ));
+        assert_noop!(
+            XGatewayRecords::broadcast_withdrawals(&[0], Chain::Ethereum, txid),
+            XRecordsErr::UnexpectedChain
+        );
+        assert_ok!(XGatewayRecords::broadcast_withdrawals(
+            &[0, 1],
+            Chain::Bitcoin,
+            txid
+        ));
+        assert_eq!(
+            XGatewayRecords::withdrawal_status(0),
+            Some(WithdrawalStatus::Broadcasted(txid))
+        );
+
+        // The recovered withdrawal is applied again.
+        assert_ok!(XGatewayRecords::set_withdrawal_state_by_root(
+            1,
+            WithdrawalState::Applying
+        ));
+        assert_eq!(
+            XGatewayRecords::withdrawal_status(1),
+            Some(WithdrawalStatus::Applied)
+        );
+        assert_ok!(XGatewayRecords::cancel_withdrawal(1, &ALICE));
+        assert_eq!(
+            XGatewayRecords::withdrawal_status(1),
+            Some(WithdrawalStatus::Dropped)
+        );
+
+        // The txid could be replaced until the withdrawal is confirmed.
+        assert_ok!(XGatewayRecords::broadcast_withdrawals(
+            &[0],
+            Chain::Bitcoin,
+            new_txid
+        ));
+        assert_ok!(XGatewayRecords::finish_withdrawal(0, None));
+        assert_eq!(
+            XGatewayRecords::withdrawal_status(0),
+            Some(WithdrawalStatus::Confirmed(Some(new_txid)))
+        );
+        assert_eq!(XGatewayRecords::pending_withdrawals(0), None);
+    })
+}
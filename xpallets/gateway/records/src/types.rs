@@ -5,7 +5,9 @@ use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use sp_core::H256;
 use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
 
 use chainx_primitives::{AddrStr, AssetId};
 use xp_runtime::Memo;
@@ -40,6 +42,66 @@ impl Default for WithdrawalState {
     }
 }
 
+/// The lifecycle of a withdrawal exposed to the indexers, kept after the withdrawal record
+/// is removed:
+///
+/// Applied <---> Processing ----> Broadcasted(txid) ----> Confirmed(txid)
+///    |                                |
+///    +----> Dropped (unlock token)    +----> Applied (recovered)
+///
+/// The txid of a broadcasted withdrawal could be reported again, e.g., replaced by fee.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum WithdrawalStatus {
+    /// The asset is locked, waiting for the trustees to process it.
+    Applied,
+    /// The withdrawal is in a proposal signed by the trustees.
+    Processing,
+    /// The trustees broadcasted the transaction paying out the withdrawal.
+    Broadcasted(H256),
+    /// The withdrawal was paid out, the txid is absent if finished by root.
+    Confirmed(Option<H256>),
+    /// The withdrawal was canceled and the asset unlocked.
+    Dropped,
+}
+
+impl WithdrawalStatus {
+    /// Returns the txid reported or confirmed for the withdrawal.
+    pub fn txid(&self) -> Option<H256> {
+        match self {
+            Self::Broadcasted(txid) => Some(*txid),
+            Self::Confirmed(txid) => *txid,
+            _ => None,
+        }
+    }
+}
+
+impl From<WithdrawalState> for WithdrawalStatus {
+    fn from(state: WithdrawalState) -> Self {
+        match state {
+            WithdrawalState::Applying => Self::Applied,
+            WithdrawalState::Processing => Self::Processing,
+            WithdrawalState::NormalFinish | WithdrawalState::RootFinish => Self::Confirmed(None),
+            WithdrawalState::NormalCancel | WithdrawalState::RootCancel => Self::Dropped,
+        }
+    }
+}
+
+/// The running totals of the applying withdrawals of a chain, updated whenever a withdrawal
+/// enters or leaves the `Applying` state.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ApplyingWithdrawals<Balance, BlockNumber> {
+    /// The number of the outputs of the applying withdrawals
+    pub output_count: u32,
+    /// The total balance of the applying withdrawals
+    pub balance: Balance,
+    /// No applying withdrawal was applied before this block, `None` if there is none
+    pub since: Option<BlockNumber>,
+}
+
 /// WithdrawalRecord for withdrawal
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct WithdrawalRecord<AccountId, Balance, BlockNumber> {
@@ -83,6 +145,9 @@ where
     pub fn balance(&self) -> Balance {
         self.balance
     }
+    pub(crate) fn set_balance(&mut self, balance: Balance) {
+        self.balance = balance;
+    }
     pub fn addr(&self) -> &AddrStr {
         &self.addr
     }
@@ -103,6 +168,8 @@ pub struct Withdrawal<AccountId, Balance, BlockNumber> {
     pub ext: Memo,
     pub height: BlockNumber,
     pub state: WithdrawalState,
+    /// The outputs of a withdrawal paid out to multiple addresses, empty if paid out to `addr` only.
+    pub outputs: Vec<(AddrStr, Balance)>,
 }
 
 impl<AccountId, Balance, BlockNumber> Withdrawal<AccountId, Balance, BlockNumber> {
@@ -118,6 +185,7 @@ impl<AccountId, Balance, BlockNumber> Withdrawal<AccountId, Balance, BlockNumber
             ext: record.ext,
             height: record.height,
             state,
+            outputs: Vec::new(),
         }
     }
 }
@@ -33,8 +33,11 @@ pub mod pallet {
     pub trait Config:
         frame_system::Config
         + pallet_balances::Config
+        + pallet_identity::Config
+        + xpallet_gateway_common::Config
         + xpallet_mining_asset::Config
         + xpallet_mining_staking::Config
+        + pallet_vesting::Config<Currency = pallet_balances::Pallet<Self>>
     {
     }
 
@@ -42,6 +45,9 @@ pub mod pallet {
     pub struct GenesisConfig<T: Config> {
         pub params: AllParams<T::AccountId, T::Balance, AssetBalanceOf<T>, StakingBalanceOf<T>>,
         pub initial_authorities: Vec<Vec<u8>>,
+        /// The initial registrars providing the identity judgements.
+        #[serde(default)]
+        pub registrars: Vec<T::AccountId>,
     }
 
     #[cfg(feature = "std")]
@@ -50,6 +56,7 @@ pub mod pallet {
             Self {
                 params: Default::default(),
                 initial_authorities: Default::default(),
+                registrars: Default::default(),
             }
         }
     }
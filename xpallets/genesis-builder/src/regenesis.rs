@@ -30,10 +30,51 @@ pub(crate) mod balances {
     }
 }
 
+pub(crate) mod identity {
+    use frame_system::RawOrigin;
+
+    use crate::Config;
+
+    // Add the registrars in order, the index of a registrar is its position.
+    pub fn initialize<T: Config>(registrars: &[T::AccountId]) {
+        for registrar in registrars {
+            pallet_identity::Pallet::<T>::add_registrar(RawOrigin::Root.into(), registrar.clone())
+                .expect("Failed to add genesis identity registrar");
+        }
+    }
+}
+
+pub(crate) mod vesting {
+    use frame_support::traits::VestingSchedule;
+    use xp_genesis_builder::{VestingInfo, VestingParams};
+
+    use crate::Config;
+
+    // Lock the vesting schedules on top of the PCX free balances.
+    pub fn initialize<T: Config>(params: &VestingParams<T::AccountId, T::Balance>) {
+        for VestingInfo {
+            who,
+            locked,
+            per_block,
+            starting_block,
+        } in &params.schedules
+        {
+            pallet_vesting::Pallet::<T>::add_vesting_schedule(
+                who,
+                *locked,
+                *per_block,
+                (*starting_block).into(),
+            )
+            .expect("Failed to initialize genesis vesting schedule");
+        }
+    }
+}
+
 pub(crate) mod xassets {
     // Set XBTC free balance.
-    use xp_genesis_builder::FreeBalanceInfo;
+    use xp_genesis_builder::{AssetRestriction, FreeBalanceInfo};
     use xp_protocol::X_BTC;
+    use xpallet_assets::{AssetRestrictions, AssetRestrictionsOf};
 
     use super::*;
     use crate::AssetBalanceOf;
@@ -43,6 +84,42 @@ pub(crate) mod xassets {
             xpallet_assets::Pallet::<T>::force_set_free_balance(&X_BTC, who, *free);
         }
     }
+
+    // Restore the asset restrictions, overriding the ones of the xassets genesis.
+    pub fn initialize_restrictions<T: Config>(asset_restrictions: &[AssetRestriction]) {
+        for AssetRestriction {
+            asset_id,
+            restrictions,
+        } in asset_restrictions
+        {
+            AssetRestrictionsOf::<T>::insert(
+                asset_id,
+                AssetRestrictions::from_bits_truncate(*restrictions),
+            );
+        }
+    }
+}
+
+pub(crate) mod xgateway_common {
+    use xp_genesis_builder::ReferralBind;
+
+    use super::*;
+
+    // Restore the referral bindings of the asset mining.
+    pub fn initialize<T: Config>(referral_binds: &[ReferralBind<T::AccountId>]) {
+        for ReferralBind {
+            who,
+            chain,
+            referral,
+        } in referral_binds
+        {
+            xpallet_gateway_common::Pallet::<T>::set_referral_binding(
+                *chain,
+                who.clone(),
+                referral.clone(),
+            );
+        }
+    }
 }
 
 pub(crate) mod xstaking {
@@ -120,9 +197,13 @@ pub(crate) fn initialize<T: Config>(config: &GenesisConfig<T>) {
     let now = std::time::Instant::now();
 
     balances::initialize::<T>(&config.params.balances);
+    vesting::initialize::<T>(&config.params.vesting);
     xassets::initialize::<T>(&config.params.xassets);
+    xassets::initialize_restrictions::<T>(&config.params.asset_restrictions);
     xstaking::initialize::<T>(&config.params.xstaking, &config.initial_authorities);
     xmining_asset::initialize::<T>(&config.params.xassets);
+    xgateway_common::initialize::<T>(&config.params.referral_binds);
+    identity::initialize::<T>(&config.registrars);
 
     frame_support::log::info!(
         "Took {:?}ms to orchestrate the regenesis state",
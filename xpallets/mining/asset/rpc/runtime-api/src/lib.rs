@@ -11,7 +11,7 @@ use codec::Codec;
 
 pub use chainx_primitives::AssetId;
 pub use xpallet_mining_asset::{
-    AssetLedger, MinerLedger, MiningAssetInfo, MiningDividendInfo, MiningWeight,
+    AssetEmissionRate, AssetLedger, MinerLedger, MiningAssetInfo, MiningDividendInfo, MiningWeight,
 };
 
 sp_api::decl_runtime_apis! {
@@ -31,5 +31,8 @@ sp_api::decl_runtime_apis! {
 
         /// Get the mining ledger details given the asset miner AccountId.
         fn miner_ledger(who: AccountId) -> BTreeMap<AssetId, MinerLedger<MiningWeight, BlockNumber>>;
+
+        /// Get the current emission rates of all the assets having the mining power.
+        fn asset_emission_rates() -> BTreeMap<AssetId, AssetEmissionRate<Balance>>;
     }
 }
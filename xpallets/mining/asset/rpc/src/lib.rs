@@ -17,7 +17,7 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcMiningWeight};
 
 use xpallet_mining_asset_rpc_runtime_api::{
-    AssetId, AssetLedger, MinerLedger, MiningAssetInfo, MiningDividendInfo,
+    AssetEmissionRate, AssetId, AssetLedger, MinerLedger, MiningAssetInfo, MiningDividendInfo,
     XMiningAssetApi as XMiningAssetRuntimeApi,
 };
 
@@ -59,6 +59,13 @@ where
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<AssetId, MinerLedger<RpcMiningWeight<MiningWeight>, BlockNumber>>>;
+
+    /// Get the current emission rates of all the assets having the mining power.
+    #[rpc(name = "xminingasset_getAssetEmissionRates")]
+    fn asset_emission_rates(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<BTreeMap<AssetId, AssetEmissionRate<RpcBalance<Balance>>>>;
 }
 
 /// A struct that implements the [`XMiningAssetApi`].
@@ -179,4 +186,29 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn asset_emission_rates(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<AssetId, AssetEmissionRate<RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.asset_emission_rates(&at)
+            .map(|rates| {
+                rates
+                    .into_iter()
+                    .map(|(id, rate)| {
+                        (
+                            id,
+                            AssetEmissionRate {
+                                fixed_power: rate.fixed_power,
+                                share: rate.share,
+                                session_reward: rate.session_reward.into(),
+                            },
+                        )
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
@@ -8,32 +8,43 @@ use super::*;
 
 const SEED: u32 = 0;
 
-benchmarks! {
-    claim {
-        xpallet_assets_registrar::Pallet::<T>::register(
-            frame_system::RawOrigin::Root.into(),
-            X_DOT,
-            xpallet_assets_registrar::AssetInfo::new::<T>(
-                b"X-DOT".to_vec(),
-                b"Polkadot".to_vec(),
-                xpallet_assets_registrar::Chain::Polkadot,
-                10,
-                b"Polkadot".to_vec(),
-            ).unwrap(),
-            true,
-            true,
-        ).unwrap();
-
-        FixedAssetPowerOf::<T>::insert(X_DOT, 100);
+fn register_asset<T: Config>(asset_id: AssetId, has_mining_rights: bool) -> DispatchResult {
+    xpallet_assets_registrar::Pallet::<T>::register(
+        frame_system::RawOrigin::Root.into(),
+        asset_id,
+        xpallet_assets_registrar::AssetInfo::new::<T>(
+            b"X-DOT".to_vec(),
+            b"Polkadot".to_vec(),
+            xpallet_assets_registrar::Chain::Polkadot,
+            10,
+            b"Polkadot".to_vec(),
+        )
+        .unwrap(),
+        true,
+        has_mining_rights,
+    )
+}
 
-        let miner = account("miner", 0, SEED);
-        xpallet_assets::Pallet::<T>::issue(&X_DOT, &miner, 1000u32.into(), true)?;
+/// Registers the mining asset `asset_id` and makes `miner` the only miner of it, with 100
+/// in the reward pot.
+fn setup_mining_asset<T: Config>(asset_id: AssetId, miner: &T::AccountId) -> DispatchResult {
+    register_asset::<T>(asset_id, true)?;
 
-        let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&X_DOT);
-        <T as xpallet_assets::Config>::Currency::make_free_balance_be(&reward_pot, 100u32.into());
-        <T as xpallet_assets::Config>::Currency::issue(100u32.into());
+    FixedAssetPowerOf::<T>::insert(asset_id, 100);
+
+    xpallet_assets::Pallet::<T>::issue(&asset_id, miner, 1000u32.into(), true)?;
+
+    let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&asset_id);
+    <T as xpallet_assets::Config>::Currency::make_free_balance_be(&reward_pot, 100u32.into());
+    <T as xpallet_assets::Config>::Currency::issue(100u32.into());
+
+    Pallet::<T>::set_claim_staking_requirement(RawOrigin::Root.into(), asset_id, 0)
+}
 
-        Pallet::<T>::set_claim_staking_requirement(RawOrigin::Root.into(), X_DOT, 0)?;
+benchmarks! {
+    claim {
+        let miner = account("miner", 0, SEED);
+        setup_mining_asset::<T>(X_DOT, &miner)?;
 
         let block_number: T::BlockNumber = frame_system::Pallet::<T>::block_number();
         frame_system::Pallet::<T>::set_block_number(block_number + 100u32.into());
@@ -60,9 +71,48 @@ benchmarks! {
 
     set_asset_power {
         let c = 1000;
-    }: _(RawOrigin::Root, X_BTC, c)
+        // Lists a new mining asset in the worst case.
+        register_asset::<T>(X_DOT, false)?;
+    }: _(RawOrigin::Root, X_DOT, c)
     verify {
-        assert_eq!(FixedAssetPowerOf::<T>::get(X_BTC), c);
+        assert_eq!(FixedAssetPowerOf::<T>::get(X_DOT), c);
+        assert!(MiningPrevilegedAssets::<T>::get().contains(&X_DOT));
+    }
+
+    claim_all {
+        let n in 1 .. 8;
+
+        let miner = account("miner", 0, SEED);
+        for i in 0..n {
+            setup_mining_asset::<T>(X_DOT + i, &miner)?;
+        }
+
+        let block_number: T::BlockNumber = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(block_number + 100u32.into());
+    }: _(RawOrigin::Signed(miner.clone()))
+    verify {
+        assert!(Pallet::<T>::free_balance(&miner) == (90 * n).into());
+    }
+
+    set_auto_claim_threshold {
+        let miner: T::AccountId = account("miner", 0, SEED);
+    }: _(RawOrigin::Signed(miner.clone()), 100u32.into())
+    verify {
+        assert_eq!(AutoClaimThresholds::<T>::get(&miner), Some(100u32.into()));
+    }
+
+    auto_claim {
+        let miner: T::AccountId = account("miner", 0, SEED);
+        setup_mining_asset::<T>(X_DOT, &miner)?;
+        AutoClaimThresholds::<T>::insert(&miner, BalanceOf::<T>::from(90u32));
+
+        let block_number: T::BlockNumber = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(block_number + 100u32.into());
+    }: {
+        Pallet::<T>::claim_eligible(&miner, 90u32.into());
+    }
+    verify {
+        assert!(Pallet::<T>::free_balance(&miner) == 90u32.into());
     }
 }
 
@@ -80,6 +130,9 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_set_claim_staking_requirement());
             assert_ok!(Pallet::<Test>::test_benchmark_set_claim_frequency_limit());
             assert_ok!(Pallet::<Test>::test_benchmark_set_asset_power());
+            assert_ok!(Pallet::<Test>::test_benchmark_claim_all());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_auto_claim_threshold());
+            assert_ok!(Pallet::<Test>::test_benchmark_auto_claim());
         });
     }
 }
@@ -0,0 +1,96 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Claims the rewards of the miners who opted in to the auto-claim, using no more than
+    /// `remaining_weight`.
+    ///
+    /// The miners are visited in the storage order, resuming from where the previous block
+    /// stopped and starting over once all of them have been visited.
+    pub(crate) fn process_auto_claims(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let claim_weight = T::WeightInfo::auto_claim();
+
+        let assets = Self::mining_previleged_assets().len() as Weight;
+        // Reading the threshold of a miner and computing the dividend of each mining asset.
+        let visit_weight = db_weight.reads(1 + 5 * assets);
+        let miner_weight = visit_weight.saturating_add(claim_weight.saturating_mul(assets));
+
+        // Reading the mining assets, reading and updating the cursor.
+        let mut consumed = db_weight.reads_writes(2, 1);
+        if assets == 0 || consumed.saturating_add(miner_weight) > remaining_weight {
+            return 0;
+        }
+
+        let mut thresholds = match AutoClaimCursor::<T>::get() {
+            Some(cursor) => AutoClaimThresholds::<T>::iter_from(cursor),
+            None => AutoClaimThresholds::<T>::iter(),
+        };
+
+        let max_claims = T::MaxAutoClaims::get();
+        let mut claims = 0u32;
+        let mut cursor = None;
+
+        while claims < max_claims && consumed.saturating_add(miner_weight) <= remaining_weight {
+            let (miner, threshold) = match thresholds.next() {
+                Some(entry) => entry,
+                None => {
+                    cursor = None;
+                    break;
+                }
+            };
+            consumed = consumed.saturating_add(visit_weight);
+            cursor = Some(AutoClaimThresholds::<T>::hashed_key_for(&miner));
+
+            let claimed = Self::claim_eligible(&miner, threshold);
+            if claimed > 0 {
+                consumed = consumed.saturating_add(claim_weight.saturating_mul(claimed.into()));
+                claims += 1;
+            }
+        }
+
+        match cursor {
+            Some(cursor) => AutoClaimCursor::<T>::put(cursor),
+            None => AutoClaimCursor::<T>::kill(),
+        }
+
+        debug!(
+            target: "runtime::mining::asset",
+            "[process_auto_claims] claims:{}, consumed_weight:{}",
+            claims, consumed
+        );
+
+        consumed
+    }
+
+    /// Claims the rewards of `who` of the mining assets whose dividend is non-zero and no
+    /// less than `threshold`, returns the number of the assets claimed.
+    ///
+    /// The assets that can not be claimed, e.g., due to the claim restrictions, are skipped.
+    pub(crate) fn claim_eligible(who: &T::AccountId, threshold: BalanceOf<T>) -> u32 {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+
+        let mut claimed = 0;
+        for asset_id in Self::mining_previleged_assets() {
+            if !MinerLedgers::<T>::contains_key(who, asset_id) {
+                continue;
+            }
+            match Self::compute_dividend_at(who, &asset_id, current_block) {
+                Ok(dividend) if !dividend.is_zero() && dividend >= threshold => {}
+                _ => continue,
+            }
+            if Self::claim_or_rollback(who, &asset_id).is_ok() {
+                claimed += 1;
+            }
+        }
+        claimed
+    }
+
+    /// Claims the reward of `who` for `asset_id`, nothing is changed on failure.
+    #[transactional]
+    fn claim_or_rollback(who: &T::AccountId, asset_id: &AssetId) -> DispatchResult {
+        <Self as Claim<T::AccountId>>::claim(who, asset_id)?;
+        Ok(())
+    }
+}
@@ -169,6 +169,9 @@ impl<T: Config> Pallet<T> {
     /// of total asset mining dividend. The 10% split will be transferred
     /// to the treasury account if the claimer does not have a referral.
     ///
+    /// The split of referral is kept in the referral reward pot of Staking
+    /// until the referral claims it.
+    ///
     /// total_asset_miner_dividend
     ///   ├──> referral(treasury) 10%
     ///   └──> claimer            90%
@@ -179,16 +182,23 @@ impl<T: Config> Pallet<T> {
         dividend: BalanceOf<T>,
     ) -> Result<(), Error<T>> {
         let to_referral_or_treasury = dividend / 10u32.saturated_into();
-        let reward_splitter =
-            T::GatewayInterface::referral_of(claimer, *claimee).unwrap_or_else(|| {
-                <T as Config>::TreasuryAccount::treasury_account()
-                    .expect("TreasuryAccount is some; qed")
-            });
-        Self::transfer(
-            claimee_reward_pot,
-            &reward_splitter,
-            to_referral_or_treasury,
-        )?;
+        match T::GatewayInterface::referral_of(claimer, *claimee) {
+            Some(referral) => match T::StakingInterface::referral_reward_pot() {
+                Some(pot) => {
+                    Self::transfer(claimee_reward_pot, &pot, to_referral_or_treasury)?;
+                    T::StakingInterface::on_referral_reward(
+                        &referral,
+                        to_referral_or_treasury.saturated_into(),
+                    );
+                }
+                None => Self::transfer(claimee_reward_pot, &referral, to_referral_or_treasury)?,
+            },
+            None => {
+                let treasury = <T as Config>::TreasuryAccount::treasury_account()
+                    .expect("TreasuryAccount is some; qed");
+                Self::transfer(claimee_reward_pot, &treasury, to_referral_or_treasury)?;
+            }
+        }
 
         let to_claimer = dividend - to_referral_or_treasury;
         Self::transfer(claimee_reward_pot, claimer, to_claimer)?;
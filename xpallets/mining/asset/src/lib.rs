@@ -5,6 +5,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::type_complexity)]
 
+mod claim;
 mod impls;
 mod rpc;
 mod types;
@@ -22,8 +23,10 @@ use sp_std::prelude::*;
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    log::warn,
-    traits::{Currency, ExistenceRequirement},
+    log::{debug, warn},
+    traits::{Currency, ExistenceRequirement, Get},
+    transactional,
+    weights::Weight,
 };
 use frame_system::{ensure_root, ensure_signed};
 use sp_runtime::traits::{SaturatedConversion, Zero};
@@ -43,6 +46,9 @@ pub use self::weights::WeightInfo;
 
 pub use pallet::*;
 
+/// The maximum number of the mining power changes kept in the history of each asset.
+const MAX_ASSET_POWER_HISTORY: usize = 32;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -66,6 +72,13 @@ pub mod pallet {
         /// Generate the reward pot account for mining asset.
         type DetermineRewardPotAccount: RewardPotAccountFor<Self::AccountId, AssetId>;
 
+        /// The origin which may set the mining power of the assets, in addition to Root.
+        type CommitteeOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The maximum number of miners whose rewards are auto-claimed in the idle time of a block.
+        #[pallet::constant]
+        type MaxAutoClaims: Get<u32>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -74,6 +87,13 @@ pub mod pallet {
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::process_auto_claims(remaining_weight)
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Claims the staking reward given the `target` validator.
@@ -91,6 +111,41 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Claims the mining rewards of all the mining assets in one call.
+        ///
+        /// The assets that can not be claimed at the moment, e.g., due to the claim
+        /// restrictions or no reward accrued yet, are skipped.
+        #[pallet::weight(<T as Config>::WeightInfo::claim_all(
+            Pallet::<T>::mining_previleged_assets().len() as u32
+        ))]
+        pub fn claim_all(origin: OriginFor<T>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let claimed = Self::claim_eligible(&sender, Zero::zero());
+            ensure!(claimed > 0, Error::<T>::NothingToClaim);
+
+            Ok(())
+        }
+
+        /// Opts in to the auto-claim of the mining rewards, the reward of an asset is claimed
+        /// in the idle time of the blocks once it reaches `threshold`. Zero opts out.
+        #[pallet::weight(<T as Config>::WeightInfo::set_auto_claim_threshold())]
+        pub fn set_auto_claim_threshold(
+            origin: OriginFor<T>,
+            #[pallet::compact] threshold: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            if threshold.is_zero() {
+                AutoClaimThresholds::<T>::remove(&sender);
+            } else {
+                AutoClaimThresholds::<T>::insert(&sender, threshold);
+            }
+
+            Self::deposit_event(Event::<T>::AutoClaimThresholdSet(sender, threshold));
+            Ok(())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::set_claim_staking_requirement())]
         pub fn set_claim_staking_requirement(
             origin: OriginFor<T>,
@@ -117,15 +172,21 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Sets the mining power of per unit of `asset_id`.
+        ///
+        /// An asset without the mining rights yet is listed into the mining assets once it's
+        /// given a non-zero power, so that a new asset can start mining without a runtime upgrade.
         #[pallet::weight(<T as Config>::WeightInfo::set_asset_power())]
         pub fn set_asset_power(
             origin: OriginFor<T>,
             #[pallet::compact] asset_id: AssetId,
             #[pallet::compact] new: FixedAssetPower,
         ) -> DispatchResult {
-            ensure_root(origin)?;
-            FixedAssetPowerOf::<T>::insert(asset_id, new);
-            Ok(())
+            T::CommitteeOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            Self::apply_set_asset_power(asset_id, new)
         }
     }
 
@@ -136,6 +197,10 @@ pub mod pallet {
         Claimed(T::AccountId, AssetId, BalanceOf<T>),
         /// Issue new balance to the reward pot. [reward_pot_account, amount]
         Minted(T::AccountId, BalanceOf<T>),
+        /// An asset miner set the auto-claim threshold, zero means opted out. [who, threshold]
+        AutoClaimThresholdSet(T::AccountId, BalanceOf<T>),
+        /// The mining power of an asset was set. [asset_id, fixed_power]
+        AssetPowerSet(AssetId, FixedAssetPower),
     }
 
     /// Old name generated by `decl_event`.
@@ -155,6 +220,10 @@ pub mod pallet {
         ZeroMiningWeight,
         /// Balances error.
         DispatchError,
+        /// None of the mining assets has any reward that can be claimed now.
+        NothingToClaim,
+        /// The asset is the native asset or not a valid asset.
+        InvalidAsset,
     }
 
     #[pallet::type_value]
@@ -204,6 +273,22 @@ pub mod pallet {
     pub type FixedAssetPowerOf<T: Config> =
         StorageMap<_, Twox64Concat, AssetId, FixedAssetPower, ValueQuery>;
 
+    /// The latest changes of the mining power of each asset, oldest first.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_power_history)]
+    pub type AssetPowerHistory<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, Vec<(T::BlockNumber, FixedAssetPower)>, ValueQuery>;
+
+    /// The auto-claim threshold of each asset miner who opted in.
+    #[pallet::storage]
+    #[pallet::getter(fn auto_claim_threshold_of)]
+    pub type AutoClaimThresholds<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>>;
+
+    /// The raw key of `AutoClaimThresholds` after which the next auto-claim resumes.
+    #[pallet::storage]
+    pub(super) type AutoClaimCursor<T: Config> = StorageValue<_, Vec<u8>>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub claim_restrictions: Vec<(AssetId, (StakingRequirement, T::BlockNumber))>,
@@ -236,6 +321,10 @@ pub mod pallet {
                 }
                 for (asset_id, fixed_power) in &config.mining_power_map {
                     FixedAssetPowerOf::<T>::insert(asset_id, fixed_power);
+                    AssetPowerHistory::<T>::insert(
+                        asset_id,
+                        vec![(T::BlockNumber::zero(), *fixed_power)],
+                    );
                 }
             };
             extra_genesis_builder(self);
@@ -245,12 +334,28 @@ pub mod pallet {
     pub trait StakingInterface<AccountId, Balance> {
         /// Returns the amount of `who`s locked balances in Staking.
         fn staked_of(who: &AccountId) -> Balance;
+
+        /// Returns the account keeping the referral rewards until they are claimed, the
+        /// rewards are transferred to the referral directly if `None`.
+        fn referral_reward_pot() -> Option<AccountId> {
+            None
+        }
+
+        /// Records `value` transferred to the referral reward pot for `referral`.
+        fn on_referral_reward(_referral: &AccountId, _value: Balance) {}
+
+        /// Returns the reward minted to all the mining assets in total per session at present.
+        fn asset_mining_session_reward() -> Balance;
     }
 
     impl<AccountId, Balance: Default> StakingInterface<AccountId, Balance> for () {
         fn staked_of(_: &AccountId) -> Balance {
             Default::default()
         }
+
+        fn asset_mining_session_reward() -> Balance {
+            Default::default()
+        }
     }
 
     impl<T: Config> StakingInterface<<T as frame_system::Config>::AccountId, u128> for T
@@ -260,6 +365,21 @@ pub mod pallet {
         fn staked_of(who: &<T as frame_system::Config>::AccountId) -> u128 {
             xpallet_mining_staking::Pallet::<T>::staked_of(who).saturated_into()
         }
+
+        fn referral_reward_pot() -> Option<<T as frame_system::Config>::AccountId> {
+            Some(xpallet_mining_staking::Pallet::<T>::referral_reward_pot())
+        }
+
+        fn on_referral_reward(referral: &<T as frame_system::Config>::AccountId, value: u128) {
+            xpallet_mining_staking::Pallet::<T>::on_referral_reward(
+                referral,
+                value.saturated_into(),
+            );
+        }
+
+        fn asset_mining_session_reward() -> u128 {
+            xpallet_mining_staking::Pallet::<T>::asset_mining_session_reward().saturated_into()
+        }
     }
 
     pub trait GatewayInterface<AccountId> {
@@ -375,6 +495,41 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    fn apply_set_asset_power(asset_id: AssetId, new: FixedAssetPower) -> DispatchResult {
+        ensure!(
+            asset_id != <T as xpallet_assets_registrar::Config>::NativeAssetId::get()
+                && xpallet_assets_registrar::Pallet::<T>::is_valid(&asset_id),
+            Error::<T>::InvalidAsset
+        );
+
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        if !new.is_zero() && !Self::mining_previleged_assets().contains(&asset_id) {
+            MiningPrevilegedAssets::<T>::mutate(|assets| assets.push(asset_id));
+            // The ledger of an asset issued before is kept as the miners' ledgers have been
+            // accumulating the mining weight along with it.
+            if !AssetLedgers::<T>::contains_key(asset_id) {
+                AssetLedgers::<T>::insert(
+                    asset_id,
+                    AssetLedger {
+                        last_total_mining_weight_update: current_block,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        FixedAssetPowerOf::<T>::insert(asset_id, new);
+        AssetPowerHistory::<T>::mutate(asset_id, |history| {
+            if history.len() >= MAX_ASSET_POWER_HISTORY {
+                history.remove(0);
+            }
+            history.push((current_block, new));
+        });
+
+        Self::deposit_event(Event::<T>::AssetPowerSet(asset_id, new));
+        Ok(())
+    }
+
     fn init_receiver_mining_ledger(
         who: &T::AccountId,
         asset_id: &AssetId,
@@ -123,6 +123,7 @@ impl xpallet_assets_registrar::Config for Test {
     type Event = Event;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = XMiningAsset;
+    type TechnicalOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -132,6 +133,9 @@ impl xpallet_assets::Config for Test {
     type TreasuryAccount = ();
     type OnCreatedAccount = frame_system::Provider<Test>;
     type OnAssetChanged = XMiningAsset;
+    type RestrictionOrigin = frame_system::EnsureRoot<AccountId>;
+    type DustReapBounty = ();
+    type DustReapExempt = frame_support::traits::Nothing;
     type WeightInfo = ();
 }
 
@@ -228,6 +232,9 @@ parameter_types! {
     pub const SessionDuration: BlockNumber = 50;
     pub const MinimumReferralId: u32 = 2;
     pub const MaximumReferralId: u32 = 12;
+    pub const PerformanceHistoryDepth: u32 = 3;
+    pub const MaxAutoPayouts: u32 = 2;
+    pub const SlashDeferDuration: SessionIndex = 2;
 }
 
 pub struct DummyStakingRewardPotAccountDeterminer;
@@ -258,6 +265,12 @@ impl xpallet_mining_staking::Config for Test {
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
     type ValidatorRegistration = Registration;
+    type PerformanceHistoryDepth = PerformanceHistoryDepth;
+    type MaxAutoPayouts = MaxAutoPayouts;
+    type SlashDeferDuration = SlashDeferDuration;
+    type SlashCancelOrigin = frame_system::EnsureRoot<AccountId>;
+    type EvmStakingOrigin = frame_system::EnsureRoot<AccountId>;
+    type ParametersOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -279,12 +292,18 @@ impl GatewayInterface<AccountId> for DummyGatewayReferralGetter {
     }
 }
 
+parameter_types! {
+    pub const MaxAutoClaims: u32 = 2;
+}
+
 impl Config for Test {
     type StakingInterface = Self;
     type GatewayInterface = DummyGatewayReferralGetter;
     type Event = Event;
     type TreasuryAccount = ();
     type DetermineRewardPotAccount = DummyAssetRewardPotAccountDeterminer;
+    type CommitteeOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxAutoClaims = MaxAutoClaims;
     type WeightInfo = ();
 }
 
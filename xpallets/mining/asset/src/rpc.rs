@@ -6,14 +6,17 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use sp_runtime::{RuntimeDebug, SaturatedConversion};
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational, Perbill, RuntimeDebug, SaturatedConversion,
+};
 
 use chainx_primitives::AssetId;
 use xp_mining_common::RewardPotAccountFor;
+use xp_mining_staking::{AssetMining, MiningPower};
 
 use crate::{
     types::*, AssetLedgers, BalanceOf, ClaimRestrictionOf, Config, FixedAssetPowerOf, MinerLedgers,
-    MiningPrevilegedAssets, Pallet,
+    MiningPrevilegedAssets, Pallet, StakingInterface,
 };
 
 /// Mining asset info.
@@ -42,6 +45,19 @@ pub struct MiningDividendInfo<Balance> {
     pub insufficient_stake: Balance,
 }
 
+/// Current emission rate of a mining asset.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetEmissionRate<Balance> {
+    /// Mining power of per unit of the asset.
+    pub fixed_power: FixedAssetPower,
+    /// Proportion of the asset in the total asset mining power.
+    pub share: Perbill,
+    /// Reward minted to the reward pot of the asset per session at present.
+    pub session_reward: Balance,
+}
+
 impl<T: Config> Pallet<T> {
     /// Get overall information about all mining assets.
     pub fn mining_assets(
@@ -104,4 +120,33 @@ impl<T: Config> Pallet<T> {
     ) -> BTreeMap<AssetId, MinerLedger<MiningWeight, T::BlockNumber>> {
         MinerLedgers::<T>::iter_prefix(&who).collect()
     }
+
+    /// Get the current emission rates of all the assets having the mining power.
+    pub fn asset_emission_rates() -> BTreeMap<AssetId, AssetEmissionRate<BalanceOf<T>>> {
+        let total_reward: u128 = T::StakingInterface::asset_mining_session_reward();
+        let asset_mining_power = <Self as AssetMining<BalanceOf<T>>>::asset_mining_power();
+        let total_power: MiningPower = asset_mining_power.iter().map(|(_, power)| power).sum();
+
+        asset_mining_power
+            .into_iter()
+            .map(|(asset_id, power)| {
+                let (share, session_reward) = if total_power == 0 {
+                    (Perbill::default(), 0)
+                } else {
+                    (
+                        Perbill::from_rational(power, total_power),
+                        multiply_by_rational(total_reward, power, total_power).unwrap_or_default(),
+                    )
+                };
+                (
+                    asset_id,
+                    AssetEmissionRate {
+                        fixed_power: FixedAssetPowerOf::<T>::get(asset_id),
+                        share,
+                        session_reward: session_reward.saturated_into(),
+                    },
+                )
+            })
+            .collect()
+    }
 }
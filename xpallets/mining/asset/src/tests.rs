@@ -2,12 +2,14 @@
 
 use frame_support::{
     assert_err, assert_ok,
-    traits::{Get, OnInitialize},
+    traits::{Get, OnIdle, OnInitialize},
 };
 use frame_system::RawOrigin;
+use sp_runtime::Perbill;
 
 use xp_mining_staking::SessionIndex;
-use xp_protocol::X_BTC;
+use xp_protocol::{X_BTC, X_DOT};
+use xpallet_assets::{AssetInfo, Chain};
 
 use super::*;
 use crate::mock::*;
@@ -452,7 +454,171 @@ fn asset_mining_reward_should_work() {
         );
         let referral =
             DummyGatewayReferralGetter::referral_of(&t_1, X_BTC).unwrap_or(TREASURY_ACCOUNT);
-        assert_eq!(Balances::free_balance(&referral), xbtc_pot_balance / 10);
+        let referral_reward = xbtc_pot_balance / 10;
+        assert_eq!(Balances::free_balance(&referral), 0);
+        assert_eq!(
+            Balances::free_balance(&XStaking::referral_reward_pot()),
+            referral_reward
+        );
+        assert_eq!(
+            XStaking::referral_rewards_of(&referral).unclaimed,
+            referral_reward
+        );
         assert_eq!(Balances::free_balance(&TREASURY_ACCOUNT), treasury_balance);
+
+        assert_ok!(XStaking::claim_referral_rewards(Origin::signed(referral)));
+        assert_eq!(Balances::free_balance(&referral), referral_reward);
+        assert_eq!(
+            XStaking::referral_rewards_of(&referral),
+            xpallet_mining_staking::ReferralRewardLedger {
+                unclaimed: 0,
+                claimed: referral_reward,
+            }
+        );
+    });
+}
+
+/// Sets up `t_1` as the only X-BTC miner, returns the balance of the X-BTC reward pot.
+fn t_setup_xbtc_miner(t_1: AccountId) -> Balance {
+    assert_ok!(t_register_xbtc());
+    assert_ok!(t_issue_xbtc(t_1, 1));
+    t_issue_pcx(4, 1000);
+    t_set_xbtc_asset_power(100);
+    assert_ok!(t_bond(4, 4, 800));
+    t_xbtc_set_claim_staking_requirement(0);
+
+    t_start_session(1);
+    Balances::free_balance(&DummyAssetRewardPotAccountDeterminer::reward_pot_account_for(&X_BTC))
+}
+
+#[test]
+fn claim_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 666_666;
+        let xbtc_pot_balance = t_setup_xbtc_miner(t_1);
+        assert!(xbtc_pot_balance > 0);
+
+        // Not a miner of any mining asset.
+        assert_err!(
+            XMiningAsset::claim_all(Origin::signed(777)),
+            Error::<Test>::NothingToClaim
+        );
+
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1)));
+        assert_eq!(
+            Balances::free_balance(&t_1),
+            xbtc_pot_balance - xbtc_pot_balance / 10
+        );
+
+        // Restricted by the claim frequency limit.
+        t_start_session(2);
+        assert_err!(
+            XMiningAsset::claim_all(Origin::signed(t_1)),
+            Error::<Test>::NothingToClaim
+        );
+    });
+}
+
+#[test]
+fn auto_claim_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 666_666;
+        let xbtc_pot_balance = t_setup_xbtc_miner(t_1);
+
+        assert_ok!(XMiningAsset::set_auto_claim_threshold(
+            Origin::signed(t_1),
+            xbtc_pot_balance + 1
+        ));
+        assert_eq!(
+            XMiningAsset::auto_claim_threshold_of(t_1),
+            Some(xbtc_pot_balance + 1)
+        );
+
+        // The accrued reward is below the threshold.
+        XMiningAsset::on_idle(System::block_number(), Weight::max_value());
+        assert_eq!(Balances::free_balance(&t_1), 0);
+
+        assert_ok!(XMiningAsset::set_auto_claim_threshold(
+            Origin::signed(t_1),
+            xbtc_pot_balance
+        ));
+        // No auto-claim without enough idle weight.
+        assert_eq!(XMiningAsset::on_idle(System::block_number(), 0), 0);
+        assert_eq!(Balances::free_balance(&t_1), 0);
+
+        assert!(XMiningAsset::on_idle(System::block_number(), Weight::max_value()) > 0);
+        assert_eq!(
+            Balances::free_balance(&t_1),
+            xbtc_pot_balance - xbtc_pot_balance / 10
+        );
+
+        // Opt out.
+        assert_ok!(XMiningAsset::set_auto_claim_threshold(
+            Origin::signed(t_1),
+            0
+        ));
+        assert_eq!(XMiningAsset::auto_claim_threshold_of(t_1), None);
+    });
+}
+
+#[test]
+fn set_asset_power_should_list_new_mining_asset() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 666_666;
+        t_setup_xbtc_miner(t_1);
+
+        let dot_info = AssetInfo::new::<Test>(
+            b"X-DOT".to_vec(),
+            b"Polkadot".to_vec(),
+            Chain::Polkadot,
+            10,
+            b"Polkadot".to_vec(),
+        )
+        .unwrap();
+        assert_ok!(XAssetsRegistrar::register(
+            Origin::root(),
+            X_DOT,
+            dot_info,
+            true,
+            false
+        ));
+        assert_ok!(XAssets::issue(&X_DOT, &t_1, 1, true));
+        assert!(!XMiningAsset::mining_previleged_assets().contains(&X_DOT));
+
+        assert_err!(
+            XMiningAsset::set_asset_power(Origin::signed(t_1), X_DOT, 300),
+            DispatchError::BadOrigin
+        );
+        assert_err!(
+            XMiningAsset::set_asset_power(Origin::root(), xp_protocol::PCX, 300),
+            Error::<Test>::InvalidAsset
+        );
+        assert_err!(
+            XMiningAsset::set_asset_power(Origin::root(), 9999, 300),
+            Error::<Test>::InvalidAsset
+        );
+
+        let now = System::block_number();
+        assert_ok!(XMiningAsset::set_asset_power(Origin::root(), X_DOT, 300));
+        assert!(XMiningAsset::mining_previleged_assets().contains(&X_DOT));
+        assert_eq!(XMiningAsset::asset_power_history(X_DOT), vec![(now, 300)]);
+        let xbtc_history = XMiningAsset::asset_power_history(X_BTC);
+        assert_eq!(xbtc_history.len(), 2);
+        assert_eq!(xbtc_history[0], (0, 400));
+        assert_eq!(xbtc_history[1].1, 100);
+
+        let rates = XMiningAsset::asset_emission_rates();
+        let (xbtc_rate, xdot_rate) = (&rates[&X_BTC], &rates[&X_DOT]);
+        assert_eq!(xbtc_rate.fixed_power, 100);
+        assert_eq!(xbtc_rate.share, Perbill::from_percent(25));
+        assert_eq!(xdot_rate.fixed_power, 300);
+        assert_eq!(xdot_rate.share, Perbill::from_percent(75));
+        assert!(xdot_rate.session_reward > xbtc_rate.session_reward);
+
+        // The newly listed asset is rewarded from the next session.
+        let xdot_pot = DummyAssetRewardPotAccountDeterminer::reward_pot_account_for(&X_DOT);
+        assert_eq!(Balances::free_balance(&xdot_pot), 0);
+        t_start_session(2);
+        assert!(Balances::free_balance(&xdot_pot) > 0);
     });
 }
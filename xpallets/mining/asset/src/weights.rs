@@ -35,15 +35,18 @@ pub trait WeightInfo {
     fn set_claim_staking_requirement() -> Weight;
     fn set_claim_frequency_limit() -> Weight;
     fn set_asset_power() -> Weight;
+    fn claim_all(n: u32) -> Weight;
+    fn set_auto_claim_threshold() -> Weight;
+    fn auto_claim() -> Weight;
 }
 
 /// Weights for xpallet_mining_asset using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn claim() -> Weight {
-        (184_016_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(12 as Weight))
-            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+        (189_352_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
     fn set_claim_staking_requirement() -> Weight {
         (7_224_000 as Weight)
@@ -56,16 +59,33 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
     fn set_asset_power() -> Weight {
-        (3_043_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+        (21_386_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn claim_all(n: u32) -> Weight {
+        (9_418_000 as Weight)
+            .saturating_add((186_790_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+    }
+    fn set_auto_claim_threshold() -> Weight {
+        (11_862_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn auto_claim() -> Weight {
+        (192_105_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
     fn claim() -> Weight {
-        (184_016_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+        (189_352_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
     fn set_claim_staking_requirement() -> Weight {
         (7_224_000 as Weight)
@@ -78,6 +98,23 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
     fn set_asset_power() -> Weight {
-        (3_043_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+        (21_386_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn claim_all(n: u32) -> Weight {
+        (9_418_000 as Weight)
+            .saturating_add((186_790_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+    }
+    fn set_auto_claim_threshold() -> Weight {
+        (11_862_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn auto_claim() -> Weight {
+        (192_105_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
 }
@@ -10,7 +10,8 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use codec::Codec;
 
 pub use xpallet_mining_staking::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger, VoteWeight,
+    NextElectionInfo, NominatorInfo, NominatorLedger, ReferralEarnings, ReferralRewardLedger,
+    Unbonded, ValidatorInfo, ValidatorLedger, ValidatorPerformanceInfo, VoteWeight,
 };
 
 sp_api::decl_runtime_apis! {
@@ -36,5 +37,14 @@ sp_api::decl_runtime_apis! {
 
         /// Get individual nominator information given the nominator AccountId.
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber>;
+
+        /// Get the performance of the current and recent eras given the validator AccountId.
+        fn validator_performance_of(who: AccountId) -> ValidatorPerformanceInfo;
+
+        /// Get the prospect of the validator in the next election.
+        fn next_election_of(who: AccountId) -> NextElectionInfo;
+
+        /// Get the referral rewards from Asset Mining given the referral id.
+        fn referral_earnings(referral_id: Vec<u8>) -> Option<ReferralEarnings<AccountId, Balance>>;
     }
 }
@@ -17,7 +17,8 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcVoteWeight};
 
 use xpallet_mining_staking_rpc_runtime_api::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger,
+    NextElectionInfo, NominatorInfo, NominatorLedger, ReferralEarnings, ReferralRewardLedger,
+    Unbonded, ValidatorInfo, ValidatorLedger, ValidatorPerformanceInfo,
     XStakingApi as XStakingRuntimeApi,
 };
 
@@ -74,6 +75,26 @@ where
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Result<NominatorInfo<BlockNumber>>;
+
+    /// Get the performance of the current and recent eras given the validator AccountId.
+    #[rpc(name = "xstaking_getValidatorPerformance")]
+    fn validator_performance_of(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<ValidatorPerformanceInfo>;
+
+    /// Get whether the validator given the AccountId will be in the next validator set.
+    #[rpc(name = "xstaking_nextElection")]
+    fn next_election_of(&self, who: AccountId, at: Option<BlockHash>) -> Result<NextElectionInfo>;
+
+    /// Get the referral rewards from Asset Mining given the referral id, e.g., `Web3`.
+    #[rpc(name = "xstaking_referralEarnings")]
+    fn referral_earnings(
+        &self,
+        referral_id: String,
+        at: Option<BlockHash>,
+    ) -> Result<Option<ReferralEarnings<AccountId, RpcBalance<Balance>>>>;
 }
 
 /// A struct that implements the [`XStakingApi`].
@@ -227,4 +248,46 @@ where
         api.nominator_info_of(&at, who)
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn validator_performance_of(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<ValidatorPerformanceInfo> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.validator_performance_of(&at, who)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn next_election_of(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<NextElectionInfo> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.next_election_of(&at, who)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn referral_earnings(
+        &self,
+        referral_id: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<ReferralEarnings<AccountId, RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.referral_earnings(&at, referral_id.into_bytes())
+            .map(|earnings| {
+                earnings.map(|earnings| ReferralEarnings {
+                    account: earnings.account,
+                    rewards: ReferralRewardLedger {
+                        unclaimed: earnings.rewards.unclaimed.into(),
+                        claimed: earnings.rewards.claimed.into(),
+                    },
+                })
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
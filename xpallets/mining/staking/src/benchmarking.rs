@@ -3,6 +3,7 @@
 use super::*;
 
 pub use frame_benchmarking::{account, benchmarks};
+use frame_support::weights::Weight;
 use frame_system::RawOrigin;
 
 const SEED: u32 = 0;
@@ -117,6 +118,63 @@ benchmarks! {
         assert!(T::Currency::total_balance(&validator) == balance_before + pot_balance.into());
     }
 
+    claim_referral_rewards {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+
+        let reward = 50u32;
+        T::Currency::make_free_balance_be(&Pallet::<T>::referral_reward_pot(), reward.into());
+        T::Currency::issue(reward.into());
+        Pallet::<T>::on_referral_reward(&validator, reward.into());
+
+        let balance_before = T::Currency::free_balance(&validator);
+    }: _(RawOrigin::Signed(validator.clone()))
+    verify {
+        assert!(T::Currency::total_balance(&validator) == balance_before + reward.into());
+        assert!(Pallet::<T>::referral_rewards_of(&validator).claimed == reward.into());
+    }
+
+    set_payout_preference {
+        let nominator = create_funded_user::<T>("nominator", 0, 100);
+    }: _(RawOrigin::Signed(nominator.clone()), PayoutPreference::Compound)
+    verify {
+        assert_eq!(PayoutPreferences::<T>::get(&nominator), PayoutPreference::Compound);
+    }
+
+    auto_payout {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        let nominator = create_funded_user::<T>("nominator", 0, 100);
+        b_bond::<T>(nominator.clone(), validator.clone(), 10);
+        PayoutPreferences::<T>::insert(&nominator, PayoutPreference::Compound);
+
+        let validator_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&validator);
+        let pot_balance = 100_000u32;
+        T::Currency::make_free_balance_be(&validator_pot, pot_balance.into());
+        T::Currency::issue(pot_balance.into());
+
+        let block_number: T::BlockNumber = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(block_number + 1u32.into());
+        let bonded_before = Nominations::<T>::get(&nominator, &validator).nomination;
+    }: {
+        Pallet::<T>::process_auto_payouts(Weight::max_value());
+    }
+    verify {
+        assert!(Nominations::<T>::get(&nominator, &validator).nomination > bonded_before);
+    }
+
+    cancel_deferred_slash {
+        let n in 1 .. 100;
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        Pallet::<T>::defer_equivocation_slashes(
+            (0..n).map(|_| (validator.clone(), Perbill::from_percent(10))).collect(),
+            0,
+            0,
+        );
+        let slash_ids = (0..n).collect::<Vec<_>>();
+    }: _(RawOrigin::Root, slash_ids)
+    verify {
+        assert_eq!(DeferredSlashes::<T>::iter().count(), 0);
+    }
+
     chill {
         let validator1: T::AccountId = create_validator::<T>("validator", 1, 1000);
         let validator2: T::AccountId = create_validator::<T>("validator", 2, 1000);
@@ -181,6 +239,51 @@ benchmarks! {
     verify {
         assert_eq!(SessionsPerEra::<T>::get(), c);
     }
+
+    set_performance_alert_threshold {
+        let threshold = Perbill::from_percent(50);
+    }: _(RawOrigin::Root, threshold)
+    verify {
+        assert_eq!(PerformanceAlertThreshold::<T>::get(), threshold);
+    }
+
+    set_backup {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        let old_backup: T::AccountId = account("backup", 0, SEED);
+        let backup: T::AccountId = account("backup", 1, SEED);
+        Pallet::<T>::set_backup(RawOrigin::Signed(validator.clone()).into(), Some(old_backup))?;
+    }: _(RawOrigin::Signed(validator.clone()), Some(backup.clone()))
+    verify {
+        assert_eq!(BackupOf::<T>::get(&validator), Some(backup));
+    }
+
+    resume_primary {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        FailedOverAt::<T>::insert(&validator, 1);
+    }: _(RawOrigin::Signed(validator.clone()))
+    verify {
+        assert!(!FailedOverAt::<T>::contains_key(&validator));
+    }
+
+    set_evm_staking_allowlist {
+    }: _(RawOrigin::Root, true)
+    verify {
+        assert!(EvmStakingAllowlistEnabled::<T>::get());
+    }
+
+    set_evm_staker {
+        let contract = H160::repeat_byte(1);
+    }: _(RawOrigin::Root, contract, true)
+    verify {
+        assert!(EvmStakers::<T>::contains_key(contract));
+    }
+
+    schedule_parameter_change {
+        let change = ParameterChange::ValidatorCount(MaximumValidatorCount::<T>::get());
+    }: _(RawOrigin::Root, change)
+    verify {
+        assert_eq!(PendingParameterChanges::<T>::get(), vec![change]);
+    }
 }
 
 #[cfg(test)]
@@ -198,12 +301,22 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_unlock_unbonded_withdrawal());
             assert_ok!(Pallet::<Test>::test_benchmark_rebond());
             assert_ok!(Pallet::<Test>::test_benchmark_claim());
+            assert_ok!(Pallet::<Test>::test_benchmark_claim_referral_rewards());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_payout_preference());
+            assert_ok!(Pallet::<Test>::test_benchmark_auto_payout());
+            assert_ok!(Pallet::<Test>::test_benchmark_cancel_deferred_slash());
             assert_ok!(Pallet::<Test>::test_benchmark_chill());
             assert_ok!(Pallet::<Test>::test_benchmark_validate());
             assert_ok!(Pallet::<Test>::test_benchmark_set_validator_count());
             assert_ok!(Pallet::<Test>::test_benchmark_set_minimum_validator_count());
             assert_ok!(Pallet::<Test>::test_benchmark_set_bonding_duration());
             assert_ok!(Pallet::<Test>::test_benchmark_set_validator_bonding_duration());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_performance_alert_threshold());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_backup());
+            assert_ok!(Pallet::<Test>::test_benchmark_resume_primary());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_evm_staking_allowlist());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_evm_staker());
+            assert_ok!(Pallet::<Test>::test_benchmark_schedule_parameter_change());
         });
     }
 }
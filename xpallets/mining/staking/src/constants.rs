@@ -1,9 +1,15 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::traits::LockIdentifier;
+use frame_support::{traits::LockIdentifier, PalletId};
+use sp_runtime::Perbill;
+
+use xp_mining_staking::SessionIndex;
 
 pub const STAKING_ID: LockIdentifier = *b"staking ";
 
+/// The pot holding the referral rewards from Asset Mining until they are claimed.
+pub const REFERRAL_REWARD_POT_ID: PalletId = PalletId(*b"pcx/refr");
+
 /// Session reward of the first 210_000 sessions.
 ///
 /// ChainX uses a Bitcoin like issuance model, the initial reward is 50 PCX.
@@ -36,3 +42,16 @@ pub const DEFAULT_BONDING_DURATION: u64 = DEFAULT_BLOCKS_PER_SESSION * 12 * 24 *
 
 /// The default bonding duration for validator is 3 * 10 days.
 pub const DEFAULT_VALIDATOR_BONDING_DURATION: u64 = DEFAULT_BONDING_DURATION * 10;
+
+/// The bounds of the era length in sessions that can be scheduled, i.e., 5 minutes to 1 day.
+pub const MINIMUM_SESSIONS_PER_ERA: SessionIndex = 1;
+pub const MAXIMUM_SESSIONS_PER_ERA: SessionIndex = 288;
+
+/// The maximum sum of the shares in a distribution ratio that can be scheduled.
+pub const MAXIMUM_DISTRIBUTION_SHARES: u32 = 10_000;
+
+/// The maximum proportion of the session reward to the treasury that can be scheduled.
+pub const MAXIMUM_TREASURY_PROPORTION: Perbill = Perbill::from_parts(500_000_000);
+
+/// The maximum proportion of the mining reward to Asset Mining that can be scheduled.
+pub const MAXIMUM_ASSET_MINING_PROPORTION: Perbill = Perbill::from_parts(500_000_000);
@@ -14,6 +14,9 @@ impl<T: Config> Pallet<T> {
         });
         ErasStartSessionIndex::<T>::insert(&current_era, &start_session_index);
 
+        // The scheduled parameters, e.g., the validator count, take effect in this election.
+        Self::apply_parameter_changes(current_era);
+
         // Set staking information for new era.
         let maybe_new_validators = Self::select_and_update_validators(current_era);
         debug!(
@@ -22,9 +25,46 @@ impl<T: Config> Pallet<T> {
             current_era, start_session_index, maybe_new_validators
         );
 
+        Self::rejoin_rotated_validators(current_era);
+
         maybe_new_validators
     }
 
+    /// Lets the validators that sat out the election of `current_era` after rotating
+    /// the session keys join in the elections again.
+    fn rejoin_rotated_validators(current_era: EraIndex) {
+        let due = RejoinEraOf::<T>::iter()
+            .filter(|(_, rejoin_era)| *rejoin_era <= current_era)
+            .map(|(validator, _)| validator)
+            .collect::<Vec<_>>();
+        for validator in due {
+            RejoinEraOf::<T>::remove(&validator);
+            Validators::<T>::mutate(&validator, |profile| {
+                profile.is_chilled = false;
+            });
+            Self::deposit_event(Event::<T>::Rejoined(validator, current_era));
+        }
+    }
+
+    /// Returns the backup of the elected `validator` in its place if the validator has
+    /// failed over and the backup has set the session keys.
+    fn backup_or_self(validator: T::AccountId, current_era: EraIndex) -> T::AccountId {
+        if !FailedOverAt::<T>::contains_key(&validator) {
+            return validator;
+        }
+        match Self::backup_of(&validator) {
+            Some(backup) if T::ValidatorRegistration::is_registered(&backup) => {
+                Self::deposit_event(Event::<T>::BackupElected(
+                    validator,
+                    backup.clone(),
+                    current_era,
+                ));
+                backup
+            }
+            _ => validator,
+        }
+    }
+
     /// Returns true if the (potential) validator is able to join in the election.
     ///
     /// Three requirements:
@@ -37,13 +77,23 @@ impl<T: Config> Pallet<T> {
             && T::ValidatorRegistration::is_registered(who)
     }
 
+    /// Same as `is_qualified_candidate` but never forces the candidate to be chilled.
+    pub(crate) fn is_eligible_candidate(who: &T::AccountId) -> bool {
+        Self::is_active(who)
+            && Self::satisfies_candidate_threshold(who)
+            && T::ValidatorRegistration::is_registered(who)
+    }
+
+    fn satisfies_candidate_threshold(who: &T::AccountId) -> bool {
+        let BondRequirement { self_bonded, total } = Self::validator_candidate_requirement();
+        Self::validator_self_bonded(who) >= self_bonded && Self::total_votes_of(who) >= total
+    }
+
     /// Returns true if the candidate meets the minimum candidate threshold.
     ///
     /// Otherwise the candidate will be **forced to be chilled**.
     fn meet_candidate_threshold(who: &T::AccountId) -> bool {
-        let BondRequirement { self_bonded, total } = Self::validator_candidate_requirement();
-        let threshold_satisfied =
-            Self::validator_self_bonded(who) >= self_bonded && Self::total_votes_of(who) >= total;
+        let threshold_satisfied = Self::satisfies_candidate_threshold(who);
 
         if !threshold_satisfied && Self::try_force_chilled(who).is_ok() {
             log::info!(
@@ -57,8 +107,14 @@ impl<T: Config> Pallet<T> {
 
     /// Filters out all the qualified validator candidates, sorted by the total nominations.
     fn filter_out_candidates() -> Vec<(BalanceOf<T>, T::AccountId)> {
-        let mut candidates = Self::validator_set()
-            .filter(Self::is_qualified_candidate)
+        Self::sort_candidates(Self::validator_set().filter(Self::is_qualified_candidate))
+    }
+
+    /// Sorts the candidates by the total nominations in descending order.
+    pub(crate) fn sort_candidates(
+        candidates: impl Iterator<Item = T::AccountId>,
+    ) -> Vec<(BalanceOf<T>, T::AccountId)> {
+        let mut candidates = candidates
             .map(|v| (Self::total_votes_of(&v), v))
             .collect::<Vec<_>>();
         candidates.sort_by(|&(ref b1, _), &(ref b2, _)| b2.cmp(b1));
@@ -71,7 +127,7 @@ impl<T: Config> Pallet<T> {
     /// choose the top-most ValidatorCount::get() of them.
     ///
     /// This should only be called at the end of an era.
-    fn select_and_update_validators(_current_era: EraIndex) -> Option<Vec<T::AccountId>> {
+    fn select_and_update_validators(current_era: EraIndex) -> Option<Vec<T::AccountId>> {
         // TODO: might move to offchain worker solution in the future.
         // Currently there is no performance issue practically.
         let candidates = Self::filter_out_candidates();
@@ -91,7 +147,7 @@ impl<T: Config> Pallet<T> {
         let validators = candidates
             .into_iter()
             .take(desired_validator_count)
-            .map(|(_, v)| v)
+            .map(|(_, v)| Self::backup_or_self(v, current_era))
             .collect::<Vec<_>>();
 
         // Always return Some(new_validators).
@@ -227,8 +227,20 @@ impl<T: Config> Pallet<T> {
         // Only the active validators can be rewarded.
         let validator_rewards = Self::distribute_session_reward();
 
-        // Reset the session offenders.
-        if let Some(offenders) = SessionOffenders::<T>::take() {
+        // Reset the session offenders, the offences of a backup are slashed on its primary.
+        let mut offenders = BTreeMap::new();
+        for (offender, slash_fraction) in SessionOffenders::<T>::take()
+            .unwrap_or_default()
+            .into_iter()
+            .chain(Self::take_due_deferred_slashes(session_index))
+        {
+            let fraction = offenders
+                .entry(Self::primary_or_self(offender))
+                .or_default();
+            *fraction = (*fraction).max(slash_fraction);
+        }
+
+        if !offenders.is_empty() {
             let force_chilled = Self::slash_offenders_in_session(offenders, validator_rewards);
             if !force_chilled.is_empty() {
                 debug!("Force chilled:{:?}", force_chilled);
@@ -296,6 +308,8 @@ impl<T: Config> Pallet<T> {
             Self::mint_and_slash(start_session);
         }
 
+        SessionStartBlock::<T>::put(<frame_system::Pallet<T>>::block_number());
+
         let next_active_era = Self::active_era().map(|e| e.index + 1).unwrap_or(0);
         debug!(
             target: "runtime::mining::staking",
@@ -320,6 +334,8 @@ impl<T: Config> Pallet<T> {
 
     /// End a session potentially ending an era.
     fn end_session(session_index: SessionIndex) {
+        Self::note_session_performance(session_index);
+
         if let Some(active_era) = Self::active_era() {
             if let Some(next_active_era_start_session_index) =
                 Self::eras_start_session_index(active_era.index + 1)
@@ -346,9 +362,9 @@ impl<T: Config> Pallet<T> {
         });
     }
 
-    /// Compute payout for era.
-    fn end_era(_active_era: ActiveEraInfo, _session_index: SessionIndex) {
-        // Ignore, ChainX has nothing to do in end_era().
+    /// Move the performance of the ending era into the history.
+    fn end_era(active_era: ActiveEraInfo, _session_index: SessionIndex) {
+        Self::close_era_performance(active_era.index);
     }
 }
 
@@ -386,7 +402,8 @@ pub type IdentificationTuple<T> = (
 type Offender<T> = IdentificationTuple<T>;
 
 /// This is intended to be used with `FilterHistoricalOffences` in Substrate/Staking.
-/// In ChainX, we always apply the slash immediately, no deferred slash.
+/// In ChainX, the offline slash is applied at the end of the session, while the
+/// equivocation slash reported via `EquivocationReporter` is deferred.
 impl<T: Config> OnOffenceHandler<Reporter<T>, IdentificationTuple<T>, Weight> for Pallet<T>
 where
     T: pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
@@ -419,6 +436,19 @@ where
             offenders_tuple, slash_session
         );
 
+        if ReportingEquivocation::<T>::get() {
+            let offenders = offenders_tuple
+                .into_iter()
+                .map(|(offender, slash_fraction)| (offender.clone(), *slash_fraction))
+                .collect();
+            Self::defer_equivocation_slashes(
+                offenders,
+                slash_session,
+                <pallet_session::Pallet<T>>::current_index(),
+            );
+            return 1;
+        }
+
         // Write a temp environment storage so that we can sum the session reward
         // together later and then perform the slashing operation only once.
         <SessionOffenders<T>>::put(offenders_tuple);
@@ -16,6 +16,9 @@
 mod constants;
 mod election;
 mod impls;
+mod parameters;
+mod payout;
+mod performance;
 mod reward;
 mod rpc;
 mod slashing;
@@ -40,8 +43,9 @@ use frame_support::{
     },
 };
 use frame_system::{ensure_root, ensure_signed};
+use sp_core::H160;
 use sp_runtime::{
-    traits::{Convert, SaturatedConversion, Saturating, StaticLookup, Zero},
+    traits::{AccountIdConversion, Convert, SaturatedConversion, Saturating, StaticLookup, Zero},
     DispatchResult, Perbill,
 };
 use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
@@ -54,7 +58,9 @@ use xpallet_support::traits::TreasuryAccount;
 use crate::constants::*;
 
 pub use self::impls::{IdentificationTuple, SimpleValidatorRewardPotAccountDeterminer};
+pub use self::performance::UnresponsivenessReporter;
 pub use self::rpc::*;
+pub use self::slashing::EquivocationReporter;
 pub use self::types::*;
 pub use self::weights::WeightInfo;
 pub use xp_mining_common::RewardPotAccountFor;
@@ -111,6 +117,29 @@ pub mod pallet {
         /// Provide information about whether or not some
         /// validator has been registered with them
         type ValidatorRegistration: ValidatorRegistration<Self::AccountId>;
+
+        /// The number of finished eras kept in the performance history of each validator.
+        #[pallet::constant]
+        type PerformanceHistoryDepth: Get<u32>;
+
+        /// The maximum number of automatic payouts processed in the idle time of a block.
+        #[pallet::constant]
+        type MaxAutoPayouts: Get<u32>;
+
+        /// The number of sessions an equivocation slash is deferred for,
+        /// during which it can be canceled by `SlashCancelOrigin`.
+        #[pallet::constant]
+        type SlashDeferDuration: Get<SessionIndex>;
+
+        /// The origin which can cancel the deferred slashes, apart from Root.
+        type SlashCancelOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The origin which can manage the allowlist of the EVM staking precompile, apart from Root.
+        type EvmStakingOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The origin which can schedule the changes of the staking parameters, apart from Root.
+        type ParametersOrigin: EnsureOrigin<Self::Origin>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -120,6 +149,13 @@ pub mod pallet {
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::process_auto_payouts(remaining_weight)
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Nominate the `target` with `value` of the origin account's balance locked.
@@ -254,11 +290,55 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Claim the referral rewards accumulated from Asset Mining.
+        #[pallet::weight(T::WeightInfo::claim_referral_rewards())]
+        pub fn claim_referral_rewards(origin: OriginFor<T>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let mut ledger = ReferralRewards::<T>::get(&sender);
+            let unclaimed = ledger.unclaimed;
+            ensure!(!unclaimed.is_zero(), Error::<T>::ZeroBalance);
+
+            T::Currency::transfer(
+                &Self::referral_reward_pot(),
+                &sender,
+                unclaimed,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            ledger.unclaimed = Zero::zero();
+            ledger.claimed = ledger.claimed.saturating_add(unclaimed);
+            ReferralRewards::<T>::insert(&sender, ledger);
+
+            Self::deposit_event(Event::<T>::ReferralRewardsClaimed(sender, unclaimed));
+            Ok(())
+        }
+
+        /// Set how the staking dividends of the origin account are paid out.
+        ///
+        /// The dividends of `Compound` and `Transfer` are claimed automatically
+        /// in the idle time of blocks.
+        #[pallet::weight(T::WeightInfo::set_payout_preference())]
+        pub fn set_payout_preference(
+            origin: OriginFor<T>,
+            preference: PayoutPreference,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            if preference == PayoutPreference::Manual {
+                PayoutPreferences::<T>::remove(&sender);
+            } else {
+                PayoutPreferences::<T>::insert(&sender, preference);
+            }
+            Self::deposit_event(Event::<T>::PayoutPreferenceSet(sender, preference));
+            Ok(())
+        }
+
         /// Declare the desire to validate for the origin account.
         #[pallet::weight(T::WeightInfo::validate())]
         pub fn validate(origin: OriginFor<T>) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             ensure!(Self::is_validator(&sender), Error::<T>::NotValidator);
+            RejoinEraOf::<T>::remove(&sender);
             Validators::<T>::mutate(sender, |validator| {
                 validator.is_chilled = false;
             });
@@ -276,6 +356,7 @@ pub mod pallet {
                     Error::<T>::TooFewActiveValidators
                 );
             }
+            RejoinEraOf::<T>::remove(&sender);
             Validators::<T>::mutate(sender, |validator| {
                 validator.is_chilled = true;
                 validator.last_chilled = Some(<frame_system::Pallet<T>>::block_number());
@@ -283,6 +364,93 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Set the new session keys, sit out the next election and rejoin the
+        /// elections afterwards automatically.
+        ///
+        /// `keys` are the SCALE encoded session keys, e.g., the result of the
+        /// `author_rotateKeys` RPC. Chilling for one era ensures the validator is
+        /// not elected before the new keys are in use.
+        #[pallet::weight(T::WeightInfo::rotate_keys_and_rebond())]
+        pub fn rotate_keys_and_rebond(
+            origin: OriginFor<T>,
+            keys: Vec<u8>,
+            proof: Vec<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(Self::is_validator(&sender), Error::<T>::NotValidator);
+            if Self::is_active(&sender) {
+                ensure!(
+                    Self::can_force_chilled(),
+                    Error::<T>::TooFewActiveValidators
+                );
+            }
+
+            T::SessionInterface::set_keys(&sender, keys, proof)?;
+
+            let rejoin_era = Self::current_era().unwrap_or_default() + 1;
+            Validators::<T>::mutate(&sender, |validator| {
+                validator.is_chilled = true;
+                validator.last_chilled = Some(<frame_system::Pallet<T>>::block_number());
+            });
+            RejoinEraOf::<T>::insert(&sender, rejoin_era);
+            Self::deposit_event(Event::<T>::KeysRotated(sender, rejoin_era));
+            Ok(())
+        }
+
+        /// Set the backup validator of the origin account, `None` to remove it.
+        ///
+        /// Once the validator misses the heartbeats for a full session, the backup is
+        /// elected in its place from the next election on, until the validator calls
+        /// `resume_primary`. The backup has to set its own session keys, its rewards,
+        /// slashes and performance are accounted to the validator.
+        #[pallet::weight(T::WeightInfo::set_backup())]
+        pub fn set_backup(origin: OriginFor<T>, backup: Option<T::AccountId>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(Self::is_validator(&sender), Error::<T>::NotValidator);
+
+            if let Some(old) = BackupOf::<T>::get(&sender) {
+                ensure!(
+                    !FailedOverAt::<T>::contains_key(&sender)
+                        && !T::SessionInterface::validators().contains(&old),
+                    Error::<T>::BackupInService
+                );
+            }
+            if let Some(ref backup) = backup {
+                ensure!(
+                    *backup != sender && !Self::is_validator(backup),
+                    Error::<T>::InvalidBackup
+                );
+                ensure!(
+                    !PrimaryOf::<T>::contains_key(backup),
+                    Error::<T>::AlreadyBackup
+                );
+            }
+
+            if let Some(old) = BackupOf::<T>::take(&sender) {
+                PrimaryOf::<T>::remove(&old);
+            }
+            if let Some(ref backup) = backup {
+                BackupOf::<T>::insert(&sender, backup);
+                PrimaryOf::<T>::insert(backup, &sender);
+            }
+            Self::deposit_event(Event::<T>::BackupSet(sender, backup));
+            Ok(())
+        }
+
+        /// Stand for the elections again in place of the backup after failing over.
+        ///
+        /// The backup keeps validating until the next era starts.
+        #[pallet::weight(T::WeightInfo::resume_primary())]
+        pub fn resume_primary(origin: OriginFor<T>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(
+                FailedOverAt::<T>::take(&sender).is_some(),
+                Error::<T>::NotFailedOver
+            );
+            Self::deposit_event(Event::<T>::PrimaryResumed(sender));
+            Ok(())
+        }
+
         /// Register to be a validator for the origin account.
         ///
         /// The reason for using `validator_nickname` instead of `referral_id` as
@@ -300,6 +468,10 @@ pub mod pallet {
             let sender = ensure_signed(origin)?;
             Self::check_referral_id(&validator_nickname)?;
             ensure!(!Self::is_validator(&sender), Error::<T>::AlreadyValidator);
+            ensure!(
+                !PrimaryOf::<T>::contains_key(&sender),
+                Error::<T>::AlreadyBackup
+            );
             ensure!(
                 (Self::validator_set().count() as u32) < MaximumValidatorCount::<T>::get(),
                 Error::<T>::TooManyValidators
@@ -375,6 +547,93 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Set the uptime and block production ratio below which a nominated validator
+        /// is reported as underperforming at the end of each era.
+        #[pallet::weight(T::WeightInfo::set_performance_alert_threshold())]
+        pub fn set_performance_alert_threshold(
+            origin: OriginFor<T>,
+            #[pallet::compact] new: Perbill,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            PerformanceAlertThreshold::<T>::put(new);
+            Ok(())
+        }
+
+        /// Cancel the deferred equivocation slashes before they are applied.
+        #[pallet::weight(T::WeightInfo::cancel_deferred_slash(slash_ids.len() as u32))]
+        pub fn cancel_deferred_slash(
+            origin: OriginFor<T>,
+            slash_ids: Vec<SlashId>,
+        ) -> DispatchResult {
+            T::SlashCancelOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            for slash_id in slash_ids {
+                let slash = DeferredSlashes::<T>::take(slash_id)
+                    .ok_or(Error::<T>::DeferredSlashNotFound)?;
+                Self::deposit_event(Event::<T>::DeferredSlashCanceled(slash_id, slash.offender));
+            }
+            Ok(())
+        }
+
+        /// Toggle whether only the allowlisted EVM contracts can call the staking precompile.
+        #[pallet::weight(T::WeightInfo::set_evm_staking_allowlist())]
+        pub fn set_evm_staking_allowlist(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            T::EvmStakingOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            EvmStakingAllowlistEnabled::<T>::put(enabled);
+            Self::deposit_event(Event::<T>::EvmStakingAllowlistSet(enabled));
+            Ok(())
+        }
+
+        /// Add or remove an EVM contract to the allowlist of the staking precompile.
+        #[pallet::weight(T::WeightInfo::set_evm_staker())]
+        pub fn set_evm_staker(
+            origin: OriginFor<T>,
+            contract: H160,
+            allowed: bool,
+        ) -> DispatchResult {
+            T::EvmStakingOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            if allowed {
+                EvmStakers::<T>::insert(contract, ());
+            } else {
+                EvmStakers::<T>::remove(contract);
+            }
+            Self::deposit_event(Event::<T>::EvmStakerSet(contract, allowed));
+            Ok(())
+        }
+
+        /// Schedule a change of the staking parameters within the bounded ranges, which takes
+        /// effect when the next era is planned.
+        ///
+        /// A pending change of the same parameter is replaced.
+        #[pallet::weight(T::WeightInfo::schedule_parameter_change())]
+        pub fn schedule_parameter_change(
+            origin: OriginFor<T>,
+            change: ParameterChange,
+        ) -> DispatchResult {
+            T::ParametersOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            Self::ensure_parameter_change_in_bounds(&change)?;
+
+            PendingParameterChanges::<T>::mutate(|changes| {
+                changes
+                    .retain(|c| sp_std::mem::discriminant(c) != sp_std::mem::discriminant(&change));
+                changes.push(change);
+            });
+            let effective_era = Self::current_era().map(|era| era + 1).unwrap_or_default();
+            Self::deposit_event(Event::<T>::ParameterChangeScheduled(change, effective_era));
+            Ok(())
+        }
+
         #[pallet::weight(10_000_000)]
         pub fn set_immortals(origin: OriginFor<T>, new: Vec<T::AccountId>) -> DispatchResult {
             ensure_root(origin)?;
@@ -457,6 +716,40 @@ pub mod pallet {
         ForceChilled(SessionIndex, Vec<T::AccountId>),
         /// Unlock the unbonded withdrawal by force. [account]
         ForceAllWithdrawn(T::AccountId),
+        /// A nominated validator performed below the alert threshold in the era. [validator, era, uptime, block_production]
+        ValidatorUnderperformed(T::AccountId, EraIndex, Perbill, Perbill),
+        /// A referral was rewarded from Asset Mining, kept in the referral reward pot. [referral, amount]
+        ReferralRewarded(T::AccountId, BalanceOf<T>),
+        /// A referral claimed its accumulated rewards. [referral, amount]
+        ReferralRewardsClaimed(T::AccountId, BalanceOf<T>),
+        /// A nominator changed how the dividends are paid out. [nominator, preference]
+        PayoutPreferenceSet(T::AccountId, PayoutPreference),
+        /// An equivocation was reported and its slash deferred. [offender, offence_session, slash_fraction, slash_id, apply_at]
+        EquivocationReported(T::AccountId, SessionIndex, Perbill, SlashId, SessionIndex),
+        /// A deferred slash was canceled. [slash_id, offender]
+        DeferredSlashCanceled(SlashId, T::AccountId),
+        /// A deferred slash was applied. [slash_id, offender, slash_fraction]
+        DeferredSlashApplied(SlashId, T::AccountId, Perbill),
+        /// A validator rotated the session keys and will rejoin at the given era. [validator, rejoin_era]
+        KeysRotated(T::AccountId, EraIndex),
+        /// A validator rejoined the elections after rotating the session keys. [validator, era]
+        Rejoined(T::AccountId, EraIndex),
+        /// A validator set or removed its backup validator. [validator, backup]
+        BackupSet(T::AccountId, Option<T::AccountId>),
+        /// A validator missed the heartbeats for a full session, its backup will be elected instead. [validator, backup, session_index]
+        FailedOver(T::AccountId, T::AccountId, SessionIndex),
+        /// The backup was elected in place of the validator. [validator, backup, era]
+        BackupElected(T::AccountId, T::AccountId, EraIndex),
+        /// A validator stood for the elections again in place of its backup. [validator]
+        PrimaryResumed(T::AccountId),
+        /// The allowlist of the EVM staking precompile was enabled or disabled. [enabled]
+        EvmStakingAllowlistSet(bool),
+        /// An EVM contract was added to or removed from the allowlist of the staking precompile. [contract, allowed]
+        EvmStakerSet(H160, bool),
+        /// A change of the staking parameters was scheduled for the era. [change, era]
+        ParameterChangeScheduled(ParameterChange, EraIndex),
+        /// A scheduled change of the staking parameters took effect in the era. [change, era]
+        ParameterChangeApplied(ParameterChange, EraIndex),
     }
 
     /// Old name generated by `decl_event`.
@@ -506,6 +799,20 @@ pub mod pallet {
         XssCheckFailed,
         /// Failed to allocate the dividend.
         AllocateDividendFailed,
+        /// The deferred slash does not exist or has been applied.
+        DeferredSlashNotFound,
+        /// The session keys can not be decoded.
+        InvalidSessionKeys,
+        /// The backup can be neither the validator itself nor another validator.
+        InvalidBackup,
+        /// The account is the backup of a validator already.
+        AlreadyBackup,
+        /// The backup is validating or elected in place of the validator.
+        BackupInService,
+        /// The validator has not failed over to its backup.
+        NotFailedOver,
+        /// The staking parameter is out of the range that can be scheduled.
+        ParameterOutOfBounds,
     }
 
     /// The ideal number of staking participants.
@@ -705,6 +1012,114 @@ pub mod pallet {
     #[pallet::getter(fn immortals)]
     pub(super) type Immortals<T: Config> = StorageValue<_, Vec<T::AccountId>>;
 
+    /// The block number at which the current session started.
+    #[pallet::storage]
+    pub(super) type SessionStartBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Validators reported unresponsive by im-online in the session being ended.
+    #[pallet::storage]
+    pub(super) type UnresponsiveValidators<T: Config> =
+        StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+    /// The performance of validators accumulated so far in the active era.
+    #[pallet::storage]
+    #[pallet::getter(fn current_era_performance_of)]
+    pub type CurrentEraPerformance<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, ValidatorPerformance, ValueQuery>;
+
+    /// The performance of validators in the last `PerformanceHistoryDepth` eras, oldest first.
+    #[pallet::storage]
+    #[pallet::getter(fn performance_history_of)]
+    pub type PerformanceHistory<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Vec<(EraIndex, ValidatorPerformance)>,
+        ValueQuery,
+    >;
+
+    #[pallet::type_value]
+    pub fn DefaultForPerformanceAlertThreshold() -> Perbill {
+        Perbill::from_percent(80)
+    }
+
+    /// Nominated validators whose uptime or block production ratio in an era falls below
+    /// this threshold are reported via `ValidatorUnderperformed`.
+    #[pallet::storage]
+    #[pallet::getter(fn performance_alert_threshold)]
+    pub type PerformanceAlertThreshold<T: Config> =
+        StorageValue<_, Perbill, ValueQuery, DefaultForPerformanceAlertThreshold>;
+
+    /// The referral rewards from Asset Mining of each validator.
+    #[pallet::storage]
+    #[pallet::getter(fn referral_rewards_of)]
+    pub type ReferralRewards<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, ReferralRewardLedger<BalanceOf<T>>, ValueQuery>;
+
+    /// The equivocation slashes applied to each validator so far.
+    #[pallet::storage]
+    #[pallet::getter(fn equivocations_of)]
+    pub type EquivocationsOf<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// The equivocation slashes to be applied.
+    #[pallet::storage]
+    #[pallet::getter(fn deferred_slashes)]
+    pub type DeferredSlashes<T: Config> =
+        StorageMap<_, Twox64Concat, SlashId, DeferredSlash<T::AccountId>>;
+
+    /// The id of the next deferred slash.
+    #[pallet::storage]
+    pub(super) type NextSlashId<T: Config> = StorageValue<_, SlashId, ValueQuery>;
+
+    /// Whether the offence being reported is an equivocation.
+    #[pallet::storage]
+    pub(super) type ReportingEquivocation<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// The payout preference of each nominator, `Manual` if absent.
+    #[pallet::storage]
+    #[pallet::getter(fn payout_preference_of)]
+    pub type PayoutPreferences<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, PayoutPreference, ValueQuery>;
+
+    /// The raw key of `Nominations` after which the next automatic payout resumes.
+    #[pallet::storage]
+    pub(super) type AutoPayoutCursor<T: Config> = StorageValue<_, Vec<u8>>;
+
+    /// The era at which a validator that rotated the session keys rejoins the elections.
+    #[pallet::storage]
+    #[pallet::getter(fn rejoin_era_of)]
+    pub type RejoinEraOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, EraIndex>;
+
+    /// The backup validator of a validator.
+    #[pallet::storage]
+    #[pallet::getter(fn backup_of)]
+    pub type BackupOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
+    /// The validator a backup validator stands by for.
+    #[pallet::storage]
+    #[pallet::getter(fn primary_of)]
+    pub type PrimaryOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
+    /// The session in which a validator missed the heartbeats and failed over to its backup.
+    #[pallet::storage]
+    #[pallet::getter(fn failed_over_at)]
+    pub type FailedOverAt<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SessionIndex>;
+
+    /// Whether only the contracts in `EvmStakers` can call the EVM staking precompile.
+    #[pallet::storage]
+    #[pallet::getter(fn evm_staking_allowlist_enabled)]
+    pub type EvmStakingAllowlistEnabled<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// The EVM contracts allowed to call the staking precompile when the allowlist is enabled.
+    #[pallet::storage]
+    pub type EvmStakers<T: Config> = StorageMap<_, Twox64Concat, H160, ()>;
+
+    /// The changes of the staking parameters to be applied when the next era is planned.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_parameter_changes)]
+    pub type PendingParameterChanges<T: Config> = StorageValue<_, Vec<ParameterChange>, ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub validator_count: u32,
@@ -819,6 +1234,9 @@ pub trait SessionInterface<AccountId>: frame_system::Config {
 
     /// Get the validators from session.
     fn validators() -> Vec<AccountId>;
+
+    /// Set the SCALE encoded session keys of the given validator.
+    fn set_keys(validator: &AccountId, keys: Vec<u8>, proof: Vec<u8>) -> DispatchResult;
 }
 
 impl<T: Config> SessionInterface<<T as frame_system::Config>::AccountId> for T
@@ -838,6 +1256,20 @@ where
     fn validators() -> Vec<<T as frame_system::Config>::AccountId> {
         <pallet_session::Pallet<T>>::validators()
     }
+
+    fn set_keys(
+        validator: &<T as frame_system::Config>::AccountId,
+        keys: Vec<u8>,
+        proof: Vec<u8>,
+    ) -> DispatchResult {
+        let keys = <T::Keys as codec::Decode>::decode(&mut &keys[..])
+            .map_err(|_| Error::<T>::InvalidSessionKeys)?;
+        <pallet_session::Pallet<T>>::set_keys(
+            frame_system::RawOrigin::Signed(validator.clone()).into(),
+            keys,
+            proof,
+        )
+    }
 }
 
 impl<T: Config> xpallet_support::traits::Validator<T::AccountId> for Pallet<T> {
@@ -857,6 +1289,29 @@ impl<T: Config> Pallet<T> {
         Validators::<T>::contains_key(who)
     }
 
+    /// Returns true if the EVM `contract` can call the staking precompile.
+    pub fn is_evm_staker_allowed(contract: &H160) -> bool {
+        !Self::evm_staking_allowlist_enabled() || EvmStakers::<T>::contains_key(contract)
+    }
+
+    /// Returns the account of the pot holding the unclaimed referral rewards.
+    pub fn referral_reward_pot() -> T::AccountId {
+        REFERRAL_REWARD_POT_ID.into_account()
+    }
+
+    /// Records `value` as the referral reward of `referral`.
+    ///
+    /// The reward must have been transferred to the referral reward pot by the caller.
+    pub fn on_referral_reward(referral: &T::AccountId, value: BalanceOf<T>) {
+        if value.is_zero() {
+            return;
+        }
+        ReferralRewards::<T>::mutate(referral, |ledger| {
+            ledger.unclaimed = ledger.unclaimed.saturating_add(value);
+        });
+        Self::deposit_event(Event::<T>::ReferralRewarded(referral.clone(), value));
+    }
+
     /// Returns the (possible) validator account behind the given referral id.
     #[inline]
     pub fn validator_for(referral_id: &[u8]) -> Option<T::AccountId> {
@@ -913,6 +1368,13 @@ impl<T: Config> Pallet<T> {
         *Self::locks(who).entry(LockedType::Bonded).or_default()
     }
 
+    /// Returns the validator the session validator `who` validates for, i.e. the primary
+    /// validator if `who` is a backup, otherwise `who` itself.
+    #[inline]
+    pub fn primary_or_self(who: T::AccountId) -> T::AccountId {
+        Self::primary_of(&who).unwrap_or(who)
+    }
+
     /// Returns the associated reward pot account for the given validator.
     #[inline]
     pub fn reward_pot_for(validator: &T::AccountId) -> T::AccountId {
@@ -1065,6 +1527,7 @@ impl<T: Config> Pallet<T> {
 
     /// Force the validator `who` to be chilled.
     fn apply_force_chilled(who: &T::AccountId) {
+        RejoinEraOf::<T>::remove(who);
         Validators::<T>::mutate(who, |validator| {
             validator.is_chilled = true;
             validator.last_chilled = Some(<frame_system::Pallet<T>>::block_number());
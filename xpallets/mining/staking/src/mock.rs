@@ -6,7 +6,7 @@ use frame_support::{parameter_types, traits::GenesisBuild};
 use sp_core::H256;
 use sp_runtime::{
     testing::{Header, UintAuthorityId},
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, ConvertInto, IdentityLookup},
     Perbill,
 };
 use xp_mining_staking::SessionIndex;
@@ -166,7 +166,7 @@ impl pallet_session::Config for Test {
     type SessionHandler = (OtherSessionHandler,);
     type Event = Event;
     type ValidatorId = AccountId;
-    type ValidatorIdOf = ();
+    type ValidatorIdOf = ConvertInto;
     type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
     type WeightInfo = ();
 }
@@ -193,6 +193,9 @@ parameter_types! {
     pub const SessionDuration: BlockNumber = 50;
     pub const MinimumReferralId: u32 = 2;
     pub const MaximumReferralId: u32 = 12;
+    pub const PerformanceHistoryDepth: u32 = 3;
+    pub const MaxAutoPayouts: u32 = 2;
+    pub const SlashDeferDuration: SessionIndex = 2;
 }
 
 pub struct Registration;
@@ -213,6 +216,12 @@ impl Config for Test {
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
     type ValidatorRegistration = Registration;
+    type PerformanceHistoryDepth = PerformanceHistoryDepth;
+    type MaxAutoPayouts = MaxAutoPayouts;
+    type SlashDeferDuration = SlashDeferDuration;
+    type SlashCancelOrigin = frame_system::EnsureRoot<AccountId>;
+    type EvmStakingOrigin = frame_system::EnsureRoot<AccountId>;
+    type ParametersOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -0,0 +1,65 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Ensures the scheduled value of a staking parameter is within the bounded range.
+    pub(crate) fn ensure_parameter_change_in_bounds(change: &ParameterChange) -> DispatchResult {
+        let in_bounds = match *change {
+            ParameterChange::ValidatorCount(count) => {
+                count >= Self::reasonable_minimum_validator_count()
+                    && count <= Self::maximum_validator_count()
+            }
+            ParameterChange::SessionsPerEra(sessions) => {
+                (MINIMUM_SESSIONS_PER_ERA..=MAXIMUM_SESSIONS_PER_ERA).contains(&sessions)
+            }
+            ParameterChange::GlobalDistribution(GlobalDistribution { treasury, mining }) => {
+                Self::is_distribution_in_bounds(mining, treasury, MAXIMUM_TREASURY_PROPORTION)
+            }
+            ParameterChange::MiningDistribution(MiningDistribution { asset, staking }) => {
+                // Required by `MiningDistribution::has_treasury_extra`.
+                !asset.is_zero()
+                    && Self::is_distribution_in_bounds(
+                        staking,
+                        asset,
+                        MAXIMUM_ASSET_MINING_PROPORTION,
+                    )
+            }
+        };
+        ensure!(in_bounds, Error::<T>::ParameterOutOfBounds);
+        Ok(())
+    }
+
+    /// Returns true if `major` is positive and the proportion of `minor` in the total shares
+    /// is no more than `max_minor`.
+    fn is_distribution_in_bounds(major: u32, minor: u32, max_minor: Perbill) -> bool {
+        match major.checked_add(minor) {
+            Some(total) if !major.is_zero() && total <= MAXIMUM_DISTRIBUTION_SHARES => {
+                Perbill::from_rational(minor, total) <= max_minor
+            }
+            _ => false,
+        }
+    }
+
+    /// Applies the pending changes of the staking parameters before the election of `new_era`.
+    pub(crate) fn apply_parameter_changes(new_era: EraIndex) {
+        for change in PendingParameterChanges::<T>::take() {
+            match change {
+                ParameterChange::ValidatorCount(count) => ValidatorCount::<T>::put(count),
+                ParameterChange::SessionsPerEra(sessions) => SessionsPerEra::<T>::put(sessions),
+                ParameterChange::GlobalDistribution(ratio) => {
+                    GlobalDistributionRatio::<T>::put(ratio)
+                }
+                ParameterChange::MiningDistribution(ratio) => {
+                    MiningDistributionRatio::<T>::put(ratio)
+                }
+            }
+            debug!(
+                target: "runtime::mining::staking",
+                "[apply_parameter_changes] era:{}, change:{:?}",
+                new_era, change
+            );
+            Self::deposit_event(Event::<T>::ParameterChangeApplied(change, new_era));
+        }
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::weights::Weight;
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Claims the dividends of the nominators who opted in to the automatic payout,
+    /// using no more than `remaining_weight`.
+    ///
+    /// The nominations are visited in the storage order, resuming from where the
+    /// previous block stopped and starting over once all of them have been visited.
+    pub(crate) fn process_auto_payouts(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let payout_weight = T::WeightInfo::auto_payout();
+        // Reading a nomination and the payout preference of its nominator.
+        let visit_weight = db_weight.reads(2);
+
+        // Reading and updating the cursor.
+        let mut consumed = db_weight.reads_writes(1, 1);
+        if consumed
+            .saturating_add(visit_weight)
+            .saturating_add(payout_weight)
+            > remaining_weight
+        {
+            return 0;
+        }
+
+        let mut nominations = match AutoPayoutCursor::<T>::get() {
+            Some(cursor) => Nominations::<T>::iter_from(cursor),
+            None => Nominations::<T>::iter(),
+        };
+
+        let max_payouts = T::MaxAutoPayouts::get();
+        let mut payouts = 0u32;
+        let mut cursor = None;
+
+        while payouts < max_payouts
+            && consumed
+                .saturating_add(visit_weight)
+                .saturating_add(payout_weight)
+                <= remaining_weight
+        {
+            let (nominator, validator, _) = match nominations.next() {
+                Some(nomination) => nomination,
+                None => {
+                    cursor = None;
+                    break;
+                }
+            };
+            consumed = consumed.saturating_add(visit_weight);
+            cursor = Some(Nominations::<T>::hashed_key_for(&nominator, &validator));
+
+            let preference = Self::payout_preference_of(&nominator);
+            if preference == PayoutPreference::Manual || !Self::is_validator(&validator) {
+                continue;
+            }
+
+            consumed = consumed.saturating_add(payout_weight);
+            if Self::auto_payout(&nominator, &validator, preference) {
+                payouts += 1;
+            }
+        }
+
+        match cursor {
+            Some(cursor) => AutoPayoutCursor::<T>::put(cursor),
+            None => AutoPayoutCursor::<T>::kill(),
+        }
+
+        debug!(
+            target: "runtime::mining::staking",
+            "[process_auto_payouts] payouts:{}, consumed_weight:{}",
+            payouts, consumed
+        );
+
+        consumed
+    }
+
+    /// Claims the dividend of `nominator` from `validator` according to `preference`.
+    ///
+    /// Returns true if some dividend has been paid out.
+    fn auto_payout(
+        nominator: &T::AccountId,
+        validator: &T::AccountId,
+        preference: PayoutPreference,
+    ) -> bool {
+        let current_block = <frame_system::Pallet<T>>::block_number();
+        let dividend = match Self::compute_dividend_at(nominator, validator, current_block) {
+            Ok(dividend) if !dividend.is_zero() => dividend,
+            _ => return false,
+        };
+
+        if <Self as Claim<T::AccountId>>::claim(nominator, validator).is_err() {
+            return false;
+        }
+
+        if preference == PayoutPreference::Compound {
+            // The dividend simply stays in the free balance if it can not be bonded.
+            let acceptable = Self::is_validator_bonding_itself(nominator, validator)
+                || Self::check_validator_acceptable_votes_limit(validator, dividend).is_ok();
+            if acceptable
+                && dividend + Self::total_locked_of(nominator) <= Self::free_balance(nominator)
+            {
+                let _ = Self::apply_bond(nominator, validator, dividend);
+            }
+        }
+
+        true
+    }
+}
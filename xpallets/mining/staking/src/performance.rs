@@ -0,0 +1,132 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+
+use sp_staking::offence::{Offence, OffenceError, ReportOffence};
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// Accumulates the liveness and expected block production of the validators
+    /// of the ending session into the performance of the active era.
+    ///
+    /// The validators with a backup fail over to it if they are unresponsive in the session.
+    pub(crate) fn note_session_performance(session_index: SessionIndex) {
+        let validators = T::SessionInterface::validators();
+        let unresponsive = UnresponsiveValidators::<T>::take();
+
+        if validators.is_empty() {
+            return;
+        }
+
+        let session_length = <frame_system::Pallet<T>>::block_number()
+            .saturating_sub(SessionStartBlock::<T>::get())
+            .saturated_into::<u32>();
+        let expected_blocks = session_length / validators.len() as u32;
+
+        for validator in validators {
+            let is_online = !unresponsive.contains(&validator);
+            if !is_online && !FailedOverAt::<T>::contains_key(&validator) {
+                if let Some(backup) = Self::backup_of(&validator) {
+                    FailedOverAt::<T>::insert(&validator, session_index);
+                    Self::deposit_event(Event::<T>::FailedOver(
+                        validator.clone(),
+                        backup,
+                        session_index,
+                    ));
+                }
+            }
+            let validator = Self::primary_or_self(validator);
+            CurrentEraPerformance::<T>::mutate(&validator, |performance| {
+                performance.active_sessions = performance.active_sessions.saturating_add(1);
+                if is_online {
+                    performance.online_sessions = performance.online_sessions.saturating_add(1);
+                }
+                performance.expected_blocks =
+                    performance.expected_blocks.saturating_add(expected_blocks);
+            });
+        }
+    }
+
+    /// Moves the performance of the ending era into the bounded history and alerts
+    /// the nominators of the validators performing below the threshold.
+    pub(crate) fn close_era_performance(era: EraIndex) {
+        let depth = T::PerformanceHistoryDepth::get() as usize;
+        let threshold = Self::performance_alert_threshold();
+
+        for (validator, performance) in CurrentEraPerformance::<T>::drain() {
+            PerformanceHistory::<T>::mutate(&validator, |history| {
+                history.push((era, performance));
+                if history.len() > depth {
+                    let excess = history.len() - depth;
+                    history.drain(..excess);
+                }
+            });
+
+            let uptime = performance.uptime();
+            let block_production = performance.block_production();
+            if (uptime < threshold || block_production < threshold)
+                && Self::is_nominated(&validator)
+            {
+                debug!(
+                    target: "runtime::mining::staking",
+                    "[close_era_performance] validator:{:?} underperformed in era {}, uptime:{:?}, block_production:{:?}",
+                    validator, era, uptime, block_production
+                );
+                Self::deposit_event(Event::<T>::ValidatorUnderperformed(
+                    validator,
+                    era,
+                    uptime,
+                    block_production,
+                ));
+            }
+        }
+    }
+
+    /// Returns true if the validator has been nominated by someone other than itself.
+    fn is_nominated(validator: &T::AccountId) -> bool {
+        let self_bonded = Nominations::<T>::get(validator, validator).nomination;
+        ValidatorLedgers::<T>::get(validator).total_nomination > self_bonded
+    }
+}
+
+impl<T: Config> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
+    fn note_author(author: T::AccountId) {
+        CurrentEraPerformance::<T>::mutate(Self::primary_or_self(author), |performance| {
+            performance.authored_blocks = performance.authored_blocks.saturating_add(1);
+        });
+    }
+
+    fn note_uncle(_author: T::AccountId, _age: T::BlockNumber) {}
+}
+
+/// Offence reporter used as `ReportUnresponsiveness` of im-online.
+///
+/// Notes the validators reported as unresponsive for the performance history
+/// and then forwards the report to `R`.
+pub struct UnresponsivenessReporter<T, R>(PhantomData<(T, R)>);
+
+impl<T, R, O> ReportOffence<T::AccountId, IdentificationTuple<T>, O>
+    for UnresponsivenessReporter<T, R>
+where
+    T: Config,
+    R: ReportOffence<T::AccountId, IdentificationTuple<T>, O>,
+    O: Offence<IdentificationTuple<T>>,
+{
+    fn report_offence(reporters: Vec<T::AccountId>, offence: O) -> Result<(), OffenceError> {
+        UnresponsiveValidators::<T>::mutate(|unresponsive| {
+            unresponsive.extend(
+                offence
+                    .offenders()
+                    .into_iter()
+                    .map(|(validator, _reward_pot)| validator),
+            );
+        });
+        R::report_offence(reporters, offence)
+    }
+
+    fn is_known_offence(offenders: &[IdentificationTuple<T>], time_slot: &O::TimeSlot) -> bool {
+        R::is_known_offence(offenders, time_slot)
+    }
+}
@@ -24,6 +24,23 @@ impl<T: Config> Pallet<T> {
         INITIAL_REWARD.saturated_into::<BalanceOf<T>>() / Self::pow2(halving_epoch)
     }
 
+    /// Returns the reward for all the mining assets in total, assuming the session ends with
+    /// this block.
+    pub fn asset_mining_session_reward() -> BalanceOf<T> {
+        let session_reward = Self::this_session_reward();
+        let (_, mining_reward) =
+            Self::global_distribution_ratio().calc_rewards::<T>(session_reward);
+
+        let mining_distribution = Self::mining_distribution_ratio();
+        let staking_reward = mining_distribution.calc_staking_reward::<T>(mining_reward);
+        let max_asset_mining_reward = mining_reward - staking_reward;
+
+        match mining_distribution.has_treasury_extra::<T>(max_asset_mining_reward) {
+            Some(treasury_extra) => max_asset_mining_reward - treasury_extra,
+            None => max_asset_mining_reward,
+        }
+    }
+
     /// Issue new fresh PCX.
     #[inline]
     pub(crate) fn mint(receiver: &T::AccountId, value: BalanceOf<T>) {
@@ -79,6 +79,7 @@ impl<T: Config> Pallet<T> {
         let current_validators: Vec<(T::AccountId, BalanceOf<T>)> =
             T::SessionInterface::validators()
                 .into_iter()
+                .map(Self::primary_or_self)
                 .filter(|v| Self::is_active(v))
                 .map(|v| {
                     let total_votes = Self::total_votes_of(&v);
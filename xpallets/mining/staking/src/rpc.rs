@@ -8,11 +8,12 @@ use serde::{Deserialize, Serialize};
 
 use sp_runtime::RuntimeDebug;
 
+use chainx_primitives::ReferralId;
 use xp_mining_common::RewardPotAccountFor;
 
 use crate::{
-    types::*, BalanceOf, Config, LastRebondOf, Nominations, Pallet, SessionInterface,
-    ValidatorLedgers, Validators,
+    types::*, BalanceOf, Config, CurrentEraPerformance, EraIndex, LastRebondOf, Nominations,
+    Pallet, PerformanceHistory, ReferralRewards, SessionInterface, ValidatorLedgers, Validators,
 };
 
 /// Total information about a validator.
@@ -45,6 +46,43 @@ pub struct NominatorInfo<BlockNumber> {
     pub last_rebond: Option<BlockNumber>,
 }
 
+/// Referral rewards from Asset Mining given the referral id.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReferralEarnings<AccountId, Balance> {
+    /// AccountId of the validator behind the referral id.
+    pub account: AccountId,
+    #[cfg_attr(feature = "std", serde(flatten))]
+    pub rewards: ReferralRewardLedger<Balance>,
+}
+
+/// Performance records of a validator.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ValidatorPerformanceInfo {
+    /// Performance accumulated so far in the active era.
+    pub current: ValidatorPerformance,
+    /// Performance in the recently finished eras, oldest first.
+    pub history: Vec<(EraIndex, ValidatorPerformance)>,
+}
+
+/// The prospect of a validator in the next election.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NextElectionInfo {
+    /// Whether the validator is qualified to join in the next election.
+    pub is_candidate: bool,
+    /// Position of the validator among the candidates ordered by the total nominations.
+    pub rank: Option<u32>,
+    /// Whether the validator will be in the validator set of the next era.
+    pub will_be_validating: bool,
+    /// The era at which the validator rejoins the elections after rotating the session keys.
+    pub rejoin_era: Option<EraIndex>,
+}
+
 impl<T: Config> Pallet<T> {
     pub fn validators_info(
     ) -> Vec<ValidatorInfo<T::AccountId, BalanceOf<T>, VoteWeight, T::BlockNumber>> {
@@ -96,4 +134,43 @@ impl<T: Config> Pallet<T> {
         let last_rebond = LastRebondOf::<T>::get(&who);
         NominatorInfo { last_rebond }
     }
+
+    pub fn validator_performance_of(who: T::AccountId) -> ValidatorPerformanceInfo {
+        ValidatorPerformanceInfo {
+            current: CurrentEraPerformance::<T>::get(&who),
+            history: PerformanceHistory::<T>::get(&who),
+        }
+    }
+
+    pub fn next_election_of(who: T::AccountId) -> NextElectionInfo {
+        let candidates =
+            Self::sort_candidates(Self::validator_set().filter(Self::is_eligible_candidate));
+        let rank = candidates
+            .iter()
+            .position(|(_, candidate)| candidate == &who)
+            .map(|position| position as u32);
+        let is_candidate = rank.is_some();
+        // The current validator set is kept if there are not enough candidates.
+        let will_be_validating =
+            if candidates.len() < Self::reasonable_minimum_validator_count() as usize {
+                T::SessionInterface::validators().contains(&who)
+            } else {
+                rank.map_or(false, |rank| rank < Self::validator_count())
+            };
+        NextElectionInfo {
+            is_candidate,
+            rank,
+            will_be_validating,
+            rejoin_era: Self::rejoin_era_of(&who),
+        }
+    }
+
+    pub fn referral_earnings(
+        referral_id: ReferralId,
+    ) -> Option<ReferralEarnings<T::AccountId, BalanceOf<T>>> {
+        Self::validator_for(&referral_id).map(|account| ReferralEarnings {
+            rewards: ReferralRewards::<T>::get(&account),
+            account,
+        })
+    }
 }
@@ -1,11 +1,84 @@
 // Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
 
+use sp_std::marker::PhantomData;
 use sp_std::ops::Mul;
 use sp_std::vec::Vec;
 
+use sp_runtime::PerThing;
+use sp_staking::offence::{Offence, OffenceError, ReportOffence};
+
 use super::*;
 
 impl<T: Config> Pallet<T> {
+    /// Returns the slash fraction of an equivocation graduated by the number of
+    /// equivocation slashes the offender already received.
+    pub(crate) fn graduated_slash_fraction(offender: &T::AccountId, base: Perbill) -> Perbill {
+        let multiplier = Self::equivocations_of(offender).saturating_add(1);
+        Perbill::from_parts(
+            base.deconstruct()
+                .saturating_mul(multiplier)
+                .min(Perbill::ACCURACY),
+        )
+    }
+
+    /// Queues the slashes of the equivocating `offenders`, which are applied
+    /// `SlashDeferDuration` sessions after `current_session` unless canceled.
+    pub(crate) fn defer_equivocation_slashes(
+        offenders: Vec<(T::AccountId, Perbill)>,
+        offence_session: SessionIndex,
+        current_session: SessionIndex,
+    ) {
+        let apply_at = current_session.saturating_add(T::SlashDeferDuration::get());
+        for (offender, base_fraction) in offenders {
+            let slash_fraction = Self::graduated_slash_fraction(&offender, base_fraction);
+            let slash_id = NextSlashId::<T>::mutate(|next| {
+                let slash_id = *next;
+                *next = next.wrapping_add(1);
+                slash_id
+            });
+            DeferredSlashes::<T>::insert(
+                slash_id,
+                DeferredSlash {
+                    offender: offender.clone(),
+                    offence_session,
+                    slash_fraction,
+                    apply_at,
+                },
+            );
+            Self::deposit_event(Event::<T>::EquivocationReported(
+                offender,
+                offence_session,
+                slash_fraction,
+                slash_id,
+                apply_at,
+            ));
+        }
+    }
+
+    /// Takes the deferred slashes due in `session_index`.
+    pub(crate) fn take_due_deferred_slashes(
+        session_index: SessionIndex,
+    ) -> Vec<(T::AccountId, Perbill)> {
+        let due = DeferredSlashes::<T>::iter()
+            .filter(|(_, slash)| slash.apply_at <= session_index)
+            .collect::<Vec<_>>();
+
+        due.into_iter()
+            .map(|(slash_id, slash)| {
+                DeferredSlashes::<T>::remove(slash_id);
+                EquivocationsOf::<T>::mutate(&slash.offender, |count| {
+                    *count = count.saturating_add(1)
+                });
+                Self::deposit_event(Event::<T>::DeferredSlashApplied(
+                    slash_id,
+                    slash.offender.clone(),
+                    slash.slash_fraction,
+                ));
+                (slash.offender, slash.slash_fraction)
+            })
+            .collect()
+    }
+
     /// Returns the force chilled offenders if any after applying the slashings.
     ///
     /// The slashed balances will be moved to the treasury.
@@ -89,3 +162,27 @@ impl<T: Config> Pallet<T> {
             .collect()
     }
 }
+
+/// Offence reporter used in the `HandleEquivocation` of BABE and GRANDPA.
+///
+/// Forwards the report to `R`, the offenders reported through it are slashed
+/// after `SlashDeferDuration` sessions instead of at the end of the current session.
+pub struct EquivocationReporter<T, R>(PhantomData<(T, R)>);
+
+impl<T, R, O> ReportOffence<T::AccountId, IdentificationTuple<T>, O> for EquivocationReporter<T, R>
+where
+    T: Config,
+    R: ReportOffence<T::AccountId, IdentificationTuple<T>, O>,
+    O: Offence<IdentificationTuple<T>>,
+{
+    fn report_offence(reporters: Vec<T::AccountId>, offence: O) -> Result<(), OffenceError> {
+        ReportingEquivocation::<T>::put(true);
+        let result = R::report_offence(reporters, offence);
+        ReportingEquivocation::<T>::kill();
+        result
+    }
+
+    fn is_known_offence(offenders: &[IdentificationTuple<T>], time_slot: &O::TimeSlot) -> bool {
+        R::is_known_offence(offenders, time_slot)
+    }
+}
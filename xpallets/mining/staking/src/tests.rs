@@ -2,7 +2,11 @@
 
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_err, assert_ok, traits::OnInitialize};
+use frame_support::{
+    assert_err, assert_ok,
+    traits::{Hooks, OnInitialize},
+    weights::Weight,
+};
 
 fn t_issue_pcx(to: AccountId, value: Balance) {
     XStaking::mint(&to, value);
@@ -898,3 +902,493 @@ fn migration_session_offset_should_work() {
         );
     });
 }
+
+#[test]
+fn validator_performance_should_work() {
+    use pallet_authorship::EventHandler;
+
+    ExtBuilder::default().build_and_execute(|| {
+        t_issue_pcx(5, 100);
+        assert_ok!(t_bond(5, 2, 10));
+
+        XStaking::note_author(1);
+        UnresponsiveValidators::<Test>::put(vec![2]);
+
+        // Era 0 ends along with session 2.
+        t_start_session(3);
+
+        let history = XStaking::performance_history_of(1);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].0, 0);
+        assert_eq!(history[0].1.active_sessions, 3);
+        assert_eq!(history[0].1.online_sessions, 3);
+        assert_eq!(history[0].1.authored_blocks, 1);
+
+        let history = XStaking::performance_history_of(2);
+        assert_eq!(history[0].1.online_sessions, 2);
+        assert_eq!(history[0].1.uptime(), Perbill::from_rational(2u32, 3u32));
+        assert_eq!(
+            XStaking::current_era_performance_of(2),
+            ValidatorPerformance::default()
+        );
+
+        // Only the nominated validator 2 is reported.
+        let underperformed = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                mock::Event::XStaking(crate::Event::ValidatorUnderperformed(
+                    validator,
+                    era,
+                    uptime,
+                    _,
+                )) => Some((validator, era, uptime)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            underperformed,
+            vec![(2, 0, Perbill::from_rational(2u32, 3u32))]
+        );
+
+        // The history is bounded by `PerformanceHistoryDepth`.
+        t_start_session(15);
+        let history = XStaking::performance_history_of(1);
+        assert_eq!(history.len(), 3);
+        assert_eq!(
+            history.iter().map(|(era, _)| *era).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    });
+}
+
+#[test]
+fn referral_rewards_should_be_claimable() {
+    ExtBuilder::default().build_and_execute(|| {
+        let referral_reward_pot = XStaking::referral_reward_pot();
+        t_issue_pcx(referral_reward_pot, 100);
+        XStaking::on_referral_reward(&1, 60);
+        XStaking::on_referral_reward(&1, 40);
+
+        assert_eq!(
+            XStaking::referral_earnings(b"1 ".to_vec()),
+            Some(ReferralEarnings {
+                account: 1,
+                rewards: ReferralRewardLedger {
+                    unclaimed: 100,
+                    claimed: 0
+                }
+            })
+        );
+        assert_eq!(XStaking::referral_earnings(b"unknown".to_vec()), None);
+
+        let before = Balances::free_balance(1);
+        assert_ok!(XStaking::claim_referral_rewards(Origin::signed(1)));
+        assert_eq!(Balances::free_balance(1), before + 100);
+        assert_eq!(Balances::free_balance(referral_reward_pot), 0);
+        assert_eq!(
+            XStaking::referral_rewards_of(1),
+            ReferralRewardLedger {
+                unclaimed: 0,
+                claimed: 100
+            }
+        );
+
+        assert_err!(
+            XStaking::claim_referral_rewards(Origin::signed(1)),
+            Error::<Test>::ZeroBalance
+        );
+    });
+}
+
+#[test]
+fn auto_payout_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        let t_3 = 3333;
+        let t_4 = 4444;
+
+        for who in [t_1, t_2, t_3, t_4] {
+            t_issue_pcx(who, 100);
+        }
+        // Allow compounding the whole dividend.
+        UpperBoundFactorOfAcceptableVotes::<Test>::put(u32::MAX);
+
+        assert_ok!(t_bond(t_1, 1, 10));
+        assert_ok!(t_bond(t_2, 1, 10));
+        assert_ok!(t_bond(t_3, 2, 10));
+        assert_ok!(t_bond(t_4, 2, 10));
+
+        for (who, preference) in [
+            (t_1, PayoutPreference::Compound),
+            (t_2, PayoutPreference::Transfer),
+            (t_3, PayoutPreference::Transfer),
+            (t_4, PayoutPreference::Compound),
+        ] {
+            assert_ok!(XStaking::set_payout_preference(
+                Origin::signed(who),
+                preference
+            ));
+        }
+        assert_ok!(XStaking::set_payout_preference(
+            Origin::signed(t_4),
+            PayoutPreference::Manual
+        ));
+        assert!(!PayoutPreferences::<Test>::contains_key(t_4));
+
+        t_start_session(1);
+        t_start_session(2);
+        let now = System::block_number();
+        let paid = |who: AccountId, validator: AccountId| {
+            XStaking::nominations(who, validator).last_vote_weight_update == now
+        };
+
+        // No payout without the leftover weight.
+        assert_eq!(XStaking::on_idle(now, 0), 0);
+        assert!(!paid(t_1, 1) && !paid(t_2, 1) && !paid(t_3, 2));
+
+        // At most `MaxAutoPayouts` per block.
+        assert!(XStaking::on_idle(now, Weight::max_value()) > 0);
+        let paid_count = [paid(t_1, 1), paid(t_2, 1), paid(t_3, 2)]
+            .iter()
+            .filter(|is_paid| **is_paid)
+            .count();
+        assert_eq!(paid_count, 2);
+
+        XStaking::on_idle(now, Weight::max_value());
+        XStaking::on_idle(now, Weight::max_value());
+        assert!(paid(t_1, 1) && paid(t_2, 1) && paid(t_3, 2));
+        assert!(!paid(t_4, 2));
+
+        // Compound bonds the dividend to the same validator.
+        let t_1_nomination = XStaking::nominations(t_1, 1).nomination;
+        assert!(t_1_nomination > 10);
+        assert_bonded_locks(t_1, t_1_nomination);
+
+        // Transfer leaves the dividend in the free balance.
+        assert_eq!(XStaking::nominations(t_2, 1).nomination, 10);
+        assert!(Balances::free_balance(t_2) > 100);
+        assert_eq!(Balances::free_balance(t_4), 100);
+    });
+}
+
+#[test]
+fn equivocation_slash_should_be_deferred() {
+    use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+
+    ExtBuilder::default().build_and_execute(|| {
+        t_start_session(1);
+
+        let offence_of = |validator: AccountId| OffenceDetails {
+            offender: (
+                validator,
+                DummyStakingRewardPotAccountDeterminer::reward_pot_account_for(&validator),
+            ),
+            reporters: vec![],
+        };
+        ReportingEquivocation::<Test>::put(true);
+        <XStaking as OnOffenceHandler<_, _, _>>::on_offence(
+            &[offence_of(1), offence_of(2)],
+            &[Perbill::from_percent(10), Perbill::from_percent(10)],
+            1,
+            DisableStrategy::Never,
+        );
+        ReportingEquivocation::<Test>::kill();
+
+        // Applied at the start of session 1 + SlashDeferDuration.
+        assert_eq!(
+            XStaking::deferred_slashes(0),
+            Some(DeferredSlash {
+                offender: 1,
+                offence_session: 1,
+                slash_fraction: Perbill::from_percent(10),
+                apply_at: 3,
+            })
+        );
+        assert_eq!(XStaking::session_offenders(), None);
+
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::signed(1), vec![1]),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XStaking::cancel_deferred_slash(Origin::root(), vec![1]));
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), vec![1]),
+            Error::<Test>::DeferredSlashNotFound
+        );
+
+        t_start_session(2);
+        assert!(XStaking::deferred_slashes(0).is_some());
+
+        t_start_session(3);
+        assert_eq!(XStaking::deferred_slashes(0), None);
+
+        let slashed = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                mock::Event::XStaking(crate::Event::Slashed(validator, _)) => Some(validator),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(slashed, vec![1]);
+
+        // The later equivocations of validator 1 are slashed more heavily.
+        assert_eq!(XStaking::equivocations_of(1), 1);
+        assert_eq!(XStaking::equivocations_of(2), 0);
+        assert_eq!(
+            XStaking::graduated_slash_fraction(&1, Perbill::from_percent(10)),
+            Perbill::from_percent(20)
+        );
+        assert_eq!(
+            XStaking::graduated_slash_fraction(&1, Perbill::from_percent(60)),
+            Perbill::one()
+        );
+    });
+}
+
+#[test]
+fn rotate_keys_and_rebond_should_work() {
+    use codec::Encode;
+    use sp_runtime::testing::UintAuthorityId;
+
+    ExtBuilder::default().build_and_execute(|| {
+        t_start_session(1);
+        assert_eq!(XStaking::current_era(), Some(0));
+
+        t_make_a_validator_candidate(5, 500);
+        t_make_a_validator_candidate(6, 600);
+        t_make_a_validator_candidate(7, 700);
+        t_make_a_validator_candidate(8, 800);
+
+        assert_eq!(
+            XStaking::next_election_of(6),
+            NextElectionInfo {
+                is_candidate: true,
+                rank: Some(2),
+                will_be_validating: true,
+                rejoin_era: None,
+            }
+        );
+
+        let keys = SessionKeys {
+            other: UintAuthorityId(60),
+        };
+        assert_err!(
+            XStaking::rotate_keys_and_rebond(Origin::signed(9), keys.encode(), vec![]),
+            Error::<Test>::NotValidator
+        );
+        assert_err!(
+            XStaking::rotate_keys_and_rebond(Origin::signed(6), vec![1], vec![]),
+            Error::<Test>::InvalidSessionKeys
+        );
+        assert_ok!(XStaking::rotate_keys_and_rebond(
+            Origin::signed(6),
+            keys.encode(),
+            vec![]
+        ));
+        assert_eq!(pallet_session::NextKeys::<Test>::get(6), Some(keys));
+        assert!(XStaking::is_chilled(&6));
+        assert_eq!(
+            XStaking::next_election_of(6),
+            NextElectionInfo {
+                is_candidate: false,
+                rank: None,
+                will_be_validating: false,
+                rejoin_era: Some(1),
+            }
+        );
+
+        // Validator 6 sits out the election of era 1 and rejoins right after it.
+        t_start_session(2);
+        assert_eq!(XStaking::current_era(), Some(1));
+        assert!(!XStaking::is_chilled(&6));
+        assert_eq!(XStaking::rejoin_era_of(6), None);
+        assert!(XStaking::next_election_of(6).will_be_validating);
+
+        t_start_session(3);
+        assert_eq!(Session::validators(), vec![8, 7, 5, 4, 3, 2]);
+
+        t_start_session(6);
+        assert_eq!(XStaking::current_era(), Some(2));
+        assert_eq!(Session::validators(), vec![8, 7, 6, 5, 4, 3]);
+    });
+}
+
+#[test]
+fn backup_validator_should_work() {
+    use pallet_authorship::EventHandler;
+    use sp_runtime::testing::UintAuthorityId;
+
+    ExtBuilder::default().build_and_execute(|| {
+        t_start_session(1);
+
+        assert_err!(
+            XStaking::set_backup(Origin::signed(9), Some(10)),
+            Error::<Test>::NotValidator
+        );
+        assert_err!(
+            XStaking::set_backup(Origin::signed(1), Some(1)),
+            Error::<Test>::InvalidBackup
+        );
+        assert_err!(
+            XStaking::set_backup(Origin::signed(1), Some(2)),
+            Error::<Test>::InvalidBackup
+        );
+        assert_ok!(XStaking::set_backup(Origin::signed(1), Some(10)));
+        assert_eq!(XStaking::backup_of(1), Some(10));
+        assert_eq!(XStaking::primary_of(10), Some(1));
+        assert_err!(
+            XStaking::set_backup(Origin::signed(2), Some(10)),
+            Error::<Test>::AlreadyBackup
+        );
+        t_issue_pcx(10, 10);
+        assert_err!(t_register(10, 10), Error::<Test>::AlreadyBackup);
+        assert_err!(
+            XStaking::resume_primary(Origin::signed(1)),
+            Error::<Test>::NotFailedOver
+        );
+        assert_ok!(Session::set_keys(
+            Origin::signed(10),
+            SessionKeys {
+                other: UintAuthorityId(10),
+            },
+            vec![]
+        ));
+
+        // Validator 1 misses the heartbeats in session 1 and its backup is elected in era 1.
+        UnresponsiveValidators::<Test>::put(vec![1]);
+        t_start_session(2);
+        assert_eq!(XStaking::failed_over_at(1), Some(1));
+        assert_eq!(XStaking::current_era(), Some(1));
+
+        t_start_session(3);
+        assert_eq!(Session::validators(), vec![4, 3, 2, 10]);
+        assert_err!(
+            XStaking::set_backup(Origin::signed(1), None),
+            Error::<Test>::BackupInService
+        );
+
+        // The blocks and the rewards of the backup are accounted to validator 1.
+        XStaking::note_author(10);
+        assert_eq!(XStaking::current_era_performance_of(1).authored_blocks, 1);
+        let reward_pot_balance = t_reward_pot_balance(1);
+        t_start_session(4);
+        assert!(t_reward_pot_balance(1) > reward_pot_balance);
+        assert_eq!(Balances::usable_balance(&10), 10);
+
+        assert_ok!(XStaking::resume_primary(Origin::signed(1)));
+        assert_eq!(XStaking::failed_over_at(1), None);
+
+        t_start_session(6);
+        assert_eq!(Session::validators(), vec![4, 3, 2, 1]);
+        assert_ok!(XStaking::set_backup(Origin::signed(1), None));
+        assert_eq!(XStaking::backup_of(1), None);
+        assert_eq!(XStaking::primary_of(10), None);
+    });
+}
+
+#[test]
+fn evm_staking_allowlist_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let contract = H160::repeat_byte(1);
+        assert!(XStaking::is_evm_staker_allowed(&contract));
+
+        assert_err!(
+            XStaking::set_evm_staking_allowlist(Origin::signed(1), true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XStaking::set_evm_staking_allowlist(Origin::root(), true));
+        assert!(!XStaking::is_evm_staker_allowed(&contract));
+
+        assert_ok!(XStaking::set_evm_staker(Origin::root(), contract, true));
+        assert!(XStaking::is_evm_staker_allowed(&contract));
+        assert!(!XStaking::is_evm_staker_allowed(&H160::repeat_byte(2)));
+
+        assert_ok!(XStaking::set_evm_staker(Origin::root(), contract, false));
+        assert!(!XStaking::is_evm_staker_allowed(&contract));
+
+        assert_ok!(XStaking::set_evm_staking_allowlist(Origin::root(), false));
+        assert!(XStaking::is_evm_staker_allowed(&contract));
+    });
+}
+
+#[test]
+fn parameter_changes_should_apply_at_next_era() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_start_session(1);
+        assert_eq!(XStaking::current_era(), Some(0));
+
+        assert_err!(
+            XStaking::schedule_parameter_change(
+                Origin::signed(1),
+                ParameterChange::SessionsPerEra(4)
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        for change in [
+            ParameterChange::ValidatorCount(0),
+            ParameterChange::ValidatorCount(DEFAULT_MAXIMUM_VALIDATOR_COUNT + 1),
+            ParameterChange::SessionsPerEra(0),
+            ParameterChange::SessionsPerEra(MAXIMUM_SESSIONS_PER_ERA + 1),
+            ParameterChange::GlobalDistribution(GlobalDistribution {
+                treasury: 60,
+                mining: 40,
+            }),
+            ParameterChange::MiningDistribution(MiningDistribution {
+                asset: 0,
+                staking: 100,
+            }),
+        ] {
+            assert_err!(
+                XStaking::schedule_parameter_change(Origin::root(), change),
+                Error::<Test>::ParameterOutOfBounds
+            );
+        }
+
+        let mining_distribution = MiningDistribution {
+            asset: 20,
+            staking: 80,
+        };
+        assert_ok!(XStaking::schedule_parameter_change(
+            Origin::root(),
+            ParameterChange::SessionsPerEra(4)
+        ));
+        assert_ok!(XStaking::schedule_parameter_change(
+            Origin::root(),
+            ParameterChange::ValidatorCount(5)
+        ));
+        assert_ok!(XStaking::schedule_parameter_change(
+            Origin::root(),
+            ParameterChange::MiningDistribution(mining_distribution)
+        ));
+        // Replaces the pending change of the validator count.
+        assert_ok!(XStaking::schedule_parameter_change(
+            Origin::root(),
+            ParameterChange::ValidatorCount(4)
+        ));
+        assert_eq!(
+            XStaking::pending_parameter_changes(),
+            vec![
+                ParameterChange::SessionsPerEra(4),
+                ParameterChange::MiningDistribution(mining_distribution),
+                ParameterChange::ValidatorCount(4),
+            ]
+        );
+        assert_eq!(XStaking::sessions_per_era(), 3);
+        assert_eq!(XStaking::validator_count(), 6);
+
+        // Era 1 starting at session 3 is planned in session 2.
+        t_start_session(2);
+        assert_eq!(XStaking::current_era(), Some(1));
+        assert_eq!(XStaking::sessions_per_era(), 4);
+        assert_eq!(XStaking::validator_count(), 4);
+        assert_eq!(XStaking::mining_distribution_ratio(), mining_distribution);
+        assert!(XStaking::pending_parameter_changes().is_empty());
+
+        // Era 1 lasts 4 sessions.
+        t_start_session(5);
+        assert_eq!(XStaking::current_era(), Some(1));
+        t_start_session(6);
+        assert_eq!(XStaking::current_era(), Some(2));
+    });
+}
@@ -9,18 +9,21 @@ use serde::{Deserialize, Serialize};
 
 use sp_runtime::{
     traits::{SaturatedConversion, Saturating},
-    DispatchError, DispatchResult, RuntimeDebug,
+    DispatchError, DispatchResult, Perbill, RuntimeDebug,
 };
 
 use chainx_primitives::{AssetId, ReferralId};
 use frame_support::log::debug;
 use xp_mining_common::{RewardPotAccountFor, WeightType};
-use xp_mining_staking::MiningPower;
+use xp_mining_staking::{MiningPower, SessionIndex};
 
 use crate::{AssetMining, BalanceOf, Config, EraIndex, Event, Pallet};
 
 pub type VoteWeight = WeightType;
 
+/// Index of a deferred slash.
+pub type SlashId = u32;
+
 /// Detailed types of reserved balances in Staking.
 #[derive(PartialEq, PartialOrd, Ord, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -143,6 +146,24 @@ impl Default for Forcing {
     }
 }
 
+/// How the staking dividends of a nominator are paid out.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PayoutPreference {
+    /// The dividends are only paid out by calling `claim` manually.
+    Manual,
+    /// The dividends are claimed automatically and bonded to the same validator.
+    Compound,
+    /// The dividends are claimed automatically into the free balance.
+    Transfer,
+}
+
+impl Default for PayoutPreference {
+    fn default() -> Self {
+        PayoutPreference::Manual
+    }
+}
+
 /// Top level shares of various reward destinations.
 #[derive(Copy, Clone, PartialEq, Eq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -224,6 +245,82 @@ impl MiningDistribution {
     }
 }
 
+/// A change of the staking parameters, which takes effect when the next era is planned.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ParameterChange {
+    /// The ideal number of validators.
+    ValidatorCount(u32),
+    /// The length of an era in sessions.
+    SessionsPerEra(SessionIndex),
+    /// The shares of the session reward between the treasury and mining.
+    GlobalDistribution(GlobalDistribution),
+    /// The shares of the mining reward between Asset Mining and Staking.
+    MiningDistribution(MiningDistribution),
+}
+
+/// Referral rewards of a validator from Asset Mining.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReferralRewardLedger<Balance> {
+    /// Rewards accumulated in the referral reward pot, not claimed yet.
+    pub unclaimed: Balance,
+    /// Total rewards claimed so far.
+    pub claimed: Balance,
+}
+
+/// Liveness and block production statistics of a validator within an era.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ValidatorPerformance {
+    /// Number of sessions in which the validator was in the active set.
+    pub active_sessions: u32,
+    /// Number of active sessions in which the validator was not reported unresponsive by im-online.
+    pub online_sessions: u32,
+    /// Number of blocks actually authored by the validator.
+    pub authored_blocks: u32,
+    /// Number of blocks the validator was expected to author given its share of the slots.
+    pub expected_blocks: u32,
+}
+
+impl ValidatorPerformance {
+    /// Returns the proportion of the active sessions in which the validator was online.
+    pub fn uptime(&self) -> Perbill {
+        if self.active_sessions == 0 {
+            Perbill::one()
+        } else {
+            Perbill::from_rational(self.online_sessions, self.active_sessions)
+        }
+    }
+
+    /// Returns the proportion of the expected blocks that were actually authored.
+    pub fn block_production(&self) -> Perbill {
+        if self.expected_blocks == 0 {
+            Perbill::one()
+        } else {
+            Perbill::from_rational(
+                self.authored_blocks.min(self.expected_blocks),
+                self.expected_blocks,
+            )
+        }
+    }
+}
+
+/// An equivocation slash waiting to be applied, which can be canceled by the council.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DeferredSlash<AccountId> {
+    /// The equivocating validator.
+    pub offender: AccountId,
+    /// The session in which the offence was committed.
+    pub offence_session: SessionIndex,
+    /// The graduated fraction of the reward pot to be slashed.
+    pub slash_fraction: Perbill,
+    /// The session at the start of which the slash is applied.
+    pub apply_at: SessionIndex,
+}
+
 /// Result of performing a slash operation.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum SlashOutcome<Balance> {
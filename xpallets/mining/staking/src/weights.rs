@@ -45,6 +45,17 @@ pub trait WeightInfo {
     fn set_validator_bonding_duration() -> Weight;
     fn set_minimum_penalty() -> Weight;
     fn set_sessions_per_era() -> Weight;
+    fn set_performance_alert_threshold() -> Weight;
+    fn claim_referral_rewards() -> Weight;
+    fn set_payout_preference() -> Weight;
+    fn auto_payout() -> Weight;
+    fn cancel_deferred_slash(n: u32) -> Weight;
+    fn rotate_keys_and_rebond() -> Weight;
+    fn set_backup() -> Weight;
+    fn resume_primary() -> Weight;
+    fn set_evm_staking_allowlist() -> Weight;
+    fn set_evm_staker() -> Weight;
+    fn schedule_parameter_change() -> Weight;
 }
 
 /// Weights for xpallet_mining_staking using the Substrate node and recommended hardware.
@@ -108,6 +119,54 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn set_sessions_per_era() -> Weight {
         (2_275_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_performance_alert_threshold() -> Weight {
+        (2_301_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn claim_referral_rewards() -> Weight {
+        (58_437_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn set_payout_preference() -> Weight {
+        (21_736_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn auto_payout() -> Weight {
+        (187_512_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn cancel_deferred_slash(n: u32) -> Weight {
+        (3_208_000 as Weight)
+            .saturating_add((9_145_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn rotate_keys_and_rebond() -> Weight {
+        (1_193_415_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(100 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn set_backup() -> Weight {
+        (42_350_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn resume_primary() -> Weight {
+        (18_620_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_evm_staking_allowlist() -> Weight {
+        (2_275_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_evm_staker() -> Weight {
+        (2_984_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn schedule_parameter_change() -> Weight {
+        (9_621_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -170,4 +229,52 @@ impl WeightInfo for () {
     fn set_sessions_per_era() -> Weight {
         (2_275_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_performance_alert_threshold() -> Weight {
+        (2_301_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn claim_referral_rewards() -> Weight {
+        (58_437_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn set_payout_preference() -> Weight {
+        (21_736_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn auto_payout() -> Weight {
+        (187_512_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn cancel_deferred_slash(n: u32) -> Weight {
+        (3_208_000 as Weight)
+            .saturating_add((9_145_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn rotate_keys_and_rebond() -> Weight {
+        (1_193_415_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(100 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn set_backup() -> Weight {
+        (42_350_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn resume_primary() -> Weight {
+        (18_620_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_evm_staking_allowlist() -> Weight {
+        (2_275_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_evm_staker() -> Weight {
+        (2_984_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn schedule_parameter_change() -> Weight {
+        (9_621_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
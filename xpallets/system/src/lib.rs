@@ -4,11 +4,19 @@
 
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
-use sp_runtime::traits::StaticLookup;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{Dispatchable, StaticLookup},
+    RuntimeDebug,
+};
 
 use frame_support::{
     dispatch::{CallMetadata, DispatchResult},
-    traits::Currency,
+    traits::{
+        schedule::{DispatchTime, MaybeHashed, Named as ScheduleNamed},
+        Currency,
+    },
 };
 
 use frame_system::ensure_root;
@@ -18,6 +26,51 @@ pub use pallet::*;
 
 const PALLET_MARK: &[u8; 1] = b"#";
 const ALWAYS_ALLOW: [&str; 1] = ["Sudo"];
+/// The prefix of the scheduler task names of the deferred enactments.
+const ENACTMENT_ID: &[u8] = b"xsystem/enactment";
+/// The scheduler priority of the deferred enactments, the same as the democracy.
+const ENACTMENT_PRIORITY: u8 = 63;
+
+/// The maximal length of the minimum client version of a runtime upgrade.
+const MAX_CLIENT_VERSION_LEN: usize = 32;
+
+pub type CallOrHashOf<T> = MaybeHashed<<T as Config>::Proposal, <T as frame_system::Config>::Hash>;
+
+/// The announcement of an upcoming runtime upgrade.
+///
+/// The nodes check it at startup, a binary older than `min_client_version` or with a native
+/// runtime older than `min_spec_version` is warned before `enactment_block` and refuses to
+/// start after it.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct RuntimeUpgrade<BlockNumber> {
+    /// The spec version of the upgraded runtime.
+    pub min_spec_version: u32,
+    /// The minimum version of the node binary, e.g. `5.2.0`.
+    pub min_client_version: Vec<u8>,
+    /// The block at which the `set_code` is enacted.
+    pub enactment_block: BlockNumber,
+}
+
+/// Parses the `major.minor.patch` prefix of a client version, e.g. `5.2.0-a1b2c3d-x86_64`.
+pub fn parse_client_version(version: &[u8]) -> Option<(u32, u32, u32)> {
+    let end = version
+        .iter()
+        .position(|b| !(b.is_ascii_digit() || *b == b'.'))
+        .unwrap_or(version.len());
+    let mut parts = version[..end].split(|b| *b == b'.').map(|part| {
+        if part.is_empty() {
+            return None;
+        }
+        part.iter().try_fold(0u32, |acc, b| {
+            acc.checked_mul(10)?.checked_add(u32::from(b - b'0'))
+        })
+    });
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(version)
+}
 
 /// The pallet's config trait.
 ///
@@ -38,6 +91,25 @@ pub mod pallet {
 
         /// The currency mechanism.
         type Currency: Currency<Self::AccountId>;
+
+        /// The call enacted after the enactment delay.
+        type Proposal: Parameter + Dispatchable<Origin = Self::Origin>;
+
+        /// Overarching type of all pallets origins.
+        type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+        /// The scheduler of the deferred enactments.
+        type Scheduler: ScheduleNamed<Self::BlockNumber, CallOrHashOf<Self>, Self::PalletsOrigin>;
+
+        /// The origin which can schedule a call to be enacted as root after the enactment
+        /// delay, e.g., the council.
+        type EnactmentOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The origin which can cancel a scheduled enactment during the enactment delay.
+        type CancelEnactmentOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The origin which can toggle the safe mode, e.g., the technical committee.
+        type SafeModeOrigin: EnsureOrigin<Self::Origin>;
     }
 
     #[pallet::pallet]
@@ -106,6 +178,124 @@ pub mod pallet {
             }
             Ok(())
         }
+
+        /// Schedule `call` to be dispatched as root after the enactment delay, the delay
+        /// gives a public warning window before the call takes effect.
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+        pub fn schedule_enactment(origin: OriginFor<T>, call: Box<T::Proposal>) -> DispatchResult {
+            T::EnactmentOrigin::ensure_origin(origin)?;
+
+            let index = Self::enactment_count();
+            let delay = Self::enactment_delay();
+            T::Scheduler::schedule_named(
+                (ENACTMENT_ID, index).encode(),
+                DispatchTime::After(delay),
+                None,
+                ENACTMENT_PRIORITY,
+                frame_system::RawOrigin::Root.into(),
+                MaybeHashed::Value(*call),
+            )
+            .map_err(|_| Error::<T>::ScheduleEnactmentFailed)?;
+
+            EnactmentCount::<T>::put(index + 1);
+            let when = frame_system::Pallet::<T>::block_number() + delay;
+            Self::deposit_event(Event::<T>::EnactmentScheduled(index, when));
+            Ok(())
+        }
+
+        /// Cancel the scheduled enactment `index` before it's dispatched.
+        #[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+        pub fn cancel_enactment(origin: OriginFor<T>, index: u32) -> DispatchResult {
+            T::CancelEnactmentOrigin::ensure_origin(origin)?;
+
+            T::Scheduler::cancel_named((ENACTMENT_ID, index).encode())
+                .map_err(|_| Error::<T>::EnactmentNotFound)?;
+            Self::deposit_event(Event::<T>::EnactmentCancelled(index));
+            Ok(())
+        }
+
+        /// Set the delay of the enactments scheduled from now on.
+        ///
+        /// This is a root-only operation.
+        #[pallet::weight(0)]
+        pub fn set_enactment_delay(origin: OriginFor<T>, delay: T::BlockNumber) -> DispatchResult {
+            ensure_root(origin)?;
+
+            EnactmentDelay::<T>::put(delay);
+            Ok(())
+        }
+
+        /// Announce the runtime upgrade enacted at `enactment_block`, the nodes older than
+        /// `min_client_version` or `min_spec_version` refuse to start after it.
+        ///
+        /// The announcement takes effect immediately, while the `set_code` itself is
+        /// expected to go through `schedule_enactment`.
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn announce_runtime_upgrade(
+            origin: OriginFor<T>,
+            min_spec_version: u32,
+            min_client_version: Vec<u8>,
+            enactment_block: T::BlockNumber,
+        ) -> DispatchResult {
+            T::EnactmentOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            ensure!(
+                min_spec_version > T::Version::get().spec_version,
+                Error::<T>::SpecVersionNotUpgraded
+            );
+            ensure!(
+                min_client_version.len() <= MAX_CLIENT_VERSION_LEN
+                    && parse_client_version(&min_client_version).is_some(),
+                Error::<T>::InvalidClientVersion
+            );
+            ensure!(
+                enactment_block > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::InvalidEnactmentBlock
+            );
+
+            RuntimeUpgradeAnnouncement::<T>::put(RuntimeUpgrade {
+                min_spec_version,
+                min_client_version,
+                enactment_block,
+            });
+            Self::deposit_event(Event::<T>::RuntimeUpgradeAnnounced(
+                min_spec_version,
+                enactment_block,
+            ));
+            Ok(())
+        }
+
+        /// Withdraw the announced runtime upgrade, e.g. when its enactment is cancelled.
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+        pub fn cancel_runtime_upgrade(origin: OriginFor<T>) -> DispatchResult {
+            T::CancelEnactmentOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            let upgrade = RuntimeUpgradeAnnouncement::<T>::take()
+                .ok_or(Error::<T>::RuntimeUpgradeNotFound)?;
+            Self::deposit_event(Event::<T>::RuntimeUpgradeCancelled(
+                upgrade.min_spec_version,
+            ));
+            Ok(())
+        }
+
+        /// Toggle the safe mode, in which the signed extrinsics can't batch any gateway call.
+        ///
+        /// The trustee transitions and the withdrawal finalizations can never be batched by
+        /// the signed extrinsics regardless of the safe mode.
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_safe_mode(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            T::SafeModeOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            SafeMode::<T>::put(enabled);
+            Self::deposit_event(Event::<T>::SafeModeToggled(enabled));
+            Ok(())
+        }
     }
 
     /// Event for the XSystem Pallet
@@ -116,6 +306,33 @@ pub mod pallet {
         Blacklisted(T::AccountId),
         /// An account was removed from the blacklist. [who]
         Unblacklisted(T::AccountId),
+        /// A call was scheduled to be enacted. [index, when]
+        EnactmentScheduled(u32, T::BlockNumber),
+        /// A scheduled enactment was cancelled. [index]
+        EnactmentCancelled(u32),
+        /// A runtime upgrade was announced. [min_spec_version, enactment_block]
+        RuntimeUpgradeAnnounced(u32, T::BlockNumber),
+        /// The announced runtime upgrade was withdrawn. [min_spec_version]
+        RuntimeUpgradeCancelled(u32),
+        /// The safe mode was toggled. [enabled]
+        SafeModeToggled(bool),
+    }
+
+    /// Error for the XSystem Pallet
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Failed to schedule the enactment.
+        ScheduleEnactmentFailed,
+        /// The enactment is not scheduled or dispatched already.
+        EnactmentNotFound,
+        /// The announced spec version is not newer than the current runtime.
+        SpecVersionNotUpgraded,
+        /// The client version is not in the `major.minor.patch` format or too long.
+        InvalidClientVersion,
+        /// The enactment block is not in the future.
+        InvalidEnactmentBlock,
+        /// No runtime upgrade is announced.
+        RuntimeUpgradeNotFound,
     }
 
     /// Network property (Mainnet / Testnet).
@@ -133,16 +350,48 @@ pub mod pallet {
     #[pallet::getter(fn blacklist)]
     pub type Blacklist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
 
+    /// The delay between scheduling a call via `schedule_enactment` and dispatching it.
+    #[pallet::storage]
+    #[pallet::getter(fn enactment_delay)]
+    pub type EnactmentDelay<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// The number of the enactments scheduled so far, used as the index of the next one.
+    #[pallet::storage]
+    #[pallet::getter(fn enactment_count)]
+    pub type EnactmentCount<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// The announced runtime upgrade, checked by the nodes at startup.
+    #[pallet::storage]
+    #[pallet::getter(fn runtime_upgrade_announcement)]
+    pub type RuntimeUpgradeAnnouncement<T: Config> =
+        StorageValue<_, RuntimeUpgrade<T::BlockNumber>, OptionQuery>;
+
+    /// Whether the signed extrinsics are forbidden to batch any gateway call.
+    #[pallet::storage]
+    #[pallet::getter(fn safe_mode)]
+    pub type SafeMode<T> = StorageValue<_, bool, ValueQuery>;
+
     #[pallet::genesis_config]
-    #[cfg_attr(feature = "std", derive(Default))]
-    pub struct GenesisConfig {
+    pub struct GenesisConfig<T: Config> {
         pub network_props: NetworkType,
+        pub enactment_delay: T::BlockNumber,
+    }
+
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self {
+                network_props: Default::default(),
+                enactment_delay: Default::default(),
+            }
+        }
     }
 
     #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
             NetworkProps::<T>::put(self.network_props);
+            EnactmentDelay::<T>::put(self.enactment_delay);
         }
     }
 }
@@ -7,12 +7,24 @@
 use codec::Codec;
 use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
 
-pub use xpallet_transaction_fee::{FeeDetails, InclusionFee};
+pub use xpallet_transaction_fee::{
+    CongestionLevel, FeeCongestion, FeeDetails, FeeEstimate, FeePolicy, InclusionFee,
+    TipSuggestion, WithdrawalFee,
+};
 
 sp_api::decl_runtime_apis! {
     pub trait XTransactionFeeApi<Balance> where
         Balance: Codec + MaybeDisplay + MaybeFromStr,
     {
         fn query_fee_details(uxt: Block::Extrinsic, len: u32) -> FeeDetails<Balance>;
+
+        /// Get the fee multiplier of the next block and the congestion level.
+        fn fee_congestion() -> FeeCongestion;
+
+        /// Get the fee breakdown of an extrinsic along with the suggested tips.
+        fn estimate_fee(uxt: Block::Extrinsic, len: u32) -> FeeEstimate<Balance>;
+
+        /// Get the split of the fees and tips in effect.
+        fn fee_policy() -> FeePolicy;
     }
 }
@@ -20,14 +20,36 @@ use sp_runtime::{
 use pallet_transaction_payment_rpc::Error;
 
 use xp_rpc::RpcBalance;
-use xpallet_transaction_fee_rpc_runtime_api::{FeeDetails, InclusionFee};
+use xpallet_transaction_fee_rpc_runtime_api::{
+    FeeCongestion, FeeDetails, FeeEstimate, FeePolicy, InclusionFee, TipSuggestion, WithdrawalFee,
+};
 
 pub use xpallet_transaction_fee_rpc_runtime_api::XTransactionFeeApi as XTransactionFeeRuntimeApi;
 
 #[rpc]
-pub trait XTransactionFeeApi<BlockHash, ResponseType> {
+pub trait XTransactionFeeApi<BlockHash, ResponseType, EstimateResponseType> {
     #[rpc(name = "xfee_queryDetails")]
     fn query_fee_details(&self, encoded_xt: Bytes, at: Option<BlockHash>) -> Result<ResponseType>;
+
+    /// Return the fee multiplier of the next block and how congested the chain is, so that the
+    /// wallets can warn users about the elevated fees.
+    #[rpc(name = "xfee_congestion")]
+    fn fee_congestion(&self, at: Option<BlockHash>) -> Result<FeeCongestion>;
+
+    /// Return the fee breakdown of an extrinsic, i.e. the inclusion fee, the extra fee of the
+    /// ChainX specific calls and the fee deducted from the withdrawn asset, along with the
+    /// suggested tips for the current congestion.
+    #[rpc(name = "chainx_estimateFee")]
+    fn estimate_fee(
+        &self,
+        encoded_xt: Bytes,
+        at: Option<BlockHash>,
+    ) -> Result<EstimateResponseType>;
+
+    /// Return the percentages of the fees and tips paid to the block author, its reward pot
+    /// and the treasury, and burnt.
+    #[rpc(name = "xfee_feePolicy")]
+    fn fee_policy(&self, at: Option<BlockHash>) -> Result<FeePolicy>;
 }
 
 /// A struct that implements the [`TransactionFeeApi`].
@@ -46,8 +68,12 @@ impl<C, P> XTransactionFee<C, P> {
     }
 }
 
-impl<C, Block, Balance> XTransactionFeeApi<<Block as BlockT>::Hash, FeeDetails<RpcBalance<Balance>>>
-    for XTransactionFee<C, Block>
+impl<C, Block, Balance>
+    XTransactionFeeApi<
+        <Block as BlockT>::Hash,
+        FeeDetails<RpcBalance<Balance>>,
+        FeeEstimate<RpcBalance<Balance>>,
+    > for XTransactionFee<C, Block>
 where
     Block: BlockT,
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
@@ -79,6 +105,55 @@ where
             })
             .map_err(into_rpc_err)
     }
+
+    fn fee_congestion(&self, at: Option<<Block as BlockT>::Hash>) -> Result<FeeCongestion> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.fee_congestion(&at).map_err(into_rpc_err)
+    }
+
+    fn fee_policy(&self, at: Option<<Block as BlockT>::Hash>) -> Result<FeePolicy> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.fee_policy(&at).map_err(into_rpc_err)
+    }
+
+    fn estimate_fee(
+        &self,
+        encoded_xt: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<FeeEstimate<RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let encoded_len = encoded_xt.len() as u32;
+
+        let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(into_rpc_err)?;
+
+        api.estimate_fee(&at, uxt, encoded_len)
+            .map(|estimate| FeeEstimate {
+                inclusion_fee: estimate.inclusion_fee.map(|fee| InclusionFee {
+                    base_fee: fee.base_fee.into(),
+                    len_fee: fee.len_fee.into(),
+                    adjusted_weight_fee: fee.adjusted_weight_fee.into(),
+                }),
+                extra_fee: estimate.extra_fee.into(),
+                withdrawal_fee: estimate.withdrawal_fee.map(|fee| WithdrawalFee {
+                    asset_id: fee.asset_id,
+                    fee: fee.fee.into(),
+                }),
+                final_fee: estimate.final_fee.into(),
+                congestion: estimate.congestion,
+                tips: TipSuggestion {
+                    slow: estimate.tips.slow.into(),
+                    normal: estimate.tips.normal.into(),
+                    fast: estimate.tips.fast.into(),
+                },
+            })
+            .map_err(into_rpc_err)
+    }
 }
 
 fn into_rpc_err(err: impl Debug) -> RpcError {
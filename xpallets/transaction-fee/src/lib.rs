@@ -4,9 +4,28 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod mock;
+mod sponsor;
+#[cfg(test)]
+mod tests;
 mod types;
 
-pub use self::types::FeeDetails;
+use sp_std::{marker::PhantomData, prelude::*};
+
+use frame_support::{traits::Get, weights::DispatchClass};
+use sp_runtime::{
+    traits::{IdentifyAccount, Member, Verify},
+    Perquintill,
+};
+
+use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+
+pub use self::sponsor::ChargeSponsoredTransactionPayment;
+pub use self::types::{
+    CongestionLevel, FeeCongestion, FeeDetails, FeeEstimate, FeeMultiplierParams, FeePolicy,
+    SponsorApproval, SponsorInfo, SponsoredCall, SponsoredCalls, TipSuggestion, WithdrawalFee,
+};
 pub use pallet_transaction_payment::InclusionFee;
 
 type BalanceOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction as pallet_transaction_payment::OnChargeTransaction<T>>::Balance;
@@ -17,6 +36,7 @@ pub use pallet::*;
 pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(crate) trait Store)]
@@ -26,14 +46,243 @@ pub mod pallet {
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_transaction_payment::Config {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// The default portion of the normal dispatch weight the fee multiplier targets.
+        #[pallet::constant]
+        type TargetBlockFullness: Get<Perquintill>;
+
+        /// The default adjustment variable of the fee multiplier.
+        #[pallet::constant]
+        type AdjustmentVariable: Get<Multiplier>;
+
+        /// The default lower bound of the fee multiplier.
+        #[pallet::constant]
+        type MinimumMultiplier: Get<Multiplier>;
+
+        /// The signature of a sponsor co-signing an extrinsic.
+        type Signature: Parameter
+            + Member
+            + Verify<Signer = Self::Signer>
+            + MaybeSerializeDeserialize;
+
+        /// The signer of [`Config::Signature`].
+        type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// The maximum number of the calls a sponsor can specify.
+        #[pallet::constant]
+        type MaxSponsoredCalls: Get<u32>;
+
+        /// The default split of the fees and tips.
+        #[pallet::constant]
+        type DefaultFeePolicy: Get<FeePolicy>;
+
+        /// A majority of the council can adjust the fee policy.
+        type CouncilOrigin: EnsureOrigin<Self::Origin>;
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Set the parameters of the fee multiplier, `None` to restore the runtime defaults.
+        #[pallet::weight(<T as frame_system::Config>::DbWeight::get().writes(1))]
+        pub fn set_fee_multiplier_params(
+            origin: OriginFor<T>,
+            params: Option<FeeMultiplierParams>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            match params {
+                Some(params) => {
+                    ensure!(params.is_valid(), Error::<T>::InvalidFeeMultiplierParams);
+                    FeeMultiplierParamsOf::<T>::put(params);
+                }
+                None => FeeMultiplierParamsOf::<T>::kill(),
+            }
+            Self::deposit_event(Event::<T>::FeeMultiplierParamsSet(
+                Self::fee_multiplier_params(),
+            ));
+            Ok(())
+        }
+
+        /// Set the split of the fees and tips, `None` to restore the runtime default.
+        #[pallet::weight(<T as frame_system::Config>::DbWeight::get().writes(1))]
+        pub fn set_fee_policy(origin: OriginFor<T>, policy: Option<FeePolicy>) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            match policy {
+                Some(policy) => {
+                    ensure!(policy.is_valid(), Error::<T>::InvalidFeePolicy);
+                    FeePolicyOf::<T>::put(policy);
+                }
+                None => FeePolicyOf::<T>::kill(),
+            }
+            Self::deposit_event(Event::<T>::FeePolicySet(Self::fee_policy()));
+            Ok(())
+        }
+
+        /// Pay the fees of the extrinsics co-signed by the sender, up to `budget` in total.
+        ///
+        /// Only the fees of `calls` are paid, the existing sponsorship is replaced.
+        #[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 1))]
+        pub fn set_sponsor(
+            origin: OriginFor<T>,
+            #[pallet::compact] budget: BalanceOf<T>,
+            calls: SponsoredCalls,
+        ) -> DispatchResult {
+            let sponsor = ensure_signed(origin)?;
+
+            if let SponsoredCalls::Only(ref calls) = calls {
+                ensure!(
+                    calls.len() <= T::MaxSponsoredCalls::get() as usize,
+                    Error::<T>::TooManySponsoredCalls
+                );
+            }
+
+            let spent = SponsorOf::<T>::get(&sponsor)
+                .map(|info| info.spent)
+                .unwrap_or_default();
+            SponsorOf::<T>::insert(
+                &sponsor,
+                SponsorInfo {
+                    budget,
+                    spent,
+                    calls,
+                },
+            );
+            Self::deposit_event(Event::<T>::SponsorSet(sponsor, budget));
+            Ok(())
+        }
+
+        /// Stop paying the fees for others.
+        #[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 1))]
+        pub fn remove_sponsor(origin: OriginFor<T>) -> DispatchResult {
+            let sponsor = ensure_signed(origin)?;
+
+            ensure!(
+                SponsorOf::<T>::contains_key(&sponsor),
+                Error::<T>::NotSponsor
+            );
+            SponsorOf::<T>::remove(&sponsor);
+            Self::deposit_event(Event::<T>::SponsorRemoved(sponsor));
+            Ok(())
+        }
     }
 
     #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// Transaction fee was paid to the block author and its reward pot in 1:9.
+        /// Transaction fee was paid to the block author and its reward pot by the fee policy.
         /// [author, author_fee, reward_pot, reward_pot_fee]
         FeePaid(T::AccountId, BalanceOf<T>, T::AccountId, BalanceOf<T>),
         /// Transaction BTC fee
         BTCFeePaid(T::AccountId, u128),
+        /// The parameters of the fee multiplier were updated. [params]
+        FeeMultiplierParamsSet(FeeMultiplierParams),
+        /// Transaction fee was paid to the treasury and burnt by the fee policy.
+        /// [treasury_fee, burnt_fee]
+        FeeDistributed(BalanceOf<T>, BalanceOf<T>),
+        /// The split of the fees and tips was updated. [policy]
+        FeePolicySet(FeePolicy),
+        /// An account started paying the fees for others. [sponsor, budget]
+        SponsorSet(T::AccountId, BalanceOf<T>),
+        /// An account stopped paying the fees for others. [sponsor]
+        SponsorRemoved(T::AccountId),
+        /// The fee of an extrinsic was paid by its sponsor. [sponsor, who, fee]
+        FeeSponsored(T::AccountId, T::AccountId, BalanceOf<T>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The target fullness, adjustment variable and minimum multiplier must be positive.
+        InvalidFeeMultiplierParams,
+        /// The portions of the fee policy must add up to 100%.
+        InvalidFeePolicy,
+        /// The sponsored calls exceed `MaxSponsoredCalls`.
+        TooManySponsoredCalls,
+        /// The account is not a sponsor.
+        NotSponsor,
+    }
+
+    /// The parameters of the fee multiplier overriding the runtime defaults.
+    #[pallet::storage]
+    pub(crate) type FeeMultiplierParamsOf<T: Config> =
+        StorageValue<_, FeeMultiplierParams, OptionQuery>;
+
+    /// The split of the fees and tips overriding the runtime default.
+    #[pallet::storage]
+    pub(crate) type FeePolicyOf<T: Config> = StorageValue<_, FeePolicy, OptionQuery>;
+
+    /// The accounts paying the fees of the extrinsics they co-signed.
+    #[pallet::storage]
+    #[pallet::getter(fn sponsor_of)]
+    pub(crate) type SponsorOf<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, SponsorInfo<BalanceOf<T>>>;
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the parameters of the fee multiplier in effect.
+    pub fn fee_multiplier_params() -> FeeMultiplierParams {
+        FeeMultiplierParamsOf::<T>::get().unwrap_or_else(|| FeeMultiplierParams {
+            target_block_fullness: T::TargetBlockFullness::get(),
+            adjustment_variable: T::AdjustmentVariable::get(),
+            minimum_multiplier: T::MinimumMultiplier::get(),
+        })
+    }
+
+    /// Returns the split of the fees and tips in effect.
+    pub fn fee_policy() -> FeePolicy {
+        FeePolicyOf::<T>::get().unwrap_or_else(T::DefaultFeePolicy::get)
+    }
+
+    /// Returns the fee multiplier of the next block and how congested the chain is.
+    pub fn fee_congestion() -> FeeCongestion {
+        let weights = T::BlockWeights::get();
+        let max_normal_weight = weights
+            .get(DispatchClass::Normal)
+            .max_total
+            .unwrap_or(weights.max_block);
+        let normal_weight = *frame_system::Pallet::<T>::block_weight().get(DispatchClass::Normal);
+        let multiplier = pallet_transaction_payment::Pallet::<T>::next_fee_multiplier();
+
+        FeeCongestion {
+            multiplier,
+            block_fullness: Perquintill::from_rational(normal_weight, max_normal_weight.max(1)),
+            target_block_fullness: Self::fee_multiplier_params().target_block_fullness,
+            level: CongestionLevel::from_multiplier(multiplier),
+        }
+    }
+}
+
+/// The target block fullness read from the on-chain parameters.
+pub struct TargetBlockFullnessOf<T>(PhantomData<T>);
+impl<T: Config> Get<Perquintill> for TargetBlockFullnessOf<T> {
+    fn get() -> Perquintill {
+        Pallet::<T>::fee_multiplier_params().target_block_fullness
     }
 }
+
+/// The adjustment variable read from the on-chain parameters.
+pub struct AdjustmentVariableOf<T>(PhantomData<T>);
+impl<T: Config> Get<Multiplier> for AdjustmentVariableOf<T> {
+    fn get() -> Multiplier {
+        Pallet::<T>::fee_multiplier_params().adjustment_variable
+    }
+}
+
+/// The minimum multiplier read from the on-chain parameters.
+pub struct MinimumMultiplierOf<T>(PhantomData<T>);
+impl<T: Config> Get<Multiplier> for MinimumMultiplierOf<T> {
+    fn get() -> Multiplier {
+        Pallet::<T>::fee_multiplier_params().minimum_multiplier
+    }
+}
+
+/// Slow adjusting fee multiplier whose parameters can be tuned on-chain via
+/// `set_fee_multiplier_params`.
+pub type DynamicFeeAdjustment<T> = TargetedFeeAdjustment<
+    T,
+    TargetBlockFullnessOf<T>,
+    AdjustmentVariableOf<T>,
+    MinimumMultiplierOf<T>,
+>;
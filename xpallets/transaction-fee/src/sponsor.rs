@@ -0,0 +1,259 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Transaction fee delegation.
+//!
+//! An extrinsic carrying a [`SponsorApproval`] in [`ChargeSponsoredTransactionPayment`] has
+//! its fee paid by the sponsor instead of the signer, given that the sponsor has registered
+//! via `set_sponsor`, the call is sponsored and the budget of the sponsor is enough.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::prelude::*;
+
+use frame_support::{
+    dispatch::GetCallMetadata,
+    weights::{DispatchInfo, PostDispatchInfo},
+};
+use sp_runtime::{
+    traits::{
+        BlakeTwo256, DispatchInfoOf, Dispatchable, Hash, One, PostDispatchInfoOf, Saturating,
+        SignedExtension, Verify, Zero,
+    },
+    transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError},
+    DispatchResult, FixedPointOperand,
+};
+
+use pallet_transaction_payment::ChargeTransactionPayment;
+
+use crate::{BalanceOf, Config, Event, Pallet, SponsorApproval, SponsorOf};
+
+/// The context of the payload signed by a sponsor.
+pub const SPONSOR_CONTEXT: &[u8] = b"chainx/sponsor";
+
+/// The sponsor of an extrinsic is not registered.
+pub const UNKNOWN_SPONSOR: u8 = 253;
+/// The call of an extrinsic is not sponsored by its sponsor.
+pub const CALL_NOT_SPONSORED: u8 = 252;
+/// The remaining budget of the sponsor can not afford the fee.
+pub const SPONSOR_BUDGET_EXHAUSTED: u8 = 251;
+
+type ApprovalOf<T> = SponsorApproval<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Index,
+    <T as Config>::Signature,
+>;
+
+/// (sponsor, who, tip, the fee taken from the budget of the sponsor in advance)
+type Sponsorship<T> = (
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::AccountId,
+    BalanceOf<T>,
+    BalanceOf<T>,
+);
+
+/// Same as `pallet_transaction_payment::ChargeTransactionPayment` if no sponsor is given,
+/// otherwise the fee and the tip are paid by the sponsor.
+///
+/// NOTE: It must be placed after `frame_system::CheckNonce`, the approval is bound to the
+/// nonce of the extrinsic.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeSponsoredTransactionPayment<T: Config> {
+    #[codec(compact)]
+    tip: BalanceOf<T>,
+    sponsor: Option<ApprovalOf<T>>,
+}
+
+impl<T: Config> ChargeSponsoredTransactionPayment<T> {
+    /// Create a sponsored extension, the fee and `tip` are paid by the sponsor of `approval`.
+    pub fn sponsored(tip: BalanceOf<T>, approval: ApprovalOf<T>) -> Self {
+        Self {
+            tip,
+            sponsor: Some(approval),
+        }
+    }
+
+    /// Returns the payload the sponsor signs for the extrinsic of `who` at `nonce` on the chain
+    /// of `genesis_hash`.
+    pub fn sponsor_payload(
+        genesis_hash: &T::Hash,
+        who: &T::AccountId,
+        nonce: T::Index,
+        tip: BalanceOf<T>,
+        call: &<T as frame_system::Config>::Call,
+    ) -> Vec<u8> {
+        (SPONSOR_CONTEXT, genesis_hash, who, nonce, tip, call).using_encoded(|payload| {
+            if payload.len() > 256 {
+                BlakeTwo256::hash(payload).as_ref().to_vec()
+            } else {
+                payload.to_vec()
+            }
+        })
+    }
+
+    fn inner(&self) -> ChargeTransactionPayment<T> {
+        ChargeTransactionPayment::from(self.tip)
+    }
+}
+
+impl<T: Config> From<BalanceOf<T>> for ChargeSponsoredTransactionPayment<T> {
+    fn from(tip: BalanceOf<T>) -> Self {
+        Self { tip, sponsor: None }
+    }
+}
+
+impl<T: Config> sp_std::fmt::Debug for ChargeSponsoredTransactionPayment<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(
+            f,
+            "ChargeSponsoredTransactionPayment<{:?}, {:?}>",
+            self.tip,
+            self.sponsor.as_ref().map(|approval| &approval.sponsor)
+        )
+    }
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config> ChargeSponsoredTransactionPayment<T>
+where
+    BalanceOf<T>: FixedPointOperand,
+    <T as frame_system::Config>::Call:
+        Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetCallMetadata,
+{
+    /// Checks the sponsorship of `approval` for the extrinsic of `who`, returns the fee
+    /// the sponsor will pay.
+    ///
+    /// The nonce of the extrinsic is only known when it's dispatched, i.e., the account nonce
+    /// increased by `CheckNonce` minus one, a future nonce is allowed in the validation.
+    fn check_sponsorship(
+        &self,
+        approval: &ApprovalOf<T>,
+        who: &T::AccountId,
+        call: &<T as frame_system::Config>::Call,
+        info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+        len: usize,
+        dispatching: bool,
+    ) -> Result<BalanceOf<T>, TransactionValidityError> {
+        let nonce = frame_system::Pallet::<T>::account_nonce(who);
+        if dispatching {
+            if approval.nonce.saturating_add(One::one()) != nonce {
+                return Err(InvalidTransaction::BadProof.into());
+            }
+        } else if approval.nonce < nonce {
+            return Err(InvalidTransaction::Stale.into());
+        }
+
+        let sponsor = Pallet::<T>::sponsor_of(&approval.sponsor)
+            .ok_or(InvalidTransaction::Custom(UNKNOWN_SPONSOR))?;
+        if !sponsor.calls.contains(&call.get_call_metadata()) {
+            return Err(InvalidTransaction::Custom(CALL_NOT_SPONSORED).into());
+        }
+
+        // Bound to the genesis hash, so the approval can't be replayed on another chain.
+        let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+        let payload = Self::sponsor_payload(&genesis_hash, who, approval.nonce, self.tip, call);
+        if !approval.signature.verify(&payload[..], &approval.sponsor) {
+            return Err(InvalidTransaction::BadProof.into());
+        }
+
+        let fee = pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, info, self.tip);
+        if fee > sponsor.budget {
+            return Err(InvalidTransaction::Custom(SPONSOR_BUDGET_EXHAUSTED).into());
+        }
+        Ok(fee)
+    }
+}
+
+impl<T: Config> SignedExtension for ChargeSponsoredTransactionPayment<T>
+where
+    BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+    <T as frame_system::Config>::Call:
+        Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetCallMetadata,
+{
+    const IDENTIFIER: &'static str = "ChargeSponsoredTransactionPayment";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = (
+        <ChargeTransactionPayment<T> as SignedExtension>::Pre,
+        Option<Sponsorship<T>>,
+    );
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        match &self.sponsor {
+            None => self.inner().validate(who, call, info, len),
+            Some(approval) => {
+                self.check_sponsorship(approval, who, call, info, len, false)?;
+                self.inner().validate(&approval.sponsor, call, info, len)
+            }
+        }
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        let approval = match &self.sponsor {
+            None => return Ok((self.inner().pre_dispatch(who, call, info, len)?, None)),
+            Some(approval) => approval,
+        };
+
+        let fee = self.check_sponsorship(approval, who, call, info, len, true)?;
+        let pre = self
+            .inner()
+            .pre_dispatch(&approval.sponsor, call, info, len)?;
+        SponsorOf::<T>::mutate(&approval.sponsor, |sponsor| {
+            if let Some(sponsor) = sponsor {
+                sponsor.budget = sponsor.budget.saturating_sub(fee);
+            }
+        });
+
+        let sponsorship = (approval.sponsor.clone(), who.clone(), self.tip, fee);
+        Ok((pre, Some(sponsorship)))
+    }
+
+    fn post_dispatch(
+        maybe_pre: Option<Self::Pre>,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        let (pre, sponsorship) = match maybe_pre {
+            Some((pre, sponsorship)) => (Some(pre), sponsorship),
+            None => (None, None),
+        };
+        ChargeTransactionPayment::<T>::post_dispatch(pre, info, post_info, len, result)?;
+
+        if let Some((sponsor, who, tip, reserved)) = sponsorship {
+            let fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+                len as u32, info, post_info, tip,
+            );
+            SponsorOf::<T>::mutate(&sponsor, |info| {
+                if let Some(info) = info {
+                    info.budget = info.budget.saturating_add(reserved).saturating_sub(fee);
+                    info.spent = info.spent.saturating_add(fee);
+                }
+            });
+            Pallet::<T>::deposit_event(Event::<T>::FeeSponsored(sponsor, who, fee));
+        }
+        Ok(())
+    }
+}
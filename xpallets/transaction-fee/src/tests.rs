@@ -0,0 +1,180 @@
+// Copyright 2019-2023 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    assert_ok,
+    weights::{DispatchInfo, PostDispatchInfo},
+};
+use sp_core::H256;
+use sp_runtime::{
+    testing::TestSignature,
+    traits::SignedExtension,
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+use crate::{
+    mock::*,
+    sponsor::{CALL_NOT_SPONSORED, SPONSOR_BUDGET_EXHAUSTED, UNKNOWN_SPONSOR},
+    ChargeSponsoredTransactionPayment, SponsorApproval, SponsorInfo, SponsoredCall, SponsoredCalls,
+};
+
+const LEN: usize = 10;
+
+fn remark() -> Call {
+    Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn info() -> DispatchInfo {
+    DispatchInfo {
+        weight: 5,
+        ..Default::default()
+    }
+}
+
+/// Returns the approval of `sponsor` for the remark of `BOB` at `nonce` on `genesis_hash`.
+fn approval(
+    sponsor: AccountId,
+    nonce: u64,
+    genesis_hash: H256,
+) -> SponsorApproval<AccountId, u64, TestSignature> {
+    let payload = ChargeSponsoredTransactionPayment::<Test>::sponsor_payload(
+        &genesis_hash,
+        &BOB,
+        nonce,
+        0,
+        &remark(),
+    );
+    SponsorApproval {
+        sponsor,
+        nonce,
+        signature: TestSignature(sponsor, payload),
+    }
+}
+
+fn genesis_hash() -> H256 {
+    System::block_hash(0)
+}
+
+fn validate(
+    approval: SponsorApproval<AccountId, u64, TestSignature>,
+) -> Result<(), TransactionValidityError> {
+    ChargeSponsoredTransactionPayment::<Test>::sponsored(0, approval)
+        .validate(&BOB, &remark(), &info(), LEN)
+        .map(|_| ())
+}
+
+#[test]
+fn sponsored_fee_should_be_paid_by_sponsor() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(TransactionFee::set_sponsor(
+            Origin::signed(ALICE),
+            100,
+            SponsoredCalls::All
+        ));
+
+        assert_eq!(validate(approval(ALICE, 0, genesis_hash())), Ok(()));
+
+        // `CheckNonce` increases the nonce before the fee is charged.
+        System::inc_account_nonce(&BOB);
+        let ext = ChargeSponsoredTransactionPayment::<Test>::sponsored(
+            0,
+            approval(ALICE, 0, genesis_hash()),
+        );
+        let pre = ext.pre_dispatch(&BOB, &remark(), &info(), LEN).unwrap();
+        // The fee is the length fee plus the weight fee.
+        let fee = LEN as Balance + 5;
+        assert_eq!(Balances::free_balance(ALICE), 1000 - fee);
+        assert_eq!(Balances::free_balance(BOB), 100);
+        assert_eq!(TransactionFee::sponsor_of(ALICE).unwrap().budget, 100 - fee);
+
+        assert_ok!(ChargeSponsoredTransactionPayment::<Test>::post_dispatch(
+            Some(pre),
+            &info(),
+            &PostDispatchInfo::default(),
+            LEN,
+            &Ok(())
+        ));
+        assert_eq!(
+            TransactionFee::sponsor_of(ALICE),
+            Some(SponsorInfo {
+                budget: 100 - fee,
+                spent: fee,
+                calls: SponsoredCalls::All,
+            })
+        );
+        System::assert_last_event(Event::TransactionFee(crate::Event::FeeSponsored(
+            ALICE, BOB, fee,
+        )));
+
+        // The approval is bound to the nonce.
+        assert_eq!(
+            validate(approval(ALICE, 0, genesis_hash())),
+            Err(InvalidTransaction::Stale.into())
+        );
+    });
+}
+
+#[test]
+fn sponsored_fee_should_not_exceed_budget() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(TransactionFee::set_sponsor(
+            Origin::signed(ALICE),
+            LEN as Balance + 4,
+            SponsoredCalls::All
+        ));
+        assert_eq!(
+            validate(approval(ALICE, 0, genesis_hash())),
+            Err(InvalidTransaction::Custom(SPONSOR_BUDGET_EXHAUSTED).into())
+        );
+
+        assert_ok!(TransactionFee::set_sponsor(
+            Origin::signed(ALICE),
+            LEN as Balance + 5,
+            SponsoredCalls::All
+        ));
+        assert_eq!(validate(approval(ALICE, 0, genesis_hash())), Ok(()));
+    });
+}
+
+#[test]
+fn invalid_sponsorship_should_be_rejected() {
+    new_test_ext().execute_with(|| {
+        // Not a sponsor.
+        assert_eq!(
+            validate(approval(ALICE, 0, genesis_hash())),
+            Err(InvalidTransaction::Custom(UNKNOWN_SPONSOR).into())
+        );
+
+        assert_ok!(TransactionFee::set_sponsor(
+            Origin::signed(ALICE),
+            100,
+            SponsoredCalls::Only(vec![SponsoredCall {
+                pallet: b"Balances".to_vec(),
+                call: None,
+            }])
+        ));
+        assert_eq!(
+            validate(approval(ALICE, 0, genesis_hash())),
+            Err(InvalidTransaction::Custom(CALL_NOT_SPONSORED).into())
+        );
+
+        assert_ok!(TransactionFee::set_sponsor(
+            Origin::signed(ALICE),
+            100,
+            SponsoredCalls::All
+        ));
+        // Signed by another account.
+        let mut forged = approval(ALICE, 0, genesis_hash());
+        forged.signature.0 = BOB;
+        assert_eq!(validate(forged), Err(InvalidTransaction::BadProof.into()));
+        // Signed for another payload.
+        let mut forged = approval(ALICE, 0, genesis_hash());
+        forged.signature.1 = b"remark".to_vec();
+        assert_eq!(validate(forged), Err(InvalidTransaction::BadProof.into()));
+        // Signed on another chain.
+        assert_eq!(
+            validate(approval(ALICE, 0, H256::repeat_byte(1))),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_eq!(validate(approval(ALICE, 0, genesis_hash())), Ok(()));
+    });
+}
@@ -5,9 +5,17 @@ use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use sp_runtime::{traits::AtLeast32BitUnsigned, RuntimeDebug};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, One, Saturating, Zero},
+    FixedPointNumber, Percent, Perquintill, RuntimeDebug,
+};
+use sp_std::prelude::*;
 
-use pallet_transaction_payment::InclusionFee;
+use frame_support::dispatch::CallMetadata;
+
+use pallet_transaction_payment::{InclusionFee, Multiplier};
+
+use chainx_primitives::AssetId;
 
 /// The `final_fee` is composed of:
 ///   - (Optional) `inclusion_fee`: Only the `Pays::Yes` transaction can have the inclusion fee.
@@ -60,3 +68,235 @@ impl<Balance: AtLeast32BitUnsigned + Copy> From<pallet_transaction_payment::FeeD
         }
     }
 }
+
+/// The parameters of the slow adjusting fee multiplier.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeMultiplierParams {
+    /// The portion of the normal dispatch weight the block fullness is adjusted towards.
+    pub target_block_fullness: Perquintill,
+    /// How fast the multiplier reacts to the deviation from the target fullness.
+    pub adjustment_variable: Multiplier,
+    /// The lower bound of the multiplier.
+    pub minimum_multiplier: Multiplier,
+}
+
+impl FeeMultiplierParams {
+    pub fn is_valid(&self) -> bool {
+        !self.target_block_fullness.is_zero()
+            && !self.adjustment_variable.is_zero()
+            && !self.minimum_multiplier.is_zero()
+    }
+}
+
+/// How the fees and tips of the PCX transactions are split.
+///
+/// The portions must add up to 100%, the rounding dust goes to the reward pot.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeePolicy {
+    /// The portion paid to the block author.
+    pub author: Percent,
+    /// The portion paid to the reward pot of the block author.
+    pub reward_pot: Percent,
+    /// The portion paid to the treasury.
+    pub treasury: Percent,
+    /// The portion burnt.
+    pub burn: Percent,
+}
+
+impl FeePolicy {
+    pub fn is_valid(&self) -> bool {
+        [self.author, self.reward_pot, self.treasury, self.burn]
+            .iter()
+            .map(|p| u32::from(p.deconstruct()))
+            .sum::<u32>()
+            == 100
+    }
+}
+
+/// How congested the chain is, judged by the fee multiplier.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum CongestionLevel {
+    /// The multiplier is below 1, the fees are cheaper than usual.
+    Idle,
+    /// The multiplier is in [1, 2).
+    Normal,
+    /// The multiplier is in [2, 10), wallets should warn about the elevated fees.
+    Elevated,
+    /// The multiplier is 10 or above.
+    High,
+}
+
+impl CongestionLevel {
+    pub fn from_multiplier(multiplier: Multiplier) -> Self {
+        if multiplier < Multiplier::one() {
+            Self::Idle
+        } else if multiplier < Multiplier::saturating_from_integer(2) {
+            Self::Normal
+        } else if multiplier < Multiplier::saturating_from_integer(10) {
+            Self::Elevated
+        } else {
+            Self::High
+        }
+    }
+}
+
+/// The fee multiplier of the next block along with the block fullness.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeCongestion {
+    /// The multiplier applied to the weight fee of the next block.
+    pub multiplier: Multiplier,
+    /// The portion of the normal dispatch weight used by the current block.
+    pub block_fullness: Perquintill,
+    /// The block fullness the multiplier is adjusted towards.
+    pub target_block_fullness: Perquintill,
+    pub level: CongestionLevel,
+}
+
+/// The withdrawal fee deducted from the withdrawn asset instead of the PCX balance.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalFee<Balance> {
+    pub asset_id: AssetId,
+    /// The fee of all the withdrawal outputs.
+    pub fee: Balance,
+}
+
+/// The suggested tips for an extrinsic to be included slowly, normally or fast.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TipSuggestion<Balance> {
+    pub slow: Balance,
+    pub normal: Balance,
+    pub fast: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> TipSuggestion<Balance> {
+    /// Suggests the tips as a portion of the inclusion fee, the more congested the chain is,
+    /// the larger the portion.
+    pub fn new(level: CongestionLevel, inclusion_fee: Balance) -> Self {
+        let (slow, normal, fast) = match level {
+            CongestionLevel::Idle => (0, 0, 0),
+            CongestionLevel::Normal => (0, 0, 10),
+            CongestionLevel::Elevated => (0, 10, 50),
+            CongestionLevel::High => (0, 50, 100),
+        };
+        Self {
+            slow: Percent::from_percent(slow) * inclusion_fee,
+            normal: Percent::from_percent(normal) * inclusion_fee,
+            fast: Percent::from_percent(fast) * inclusion_fee,
+        }
+    }
+}
+
+/// The fee breakdown of an extrinsic.
+///
+/// ```ignore
+/// final_fee = inclusion_fee + extra_fee;
+/// ```
+///
+/// The `withdrawal_fee` is not a part of `final_fee` as it's paid in the withdrawn asset.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeEstimate<Balance> {
+    pub inclusion_fee: Option<InclusionFee<Balance>>,
+    /// Additional PCX fee for some ChainX specific calls.
+    pub extra_fee: Balance,
+    /// The fee deducted from the withdrawn asset for the withdrawal calls.
+    pub withdrawal_fee: Option<WithdrawalFee<Balance>>,
+    /// The PCX fee excluding the tip.
+    pub final_fee: Balance,
+    pub congestion: CongestionLevel,
+    pub tips: TipSuggestion<Balance>,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> FeeEstimate<Balance> {
+    pub fn new(
+        details: FeeDetails<Balance>,
+        withdrawal_fee: Option<WithdrawalFee<Balance>>,
+        congestion: CongestionLevel,
+    ) -> Self {
+        let inclusion_fee = details.inclusion_fee.as_ref().map_or(Zero::zero(), |fee| {
+            fee.base_fee
+                .saturating_add(fee.len_fee)
+                .saturating_add(fee.adjusted_weight_fee)
+        });
+        Self {
+            final_fee: inclusion_fee.saturating_add(details.extra_fee),
+            inclusion_fee: details.inclusion_fee,
+            extra_fee: details.extra_fee,
+            withdrawal_fee,
+            congestion,
+            tips: TipSuggestion::new(congestion, inclusion_fee),
+        }
+    }
+}
+
+/// A call sponsored by a sponsor, given by the pallet name and the call name.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SponsoredCall {
+    pub pallet: Vec<u8>,
+    /// All the calls of `pallet` are sponsored if `None`.
+    pub call: Option<Vec<u8>>,
+}
+
+/// The calls a sponsor pays the fee for.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum SponsoredCalls {
+    All,
+    Only(Vec<SponsoredCall>),
+}
+
+impl SponsoredCalls {
+    /// Returns true if the call given by its metadata is sponsored.
+    pub fn contains(&self, metadata: &CallMetadata) -> bool {
+        match self {
+            Self::All => true,
+            Self::Only(calls) => calls.iter().any(|call| {
+                call.pallet == metadata.pallet_name.as_bytes()
+                    && call
+                        .call
+                        .as_ref()
+                        .map_or(true, |name| name == metadata.function_name.as_bytes())
+            }),
+        }
+    }
+}
+
+/// The sponsorship of a sponsor.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SponsorInfo<Balance> {
+    /// The remaining amount the sponsor is willing to pay for the fees.
+    pub budget: Balance,
+    /// The total amount of the fees paid by the sponsor.
+    pub spent: Balance,
+    pub calls: SponsoredCalls,
+}
+
+/// The co-signature of a sponsor on an extrinsic.
+///
+/// The sponsor signs `(SPONSOR_CONTEXT, genesis_hash, who, nonce, tip, call)`, where `who` and
+/// `nonce` are the signer of the extrinsic and its nonce, so the approval can't be replayed,
+/// neither on this chain nor on another one.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct SponsorApproval<AccountId, Index, Signature> {
+    pub sponsor: AccountId,
+    pub nonce: Index,
+    pub signature: Signature,
+}